@@ -0,0 +1,52 @@
+//! `clnrm fmt --check` Benchmark
+//!
+//! Compares the line-scan fast path of `content_needs_formatting` with
+//! formatting each file and comparing, on already formatted configs of
+//! increasing size (the common case for `--check` in CI).
+
+use clnrm_core::formatting::{content_needs_formatting, format_toml_content};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+/// An already formatted config with `steps` `[[steps]]` entries
+fn formatted_config(steps: usize) -> String {
+    let mut content = String::from("[meta]\nname = \"bench\"\nversion = \"1.0\"\n");
+    for i in 0..steps {
+        content.push_str(&format!(
+            "\n# Step {i}\n[[steps]]\nname = \"step_{i}\"\ncommand = [\"sh\", \"-c\", \"echo {i}\"]\nexpected_output_regex = \"^{i}$\" # exact\n"
+        ));
+    }
+    content
+}
+
+fn bench_fmt_check(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fmt_check");
+
+    for steps in [10, 100, 1_000] {
+        let content = formatted_config(steps);
+
+        group.bench_with_input(
+            BenchmarkId::new("format_and_compare", steps),
+            &content,
+            |b, content| {
+                b.iter(|| {
+                    let formatted = format_toml_content(black_box(content)).expect("valid TOML");
+                    formatted != *content
+                })
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("content_needs_formatting", steps),
+            &content,
+            |b, content| {
+                b.iter(|| content_needs_formatting(black_box(content)).expect("valid TOML"))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_fmt_check);
+criterion_main!(benches);
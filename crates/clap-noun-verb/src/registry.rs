@@ -150,11 +150,11 @@ impl CommandRegistry {
 
     /// Build the complete clap command structure
     pub fn build_command(&self) -> Command {
-        let mut cmd = Command::new(self.config.name.as_str())
-            .about(self.config.about.as_str());
+        let mut cmd = Command::new(&*Box::leak(self.config.name.clone().into_boxed_str()))
+            .about(&*Box::leak(self.config.about.clone().into_boxed_str()));
 
         if let Some(version) = &self.config.version {
-            cmd = cmd.version(&**version);
+            cmd = cmd.version(&*Box::leak(version.clone().into_boxed_str()));
         }
 
         // Add global arguments
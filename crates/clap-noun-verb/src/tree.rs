@@ -170,8 +170,8 @@ impl TreeNode {
 
     /// Build the clap command for this node
     pub fn build_command(&self) -> Command {
-        let mut cmd = Command::new(self.name.as_str())
-            .about(self.about.as_str());
+        let mut cmd = Command::new(&*Box::leak(self.name.clone().into_boxed_str()))
+            .about(&*Box::leak(self.about.clone().into_boxed_str()));
 
         for child in &self.children {
             cmd = cmd.subcommand(child.build_command());
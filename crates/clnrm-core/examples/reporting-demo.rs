@@ -11,8 +11,8 @@
 //! ```
 
 use clnrm_core::{
-    generate_reports, DigestReporter, JsonReporter, JunitReporter, ReportConfig, Result,
-    ValidationReport,
+    generate_reports, DigestReporter, JsonReporter, JunitReporter, ReportConfig, ReportMeta,
+    Result, ValidationReport,
 };
 use std::path::Path;
 
@@ -122,7 +122,8 @@ fn demo_unified_generation() -> Result<()> {
 }"#;
 
     println!("Generating all reports...");
-    generate_reports(&config, &report, spans_json)?;
+    let meta = ReportMeta::new("reporting-demo");
+    generate_reports(&config, &report, spans_json, &meta)?;
     println!("  ✓ All reports generated successfully");
     println!();
 
@@ -0,0 +1,125 @@
+//! Database query assertions
+//!
+//! Run SQL against a database service started in the environment and check
+//! what it returns: how many rows a query selects, or the single value it
+//! selects. Queries go through the service's plugin (`psql` inside the
+//! container for PostgreSQL), so tests need no client driver.
+//!
+//! ```no_run
+//! use clnrm_core::assertions::database::{assert_query_eq, assert_row_count, DatabaseService};
+//! use clnrm_core::cleanroom::{CleanroomEnvironment, ServiceHandle};
+//!
+//! # async fn example(env: &CleanroomEnvironment, db: &ServiceHandle) -> clnrm_core::error::Result<()> {
+//! let service = DatabaseService::new(env, db);
+//! assert_row_count(&service, "SELECT id FROM users", 3).await?;
+//! assert_query_eq(&service, "SELECT name FROM users WHERE id = 1", "alice").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The same checks can be declared on a scenario:
+//!
+//! ```toml
+//! [[scenario.assertions]]
+//! service = "db"
+//! query = "SELECT id FROM users"
+//! row_count = 3
+//! ```
+
+use crate::cleanroom::{CleanroomEnvironment, ServiceHandle};
+use crate::error::{CleanroomError, Result};
+
+/// A running database service to run queries against
+#[derive(Debug, Clone, Copy)]
+pub struct DatabaseService<'a> {
+    env: &'a CleanroomEnvironment,
+    handle: &'a ServiceHandle,
+}
+
+impl<'a> DatabaseService<'a> {
+    /// Wrap the service `handle` started in `env`
+    pub fn new(env: &'a CleanroomEnvironment, handle: &'a ServiceHandle) -> Self {
+        Self { env, handle }
+    }
+
+    /// Service name
+    pub fn name(&self) -> &str {
+        &self.handle.service_name
+    }
+
+    /// Run `sql` and capture the rows it selects
+    pub async fn query(&self, sql: &str) -> Result<QueryResult> {
+        let rows = self.env.query_service(self.handle, sql).await?;
+        Ok(QueryResult { rows })
+    }
+}
+
+/// Rows selected by a query, each column as text
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryResult {
+    /// Selected rows
+    pub rows: Vec<Vec<String>>,
+}
+
+impl QueryResult {
+    /// Number of rows
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The value of a query that selected one row with one column
+    pub fn single_value(&self) -> Option<&str> {
+        match self.rows.as_slice() {
+            [row] => match row.as_slice() {
+                [value] => Some(value),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Assert that `sql` selects exactly `expected` rows
+pub async fn assert_row_count(
+    service: &DatabaseService<'_>,
+    sql: &str,
+    expected: usize,
+) -> Result<()> {
+    let result = service.query(sql).await?;
+    if result.row_count() == expected {
+        return Ok(());
+    }
+    Err(CleanroomError::validation_error(format!(
+        "Expected {} row(s) from '{}' on service '{}', got {}",
+        expected,
+        sql,
+        service.name(),
+        result.row_count()
+    )))
+}
+
+/// Assert that `sql` selects a single value equal to `expected`
+pub async fn assert_query_eq(
+    service: &DatabaseService<'_>,
+    sql: &str,
+    expected: &str,
+) -> Result<()> {
+    let result = service.query(sql).await?;
+    match result.single_value() {
+        Some(value) if value == expected => Ok(()),
+        Some(value) => Err(CleanroomError::validation_error(format!(
+            "Expected '{}' from '{}' on service '{}', got '{}'",
+            expected,
+            sql,
+            service.name(),
+            value
+        ))),
+        None => Err(CleanroomError::validation_error(format!(
+            "Expected a single value from '{}' on service '{}', got {} row(s): {:?}",
+            sql,
+            service.name(),
+            result.row_count(),
+            result.rows
+        ))),
+    }
+}
@@ -0,0 +1,327 @@
+//! HTTP response assertions
+//!
+//! Checks for responses from web services, such as one started with
+//! `with_web_server`: status codes, headers, and values inside a JSON body
+//! addressed by a JSONPath like `$.user.roles[0]`.
+//!
+//! ```no_run
+//! use clnrm_core::assertions::http::{self, assert_header, assert_json_path, assert_status};
+//! use serde_json::json;
+//!
+//! # async fn example() -> clnrm_core::error::Result<()> {
+//! let response = http::get("http://localhost:8080/api/users/42").await?;
+//! assert_status(&response, 200)?;
+//! assert_header(&response, "content-type", "application/json")?;
+//! assert_json_path(&response, "$.user.id", 42)?;
+//! assert_json_path(&response, "$.user", json!({ "id": 42, "name": "jane" }))?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{CleanroomError, Result};
+use serde_json::Value;
+
+/// An HTTP response captured for assertions
+#[derive(Debug, Clone)]
+pub struct HttpResponse {
+    /// Status code
+    pub status: u16,
+    /// Headers in received order; names are matched case-insensitively
+    pub headers: Vec<(String, String)>,
+    /// Response body
+    pub body: String,
+}
+
+impl HttpResponse {
+    /// Create a response from a status code and body, e.g. output captured
+    /// from `curl` in a container
+    pub fn new(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    /// Add a header
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Capture a `reqwest` response, reading its whole body
+    pub async fn from_reqwest(response: reqwest::Response) -> Result<Self> {
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (
+                    name.to_string(),
+                    String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                )
+            })
+            .collect();
+        let body = response.text().await.map_err(|e| {
+            CleanroomError::network_error(format!("Failed to read response body: {}", e))
+        })?;
+
+        Ok(Self {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// First value of the header called `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Parse the body as JSON
+    pub fn json(&self) -> Result<Value> {
+        serde_json::from_str(&self.body).map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Response body is not valid JSON: {} (body: {})",
+                e,
+                truncate(&self.body)
+            ))
+        })
+    }
+}
+
+/// Send a GET request to `url` and capture the response
+pub async fn get(url: &str) -> Result<HttpResponse> {
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| CleanroomError::network_error(format!("GET {} failed: {}", url, e)))?;
+    HttpResponse::from_reqwest(response).await
+}
+
+/// Assert that the response has status `expected`
+pub fn assert_status(response: &HttpResponse, expected: u16) -> Result<()> {
+    if response.status == expected {
+        return Ok(());
+    }
+    Err(CleanroomError::validation_error(format!(
+        "Expected HTTP status {}, got {} (body: {})",
+        expected,
+        response.status,
+        truncate(&response.body)
+    )))
+}
+
+/// Assert that header `name` (case-insensitive) has the value `expected`
+///
+/// Values are compared as media types: the part before any `;` ignores case
+/// and the whitespace around `;`, and an `expected` without parameters
+/// matches a value with them, so `application/json` matches
+/// `application/json; charset=utf-8`. Give the parameters to check them too.
+pub fn assert_header(response: &HttpResponse, name: &str, expected: &str) -> Result<()> {
+    match response.header(name) {
+        Some(value) if header_matches(value, expected) => Ok(()),
+        Some(value) => Err(CleanroomError::validation_error(format!(
+            "Expected header '{}' to be '{}', got '{}'",
+            name, expected, value
+        ))),
+        None => Err(CleanroomError::validation_error(format!(
+            "Expected header '{}' to be '{}', but the response has no such header",
+            name, expected
+        ))),
+    }
+}
+
+/// Whether header `value` matches `expected` as described on [`assert_header`]
+fn header_matches(value: &str, expected: &str) -> bool {
+    let mut value_parts = value.split(';').map(str::trim);
+    let mut expected_parts = expected.split(';').map(str::trim);
+
+    let same_type = match (value_parts.next(), expected_parts.next()) {
+        (Some(value_type), Some(expected_type)) => value_type.eq_ignore_ascii_case(expected_type),
+        _ => false,
+    };
+    let expected_params: Vec<&str> = expected_parts.collect();
+    same_type && (expected_params.is_empty() || value_parts.eq(expected_params))
+}
+
+/// Assert that the JSON body holds `expected` at `path`
+///
+/// `path` is a JSONPath of field and index steps: `$`, `.field`, `[0]` and
+/// `['field']`. On mismatch the error lists every differing location, e.g.
+/// `$.user.id: expected 42, found 41`.
+pub fn assert_json_path(
+    response: &HttpResponse,
+    path: &str,
+    expected: impl Into<Value>,
+) -> Result<()> {
+    let expected = expected.into();
+    let body = response.json()?;
+    let steps = parse_json_path(path)?;
+
+    let mut actual = &body;
+    for (i, step) in steps.iter().enumerate() {
+        let next = match step {
+            PathStep::Field(field) => actual.get(field.as_str()),
+            PathStep::Index(index) => actual.get(*index),
+        };
+        actual = next.ok_or_else(|| {
+            CleanroomError::validation_error(format!(
+                "JSON path '{}' not found: {} has no {} (found {})",
+                path,
+                format_path(&steps[..i]),
+                describe_step(step),
+                truncate(&actual.to_string())
+            ))
+        })?;
+    }
+
+    let mut differences = Vec::new();
+    diff_json(
+        &expected,
+        actual,
+        &mut format_path(&steps),
+        &mut differences,
+    );
+    if differences.is_empty() {
+        return Ok(());
+    }
+    Err(CleanroomError::validation_error(format!(
+        "JSON path '{}' does not match:\n  {}",
+        path,
+        differences.join("\n  ")
+    )))
+}
+
+/// One step of a JSONPath
+#[derive(Debug, Clone, PartialEq)]
+enum PathStep {
+    Field(String),
+    Index(usize),
+}
+
+fn parse_json_path(path: &str) -> Result<Vec<PathStep>> {
+    let invalid = |reason: &str| {
+        CleanroomError::validation_error(format!("Invalid JSON path '{}': {}", path, reason))
+    };
+
+    let mut rest = path
+        .trim()
+        .strip_prefix('$')
+        .ok_or_else(|| invalid("must start with '$'"))?;
+    let mut steps = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+            if end == 0 {
+                return Err(invalid("empty field name after '.'"));
+            }
+            steps.push(PathStep::Field(after_dot[..end].to_string()));
+            rest = &after_dot[end..];
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            let end = after_bracket
+                .find(']')
+                .ok_or_else(|| invalid("unclosed '['"))?;
+            let inner = after_bracket[..end].trim();
+            let quoted = ['\'', '"'].iter().find_map(|quote| {
+                inner
+                    .strip_prefix(*quote)
+                    .and_then(|s| s.strip_suffix(*quote))
+            });
+            let step = match quoted {
+                Some(field) => PathStep::Field(field.to_string()),
+                None => PathStep::Index(inner.parse().map_err(|_| {
+                    invalid(&format!(
+                        "'[{}]' is neither an index nor a quoted field",
+                        inner
+                    ))
+                })?),
+            };
+            steps.push(step);
+            rest = &after_bracket[end + 1..];
+        } else {
+            return Err(invalid(&format!("unexpected '{}'", rest)));
+        }
+    }
+
+    Ok(steps)
+}
+
+fn format_path(steps: &[PathStep]) -> String {
+    steps.iter().fold("$".to_string(), |mut path, step| {
+        match step {
+            PathStep::Field(field) => {
+                path.push('.');
+                path.push_str(field);
+            }
+            PathStep::Index(index) => path.push_str(&format!("[{}]", index)),
+        }
+        path
+    })
+}
+
+fn describe_step(step: &PathStep) -> String {
+    match step {
+        PathStep::Field(field) => format!("field '{}'", field),
+        PathStep::Index(index) => format!("index {}", index),
+    }
+}
+
+/// Collect a line per location where `actual` differs from `expected`
+fn diff_json(expected: &Value, actual: &Value, path: &mut String, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected_fields), Value::Object(actual_fields)) => {
+            for (key, expected_value) in expected_fields {
+                let len = path.len();
+                path.push('.');
+                path.push_str(key);
+                match actual_fields.get(key) {
+                    Some(actual_value) => {
+                        diff_json(expected_value, actual_value, path, differences)
+                    }
+                    None => {
+                        differences.push(format!("{}: missing, expected {}", path, expected_value))
+                    }
+                }
+                path.truncate(len);
+            }
+            for (key, actual_value) in actual_fields {
+                if !expected_fields.contains_key(key) {
+                    differences.push(format!("{}.{}: unexpected {}", path, key, actual_value));
+                }
+            }
+        }
+        (Value::Array(expected_items), Value::Array(actual_items)) => {
+            for (index, expected_item) in expected_items.iter().enumerate() {
+                let len = path.len();
+                path.push_str(&format!("[{}]", index));
+                match actual_items.get(index) {
+                    Some(actual_item) => diff_json(expected_item, actual_item, path, differences),
+                    None => {
+                        differences.push(format!("{}: missing, expected {}", path, expected_item))
+                    }
+                }
+                path.truncate(len);
+            }
+            for (index, actual_item) in actual_items.iter().enumerate().skip(expected_items.len()) {
+                differences.push(format!("{}[{}]: unexpected {}", path, index, actual_item));
+            }
+        }
+        _ if expected != actual => {
+            differences.push(format!("{}: expected {}, found {}", path, expected, actual));
+        }
+        _ => {}
+    }
+}
+
+/// Shorten a body for error messages
+fn truncate(body: &str) -> String {
+    const MAX_CHARS: usize = 200;
+    match body.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}...", &body[..end]),
+        None => body.to_string(),
+    }
+}
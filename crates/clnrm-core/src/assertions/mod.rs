@@ -2,6 +2,12 @@
 //!
 //! This module provides Jane-friendly assertions that understand the domain
 //! and provide clear, actionable feedback when tests fail.
+//!
+//! - `http` - Status, header and JSON body checks for web service responses
+//! - `database` - Row count and value checks for SQL queries against database services
+
+pub mod database;
+pub mod http;
 
 use crate::error::{CleanroomError, Result};
 use serde::{Deserialize, Serialize};
@@ -4,7 +4,7 @@
 //! Following core team best practices for fast, reliable test execution.
 
 use crate::backend::{Backend, Cmd, RunResult};
-use crate::error::Result;
+use crate::error::{CleanroomError, Result};
 use std::collections::HashMap;
 
 /// High-performance mock backend for fast testing
@@ -102,6 +102,7 @@ impl MockBackend {
         if let Some(response) = self.responses.get(&cmd_key) {
             Ok(RunResult {
                 exit_code: response.exit_code,
+                oom_killed: false,
                 stdout: response.stdout.clone(),
                 stderr: response.stderr.clone(),
                 duration_ms: 1, // 1ms for realistic timing
@@ -115,6 +116,7 @@ impl MockBackend {
             // Default success for unknown commands - simulates container behavior
             Ok(RunResult {
                 exit_code: 0,
+                oom_killed: false,
                 stdout: format!("mock output for: {}", cmd.bin),
                 stderr: "".to_string(),
                 duration_ms: 1,
@@ -131,7 +133,13 @@ impl MockBackend {
 impl Backend for MockBackend {
     /// Run a command in the mock backend
     fn run_cmd(&self, cmd: Cmd) -> Result<RunResult> {
-        self.execute_mock_cmd(&cmd)
+        let mut result = self.execute_mock_cmd(&cmd)?;
+        if let Some(path) = &cmd.stdout_file {
+            std::fs::write(path, std::mem::take(&mut result.stdout)).map_err(|e| {
+                CleanroomError::io_error(format!("Failed to write {}: {}", path.display(), e))
+            })?;
+        }
+        Ok(result)
     }
 
     /// Get the name of the backend
@@ -5,14 +5,21 @@
 use crate::error::Result;
 use crate::policy::Policy;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 // Module structure for backends
 pub mod mock;
+pub mod network;
+pub mod process;
+pub mod runtime;
 pub mod testcontainer;
 pub mod volume;
 
 pub use mock::MockBackend;
+pub use network::HermeticNetwork;
+pub use process::{HostBackend, ProcessBackend};
+pub use runtime::{active_backend, select_backend, ContainerBackend, DockerBackend, PodmanBackend};
 pub use testcontainer::TestcontainerBackend;
 pub use volume::{VolumeMount, VolumeValidator};
 
@@ -26,6 +33,17 @@ pub fn mock_backend() -> MockBackend {
 // pub fn create_mock_backend() -> MockBackend {
 //     MockBackend::new()
 // }
+/// Exit code of the [`Cmd::argv`] wrapper when the working directory is missing
+pub const WORKDIR_MISSING_EXIT_CODE: i32 = 125;
+
+/// Shell script that enters `$1` and execs the remaining arguments, failing
+/// with [`WORKDIR_MISSING_EXIT_CODE`] if `$1` isn't a directory
+const WORKDIR_SCRIPT: &str = concat!(
+    r#"cd -- "$1" 2>/dev/null || "#,
+    r#"{ echo "clnrm: working directory '$1' does not exist" >&2; exit 125; }; "#,
+    r#"shift; exec "$@""#,
+);
+
 /// Command to execute with all configuration
 #[derive(Debug, Clone)]
 pub struct Cmd {
@@ -39,6 +57,17 @@ pub struct Cmd {
     pub env: HashMap<String, String>,
     /// Policy constraints
     pub policy: Policy,
+    /// Kill the command if it runs longer than this
+    pub timeout: Option<Duration>,
+    /// Docker network to run the command's container on instead of the
+    /// default bridge
+    pub network: Option<String>,
+    /// Tracer provider the command's container spans are emitted through,
+    /// instead of the global one
+    pub tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+    /// File the command's stdout is written to as it runs, instead of being
+    /// captured in [`RunResult::stdout`]
+    pub stdout_file: Option<PathBuf>,
 }
 
 /// Result of a command execution
@@ -46,6 +75,9 @@ pub struct Cmd {
 pub struct RunResult {
     /// Exit code of the command
     pub exit_code: i32,
+    /// Whether the container runtime reports the command's container was
+    /// killed for exceeding its memory limit
+    pub oom_killed: bool,
     /// Standard output
     pub stdout: String,
     /// Standard error
@@ -69,6 +101,7 @@ impl RunResult {
     pub fn new(exit_code: i32, stdout: String, stderr: String, duration_ms: u64) -> Self {
         Self {
             exit_code,
+            oom_killed: false,
             stdout,
             stderr,
             duration_ms,
@@ -100,6 +133,10 @@ impl Cmd {
             workdir: None,
             env: HashMap::new(),
             policy: Policy::default(),
+            timeout: None,
+            network: None,
+            tracer_provider: None,
+            stdout_file: None,
         }
     }
 
@@ -134,6 +171,61 @@ impl Cmd {
         self.policy = policy;
         self
     }
+
+    /// Set execution timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Run the command's container on a Docker network
+    pub fn network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    /// Emit the command's container spans through `provider`
+    pub fn tracer_provider(
+        mut self,
+        provider: opentelemetry_sdk::trace::SdkTracerProvider,
+    ) -> Self {
+        self.tracer_provider = Some(provider);
+        self
+    }
+
+    /// Write the command's stdout to `path` instead of capturing it, so
+    /// output too large to hold in memory can be read back from disk
+    pub fn stdout_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.stdout_file = Some(path.into());
+        self
+    }
+
+    /// Command line to exec: the binary and its arguments, run from
+    /// `workdir` if one is set
+    ///
+    /// Docker creates a missing working directory instead of failing, so the
+    /// directory is entered by a shell wrapper that exits with
+    /// [`WORKDIR_MISSING_EXIT_CODE`] if it doesn't exist.
+    pub fn argv(&self) -> Vec<String> {
+        let command = std::iter::once(self.bin.clone()).chain(self.args.iter().cloned());
+        match &self.workdir {
+            Some(workdir) => ["sh", "-c", WORKDIR_SCRIPT, "sh"]
+                .into_iter()
+                .map(str::to_string)
+                .chain(std::iter::once(workdir.to_string_lossy().to_string()))
+                .chain(command)
+                .collect(),
+            None => command.collect(),
+        }
+    }
+
+    /// The working directory a run of [`Cmd::argv`] failed to enter, if
+    /// that is why it exited
+    pub fn missing_workdir(&self, exit_code: i32, stderr: &str) -> Option<&Path> {
+        self.workdir.as_deref().filter(|_| {
+            exit_code == WORKDIR_MISSING_EXIT_CODE && stderr.contains("clnrm: working directory '")
+        })
+    }
 }
 
 /// Trait for backend execution environments
@@ -0,0 +1,149 @@
+//! Egress-blocking Docker networks for hermetic tests
+//!
+//! With `[expect.hermeticity] enforce_network = true`, a test's command
+//! containers run on an internal Docker network that has no route to the
+//! outside. Declared services run on that network only, under their service
+//! name, so they stay reachable while everything else is not.
+//!
+//! Blocked connections surface as resolver or connect errors in the
+//! command's output; [`egress_violation`] recognises the common ones and
+//! names the host the command tried to reach.
+
+use crate::error::{CleanroomError, Result};
+use crate::services::generic::docker_client;
+use regex::Regex;
+use std::sync::OnceLock;
+use testcontainers::bollard::models::{NetworkCreateRequest, NetworkDisconnectRequest};
+use testcontainers::bollard::query_parameters::InspectNetworkOptions;
+use uuid::Uuid;
+
+/// Prefix of hermetic network names
+const NETWORK_NAME_PREFIX: &str = "clnrm-hermetic-";
+
+/// Host reported when a failure doesn't name one
+const UNKNOWN_HOST: &str = "an external address";
+
+/// Internal Docker network without outside access
+#[derive(Debug)]
+pub struct HermeticNetwork {
+    name: String,
+}
+
+impl HermeticNetwork {
+    /// Create a uniquely named internal network
+    ///
+    /// # Errors
+    /// Returns error if Docker is unreachable or refuses to create the network
+    pub async fn create() -> Result<Self> {
+        let name = format!("{}{}", NETWORK_NAME_PREFIX, Uuid::new_v4().simple());
+        let request = NetworkCreateRequest {
+            name: name.clone(),
+            internal: Some(true),
+            ..Default::default()
+        };
+
+        docker_client()?
+            .create_network(request)
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error(format!(
+                    "Failed to create hermetic network '{}'",
+                    name
+                ))
+                .with_source(e.to_string())
+            })?;
+
+        Ok(Self { name })
+    }
+
+    /// Docker network name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Remove the network, first detaching containers still on it, such as
+    /// services kept by `--keep-services`
+    ///
+    /// # Errors
+    /// Returns error if Docker refuses to detach a container or remove the network
+    pub async fn remove(self) -> Result<()> {
+        let docker = docker_client()?;
+        let network = docker
+            .inspect_network(&self.name, None::<InspectNetworkOptions>)
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error(format!(
+                    "Failed to inspect hermetic network '{}'",
+                    self.name
+                ))
+                .with_source(e.to_string())
+            })?;
+
+        for container in network.containers.unwrap_or_default().into_keys() {
+            let request = NetworkDisconnectRequest {
+                container: Some(container.clone()),
+                force: Some(true),
+            };
+            docker
+                .disconnect_network(&self.name, request)
+                .await
+                .map_err(|e| {
+                    CleanroomError::container_error(format!(
+                        "Failed to detach container {} from hermetic network '{}'",
+                        container, self.name
+                    ))
+                    .with_source(e.to_string())
+                })?;
+        }
+
+        docker.remove_network(&self.name).await.map_err(|e| {
+            CleanroomError::container_error(format!(
+                "Failed to remove hermetic network '{}'",
+                self.name
+            ))
+            .with_source(e.to_string())
+        })
+    }
+}
+
+/// Host a command tried to reach from a hermetic network, if its output
+/// shows a blocked lookup or connection
+///
+/// Failures naming one of `allowed_hosts` (the declared services) are not
+/// egress and are ignored.
+pub fn egress_violation(output: &str, allowed_hosts: &[String]) -> Option<String> {
+    for pattern in egress_patterns() {
+        for captures in pattern.captures_iter(output) {
+            let host = captures
+                .get(1)
+                .map_or(UNKNOWN_HOST, |host| host.as_str())
+                .trim_end_matches(['.', ':']);
+            if !allowed_hosts.iter().any(|allowed| allowed == host) {
+                return Some(host.to_string());
+            }
+        }
+    }
+    None
+}
+
+/// Error messages left by blocked lookups and connections
+///
+/// Covers curl, busybox wget/ping/nslookup, glibc and Python; the first
+/// group, when present, captures the host.
+fn egress_patterns() -> &'static [Regex] {
+    static PATTERNS: OnceLock<Vec<Regex>> = OnceLock::new();
+    PATTERNS.get_or_init(|| {
+        [
+            r"Could not resolve host: ([^\s;,]+)",
+            r"Failed to connect to ([^\s]+) port",
+            r"bad address '([^']+)'",
+            r"Failed to resolve '([^']+)'",
+            r"can't resolve '([^']+)'",
+            r"([A-Za-z0-9][A-Za-z0-9.-]*): (?:Name or service not known|Name does not resolve|Temporary failure in name resolution)",
+            r"Network (?:is )?unreachable",
+        ]
+        .iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .collect()
+    })
+}
@@ -0,0 +1,223 @@
+//! Host process backend for CI sandboxes without a container runtime
+//!
+//! With `[containers] backend = "process"` (or `CLNRM_BACKEND=process`) step
+//! commands run directly on the host. Each command gets a fresh scratch
+//! directory as its working directory and `HOME`, and an environment cleared
+//! down to `PATH` plus the step's own variables.
+//!
+//! This is NOT hermetic: commands use the host's binaries instead of the
+//! test's image and share its filesystem, network and resources. Services
+//! and network isolation need a real container runtime.
+
+use crate::backend::runtime::ContainerBackend;
+use crate::backend::{Backend, Cmd, RunResult};
+use crate::error::{CleanroomError, Result};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// How often a running command is polled for exit
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The `process` container backend: no containers, steps run on the host
+#[derive(Debug, Default)]
+pub struct ProcessBackend;
+
+impl ContainerBackend for ProcessBackend {
+    fn name(&self) -> &'static str {
+        "process"
+    }
+
+    fn binary(&self) -> &'static str {
+        "sh"
+    }
+
+    fn api_host(&self) -> Result<Option<String>> {
+        Err(CleanroomError::container_error(
+            "The process backend runs steps on the host and cannot start containers",
+        )
+        .with_context("Services and network isolation need backend \"docker\" or \"podman\""))
+    }
+
+    fn step_backend(&self, default_image: &str) -> Result<Arc<dyn Backend>> {
+        warn!(
+            "⚠️  Container backend \"process\": steps run directly on this host, NOT in '{}'. \
+             There is no filesystem, network or resource isolation; results are not hermetic.",
+            default_image
+        );
+        Ok(Arc::new(HostBackend))
+    }
+}
+
+/// Runs each command as a host process in its own scratch directory
+#[derive(Debug, Default)]
+pub struct HostBackend;
+
+impl HostBackend {
+    fn run_in(&self, cmd: &Cmd, sandbox: &Path) -> Result<RunResult> {
+        let start_time = Instant::now();
+
+        // A container workdir such as `/app` maps into the sandbox, where
+        // there is no image to provide it
+        let mut host_cmd = cmd.clone();
+        if let Some(workdir) = &cmd.workdir {
+            // `..` would resolve outside the sandbox
+            if workdir
+                .components()
+                .any(|component| component == Component::ParentDir)
+            {
+                return Err(CleanroomError::validation_error(format!(
+                    "Working directory '{}' leaves the process backend's scratch directory",
+                    workdir.display()
+                )));
+            }
+            let dir = sandbox.join(workdir.strip_prefix("/").unwrap_or(workdir));
+            std::fs::create_dir_all(&dir).map_err(|e| {
+                CleanroomError::io_error(format!("Failed to create {}: {}", dir.display(), e))
+            })?;
+            host_cmd.workdir = Some(dir);
+        }
+        let argv = host_cmd.argv();
+        let cmd_string = format!("{} {}", cmd.bin, cmd.args.join(" "));
+        debug!(
+            "Running '{}' on the host in {}",
+            cmd_string,
+            sandbox.display()
+        );
+
+        let mut command = Command::new(&argv[0]);
+        command
+            .args(&argv[1..])
+            .current_dir(sandbox)
+            .env_clear()
+            .env("PATH", std::env::var_os("PATH").unwrap_or_default())
+            .env("HOME", sandbox)
+            .env("TMPDIR", sandbox)
+            .envs(cmd.policy.to_env())
+            .envs(&cmd.env)
+            .stdin(Stdio::null())
+            .stdout(match &cmd.stdout_file {
+                Some(path) => Stdio::from(std::fs::File::create(path).map_err(|e| {
+                    CleanroomError::io_error(format!("Failed to create {}: {}", path.display(), e))
+                })?),
+                None => Stdio::piped(),
+            })
+            .stderr(Stdio::piped());
+        let mut child = command.spawn().map_err(|e| {
+            CleanroomError::container_error(format!("Failed to run '{}' on the host", cmd_string))
+                .with_source(e.to_string())
+        })?;
+
+        let stdout = read_pipe(child.stdout.take());
+        let stderr = read_pipe(child.stderr.take());
+
+        let status = loop {
+            let polled = child.try_wait().map_err(|e| {
+                CleanroomError::container_error(format!("Failed to wait for '{}'", cmd_string))
+                    .with_source(e.to_string())
+            })?;
+            if let Some(status) = polled {
+                break status;
+            }
+            if let Some(timeout) = cmd.timeout.filter(|t| start_time.elapsed() > *t) {
+                warn!(
+                    "Command '{}' exceeded its {}ms timeout, killing it",
+                    cmd_string,
+                    timeout.as_millis()
+                );
+                if let Err(e) = child.kill() {
+                    warn!("Failed to kill '{}': {}", cmd_string, e);
+                }
+                let _ = child.wait();
+                return Err(CleanroomError::timeout_error(format!(
+                    "Command '{}' timed out after {}ms (limit: {}ms)",
+                    cmd_string,
+                    start_time.elapsed().as_millis(),
+                    timeout.as_millis()
+                )));
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        };
+
+        let stdout = join_pipe(stdout);
+        let stderr = join_pipe(stderr);
+
+        Ok(RunResult {
+            exit_code: status.code().unwrap_or(-1),
+            oom_killed: false,
+            stdout,
+            stderr,
+            duration_ms: start_time.elapsed().as_millis() as u64,
+            steps: Vec::new(),
+            redacted_env: Vec::new(),
+            backend: "process".to_string(),
+            concurrent: false,
+            step_order: Vec::new(),
+        })
+    }
+}
+
+impl Backend for HostBackend {
+    fn run_cmd(&self, cmd: Cmd) -> Result<RunResult> {
+        if cmd.network.is_some() {
+            return Err(CleanroomError::container_error(
+                "The process backend cannot run commands on an isolated network",
+            ));
+        }
+
+        let sandbox = scratch_dir()?;
+        let result = self.run_in(&cmd, &sandbox);
+        if let Err(e) = std::fs::remove_dir_all(&sandbox) {
+            warn!("Failed to remove {}: {}", sandbox.display(), e);
+        }
+        result
+    }
+
+    fn name(&self) -> &str {
+        "process"
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+
+    fn supports_hermetic(&self) -> bool {
+        false
+    }
+
+    fn supports_deterministic(&self) -> bool {
+        false
+    }
+}
+
+/// Create a fresh, empty working directory for one command
+fn scratch_dir() -> Result<PathBuf> {
+    let dir = std::env::temp_dir().join(format!("clnrm-process-{}", Uuid::new_v4().simple()));
+    std::fs::create_dir_all(&dir).map_err(|e| {
+        CleanroomError::io_error(format!("Failed to create {}: {}", dir.display(), e))
+    })?;
+    Ok(dir)
+}
+
+/// Drain a child's pipe on a helper thread so it can't fill up and block it
+fn read_pipe<R: Read + Send + 'static>(pipe: Option<R>) -> Option<JoinHandle<Vec<u8>>> {
+    pipe.map(|mut pipe| {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            let _ = pipe.read_to_end(&mut buffer);
+            buffer
+        })
+    })
+}
+
+fn join_pipe(reader: Option<JoinHandle<Vec<u8>>>) -> String {
+    reader
+        .and_then(|reader| reader.join().ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+        .unwrap_or_default()
+}
@@ -0,0 +1,280 @@
+//! Container runtimes behind the Docker Engine API
+//!
+//! Containers are driven through testcontainers and bollard, which speak the
+//! Docker Engine API. Podman serves the same API from its own socket, so a
+//! [`ContainerBackend`] only has to say which CLI it is and where its API
+//! lives. The backend is chosen by `CLNRM_BACKEND`, then `[containers]
+//! backend` in `cleanroom.toml`, and defaults to Docker.
+//!
+//! bollard clients are connected to that API explicitly. testcontainers only
+//! reads it from `DOCKER_HOST`, so the CLI calls [`export_api_host`] before
+//! it starts any threads; the environment is never changed after that.
+//!
+//! The opt-in `process` backend ([`super::process::ProcessBackend`]) runs
+//! steps on the host instead, for CI machines without any runtime.
+
+use crate::backend::process::ProcessBackend;
+use crate::backend::{Backend, TestcontainerBackend};
+use crate::error::{CleanroomError, Result};
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+use testcontainers::bollard::{Docker, API_DEFAULT_VERSION};
+use tracing::debug;
+
+/// Environment variable that overrides the configured backend
+pub const BACKEND_ENV_VAR: &str = "CLNRM_BACKEND";
+
+/// Backend used when none is configured
+pub const DEFAULT_BACKEND: &str = "docker";
+
+/// Names accepted for `[containers] backend` and `CLNRM_BACKEND`
+pub const SUPPORTED_BACKENDS: &[&str] = &["docker", "podman", "process"];
+
+/// Socket Docker listens on when `DOCKER_HOST` isn't set
+const DEFAULT_DOCKER_SOCKET: &str = "/var/run/docker.sock";
+
+/// Request timeout of API clients, in seconds
+const CLIENT_TIMEOUT_SECS: u64 = 120;
+
+/// A container runtime, reached through the Docker Engine API
+pub trait ContainerBackend: Send + Sync + std::fmt::Debug {
+    /// Backend name, as written in configuration
+    fn name(&self) -> &'static str;
+
+    /// The runtime's command-line binary
+    fn binary(&self) -> &'static str;
+
+    /// API host (e.g. `unix:///run/podman/podman.sock`) to reach the runtime
+    /// on, or `None` to use Docker's defaults and `DOCKER_HOST`
+    ///
+    /// # Errors
+    /// * The runtime isn't installed or its API isn't reachable
+    fn api_host(&self) -> Result<Option<String>>;
+
+    /// Check that testcontainers will start containers on this runtime
+    ///
+    /// testcontainers only reads the API host from `DOCKER_HOST`, which
+    /// [`export_api_host`] sets at startup, so a backend with its own socket
+    /// needs `DOCKER_HOST` to point at it.
+    ///
+    /// # Errors
+    /// * The runtime isn't installed or its API isn't reachable
+    /// * `DOCKER_HOST` points at a different API
+    fn connect(&self) -> Result<()> {
+        let Some(host) = self.api_host()? else {
+            return Ok(());
+        };
+
+        match std::env::var("DOCKER_HOST") {
+            Ok(current) if current == host => Ok(()),
+            current => Err(CleanroomError::container_error(format!(
+                "Containers would not start on {}: DOCKER_HOST is {}",
+                self.name(),
+                current.as_deref().unwrap_or("not set")
+            ))
+            .with_context(format!("Set DOCKER_HOST={}", host))),
+        }
+    }
+
+    /// API client for operations testcontainers does not expose, connected
+    /// to this runtime's API
+    ///
+    /// # Errors
+    /// * The runtime isn't installed or its API isn't reachable
+    fn client(&self) -> Result<Docker> {
+        let client = match self.api_host()? {
+            Some(host) => {
+                Docker::connect_with_socket(&host, CLIENT_TIMEOUT_SECS, API_DEFAULT_VERSION)
+            }
+            None => Docker::connect_with_defaults(),
+        };
+        client.map_err(|e| {
+            CleanroomError::container_error(format!("Failed to connect to {}", self.name()))
+                .with_source(e.to_string())
+        })
+    }
+
+    /// Backend that runs step commands, in fresh `default_image` containers
+    ///
+    /// # Errors
+    /// * The runtime isn't installed or its API isn't reachable
+    /// * `default_image` isn't a valid image reference
+    fn step_backend(&self, default_image: &str) -> Result<Arc<dyn Backend>> {
+        self.connect()?;
+        let backend = TestcontainerBackend::new(default_image).map_err(|e| {
+            CleanroomError::container_error("Failed to initialize test container backend")
+                .with_context(format!("Cannot use default image '{}'", default_image))
+                .with_source(e.to_string())
+        })?;
+        Ok(Arc::new(backend))
+    }
+}
+
+/// Docker, through `DOCKER_HOST` or its default socket
+#[derive(Debug, Default)]
+pub struct DockerBackend;
+
+impl ContainerBackend for DockerBackend {
+    fn name(&self) -> &'static str {
+        "docker"
+    }
+
+    fn binary(&self) -> &'static str {
+        "docker"
+    }
+
+    /// `DOCKER_HOST` may name a remote daemon, so only without it is a local
+    /// install required: the `docker` CLI or the default socket
+    fn api_host(&self) -> Result<Option<String>> {
+        let installed = std::env::var_os("DOCKER_HOST").is_some()
+            || Path::new(DEFAULT_DOCKER_SOCKET).exists()
+            || on_path(self.binary());
+        if !installed {
+            return Err(not_installed(self));
+        }
+        Ok(None)
+    }
+}
+
+/// Podman, through the API socket reported by `podman info`
+#[derive(Debug, Default)]
+pub struct PodmanBackend {
+    host: OnceLock<String>,
+}
+
+impl ContainerBackend for PodmanBackend {
+    fn name(&self) -> &'static str {
+        "podman"
+    }
+
+    fn binary(&self) -> &'static str {
+        "podman"
+    }
+
+    fn api_host(&self) -> Result<Option<String>> {
+        if let Some(host) = self.host.get() {
+            return Ok(Some(host.clone()));
+        }
+
+        let output = std::process::Command::new(self.binary())
+            .args(["info", "--format", "{{.Host.RemoteSocket.Path}}"])
+            .output()
+            .map_err(|e| match e.kind() {
+                ErrorKind::NotFound => not_installed(self),
+                _ => CleanroomError::container_error("Failed to run `podman info`")
+                    .with_source(e.to_string()),
+            })?;
+        if !output.status.success() {
+            return Err(CleanroomError::container_error(
+                "Failed to query Podman for its API socket",
+            )
+            .with_source(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+
+        let socket = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let socket = socket.strip_prefix("unix://").unwrap_or(&socket);
+        if socket.is_empty() || !Path::new(socket).exists() {
+            return Err(CleanroomError::container_error(format!(
+                "Podman API socket '{}' is not available",
+                socket
+            ))
+            .with_context(
+                "Start it with `systemctl --user start podman.socket` or `podman system service`",
+            ));
+        }
+
+        let host = format!("unix://{}", socket);
+        Ok(Some(self.host.get_or_init(|| host).clone()))
+    }
+}
+
+/// Whether `binary` is a file in one of the `PATH` directories
+fn on_path(binary: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(binary).is_file()))
+}
+
+fn not_installed(backend: &dyn ContainerBackend) -> CleanroomError {
+    CleanroomError::container_error(format!(
+        "Container backend '{}' is not installed: `{}` was not found on PATH",
+        backend.name(),
+        backend.binary()
+    ))
+    .with_context(format!(
+        "Install {} or choose another backend with {} or [containers] backend",
+        backend.name(),
+        BACKEND_ENV_VAR
+    ))
+}
+
+/// Backend for a configured name
+///
+/// # Errors
+/// * `name` isn't one of [`SUPPORTED_BACKENDS`]
+pub fn backend_from_name(name: &str) -> Result<Arc<dyn ContainerBackend>> {
+    match name.trim().to_lowercase().as_str() {
+        "docker" => Ok(Arc::new(DockerBackend)),
+        "podman" => Ok(Arc::new(PodmanBackend::default())),
+        "process" => Ok(Arc::new(ProcessBackend)),
+        other => Err(CleanroomError::configuration_error(format!(
+            "Unknown container backend '{}': expected one of {}",
+            other,
+            SUPPORTED_BACKENDS.join(", ")
+        ))),
+    }
+}
+
+/// Backend named by `CLNRM_BACKEND`, else by `configured`, else Docker
+///
+/// # Errors
+/// * The chosen name isn't one of [`SUPPORTED_BACKENDS`]
+pub fn select_backend(configured: Option<&str>) -> Result<Arc<dyn ContainerBackend>> {
+    match std::env::var(BACKEND_ENV_VAR) {
+        Ok(name) if !name.trim().is_empty() => backend_from_name(&name)
+            .map_err(|e| e.with_context(format!("Invalid {}", BACKEND_ENV_VAR))),
+        _ => backend_from_name(configured.unwrap_or(DEFAULT_BACKEND)),
+    }
+}
+
+/// Export the configured runtime's API host as `DOCKER_HOST`
+///
+/// testcontainers can't be handed an API host, so binaries call this at
+/// startup, while they are still single-threaded. A `DOCKER_HOST` that is
+/// already set is left alone; a runtime that can't be reached is reported
+/// once containers are needed.
+pub fn export_api_host() {
+    if std::env::var_os("DOCKER_HOST").is_some() {
+        return;
+    }
+
+    let config = crate::config::load_cleanroom_config().ok();
+    let configured = config.as_ref().map(|c| c.containers.backend.as_str());
+    let Ok(backend) = select_backend(configured) else {
+        return;
+    };
+    if let Ok(Some(host)) = backend.api_host() {
+        debug!("Using {} API at {}", backend.name(), host);
+        std::env::set_var("DOCKER_HOST", host);
+    }
+}
+
+fn active() -> &'static RwLock<Arc<dyn ContainerBackend>> {
+    static ACTIVE: OnceLock<RwLock<Arc<dyn ContainerBackend>>> = OnceLock::new();
+    ACTIVE.get_or_init(|| RwLock::new(Arc::new(DockerBackend)))
+}
+
+/// Make `backend` the one new containers are started with
+pub fn set_active_backend(backend: Arc<dyn ContainerBackend>) {
+    *active()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = backend;
+}
+
+/// Backend new containers are started with (Docker until one is set)
+pub fn active_backend() -> Arc<dyn ContainerBackend> {
+    active()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
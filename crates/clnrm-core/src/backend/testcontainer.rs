@@ -3,12 +3,15 @@
 //! Provides testcontainers-rs integration for hermetic, isolated execution
 //! with automatic container lifecycle management.
 
+use crate::backend::runtime::active_backend;
 use crate::backend::volume::{VolumeMount, VolumeValidator};
 use crate::backend::{Backend, Cmd, RunResult};
 use crate::error::{BackendError, Result};
 use crate::policy::Policy;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
+use testcontainers::bollard::query_parameters::InspectContainerOptions;
 use testcontainers::{core::ExecCommand, runners::SyncRunner, GenericImage, ImageExt};
 
 use tracing::{info, instrument, warn};
@@ -205,14 +208,12 @@ impl TestcontainerBackend {
 
         {
             use crate::telemetry::events;
-            use opentelemetry::global;
-            use opentelemetry::trace::{Span, Tracer, TracerProvider};
+            use opentelemetry::trace::{Span, Tracer};
 
             // Get current span and record container.start event
-            let tracer_provider = global::tracer_provider();
-            let mut span = tracer_provider
-                .tracer("clnrm-backend")
-                .start("clnrm.container.start");
+            let tracer =
+                crate::telemetry::test_run::tracer(cmd.tracer_provider.as_ref(), "clnrm-backend");
+            let mut span = tracer.start("clnrm.container.start");
 
             events::record_container_start(
                 &mut span,
@@ -270,10 +271,9 @@ impl TestcontainerBackend {
         // Alpine containers exit immediately without a command
         container_request = container_request.with_cmd(vec!["sleep", "3600"]);
 
-        // Set working directory if specified
-        if let Some(workdir) = &cmd.workdir {
-            container_request =
-                container_request.with_working_dir(workdir.to_string_lossy().to_string());
+        // Attach to the requested network, e.g. a hermetic one without egress
+        if let Some(network) = &cmd.network {
+            container_request = container_request.with_network(network.clone());
         }
 
         // Start container using SyncRunner with timeout monitoring
@@ -300,68 +300,80 @@ impl TestcontainerBackend {
 
         info!("Container started successfully, executing command");
 
-        // Execute command - testcontainers expects Vec<&str> for exec
-        let cmd_args: Vec<&str> = std::iter::once(cmd.bin.as_str())
-            .chain(cmd.args.iter().map(|s| s.as_str()))
-            .collect();
+        // Execute command, from its working directory if one is set
+        let cmd_args = cmd.argv();
 
-        #[allow(unused_variables)]
         let cmd_string = format!("{} {}", cmd.bin, cmd.args.join(" "));
 
         let exec_cmd = ExecCommand::new(cmd_args);
-        let mut exec_result = container
+        let exec_start = Instant::now();
+        let exec_result = container
             .exec(exec_cmd)
             .map_err(|e| BackendError::Runtime(format!("Command execution failed: {}", e)))?;
 
+        let (stdout, stderr, exit_code) = match cmd.timeout {
+            Some(timeout) => {
+                // Read on a helper thread so a hung command can be abandoned
+                let (tx, rx) = std::sync::mpsc::channel();
+                let stdout_file = cmd.stdout_file.clone();
+                std::thread::spawn(move || {
+                    let _ = tx.send(read_exec_output(exec_result, stdout_file.as_deref()));
+                });
+
+                match rx.recv_timeout(timeout.saturating_sub(exec_start.elapsed())) {
+                    Ok(output) => output?,
+                    Err(_) => {
+                        let elapsed_ms = exec_start.elapsed().as_millis();
+                        warn!(
+                            "Command '{}' exceeded its {}ms timeout, killing container {}",
+                            cmd_string,
+                            timeout.as_millis(),
+                            container.id()
+                        );
+                        // Killing the container closes the exec streams, which
+                        // ends the reader thread; dropping it removes the container
+                        if let Err(e) = container.stop_with_timeout(Some(0)) {
+                            warn!("Failed to kill container {}: {}", container.id(), e);
+                        }
+                        return Err(crate::error::CleanroomError::timeout_error(format!(
+                            "Command '{}' timed out after {}ms (limit: {}ms)",
+                            cmd_string,
+                            elapsed_ms,
+                            timeout.as_millis()
+                        )));
+                    }
+                }
+            }
+            None => read_exec_output(exec_result, cmd.stdout_file.as_deref())?,
+        };
+
+        if let Some(workdir) = cmd.missing_workdir(exit_code, &stderr) {
+            return Err(crate::error::CleanroomError::container_error(format!(
+                "Working directory '{}' does not exist in the container",
+                workdir.display()
+            )));
+        }
+
+        let oom_killed = exit_code != 0 && was_oom_killed(container.id());
+
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
         info!("Command completed in {}ms", duration_ms);
 
-        // Extract output - SyncExecResult provides stdout() and stderr() as streams
-        use std::io::Read;
-        let mut stdout = String::new();
-        let mut stderr = String::new();
-
-        exec_result
-            .stdout()
-            .read_to_string(&mut stdout)
-            .map_err(|e| BackendError::Runtime(format!("Failed to read stdout: {}", e)))?;
-        exec_result
-            .stderr()
-            .read_to_string(&mut stderr)
-            .map_err(|e| BackendError::Runtime(format!("Failed to read stderr: {}", e)))?;
-
-        // Extract exit code with proper error handling
-        // testcontainers may return None if exit code is unavailable
-        #[allow(clippy::unnecessary_lazy_evaluations)] // Need closure for warn! macro
-        let exit_code = exec_result
-            .exit_code()
-            .map_err(|e| BackendError::Runtime(format!("Failed to get exit code: {}", e)))?
-            .unwrap_or_else(|| {
-                // Exit code unavailable - this can happen with certain container states
-                // Return -1 to indicate unknown/error state (POSIX convention for signal termination)
-                warn!("Exit code unavailable from container, defaulting to -1");
-                -1
-            }) as i32;
-
         {
             use crate::telemetry::events;
-            use opentelemetry::global;
-            use opentelemetry::trace::{Span, Tracer, TracerProvider};
+            use opentelemetry::trace::{Span, Tracer};
 
             // Record container.exec event
-            let tracer_provider = global::tracer_provider();
-            let mut exec_span = tracer_provider
-                .tracer("clnrm-backend")
-                .start("clnrm.container.exec");
+            let tracer =
+                crate::telemetry::test_run::tracer(cmd.tracer_provider.as_ref(), "clnrm-backend");
+            let mut exec_span = tracer.start("clnrm.container.exec");
 
             events::record_container_exec(&mut exec_span, &cmd_string, exit_code);
             exec_span.end();
 
             // Record container.stop event
-            let mut stop_span = tracer_provider
-                .tracer("clnrm-backend")
-                .start("clnrm.container.stop");
+            let mut stop_span = tracer.start("clnrm.container.stop");
 
             events::record_container_stop(&mut stop_span, &container_id, exit_code);
             stop_span.end();
@@ -369,6 +381,7 @@ impl TestcontainerBackend {
 
         Ok(RunResult {
             exit_code,
+            oom_killed,
             stdout,
             stderr,
             duration_ms,
@@ -381,6 +394,92 @@ impl TestcontainerBackend {
     }
 }
 
+/// Drain stdout and stderr of an exec and fetch its exit code
+///
+/// Stdout is copied to `stdout_file` as it arrives when one is given, and
+/// left out of the returned output.
+fn read_exec_output(
+    mut exec_result: testcontainers::core::SyncExecResult,
+    stdout_file: Option<&Path>,
+) -> Result<(String, String, i32)> {
+    // SyncExecResult provides stdout() and stderr() as streams
+    use std::io::Read;
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+
+    match stdout_file {
+        Some(path) => {
+            let mut file = std::fs::File::create(path).map_err(|e| {
+                BackendError::Runtime(format!("Failed to create {}: {}", path.display(), e))
+            })?;
+            std::io::copy(&mut exec_result.stdout(), &mut file)
+                .map_err(|e| BackendError::Runtime(format!("Failed to read stdout: {}", e)))?;
+        }
+        None => {
+            exec_result
+                .stdout()
+                .read_to_string(&mut stdout)
+                .map_err(|e| BackendError::Runtime(format!("Failed to read stdout: {}", e)))?;
+        }
+    }
+    exec_result
+        .stderr()
+        .read_to_string(&mut stderr)
+        .map_err(|e| BackendError::Runtime(format!("Failed to read stderr: {}", e)))?;
+
+    // Extract exit code with proper error handling
+    // testcontainers may return None if exit code is unavailable
+    #[allow(clippy::unnecessary_lazy_evaluations)] // Need closure for warn! macro
+    let exit_code = exec_result
+        .exit_code()
+        .map_err(|e| BackendError::Runtime(format!("Failed to get exit code: {}", e)))?
+        .unwrap_or_else(|| {
+            // Exit code unavailable - this can happen with certain container states
+            // Return -1 to indicate unknown/error state (POSIX convention for signal termination)
+            warn!("Exit code unavailable from container, defaulting to -1");
+            -1
+        }) as i32;
+
+    Ok((stdout, stderr, exit_code))
+}
+
+/// Whether the runtime reports the container was killed for exceeding its
+/// memory limit
+///
+/// The exit code can't tell: timeouts and `docker kill` end a command with
+/// SIGKILL (137) as well. A failed inspection is logged and counts as no.
+fn was_oom_killed(container_id: &str) -> bool {
+    let id = container_id.to_string();
+    // On a thread of its own, as the caller may already be inside a runtime
+    let inspected = std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+        runtime.block_on(async {
+            active_backend()
+                .client()
+                .map_err(|e| e.to_string())?
+                .inspect_container(&id, None::<InspectContainerOptions>)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    })
+    .join()
+    .unwrap_or_else(|_| Err("inspection thread panicked".to_string()));
+
+    match inspected {
+        Ok(inspect) => inspect
+            .state
+            .and_then(|state| state.oom_killed)
+            .unwrap_or(false),
+        Err(e) => {
+            warn!("Failed to inspect container {}: {}", container_id, e);
+            false
+        }
+    }
+}
+
 impl Backend for TestcontainerBackend {
     fn run_cmd(&self, cmd: Cmd) -> Result<RunResult> {
         // Use synchronous execution with timeout
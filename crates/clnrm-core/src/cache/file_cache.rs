@@ -33,6 +33,9 @@ pub struct CacheFile {
     pub version: String,
     /// File path to hash mapping
     pub hashes: HashMap<String, String>,
+    /// File path to duration (ms) of its most recent run
+    #[serde(default)]
+    pub durations: HashMap<String, u64>,
     /// Last update timestamp
     pub last_updated: DateTime<Utc>,
 }
@@ -43,6 +46,7 @@ impl CacheFile {
         Self {
             version: CACHE_VERSION.to_string(),
             hashes: HashMap::new(),
+            durations: HashMap::new(),
             last_updated: Utc::now(),
         }
     }
@@ -170,6 +174,34 @@ impl FileCache {
     pub fn cache_path(&self) -> &Path {
         &self.cache_path
     }
+
+    /// Record how long a test file took to run, replacing any earlier timing
+    pub fn record_duration(&self, file_path: &Path, duration_ms: u64) -> Result<()> {
+        let file_key = file_path
+            .to_str()
+            .ok_or_else(|| CleanroomError::validation_error("Invalid file path encoding"))?
+            .to_string();
+
+        let mut cache = self.cache.lock().map_err(|e| {
+            CleanroomError::internal_error(format!("Failed to acquire cache lock: {}", e))
+        })?;
+
+        cache.durations.insert(file_key, duration_ms);
+        Ok(())
+    }
+
+    /// Duration (ms) of a test file's most recent recorded run
+    pub fn duration(&self, file_path: &Path) -> Result<Option<u64>> {
+        let file_key = file_path
+            .to_str()
+            .ok_or_else(|| CleanroomError::validation_error("Invalid file path encoding"))?;
+
+        let cache = self.cache.lock().map_err(|e| {
+            CleanroomError::internal_error(format!("Failed to acquire cache lock: {}", e))
+        })?;
+
+        Ok(cache.durations.get(file_key).copied())
+    }
 }
 
 impl Cache for FileCache {
@@ -231,6 +263,7 @@ impl Cache for FileCache {
             CleanroomError::internal_error(format!("Failed to acquire cache lock: {}", e))
         })?;
 
+        cache.durations.remove(&file_key);
         if cache.hashes.remove(&file_key).is_some() {
             debug!("Removed from cache: {}", file_key);
         }
@@ -282,6 +315,7 @@ impl Cache for FileCache {
 
         let count = cache.hashes.len();
         cache.hashes.clear();
+        cache.durations.clear();
         cache.last_updated = Utc::now();
 
         info!("Cleared {} entries from cache", count);
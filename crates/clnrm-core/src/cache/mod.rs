@@ -6,6 +6,9 @@
 //! ## Architecture
 //! Pipeline: Render → Hash → Load cache → Compare → Run (if changed) → Update cache
 //!
+//! Each run's duration is recorded too, so `--shard-strategy duration` can
+//! balance shards by how long their tests took last time.
+//!
 //! ## Cache Structure
 //! File: `~/.clnrm/cache/hashes.json`
 //! ```json
@@ -15,6 +18,10 @@
 //!     "tests/api.clnrm.toml": "abc123...",
 //!     "tests/db.clnrm.toml": "def456..."
 //!   },
+//!   "durations": {
+//!     "tests/api.clnrm.toml": 1840,
+//!     "tests/db.clnrm.toml": 12075
+//!   },
 //!   "last_updated": "2025-10-17T12:34:56Z"
 //! }
 //! ```
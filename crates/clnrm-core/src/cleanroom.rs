@@ -4,15 +4,19 @@
 //! principle. Every feature of this framework is validated by using the framework
 //! to test its own functionality.
 
-use crate::backend::{Backend, Cmd, TestcontainerBackend};
+use crate::backend::{Backend, Cmd, RunResult, TestcontainerBackend};
+use crate::coverage::tracker::CoverageTracker;
 use crate::error::{CleanroomError, Result};
-use opentelemetry::global;
-use opentelemetry::trace::{Span, Tracer, TracerProvider};
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::trace::{Span, Tracer};
 use opentelemetry::KeyValue;
+use opentelemetry_sdk::trace::SdkTracerProvider;
 use std::any::Any;
 use std::collections::HashMap;
 use std::os::unix::process::ExitStatusExt;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
@@ -28,7 +32,135 @@ pub trait ServicePlugin: Send + Sync + std::fmt::Debug {
     fn stop(&self, handle: ServiceHandle) -> Result<()>;
 
     /// Check service health
+    ///
+    /// A liveness probe for a service that is already running; readiness at
+    /// startup is [`ServicePlugin::ready_check`].
     fn health_check(&self, handle: &ServiceHandle) -> HealthStatus;
+
+    /// Block until the service accepts connections, failing after `timeout`
+    ///
+    /// Called by `start_service` once `start` returns, so a scenario's first
+    /// command never reaches a service that is still booting. Plugins whose
+    /// `start` already waits keep this default, which reports them ready.
+    fn ready_check(&self, _handle: &ServiceHandle, _timeout: Duration) -> Result<()> {
+        Ok(())
+    }
+
+    /// Read the container output the service has produced so far
+    ///
+    /// Plugins that don't own a container keep this default, which reports
+    /// log capture as unsupported.
+    fn logs(&self, _handle: &ServiceHandle) -> Result<ServiceLogs> {
+        Err(CleanroomError::service_error(format!(
+            "Service '{}' does not support log capture",
+            self.name()
+        )))
+    }
+
+    /// Run a SQL query against the service and return the rows it selected
+    ///
+    /// Each row holds the selected columns as text. Plugins that aren't
+    /// databases keep this default, which reports queries as unsupported.
+    fn query(&self, _handle: &ServiceHandle, _sql: &str) -> Result<Vec<Vec<String>>> {
+        Err(CleanroomError::service_error(format!(
+            "Service '{}' does not support SQL queries",
+            self.name()
+        )))
+    }
+
+    /// Leave the service's container running once the plugin is dropped
+    ///
+    /// Returns the container ID, or `None` for plugins that don't own a
+    /// container and so have nothing to keep.
+    fn keep_running(&self, _handle: &ServiceHandle) -> Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// Container output captured from a running service
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ServiceLogs {
+    /// Container stdout
+    pub stdout: String,
+    /// Container stderr
+    pub stderr: String,
+}
+
+impl ServiceLogs {
+    /// Read everything a container has written to stdout and stderr
+    pub(crate) async fn from_container<I: testcontainers::Image>(
+        container: &testcontainers::ContainerAsync<I>,
+    ) -> Result<Self> {
+        let stdout = container.stdout_to_vec().await.map_err(|e| {
+            CleanroomError::container_error("Failed to read container stdout")
+                .with_source(e.to_string())
+        })?;
+        let stderr = container.stderr_to_vec().await.map_err(|e| {
+            CleanroomError::container_error("Failed to read container stderr")
+                .with_source(e.to_string())
+        })?;
+
+        Ok(Self {
+            stdout: String::from_utf8_lossy(&stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&stderr).into_owned(),
+        })
+    }
+
+    /// Stdout followed by stderr, as written to a log artifact
+    pub fn combined(&self) -> String {
+        let mut combined = self.stdout.clone();
+        if !combined.is_empty() && !combined.ends_with('\n') && !self.stderr.is_empty() {
+            combined.push('\n');
+        }
+        combined.push_str(&self.stderr);
+        combined
+    }
+
+    /// The last `lines` lines of the combined output
+    pub fn tail(&self, lines: usize) -> Vec<String> {
+        let combined = self.combined();
+        let all: Vec<&str> = combined.lines().collect();
+        let start = all.len().saturating_sub(lines);
+        all[start..].iter().map(|line| line.to_string()).collect()
+    }
+}
+
+/// How far into a service's stdout and stderr its logs have been followed
+#[derive(Debug, Clone, Copy, Default)]
+struct LogCursor {
+    stdout: usize,
+    stderr: usize,
+}
+
+impl LogCursor {
+    /// A cursor past everything already in `logs`
+    fn at_end(logs: &ServiceLogs) -> Self {
+        Self {
+            stdout: logs.stdout.len(),
+            stderr: logs.stderr.len(),
+        }
+    }
+
+    /// Complete lines written since the cursor, stdout first; moves the
+    /// cursor past them and leaves partial lines for the next read
+    fn advance(&mut self, logs: &ServiceLogs) -> Vec<String> {
+        let mut lines = Self::advance_stream(&mut self.stdout, &logs.stdout);
+        lines.extend(Self::advance_stream(&mut self.stderr, &logs.stderr));
+        lines
+    }
+
+    fn advance_stream(offset: &mut usize, output: &str) -> Vec<String> {
+        // Output shorter than what was read means the container was replaced
+        if output.len() < *offset || !output.is_char_boundary(*offset) {
+            *offset = 0;
+        }
+        let unread = &output[*offset..];
+        let Some(end) = unread.rfind('\n').map(|end| end + 1) else {
+            return Vec::new();
+        };
+        *offset += end;
+        unread[..end].lines().map(str::to_string).collect()
+    }
 }
 
 /// Service handle for managing service instances
@@ -53,11 +185,40 @@ pub enum HealthStatus {
     Unknown,
 }
 
+/// How long `start_service` waits for a started service to become ready
+pub const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long past a command's timeout to wait for the backend to kill it
+/// before abandoning the command
+///
+/// Backends enforce command timeouts themselves so they can kill the
+/// command's container. Racing them with the same limit here would abandon
+/// the command before it is killed, leaving the container running, so this
+/// guard only fires for a backend that fails to stop it.
+const TIMEOUT_GRACE: Duration = Duration::from_secs(5);
+
+/// Start `plugin` and wait until it is ready, stopping it if it never is
+fn start_until_ready(plugin: &dyn ServicePlugin) -> Result<ServiceHandle> {
+    let handle = plugin.start()?;
+    if let Err(e) = plugin.ready_check(&handle, DEFAULT_READY_TIMEOUT) {
+        let service_name = handle.service_name.clone();
+        if let Err(stop_error) = plugin.stop(handle) {
+            tracing::warn!(
+                "Failed to stop service '{}' after it never became ready: {}",
+                service_name,
+                stop_error
+            );
+        }
+        return Err(e);
+    }
+    Ok(handle)
+}
+
 /// Plugin-based service registry
 #[derive(Debug, Default)]
 pub struct ServiceRegistry {
     /// Registered service plugins
-    plugins: HashMap<String, Box<dyn ServicePlugin>>,
+    plugins: HashMap<String, Arc<dyn ServicePlugin>>,
     /// Active service instances
     active_services: HashMap<String, ServiceHandle>,
 }
@@ -122,16 +283,31 @@ impl ServiceRegistry {
     /// Register a service plugin
     pub fn register_plugin(&mut self, plugin: Box<dyn ServicePlugin>) {
         let name = plugin.name().to_string();
-        self.plugins.insert(name, plugin);
+        self.plugins.insert(name, Arc::from(plugin));
     }
 
-    /// Start a service by name
+    /// Get a shared reference to a registered plugin
+    ///
+    /// Lets callers start a plugin without holding the registry lock, so
+    /// independent services can boot concurrently.
+    pub fn get_plugin(&self, service_name: &str) -> Result<Arc<dyn ServicePlugin>> {
+        self.plugins.get(service_name).cloned().ok_or_else(|| {
+            CleanroomError::internal_error(format!("Service plugin '{}' not found", service_name))
+        })
+    }
+
+    /// Record a handle for a service started outside the registry
+    pub fn track_active_service(&mut self, handle: ServiceHandle) {
+        self.active_services.insert(handle.id.clone(), handle);
+    }
+
+    /// Start a service by name, returning once it is ready
     pub async fn start_service(&mut self, service_name: &str) -> Result<ServiceHandle> {
         let plugin = self.plugins.get(service_name).ok_or_else(|| {
             CleanroomError::internal_error(format!("Service plugin '{}' not found", service_name))
         })?;
 
-        let handle = plugin.start()?;
+        let handle = start_until_ready(plugin.as_ref())?;
         self.active_services
             .insert(handle.id.clone(), handle.clone());
 
@@ -154,6 +330,23 @@ impl ServiceRegistry {
         Ok(())
     }
 
+    /// Stop tracking a service without stopping it, leaving its container running
+    ///
+    /// Returns the container ID if the plugin kept one.
+    pub fn keep_service(&mut self, handle_id: &str) -> Result<Option<String>> {
+        let Some(handle) = self.active_services.remove(handle_id) else {
+            return Ok(None);
+        };
+        let plugin = self.plugins.get(&handle.service_name).ok_or_else(|| {
+            CleanroomError::internal_error(format!(
+                "Service plugin '{}' not found for handle '{}'",
+                handle.service_name, handle_id
+            ))
+        })?;
+
+        plugin.keep_running(&handle)
+    }
+
     /// Check health of all services
     pub async fn check_all_health(&self) -> HashMap<String, HealthStatus> {
         let mut health_status = HashMap::new();
@@ -187,30 +380,8 @@ impl ServiceRegistry {
             CleanroomError::internal_error(format!("Service with ID '{}' not found", service_id))
         })?;
 
-        let _plugin = self.plugins.get(&handle.service_name).ok_or_else(|| {
-            CleanroomError::internal_error(format!(
-                "Service plugin '{}' not found",
-                handle.service_name
-            ))
-        })?;
-
-        // For now, return mock logs since actual log retrieval depends on the service implementation
-        // In a real implementation, this would call plugin.get_logs(handle, lines)
-        let mock_logs = vec![
-            format!(
-                "[{}] Service '{}' started",
-                chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"),
-                handle.service_name
-            ),
-            format!(
-                "[{}] Service '{}' is running",
-                chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"),
-                handle.service_name
-            ),
-        ];
-
-        // Return only the requested number of lines
-        Ok(mock_logs.into_iter().take(lines).collect())
+        let plugin = self.get_plugin(&handle.service_name)?;
+        Ok(plugin.logs(handle)?.tail(lines))
     }
 }
 
@@ -267,6 +438,9 @@ impl Default for SimpleMetrics {
 pub struct ExecutionResult {
     /// Exit code of the executed command
     pub exit_code: i32,
+    /// Whether the container runtime reports the container was killed for
+    /// exceeding its memory limit
+    pub oom_killed: bool,
     /// Standard output from the command
     pub stdout: String,
     /// Standard error from the command
@@ -303,6 +477,82 @@ impl ExecutionResult {
     pub fn failed(&self) -> bool {
         !self.succeeded()
     }
+
+    /// Why the command failed, judged from its exit code and whether it was
+    /// OOM killed
+    ///
+    /// `None` when it succeeded. Timeouts surface as errors from
+    /// `execute_in_container_with_timeout`, and regex mismatches are judged
+    /// by the caller that holds the pattern.
+    pub fn failure_reason(&self) -> Option<FailureReason> {
+        FailureReason::from_exit(self.exit_code, self.oom_killed)
+    }
+}
+
+/// Category of a failed command, for reporting failures consistently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailureReason {
+    /// The command exited with a non-zero code
+    NonZeroExit,
+    /// The command ran past its timeout or the test's duration limit
+    Timeout,
+    /// The container runtime reports the kernel's OOM killer ended the
+    /// command for exceeding the container's memory limit
+    OomKilled,
+    /// The command succeeded but its output did not match the expected regex
+    RegexMismatch,
+    /// The command exited with 0 where the step expected another exit code
+    UnexpectedExitCode,
+    /// The command was terminated by another signal
+    Signal,
+}
+
+impl FailureReason {
+    /// Categorize an exit code: 128+N or -1 is a signal
+    ///
+    /// An OOM kill looks like any other SIGKILL (137) here; use
+    /// [`Self::from_exit`] where the runtime's `OOMKilled` state is known.
+    pub fn from_exit_code(exit_code: i32) -> Option<Self> {
+        match exit_code {
+            0 => None,
+            -1 | 129..=159 => Some(Self::Signal),
+            _ => Some(Self::NonZeroExit),
+        }
+    }
+
+    /// Categorize an exit code, given whether the container runtime reports
+    /// the command's container was OOM killed
+    pub fn from_exit(exit_code: i32, oom_killed: bool) -> Option<Self> {
+        match exit_code {
+            0 => None,
+            _ if oom_killed => Some(Self::OomKilled),
+            _ => Self::from_exit_code(exit_code),
+        }
+    }
+
+    /// The category of an execution error, if it is one
+    pub fn from_error(error: &CleanroomError) -> Option<Self> {
+        (error.kind == crate::error::ErrorKind::Timeout).then_some(Self::Timeout)
+    }
+
+    /// Stable snake_case name, as used in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::NonZeroExit => "non_zero_exit",
+            Self::Timeout => "timeout",
+            Self::OomKilled => "oom_killed",
+            Self::RegexMismatch => "regex_mismatch",
+            Self::UnexpectedExitCode => "unexpected_exit_code",
+            Self::Signal => "signal",
+        }
+    }
+}
+
+impl std::fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Simple environment wrapper around existing infrastructure
@@ -323,6 +573,13 @@ pub struct CleanroomEnvironment {
     meter: opentelemetry::metrics::Meter,
     /// Telemetry configuration and state
     telemetry: Arc<RwLock<TelemetryState>>,
+    /// Docker network command containers run on, if not the default bridge
+    network: Arc<RwLock<Option<String>>>,
+    /// Tracer provider of the test running in this environment, if it
+    /// exports its own spans
+    tracer_provider: Arc<RwLock<Option<SdkTracerProvider>>>,
+    /// Behavior coverage recorded by the tests run in this environment
+    coverage: CoverageTracker,
 }
 
 impl Default for CleanroomEnvironment {
@@ -358,6 +615,9 @@ impl Default for CleanroomEnvironment {
             container_registry: Arc::new(RwLock::new(HashMap::new())),
             meter: global::meter("clnrm-cleanroom"),
             telemetry: Arc::new(RwLock::new(TelemetryState::new())),
+            network: Arc::new(RwLock::new(None)),
+            tracer_provider: Arc::new(RwLock::new(None)),
+            coverage: CoverageTracker::new(),
         }
     }
 }
@@ -420,13 +680,16 @@ impl CleanroomEnvironment {
     ///
     /// # Arguments
     /// * `config` - Optional CleanroomConfig. If None, uses default settings.
-    ///   If Some, uses configured default_image for test containers.
+    ///   If Some, uses configured default_image for test containers and
+    ///   `[containers] backend` as the container runtime.
     ///
     /// # Returns
     /// * `Result<Self>` - CleanroomEnvironment instance
     ///
     /// # Errors
     /// * Returns error if backend initialization fails (e.g., invalid image)
+    /// * Returns error if the container backend (or `CLNRM_BACKEND`) is unknown
+    ///   or not installed
     pub async fn with_config(config: Option<crate::config::CleanroomConfig>) -> Result<Self> {
         // Extract default image from config or use fallback
         let default_image = config
@@ -434,13 +697,17 @@ impl CleanroomEnvironment {
             .map(|c| c.containers.default_image.clone())
             .unwrap_or_else(|| "alpine:latest".to_string());
 
+        // Containers started from here on run on the configured runtime,
+        // which fails now rather than at the first container if it's missing
+        let container_backend = crate::backend::runtime::select_backend(
+            config.as_ref().map(|c| c.containers.backend.as_str()),
+        )?;
+        let backend = container_backend.step_backend(&default_image)?;
+        crate::backend::runtime::set_active_backend(container_backend);
+
         Ok(Self {
             session_id: Uuid::new_v4(),
-            backend: Arc::new(TestcontainerBackend::new(&default_image).map_err(|e| {
-                CleanroomError::container_error("Failed to initialize test container backend")
-                    .with_context(format!("Cannot use default image '{}'", default_image))
-                    .with_source(e.to_string())
-            })?),
+            backend,
             services: Arc::new(RwLock::new(ServiceRegistry::new().with_default_plugins())),
             metrics: Arc::new(RwLock::new(SimpleMetrics::default())),
             container_registry: Arc::new(RwLock::new(HashMap::new())),
@@ -449,6 +716,9 @@ impl CleanroomEnvironment {
                 meter_provider.meter("clnrm-cleanroom")
             },
             telemetry: Arc::new(RwLock::new(TelemetryState::new())),
+            network: Arc::new(RwLock::new(None)),
+            tracer_provider: Arc::new(RwLock::new(None)),
+            coverage: CoverageTracker::new(),
         })
     }
 
@@ -457,9 +727,9 @@ impl CleanroomEnvironment {
     where
         F: FnOnce() -> Result<T>,
     {
-        let tracer_provider = global::tracer_provider();
-        let mut span = tracer_provider
+        let mut span = self
             .tracer("clnrm-cleanroom")
+            .await
             .start(format!("test.{}", _test_name));
         span.set_attributes(vec![
             KeyValue::new("test.name", _test_name.to_string()),
@@ -574,9 +844,30 @@ impl CleanroomEnvironment {
     }
 
     /// Start a service by name
+    ///
+    /// The plugin's blocking `start()` and `ready_check()` run on the
+    /// blocking thread pool with the registry lock released, so concurrent
+    /// calls start services in parallel. A service that isn't ready within
+    /// [`DEFAULT_READY_TIMEOUT`] is stopped and the start fails.
     pub async fn start_service(&self, service_name: &str) -> Result<ServiceHandle> {
+        let plugin = {
+            let services = self.services.read().await;
+            services.get_plugin(service_name)?
+        };
+
+        let handle = tokio::task::spawn_blocking(move || start_until_ready(plugin.as_ref()))
+            .await
+            .map_err(|e| {
+                CleanroomError::internal_error(format!(
+                    "Service '{}' startup task failed",
+                    service_name
+                ))
+                .with_source(e.to_string())
+            })??;
+
         let mut services = self.services.write().await;
-        services.start_service(service_name).await
+        services.track_active_service(handle.clone());
+        Ok(handle)
     }
 
     /// Stop a service by handle ID
@@ -585,6 +876,14 @@ impl CleanroomEnvironment {
         services.stop_service(handle_id).await
     }
 
+    /// Leave a service's container running after this environment is dropped
+    ///
+    /// Returns the container ID if the service owns one.
+    pub async fn keep_service(&self, handle_id: &str) -> Result<Option<String>> {
+        let mut services = self.services.write().await;
+        services.keep_service(handle_id)
+    }
+
     /// Execute a command in a default test container and return full output
     ///
     /// # Arguments
@@ -594,10 +893,56 @@ impl CleanroomEnvironment {
     /// # Returns
     /// * `Result<std::process::Output>` - Command output with stdout, stderr, and exit status
     pub async fn execute_command_with_output(
+        &self,
+        handle: &ServiceHandle,
+        command_args: &[String],
+    ) -> Result<std::process::Output> {
+        self.execute_command_in_workdir(handle, command_args, None)
+            .await
+    }
+
+    /// Execute a command like [`Self::execute_command_with_output`], from
+    /// `workdir` if one is given
+    ///
+    /// Fails with a container error if `workdir` doesn't exist.
+    pub async fn execute_command_in_workdir(
         &self,
         _handle: &ServiceHandle,
         command_args: &[String],
+        workdir: Option<&str>,
     ) -> Result<std::process::Output> {
+        let run_result = self.run_command(command_args, workdir, None).await?;
+
+        // Convert RunResult to std::process::Output for compatibility
+        Ok(std::process::Output {
+            status: std::process::ExitStatus::from_raw(run_result.exit_code),
+            stdout: run_result.stdout.into_bytes(),
+            stderr: run_result.stderr.into_bytes(),
+        })
+    }
+
+    /// Execute a command like [`Self::execute_command_in_workdir`], writing
+    /// its stdout to `stdout_file` as it runs
+    ///
+    /// The result's `stdout` is empty; read the file instead. It keeps the
+    /// backend's exit code and OOM-kill state.
+    pub async fn execute_command_to_file(
+        &self,
+        _handle: &ServiceHandle,
+        command_args: &[String],
+        workdir: Option<&str>,
+        stdout_file: &Path,
+    ) -> Result<RunResult> {
+        self.run_command(command_args, workdir, Some(stdout_file))
+            .await
+    }
+
+    async fn run_command(
+        &self,
+        command_args: &[String],
+        workdir: Option<&str>,
+        stdout_file: Option<&Path>,
+    ) -> Result<RunResult> {
         if command_args.is_empty() {
             return Err(CleanroomError::validation_error(
                 "Command arguments cannot be empty",
@@ -609,10 +954,16 @@ impl CleanroomEnvironment {
         for arg in &command_args[1..] {
             cmd = cmd.arg(arg);
         }
+        if let Some(workdir) = workdir {
+            cmd = cmd.workdir(workdir.into());
+        }
+        if let Some(stdout_file) = stdout_file {
+            cmd = cmd.stdout_file(stdout_file);
+        }
 
         // Execute command in default test container using backend
         let backend = self.backend.clone();
-        let run_result = tokio::task::spawn_blocking(move || backend.run_cmd(cmd))
+        tokio::task::spawn_blocking(move || backend.run_cmd(cmd))
             .await
             .map_err(|e| {
                 CleanroomError::internal_error(format!("Failed to spawn backend execution: {}", e))
@@ -621,16 +972,7 @@ impl CleanroomEnvironment {
                 CleanroomError::container_error("Failed to execute command in container")
                     .with_context("Command execution failed in test container")
                     .with_source(e.to_string())
-            })?;
-
-        // Convert RunResult to std::process::Output for compatibility
-        let output = std::process::Output {
-            status: std::process::ExitStatus::from_raw(run_result.exit_code),
-            stdout: run_result.stdout.into_bytes(),
-            stderr: run_result.stderr.into_bytes(),
-        };
-
-        Ok(output)
+            })
     }
 
     /// Get service registry (read-only access)
@@ -700,10 +1042,100 @@ impl CleanroomEnvironment {
         self.services.read().await.check_all_health().await
     }
 
-    /// Get service logs
+    /// Get the last `lines` lines of a service's logs
     pub async fn get_service_logs(&self, service_id: &str, lines: usize) -> Result<Vec<String>> {
-        let services = self.services.read().await;
-        services.get_service_logs(service_id, lines).await
+        let handle = {
+            let services = self.services.read().await;
+            services
+                .active_services()
+                .get(service_id)
+                .cloned()
+                .ok_or_else(|| {
+                    CleanroomError::internal_error(format!(
+                        "Service with ID '{}' not found",
+                        service_id
+                    ))
+                })?
+        };
+
+        Ok(self.service_logs(&handle).await?.tail(lines))
+    }
+
+    /// Stream a service's logs like `docker logs -f`
+    ///
+    /// Calls `on_line` with the last `lines` lines, then polls the service's
+    /// logs every `poll_interval` and calls it for each line written since.
+    /// Runs until the logs can't be read (e.g. the service stopped); drop the
+    /// future to stop following.
+    pub async fn follow_service_logs<F>(
+        &self,
+        handle: &ServiceHandle,
+        lines: usize,
+        poll_interval: Duration,
+        mut on_line: F,
+    ) -> Result<()>
+    where
+        F: FnMut(&str),
+    {
+        let logs = self.service_logs(handle).await?;
+        for line in logs.tail(lines) {
+            on_line(&line);
+        }
+
+        let mut cursor = LogCursor::at_end(&logs);
+        loop {
+            tokio::time::sleep(poll_interval).await;
+            let logs = self.service_logs(handle).await?;
+            for line in cursor.advance(&logs) {
+                on_line(&line);
+            }
+        }
+    }
+
+    /// Read a service's container output
+    ///
+    /// Like [`Self::start_service`], the plugin's blocking `logs()` runs on
+    /// the blocking thread pool with the registry lock released.
+    pub async fn service_logs(&self, handle: &ServiceHandle) -> Result<ServiceLogs> {
+        let plugin = {
+            let services = self.services.read().await;
+            services.get_plugin(&handle.service_name)?
+        };
+
+        let task_handle = handle.clone();
+        tokio::task::spawn_blocking(move || plugin.logs(&task_handle))
+            .await
+            .map_err(|e| {
+                CleanroomError::internal_error(format!(
+                    "Service '{}' log capture task failed",
+                    handle.service_name
+                ))
+                .with_source(e.to_string())
+            })?
+    }
+
+    /// Run a SQL query against a database service
+    pub async fn query_service(
+        &self,
+        handle: &ServiceHandle,
+        sql: &str,
+    ) -> Result<Vec<Vec<String>>> {
+        let plugin = {
+            let services = self.services.read().await;
+            services.get_plugin(&handle.service_name)?
+        };
+
+        let task_handle = handle.clone();
+        let task_sql = sql.to_string();
+        tokio::task::spawn_blocking(move || plugin.query(&task_handle, &task_sql))
+            .await
+            .map_err(|e| {
+                CleanroomError::internal_error(format!(
+                    "Service '{}' query task failed",
+                    handle.service_name
+                ))
+                .with_source(e.to_string())
+            })?
     }
 
     /// Get session ID
@@ -716,6 +1148,37 @@ impl CleanroomEnvironment {
         self.backend.as_ref() as &dyn Backend
     }
 
+    /// Behavior coverage recorded in this environment
+    ///
+    /// Scenarios that collect spans record the span names and the
+    /// integrations those spans exercise here.
+    pub fn coverage(&self) -> &CoverageTracker {
+        &self.coverage
+    }
+
+    /// Run command containers on `network`, or on the default bridge for `None`
+    ///
+    /// Services started afterwards that support it join the network too.
+    pub async fn set_network(&self, network: Option<String>) {
+        *self.network.write().await = network;
+    }
+
+    /// Docker network command containers run on, if not the default bridge
+    pub async fn network(&self) -> Option<String> {
+        self.network.read().await.clone()
+    }
+
+    /// Emit the spans of tests and commands run here through `provider`
+    /// instead of the global tracer provider
+    pub async fn set_tracer_provider(&self, provider: Option<SdkTracerProvider>) {
+        *self.tracer_provider.write().await = provider;
+    }
+
+    /// Tracer from this environment's tracer provider
+    async fn tracer(&self, name: &'static str) -> BoxedTracer {
+        crate::telemetry::test_run::tracer(self.tracer_provider.read().await.as_ref(), name)
+    }
+
     /// Execute a command in a container with proper error handling and observability
     /// Core Team Compliance: Async for I/O operations, proper error handling, no unwrap/expect
     ///
@@ -726,10 +1189,42 @@ impl CleanroomEnvironment {
         container_name: &str,
         command: &[String],
     ) -> Result<ExecutionResult> {
-        let tracer_provider = global::tracer_provider();
-        let mut span = tracer_provider
-            .tracer("clnrm-cleanroom")
-            .start(format!("container.exec.{}", container_name));
+        self.execute_in_container_with_timeout(container_name, command, None)
+            .await
+    }
+
+    /// Execute a command in a container, failing if it runs longer than `timeout`
+    ///
+    /// On timeout the backend kills the command's container and this returns
+    /// a timeout error. A backend that doesn't stop the command within
+    /// [`TIMEOUT_GRACE`] of the limit is abandoned with a timeout error too.
+    pub async fn execute_in_container_with_timeout(
+        &self,
+        container_name: &str,
+        command: &[String],
+        timeout: Option<Duration>,
+    ) -> Result<ExecutionResult> {
+        self.execute_in_container_with_env(container_name, command, &HashMap::new(), None, timeout)
+            .await
+    }
+
+    /// Execute a command in a container with extra environment variables,
+    /// failing if it runs longer than `timeout`
+    ///
+    /// With a `workdir` the command runs from that directory, and fails with
+    /// a container error if it doesn't exist.
+    pub async fn execute_in_container_with_env(
+        &self,
+        container_name: &str,
+        command: &[String],
+        env: &HashMap<String, String>,
+        workdir: Option<&str>,
+        timeout: Option<Duration>,
+    ) -> Result<ExecutionResult> {
+        let tracer_provider = self.tracer_provider.read().await.clone();
+        let mut span =
+            crate::telemetry::test_run::tracer(tracer_provider.as_ref(), "clnrm-cleanroom")
+                .start(format!("container.exec.{}", container_name));
         span.set_attributes(vec![
             KeyValue::new("container.name", container_name.to_string()),
             KeyValue::new("command", command.join(" ")),
@@ -740,16 +1235,57 @@ impl CleanroomEnvironment {
 
         // Execute command using backend - this creates a fresh container for each command
         // This provides maximum isolation and is appropriate for testing scenarios
-        let cmd = Cmd::new("sh")
+        let mut cmd = Cmd::new("sh")
             .arg("-c")
             .arg(command.join(" "))
             .env("CONTAINER_NAME", container_name);
+        for (key, value) in env {
+            cmd = cmd.env(key, value);
+        }
+        if let Some(workdir) = workdir {
+            cmd = cmd.workdir(workdir.into());
+        }
+        if let Some(timeout) = timeout {
+            cmd = cmd.timeout(timeout);
+        }
+        if let Some(network) = self.network().await {
+            cmd = cmd.network(network);
+        }
+        if let Some(tracer_provider) = tracer_provider {
+            cmd = cmd.tracer_provider(tracer_provider);
+        }
 
         // Use spawn_blocking to avoid runtime conflicts with testcontainers
         // Clone the backend to move it into the blocking task
         let backend = self.backend.clone();
-        let execution_result = tokio::task::spawn_blocking(move || backend.run_cmd(cmd))
-            .await
+        let task = tokio::task::spawn_blocking(move || backend.run_cmd(cmd));
+        let joined = match timeout {
+            // The backend enforces the timeout itself, so this guard only
+            // catches a backend that failed to stop the command
+            Some(timeout) => match tokio::time::timeout(timeout + TIMEOUT_GRACE, task).await {
+                Ok(joined) => joined,
+                Err(_) => {
+                    span.set_attribute(KeyValue::new(
+                        "execution.failure_reason",
+                        FailureReason::Timeout.as_str(),
+                    ));
+                    span.set_status(opentelemetry::trace::Status::error("Command timed out"));
+                    span.end();
+                    return Err(CleanroomError::timeout_error(format!(
+                        "Command timed out after {}ms (limit: {}ms)",
+                        start_time.elapsed().as_millis(),
+                        timeout.as_millis()
+                    ))
+                    .with_context(format!(
+                        "Container: {}, Command: {}",
+                        container_name,
+                        command.join(" ")
+                    )));
+                }
+            },
+            None => task.await,
+        };
+        let execution_result = joined
             .map_err(|e| {
                 {
                     span.set_status(opentelemetry::trace::Status::error("Task join failed"));
@@ -760,13 +1296,27 @@ impl CleanroomEnvironment {
                     .with_source(e.to_string())
             })?
             .map_err(|e| {
+                let failure_reason = FailureReason::from_error(&e);
                 {
+                    if let Some(reason) = failure_reason {
+                        span.set_attribute(KeyValue::new(
+                            "execution.failure_reason",
+                            reason.as_str(),
+                        ));
+                    }
                     span.set_status(opentelemetry::trace::Status::error(
                         "Command execution failed",
                     ));
                     span.end();
                 }
-                CleanroomError::container_error("Failed to execute command in container")
+                // A backend timeout stays a timeout so callers can tell it apart
+                let error = match failure_reason {
+                    Some(FailureReason::Timeout) => {
+                        CleanroomError::timeout_error("Command timed out in container")
+                    }
+                    _ => CleanroomError::container_error("Failed to execute command in container"),
+                };
+                error
                     .with_context(format!(
                         "Container: {}, Command: {}",
                         container_name,
@@ -801,7 +1351,10 @@ impl CleanroomEnvironment {
             KeyValue::new("execution.duration_ms", duration.as_millis().to_string()),
         ]);
 
-        if execution_result.exit_code != 0 {
+        if let Some(reason) =
+            FailureReason::from_exit(execution_result.exit_code, execution_result.oom_killed)
+        {
+            span.set_attribute(KeyValue::new("execution.failure_reason", reason.as_str()));
             span.set_status(opentelemetry::trace::Status::error("Command failed"));
         }
 
@@ -809,6 +1362,7 @@ impl CleanroomEnvironment {
 
         Ok(ExecutionResult {
             exit_code: execution_result.exit_code,
+            oom_killed: execution_result.oom_killed,
             stdout: execution_result.stdout,
             stderr: execution_result.stderr,
             duration,
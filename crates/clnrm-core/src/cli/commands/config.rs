@@ -0,0 +1,55 @@
+//! Config command implementation
+//!
+//! Merges layered test configurations, such as a shared base file plus
+//! per-environment overrides.
+
+use crate::error::{CleanroomError, Result};
+use clnrm_template::toml::MergeStrategy;
+use clnrm_template::TomlMerger;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// Deep-merge `files` in order into one TOML document
+///
+/// Later files win on scalars and plain arrays. Tables are merged key by
+/// key, and arrays of named tables such as `[[scenario]]` are merged by
+/// `name` (see [`TomlMerger::merge`]).
+pub fn merge_config_files(files: &[PathBuf]) -> Result<String> {
+    let merger = TomlMerger::new().with_strategy(MergeStrategy::DeepMerge);
+    let mut merged: Option<serde_json::Value> = None;
+
+    for file in files {
+        let content = std::fs::read_to_string(file).map_err(|e| {
+            CleanroomError::io_error(format!("Failed to read {}: {}", file.display(), e))
+        })?;
+        let layer: serde_json::Value = toml::from_str(&content).map_err(|e| {
+            CleanroomError::config_error(format!("Failed to parse {}: {}", file.display(), e))
+        })?;
+
+        merged = Some(match merged {
+            Some(base) => merger.merge(&base, &layer)?,
+            None => layer,
+        });
+    }
+
+    let merged =
+        merged.ok_or_else(|| CleanroomError::validation_error("No config files to merge"))?;
+    toml::to_string_pretty(&merged).map_err(|e| {
+        CleanroomError::serialization_error(format!("Failed to serialize merged config: {}", e))
+    })
+}
+
+/// Merge `files` and write the result to `output`, or print it to stdout
+pub fn merge_configs(files: &[PathBuf], output: Option<&Path>) -> Result<()> {
+    let merged = merge_config_files(files)?;
+
+    if let Some(out) = output {
+        std::fs::write(out, merged)
+            .map_err(|e| CleanroomError::io_error(format!("Failed to write output: {}", e)))?;
+        info!("✓ Merged {} config(s) into {}", files.len(), out.display());
+    } else {
+        print!("{}", merged);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Explain command implementation
+//!
+//! Prints the description and remediation hint for a stable error code
+//! such as `CLNRM-V010`, or the full catalog when no code is given.
+
+use crate::error::{CleanroomError, ErrorCode, Result};
+
+/// Longer explanation of an error code
+pub struct Explanation {
+    /// Short title
+    pub title: &'static str,
+    /// What the error means
+    pub description: &'static str,
+    /// How to fix it
+    pub remediation: &'static str,
+}
+
+/// Look up the explanation for an error code
+pub fn explanation(code: ErrorCode) -> Explanation {
+    match code {
+        ErrorCode::TomlParse => Explanation {
+            title: "TOML parse error",
+            description: "The test file (after template rendering) is not valid TOML, or a \
+                          table or key does not match the test schema.",
+            remediation: "Check the line and column in the message. Run `clnrm render` on \
+                          templated files to see the TOML that was parsed, and `clnrm validate` \
+                          to check the schema.",
+        },
+        ErrorCode::YamlParse => Explanation {
+            title: "YAML parse error",
+            description: "The `.clnrm.yaml` test file is not valid YAML, or its structure does \
+                          not match the test schema.",
+            remediation: "Check the line and column in the message; indentation and unquoted \
+                          special characters are the usual causes.",
+        },
+        ErrorCode::TemplateRender => Explanation {
+            title: "Template rendering failed",
+            description: "Tera could not render the test template: an undefined variable, an \
+                          unknown function or filter, a missing `{% include %}` target, or a \
+                          syntax error.",
+            remediation: "Read the cause chain at the end of the message. Run `clnrm render \
+                          <file> --show-vars` to see which variables are available.",
+        },
+        ErrorCode::InvalidConfig => Explanation {
+            title: "Invalid test configuration",
+            description: "The file parsed, but the configuration is inconsistent, e.g. no test \
+                          name, no steps, or a step referencing an undefined service.",
+            remediation: "Fix the field named in the message, then run `clnrm validate <file>`.",
+        },
+        ErrorCode::SpanNotFound => Explanation {
+            title: "Expected span not found",
+            description: "An expectation refers to a span name that does not appear in the \
+                          collected trace.",
+            remediation: "Check the span name for typos and that the code path emitting it ran. \
+                          Use `clnrm spans <trace>` to list the spans that were captured.",
+        },
+        ErrorCode::SpanCountMismatch => Explanation {
+            title: "Span count mismatch",
+            description: "The number of spans, events or errors in the trace is outside the \
+                          bounds set in `[expect.counts]`.",
+            remediation: "Compare the expected and found counts in the message. If the new \
+                          count is correct, update `eq`/`gte`/`lte` in `[expect.counts]`; \
+                          otherwise look for duplicated or skipped operations.",
+        },
+        ErrorCode::SpanOrder => Explanation {
+            title: "Span order violation",
+            description: "Two spans named in `[expect.order]` did not occur in the required \
+                          sequence.",
+            remediation: "Check whether the operations run concurrently. Relax the constraint \
+                          or make the code await the first operation before starting the second.",
+        },
+        ErrorCode::SpanWindow => Explanation {
+            title: "Span outside its time window",
+            description: "A span listed in an `[[expect.window]]` started before or ended after \
+                          the outer span that should contain it.",
+            remediation: "Make sure the child work is awaited inside the outer span, or remove \
+                          the child from the window if it is intentionally detached.",
+        },
+        ErrorCode::SpanAttribute => Explanation {
+            title: "Span attribute mismatch",
+            description: "A span's attribute is missing or its value does not match the \
+                          expectation.",
+            remediation: "Compare the expected and found values in the message and check the \
+                          attribute key, including its namespace.",
+        },
+        ErrorCode::SpanStatus => Explanation {
+            title: "Span status mismatch",
+            description: "A span's status (UNSET, OK or ERROR) differs from `[expect.status]`.",
+            remediation: "An unexpected ERROR usually means the operation failed; check the \
+                          step output. An unexpected UNSET means the code never sets a status.",
+        },
+        ErrorCode::SpanDuration => Explanation {
+            title: "Span duration out of bounds",
+            description: "A span took longer or shorter than the bounds in its duration \
+                          expectation, or has no timing data to check: its timestamps are \
+                          missing, end before they start, or came from a frozen clock.",
+            remediation: "Check for slow dependencies or cold starts. Widen the bounds if the \
+                          new timing is expected on this machine. Spans without timing data \
+                          need real start and end timestamps from the exporter.",
+        },
+        ErrorCode::SpanEvent => Explanation {
+            title: "Span event missing",
+            description: "A span in `[[expect.event]]` did not emit the named event, or the \
+                          event's attributes do not match.",
+            remediation: "Check that the code records the event on that span (not a child) and \
+                          that the exporter includes event attributes.",
+        },
+        ErrorCode::SpanGraph => Explanation {
+            title: "Span graph mismatch",
+            description: "Parent/child relationships in the trace do not match `[expect.graph]`: \
+                          a required edge is missing, a forbidden edge is present, or the spans \
+                          form a cycle.",
+            remediation: "Check that context is propagated so children attach to the right \
+                          parent. Use `clnrm graph <trace>` to inspect the actual tree.",
+        },
+        ErrorCode::Hermeticity => Explanation {
+            title: "Hermeticity violation",
+            description: "Span attributes show the test reached external services or ran with \
+                          unexpected resource attributes, so its result may depend on the \
+                          environment.",
+            remediation: "Replace the external dependency with a service in the test file, or \
+                          adjust `[expect.hermeticity]` if the access is intended.",
+        },
+        ErrorCode::MetricNotFound => Explanation {
+            title: "Expected metric not found",
+            description: "A metric in `[[expect.metric]]` was not exported, or none of its data \
+                          points have the expected attributes.",
+            remediation: "Check the metric name and attributes, that the scenario collects \
+                          `metrics:otlp` or `metrics:file:<path>`, and that the meter provider \
+                          is flushed before the process exits.",
+        },
+        ErrorCode::MetricValue => Explanation {
+            title: "Metric value mismatch",
+            description: "A metric in `[[expect.metric]]` was exported, but its value or \
+                          histogram sample count is outside the expected bounds.",
+            remediation: "Compare the expected value with what the scenario should record. \
+                          Counters are totalled across matching series; narrow them with \
+                          `attributes`.",
+        },
+    }
+}
+
+/// Print the explanation for `code`, or every code when `code` is `None`
+pub fn explain_error_code(code: Option<&str>) -> Result<()> {
+    let Some(code) = code else {
+        println!("Error codes (run `clnrm explain <code>` for details):");
+        for known in ErrorCode::ALL {
+            println!("  {}  {}", known, explanation(*known).title);
+        }
+        return Ok(());
+    };
+
+    let error_code = ErrorCode::parse(code).ok_or_else(|| {
+        CleanroomError::validation_error(format!("Unknown error code: {}", code))
+            .with_context("Run `clnrm explain` to list all error codes")
+    })?;
+    let explanation = explanation(error_code);
+
+    println!("{}: {}", error_code, explanation.title);
+    println!();
+    println!("{}", explanation.description);
+    println!();
+    println!("How to fix:");
+    println!("  {}", explanation.remediation);
+
+    Ok(())
+}
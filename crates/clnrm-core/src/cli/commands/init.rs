@@ -3,6 +3,7 @@
 //! Handles project initialization with template generation and directory
 //! structure creation.
 
+use crate::cli::types::InitTemplate;
 use crate::error::{CleanroomError, Result};
 
 /// Initialize a new test project in the current directory
@@ -118,3 +119,135 @@ version = "0.1.0"
 
     Ok(())
 }
+
+/// Scaffold `tests/<service>.clnrm.toml` with a service, a scenario that
+/// exercises it, and span expectations
+pub fn init_from_template(template: InitTemplate, force: bool) -> Result<()> {
+    let (service, content) = match template {
+        InitTemplate::Postgres => ("postgres", POSTGRES_TEMPLATE),
+        InitTemplate::Redis => ("redis", REDIS_TEMPLATE),
+        InitTemplate::Http => ("http", HTTP_TEMPLATE),
+    };
+
+    let tests_dir = std::path::Path::new("tests");
+    let test_file = tests_dir.join(format!("{}.clnrm.toml", service));
+
+    if test_file.exists() && !force {
+        return Err(CleanroomError::validation_error(format!(
+            "{} already exists",
+            test_file.display()
+        ))
+        .with_context("Use --force to overwrite"));
+    }
+
+    std::fs::create_dir_all(tests_dir)?;
+    std::fs::write(&test_file, content)?;
+
+    println!("✅ Created {} test: {}", service, test_file.display());
+    println!("🔍 Validate: clnrm dry-run {}", test_file.display());
+    println!("🚀 Run:      clnrm run {}", test_file.display());
+
+    Ok(())
+}
+
+const POSTGRES_TEMPLATE: &str = r##"# PostgreSQL Service Test
+# Generated by clnrm init --template postgres
+#
+# The scenario queries the database with psql and prints a span built from
+# the server's reply as a JSON line on stdout, so the expectations only pass
+# if the query really ran. Replace `run` with your instrumented client to
+# validate real database traffic.
+
+[meta]
+name = "postgres_test"
+version = "1.0"
+description = "PostgreSQL service with a query scenario and span expectations"
+
+[service.postgres]
+plugin = "postgres"
+image = "postgres:16-alpine"
+database = "app"
+username = "app"
+password = "app"
+
+[[scenario]]
+name = "query_postgres"
+service = "postgres"
+run = '''sh -c 'trace=$(tr -d - < /proc/sys/kernel/random/uuid) && db=$(psql -U app -d app -tAc "select current_database()") && echo "{\"name\":\"db.query\",\"trace_id\":\"$trace\",\"span_id\":\"$(echo $trace | cut -c1-16)\",\"kind\":\"client\",\"attributes\":{\"db.system\":\"postgresql\",\"db.name\":\"$db\",\"db.statement\":\"select current_database()\"}}"' '''
+artifacts.collect = ["spans:stdout"]
+
+[[expect.span]]
+name = "db.query"
+kind = "client"
+attrs.all = { "db.system" = "postgresql", "db.name" = "app" }
+
+[expect.counts]
+spans_total = { gte = 1 }
+"##;
+
+const REDIS_TEMPLATE: &str = r##"# Redis Service Test
+# Generated by clnrm init --template redis
+#
+# The scenario writes a key and reads it back with redis-cli, printing a
+# span per command with the server's reply as JSON lines on stdout. Replace
+# `run` with your instrumented client to validate real cache traffic.
+
+[meta]
+name = "redis_test"
+version = "1.0"
+description = "Redis service with a cache scenario and span expectations"
+
+[service.redis]
+plugin = "generic_container"
+image = "redis:7-alpine"
+ports = [6379]
+
+[[scenario]]
+name = "cache_roundtrip"
+service = "redis"
+run = '''sh -c 'trace=$(tr -d - < /proc/sys/kernel/random/uuid) && set=$(redis-cli set clnrm:greeting hello) && get=$(redis-cli get clnrm:greeting) && echo "{\"name\":\"redis.set\",\"trace_id\":\"$trace\",\"span_id\":\"$(echo $trace | cut -c1-16)\",\"kind\":\"client\",\"attributes\":{\"db.system\":\"redis\",\"redis.reply\":\"$set\"}}" && echo "{\"name\":\"redis.get\",\"trace_id\":\"$trace\",\"span_id\":\"$(echo $trace | cut -c17-32)\",\"kind\":\"client\",\"attributes\":{\"db.system\":\"redis\",\"redis.reply\":\"$get\"}}"' '''
+artifacts.collect = ["spans:stdout"]
+
+[[expect.span]]
+name = "redis.set"
+kind = "client"
+attrs.all = { "db.system" = "redis", "redis.reply" = "OK" }
+
+[[expect.span]]
+name = "redis.get"
+kind = "client"
+attrs.all = { "db.system" = "redis", "redis.reply" = "hello" }
+
+[expect.counts]
+spans_total = { eq = 2 }
+"##;
+
+const HTTP_TEMPLATE: &str = r##"# HTTP Service Test
+# Generated by clnrm init --template http
+#
+# The scenario sends a request with wget and prints a span built from the
+# response status and the path the echo service saw as a JSON line on
+# stdout. Replace `run` with your instrumented client to validate real HTTP
+# traffic.
+
+[meta]
+name = "http_test"
+version = "1.0"
+description = "HTTP echo service with a request scenario and span expectations"
+
+[service.http]
+plugin = "generic_container"
+image = "mendhak/http-https-echo:31"
+ports = [8080]
+
+[[scenario]]
+name = "get_request"
+service = "http"
+run = '''sh -c 'trace=$(tr -d - < /proc/sys/kernel/random/uuid) && status=$(wget -S -O /dev/null http://localhost:8080/clnrm 2>&1 | grep -o "HTTP/[0-9.]* [0-9]*" | tail -n 1 | cut -d" " -f2) && path=$(wget -q -O - http://localhost:8080/clnrm | grep -o "\"path\":\"[^\"]*\"" | cut -d\" -f4) && echo "{\"name\":\"GET $path\",\"trace_id\":\"$trace\",\"span_id\":\"$(echo $trace | cut -c1-16)\",\"kind\":\"client\",\"attributes\":{\"http.request.method\":\"GET\",\"url.path\":\"$path\",\"http.response.status_code\":\"$status\"}}"' '''
+artifacts.collect = ["spans:stdout"]
+
+[[expect.span]]
+name = "GET /clnrm"
+kind = "client"
+attrs.all = { "http.request.method" = "GET", "url.path" = "/clnrm", "http.response.status_code" = "200" }
+"##;
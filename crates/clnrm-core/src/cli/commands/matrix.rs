@@ -0,0 +1,40 @@
+//! Matrix command implementation
+//!
+//! Previews the concrete test cases a test's `[matrix]` expands to, without
+//! starting any containers.
+
+use crate::config::{expand_matrix, load_config_from_file, matrix_combinations};
+use crate::error::Result;
+use std::path::Path;
+
+/// Print the name of every case `file`'s matrix expands to
+pub fn list_matrix_cases(file: &Path) -> Result<()> {
+    let config = load_config_from_file(file)?;
+    let Some(ref matrix) = config.matrix else {
+        println!(
+            "ℹ️  {} has no [matrix] section; it runs as a single test",
+            file.display()
+        );
+        return Ok(());
+    };
+
+    let mut dimensions: Vec<_> = matrix
+        .iter()
+        .map(|(name, values)| format!("{} ({})", name, values.len()))
+        .collect();
+    dimensions.sort();
+    let combinations = matrix_combinations(matrix)?;
+    let cases = expand_matrix(&config)?;
+
+    println!(
+        "📊 {}: matrix expands to {} case(s) across {}",
+        file.display(),
+        combinations.len(),
+        dimensions.join(" × ")
+    );
+    for case in &cases {
+        println!("  {}", case.get_name()?);
+    }
+
+    Ok(())
+}
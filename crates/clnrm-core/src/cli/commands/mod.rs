@@ -2,15 +2,16 @@
 //!
 //! Exports all CLI command implementations with their associated functionality.
 
-pub mod collector_noun_verb;
+pub mod config;
+pub mod explain;
 pub mod health;
 pub mod init;
+pub mod matrix;
 pub mod plugins;
 pub mod report;
 pub mod run;
 pub mod self_test;
 pub mod services;
-pub mod services_noun_verb;
 pub mod template;
 pub mod v0_7_0;
 pub mod validate;
@@ -21,20 +22,31 @@ pub use run::{
     run_tests_sequential_with_results, run_tests_with_shard,
 };
 
-pub use init::init_project;
+pub use explain::explain_error_code;
+
+pub use init::{init_from_template, init_project};
+
+pub use config::merge_configs;
+pub use matrix::list_matrix_cases;
 pub use template::{
     generate_deterministic_template, generate_from_template, generate_full_validation_template,
     generate_lifecycle_matcher, generate_macro_library, generate_matrix_template,
     generate_otel_template,
 };
 
-pub use validate::{validate_config, validate_single_config};
+pub use validate::{print_config_schema, validate_config, validate_single_config};
 
 pub use plugins::list_plugins;
 
-pub use services::{ai_manage, restart_service, show_service_logs, show_service_status};
+pub use services::{
+    ai_manage, exec_in_service, restart_service, services_down, show_service_logs,
+    show_service_status,
+};
 
-pub use report::{display_test_results, generate_framework_report, generate_report};
+pub use report::{
+    compare_coverage, display_test_results, generate_coverage_report, generate_framework_report,
+    generate_report,
+};
 
 pub use self_test::run_self_tests;
 
@@ -42,16 +54,18 @@ pub use health::system_health_check;
 
 // Re-export v0.7.0 commands
 pub use v0_7_0::dev::{run_dev_mode, run_dev_mode_with_filters};
-pub use v0_7_0::diff::diff_traces;
+pub use v0_7_0::diff::{diff_trace_dirs, diff_traces, DurationThreshold};
 pub use v0_7_0::dry_run::{dry_run_validate, ValidationResult as DryRunValidationResult};
-pub use v0_7_0::fmt::format_files;
+pub use v0_7_0::fmt::{format_files, format_stdin, format_stream};
 pub use v0_7_0::graph::visualize_graph;
 pub use v0_7_0::lint::lint_files;
 pub use v0_7_0::record::run_record;
+pub use v0_7_0::repro::reproduce_baselines;
+pub use v0_7_0::verify_digest::verify_digest;
 
 // Re-export PRD v1.0 additional commands (stubs)
 pub use v0_7_0::prd_commands::{
-    filter_spans, pull_images, render_template_with_vars, reproduce_baseline,
-    run_red_green_validation, show_collector_logs, show_collector_status, start_collector,
-    stop_collector,
+    check_images, filter_spans, pull_images, render_template_dir, render_template_with_vars,
+    reproduce_baseline, run_red_green_validation, show_collector_logs, show_collector_status,
+    start_collector, stop_collector,
 };
@@ -12,6 +12,8 @@ pub fn list_plugins() -> Result<()> {
     // List core plugins
     println!("✅ generic_container (alpine, ubuntu, debian)");
     println!("✅ surreal_db (database integration)");
+    println!("✅ postgres (PostgreSQL with init SQL migrations)");
+    println!("✅ web_server (HTTP echo endpoint or static directory)");
     println!("✅ network_tools (curl, wget, netcat)");
 
     // List AI/LLM proxy plugins for automated rollout
@@ -3,10 +3,16 @@
 //! Handles test report generation in various formats with comprehensive
 //! error handling and file I/O operations.
 
+use crate::coverage::manifest::BehaviorManifest;
+use crate::coverage::report::{ReportFormat, ReportGenerator};
+use crate::coverage::tracker::CoverageTracker;
+use crate::coverage::BehaviorCoverage;
 use crate::error::{CleanroomError, Result};
 use crate::testing::FrameworkTestResults;
-use std::path::PathBuf;
+use crate::validation::span_validator::SpanValidator;
+use std::path::{Path, PathBuf};
 use tracing::info;
+use walkdir::WalkDir;
 
 /// Generate test reports
 pub async fn generate_report(
@@ -71,6 +77,175 @@ pub async fn generate_report(
     Ok(())
 }
 
+/// Generate a behavior coverage report
+///
+/// The snapshot is scored against `manifest` and rendered as `html`,
+/// `markdown` or `json` to `output` (or stdout).
+pub async fn generate_coverage_report(
+    coverage: &Path,
+    manifest: &Path,
+    output: Option<&PathBuf>,
+    format: &str,
+) -> Result<()> {
+    let manifest = BehaviorManifest::load(manifest)?;
+    let report = load_coverage_tracker(coverage)?.report(&manifest).await?;
+    let content = ReportGenerator::generate(&report, ReportFormat::from_str(format)?)?;
+
+    if let Some(output_path) = output {
+        tokio::fs::write(output_path, content).await.map_err(|e| {
+            CleanroomError::internal_error("Failed to write output file")
+                .with_context(format!("Output file: {}", output_path.display()))
+                .with_source(e.to_string())
+        })?;
+        info!("Coverage report generated: {}", output_path.display());
+    } else {
+        println!("{}", content);
+    }
+
+    Ok(())
+}
+
+/// Compare behavior coverage against a baseline snapshot
+///
+/// Both snapshots are scored against `manifest`. The diff is written to
+/// `output` (or stdout) as text, or as JSON when `format` is `json`.
+///
+/// # Errors
+/// Returns a validation error if overall coverage dropped by more than
+/// `max_drop` percentage points.
+pub async fn compare_coverage(
+    baseline: &Path,
+    current: &Path,
+    manifest: &Path,
+    max_drop: f64,
+    output: Option<&PathBuf>,
+    format: &str,
+) -> Result<()> {
+    let manifest = BehaviorManifest::load(manifest)?;
+    let previous = load_coverage_tracker(baseline)?.report(&manifest).await?;
+    let report = load_coverage_tracker(current)?.report(&manifest).await?;
+    let diff = report.diff(&previous);
+
+    let content = if format == "json" {
+        serde_json::to_string_pretty(&diff).map_err(|e| {
+            CleanroomError::internal_error("JSON serialization failed")
+                .with_context("Failed to serialize coverage diff to JSON")
+                .with_source(e.to_string())
+        })?
+    } else {
+        diff.format_text()
+    };
+
+    if let Some(output_path) = output {
+        tokio::fs::write(output_path, content).await.map_err(|e| {
+            CleanroomError::internal_error("Failed to write output file")
+                .with_context(format!("Output file: {}", output_path.display()))
+                .with_source(e.to_string())
+        })?;
+        info!("Coverage diff written: {}", output_path.display());
+    } else {
+        println!("{}", content);
+    }
+
+    if diff.exceeds_drop(max_drop) {
+        return Err(CleanroomError::validation_error(format!(
+            "Coverage dropped from {:.1}% to {:.1}% ({:.1} points), more than the allowed {:.1}",
+            diff.previous_coverage,
+            diff.current_coverage,
+            -diff.coverage_delta(),
+            max_drop
+        )));
+    }
+
+    Ok(())
+}
+
+/// Check that recorded traces cover every behavior a manifest declares
+///
+/// `traces` is a spans JSON file or a directory searched for `*.json`
+/// files. Span names, API endpoints served and integrations called are
+/// matched against `manifest`, and every uncovered behavior is listed.
+///
+/// # Errors
+/// Returns a validation error if any declared behavior is uncovered.
+pub fn check_coverage(manifest: &Path, traces: &Path) -> Result<()> {
+    let manifest = BehaviorManifest::load(manifest)?;
+    let trace_files = discover_trace_files(traces);
+    if trace_files.is_empty() {
+        return Err(CleanroomError::validation_error(format!(
+            "No trace files found in {}",
+            traces.display()
+        )));
+    }
+
+    let mut coverage = BehaviorCoverage::new();
+    for file in &trace_files {
+        coverage.record_spans(SpanValidator::from_file(file)?.spans());
+    }
+    let report = manifest.calculate_coverage(&coverage)?;
+    let uncovered = &report.uncovered_behaviors;
+
+    if uncovered.is_empty() {
+        println!(
+            "✅ All {} behaviors declared by {} are covered ({} trace file(s))",
+            report.total_behaviors,
+            manifest.system.name,
+            trace_files.len()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "❌ {} of {} behaviors declared by {} are not covered:",
+        uncovered.count(),
+        report.total_behaviors,
+        manifest.system.name
+    );
+    for (dimension, behaviors) in uncovered.by_dimension() {
+        for behavior in behaviors {
+            println!("  {}: {}", dimension, behavior);
+        }
+    }
+
+    Err(CleanroomError::validation_error(format!(
+        "{} declared behavior(s) not covered by traces in {}",
+        uncovered.count(),
+        traces.display()
+    )))
+}
+
+/// Trace files at `path`: the file itself, or the `*.json` files under a
+/// directory, sorted by path
+fn discover_trace_files(path: &Path) -> Vec<PathBuf> {
+    if path.is_file() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = WalkDir::new(path)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Load a coverage snapshot weighted by the project's `[coverage.weights]`
+///
+/// The project's cleanroom.toml is looked up from the current directory
+/// upwards; without one the default weights apply.
+fn load_coverage_tracker(path: &Path) -> Result<CoverageTracker> {
+    let current_dir = std::env::current_dir()
+        .map_err(|e| CleanroomError::io_error(format!("Failed to get current directory: {}", e)))?;
+    let weights = match crate::config::find_project_config(&current_dir) {
+        Some(config) => crate::config::load_coverage_config(config)?.weights,
+        None => Default::default(),
+    };
+    Ok(CoverageTracker::load_snapshot(path)?.with_weights(weights))
+}
+
 /// Generate HTML report
 fn generate_html_report(results: &FrameworkTestResults) -> Result<String> {
     let mut html = String::new();
@@ -1,20 +1,25 @@
 //! Cache management for test execution
 
 use crate::cache::{Cache, CacheManager};
-use crate::cli::types::CliTestResult;
+use crate::cli::types::{CliConfig, CliTestResult};
+use crate::config::parse_config_for_path;
 use crate::error::{CleanroomError, Result};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Filter tests that have changed since last cache update
 ///
-/// Returns only test files whose raw content has changed.
+/// Returns only test files whose raw content, any container image they
+/// resolve to, or the `--seed` override has changed. The default step image
+/// comes from `config.cleanroom`, or cleanroom.toml when that isn't set.
 /// Note: We use raw content for caching, not rendered templates, because
 /// template rendering requires vars from the parsed TOML (chicken-and-egg problem).
 pub async fn filter_changed_tests(
     test_files: &[PathBuf],
     cache_manager: &CacheManager,
+    config: &CliConfig,
 ) -> Result<Vec<PathBuf>> {
     let mut changed_tests = Vec::new();
+    let default_image = configured_default_image(config);
 
     for test_file in test_files {
         // Read raw file content (don't render templates)
@@ -26,8 +31,9 @@ pub async fn filter_changed_tests(
             ))
         })?;
 
-        // Check if file has changed based on raw content
-        if cache_manager.has_changed(test_file, &content)? {
+        // Check if file has changed based on raw content and resolved images
+        let cache_input = cache_input(test_file, &content, default_image.as_deref(), config.seed);
+        if cache_manager.has_changed(test_file, &cache_input)? {
             changed_tests.push(test_file.clone());
         }
     }
@@ -37,21 +43,26 @@ pub async fn filter_changed_tests(
 
 /// Update cache for test results
 ///
-/// Updates cache hashes for successfully executed tests using raw content.
+/// Updates cache hashes for successfully executed tests using raw content
+/// and resolved images, and records every executed test's duration for
+/// duration-based sharding.
 pub async fn update_cache_for_results(
     results: &[CliTestResult],
     cache_manager: &CacheManager,
+    config: &CliConfig,
 ) -> Result<()> {
+    let default_image = configured_default_image(config);
+
     for result in results {
+        // Keyed by the discovered path, as `shard_tests` looks timings up
+        let test_path = &result.path;
+        cache_manager.record_duration(test_path, result.duration_ms)?;
+
         // Only update cache for passed tests
         if result.passed {
-            // Reconstruct the file path from test name
-            // This assumes test names match file names (which they should)
-            let test_path = PathBuf::from(&result.name);
-
             // Check if file exists and update cache
             if test_path.exists() {
-                let content = std::fs::read_to_string(&test_path).map_err(|e| {
+                let content = std::fs::read_to_string(test_path).map_err(|e| {
                     CleanroomError::io_error(format!(
                         "Failed to read test file '{}': {}",
                         test_path.display(),
@@ -59,11 +70,98 @@ pub async fn update_cache_for_results(
                     ))
                 })?;
 
-                // Update cache with raw content
-                cache_manager.update(&test_path, &content)?;
+                // Update cache with raw content and resolved images
+                let cache_input =
+                    cache_input(test_path, &content, default_image.as_deref(), config.seed);
+                cache_manager.update(test_path, &cache_input)?;
             }
         }
     }
 
     Ok(())
 }
+
+/// Default step image from the cleanroom configuration, if any
+fn configured_default_image(config: &CliConfig) -> Option<String> {
+    config
+        .cleanroom
+        .clone()
+        .map_or_else(crate::config::load_cleanroom_config, Ok)
+        .ok()
+        .map(|config| config.containers.default_image)
+}
+
+/// Built-in image of a service plugin used when its `image` is omitted
+fn plugin_default_image(plugin: &str) -> Option<String> {
+    match plugin {
+        "postgres" => Some(format!(
+            "{}:{}",
+            crate::services::postgres::DEFAULT_POSTGRES_IMAGE,
+            crate::services::postgres::DEFAULT_POSTGRES_TAG
+        )),
+        "web_server" => Some(format!(
+            "{}:{}",
+            crate::services::web_server::DEFAULT_WEB_SERVER_IMAGE,
+            crate::services::web_server::DEFAULT_WEB_SERVER_TAG
+        )),
+        "surrealdb" => Some(crate::services::surrealdb::default_image()),
+        _ => None,
+    }
+}
+
+/// Build the content hashed for a test file's cache entry
+///
+/// Appends every container image the test resolves to, so bumping an image
+/// in config busts the cache only for the tests that use it:
+/// - each service's `image`, or the plugin's built-in image when omitted
+/// - the cleanroom `default_image`, if the test has `[[steps]]` or
+///   `[[scenario]]` entries (whose commands run in it) or its raw content
+///   can't be parsed (e.g. it contains templates)
+///
+/// A `--seed` override is appended too, so each seed in a sweep runs.
+fn cache_input(
+    path: &Path,
+    content: &str,
+    default_image: Option<&str>,
+    seed: Option<u64>,
+) -> String {
+    let mut images = Vec::new();
+
+    match parse_config_for_path(path, content) {
+        Ok(test_config) => {
+            let services = test_config
+                .services
+                .iter()
+                .chain(test_config.service.iter())
+                .flat_map(|services| services.values());
+
+            for service in services {
+                let image = service
+                    .image
+                    .clone()
+                    .or_else(|| plugin_default_image(&service.plugin))
+                    .unwrap_or_else(|| format!("plugin:{}", service.plugin));
+                images.push(image);
+            }
+
+            if !test_config.steps.is_empty() || !test_config.scenario.is_empty() {
+                images.extend(default_image.map(str::to_string));
+            }
+        }
+        Err(_) => images.extend(default_image.map(str::to_string)),
+    }
+
+    let mut input = content.to_string();
+
+    if !images.is_empty() {
+        images.sort();
+        images.dedup();
+        input.push_str(&format!("\n# clnrm-cache-images: {}", images.join(",")));
+    }
+
+    if let Some(seed) = seed {
+        input.push_str(&format!("\n# clnrm-cache-seed: {}", seed));
+    }
+
+    input
+}
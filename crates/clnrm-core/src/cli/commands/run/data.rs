@@ -0,0 +1,264 @@
+//! Data-driven scenario expansion
+//!
+//! A `[data]` section points at a CSV or JSON file. Every scenario runs once
+//! per row as `<scenario>[<row>]`, with the row's fields available to its
+//! `run` command and steps as `{{ row.<field> }}`. A row that can't be read
+//! or rendered fails its own cases; the other rows still run.
+
+use crate::config::{DataConfig, DataFormat, ScenarioConfig};
+use crate::error::{CleanroomError, Result};
+use crate::TemplateRenderer;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// One row of a data file: its fields, or why it couldn't be read
+pub type DataRow = std::result::Result<Map<String, Value>, String>;
+
+/// Scenarios expanded over the rows of a data file
+#[derive(Debug)]
+pub struct DataExpansion {
+    /// One scenario per (row, scenario) pair whose row could be used
+    pub scenarios: Vec<ScenarioConfig>,
+    /// Case names of the rows that could not be used, with the reason
+    pub failures: Vec<(String, CleanroomError)>,
+}
+
+/// Read the rows of `data`, resolving its file against the test file's directory
+///
+/// Fails only when the file as a whole is unusable (missing, no CSV header,
+/// not a JSON array); problems with individual rows are kept per row.
+pub fn load_rows(data: &DataConfig, test_path: &Path) -> Result<Vec<DataRow>> {
+    let path = test_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&data.file);
+    let content = std::fs::read_to_string(&path).map_err(|e| {
+        CleanroomError::config_error(format!(
+            "Failed to read data file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    match data.resolved_format()? {
+        DataFormat::Csv => csv_rows(&content),
+        DataFormat::Json => json_rows(&content),
+    }
+    .map_err(|e| e.with_context(format!("Data file: {}", path.display())))
+}
+
+/// Expand every scenario once per row
+///
+/// `run` and each step's command, workdir and env values are rendered with
+/// the row as `row`, and `depends_on` is rewritten to point at the same
+/// row's cases. Each row renders with its own copy of `renderer`, so no
+/// row's fields are visible to another. If any scenario fails to render for
+/// a row, the whole row is reported as failed so no case depends on a
+/// missing one.
+pub fn expand_scenarios(
+    scenarios: &[ScenarioConfig],
+    rows: &[DataRow],
+    renderer: &TemplateRenderer,
+) -> DataExpansion {
+    let mut expansion = DataExpansion {
+        scenarios: Vec::with_capacity(scenarios.len() * rows.len()),
+        failures: Vec::new(),
+    };
+
+    for (index, row) in rows.iter().enumerate() {
+        let expanded = match row {
+            Ok(fields) => {
+                let mut row_renderer = renderer.clone();
+                row_renderer.merge_user_vars(HashMap::from([(
+                    "row".to_string(),
+                    Value::Object(fields.clone()),
+                )]));
+                scenarios
+                    .iter()
+                    .map(|scenario| expand_scenario(scenario, index, &mut row_renderer))
+                    .collect::<Result<Vec<_>>>()
+            }
+            Err(reason) => Err(CleanroomError::validation_error(format!(
+                "Data row {} is malformed: {}",
+                index, reason
+            ))),
+        };
+
+        match expanded {
+            Ok(cases) => expansion.scenarios.extend(cases),
+            Err(e) => expansion.failures.extend(
+                scenarios
+                    .iter()
+                    .map(|scenario| (case_name(&scenario.name, index), e.clone())),
+            ),
+        }
+    }
+
+    expansion
+}
+
+/// Name of the case running `scenario` for data row `index`
+pub fn case_name(scenario: &str, index: usize) -> String {
+    format!("{}[{}]", scenario, index)
+}
+
+/// Scenario name of a case named `scenario[index]`
+pub fn case_scenario(name: &str) -> Option<&str> {
+    let (scenario, index) = name.strip_suffix(']')?.rsplit_once('[')?;
+    (!index.is_empty() && index.bytes().all(|b| b.is_ascii_digit())).then_some(scenario)
+}
+
+fn expand_scenario(
+    scenario: &ScenarioConfig,
+    index: usize,
+    renderer: &mut TemplateRenderer,
+) -> Result<ScenarioConfig> {
+    let name = case_name(&scenario.name, index);
+    let mut case = scenario.clone();
+
+    if let Some(run) = &scenario.run {
+        case.run = Some(renderer.render_str(run, &name)?);
+    }
+    for step in &mut case.steps {
+        let template = format!("{}_{}", name, step.name);
+        for arg in &mut step.command {
+            *arg = renderer.render_str(arg, &template)?;
+        }
+        if let Some(workdir) = &mut step.workdir {
+            *workdir = renderer.render_str(workdir, &template)?;
+        }
+        for value in step.env.iter_mut().flat_map(HashMap::values_mut) {
+            *value = renderer.render_str(value, &template)?;
+        }
+    }
+    case.depends_on = scenario
+        .depends_on
+        .iter()
+        .map(|dep| case_name(dep, index))
+        .collect();
+    case.name = name;
+
+    Ok(case)
+}
+
+/// Rows of a CSV file, keyed by the header row
+fn csv_rows(content: &str) -> Result<Vec<DataRow>> {
+    let mut records = parse_csv(content).into_iter();
+    let header = match records.next() {
+        Some(Ok(header)) => header,
+        Some(Err(reason)) => {
+            return Err(CleanroomError::validation_error(format!(
+                "Invalid CSV header: {}",
+                reason
+            )))
+        }
+        None => {
+            return Err(CleanroomError::validation_error(
+                "CSV data file is empty: expected a header row",
+            ))
+        }
+    };
+
+    Ok(records
+        .map(|record| {
+            let fields = record?;
+            if fields.len() != header.len() {
+                return Err(format!(
+                    "expected {} field(s), found {}",
+                    header.len(),
+                    fields.len()
+                ));
+            }
+            Ok(header
+                .iter()
+                .cloned()
+                .zip(fields.into_iter().map(Value::String))
+                .collect())
+        })
+        .collect())
+}
+
+/// Rows of a JSON file holding an array of objects
+fn json_rows(content: &str) -> Result<Vec<DataRow>> {
+    let value: Value = serde_json::from_str(content).map_err(|e| {
+        CleanroomError::serialization_error(format!("Invalid JSON data file: {}", e))
+    })?;
+    let Value::Array(items) = value else {
+        return Err(CleanroomError::validation_error(
+            "JSON data file must hold an array of objects",
+        ));
+    };
+
+    Ok(items
+        .into_iter()
+        .map(|item| match item {
+            Value::Object(fields) => Ok(fields),
+            other => Err(format!("expected a JSON object, found {}", other)),
+        })
+        .collect())
+}
+
+/// Split CSV text into records (RFC 4180 quoting, blank lines skipped)
+///
+/// A record with a stray or unterminated quote is returned as an error so
+/// only that row fails.
+fn parse_csv(content: &str) -> Vec<std::result::Result<Vec<String>, String>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut error = None;
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => in_quotes = true,
+            '"' => {
+                error.get_or_insert_with(|| format!("unexpected quote in field '{}'", field));
+            }
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                finish_record(&mut records, std::mem::take(&mut fields), error.take());
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        error.get_or_insert_with(|| "unterminated quoted field".to_string());
+    }
+    if !field.is_empty() || !fields.is_empty() || error.is_some() {
+        fields.push(field);
+        finish_record(&mut records, fields, error);
+    }
+
+    records
+}
+
+fn finish_record(
+    records: &mut Vec<std::result::Result<Vec<String>, String>>,
+    fields: Vec<String>,
+    error: Option<String>,
+) {
+    match error {
+        Some(reason) => records.push(Err(reason)),
+        // A blank line parses as a single empty field
+        None if fields.len() == 1 && fields[0].is_empty() => {}
+        None => records.push(Ok(fields)),
+    }
+}
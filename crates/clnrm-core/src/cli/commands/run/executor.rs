@@ -2,10 +2,11 @@
 
 use crate::cli::types::{CliConfig, CliTestResult};
 use crate::error::{CleanroomError, Result};
+use std::collections::HashMap;
 use std::path::PathBuf;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
-use super::single::run_single_test;
+use super::single::run_test_file;
 
 /// Run tests sequentially and return results
 pub async fn run_tests_sequential_with_results(
@@ -13,44 +14,57 @@ pub async fn run_tests_sequential_with_results(
     config: &CliConfig,
 ) -> Result<Vec<CliTestResult>> {
     let mut results = Vec::new();
+    run_tests_sequential_into(paths, config, &mut results).await?;
+    Ok(results)
+}
+
+/// Run tests sequentially, appending each result to `results` as it
+/// completes
+///
+/// Results of finished tests stay in `results` if the run is dropped
+/// part-way, e.g. by `--timeout`.
+pub async fn run_tests_sequential_into(
+    paths: &[PathBuf],
+    config: &CliConfig,
+    results: &mut Vec<CliTestResult>,
+) -> Result<()> {
+    let mut failures = 0;
 
     for path in paths {
         debug!("Processing test file: {}", path.display());
-        let test_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-
-        let start_time = std::time::Instant::now();
-        match run_single_test(path, config).await {
-            Ok(_) => {
-                let duration = start_time.elapsed().as_millis() as u64;
-                info!("Test passed: {}", path.display());
-                results.push(CliTestResult {
-                    name: test_name,
-                    passed: true,
-                    duration_ms: duration,
-                    error: None,
-                });
+
+        let result = run_test_file(path, config).await?;
+        if result.passed {
+            info!("Test passed: {}", path.display());
+        } else {
+            error!(
+                "Test failed: {} - {}",
+                path.display(),
+                result.error.as_deref().unwrap_or("unknown error")
+            );
+        }
+
+        let failed = !result.passed;
+        results.push(CliTestResult::new(path, result));
+
+        if failed {
+            failures += 1;
+            if config.fail_fast {
+                break;
             }
-            Err(e) => {
-                let duration = start_time.elapsed().as_millis() as u64;
-                error!("Test failed: {} - {}", path.display(), e);
-                results.push(CliTestResult {
-                    name: test_name,
-                    passed: false,
-                    duration_ms: duration,
-                    error: Some(e.to_string()),
-                });
-                if config.fail_fast {
-                    break;
-                }
+            if bail_reached(config, failures) {
+                warn!("🛑 Stopped after {} failures (bail)", failures);
+                break;
             }
         }
     }
 
-    Ok(results)
+    Ok(())
+}
+
+/// Whether `--bail N` is set and `failures` has reached it
+pub fn bail_reached(config: &CliConfig, failures: usize) -> bool {
+    matches!(config.bail, Some(limit) if failures >= limit)
 }
 
 /// Run tests sequentially (legacy - kept for compatibility)
@@ -76,70 +90,140 @@ pub async fn run_tests_sequential(paths: &[PathBuf], config: &CliConfig) -> Resu
 }
 
 /// Run tests in parallel and return results
+///
+/// At most `config.jobs` tests run at once. Once `--bail` is reached no new
+/// tests are scheduled, but tests already running are allowed to finish.
+/// Results are ordered by test file path, not by completion, so output is
+/// the same from one run to the next.
 pub async fn run_tests_parallel_with_results(
     paths: &[PathBuf],
     config: &CliConfig,
 ) -> Result<Vec<CliTestResult>> {
+    let mut results = Vec::new();
+    run_tests_parallel_into(paths, config, &mut results).await?;
+    Ok(results)
+}
+
+/// Run tests in parallel, adding each result to `results` as it completes
+///
+/// The results added are kept in test file path order, whatever order the
+/// tests finish in. Dropping the run part-way, e.g. on `--timeout`, aborts
+/// the tests still running; results of finished tests stay in `results`.
+pub async fn run_tests_parallel_into(
+    paths: &[PathBuf],
+    config: &CliConfig,
+    results: &mut Vec<CliTestResult>,
+) -> Result<()> {
     use tokio::task::JoinSet;
 
     let mut join_set = JoinSet::new();
-    let mut results = Vec::new();
-
-    // Spawn tasks for each test file
-    for path in paths {
+    let mut pending = paths.iter();
+    let mut failures = 0;
+    let mut bailed = false;
+
+    // Path of each running task, and of each result added so far
+    let mut running = HashMap::new();
+    let mut finished: Vec<&PathBuf> = Vec::new();
+    let first = results.len();
+    let mut add_result = |path, result| {
+        let index = finished.partition_point(|done| *done <= path);
+        finished.insert(index, path);
+        results.insert(first + index, result);
+    };
+
+    let spawn_test = |join_set: &mut JoinSet<_>, path: &PathBuf| {
         let path_clone = path.clone();
         let config_clone = config.clone();
-        let test_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-
-        join_set.spawn(async move {
-            let start_time = std::time::Instant::now();
-            let result = run_single_test(&path_clone, &config_clone).await;
-            let duration = start_time.elapsed().as_millis() as u64;
-            (test_name, result, duration)
-        });
+
+        join_set
+            .spawn(async move { run_test_file(&path_clone, &config_clone).await })
+            .id()
+    };
+
+    // Spawn the first batch of tasks
+    for path in pending.by_ref().take(config.jobs.max(1)) {
+        running.insert(spawn_test(&mut join_set, path), path);
     }
 
-    // Collect results
-    while let Some(result) = join_set.join_next().await {
-        match result {
-            Ok((test_name, Ok(_), duration)) => {
-                results.push(CliTestResult {
-                    name: test_name,
-                    passed: true,
-                    duration_ms: duration,
-                    error: None,
-                });
+    // Collect results, scheduling the next test as each one finishes
+    while let Some(result) = join_set.join_next_with_id().await {
+        let id = match &result {
+            Ok((id, _)) => *id,
+            Err(e) => e.id(),
+        };
+        let Some(path) = running.remove(&id) else {
+            continue;
+        };
+
+        let failed = match result.map(|(_, result)| result) {
+            Ok(Ok(test_result)) => {
+                let failed = !test_result.passed;
+                if let Some(e) = &test_result.error {
+                    error!("Test failed: {}", e);
+                }
+                add_result(path, CliTestResult::new(path, test_result));
+                if failed && config.fail_fast {
+                    join_set.abort_all();
+                    break;
+                }
+                failed
             }
-            Ok((test_name, Err(e), duration)) => {
+            Ok(Err(e)) => {
                 error!("Test failed: {}", e);
-                results.push(CliTestResult {
-                    name: test_name,
-                    passed: false,
-                    duration_ms: duration,
-                    error: Some(e.to_string()),
-                });
+                add_result(
+                    path,
+                    CliTestResult {
+                        name: path.display().to_string(),
+                        path: path.clone(),
+                        passed: false,
+                        duration_ms: 0,
+                        error: Some(e.to_string()),
+                        steps: Vec::new(),
+                    },
+                );
                 if config.fail_fast {
                     join_set.abort_all();
                     break;
                 }
+                true
             }
             Err(e) => {
                 error!("Task failed: {}", e);
-                results.push(CliTestResult {
-                    name: "unknown".to_string(),
-                    passed: false,
-                    duration_ms: 0,
-                    error: Some(e.to_string()),
-                });
+                add_result(
+                    path,
+                    CliTestResult {
+                        name: path.display().to_string(),
+                        path: path.clone(),
+                        passed: false,
+                        duration_ms: 0,
+                        error: Some(e.to_string()),
+                        steps: Vec::new(),
+                    },
+                );
+                true
+            }
+        };
+
+        if failed {
+            failures += 1;
+            if !bailed && bail_reached(config, failures) {
+                bailed = true;
+                warn!(
+                    "🛑 Stopped after {} failures (bail), waiting for {} running test(s)",
+                    failures,
+                    join_set.len()
+                );
+            }
+        }
+
+        if !bailed {
+            if let Some(path) = pending.next() {
+                running.insert(spawn_test(&mut join_set, path), path);
             }
         }
     }
 
-    Ok(results)
+    Ok(())
 }
 
 /// Run tests in parallel (legacy - kept for compatibility)
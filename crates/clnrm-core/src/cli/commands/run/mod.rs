@@ -11,36 +11,52 @@
 //! - `assertions` - Test assertion validation (extracted from original)
 //! - `watch` - Watch mode implementation (extracted from original)
 //! - `single` - Single test execution (extracted from original)
+//! - `shard` - Splitting tests between `--shard` runners
+//! - `plan` - Resolving what a run would execute for `--list`
+//! - `repeat` - Repeated runs and pass rates for `--repeat`
 
 pub mod cache;
+pub mod data;
 pub mod executor;
+pub mod plan;
+pub mod repeat;
 pub mod scenario;
 pub mod services;
+pub mod shard;
 pub mod single;
 pub mod watch;
 use crate::cache::{Cache, CacheManager};
-use crate::cli::types::{CliConfig, OutputFormat};
+use crate::cli::types::{CliConfig, CliTestResult, OutputFormat};
 use crate::cli::utils::{discover_test_files, generate_junit_xml};
 use crate::error::{CleanroomError, Result};
 use std::path::PathBuf;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::telemetry::spans;
 
 // Re-export executor functions
 pub use executor::{
-    run_tests_parallel, run_tests_parallel_with_results, run_tests_sequential,
-    run_tests_sequential_with_results,
+    bail_reached, run_tests_parallel, run_tests_parallel_into, run_tests_parallel_with_results,
+    run_tests_sequential, run_tests_sequential_into, run_tests_sequential_with_results,
 };
 
 // Re-export cache functions
 pub use cache::{filter_changed_tests, update_cache_for_results};
 
+// Re-export sharding
+pub use shard::shard_tests;
+
+// Re-export run planning
+pub use plan::{list_run_plan, plan_run, PlannedTest, RunPlan};
+
+// Re-export repeated runs
+pub use repeat::{run_tests_repeated, FlakinessReport, TestPassRate};
+
 // Re-export single test execution
-pub use single::run_single_test;
+pub use single::{run_single_test, run_test_file};
 
 // Re-export scenario execution
-pub use scenario::execute_scenario;
+pub use scenario::{execute_scenario, ScenarioFilter};
 
 // Re-export watch functionality
 pub use watch::watch_and_run;
@@ -80,6 +96,11 @@ pub async fn run_tests_with_shard(
     config: &CliConfig,
     shard: Option<(usize, usize)>,
 ) -> Result<()> {
+    // Reject a bad --scenario pattern before running anything
+    if let Some(pattern) = &config.scenario_filter {
+        ScenarioFilter::new(pattern.as_str())?;
+    }
+
     // If sharding is enabled, log it
     if let Some((i, m)) = shard {
         info!("🔀 Running shard {}/{}", i, m);
@@ -119,6 +140,11 @@ pub async fn run_tests_with_shard_and_report(
     shard: Option<(usize, usize)>,
     report_junit: Option<&std::path::Path>,
 ) -> Result<()> {
+    // Reject a bad --scenario pattern before running anything
+    if let Some(pattern) = &config.scenario_filter {
+        ScenarioFilter::new(pattern.as_str())?;
+    }
+
     // If sharding is enabled, log it
     if let Some((i, m)) = shard {
         info!("🔀 Running shard {}/{}", i, m);
@@ -176,34 +202,12 @@ async fn run_tests_impl(
         all_test_files.clone()
     } else {
         info!("🔍 Checking cache...");
-        filter_changed_tests(&all_test_files, &cache_manager).await?
+        filter_changed_tests(&all_test_files, &cache_manager, config).await?
     };
 
     // Apply sharding if requested
-    let tests_to_run = if let Some((i, m)) = shard {
-        info!(
-            "🔀 Applying shard {}/{} to {} tests",
-            i,
-            m,
-            tests_to_run.len()
-        );
-
-        // Distribute tests across shards using modulo arithmetic
-        // Shard i (1-based) gets tests where (index % m) == (i - 1)
-        let sharded_tests: Vec<PathBuf> = tests_to_run
-            .into_iter()
-            .enumerate()
-            .filter(|(idx, _)| (idx % m) == (i - 1))
-            .map(|(_, path)| path)
-            .collect();
-
-        info!(
-            "🔀 Shard {}/{} will run {} test(s)",
-            i,
-            m,
-            sharded_tests.len()
-        );
-        sharded_tests
+    let tests_to_run = if let Some(shard) = shard {
+        shard_tests(tests_to_run, shard, &config.shard_strategy, &cache_manager)?
     } else {
         tests_to_run
     };
@@ -232,16 +236,12 @@ async fn run_tests_impl(
     info!("Running {} scenario(s)...", tests_to_run.len());
 
     let start_time = std::time::Instant::now();
-    let results = if config.parallel {
-        run_tests_parallel_with_results(&tests_to_run, config).await?
-    } else {
-        run_tests_sequential_with_results(&tests_to_run, config).await?
-    };
+    let (results, timed_out) = run_with_budget(&tests_to_run, config).await?;
 
     let total_duration = start_time.elapsed().as_millis() as u64;
 
     // Update cache for successfully executed tests
-    update_cache_for_results(&results, &cache_manager).await?;
+    update_cache_for_results(&results, &cache_manager, config).await?;
     cache_manager.save()?;
 
     if !config.force && skipped_count == 0 {
@@ -256,12 +256,20 @@ async fn run_tests_impl(
         tests: results,
         total_duration_ms: total_duration,
     };
+    save_coverage_snapshot(config).await?;
 
     // Output results based on format
     match config.format {
         OutputFormat::Junit => {
-            let junit_xml = generate_junit_xml(&cli_results)?;
+            let junit_xml = generate_junit_xml(&cli_results, config.junit_flat)?;
             println!("{}", junit_xml);
+            if timed_out {
+                return Err(run_timeout_error(
+                    config,
+                    cli_results.tests.len(),
+                    tests_to_run.len(),
+                ));
+            }
         }
         _ => {
             // Default human-readable output
@@ -270,23 +278,26 @@ async fn run_tests_impl(
 
             println!();
             for result in &cli_results.tests {
-                if result.passed {
-                    info!("✅ {} - PASS ({}ms)", result.name, result.duration_ms);
-                } else {
-                    error!("❌ {} - FAIL ({}ms)", result.name, result.duration_ms);
-                    if let Some(error) = &result.error {
-                        error!("   Error: {}", error);
-                    }
-                }
+                log_test_result(result);
             }
 
             info!("Test Results: {} passed, {} failed", passed, failed);
 
+            if timed_out {
+                return Err(run_timeout_error(
+                    config,
+                    cli_results.tests.len(),
+                    tests_to_run.len(),
+                ));
+            }
+
             if failed > 0 {
-                return Err(CleanroomError::validation_error(format!(
-                    "{} test(s) failed",
-                    failed
-                )));
+                return Err(tests_failed_error(
+                    config,
+                    failed,
+                    cli_results.tests.len(),
+                    tests_to_run.len(),
+                ));
             }
         }
     }
@@ -294,6 +305,15 @@ async fn run_tests_impl(
     Ok(())
 }
 
+/// Save the run's behavior coverage to `--coverage-snapshot`, if given
+async fn save_coverage_snapshot(config: &CliConfig) -> Result<()> {
+    if let Some(path) = &config.coverage_snapshot {
+        config.coverage.save_snapshot(path).await?;
+        info!("📊 Coverage snapshot written to {}", path.display());
+    }
+    Ok(())
+}
+
 /// Implementation of run_tests with sharding and JUnit report support
 async fn run_tests_impl_with_report(
     paths: &[PathBuf],
@@ -343,34 +363,12 @@ async fn run_tests_impl_with_report(
         all_test_files.clone()
     } else {
         info!("🔍 Checking cache...");
-        filter_changed_tests(&all_test_files, &cache_manager).await?
+        filter_changed_tests(&all_test_files, &cache_manager, config).await?
     };
 
     // Apply sharding if requested
-    let tests_to_run = if let Some((i, m)) = shard {
-        info!(
-            "🔀 Applying shard {}/{} to {} tests",
-            i,
-            m,
-            tests_to_run.len()
-        );
-
-        // Distribute tests across shards using modulo arithmetic
-        // Shard i (1-based) gets tests where (index % m) == (i - 1)
-        let sharded_tests: Vec<PathBuf> = tests_to_run
-            .into_iter()
-            .enumerate()
-            .filter(|(idx, _)| (idx % m) == (i - 1))
-            .map(|(_, path)| path)
-            .collect();
-
-        info!(
-            "🔀 Shard {}/{} will run {} test(s)",
-            i,
-            m,
-            sharded_tests.len()
-        );
-        sharded_tests
+    let tests_to_run = if let Some(shard) = shard {
+        shard_tests(tests_to_run, shard, &config.shard_strategy, &cache_manager)?
     } else {
         tests_to_run
     };
@@ -399,16 +397,12 @@ async fn run_tests_impl_with_report(
     info!("Running {} scenario(s)...", tests_to_run.len());
 
     let start_time = std::time::Instant::now();
-    let results = if config.parallel {
-        run_tests_parallel_with_results(&tests_to_run, config).await?
-    } else {
-        run_tests_sequential_with_results(&tests_to_run, config).await?
-    };
+    let (results, timed_out) = run_with_budget(&tests_to_run, config).await?;
 
     let total_duration = start_time.elapsed().as_millis() as u64;
 
     // Update cache for successfully executed tests
-    update_cache_for_results(&results, &cache_manager).await?;
+    update_cache_for_results(&results, &cache_manager, config).await?;
     cache_manager.save()?;
 
     if !config.force && skipped_count == 0 {
@@ -423,11 +417,12 @@ async fn run_tests_impl_with_report(
         tests: results,
         total_duration_ms: total_duration,
     };
+    save_coverage_snapshot(config).await?;
 
     // Generate JUnit report if requested
     if let Some(junit_path) = report_junit {
         info!("📄 Generating JUnit XML report: {}", junit_path.display());
-        let junit_xml = generate_junit_xml(&cli_results)?;
+        let junit_xml = generate_junit_xml(&cli_results, config.junit_flat)?;
         std::fs::write(junit_path, &junit_xml).map_err(|e| {
             CleanroomError::io_error(format!(
                 "Failed to write JUnit report to {}: {}",
@@ -441,8 +436,15 @@ async fn run_tests_impl_with_report(
     // Output results based on format
     match config.format {
         OutputFormat::Junit => {
-            let junit_xml = generate_junit_xml(&cli_results)?;
+            let junit_xml = generate_junit_xml(&cli_results, config.junit_flat)?;
             println!("{}", junit_xml);
+            if timed_out {
+                return Err(run_timeout_error(
+                    config,
+                    cli_results.tests.len(),
+                    tests_to_run.len(),
+                ));
+            }
         }
         _ => {
             // Default human-readable output
@@ -451,23 +453,26 @@ async fn run_tests_impl_with_report(
 
             println!();
             for result in &cli_results.tests {
-                if result.passed {
-                    info!("✅ {} - PASS ({}ms)", result.name, result.duration_ms);
-                } else {
-                    error!("❌ {} - FAIL ({}ms)", result.name, result.duration_ms);
-                    if let Some(error) = &result.error {
-                        error!("   Error: {}", error);
-                    }
-                }
+                log_test_result(result);
             }
 
             info!("Test Results: {} passed, {} failed", passed, failed);
 
+            if timed_out {
+                return Err(run_timeout_error(
+                    config,
+                    cli_results.tests.len(),
+                    tests_to_run.len(),
+                ));
+            }
+
             if failed > 0 {
-                return Err(CleanroomError::validation_error(format!(
-                    "{} test(s) failed",
-                    failed
-                )));
+                return Err(tests_failed_error(
+                    config,
+                    failed,
+                    cli_results.tests.len(),
+                    tests_to_run.len(),
+                ));
             }
         }
     }
@@ -475,6 +480,119 @@ async fn run_tests_impl_with_report(
     Ok(())
 }
 
+/// Run the tests, giving up once `--timeout` has elapsed
+///
+/// Returns the results of the tests that completed and whether the budget
+/// ran out. On expiry the run is dropped, which cancels the tests still in
+/// flight and stops their services.
+async fn run_with_budget(
+    tests: &[PathBuf],
+    config: &CliConfig,
+) -> Result<(Vec<CliTestResult>, bool)> {
+    let mut results = Vec::new();
+    let run = async {
+        if config.parallel {
+            run_tests_parallel_into(tests, config, &mut results).await
+        } else {
+            run_tests_sequential_into(tests, config, &mut results).await
+        }
+    };
 
+    let timed_out = match config.timeout {
+        Some(budget) => match tokio::time::timeout(budget, run).await {
+            Ok(outcome) => {
+                outcome?;
+                false
+            }
+            Err(_) => true,
+        },
+        None => {
+            run.await?;
+            false
+        }
+    };
 
+    Ok((results, timed_out))
+}
 
+/// Error for a run stopped by `--timeout` with `completed` of `total`
+/// tests finished
+fn run_timeout_error(config: &CliConfig, completed: usize, total: usize) -> CleanroomError {
+    let budget = config.timeout.unwrap_or_default();
+    warn!(
+        "⏱️  Run timed out after {}s, {} test(s) cancelled or not run",
+        budget.as_secs(),
+        total - completed
+    );
+    CleanroomError::timeout_error(format!(
+        "run timed out after {}s ({} of {} test(s) completed)",
+        budget.as_secs(),
+        completed,
+        total
+    ))
+}
+
+/// Error for a run with `failed` failing tests, noting when `--bail` left
+/// some of the `total` tests unrun after `completed` finished
+fn tests_failed_error(
+    config: &CliConfig,
+    failed: usize,
+    completed: usize,
+    total: usize,
+) -> CleanroomError {
+    let mut message = format!("{} test(s) failed", failed);
+    if let Some(limit) = config.bail {
+        let not_run = total - completed;
+        if bail_reached(config, failed) && not_run > 0 {
+            warn!(
+                "🛑 Stopped after {} failures (bail), {} test(s) not run",
+                limit, not_run
+            );
+            message.push_str(&format!(", stopped after {} failures (bail)", limit));
+        }
+    }
+    CleanroomError::validation_error(message)
+}
+
+/// Log a test result with `test`, `status` and `duration_ms` fields so
+/// `--log-format json` emits one machine-readable object per test
+fn log_test_result(result: &CliTestResult) {
+    let retries: u32 = result.steps.iter().map(|step| step.retries).sum();
+    if result.passed {
+        info!(
+            test = %result.name,
+            status = "pass",
+            duration_ms = result.duration_ms,
+            retries,
+            "✅ {} - PASS ({}ms)",
+            result.name,
+            result.duration_ms
+        );
+    } else {
+        error!(
+            test = %result.name,
+            status = "fail",
+            duration_ms = result.duration_ms,
+            retries,
+            failure_reason = result.failure_reason().map(|reason| reason.as_str()),
+            "❌ {} - FAIL ({}ms)",
+            result.name,
+            result.duration_ms
+        );
+        if let Some(error) = &result.error {
+            error!(test = %result.name, "   Error: {}", error);
+        }
+    }
+
+    for step in result.steps.iter().filter(|step| step.retries > 0) {
+        warn!(
+            test = %result.name,
+            step = %step.name,
+            retries = step.retries,
+            "   🔁 Step '{}' {} after {} retries",
+            step.name,
+            if step.success { "passed" } else { "failed" },
+            step.retries
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Execution plan for `clnrm run --list`
+//!
+//! Resolves which test files a run would execute, after discovery, cache
+//! filtering and `--shard`, without starting any container. Useful for
+//! checking what a CI shard will pick up or why shards are uneven.
+
+use crate::cache::CacheManager;
+use crate::cli::types::CliConfig;
+use crate::cli::utils::discover_test_files;
+use crate::config::load_config_from_file;
+use crate::error::Result;
+use std::path::PathBuf;
+
+use super::{filter_changed_tests, shard_tests, ScenarioFilter};
+
+/// The files a run would execute and the ones it would leave out
+#[derive(Debug, Clone, Default)]
+pub struct RunPlan {
+    /// Shard (index, total) the plan was resolved for
+    pub shard: Option<(usize, usize)>,
+    /// Files this run would execute, in execution order
+    pub tests: Vec<PlannedTest>,
+    /// Files skipped because they are unchanged since their last passing run
+    pub cache_skipped: Vec<PathBuf>,
+    /// Number of changed files assigned to other shards
+    pub other_shards: usize,
+}
+
+/// A test file in the plan
+#[derive(Debug, Clone)]
+pub struct PlannedTest {
+    /// Path to the test file
+    pub path: PathBuf,
+    /// Scenarios that would run (those matching `--scenario`, if given)
+    pub scenarios: usize,
+    /// Top-level `[[steps]]` that would run
+    pub steps: usize,
+    /// Why the file could not be loaded; the run would report it as failed
+    pub error: Option<String>,
+}
+
+impl PlannedTest {
+    fn load(path: PathBuf, config: &CliConfig, filter: Option<&ScenarioFilter>) -> Self {
+        match load_config_from_file(&path) {
+            Ok(test_config) => {
+                let (scenarios, steps) = match filter {
+                    Some(filter) => {
+                        let selected = filter.select(&test_config.scenario).len();
+                        let steps = if config.scenario_only {
+                            0
+                        } else {
+                            test_config.steps.len()
+                        };
+                        (selected, steps)
+                    }
+                    None => (test_config.scenario.len(), test_config.steps.len()),
+                };
+                Self {
+                    path,
+                    scenarios,
+                    steps,
+                    error: None,
+                }
+            }
+            Err(e) => Self {
+                path,
+                scenarios: 0,
+                steps: 0,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+impl RunPlan {
+    /// Human-readable listing, one line per file
+    pub fn render(&self) -> String {
+        let mut out = match self.shard {
+            Some((i, m)) => format!(
+                "Run plan for shard {}/{}: {} file(s)\n",
+                i,
+                m,
+                self.tests.len()
+            ),
+            None => format!("Run plan: {} file(s)\n", self.tests.len()),
+        };
+
+        for test in &self.tests {
+            match &test.error {
+                Some(error) => {
+                    out.push_str(&format!("  {} (invalid: {})\n", test.path.display(), error))
+                }
+                None if test.steps > 0 => out.push_str(&format!(
+                    "  {} ({} scenario(s), {} step(s))\n",
+                    test.path.display(),
+                    test.scenarios,
+                    test.steps
+                )),
+                None => out.push_str(&format!(
+                    "  {} ({} scenario(s))\n",
+                    test.path.display(),
+                    test.scenarios
+                )),
+            }
+        }
+
+        if !self.cache_skipped.is_empty() {
+            out.push_str(&format!(
+                "Skipped (unchanged, cached): {} file(s)\n",
+                self.cache_skipped.len()
+            ));
+            for path in &self.cache_skipped {
+                out.push_str(&format!("  {}\n", path.display()));
+            }
+        }
+
+        if let Some((_, m)) = self.shard {
+            out.push_str(&format!(
+                "Assigned to the other {} shard(s): {} file(s)\n",
+                m - 1,
+                self.other_shards
+            ));
+        }
+
+        out
+    }
+}
+
+/// Resolve the plan `clnrm run` would execute for `paths`
+///
+/// Applies the same discovery, cache filtering and sharding as a real run.
+/// The cache is read but never updated.
+pub async fn plan_run(
+    paths: &[PathBuf],
+    config: &CliConfig,
+    shard: Option<(usize, usize)>,
+) -> Result<RunPlan> {
+    let filter = config
+        .scenario_filter
+        .as_deref()
+        .map(ScenarioFilter::new)
+        .transpose()?;
+
+    let mut all_test_files = Vec::new();
+    for path in paths {
+        all_test_files.extend(discover_test_files(path)?);
+    }
+
+    let cache_manager = CacheManager::new()?;
+    let changed = if config.force {
+        all_test_files.clone()
+    } else {
+        filter_changed_tests(&all_test_files, &cache_manager, config).await?
+    };
+    let cache_skipped = all_test_files
+        .into_iter()
+        .filter(|path| !changed.contains(path))
+        .collect();
+
+    let changed_count = changed.len();
+    let tests_to_run = match shard {
+        Some(shard) => shard_tests(changed, shard, &config.shard_strategy, &cache_manager)?,
+        None => changed,
+    };
+
+    Ok(RunPlan {
+        shard,
+        other_shards: changed_count - tests_to_run.len(),
+        tests: tests_to_run
+            .into_iter()
+            .map(|path| PlannedTest::load(path, config, filter.as_ref()))
+            .collect(),
+        cache_skipped,
+    })
+}
+
+/// Print the plan for `clnrm run --list` without running anything
+pub async fn list_run_plan(
+    paths: &[PathBuf],
+    config: &CliConfig,
+    shard: Option<(usize, usize)>,
+) -> Result<()> {
+    let plan = plan_run(paths, config, shard).await?;
+    print!("{}", plan.render());
+    Ok(())
+}
@@ -0,0 +1,178 @@
+//! Repeated runs for flakiness detection (`clnrm run --repeat N`)
+//!
+//! Every selected test runs N times, honouring `--parallel`, and the results
+//! are aggregated into a pass rate per test. A test that neither always
+//! passes nor always fails is flaky.
+
+use crate::cli::types::{CliConfig, CliTestResult};
+use crate::error::{CleanroomError, Result};
+use std::path::PathBuf;
+use tracing::info;
+
+use super::{plan_run, run_tests_parallel_into, run_tests_sequential_into};
+
+/// Pass rate of one test over the repeated runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestPassRate {
+    /// Test name (its file name)
+    pub name: String,
+    /// Runs that passed
+    pub passed: usize,
+    /// Runs attempted
+    pub runs: usize,
+}
+
+impl TestPassRate {
+    /// Percentage of runs that failed
+    pub fn failure_rate(&self) -> f64 {
+        if self.runs == 0 {
+            return 0.0;
+        }
+        (self.runs - self.passed) as f64 * 100.0 / self.runs as f64
+    }
+
+    /// Whether the test both passed and failed across the runs
+    pub fn is_flaky(&self) -> bool {
+        self.passed > 0 && self.passed < self.runs
+    }
+
+    /// Whether the test failed every run
+    pub fn is_failing(&self) -> bool {
+        self.runs > 0 && self.passed == 0
+    }
+}
+
+/// Pass rates of every test over `runs` repeated runs
+#[derive(Debug, Clone, Default)]
+pub struct FlakinessReport {
+    /// Number of times each test was run
+    pub runs: usize,
+    /// Pass rate per test, in execution order
+    pub tests: Vec<TestPassRate>,
+}
+
+impl FlakinessReport {
+    /// Aggregate `results` of `runs` repeated runs of the tests `names`
+    ///
+    /// A run with no result for a test, e.g. one cut short by `--bail`,
+    /// counts as a failure.
+    pub fn from_results(names: &[String], results: &[CliTestResult], runs: usize) -> Self {
+        let tests = names
+            .iter()
+            .map(|name| TestPassRate {
+                name: name.clone(),
+                passed: results
+                    .iter()
+                    .filter(|r| r.passed && &r.name == name)
+                    .count()
+                    .min(runs),
+                runs,
+            })
+            .collect();
+        Self { runs, tests }
+    }
+
+    /// One line per test, e.g. `flaky.toml: 17/20 passed (FLAKY)`
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for test in &self.tests {
+            let flag = if test.is_flaky() {
+                " (FLAKY)"
+            } else if test.is_failing() {
+                " (FAILING)"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "{}: {}/{} passed{}\n",
+                test.name, test.passed, test.runs, flag
+            ));
+        }
+        out
+    }
+
+    /// Fail if a test failed every run, or is flaky with a failure rate
+    /// above `threshold` percent
+    pub fn check(&self, threshold: f64) -> Result<()> {
+        let failing = self.tests.iter().filter(|t| t.is_failing()).count();
+        let flaky: Vec<&TestPassRate> = self
+            .tests
+            .iter()
+            .filter(|t| t.is_flaky() && t.failure_rate() > threshold)
+            .collect();
+
+        if !flaky.is_empty() {
+            return Err(CleanroomError::validation_error(format!(
+                "{} flaky test(s) failed more than {}% of {} runs: {}",
+                flaky.len(),
+                threshold,
+                self.runs,
+                flaky
+                    .iter()
+                    .map(|t| t.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        if failing > 0 {
+            return Err(CleanroomError::validation_error(format!(
+                "{} test(s) failed all {} runs",
+                failing, self.runs
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Run the selected tests `repeat` times and report each test's pass rate
+///
+/// Tests are selected as for a normal run, except that the cache is
+/// bypassed and not updated. Fails if any test failed every run or is flaky
+/// above `flaky_threshold` percent failures.
+pub async fn run_tests_repeated(
+    paths: &[PathBuf],
+    config: &CliConfig,
+    shard: Option<(usize, usize)>,
+    repeat: usize,
+    flaky_threshold: f64,
+) -> Result<()> {
+    if repeat == 0 {
+        return Err(CleanroomError::validation_error(
+            "--repeat must be at least 1",
+        ));
+    }
+
+    let config = CliConfig {
+        force: true,
+        ..config.clone()
+    };
+    let tests: Vec<PathBuf> = plan_run(paths, &config, shard)
+        .await?
+        .tests
+        .into_iter()
+        .map(|test| test.path)
+        .collect();
+    let names: Vec<String> = tests
+        .iter()
+        .map(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown")
+                .to_string()
+        })
+        .collect();
+
+    let mut results = Vec::new();
+    for run in 1..=repeat {
+        info!("🔁 Run {}/{} of {} test(s)", run, repeat, tests.len());
+        if config.parallel {
+            run_tests_parallel_into(&tests, &config, &mut results).await?;
+        } else {
+            run_tests_sequential_into(&tests, &config, &mut results).await?;
+        }
+    }
+
+    let report = FlakinessReport::from_results(&names, &results, repeat);
+    print!("{}", report.render());
+    report.check(flaky_threshold)
+}
@@ -3,25 +3,335 @@
 //! Handles execution of test scenarios including command execution,
 //! OTEL span parsing, determinism application, and validation.
 
-use crate::cleanroom::CleanroomEnvironment;
+use crate::assertions::database::{assert_query_eq, assert_row_count, DatabaseService};
+use crate::backend::network::egress_violation;
+use crate::cleanroom::{CleanroomEnvironment, FailureReason};
 use crate::config::types::parse_shell_command;
+use crate::config::ScenarioConfig;
 use crate::determinism::DeterminismEngine;
 use crate::error::{CleanroomError, Result};
-use crate::otel::stdout_parser::StdoutSpanParser;
-use crate::reporting::{generate_reports, ReportConfig};
+use crate::otel::metrics::{parse_metrics_json, MetricData};
+use crate::otel::otlp_receiver::{OtlpReceiver, DEFAULT_OTLP_HTTP_ENDPOINT};
+use crate::otel::redact::SpanRedactor;
+use crate::otel::span_source::{
+    JsonFileSpanSource, OtlpSpanSource, SpanSource, SpanSourceSpec, StdoutSpanSource,
+};
+use crate::reporting::{generate_reports, ReportConfig, ReportMeta};
+use crate::scenario::artifacts::ArtifactCollector;
+use crate::scenario::StepResult;
 use crate::validation::orchestrator::PrdExpectations;
 use crate::validation::{
-    CountExpectation, GraphExpectation, HermeticityExpectation, WindowExpectation,
+    AttributeExpectation, CountExpectation, DurationExpectation, EventAssertion, GraphExpectation,
+    HermeticityExpectation, MetricExpectation, WindowExpectation,
 };
-use std::collections::HashMap;
-use tracing::{debug, error, info};
+use futures_util::future::join_all;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::time::Duration;
+use tracing::{debug, error, info, warn};
+
+/// How long to keep receiving OTLP data after the scenario command exits,
+/// so batches flushed on exporter shutdown still arrive
+const OTLP_FLUSH_GRACE: Duration = Duration::from_millis(500);
+
+/// Lines of each service's log attached to a failed scenario's error
+const FAILURE_LOG_TAIL_LINES: usize = 20;
+
+/// Leading stdout kept in a scenario's step result; the rest is only read
+/// from disk, for spans and egress checks
+const REPORTED_STDOUT_LIMIT_BYTES: usize = 1024 * 1024;
+
+/// `--scenario` filter: a regex that must match the whole scenario name,
+/// so a plain name selects just that scenario
+///
+/// Data-driven cases (`login[3]`) also match through their scenario name.
+#[derive(Debug, Clone)]
+pub struct ScenarioFilter {
+    pattern: String,
+    regex: Regex,
+}
+
+impl ScenarioFilter {
+    /// Build a filter, failing with a configuration error if `pattern` isn't
+    /// a valid regex
+    pub fn new(pattern: impl Into<String>) -> Result<Self> {
+        let pattern = pattern.into();
+        let regex = Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| {
+            CleanroomError::configuration_error(format!(
+                "Invalid --scenario pattern '{}': {}",
+                pattern, e
+            ))
+        })?;
+        Ok(Self { pattern, regex })
+    }
+
+    /// The pattern as given on the command line
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    /// Whether the scenario called `name` was selected
+    pub fn matches(&self, name: &str) -> bool {
+        let matches_name = |name: &str| name == self.pattern || self.regex.is_match(name);
+        matches_name(name) || super::data::case_scenario(name).is_some_and(matches_name)
+    }
+
+    /// Names of the scenarios to run: every match plus, transitively, the
+    /// scenarios they depend on
+    pub fn select<'a>(&self, scenarios: &'a [ScenarioConfig]) -> HashSet<&'a str> {
+        let by_name: HashMap<&str, &ScenarioConfig> =
+            scenarios.iter().map(|s| (s.name.as_str(), s)).collect();
+
+        let mut pending: Vec<&ScenarioConfig> =
+            scenarios.iter().filter(|s| self.matches(&s.name)).collect();
+        let mut selected = HashSet::new();
+        while let Some(scenario) = pending.pop() {
+            if selected.insert(scenario.name.as_str()) {
+                pending.extend(
+                    scenario
+                        .depends_on
+                        .iter()
+                        .filter_map(|dep| by_name.get(dep.as_str()).copied()),
+                );
+            }
+        }
+        selected
+    }
+}
+
+/// Execute scenarios stage by stage in dependency order
+///
+/// `stages` comes from [`crate::config::TestConfig::scenario_stages`]. Within
+/// a stage, scenarios run in declaration order; consecutive scenarios with
+/// `concurrent = true` run in parallel as one batch. A scenario whose
+/// dependency failed or was skipped is recorded as skipped instead of run.
+///
+/// The run stops at the first failed scenario, once the batch it ran in has
+/// finished, unless it has `continue_on_failure = true`; the scenarios left
+/// are recorded as skipped. The first failure is returned either way.
+pub async fn execute_scenarios(
+    stages: &[Vec<&ScenarioConfig>],
+    env: &CleanroomEnvironment,
+    service_handles: &HashMap<String, crate::cleanroom::ServiceHandle>,
+    test_config: &crate::config::TestConfig,
+    step_results: &mut Vec<StepResult>,
+) -> Result<()> {
+    let mut not_succeeded: HashSet<&str> = HashSet::new();
+    let mut first_error = None;
+    // Failed scenario that stopped the run
+    let mut stopped_by: Option<&str> = None;
+
+    for stage in stages {
+        let mut remaining = stage.as_slice();
+        while let Some(&scenario) = remaining.first() {
+            let batch_len = if scenario.concurrent.unwrap_or(false) {
+                remaining
+                    .iter()
+                    .take_while(|next| next.concurrent.unwrap_or(false))
+                    .count()
+            } else {
+                1
+            };
+            let (batch, rest) = remaining.split_at(batch_len);
+            remaining = rest;
+
+            let mut runnable = Vec::with_capacity(batch.len());
+            for &scenario in batch {
+                if let Some(failed) = stopped_by {
+                    info!(
+                        "⏭️  Skipping scenario '{}': scenario '{}' failed",
+                        scenario.name, failed
+                    );
+                    step_results.push(StepResult::skipped("run", &scenario.name));
+                    continue;
+                }
+                match scenario
+                    .depends_on
+                    .iter()
+                    .find(|dep| not_succeeded.contains(dep.as_str()))
+                {
+                    Some(dep) => {
+                        info!(
+                            "⏭️  Skipping scenario '{}': dependency '{}' did not succeed",
+                            scenario.name, dep
+                        );
+                        step_results.push(StepResult::skipped("run", &scenario.name));
+                        not_succeeded.insert(&scenario.name);
+                    }
+                    None => runnable.push(scenario),
+                }
+            }
+
+            let outcomes = join_all(runnable.into_iter().map(|scenario| async move {
+                let mut results = Vec::new();
+                let outcome =
+                    execute_scenario(scenario, env, service_handles, test_config, &mut results)
+                        .await;
+                (scenario, results, outcome)
+            }))
+            .await;
+
+            for (scenario, results, outcome) in outcomes {
+                step_results.extend(results);
+                let Err(e) = outcome else {
+                    continue;
+                };
+
+                error!("❌ Scenario '{}' failed: {}", scenario.name, e);
+                not_succeeded.insert(&scenario.name);
+                if scenario.continue_on_failure.unwrap_or(false) {
+                    warn!(
+                        "⚠️  Continuing past failed scenario '{}' (continue_on_failure)",
+                        scenario.name
+                    );
+                } else {
+                    stopped_by.get_or_insert(&scenario.name);
+                }
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
 
 /// Execute a single scenario with OTEL validation
+///
+/// The scenario's `run` command is recorded in `step_results` as a step named
+/// `run` whose source is the scenario name.
+///
+/// Once the scenario ends, service logs requested via `logs:<service>` are
+/// written to its artifact directory. If it failed, the tail of every
+/// service's log is attached to the error context.
 pub async fn execute_scenario(
-    scenario: &crate::config::ScenarioConfig,
+    scenario: &ScenarioConfig,
     env: &CleanroomEnvironment,
     service_handles: &HashMap<String, crate::cleanroom::ServiceHandle>,
     test_config: &crate::config::TestConfig,
+    step_results: &mut Vec<StepResult>,
+) -> Result<()> {
+    let outcome = run_scenario(scenario, env, service_handles, test_config, step_results).await;
+
+    collect_service_logs(scenario, env, service_handles).await;
+
+    match outcome {
+        Ok(()) => Ok(()),
+        Err(mut e) => match service_log_tails(env, service_handles).await {
+            Some(tails) => {
+                let context = match e.context.take() {
+                    Some(existing) => format!("{}\n{}", existing, tails),
+                    None => tails,
+                };
+                Err(e.with_context(context))
+            }
+            None => Err(e),
+        },
+    }
+}
+
+/// Write the container output of each `logs:<service>` artifact to
+/// `.clnrm/artifacts/<scenario>/<service>.log`
+///
+/// Failures are logged rather than returned so they never mask the
+/// scenario's own outcome.
+async fn collect_service_logs(
+    scenario: &ScenarioConfig,
+    env: &CleanroomEnvironment,
+    service_handles: &HashMap<String, crate::cleanroom::ServiceHandle>,
+) {
+    let Some(ref artifacts) = scenario.artifacts else {
+        return;
+    };
+
+    let services: Vec<&str> = artifacts
+        .collect
+        .iter()
+        .filter_map(|spec| spec.strip_prefix("logs:"))
+        .filter(|stream| !matches!(*stream, "stdout" | "stderr"))
+        .collect();
+    if services.is_empty() {
+        return;
+    }
+
+    let collector = ArtifactCollector::new(&scenario.name);
+    if let Err(e) = collector.ensure_artifact_dir().await {
+        warn!("⚠️  Could not create artifact directory: {}", e);
+        return;
+    }
+
+    for service in services {
+        let Some(handle) = service_handles.get(service) else {
+            warn!(
+                "⚠️  Scenario '{}' collects logs from unknown service '{}'",
+                scenario.name, service
+            );
+            continue;
+        };
+
+        let result = match env.service_logs(handle).await {
+            Ok(logs) => collector.collect_logs(&logs.combined(), service).await,
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(path) => info!("📄 Saved '{}' logs to {}", service, path.display()),
+            Err(e) => warn!(
+                "⚠️  Could not collect logs for service '{}': {}",
+                service, e
+            ),
+        }
+    }
+}
+
+/// Render the last lines of each service's log for a failure report
+///
+/// Services whose logs can't be read are skipped; returns `None` when no
+/// service produced any output.
+async fn service_log_tails(
+    env: &CleanroomEnvironment,
+    service_handles: &HashMap<String, crate::cleanroom::ServiceHandle>,
+) -> Option<String> {
+    let mut names: Vec<&String> = service_handles.keys().collect();
+    names.sort();
+
+    let mut sections = Vec::new();
+    for name in names {
+        let tail = match env.service_logs(&service_handles[name]).await {
+            Ok(logs) => logs.tail(FAILURE_LOG_TAIL_LINES),
+            Err(e) => {
+                debug!("No logs for service '{}': {}", name, e);
+                continue;
+            }
+        };
+        if tail.is_empty() {
+            continue;
+        }
+
+        sections.push(format!(
+            "Last {} line(s) of service '{}' log:\n{}",
+            tail.len(),
+            name,
+            tail.join("\n")
+        ));
+    }
+
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections.join("\n"))
+    }
+}
+
+/// Run a scenario's command and validate the spans it produced
+async fn run_scenario(
+    scenario: &ScenarioConfig,
+    env: &CleanroomEnvironment,
+    service_handles: &HashMap<String, crate::cleanroom::ServiceHandle>,
+    test_config: &crate::config::TestConfig,
+    step_results: &mut Vec<StepResult>,
 ) -> Result<()> {
     info!("🚀 Executing scenario: {}", scenario.name);
 
@@ -33,7 +343,7 @@ pub async fn execute_scenario(
         )));
     }
 
-    // Get service handle
+    // Get service name
     let service_name = scenario.service.as_ref().ok_or_else(|| {
         CleanroomError::validation_error(format!(
             "Scenario '{}' missing 'service' field",
@@ -41,13 +351,6 @@ pub async fn execute_scenario(
         ))
     })?;
 
-    let handle = service_handles.get(service_name).ok_or_else(|| {
-        CleanroomError::validation_error(format!(
-            "Scenario '{}' references unknown service '{}'",
-            scenario.name, service_name
-        ))
-    })?;
-
     // Parse shell command
     let run_command = scenario.run.as_ref().ok_or_else(|| {
         CleanroomError::validation_error(format!(
@@ -57,36 +360,170 @@ pub async fn execute_scenario(
     })?;
 
     let command_args = parse_shell_command(run_command)?;
+
+    // The command must pass both the test's [policy] and the scenario's own,
+    // so a scenario policy can only narrow the test's
+    let policies = [test_config.policy.as_ref(), scenario.policy.as_ref()];
+    for policy in policies.into_iter().flatten() {
+        if let Err(e) = policy.to_policy().check_command(&command_args) {
+            let e = CleanroomError::validation_error(format!(
+                "Scenario '{}' blocked by [policy]: {}",
+                scenario.name, e.message
+            ));
+            step_results.push(StepResult::failed("run", &scenario.name, e.to_string()));
+            return Err(e);
+        }
+    }
+
+    // Get service handle
+    let handle = service_handles.get(service_name).ok_or_else(|| {
+        CleanroomError::validation_error(format!(
+            "Scenario '{}' references unknown service '{}'",
+            scenario.name, service_name
+        ))
+    })?;
+
+    let collect = |spec: &str| {
+        scenario
+            .artifacts
+            .as_ref()
+            .is_some_and(|a| a.collect.iter().any(|c| c == spec))
+    };
+    let otlp_receiver = if collect("spans:otlp") || collect("metrics:otlp") {
+        let endpoint = otlp_receiver_endpoint(test_config);
+        let receiver = OtlpReceiver::start(&endpoint).await?;
+        info!("📡 Receiving OTLP on {} (http/json)", receiver.local_addr());
+        Some(receiver)
+    } else {
+        None
+    };
+
     info!("🔧 Executing command in container: {}", run_command);
 
-    // Execute command in container and capture stdout/stderr
+    // Execute command in container; stdout is written to a file so spans
+    // are parsed from disk a line at a time however much it prints
+    let step_start = std::time::Instant::now();
+    let stdout_file = tempfile::NamedTempFile::new()
+        .map_err(|e| CleanroomError::io_error(format!("Failed to create stdout file: {}", e)))?;
     let output = env
-        .execute_command_with_output(handle, &command_args)
+        .execute_command_to_file(
+            handle,
+            &command_args,
+            scenario.workdir.as_deref(),
+            stdout_file.path(),
+        )
         .await?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let stderr = &output.stderr;
+
+    // Services of a hermetic test run on its network, so their commands are
+    // checked for blocked egress like steps are
+    let allowed_hosts: Option<Vec<String>> = env
+        .network()
+        .await
+        .map(|_| service_handles.keys().cloned().collect());
+    let stdout = read_spooled_stdout(stdout_file.path(), allowed_hosts.clone()).await?;
+    let egress = allowed_hosts.and_then(|allowed_hosts| {
+        stdout
+            .egress
+            .clone()
+            .or_else(|| egress_violation(stderr, &allowed_hosts))
+    });
+
+    // [meta] redact masks what is logged and reported; spans are still
+    // parsed from the raw output
+    let redactor = match &test_config.meta {
+        Some(meta) => meta.output_redactor()?,
+        None => SpanRedactor::new(),
+    };
+    let redacted_stderr = redactor.redact_text(stderr);
 
     if !stderr.is_empty() {
-        info!("⚠️  Stderr: {}", stderr.trim());
+        info!("⚠️  Stderr: {}", redacted_stderr.trim());
     }
 
-    if !output.status.success() {
+    let run_step = step_results.len();
+    step_results.push(StepResult {
+        name: "run".to_string(),
+        command: run_command.clone(),
+        exit_code: output.exit_code,
+        stdout: redactor.redact_text(&stdout.head),
+        stderr: redacted_stderr,
+        duration_ms: step_start.elapsed().as_millis() as u64,
+        start_ts: 0,
+        success: output.success() && egress.is_none(),
+        source: scenario.name.clone(),
+        retries: 0,
+        skipped: false,
+        failure_reason: match egress {
+            Some(_) => None,
+            None => FailureReason::from_exit(output.exit_code, output.oom_killed),
+        },
+        spans: Vec::new(),
+    });
+
+    if let Some(host) = egress {
+        return Err(CleanroomError::validation_error(format!(
+            "Scenario '{}' failed: hermetic violation: attempted egress to {}",
+            scenario.name, host
+        )));
+    }
+
+    if !output.success() {
         return Err(CleanroomError::validation_error(format!(
             "Scenario '{}' command failed with exit code: {}",
-            scenario.name,
-            output.status.code().unwrap_or(-1)
+            scenario.name, output.exit_code
         )));
     }
 
-    debug!("📤 Command stdout length: {} bytes", stdout.len());
+    debug!("📤 Command stdout length: {} bytes", stdout.len);
+
+    // Whether the OTLP receiver already waited for in-flight exports
+    let mut otlp_flushed = false;
 
-    // Parse OTEL spans from stdout if artifacts.collect includes "spans:default"
+    // Collect OTEL spans if artifacts.collect includes "spans:<source>"
     if let Some(ref artifacts) = scenario.artifacts {
-        if artifacts.collect.iter().any(|a| a.starts_with("spans:")) {
-            info!("🔍 Parsing OTEL spans from stdout...");
-            let mut spans = StdoutSpanParser::parse(&stdout)?;
-            info!("✅ Collected {} span(s) from stdout", spans.len());
+        let mut sources: Vec<SpanSourceSpec> = Vec::new();
+        for spec in artifacts
+            .collect
+            .iter()
+            .filter_map(|a| SpanSourceSpec::parse(a))
+        {
+            if !sources.contains(&spec) {
+                sources.push(spec);
+            }
+        }
+        if !sources.is_empty() {
+            let mut spans = Vec::new();
+            for spec in sources {
+                let (label, collected) = match spec {
+                    SpanSourceSpec::Otlp => match otlp_receiver.as_ref() {
+                        Some(receiver) => {
+                            otlp_flushed = true;
+                            (
+                                "OTLP".to_string(),
+                                OtlpSpanSource::new(receiver, OTLP_FLUSH_GRACE)
+                                    .collect()
+                                    .await?,
+                            )
+                        }
+                        None => continue,
+                    },
+                    SpanSourceSpec::Stdout => (
+                        "stdout".to_string(),
+                        StdoutSpanSource::new(stdout_file.path()).collect().await?,
+                    ),
+                    SpanSourceSpec::File(path) => (
+                        path.display().to_string(),
+                        JsonFileSpanSource::new(path).collect().await?,
+                    ),
+                };
+                info!("✅ Collected {} span(s) from {}", collected.len(), label);
+                spans.extend(collected);
+            }
+
+            // Spans whose timestamps get filled in by a frozen clock below
+            let mut synthetic_timing = HashSet::new();
 
             // Apply determinism if configured
             if let Some(ref det_config) = test_config.determinism {
@@ -105,6 +542,11 @@ pub async fn execute_scenario(
                             frozen_timestamp.timestamp_nanos_opt().unwrap_or(0) as u64;
 
                         for span in &mut spans {
+                            if span.start_time_unix_nano.is_none()
+                                || span.end_time_unix_nano.is_none()
+                            {
+                                synthetic_timing.insert(span.span_id.clone());
+                            }
                             if span.start_time_unix_nano.is_none() {
                                 span.start_time_unix_nano = Some(frozen_nanos);
                             }
@@ -117,8 +559,13 @@ pub async fn execute_scenario(
                 }
             }
 
+            // Keep the spans with the run step, e.g. for `clnrm record`
+            step_results[run_step].spans = spans.clone();
+            env.coverage().record_spans(&spans).await;
+
             // Build expectations from test_config.expect
-            let expectations = build_prd_expectations(test_config)?;
+            let expectations =
+                build_prd_expectations(test_config)?.with_synthetic_timing(synthetic_timing);
 
             // Run all validations
             info!("🔬 Running validation layers...");
@@ -142,7 +589,7 @@ pub async fn execute_scenario(
             // Generate reports if configured
             if let Some(ref report_config) = test_config.report {
                 info!("📊 Generating reports...");
-                let report_cfg = ReportConfig::new()
+                let mut report_cfg = ReportConfig::new()
                     .with_json(
                         report_config
                             .json
@@ -164,6 +611,9 @@ pub async fn execute_scenario(
                             .unwrap_or(&"digest.txt".to_string())
                             .clone(),
                     );
+                if let Some(ref consolidated) = report_config.consolidated {
+                    report_cfg = report_cfg.with_consolidated(consolidated.clone());
+                }
 
                 let spans_json = serde_json::to_string_pretty(&spans).map_err(|e| {
                     CleanroomError::internal_error(format!(
@@ -172,7 +622,13 @@ pub async fn execute_scenario(
                     ))
                 })?;
 
-                generate_reports(&report_cfg, &validation_report, &spans_json)?;
+                let meta = ReportMeta::new(test_config.get_name()?)
+                    .with_version(test_config.get_version())
+                    .with_description(test_config.get_description())
+                    .with_scenario(&scenario.name)
+                    .with_seed(test_config.determinism.as_ref().and_then(|d| d.seed));
+
+                generate_reports(&report_cfg, &validation_report, &spans_json, &meta)?;
                 info!("✅ Reports generated successfully");
             }
 
@@ -187,10 +643,171 @@ pub async fn execute_scenario(
         }
     }
 
+    // Validate [[expect.metric]] against the metrics the scenario exported
+    let metric_expectations = build_metric_expectations(test_config)?;
+    if !metric_expectations.is_empty() {
+        let metrics = collect_metrics(scenario, otlp_receiver.as_ref(), otlp_flushed).await?;
+        let expectations = metric_expectations
+            .into_iter()
+            .fold(PrdExpectations::new(), PrdExpectations::add_metric);
+        let metric_report = expectations.validate_metrics(&metrics);
+        if !metric_report.is_success() {
+            error!("❌ Metrics: {}", metric_report.summary());
+            return Err(CleanroomError::validation_error(format!(
+                "Scenario '{}' validation failed: {}",
+                scenario.name,
+                metric_report.first_error().unwrap_or("unknown error")
+            )));
+        }
+        info!("✅ Metrics: {}", metric_report.summary());
+    }
+
+    check_query_assertions(scenario, env, service_handles).await?;
+
     info!("✅ Scenario '{}' completed successfully", scenario.name);
     Ok(())
 }
 
+/// A scenario command's stdout, read back from the file it was written to
+struct SpooledStdout {
+    /// Leading whole lines, up to [`REPORTED_STDOUT_LIMIT_BYTES`]
+    head: String,
+    /// Size of the whole output in bytes
+    len: u64,
+    /// Host a line shows blocked egress to, when egress is checked
+    egress: Option<String>,
+}
+
+/// Read `path` one line at a time, keeping the leading lines for the step
+/// result and, given `allowed_hosts`, checking each line for blocked egress
+async fn read_spooled_stdout(
+    path: &std::path::Path,
+    allowed_hosts: Option<Vec<String>>,
+) -> Result<SpooledStdout> {
+    let path = path.to_path_buf();
+    let read = move || -> std::io::Result<SpooledStdout> {
+        let mut reader = BufReader::new(std::fs::File::open(&path)?);
+        let mut stdout = SpooledStdout {
+            head: String::new(),
+            len: 0,
+            egress: None,
+        };
+        let mut line = Vec::new();
+        let mut head_full = false;
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                return Ok(stdout);
+            }
+            stdout.len += line.len() as u64;
+            let text = String::from_utf8_lossy(&line);
+            if let Some(allowed_hosts) = &allowed_hosts {
+                if stdout.egress.is_none() {
+                    stdout.egress = egress_violation(&text, allowed_hosts);
+                }
+            }
+            head_full = head_full || stdout.head.len() + text.len() > REPORTED_STDOUT_LIMIT_BYTES;
+            if !head_full {
+                stdout.head.push_str(&text);
+            }
+        }
+    };
+
+    tokio::task::spawn_blocking(read)
+        .await
+        .map_err(|e| CleanroomError::internal_error(format!("Failed to read stdout: {}", e)))?
+        .map_err(|e| CleanroomError::io_error(format!("Failed to read stdout: {}", e)))
+}
+
+/// Metrics from the scenario's `metrics:otlp` and `metrics:file:<path>`
+/// artifacts
+async fn collect_metrics(
+    scenario: &ScenarioConfig,
+    otlp_receiver: Option<&OtlpReceiver>,
+    otlp_flushed: bool,
+) -> Result<Vec<MetricData>> {
+    let artifacts = scenario
+        .artifacts
+        .as_ref()
+        .map(|a| a.collect.as_slice())
+        .unwrap_or_default();
+    let sources: Vec<&str> = artifacts
+        .iter()
+        .filter_map(|a| a.strip_prefix("metrics:"))
+        .collect();
+    if sources.is_empty() {
+        return Err(CleanroomError::config_error(format!(
+            "[[expect.metric]] needs scenario '{}' to collect \"metrics:otlp\" or \
+             \"metrics:file:<path>\" in artifacts.collect",
+            scenario.name
+        )));
+    }
+
+    let mut metrics = Vec::new();
+    for source in sources {
+        if let Some(path) = source.strip_prefix("file:") {
+            let content = tokio::fs::read_to_string(path).await.map_err(|e| {
+                CleanroomError::io_error(format!("Failed to read metrics file {}: {}", path, e))
+            })?;
+            let collected = parse_metrics_json(&content)?;
+            info!("✅ Collected {} metric(s) from {}", collected.len(), path);
+            metrics.extend(collected);
+        } else if let (Some(receiver), "otlp") = (otlp_receiver, source) {
+            if !otlp_flushed {
+                tokio::time::sleep(OTLP_FLUSH_GRACE).await;
+            }
+            let collected = receiver.metrics();
+            info!("✅ Collected {} metric(s) from OTLP", collected.len());
+            metrics.extend(collected);
+        } else {
+            warn!("Unknown metrics source 'metrics:{}', ignoring it", source);
+        }
+    }
+    Ok(metrics)
+}
+
+/// Run the scenario's `[[scenario.assertions]]` queries against their
+/// database services
+async fn check_query_assertions(
+    scenario: &ScenarioConfig,
+    env: &CleanroomEnvironment,
+    service_handles: &HashMap<String, crate::cleanroom::ServiceHandle>,
+) -> Result<()> {
+    for assertion in &scenario.assertions {
+        let service_name = assertion
+            .service
+            .as_ref()
+            .or(scenario.service.as_ref())
+            .ok_or_else(|| {
+                CleanroomError::validation_error(format!(
+                    "Scenario '{}' assertion '{}' has no service to query",
+                    scenario.name, assertion.query
+                ))
+            })?;
+        let handle = service_handles.get(service_name).ok_or_else(|| {
+            CleanroomError::validation_error(format!(
+                "Scenario '{}' assertion references unknown service '{}'",
+                scenario.name, service_name
+            ))
+        })?;
+
+        let service = DatabaseService::new(env, handle);
+        let result = match (assertion.row_count, &assertion.equals) {
+            (Some(expected), _) => assert_row_count(&service, &assertion.query, expected).await,
+            (None, Some(expected)) => assert_query_eq(&service, &assertion.query, expected).await,
+            (None, None) => assertion.validate(),
+        };
+        result.map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Scenario '{}' assertion failed: {}",
+                scenario.name, e.message
+            ))
+        })?;
+        info!("✅ Assertion passed: {}", assertion.query);
+    }
+    Ok(())
+}
+
 /// Build PrdExpectations from TestConfig.expect
 fn build_prd_expectations(test_config: &crate::config::TestConfig) -> Result<PrdExpectations> {
     let mut expectations = PrdExpectations::new();
@@ -292,7 +909,125 @@ fn build_prd_expectations(test_config: &crate::config::TestConfig) -> Result<Prd
             };
             expectations = expectations.with_hermeticity(hermetic);
         }
+
+        // Build attribute expectations
+        for attr_config in &expect.attributes {
+            let attribute = match (&attr_config.value, &attr_config.value_regex) {
+                (Some(value), None) => {
+                    AttributeExpectation::equals(&attr_config.span, &attr_config.key, value)
+                }
+                (None, Some(pattern)) => {
+                    AttributeExpectation::matches(&attr_config.span, &attr_config.key, pattern)?
+                }
+                _ => {
+                    return Err(CleanroomError::config_error(format!(
+                        "[[expect.attributes]] for span '{}' key '{}' must set exactly one of \
+                         'value' or 'value_regex'",
+                        attr_config.span, attr_config.key
+                    )))
+                }
+            };
+            expectations = expectations.add_attribute(attribute);
+        }
+
+        // Build duration expectations
+        for duration_config in &expect.duration {
+            if duration_config.min_ms.is_none() && duration_config.max_ms.is_none() {
+                return Err(CleanroomError::config_error(format!(
+                    "[[expect.duration]] for span '{}' must set 'min_ms' and/or 'max_ms'",
+                    duration_config.span
+                )));
+            }
+            if let (Some(min), Some(max)) = (duration_config.min_ms, duration_config.max_ms) {
+                if min > max {
+                    return Err(CleanroomError::config_error(format!(
+                        "[[expect.duration]] for span '{}' has min_ms ({}) greater than max_ms ({})",
+                        duration_config.span, min, max
+                    )));
+                }
+            }
+
+            let mut duration = DurationExpectation::new(&duration_config.span);
+            if let Some(min) = duration_config.min_ms {
+                duration = duration.with_min_ms(min);
+            }
+            if let Some(max) = duration_config.max_ms {
+                duration = duration.with_max_ms(max);
+            }
+            expectations = expectations.add_duration(duration);
+        }
+
+        // Build event expectations
+        for event_config in &expect.event {
+            let event = event_config.attributes.iter().fold(
+                EventAssertion::new(&event_config.span, &event_config.name),
+                |event, (key, value)| event.with_attribute(key, value),
+            );
+            expectations = expectations.add_event(event);
+        }
+    }
+
+    for metric in build_metric_expectations(test_config)? {
+        expectations = expectations.add_metric(metric);
     }
 
     Ok(expectations)
 }
+
+/// Build metric expectations from `[[expect.metric]]`
+fn build_metric_expectations(
+    test_config: &crate::config::TestConfig,
+) -> Result<Vec<MetricExpectation>> {
+    let Some(ref expect) = test_config.expect else {
+        return Ok(Vec::new());
+    };
+
+    expect
+        .metric
+        .iter()
+        .map(|metric_config| {
+            let mut metric = metric_config.attributes.iter().fold(
+                MetricExpectation::new(&metric_config.name),
+                |metric, (key, value)| metric.with_attribute(key, value),
+            );
+            if let Some(value) = metric_config.value {
+                metric = metric.with_value(value);
+            }
+            if let Some(min) = metric_config.min_value {
+                metric = metric.with_min_value(min);
+            }
+            if let Some(max) = metric_config.max_value {
+                metric = metric.with_max_value(max);
+            }
+            if let Some(count) = metric_config.count {
+                metric = metric.with_count(count);
+            }
+            if let Some(min) = metric_config.min_count {
+                metric = metric.with_min_count(min);
+            }
+
+            if !metric.has_bounds() {
+                return Err(CleanroomError::config_error(format!(
+                    "[[expect.metric]] for '{}' must set 'value', 'min_value', 'max_value', \
+                     'count' or 'min_count'",
+                    metric_config.name
+                )));
+            }
+            Ok(metric)
+        })
+        .collect()
+}
+
+/// Address for the scenario OTLP receiver: all interfaces, on the port of the
+/// test's `[otel] endpoint` if one is set, so containers exporting to that
+/// endpoint via the host reach the receiver
+fn otlp_receiver_endpoint(test_config: &crate::config::TestConfig) -> String {
+    test_config
+        .otel
+        .as_ref()
+        .and_then(|otel| otel.endpoint.as_deref())
+        .and_then(|endpoint| url::Url::parse(endpoint).ok())
+        .and_then(|url| url.port())
+        .map(|port| format!("0.0.0.0:{}", port))
+        .unwrap_or_else(|| DEFAULT_OTLP_HTTP_ENDPOINT.to_string())
+}
@@ -3,112 +3,284 @@
 //! Handles loading services from configuration and registering them with the
 //! cleanroom environment.
 
-use crate::cleanroom::CleanroomEnvironment;
+use crate::cleanroom::{CleanroomEnvironment, ServiceHandle, ServicePlugin};
+use crate::config::ServiceConfig;
 use crate::error::{CleanroomError, Result};
+use crate::services::keep::short_container_id;
 use crate::telemetry::spans;
-use std::collections::HashMap;
-use tracing::{debug, info};
+use futures_util::future::try_join_all;
+use std::collections::{BTreeMap, HashMap};
+use tracing::{debug, info, warn, Instrument};
 
 /// Load services from configuration and register them with the environment
+///
+/// Services are registered in name order so logs are deterministic, then
+/// started with [`start_services`].
+///
+/// If the environment runs commands on a network (see
+/// [`CleanroomEnvironment::set_network`]), generic container services join it.
 pub async fn load_services_from_config(
     env: &CleanroomEnvironment,
-    services: &HashMap<String, crate::config::ServiceConfig>,
-) -> Result<HashMap<String, crate::cleanroom::ServiceHandle>> {
-    let mut service_handles = HashMap::new();
+    services: &HashMap<String, ServiceConfig>,
+) -> Result<HashMap<String, ServiceHandle>> {
+    let network = env.network().await;
 
-    for (service_name, service_config) in services {
-        debug!(
-            "Loading service: {} (type: {}, plugin: {})",
-            service_name, service_config.plugin, service_config.plugin
-        );
+    let mut ordered: Vec<(&String, &ServiceConfig)> = services.iter().collect();
+    ordered.sort_by(|a, b| a.0.cmp(b.0));
+
+    for (service_name, service_config) in ordered {
+        let plugin = create_service_plugin(service_name, service_config, network.as_deref())?;
+        if network.is_some() && service_config.plugin != "generic_container" {
+            warn!(
+                "⚠️  Service '{}' ({}) isn't isolated: only generic containers join the hermetic network",
+                service_name, service_config.plugin
+            );
+        }
+        env.register_service(plugin).await?;
+        info!("📦 Registered service plugin: {}", service_name);
+    }
 
-        // Create plugin based on service type
-        let plugin: Box<dyn crate::cleanroom::ServicePlugin> =
-            match service_config.plugin.as_str() {
-                "surrealdb" => {
-                    use crate::services::surrealdb::SurrealDbPlugin;
+    start_services(env, services).await
+}
 
-                    let username = service_config.username.as_deref().unwrap_or("root");
-                    let password = service_config.password.as_deref().unwrap_or("root");
-                    let strict = service_config.strict.unwrap_or(false);
+/// Start services already registered with the environment
+///
+/// Services start stage by stage according to `startup_order` (default 0).
+/// All services within a stage start concurrently; a stage only begins once
+/// every service in the previous stage is up.
+pub async fn start_services(
+    env: &CleanroomEnvironment,
+    services: &HashMap<String, ServiceConfig>,
+) -> Result<HashMap<String, ServiceHandle>> {
+    let mut service_handles = HashMap::new();
 
-                    let plugin = SurrealDbPlugin::with_credentials(username, password)
-                        .with_name(service_name)
-                        .with_strict(strict);
+    let mut ordered: Vec<(&String, &ServiceConfig)> = services.iter().collect();
+    ordered.sort_by(|a, b| a.0.cmp(b.0));
 
-                    Box::new(plugin)
-                }
-                "generic_container" => {
-                    use crate::services::generic::GenericContainerPlugin;
+    let mut stages: BTreeMap<u32, Vec<(&String, &ServiceConfig)>> = BTreeMap::new();
+    for (service_name, service_config) in ordered {
+        stages
+            .entry(service_config.startup_order.unwrap_or(0))
+            .or_default()
+            .push((service_name, service_config));
+    }
 
-                    let image = service_config.image.as_deref().ok_or_else(|| {
-                        CleanroomError::validation_error(format!(
-                            "Service '{}': generic_container requires 'image' field",
+    for (stage, stage_services) in stages {
+        debug!(
+            "Starting {} service(s) in startup stage {}",
+            stage_services.len(),
+            stage
+        );
+
+        let started = try_join_all(stage_services.into_iter().map(
+            |(service_name, service_config)| {
+                let service_span = spans::service_start_span(service_name, &service_config.plugin);
+                async move {
+                    let handle = env.start_service(service_name).await.map_err(|e| {
+                        CleanroomError::service_error(format!(
+                            "Failed to start service '{}'",
                             service_name
                         ))
+                        .with_context("Service startup failed")
+                        .with_source(e.to_string())
                     })?;
 
-                    let mut plugin = GenericContainerPlugin::new(service_name, image);
-
-                    if let Some(env_vars) = &service_config.env {
-                        for (key, value) in env_vars {
-                            plugin = plugin.with_env(key, value);
-                        }
-                    }
-
-                    if let Some(ports) = &service_config.ports {
-                        for port in ports {
-                            plugin = plugin.with_port(*port);
-                        }
-                    }
-
-                    if let Some(volumes) = &service_config.volumes {
-                        for volume in volumes {
-                            plugin = plugin
-                                .with_volume(
-                                    &volume.host_path,
-                                    &volume.container_path,
-                                    volume.read_only.unwrap_or(false),
-                                )
-                                .map_err(|e| {
-                                    CleanroomError::validation_error(format!(
-                                        "Service '{}': invalid volume configuration: {}",
-                                        service_name, e
-                                    ))
-                                })?;
-                        }
-                    }
-
-                    Box::new(plugin)
+                    info!(
+                        "✅ Service '{}' started successfully (handle: {})",
+                        service_name, handle.id
+                    );
+
+                    Ok::<_, CleanroomError>((service_name.clone(), handle))
+                }
+                .instrument(service_span)
+            },
+        ))
+        .await?;
+
+        service_handles.extend(started);
+    }
+
+    Ok(service_handles)
+}
+
+/// Leave a failed test's services running for `--keep-services`
+///
+/// Logs each kept container with the commands to inspect and remove it.
+pub async fn keep_services(
+    env: &CleanroomEnvironment,
+    service_handles: &HashMap<String, ServiceHandle>,
+) {
+    let mut handles: Vec<_> = service_handles.iter().collect();
+    handles.sort_by(|a, b| a.0.cmp(b.0));
+
+    warn!("🔍 Test failed - keeping services running for inspection (--keep-services)");
+    for (service_name, handle) in handles {
+        match env.keep_service(&handle.id).await {
+            Ok(Some(container_id)) => {
+                warn!(
+                    "📦 Service '{}' left running (container {})",
+                    service_name,
+                    short_container_id(&container_id)
+                );
+                warn!(
+                    "   Inspect with: clnrm services exec {} -- <command>",
+                    service_name
+                );
+            }
+            Ok(None) => {
+                warn!(
+                    "⚠️  Service '{}' (handle: {}) has no container to keep",
+                    service_name, handle.id
+                );
+            }
+            Err(e) => {
+                warn!("⚠️  Failed to keep service '{}': {}", service_name, e);
+            }
+        }
+    }
+    warn!("🧹 Remove kept services with: clnrm services down");
+}
+
+/// Create a service plugin from its configuration
+///
+/// Generic containers join `network` when one is given.
+pub fn create_service_plugin(
+    service_name: &str,
+    service_config: &ServiceConfig,
+    network: Option<&str>,
+) -> Result<Box<dyn ServicePlugin>> {
+    debug!(
+        "Loading service: {} (type: {}, plugin: {})",
+        service_name, service_config.plugin, service_config.plugin
+    );
+
+    // Create plugin based on service type
+    let plugin: Box<dyn ServicePlugin> = match service_config.plugin.as_str() {
+        "surrealdb" => {
+            use crate::services::surrealdb::SurrealDbPlugin;
+
+            let username = service_config.username.as_deref().unwrap_or("root");
+            let password = service_config.password.as_deref().unwrap_or("root");
+            let strict = service_config.strict.unwrap_or(false);
+
+            let plugin = SurrealDbPlugin::with_credentials(username, password)
+                .with_name(service_name)
+                .with_strict(strict);
+
+            Box::new(plugin)
+        }
+        "postgres" => {
+            use crate::services::postgres::PostgresPlugin;
+
+            let mut plugin = PostgresPlugin::new(service_name);
+
+            if let Some(image) = &service_config.image {
+                plugin = plugin.with_image(image);
+            }
+            if let Some(database) = &service_config.database {
+                plugin = plugin.with_database(database);
+            }
+            if let Some(username) = &service_config.username {
+                plugin = plugin.with_user(username);
+            }
+            if let Some(password) = &service_config.password {
+                plugin = plugin.with_password(password);
+            }
+            if let Some(init_sql) = &service_config.init_sql {
+                plugin = plugin.with_init_sql(init_sql);
+            }
+
+            Box::new(plugin)
+        }
+        "web_server" => {
+            use crate::services::web_server::WebServerPlugin;
+
+            let mut plugin = WebServerPlugin::new(service_name);
+
+            if let Some(image) = &service_config.image {
+                plugin = plugin.with_image(image);
+            }
+            if let Some(port) = service_config
+                .ports
+                .as_ref()
+                .and_then(|ports| ports.first())
+            {
+                plugin = plugin.with_port(*port);
+            }
+            if let Some(static_dir) = &service_config.static_dir {
+                plugin = plugin.with_static_root(static_dir);
+            }
+
+            Box::new(plugin)
+        }
+        "generic_container" => {
+            use crate::services::generic::GenericContainerPlugin;
+
+            let image = service_config.image.as_deref().ok_or_else(|| {
+                CleanroomError::validation_error(format!(
+                    "Service '{}': generic_container requires 'image' field",
+                    service_name
+                ))
+            })?;
+
+            let mut plugin = GenericContainerPlugin::new(service_name, image);
+
+            if let Some(env_vars) = &service_config.env {
+                for (key, value) in env_vars {
+                    plugin = plugin.with_env(key, value);
                 }
-                _ => {
-                    return Err(CleanroomError::validation_error(format!(
-                        "Unknown service plugin: {}",
-                        service_config.plugin
-                    )));
+            }
+
+            if let Some(ports) = &service_config.ports {
+                for port in ports {
+                    plugin = plugin.with_port(*port);
                 }
-            };
+            }
 
-        env.register_service(plugin).await?;
-        info!("📦 Registered service plugin: {}", service_name);
+            if let Some(volumes) = &service_config.volumes {
+                for volume in volumes {
+                    plugin = plugin
+                        .with_volume(
+                            &volume.host_path,
+                            &volume.container_path,
+                            volume.read_only.unwrap_or(false),
+                        )
+                        .map_err(|e| {
+                            CleanroomError::validation_error(format!(
+                                "Service '{}': invalid volume configuration: {}",
+                                service_name, e
+                            ))
+                        })?;
+                }
+            }
 
-        let service_span = spans::service_start_span(service_name, &service_config.plugin);
+            if let Some(bytes) = service_config.memory_limit_bytes().map_err(|e| {
+                CleanroomError::validation_error(format!("Service '{}': {}", service_name, e))
+            })? {
+                plugin = plugin.with_memory_limit(bytes);
+            }
 
-        let _service_guard = service_span.enter();
+            if let Some(cpus) = service_config.cpus {
+                plugin = plugin.with_cpu_limit(cpus);
+            }
 
-        let handle = env.start_service(service_name).await.map_err(|e| {
-            CleanroomError::service_error(format!("Failed to start service '{}'", service_name))
-                .with_context("Service startup failed")
-                .with_source(e.to_string())
-        })?;
+            if let Some(health_check) = &service_config.health_check {
+                plugin = plugin.with_health_check(health_check.clone());
+            }
 
-        info!(
-            "✅ Service '{}' started successfully (handle: {})",
-            service_name, handle.id
-        );
+            if let Some(network) = network {
+                plugin = plugin.with_network(network);
+            }
 
-        service_handles.insert(service_name.clone(), handle);
-    }
+            Box::new(plugin)
+        }
+        _ => {
+            return Err(CleanroomError::validation_error(format!(
+                "Unknown service plugin: {}",
+                service_config.plugin
+            )));
+        }
+    };
 
-    Ok(service_handles)
+    Ok(plugin)
 }
@@ -0,0 +1,109 @@
+//! Test sharding across runners
+//!
+//! `--shard i/m` selects the tests runner `i` of `m` executes. Two strategies
+//! decide the split:
+//! - `modulo`: tests are dealt round-robin in discovery order
+//! - `duration`: tests are balanced by the durations the cache recorded on
+//!   previous runs, using greedy longest-processing-time assignment
+//!
+//! Every runner must compute the same split, so both strategies are
+//! deterministic for a given test list and cache.
+
+use crate::cache::CacheManager;
+use crate::cli::types::ShardStrategy;
+use crate::error::Result;
+use std::path::PathBuf;
+use tracing::{info, warn};
+
+/// Select the tests shard `i` (1-based) of `m` should run
+pub fn shard_tests(
+    tests: Vec<PathBuf>,
+    (i, m): (usize, usize),
+    strategy: &ShardStrategy,
+    cache_manager: &CacheManager,
+) -> Result<Vec<PathBuf>> {
+    info!("🔀 Applying shard {}/{} to {} tests", i, m, tests.len());
+
+    let sharded_tests = match strategy {
+        ShardStrategy::Modulo => modulo_shard(tests, i, m),
+        ShardStrategy::Duration => {
+            let durations = tests
+                .iter()
+                .map(|test| cache_manager.duration(test))
+                .collect::<Result<Vec<_>>>()?;
+
+            if durations.iter().all(Option::is_none) {
+                warn!("No recorded test durations in the cache - falling back to modulo sharding");
+                modulo_shard(tests, i, m)
+            } else {
+                duration_shard(tests, &durations, i, m)
+            }
+        }
+    };
+
+    info!(
+        "🔀 Shard {}/{} will run {} test(s)",
+        i,
+        m,
+        sharded_tests.len()
+    );
+    Ok(sharded_tests)
+}
+
+/// Shard i (1-based) gets tests where (index % m) == (i - 1)
+fn modulo_shard(tests: Vec<PathBuf>, i: usize, m: usize) -> Vec<PathBuf> {
+    tests
+        .into_iter()
+        .enumerate()
+        .filter(|(idx, _)| (idx % m) == (i - 1))
+        .map(|(_, path)| path)
+        .collect()
+}
+
+/// Longest-processing-time bin packing over recorded durations
+///
+/// Tests are taken longest first and each goes to the shard with the least
+/// total time so far. Tests with no recorded duration (e.g. new files) are
+/// estimated at the mean of those with one.
+fn duration_shard(
+    tests: Vec<PathBuf>,
+    durations: &[Option<u64>],
+    i: usize,
+    m: usize,
+) -> Vec<PathBuf> {
+    let known: Vec<u64> = durations.iter().flatten().copied().collect();
+    let estimate = known.iter().sum::<u64>() / (known.len().max(1) as u64);
+
+    let mut weighted: Vec<(u64, PathBuf)> = tests
+        .into_iter()
+        .zip(durations)
+        .map(|(test, duration)| (duration.unwrap_or(estimate), test))
+        .collect();
+    // Ties are broken by path so every runner computes the same split
+    weighted.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+
+    let mut loads = vec![0u64; m];
+    let mut sharded_tests = Vec::new();
+    for (duration, test) in weighted {
+        let shard = loads
+            .iter()
+            .enumerate()
+            .min_by_key(|&(idx, &load)| (load, idx))
+            .map_or(0, |(idx, _)| idx);
+
+        loads[shard] += duration;
+        if shard == i - 1 {
+            sharded_tests.push(test);
+        }
+    }
+
+    info!(
+        "🔀 Shard {}/{} estimated at {:.1}s (shards range {:.1}s-{:.1}s)",
+        i,
+        m,
+        loads[i - 1] as f64 / 1000.0,
+        loads.iter().min().copied().unwrap_or(0) as f64 / 1000.0,
+        loads.iter().max().copied().unwrap_or(0) as f64 / 1000.0,
+    );
+    sharded_tests
+}
@@ -3,30 +3,185 @@
 //! Handles execution of individual test files with proper error handling,
 //! template rendering, and service management.
 
-use crate::cleanroom::CleanroomEnvironment;
+use crate::backend::network::{egress_violation, HermeticNetwork};
+use crate::cleanroom::{CleanroomEnvironment, FailureReason};
 use crate::cli::types::CliConfig;
+use crate::config::{MetaConfig, StepConfig, TestConfig};
 use crate::error::{CleanroomError, Result};
+use crate::otel::redact::SpanRedactor;
+use crate::scenario::StepResult;
 use crate::telemetry::spans;
+use crate::testing::TestResult;
+use crate::TemplateRenderer;
 use std::collections::HashMap;
-use std::path::PathBuf;
-use tracing::{debug, info, warn};
+use std::path::{Path, PathBuf};
+use tracing::{debug, error, info, warn};
 
-use super::{scenario, services};
+use super::{data, scenario, services};
+
+/// Default delay before the first retry of a failed step
+const DEFAULT_RETRY_DELAY_MS: u64 = 500;
+
+/// Run a single test file and return its structured result
+///
+/// Test failures are reported through `TestResult::passed` and
+/// `TestResult::error` rather than as an `Err`, so callers embedding clnrm
+/// can aggregate results across files without scraping logs. The test name
+/// is the file name, matching the CLI report output.
+///
+/// # Example
+///
+/// ```no_run
+/// # use clnrm_core::cli::commands::run::run_test_file;
+/// # use clnrm_core::cli::types::CliConfig;
+/// # use std::path::Path;
+/// # async fn example() -> clnrm_core::error::Result<()> {
+/// let result = run_test_file(Path::new("tests/basic.clnrm.toml"), &CliConfig::default()).await?;
+/// println!("{}: passed={} ({}ms)", result.name, result.passed, result.duration_ms);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run_test_file(path: &Path, config: &CliConfig) -> Result<TestResult> {
+    let test_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string();
+
+    let start_time = std::time::Instant::now();
+    let mut steps = Vec::new();
+    let outcome = run_single_test_with_steps(&path.to_path_buf(), config, &mut steps).await;
+    let duration_ms = start_time.elapsed().as_millis() as u64;
+
+    Ok(match outcome {
+        Ok(()) => TestResult {
+            name: test_name,
+            passed: true,
+            duration_ms,
+            error: None,
+            steps,
+        },
+        Err(e) => TestResult {
+            name: test_name,
+            passed: false,
+            duration_ms,
+            error: Some(e.to_string()),
+            steps,
+        },
+    })
+}
 
 /// Run a single test file
-#[tracing::instrument(name = "clnrm.test", skip(_config), fields(test.hermetic = true))]
-pub async fn run_single_test(path: &PathBuf, _config: &CliConfig) -> Result<()> {
-    let content = std::fs::read_to_string(path).map_err(|e| {
-        CleanroomError::config_error(format!("Failed to read config file: {}", e))
-    })?;
+pub async fn run_single_test(path: &PathBuf, config: &CliConfig) -> Result<()> {
+    run_single_test_with_steps(path, config, &mut Vec::new()).await
+}
+
+/// Run a single test file, recording a `StepResult` for each `[[steps]]` entry
+///
+/// Results are pushed as each step finishes, so on failure `step_results`
+/// still holds every step up to and including the one that failed. A test
+/// with a `[matrix]` runs once per combination; every case runs even if an
+/// earlier one failed, and the test fails if any case did.
+async fn run_single_test_with_steps(
+    path: &PathBuf,
+    config: &CliConfig,
+    step_results: &mut Vec<StepResult>,
+) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| CleanroomError::config_error(format!("Failed to read config file: {}", e)))?;
+
+    let test_config = crate::config::parse_config_for_path(path, &content)?;
+    if test_config.matrix.is_none() {
+        return run_test_config(path, config, test_config, step_results).await;
+    }
 
-    let test_config: crate::config::TestConfig = toml::from_str(&content)
-        .map_err(|e| CleanroomError::config_error(format!("TOML parse error: {}", e)))?;
+    let cases = crate::config::expand_matrix(&test_config)?;
+    info!("🧮 Matrix expands to {} case(s)", cases.len());
+
+    let total = cases.len();
+    let mut failed = Vec::new();
+    let mut first_error = None;
+    for case in cases {
+        let case_name = case.get_name()?;
+        if let Err(e) = run_test_config(path, config, case, step_results).await {
+            error!("❌ Matrix case '{}' failed: {}", case_name, e);
+            failed.push(case_name);
+            first_error.get_or_insert(e);
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e.with_context(format!(
+            "{} of {} matrix case(s) failed: {}",
+            failed.len(),
+            total,
+            failed.join(", ")
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Run one parsed test config, e.g. a single matrix case
+#[tracing::instrument(
+    name = "clnrm.test",
+    skip(config, test_config, step_results),
+    fields(test.hermetic = true)
+)]
+async fn run_test_config(
+    path: &PathBuf,
+    config: &CliConfig,
+    mut test_config: TestConfig,
+    step_results: &mut Vec<StepResult>,
+) -> Result<()> {
+    // --seed overrides (or enables) seeded determinism for this run
+    if let Some(seed) = config.seed {
+        test_config
+            .determinism
+            .get_or_insert(crate::config::DeterminismConfig {
+                seed: None,
+                freeze_clock: None,
+            })
+            .seed = Some(seed);
+    }
+
+    // Limits are checked before any container starts
+    let run_start = std::time::Instant::now();
+    let mut max_duration = None;
+    if let Some(limits) = test_config.limits.clone() {
+        limits.validate(&test_config)?;
+        max_duration = limits.max_duration_ms.map(std::time::Duration::from_millis);
+
+        if let Some(max_memory) = limits.max_memory_bytes {
+            let services = test_config
+                .services
+                .iter_mut()
+                .chain(test_config.service.iter_mut());
+            for service in services.flat_map(|services| services.values_mut()) {
+                service.memory.get_or_insert_with(|| max_memory.to_string());
+            }
+        }
+    }
+
+    // `init_sql` paths are relative to the test file
+    let test_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    let services = test_config
+        .services
+        .iter_mut()
+        .chain(test_config.service.iter_mut());
+    for service in services.flat_map(|services| services.values_mut()) {
+        if let Some(init_sql) = service.init_sql.as_mut() {
+            *init_sql = test_dir.join(&*init_sql).display().to_string();
+        }
+    }
 
     let test_name = test_config.get_name()?;
 
     tracing::Span::current().record("test.name", &test_name);
 
+    // Spans emitted while the test runs go to its own [otel] collector;
+    // dropping the guard when the test finishes exports them
+    let test_telemetry = crate::telemetry::test_run::init_test_telemetry(&test_config)?;
+
     info!("🚀 Executing test: {}", test_name);
     info!("🚀 Executing test: {}", test_name);
 
@@ -37,12 +192,31 @@ pub async fn run_single_test(path: &PathBuf, _config: &CliConfig) -> Result<()>
 
     // Create template renderer with vars from test config
     let mut template_renderer = crate::TemplateRenderer::new()?;
+    if let Some(env_file) = &config.env_file {
+        template_renderer = template_renderer.with_env_file(env_file)?;
+    }
     if let Some(vars) = &test_config.vars {
         template_renderer.merge_user_vars(vars.clone());
     }
+    if let Some(vars_file) = &config.vars_file {
+        template_renderer.merge_user_vars(crate::TemplateContext::from_json_file(vars_file)?.vars);
+    }
+    if let Some(det_config) = test_config
+        .determinism
+        .as_ref()
+        .filter(|d| d.is_deterministic())
+    {
+        let engine = crate::determinism::DeterminismEngine::new(det_config.clone())?;
+        template_renderer = template_renderer.with_determinism(std::sync::Arc::new(engine));
+    }
 
-    // Load cleanroom configuration for default container settings
-    let cleanroom_config = match crate::config::load_cleanroom_config() {
+    // Load cleanroom configuration for default container settings, unless
+    // the caller supplied one
+    let cleanroom_config = match config
+        .cleanroom
+        .clone()
+        .map_or_else(crate::config::load_cleanroom_config, Ok)
+    {
         Ok(config) => {
             info!(
                 "Successfully loaded cleanroom config with default_image: {}",
@@ -63,110 +237,384 @@ pub async fn run_single_test(path: &PathBuf, _config: &CliConfig) -> Result<()>
                 .with_context("Test execution requires cleanroom environment")
                 .with_source(e.to_string())
         })?;
+    if let Some(test_telemetry) = &test_telemetry {
+        environment
+            .set_tracer_provider(Some(test_telemetry.tracer_provider().clone()))
+            .await;
+    }
+
+    // [policy] allow/deny lists are checked before each step runs
+    let policy = test_config.policy.as_ref().map(|policy| policy.to_policy());
+
+    // Steps see no host environment beyond what is allowlisted
+    let inherited_env = inherited_env(config, test_config.meta.as_ref())?;
+
+    // [expect.hermeticity] enforce_network runs steps without network egress;
+    // services join the network so steps can still reach them
+    let hermetic_network = if test_config.enforces_network() {
+        let network = HermeticNetwork::create().await?;
+        info!("🔒 Running steps on hermetic network '{}'", network.name());
+        environment
+            .set_network(Some(network.name().to_string()))
+            .await;
+        Some(network)
+    } else {
+        None
+    };
 
     // Load services from config (support both v0.4.x [services] and v1.0 [service] formats)
-    let service_handles = if let Some(services) = &test_config.services {
-        services::load_services_from_config(&environment, services).await?
+    let loaded = if let Some(services) = &test_config.services {
+        services::load_services_from_config(&environment, services).await
     } else if let Some(services) = &test_config.service {
         // v1.0 format: [service.name]
-        services::load_services_from_config(&environment, services).await?
+        services::load_services_from_config(&environment, services).await
     } else {
-        HashMap::new()
+        Ok(HashMap::new())
+    };
+    let service_handles = match loaded {
+        Ok(handles) => handles,
+        Err(e) => {
+            remove_hermetic_network(hermetic_network).await;
+            return Err(e);
+        }
     };
 
-    // Execute test steps
-    for (i, step) in test_config.steps.iter().enumerate() {
-        info!("📋 Step {}: {}", i + 1, step.name);
+    // Steps and scenarios run in one block so services are handled below
+    // whether or not the test failed
+    let outcome: Result<()> = async {
+        // Execute test steps
+        let test_start = std::time::Instant::now();
+        let mut step_error = None;
+        'steps: for (i, step) in test_config.steps.iter().enumerate() {
+            remaining_duration(max_duration, run_start)?;
+            info!("📋 Step {}: {}", i + 1, step.name);
 
-        if step.command.is_empty() {
-            return Err(CleanroomError::validation_error(format!(
-                "Step '{}' has empty command",
-                step.name
-            )));
-        }
+            if config.scenario_only {
+                info!("⏭️  Skipping step '{}' (--scenario-only)", step.name);
+                step_results.push(StepResult::skipped(&step.name, &test_name));
+                continue;
+            }
+
+            if step.command.is_empty() {
+                return Err(CleanroomError::validation_error(format!(
+                    "Step '{}' has empty command",
+                    step.name
+                )));
+            }
+
+            if let Some(reason) = skip_reason(step, &mut template_renderer)? {
+                info!("⏭️  Skipping step '{}' ({})", step.name, reason);
+                step_results.push(StepResult::skipped(&step.name, &test_name));
+                continue;
+            }
+
+            // Render command templates with vars context
+            let rendered_command: Vec<String> = step
+                .command
+                .iter()
+                .map(|arg| {
+                    template_renderer
+                        .render_str(arg, &format!("step_{}_arg", step.name))
+                        .map_err(|e| e.into())
+                })
+                .collect::<std::result::Result<Vec<String>, CleanroomError>>()?;
 
-        // Render command templates with vars context
-          let rendered_command: Vec<String> = step
-            .command
-              .iter()
-              .map(|arg| template_renderer.render_str(arg, &format!("step_{}_arg", step.name)).map_err(|e| e.into()))
-              .collect::<std::result::Result<Vec<String>, CleanroomError>>()?;
+            let workdir = step
+                .workdir
+                .as_ref()
+                .map(|workdir| {
+                    template_renderer.render_str(workdir, &format!("step_{}_workdir", step.name))
+                })
+                .transpose()?;
 
-        info!("🔧 Executing: {}", rendered_command.join(" "));
-        info!("🔧 Executing: {}", rendered_command.join(" "));
+            // Env values are rendered like command args, and override
+            // inherited host variables of the same name
+            let mut step_env = inherited_env.clone();
+            for (key, value) in step.env.iter().flatten() {
+                let value = template_renderer
+                    .render_str(value, &format!("step_{}_env_{}", step.name, key))?;
+                step_env.insert(key.clone(), value);
+            }
+
+            if let Some(policy) = &policy {
+                if let Err(e) = policy.check_command(&rendered_command) {
+                    let e = CleanroomError::validation_error(format!(
+                        "Step '{}' blocked by [policy]: {}",
+                        step.name, e.message
+                    ));
+                    step_results.push(StepResult::failed(&step.name, &test_name, e.to_string()));
+                    continue_past(step, e, &mut step_error)?;
+                    continue;
+                }
+            }
+
+            // [meta]/step `redact` masks output everywhere except the
+            // expected_output_regex match
+            let redactor = step.output_redactor(test_config.meta.as_ref())?;
 
-        let command_span = spans::command_execute_span(&rendered_command.join(" "));
+            info!("🔧 Executing: {}", rendered_command.join(" "));
+            info!("🔧 Executing: {}", rendered_command.join(" "));
 
-        let _command_guard = command_span.enter();
+            let command_span = spans::command_execute_span(&rendered_command.join(" "));
+
+            let _command_guard = command_span.enter();
 
-        let stdout = {
             // Execute command in a fresh container for proper isolation
             // Core Team Compliance: Use async for I/O, proper error handling, no unwrap/expect
             let container_name = format!("test-{}-step-{}", test_name, step.name);
-            let execution_result = environment
-                .execute_in_container(&container_name, &rendered_command)
-                .await
-                .map_err(|e| {
-                    CleanroomError::container_error(format!(
-                        "Failed to execute command '{}' in container '{}': {}",
-                        rendered_command.join(" "),
-                        container_name,
-                        e
-                    ))
-                })?;
+            let start_ts = test_start.elapsed().as_millis() as u64;
+            let step_start = std::time::Instant::now();
+            let max_retries = step.retries.unwrap_or(0);
+            let retry_delay_ms = step.retry_delay_ms.unwrap_or(DEFAULT_RETRY_DELAY_MS);
+            let mut retries = 0;
+            let execution_result = loop {
+                if max_retries > 0 {
+                    info!(
+                        "🔁 Step '{}' attempt {}/{}",
+                        step.name,
+                        retries + 1,
+                        max_retries + 1
+                    );
+                }
+
+                // The step may not outlive the test's own duration limit
+                let step_timeout = match (
+                    step.timeout_ms.map(std::time::Duration::from_millis),
+                    remaining_duration(max_duration, run_start)?,
+                ) {
+                    (Some(step_timeout), Some(remaining)) => Some(step_timeout.min(remaining)),
+                    (step_timeout, remaining) => step_timeout.or(remaining),
+                };
+
+                let attempt_start = std::time::Instant::now();
+                let executed = environment
+                    .execute_in_container_with_env(
+                        &container_name,
+                        &rendered_command,
+                        &step_env,
+                        workdir.as_deref(),
+                        step_timeout,
+                    )
+                    .await;
+                let execution_result = match executed {
+                    Ok(execution_result) => execution_result,
+                    Err(e) => {
+                        // Running out of the test's duration limit counts as a timeout
+                        let remaining = remaining_duration(max_duration, run_start);
+                        let (failure_reason, e) = match remaining {
+                            Err(limit) => (Some(FailureReason::Timeout), limit),
+                            Ok(_) => {
+                                let failure_reason = FailureReason::from_error(&e);
+                                let error = match failure_reason {
+                                    // The backend has already killed the command
+                                    Some(FailureReason::Timeout) => {
+                                        CleanroomError::container_error(format!(
+                                            "Command '{}' in container '{}' was killed after {}ms",
+                                            rendered_command.join(" "),
+                                            container_name,
+                                            attempt_start.elapsed().as_millis()
+                                        ))
+                                        .with_source(e.to_string())
+                                    }
+                                    _ => CleanroomError::container_error(format!(
+                                        "Failed to execute command '{}' in container '{}': {}",
+                                        rendered_command.join(" "),
+                                        container_name,
+                                        e
+                                    )),
+                                };
+                                (failure_reason, error)
+                            }
+                        };
+                        step_results.push(StepResult {
+                            command: rendered_command.join(" "),
+                            duration_ms: step_start.elapsed().as_millis() as u64,
+                            start_ts,
+                            retries,
+                            failure_reason,
+                            ..StepResult::failed(&step.name, &test_name, e.to_string())
+                        });
+                        continue_past(step, e, &mut step_error)?;
+                        continue 'steps;
+                    }
+                };
+
+                if execution_result.exit_code == step.expected_exit_code() || retries >= max_retries
+                {
+                    break execution_result;
+                }
+
+                // Exponential backoff: delay, 2*delay, 4*delay, ...
+                let delay_ms = retry_delay_ms.saturating_mul(1u64 << retries.min(16));
+                warn!(
+                    "⚠️  Step '{}' exited with code {} on attempt {}/{}, retrying in {}ms",
+                    step.name,
+                    execution_result.exit_code,
+                    retries + 1,
+                    max_retries + 1,
+                    delay_ms
+                );
+                tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+                retries += 1;
+            };
+            let duration_ms = step_start.elapsed().as_millis() as u64;
+
+            if retries > 0 && execution_result.exit_code == step.expected_exit_code() {
+                info!(
+                    "✅ Step '{}' succeeded after {} retries",
+                    step.name, retries
+                );
+            }
 
             let stdout = &execution_result.stdout;
             let stderr = &execution_result.stderr;
+            let redacted_stdout = redactor.redact_text(stdout);
+            let redacted_stderr = redactor.redact_text(stderr);
 
             if !stderr.is_empty() {
-                warn!("⚠️  Stderr: {}", stderr.trim());
-                info!("⚠️  Stderr: {}", stderr.trim());
+                warn!("⚠️  Stderr: {}", redacted_stderr.trim());
+                info!("⚠️  Stderr: {}", redacted_stderr.trim());
             }
 
-            if execution_result.exit_code != 0 {
-                return Err(CleanroomError::validation_error(format!(
-                    "Step '{}' failed with exit code: {}",
-                    step.name, execution_result.exit_code
-                )));
+            let egress = hermetic_network.as_ref().and_then(|_| {
+                let allowed_hosts: Vec<String> = service_handles.keys().cloned().collect();
+                egress_violation(&format!("{}\n{}", stdout, stderr), &allowed_hosts)
+            });
+            let outcome = match &egress {
+                Some(host) => Err(CleanroomError::validation_error(format!(
+                    "Step '{}' failed: hermetic violation: attempted egress to {}",
+                    step.name, host
+                ))),
+                None => check_step_output(step, execution_result.exit_code, stdout, &redactor),
+            };
+            // Output is only checked against the regex once the exit code matched
+            let failure_reason = match (&outcome, &egress) {
+                (Ok(()), _) | (Err(_), Some(_)) => None,
+                (Err(_), None) if execution_result.exit_code != step.expected_exit_code() => {
+                    execution_result
+                        .failure_reason()
+                        .or(Some(FailureReason::UnexpectedExitCode))
+                }
+                (Err(_), None) => Some(FailureReason::RegexMismatch),
+            };
+
+            step_results.push(StepResult {
+                name: step.name.clone(),
+                command: rendered_command.join(" "),
+                exit_code: execution_result.exit_code,
+                stdout: redacted_stdout,
+                stderr: redacted_stderr,
+                duration_ms,
+                start_ts,
+                success: outcome.is_ok(),
+                source: test_name.clone(),
+                retries,
+                skipped: false,
+                failure_reason,
+                spans: Vec::new(),
+            });
+
+            if let Err(e) = outcome {
+                continue_past(step, e, &mut step_error)?;
+                continue;
             }
 
-            stdout.to_string()
-        };
+            info!("✅ Step '{}' completed successfully", step.name);
+        }
 
-        info!("📤 Output: {}", stdout.trim());
-        info!("📤 Output: {}", stdout.trim());
+        // [data] runs every scenario once per row, as `<scenario>[<row>]`
+        let mut row_failures = Vec::new();
+        if let Some(data_config) = test_config.data.clone() {
+            let rows = data::load_rows(&data_config, path)?;
+            info!(
+                "📊 Expanding {} scenario(s) over {} data row(s)",
+                test_config.scenario.len(),
+                rows.len()
+            );
+            let expansion =
+                data::expand_scenarios(&test_config.scenario, &rows, &template_renderer);
+            test_config.scenario = expansion.scenarios;
+            row_failures = expansion.failures;
+        }
 
-        if let Some(regex) = &step.expected_output_regex {
-            debug!("Expected output regex: {}", regex);
-            let re = regex::Regex::new(regex).map_err(|e| {
-                CleanroomError::validation_error(format!(
-                    "Invalid regex '{}' in step '{}': {}",
-                    regex, step.name, e
-                ))
-            })?;
+        // Rows that couldn't be used fail their own cases, not the others
+        let scenario_filter = config
+            .scenario_filter
+            .as_deref()
+            .map(scenario::ScenarioFilter::new)
+            .transpose()?;
+        let mut row_error = None;
+        for (case, error) in row_failures {
+            if let Some(filter) = &scenario_filter {
+                if !filter.matches(&case) {
+                    continue;
+                }
+            }
+            error!("❌ Scenario '{}' failed: {}", case, error);
+            step_results.push(StepResult::failed("data", &case, error.to_string()));
+            row_error.get_or_insert(error);
+        }
 
-            // Trim output before regex match to handle trailing newlines from echo
-            let trimmed_output = stdout.trim();
-            if !re.is_match(trimmed_output) {
-                return Err(CleanroomError::validation_error(format!(
-                    "Step '{}' output did not match expected regex '{}'. Output: {}",
-                    step.name, regex, trimmed_output
-                )));
+        // Execute scenario blocks (v1.0 format)
+        if !test_config.scenario.is_empty() {
+            info!("📋 Executing {} scenario(s)", test_config.scenario.len());
+
+            let mut stages = test_config.scenario_stages()?;
+            if let Some(filter) = &scenario_filter {
+                let selected = filter.select(&test_config.scenario);
+                if selected.is_empty() {
+                    warn!(
+                        "⚠️  No scenario in '{}' matches '{}'",
+                        test_name,
+                        filter.pattern()
+                    );
+                }
+                for stage in &mut stages {
+                    stage.retain(|s| {
+                        let keep = selected.contains(s.name.as_str());
+                        if !keep {
+                            info!(
+                                "⏭️  Skipping scenario '{}' (--scenario {})",
+                                s.name,
+                                filter.pattern()
+                            );
+                            step_results.push(StepResult::skipped("run", &s.name));
+                        }
+                        keep
+                    });
+                }
+            }
+            let scenarios = scenario::execute_scenarios(
+                &stages,
+                &environment,
+                &service_handles,
+                &test_config,
+                step_results,
+            );
+            match (max_duration, remaining_duration(max_duration, run_start)?) {
+                (Some(limit), Some(remaining)) => tokio::time::timeout(remaining, scenarios)
+                    .await
+                    .map_err(|_| duration_limit_error(limit, run_start))??,
+                _ => scenarios.await?,
             }
-            info!("✅ Output matches expected regex");
         }
 
-        info!("✅ Step '{}' completed successfully", step.name);
+        match step_error.or(row_error) {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
     }
+    .await;
 
-    // Execute scenario blocks (v1.0 format)
-    if !test_config.scenario.is_empty() {
-        info!("📋 Executing {} scenario(s)", test_config.scenario.len());
+    // Coverage recorded by the scenarios counts toward the whole run's
+    config.coverage.merge(environment.coverage()).await;
 
-        for scenario in &test_config.scenario {
-            scenario::execute_scenario(scenario, &environment, &service_handles, &test_config)
-                .await?;
-        }
+    // --keep-services leaves a failed test's services up for inspection
+    if outcome.is_err() && config.keep_services {
+        services::keep_services(&environment, &service_handles).await;
+        // Kept services are detached from the network rather than leak it
+        remove_hermetic_network(hermetic_network).await;
+        return outcome;
     }
 
     // Cleanup services
@@ -182,7 +630,173 @@ pub async fn run_single_test(path: &PathBuf, _config: &CliConfig) -> Result<()>
         }
     }
 
+    remove_hermetic_network(hermetic_network).await;
+
+    outcome?;
+
     info!("🎉 Test '{}' completed successfully!", test_name);
     info!("🎉 Test '{}' completed successfully!", test_name);
     Ok(())
 }
+
+/// Remove a test's hermetic network, if it had one
+///
+/// Failures are logged rather than returned so they never mask the test's
+/// own outcome.
+async fn remove_hermetic_network(network: Option<HermeticNetwork>) {
+    if let Some(network) = network {
+        if let Err(e) = network.remove().await {
+            warn!("⚠️  Failed to remove hermetic network: {}", e);
+        }
+    }
+}
+
+/// Host environment variables passed through to step containers
+///
+/// Only variables named by `--env-inherit` or `[meta] inherit_env` are
+/// passed; allowlisted variables that aren't set on the host are left out,
+/// so a step relying on them fails the same way everywhere.
+fn inherited_env(config: &CliConfig, meta: Option<&MetaConfig>) -> Result<HashMap<String, String>> {
+    let meta_names = meta.and_then(|meta| meta.inherit_env.as_ref());
+    let mut inherited = HashMap::new();
+    for name in config
+        .inherit_env
+        .iter()
+        .chain(meta_names.into_iter().flatten())
+    {
+        if name.is_empty() || name.contains('=') {
+            return Err(CleanroomError::config_error(format!(
+                "Invalid environment variable name '{}' in --env-inherit or [meta] inherit_env",
+                name
+            )));
+        }
+        match std::env::var(name) {
+            Ok(value) => {
+                debug!("Passing host environment variable {} to steps", name);
+                inherited.insert(name.clone(), value);
+            }
+            Err(_) => warn!(
+                "⚠️  Environment variable {} is allowlisted but not set on the host",
+                name
+            ),
+        }
+    }
+    Ok(inherited)
+}
+
+/// Time left before the test hits `limits.max_duration_ms`
+///
+/// Returns `None` when no duration limit is set, and an error naming the
+/// limit once it has been reached.
+fn remaining_duration(
+    max_duration: Option<std::time::Duration>,
+    run_start: std::time::Instant,
+) -> Result<Option<std::time::Duration>> {
+    let Some(max_duration) = max_duration else {
+        return Ok(None);
+    };
+
+    match max_duration.checked_sub(run_start.elapsed()) {
+        Some(remaining) if !remaining.is_zero() => Ok(Some(remaining)),
+        _ => Err(duration_limit_error(max_duration, run_start)),
+    }
+}
+
+fn duration_limit_error(
+    max_duration: std::time::Duration,
+    run_start: std::time::Instant,
+) -> CleanroomError {
+    CleanroomError::validation_error(format!(
+        "Test exceeded limits.max_duration_ms = {} (ran for {}ms)",
+        max_duration.as_millis(),
+        run_start.elapsed().as_millis()
+    ))
+}
+
+/// Let the test go on past a failed step with `continue_on_failure = true`
+///
+/// The first such failure is kept in `step_error` and fails the test once
+/// the remaining steps have run; any other failure is returned.
+fn continue_past(
+    step: &StepConfig,
+    error: CleanroomError,
+    step_error: &mut Option<CleanroomError>,
+) -> Result<()> {
+    if !step.continue_on_failure.unwrap_or(false) {
+        return Err(error);
+    }
+
+    warn!(
+        "⚠️  Step '{}' failed, continuing (continue_on_failure): {}",
+        step.name, error
+    );
+    step_error.get_or_insert(error);
+    Ok(())
+}
+
+/// Decide whether a step should be skipped, returning the reason if so
+///
+/// `skip_if` is evaluated as a Tera expression against the test vars, so
+/// `skip_if = "env(name='CI') == 'true'"` skips the step only on CI.
+fn skip_reason(step: &StepConfig, renderer: &mut TemplateRenderer) -> Result<Option<String>> {
+    if step.skip.unwrap_or(false) {
+        return Ok(Some("skip = true".to_string()));
+    }
+
+    if let Some(condition) = &step.skip_if {
+        let skip = renderer
+            .eval_condition(condition, &format!("step_{}_skip_if", step.name))
+            .map_err(|e| {
+                CleanroomError::validation_error(format!(
+                    "Invalid skip_if expression in step '{}'",
+                    step.name
+                ))
+                .with_source(e.to_string())
+            })?;
+
+        if skip {
+            return Ok(Some(format!("skip_if: {}", condition)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Check a step's exit code and output against its expectations
+///
+/// The regex runs on the raw output; anything logged or put in an error
+/// message goes through `redactor` first.
+fn check_step_output(
+    step: &StepConfig,
+    exit_code: i32,
+    stdout: &str,
+    redactor: &SpanRedactor,
+) -> Result<()> {
+    step.check_exit_code(exit_code)?;
+
+    let redacted_output = redactor.redact_text(stdout.trim());
+    info!("📤 Output: {}", redacted_output);
+    info!("📤 Output: {}", redacted_output);
+
+    if let Some(regex) = &step.expected_output_regex {
+        debug!("Expected output regex: {}", regex);
+        let re = regex::Regex::new(regex).map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Invalid regex '{}' in step '{}': {}",
+                regex, step.name, e
+            ))
+        })?;
+
+        // Trim output before regex match to handle trailing newlines from echo
+        let trimmed_output = stdout.trim();
+        if !re.is_match(trimmed_output) {
+            return Err(CleanroomError::validation_error(format!(
+                "Step '{}' output did not match expected regex '{}'. Output: {}",
+                step.name, regex, redacted_output
+            )));
+        }
+        info!("✅ Output matches expected regex");
+    }
+
+    Ok(())
+}
@@ -3,12 +3,16 @@
 //! Handles framework self-testing with comprehensive validation, reporting, and OpenTelemetry export.
 
 use crate::error::{CleanroomError, Result};
-use tracing::{info, span, Level};
+use tracing::instrument::WithSubscriber;
+use tracing::{field, info, span, Instrument, Level};
 
-use crate::telemetry::{init_otel, Export, OtelConfig, OtelGuard};
+use crate::telemetry::{otel_dispatch, Export, OtelConfig};
 
 /// Run framework self-tests with optional OTEL export
 ///
+/// With an exporter other than `none`, the run, each suite and each test
+/// are exported as spans, so the self-test can be viewed as a trace tree.
+///
 /// Core Team Compliance:
 /// - ✅ Async function for I/O operations
 /// - ✅ Proper error handling with CleanroomError
@@ -16,45 +20,66 @@ use crate::telemetry::{init_otel, Export, OtelConfig, OtelGuard};
 /// - ✅ Use tracing for internal operations
 pub async fn run_self_tests(
     suite: Option<String>,
+    filter: Option<String>,
     report: bool,
     otel_exporter: String,
-    _otel_endpoint: Option<String>,
+    otel_endpoint: Option<String>,
 ) -> Result<()> {
-    // Initialize OTEL if requested
-    let _guard = if otel_exporter != "none" {
-        Some(init_otel_for_self_test(
-            &otel_exporter,
-            _otel_endpoint.as_deref(),
-        )?)
-    } else {
-        None
-    };
+    if otel_exporter == "none" {
+        return run_traced_self_tests(suite, filter, report, &otel_exporter).await;
+    }
 
+    // Logging already owns the global subscriber, so this run's spans are
+    // routed to the exporter explicitly. The guard flushes them on drop.
+    let (_guard, dispatch) = otel_dispatch(self_test_otel_config(
+        &otel_exporter,
+        otel_endpoint.as_deref(),
+    )?)?;
+    run_traced_self_tests(suite, filter, report, &otel_exporter)
+        .with_subscriber(dispatch)
+        .await
+}
+
+/// Run the self-tests under a `clnrm.self_test` root span
+async fn run_traced_self_tests(
+    suite: Option<String>,
+    filter: Option<String>,
+    report: bool,
+    otel_exporter: &str,
+) -> Result<()> {
     // Use tracing instead of println for internal operations
     info!("Starting framework self-tests");
 
-    let _root_span = if otel_exporter != "none" {
-        span!(
-            Level::INFO,
-            "clnrm.self_test",
-            clnrm.version = env!("CARGO_PKG_VERSION"),
-            test.suite = suite.as_deref().unwrap_or("all"),
-            otel.exporter = %otel_exporter,
-        )
-    } else {
-        span!(Level::INFO, "clnrm.self_test")
-    };
-
-    let _enter = _root_span.enter();
+    let root_span = span!(
+        Level::INFO,
+        "clnrm.self_test",
+        clnrm.version = env!("CARGO_PKG_VERSION"),
+        test.suite = suite.as_deref().unwrap_or("all"),
+        otel.exporter = otel_exporter,
+        result = field::Empty,
+        error.type = field::Empty,
+        total_tests = field::Empty,
+        failed_tests = field::Empty,
+    );
+
+    run_self_test_suites(suite, filter, report, &root_span)
+        .instrument(root_span.clone())
+        .await
+}
 
+/// Validate the suite and filter, then run and report the selected self-tests
+async fn run_self_test_suites(
+    suite: Option<String>,
+    filter: Option<String>,
+    report: bool,
+    root_span: &tracing::Span,
+) -> Result<()> {
     // Validate suite parameter if provided
     if let Some(ref suite_name) = suite {
         const VALID_SUITES: &[&str] = &["framework", "container", "plugin", "cli", "otel"];
         if !VALID_SUITES.contains(&suite_name.as_str()) {
-            {
-                _root_span.record("result", "error");
-                _root_span.record("error.type", "validation_error");
-            }
+            root_span.record("result", "error");
+            root_span.record("error.type", "validation_error");
 
             return Err(CleanroomError::validation_error(format!(
                 "Invalid test suite '{}'. Valid suites: {}",
@@ -64,12 +89,24 @@ pub async fn run_self_tests(
         }
     }
 
+    let filter = filter
+        .as_deref()
+        .map(|pattern| {
+            regex::Regex::new(pattern).map_err(|e| {
+                CleanroomError::validation_error(format!(
+                    "Invalid test filter '{}': {}",
+                    pattern, e
+                ))
+            })
+        })
+        .transpose()?;
+
     // Run basic self-tests
     info!("🧪 Running framework self-tests");
 
-    // Run framework tests with optional suite filter
-    use crate::testing::run_framework_tests_by_suite;
-    let test_results = run_framework_tests_by_suite(suite.as_deref())
+    // Run framework tests with optional suite and test name filters
+    use crate::testing::run_framework_tests_filtered;
+    let test_results = run_framework_tests_filtered(suite.as_deref(), filter.as_ref())
         .await
         .map_err(|e| {
             CleanroomError::internal_error("Framework self-tests failed")
@@ -77,6 +114,15 @@ pub async fn run_self_tests(
                 .with_source(e.to_string())
         })?;
 
+    if test_results.total_tests == 0 {
+        if let Some(filter) = filter {
+            return Err(CleanroomError::validation_error(format!(
+                "No self-tests match filter '{}'",
+                filter
+            )));
+        }
+    }
+
     // Display results (CLI output is acceptable for user-facing messages)
     crate::cli::commands::report::display_test_results(&test_results);
 
@@ -91,15 +137,13 @@ pub async fn run_self_tests(
             })?;
     }
 
-    {
-        if test_results.failed_tests > 0 {
-            _root_span.record("result", "fail");
-            _root_span.record("failed_tests", test_results.failed_tests);
-        } else {
-            _root_span.record("result", "pass");
-        }
-        _root_span.record("total_tests", test_results.total_tests);
+    if test_results.failed_tests > 0 {
+        root_span.record("result", "fail");
+        root_span.record("failed_tests", test_results.failed_tests);
+    } else {
+        root_span.record("result", "pass");
     }
+    root_span.record("total_tests", test_results.total_tests);
 
     // Return proper error with context
     if test_results.failed_tests > 0 {
@@ -113,11 +157,13 @@ pub async fn run_self_tests(
     }
 }
 
-/// Initialize OTEL for self-test with proper error handling
-fn init_otel_for_self_test(exporter: &str, endpoint: Option<&str>) -> Result<OtelGuard> {
+/// OTEL configuration for the self-test's exporter, with proper error handling
+///
+/// `otlp` is shorthand for `otlp-http`.
+fn self_test_otel_config(exporter: &str, endpoint: Option<&str>) -> Result<OtelConfig> {
     let export = match exporter {
         "stdout" => Export::Stdout,
-        "otlp-http" => {
+        "otlp" | "otlp-http" => {
             let endpoint = endpoint.ok_or_else(|| {
                 CleanroomError::validation_error("OTEL endpoint required for otlp-http exporter")
             })?;
@@ -139,20 +185,18 @@ fn init_otel_for_self_test(exporter: &str, endpoint: Option<&str>) -> Result<Ote
         }
         _ => {
             return Err(CleanroomError::validation_error(format!(
-                "Invalid OTEL exporter '{}'. Valid: none, stdout, otlp-http, otlp-grpc",
+                "Invalid OTEL exporter '{}'. Valid: none, stdout, otlp, otlp-http, otlp-grpc",
                 exporter
             )))
         }
     };
 
-    let config = OtelConfig {
+    Ok(OtelConfig {
         service_name: "clnrm-self-test",
         deployment_env: "test",
         sample_ratio: 1.0,
         export,
         enable_fmt_layer: false,
         headers: None,
-    };
-
-    init_otel(config)
+    })
 }
@@ -1,11 +1,16 @@
 //! Services command implementation
 //!
-//! Handles service management including status, logs, restart operations,
-//! and AI-driven autonomous service lifecycle management.
+//! Handles service management including status, logs, restart, exec and
+//! down operations, and AI-driven autonomous service lifecycle management.
 
 use crate::cleanroom::CleanroomEnvironment;
 use crate::error::{CleanroomError, Result};
+use crate::services::keep::{
+    exec_in_kept_service, find_running_service, list_kept_services, remove_kept_services,
+    runtime_reachable, RemovedServices,
+};
 use crate::services::service_manager::{AutoScaleConfig, ServiceManager, ServiceMetrics};
+use std::time::Duration;
 use tracing::warn;
 
 /// Show service status
@@ -38,8 +43,14 @@ pub async fn show_service_status() -> Result<()> {
     Ok(())
 }
 
+/// How often `services logs --follow` polls for new output
+const LOG_FOLLOW_INTERVAL: Duration = Duration::from_millis(500);
+
 /// Show service logs
-pub async fn show_service_logs(service: &str, lines: usize) -> Result<()> {
+///
+/// With `follow`, keeps streaming new lines after the last `lines` until
+/// Ctrl+C or the service's logs can no longer be read.
+pub async fn show_service_logs(service: &str, lines: usize, follow: bool) -> Result<()> {
     println!("📄 Service Logs for '{}':", service);
 
     // Create a temporary environment to check for services
@@ -60,6 +71,26 @@ pub async fn show_service_logs(service: &str, lines: usize) -> Result<()> {
         Some(handle) => {
             println!("Service found: {} (ID: {})", handle.service_name, handle.id);
 
+            if follow {
+                println!("📄 Following logs (press Ctrl+C to stop):");
+                // Dropping the follow future on Ctrl+C ends the stream; it
+                // runs on this task, so nothing is left behind
+                tokio::select! {
+                    result = environment.follow_service_logs(
+                        handle,
+                        lines,
+                        LOG_FOLLOW_INTERVAL,
+                        |line| println!("  {}", line),
+                    ) => {
+                        if let Err(e) = result {
+                            println!("⚠️  Log stream ended: {}", e);
+                        }
+                    }
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+                return Ok(());
+            }
+
             // Try to retrieve logs from the service
             match environment.get_service_logs(&handle.id, lines).await {
                 Ok(logs) => {
@@ -166,6 +197,77 @@ pub async fn restart_service(service: &str) -> Result<()> {
     Ok(())
 }
 
+/// Execute a command in a running service
+///
+/// Finds the service's container by its clnrm labels, so this works on
+/// containers a separate `clnrm run --keep-services` left running. Prints the
+/// command's stdout and stderr and returns its exit code.
+pub async fn exec_in_service(service: &str, command: &[String]) -> Result<i32> {
+    let services = list_kept_services().await?;
+    let target = find_running_service(&services, service)?;
+
+    let result = exec_in_kept_service(target, command).await?;
+
+    print!("{}", result.stdout);
+    eprint!("{}", result.stderr);
+    Ok(result.exit_code)
+}
+
+/// Remove service containers left running by `run --keep-services` or an
+/// interrupted run
+///
+/// Only containers started from the current directory are removed unless
+/// `all` is set; any others are reported as left behind.
+pub async fn services_down(all: bool) -> Result<()> {
+    // Without a reachable runtime no containers can be left running
+    if !runtime_reachable().await {
+        println!("✅ No kept services to remove (no container runtime is reachable)");
+        return Ok(());
+    }
+
+    let RemovedServices { removed, failed } = remove_kept_services(all).await?;
+
+    if removed.is_empty() && failed.is_empty() {
+        println!("✅ No kept services to remove");
+    } else if !removed.is_empty() {
+        for service in &removed {
+            println!(
+                "🛑 Removed service '{}' (container {})",
+                service.service_name,
+                service.short_id()
+            );
+        }
+        println!("✅ Removed {} service container(s)", removed.len());
+    }
+
+    if !failed.is_empty() {
+        for (service, error) in &failed {
+            println!(
+                "❌ Failed to remove service '{}' (container {}): {}",
+                service.service_name,
+                service.short_id(),
+                error
+            );
+        }
+        return Err(CleanroomError::container_error(format!(
+            "Failed to remove {} service container(s)",
+            failed.len()
+        )));
+    }
+
+    if !all {
+        let left = list_kept_services().await?.len();
+        if left > 0 {
+            println!(
+                "💡 {} service container(s) started from other directories were left; remove them with 'clnrm services down --all'",
+                left
+            );
+        }
+    }
+
+    Ok(())
+}
+
 /// AI-driven service lifecycle management
 ///
 /// Provides autonomous service management with auto-scaling, load prediction,
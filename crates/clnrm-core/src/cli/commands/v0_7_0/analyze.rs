@@ -5,6 +5,10 @@
 //!
 //! This module implements first-failing-rule reporting, showing the FIRST
 //! validator that fails with detailed context and recommendations.
+//!
+//! Reports serialize to JSON, so a report saved from one run can be the
+//! baseline for the next: [`AnalysisReport::compare_to`] lists validators
+//! that started failing or started passing since then.
 
 use crate::config::types::TestConfig;
 use crate::error::{CleanroomError, Result};
@@ -15,6 +19,7 @@ use crate::validation::order_validator::OrderExpectation;
 use crate::validation::span_validator::{SpanData, SpanValidator};
 use crate::validation::status_validator::{StatusCode, StatusExpectation};
 use crate::validation::window_validator::WindowExpectation;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::path::Path;
 
@@ -592,7 +597,7 @@ fn count_events(spans: &[SpanData]) -> usize {
     spans
         .iter()
         .filter_map(|s| s.events.as_ref())
-        .map(|events| events.len())
+        .map(Vec::len)
         .sum()
 }
 
@@ -609,7 +614,7 @@ fn compute_trace_digest(spans: &[SpanData]) -> Result<String> {
 }
 
 /// Analysis report containing all validation results
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisReport {
     /// Test name from TOML
     pub test_name: String,
@@ -626,6 +631,68 @@ pub struct AnalysisReport {
 }
 
 impl AnalysisReport {
+    /// Load a report previously saved with [`save`](Self::save)
+    ///
+    /// # Errors
+    /// * The file cannot be read or isn't a JSON analysis report
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to read analysis report {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            CleanroomError::serialization_error(format!(
+                "Failed to parse analysis report {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Write the report as pretty-printed JSON
+    ///
+    /// # Errors
+    /// * The file cannot be written
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            CleanroomError::serialization_error(format!(
+                "Failed to serialize analysis report: {}",
+                e
+            ))
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to write analysis report {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    /// Compare validator outcomes against an earlier report
+    ///
+    /// Validators are matched by name. A failing validator that the baseline
+    /// didn't run counts as newly failing.
+    pub fn compare_to(&self, baseline: &AnalysisReport) -> BaselineComparison {
+        let mut comparison = BaselineComparison::default();
+        for validator in &self.validators {
+            let before = baseline
+                .validators
+                .iter()
+                .find(|b| b.name == validator.name)
+                .map(|b| b.passed);
+            match (before, validator.passed) {
+                (Some(true) | None, false) => comparison.newly_failing.push(validator.clone()),
+                (Some(false), true) => comparison.newly_passing.push(validator.clone()),
+                _ => {}
+            }
+        }
+        comparison
+    }
+
     /// Check if all validators passed
     pub fn is_success(&self) -> bool {
         self.validators.iter().all(|v| v.passed)
@@ -689,7 +756,7 @@ impl AnalysisReport {
 }
 
 /// Individual validator result
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ValidatorResult {
     /// Validator name
     pub name: String,
@@ -698,3 +765,46 @@ pub struct ValidatorResult {
     /// Details or error message
     pub details: String,
 }
+
+/// Validators whose outcome changed since a baseline report
+#[derive(Debug, Clone, Default)]
+pub struct BaselineComparison {
+    /// Validators failing now that passed (or didn't run) in the baseline
+    pub newly_failing: Vec<ValidatorResult>,
+    /// Validators passing now that failed in the baseline
+    pub newly_passing: Vec<ValidatorResult>,
+}
+
+impl BaselineComparison {
+    /// Whether any validator started failing
+    pub fn has_regressions(&self) -> bool {
+        !self.newly_failing.is_empty()
+    }
+
+    /// Generate human-readable summary of the changes
+    pub fn format_summary(&self) -> String {
+        let mut output = String::from("📈 Compared to baseline\n");
+
+        if self.newly_failing.is_empty() && self.newly_passing.is_empty() {
+            output.push_str("  No validator changed outcome\n");
+            return output;
+        }
+
+        output.push_str(&format!(
+            "  {} new validation failure(s), {} newly passing\n",
+            self.newly_failing.len(),
+            self.newly_passing.len()
+        ));
+        for validator in &self.newly_failing {
+            output.push_str(&format!(
+                "  ❌ {} now fails ({})\n",
+                validator.name, validator.details
+            ));
+        }
+        for validator in &self.newly_passing {
+            output.push_str(&format!("  ✅ {} now passes\n", validator.name));
+        }
+
+        output
+    }
+}
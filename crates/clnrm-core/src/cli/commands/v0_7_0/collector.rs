@@ -13,7 +13,16 @@
 use crate::error::{CleanroomError, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+/// Container port of the collector's OTLP/HTTP receiver in the built-in config
+const DEFAULT_HTTP_CONTAINER_PORT: u16 = 4318;
+
+/// Container port of the collector's OTLP/gRPC receiver in the built-in config
+const DEFAULT_GRPC_CONTAINER_PORT: u16 = 4317;
+
+/// Where the collector config is mounted inside the container
+const CONTAINER_CONFIG_PATH: &str = "/etc/otel-collector-config.yaml";
 
 /// Collector state stored persistently
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +37,23 @@ struct CollectorState {
     image: String,
     /// Timestamp when started
     started_at: chrono::DateTime<chrono::Utc>,
+    /// Custom collector config, if `--config` was given
+    #[serde(default)]
+    config: Option<PathBuf>,
+    /// Container port the HTTP port is mapped to
+    #[serde(default = "default_http_container_port")]
+    http_container_port: u16,
+    /// Container port the gRPC port is mapped to
+    #[serde(default = "default_grpc_container_port")]
+    grpc_container_port: u16,
+}
+
+fn default_http_container_port() -> u16 {
+    DEFAULT_HTTP_CONTAINER_PORT
+}
+
+fn default_grpc_container_port() -> u16 {
+    DEFAULT_GRPC_CONTAINER_PORT
 }
 
 impl CollectorState {
@@ -185,6 +211,89 @@ fn follow_container_logs(container_id: &str) -> Result<()> {
     Ok(())
 }
 
+/// Published ports of a container, e.g. `4318/tcp -> 0.0.0.0:4318`
+fn get_published_ports(container_id: &str) -> Result<Vec<String>> {
+    use std::process::Command;
+
+    let output = Command::new("docker")
+        .args(["port", container_id])
+        .output()
+        .map_err(|e| {
+            CleanroomError::container_error(format!("Failed to inspect container ports: {}", e))
+        })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(CleanroomError::container_error(format!(
+            "Failed to get container ports: {}",
+            stderr
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+/// Check a custom collector config before any container starts
+///
+/// Returns the config's absolute path (Docker only bind-mounts absolute
+/// paths) and the container ports of its OTLP HTTP and gRPC receivers.
+fn load_custom_config(path: &Path) -> Result<(PathBuf, u16, u16)> {
+    if !path.is_file() {
+        return Err(CleanroomError::config_error(format!(
+            "Collector config not found: {}",
+            path.display()
+        )));
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| {
+        CleanroomError::io_error(format!(
+            "Failed to read collector config {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let config: serde_yaml::Value = serde_yaml::from_str(&content).map_err(|e| {
+        CleanroomError::config_error(format!(
+            "Invalid collector config {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    let absolute_path = path.canonicalize().map_err(|e| {
+        CleanroomError::io_error(format!(
+            "Failed to resolve collector config {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    Ok((
+        absolute_path,
+        receiver_port(&config, "http").unwrap_or(DEFAULT_HTTP_CONTAINER_PORT),
+        receiver_port(&config, "grpc").unwrap_or(DEFAULT_GRPC_CONTAINER_PORT),
+    ))
+}
+
+/// Port of `receivers.otlp.protocols.<protocol>.endpoint`, if set
+fn receiver_port(config: &serde_yaml::Value, protocol: &str) -> Option<u16> {
+    config
+        .get("receivers")?
+        .get("otlp")?
+        .get("protocols")?
+        .get(protocol)?
+        .get("endpoint")?
+        .as_str()?
+        .rsplit_once(':')?
+        .1
+        .parse()
+        .ok()
+}
+
 /// Start local OTEL collector
 ///
 /// Starts a local OpenTelemetry collector container for development.
@@ -194,6 +303,8 @@ fn follow_container_logs(container_id: &str) -> Result<()> {
 /// * `image` - Collector image to use
 /// * `http_port` - HTTP port for OTLP receiver
 /// * `grpc_port` - gRPC port for OTLP receiver
+/// * `config` - Custom collector config to mount; the host ports map to the
+///   ports its OTLP receivers listen on
 /// * `detach` - Run in background
 ///
 /// # Core Team Standards
@@ -205,8 +316,12 @@ pub async fn start_collector(
     image: &str,
     http_port: u16,
     grpc_port: u16,
+    config: Option<&Path>,
     detach: bool,
 ) -> Result<()> {
+    // A bad custom config fails before any container is touched
+    let custom_config = config.map(load_custom_config).transpose()?;
+
     // Check if collector is already running
     if let Some(state) = CollectorState::load()? {
         if is_container_running(&state.container_id)? {
@@ -260,18 +375,34 @@ service:
       exporters: [logging, file]
 "#;
 
-    // Write config to temporary file
-    let config_dir = PathBuf::from(".clnrm");
-    let config_path = config_dir.join("otel-collector-config.yaml");
-    fs::write(&config_path, config_content).map_err(|e| {
-        CleanroomError::io_error(format!("Failed to write collector config: {}", e))
-    })?;
+    let (config_path, http_container_port, grpc_container_port) = match custom_config {
+        Some(custom_config) => custom_config,
+        None => {
+            // Write the built-in config to the state directory
+            let config_path =
+                CollectorState::state_file_path()?.with_file_name("otel-collector-config.yaml");
+            fs::write(&config_path, config_content).map_err(|e| {
+                CleanroomError::io_error(format!("Failed to write collector config: {}", e))
+            })?;
+            let config_path = config_path.canonicalize().map_err(|e| {
+                CleanroomError::io_error(format!("Failed to resolve collector config: {}", e))
+            })?;
+            (
+                config_path,
+                DEFAULT_HTTP_CONTAINER_PORT,
+                DEFAULT_GRPC_CONTAINER_PORT,
+            )
+        }
+    };
 
     tracing::info!("Starting OTEL collector container");
     println!("🚀 Starting OTEL collector...");
     println!("   Image: {}", image);
     println!("   HTTP Port: {}", http_port);
     println!("   gRPC Port: {}", grpc_port);
+    if config.is_some() {
+        println!("   Config: {}", config_path.display());
+    }
 
     // Start container using docker command
     use std::process::Command;
@@ -290,16 +421,13 @@ service:
             "--name",
             container_name,
             "-p",
-            &format!("{}:4318", http_port),
+            &format!("{}:{}", http_port, http_container_port),
             "-p",
-            &format!("{}:4317", grpc_port),
+            &format!("{}:{}", grpc_port, grpc_container_port),
             "-v",
-            &format!(
-                "{}:/etc/otel-collector-config.yaml:ro",
-                config_path.display()
-            ),
+            &format!("{}:{}:ro", config_path.display(), CONTAINER_CONFIG_PATH),
             image,
-            "--config=/etc/otel-collector-config.yaml",
+            &format!("--config={}", CONTAINER_CONFIG_PATH),
         ])
         .output()
         .map_err(|e| {
@@ -336,6 +464,9 @@ service:
         grpc_port,
         image: image.to_string(),
         started_at: chrono::Utc::now(),
+        config: config.map(|_| config_path),
+        http_container_port,
+        grpc_container_port,
     };
     state.save()?;
 
@@ -402,6 +533,30 @@ pub async fn show_collector_status() -> Result<()> {
                 println!("   HTTP Endpoint: http://localhost:{}", state.http_port);
                 println!("   gRPC Endpoint: http://localhost:{}", state.grpc_port);
                 println!("   Image: {}", state.image);
+                match &state.config {
+                    Some(config) => println!("   Config: {}", config.display()),
+                    None => println!("   Config: built-in"),
+                }
+
+                // Show what Docker actually published, which is what exporters reach
+                match get_published_ports(&state.container_id) {
+                    Ok(ports) => {
+                        println!("   Ports:");
+                        for port in ports {
+                            println!("     {}", port);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to get collector ports: {}", e);
+                        println!(
+                            "   Ports: {} -> {}/tcp, {} -> {}/tcp",
+                            state.http_port,
+                            state.http_container_port,
+                            state.grpc_port,
+                            state.grpc_container_port
+                        );
+                    }
+                }
                 println!(
                     "   Started: {}",
                     state.started_at.format("%Y-%m-%d %H:%M:%S UTC")
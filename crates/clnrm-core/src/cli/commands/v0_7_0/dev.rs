@@ -34,6 +34,7 @@ pub struct DevWatcher;
 /// * `clear_screen` - Clear terminal before each test run
 /// * `only_pattern` - Optional pattern to filter scenarios (substring match on path)
 /// * `timebox_ms` - Optional maximum execution time per scenario in milliseconds
+/// * `all_on_change` - Re-run every test on any change instead of only the affected ones
 /// * `cli_config` - CLI configuration for test execution
 ///
 /// # Performance
@@ -51,7 +52,7 @@ pub struct DevWatcher;
 /// let paths = vec![PathBuf::from("tests/")];
 /// let config = CliConfig::default();
 ///
-/// run_dev_mode_with_filters(Some(paths), 300, true, None, None, config).await?;
+/// run_dev_mode_with_filters(Some(paths), 300, true, None, None, false, config).await?;
 /// # Ok(())
 /// # }
 /// ```
@@ -61,6 +62,7 @@ pub async fn run_dev_mode_with_filters(
     clear_screen: bool,
     only_pattern: Option<String>,
     timebox_ms: Option<u64>,
+    all_on_change: bool,
     cli_config: CliConfig,
 ) -> Result<()> {
     info!("🚀 Starting development mode with file watching");
@@ -113,6 +115,7 @@ pub async fn run_dev_mode_with_filters(
     if let Some(timeout) = timebox_ms {
         info!("  Timebox: {}ms", timeout);
     }
+    info!("  Re-run all on change: {}", all_on_change);
     info!("  Parallel: {}", cli_config.parallel);
     info!("  Jobs: {}", cli_config.jobs);
 
@@ -140,6 +143,9 @@ pub async fn run_dev_mode_with_filters(
     if let Some(timeout) = timebox_ms {
         watch_config = watch_config.with_timebox(timeout);
     }
+    if all_on_change {
+        watch_config = watch_config.with_all_on_change();
+    }
 
     // Start watching
     info!("📁 Watching for .toml.tera file changes...");
@@ -159,12 +165,22 @@ pub async fn run_dev_mode_with_filters(
 
 /// Legacy function for backward compatibility
 ///
-/// Calls the new `run_dev_mode_with_filters` with no filtering or timeboxing
+/// Calls the new `run_dev_mode_with_filters` with no filtering or timeboxing,
+/// re-running only the tests affected by each change
 pub async fn run_dev_mode(
     paths: Option<Vec<PathBuf>>,
     debounce_ms: u64,
     clear_screen: bool,
     cli_config: CliConfig,
 ) -> Result<()> {
-    run_dev_mode_with_filters(paths, debounce_ms, clear_screen, None, None, cli_config).await
+    run_dev_mode_with_filters(
+        paths,
+        debounce_ms,
+        clear_screen,
+        None,
+        None,
+        false,
+        cli_config,
+    )
+    .await
 }
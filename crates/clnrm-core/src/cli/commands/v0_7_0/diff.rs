@@ -1,9 +1,58 @@
 //! Diff command for trace comparison
 //!
 //! Compares two OpenTelemetry traces to detect regressions.
+//!
+//! Spans are paired across the two traces by a key chosen with [`MatchBy`]:
+//! their name path (the span's name under its ancestors' names), which stays
+//! stable when a re-run mints fresh span IDs, or their span ID. Spans sharing
+//! a key are paired in trace order; leftovers are reported as added or
+//! removed. A pair is reported as modified when its attributes differ or its
+//! duration changed by at least the [`DurationThreshold`], which keeps
+//! latency jitter between two real runs from showing up as differences.
+//!
+//! Two directories are compared file by file: trace files are paired by
+//! their path relative to each directory, and files present on only one
+//! side are reported as added or removed.
 
+use crate::cli::types::MatchBy;
 use crate::error::{CleanroomError, Result};
-use std::path::Path;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Object keys holding span metadata rather than spans
+const NON_SPAN_KEYS: &[&str] = &["attributes", "events", "links", "resource", "scope"];
+
+/// Minimum duration change for a span to count as modified
+///
+/// A change is reported only if it reaches every threshold that is set;
+/// with none set, any change is reported.
+#[derive(Debug, Clone, Default)]
+pub struct DurationThreshold {
+    /// Absolute change in milliseconds
+    pub ms: Option<u64>,
+    /// Change as a percentage of the baseline duration
+    pub pct: Option<f64>,
+}
+
+impl DurationThreshold {
+    /// Whether a change from `baseline_ns` to `current_ns` should be reported
+    pub fn is_significant(&self, baseline_ns: u64, current_ns: u64) -> bool {
+        let delta_ns = baseline_ns.abs_diff(current_ns);
+        if delta_ns == 0 {
+            return false;
+        }
+
+        let over_ms = self
+            .ms
+            .is_none_or(|ms| delta_ns >= ms.saturating_mul(1_000_000));
+        let over_pct = self.pct.is_none_or(|pct| {
+            baseline_ns == 0 || delta_ns as f64 * 100.0 / baseline_ns as f64 >= pct
+        });
+        over_ms && over_pct
+    }
+}
 
 /// Result of trace comparison
 #[derive(Debug, Clone)]
@@ -22,134 +71,530 @@ pub struct DiffResult {
     pub modified: Vec<String>,
 }
 
+impl DiffResult {
+    /// Whether any span was added, removed or modified
+    pub fn has_changes(&self) -> bool {
+        self.added_count + self.removed_count + self.modified_count > 0
+    }
+
+    fn to_json(&self) -> Value {
+        serde_json::json!({
+            "added_count": self.added_count,
+            "removed_count": self.removed_count,
+            "modified_count": self.modified_count,
+            "added": self.added,
+            "removed": self.removed,
+            "modified": self.modified,
+        })
+    }
+}
+
+/// Result of comparing two directories of traces
+#[derive(Debug, Clone, Default)]
+pub struct DirDiffResult {
+    /// Trace files only in the current directory
+    pub added: Vec<String>,
+    /// Trace files only in the baseline directory
+    pub removed: Vec<String>,
+    /// Trace files in both directories whose spans differ, with their diff
+    pub changed: Vec<(String, DiffResult)>,
+    /// Number of trace files in both directories with no differences
+    pub unchanged_count: usize,
+}
+
+impl DirDiffResult {
+    /// Whether any trace file was added, removed or changed
+    pub fn has_changes(&self) -> bool {
+        !self.added.is_empty() || !self.removed.is_empty() || !self.changed.is_empty()
+    }
+}
+
 /// Compare two traces
 pub fn diff_traces(
     baseline: &Path,
     current: &Path,
     format: &str,
     only_changes: bool,
+    match_by: &MatchBy,
+    threshold: &DurationThreshold,
+) -> Result<DiffResult> {
+    let result = compare_traces(baseline, current, match_by, threshold)?;
+
+    match format {
+        "json" => println!("{}", to_pretty_json(&result.to_json())?),
+        _ => {
+            print_span_changes(&result, "");
+            if !only_changes || !result.has_changes() {
+                println!(
+                    "\nSummary: {} added, {} removed, {} modified",
+                    result.added_count, result.removed_count, result.modified_count
+                );
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Compare every trace file in `baseline_dir` with the file at the same
+/// relative path in `current_dir`
+///
+/// The report lists added, removed and changed files; `details` adds each
+/// changed file's span differences. The JSON format always includes them.
+pub fn diff_trace_dirs(
+    baseline_dir: &Path,
+    current_dir: &Path,
+    format: &str,
+    only_changes: bool,
+    details: bool,
+    match_by: &MatchBy,
+    threshold: &DurationThreshold,
+) -> Result<DirDiffResult> {
+    let baseline_files = trace_files(baseline_dir)?;
+    let mut current_files = trace_files(current_dir)?;
+
+    let mut result = DirDiffResult::default();
+    for (name, baseline) in baseline_files {
+        match current_files.remove(&name) {
+            Some(current) => {
+                let diff = compare_traces(&baseline, &current, match_by, threshold)?;
+                if diff.has_changes() {
+                    result.changed.push((name, diff));
+                } else {
+                    result.unchanged_count += 1;
+                }
+            }
+            None => result.removed.push(name),
+        }
+    }
+    result.added = current_files.into_keys().collect();
+
+    match format {
+        "json" => {
+            let changed: serde_json::Map<String, Value> = result
+                .changed
+                .iter()
+                .map(|(name, diff)| (name.clone(), diff.to_json()))
+                .collect();
+            let json = serde_json::json!({
+                "added": result.added,
+                "removed": result.removed,
+                "changed": changed,
+                "unchanged_count": result.unchanged_count,
+            });
+            println!("{}", to_pretty_json(&json)?);
+        }
+        _ => {
+            for name in &result.added {
+                println!("+ {} (no baseline)", name);
+            }
+            for name in &result.removed {
+                println!("- {} (no current trace)", name);
+            }
+            for (name, diff) in &result.changed {
+                println!(
+                    "~ {}: {} added, {} removed, {} modified",
+                    name, diff.added_count, diff.removed_count, diff.modified_count
+                );
+                if details {
+                    print_span_changes(diff, "    ");
+                }
+            }
+
+            if !only_changes || !result.has_changes() {
+                println!(
+                    "\nSummary: {} file(s) changed, {} added, {} removed, {} unchanged",
+                    result.changed.len(),
+                    result.added.len(),
+                    result.removed.len(),
+                    result.unchanged_count
+                );
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Compare two trace files without printing anything
+pub fn compare_traces(
+    baseline: &Path,
+    current: &Path,
+    match_by: &MatchBy,
+    threshold: &DurationThreshold,
 ) -> Result<DiffResult> {
+    if threshold
+        .pct
+        .is_some_and(|pct| !pct.is_finite() || pct < 0.0)
+    {
+        return Err(CleanroomError::validation_error(
+            "Duration threshold percentage must be a non-negative number",
+        ));
+    }
+
     // Read baseline and current traces
-    let baseline_content = std::fs::read_to_string(baseline)
-        .map_err(|e| CleanroomError::io_error(format!("Failed to read baseline: {}", e)))?;
+    let baseline_content = std::fs::read_to_string(baseline).map_err(|e| {
+        CleanroomError::io_error(format!(
+            "Failed to read baseline {}: {}",
+            baseline.display(),
+            e
+        ))
+    })?;
 
-    let current_content = std::fs::read_to_string(current)
-        .map_err(|e| CleanroomError::io_error(format!("Failed to read current: {}", e)))?;
+    let current_content = std::fs::read_to_string(current).map_err(|e| {
+        CleanroomError::io_error(format!(
+            "Failed to read current {}: {}",
+            current.display(),
+            e
+        ))
+    })?;
 
     // Parse JSON traces
-    let baseline_json: serde_json::Value =
-        serde_json::from_str(&baseline_content).map_err(|e| {
-            CleanroomError::serialization_error(format!("Failed to parse baseline JSON: {}", e))
-        })?;
+    let baseline_json: Value = serde_json::from_str(&baseline_content).map_err(|e| {
+        CleanroomError::serialization_error(format!(
+            "Failed to parse baseline JSON {}: {}",
+            baseline.display(),
+            e
+        ))
+    })?;
 
-    let current_json: serde_json::Value = serde_json::from_str(&current_content).map_err(|e| {
-        CleanroomError::serialization_error(format!("Failed to parse current JSON: {}", e))
+    let current_json: Value = serde_json::from_str(&current_content).map_err(|e| {
+        CleanroomError::serialization_error(format!(
+            "Failed to parse current JSON {}: {}",
+            current.display(),
+            e
+        ))
     })?;
 
-    // Extract span names
-    let baseline_spans = extract_span_names(&baseline_json);
-    let current_spans = extract_span_names(&current_json);
+    // Extract spans and pair them across the traces
+    let baseline_spans = extract_spans(&baseline_json);
+    let current_spans = extract_spans(&current_json);
+    let pairing = pair_spans(&baseline_spans, &current_spans, match_by);
 
     // Compute differences
-    let added: Vec<String> = current_spans
+    let added: Vec<String> = pairing
+        .added
         .iter()
-        .filter(|s| !baseline_spans.contains(s))
-        .cloned()
+        .map(|span| span.label(match_by))
         .collect();
 
-    let removed: Vec<String> = baseline_spans
+    let removed: Vec<String> = pairing
+        .removed
         .iter()
-        .filter(|s| !current_spans.contains(s))
-        .cloned()
+        .map(|span| span.label(match_by))
         .collect();
 
-    // For now, we don't detect modifications (would need deeper analysis)
-    let modified = Vec::new();
+    let modified: Vec<String> = pairing
+        .matched
+        .iter()
+        .filter_map(|(before, after)| span_changes(before, after, match_by, threshold))
+        .collect();
 
-    let result = DiffResult {
+    Ok(DiffResult {
         added_count: added.len(),
         removed_count: removed.len(),
         modified_count: modified.len(),
         added,
         removed,
         modified,
-    };
+    })
+}
 
-    // Display results
-    match format {
-        "json" => {
-            let json = serde_json::json!({
-                "added_count": result.added_count,
-                "removed_count": result.removed_count,
-                "modified_count": result.modified_count,
-                "added": result.added,
-                "removed": result.removed,
-                "modified": result.modified,
-            });
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&json).map_err(|e| {
-                    CleanroomError::serialization_error(format!("Failed to serialize JSON: {}", e))
-                })?
-            );
+/// Print the added, removed and modified spans of a diff, each line
+/// prefixed with `indent`
+fn print_span_changes(result: &DiffResult, indent: &str) {
+    if result.added_count > 0 {
+        println!("{}Added spans ({}):", indent, result.added_count);
+        for span in &result.added {
+            println!("{}  + {}", indent, span);
         }
-        _ => {
-            // Human-readable format
-            if result.added_count > 0 {
-                println!("Added spans ({}):", result.added_count);
-                for span in &result.added {
-                    println!("  + {}", span);
-                }
-            }
+    }
 
-            if result.removed_count > 0 {
-                println!("Removed spans ({}):", result.removed_count);
-                for span in &result.removed {
-                    println!("  - {}", span);
-                }
-            }
+    if result.removed_count > 0 {
+        println!("{}Removed spans ({}):", indent, result.removed_count);
+        for span in &result.removed {
+            println!("{}  - {}", indent, span);
+        }
+    }
 
-            if result.modified_count > 0 {
-                println!("Modified spans ({}):", result.modified_count);
-                for span in &result.modified {
-                    println!("  ~ {}", span);
-                }
-            }
+    if result.modified_count > 0 {
+        println!("{}Modified spans ({}):", indent, result.modified_count);
+        for span in &result.modified {
+            println!("{}  ~ {}", indent, span);
+        }
+    }
+}
 
-            if !only_changes
-                || result.added_count + result.removed_count + result.modified_count == 0
-            {
-                println!(
-                    "\nSummary: {} added, {} removed, {} modified",
-                    result.added_count, result.removed_count, result.modified_count
-                );
-            }
+fn to_pretty_json(json: &Value) -> Result<String> {
+    serde_json::to_string_pretty(json).map_err(|e| {
+        CleanroomError::serialization_error(format!("Failed to serialize JSON: {}", e))
+    })
+}
+
+/// The `*.json` trace files under `dir`, keyed by their path relative to it
+fn trace_files(dir: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut files = BTreeMap::new();
+    for entry in WalkDir::new(dir).follow_links(true) {
+        let entry = entry.map_err(|e| {
+            CleanroomError::io_error(format!("Failed to read {}: {}", dir.display(), e))
+        })?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
         }
+        let name = path
+            .strip_prefix(dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .to_string();
+        files.insert(name, entry.into_path());
     }
+    Ok(files)
+}
 
-    Ok(result)
+/// A span found in a JSON trace
+#[derive(Debug, Clone)]
+struct TraceSpan {
+    name: String,
+    span_id: Option<String>,
+    parent_span_id: Option<String>,
+    /// Names from the root span down to this one, e.g. `run > step > db`
+    path: String,
+    duration_ns: Option<u64>,
+    attributes: BTreeMap<String, String>,
 }
 
-/// Extract span names from JSON trace
-fn extract_span_names(json: &serde_json::Value) -> Vec<String> {
-    let mut spans = Vec::new();
+impl TraceSpan {
+    fn from_json(name: &str, span: &Value) -> Self {
+        let id = |snake: &str, camel: &str| {
+            span.get(snake)
+                .or_else(|| span.get(camel))
+                .and_then(Value::as_str)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+        };
+
+        Self {
+            name: name.to_string(),
+            span_id: id("span_id", "spanId"),
+            parent_span_id: id("parent_span_id", "parentSpanId"),
+            path: name.to_string(),
+            duration_ns: span_duration_ns(span),
+            attributes: span_attributes(span),
+        }
+    }
+
+    /// Key spans are paired by; spans without an ID fall back to their path
+    fn key(&self, match_by: &MatchBy) -> &str {
+        match (match_by, &self.span_id) {
+            (MatchBy::Id, Some(span_id)) => span_id,
+            _ => &self.path,
+        }
+    }
+
+    /// How the span is shown in the diff
+    fn label(&self, match_by: &MatchBy) -> String {
+        match (match_by, &self.span_id) {
+            (MatchBy::Id, Some(span_id)) => format!("{} ({})", self.path, span_id),
+            _ => self.path.clone(),
+        }
+    }
+}
+
+/// Spans paired across the baseline and current traces
+#[derive(Default)]
+struct Pairing<'a> {
+    matched: Vec<(&'a TraceSpan, &'a TraceSpan)>,
+    added: Vec<&'a TraceSpan>,
+    removed: Vec<&'a TraceSpan>,
+}
+
+/// Pair spans with equal keys in trace order
+fn pair_spans<'a>(
+    baseline: &'a [TraceSpan],
+    current: &'a [TraceSpan],
+    match_by: &MatchBy,
+) -> Pairing<'a> {
+    let mut unpaired: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (idx, span) in current.iter().enumerate() {
+        unpaired
+            .entry(span.key(match_by))
+            .or_default()
+            .push_back(idx);
+    }
 
-    if let Some(array) = json.as_array() {
-        for item in array {
-            if let Some(name) = item.get("name").and_then(|n| n.as_str()) {
-                spans.push(name.to_string());
+    let mut pairing = Pairing::default();
+    for span in baseline {
+        match unpaired
+            .get_mut(span.key(match_by))
+            .and_then(VecDeque::pop_front)
+        {
+            Some(idx) => pairing.matched.push((span, &current[idx])),
+            None => pairing.removed.push(span),
+        }
+    }
+
+    let mut added: Vec<usize> = unpaired.into_values().flatten().collect();
+    added.sort_unstable();
+    pairing.added = added.into_iter().map(|idx| &current[idx]).collect();
+    pairing
+}
+
+/// Describe how a paired span changed, if it did
+///
+/// Duration changes only count when they reach the threshold.
+fn span_changes(
+    before: &TraceSpan,
+    after: &TraceSpan,
+    match_by: &MatchBy,
+    threshold: &DurationThreshold,
+) -> Option<String> {
+    let mut changes = Vec::new();
+
+    for (key, old) in &before.attributes {
+        match after.attributes.get(key) {
+            Some(new) if new != old => {
+                changes.push(format!("attribute '{}' {} -> {}", key, old, new))
             }
+            None => changes.push(format!("attribute '{}' removed", key)),
+            _ => {}
         }
-    } else if let Some(obj) = json.as_object() {
-        if let Some(name) = obj.get("name").and_then(|n| n.as_str()) {
-            spans.push(name.to_string());
+    }
+    for (key, new) in &after.attributes {
+        if !before.attributes.contains_key(key) {
+            changes.push(format!("attribute '{}' added ({})", key, new));
         }
+    }
 
-        // Recursively extract from nested structures
-        for (_, value) in obj {
-            spans.extend(extract_span_names(value));
+    if let (Some(old), Some(new)) = (before.duration_ns, after.duration_ns) {
+        if threshold.is_significant(old, new) {
+            let delta_ms = (new as f64 - old as f64) / 1_000_000.0;
+            let change = if old == 0 {
+                format!("{:+.3}ms", delta_ms)
+            } else {
+                let delta_pct = (new as f64 - old as f64) * 100.0 / old as f64;
+                format!("{:+.3}ms, {:+.1}%", delta_ms, delta_pct)
+            };
+            changes.push(format!(
+                "duration {:.3}ms -> {:.3}ms ({})",
+                old as f64 / 1_000_000.0,
+                new as f64 / 1_000_000.0,
+                change
+            ));
         }
     }
 
+    if changes.is_empty() {
+        None
+    } else {
+        Some(format!("{}: {}", after.label(match_by), changes.join("; ")))
+    }
+}
+
+/// Extract spans from JSON trace
+///
+/// Any object with a `name` is a span; other objects and arrays are searched
+/// recursively, so both flat span arrays and OTLP's nested
+/// `resourceSpans`/`scopeSpans` layout work.
+fn extract_spans(json: &Value) -> Vec<TraceSpan> {
+    let mut spans = Vec::new();
+    collect_spans(json, &mut spans);
+    assign_paths(&mut spans);
     spans
 }
+
+fn collect_spans(json: &Value, spans: &mut Vec<TraceSpan>) {
+    match json {
+        Value::Array(items) => {
+            for item in items {
+                collect_spans(item, spans);
+            }
+        }
+        Value::Object(obj) => {
+            if let Some(name) = obj.get("name").and_then(Value::as_str) {
+                spans.push(TraceSpan::from_json(name, json));
+                return;
+            }
+
+            for (key, value) in obj {
+                if !NON_SPAN_KEYS.contains(&key.as_str()) {
+                    collect_spans(value, spans);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Fill in each span's name path by following parent span IDs
+fn assign_paths(spans: &mut [TraceSpan]) {
+    let by_id: HashMap<&str, usize> = spans
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, span)| Some((span.span_id.as_deref()?, idx)))
+        .collect();
+
+    let paths: Vec<String> = spans
+        .iter()
+        .map(|span| {
+            let mut names = vec![span.name.as_str()];
+            let mut parent = span.parent_span_id.as_deref();
+            // Bounded by the span count in case parent links form a cycle
+            while let Some(&idx) = parent.and_then(|id| by_id.get(id)) {
+                if names.len() > spans.len() {
+                    break;
+                }
+                names.push(&spans[idx].name);
+                parent = spans[idx].parent_span_id.as_deref();
+            }
+            names.reverse();
+            names.join(" > ")
+        })
+        .collect();
+
+    for (span, path) in spans.iter_mut().zip(paths) {
+        span.path = path;
+    }
+}
+
+/// Span attributes as text, from a `{key: value}` map or OTLP's
+/// `[{key, value: {stringValue: ...}}]` list
+fn span_attributes(span: &Value) -> BTreeMap<String, String> {
+    let text = |value: &Value| {
+        value
+            .as_str()
+            .map_or_else(|| value.to_string(), str::to_string)
+    };
+
+    match span.get("attributes") {
+        Some(Value::Object(map)) => map.iter().map(|(k, v)| (k.clone(), text(v))).collect(),
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(|item| {
+                let key = item.get("key")?.as_str()?;
+                let value = match item.get("value")? {
+                    Value::Object(typed) if typed.len() == 1 => typed.values().next()?,
+                    value => value,
+                };
+                Some((key.to_string(), text(value)))
+            })
+            .collect(),
+        _ => BTreeMap::new(),
+    }
+}
+/// Span duration from its start/end timestamps
+///
+/// Accepts clnrm's snake_case fields and OTLP JSON's camelCase ones, as
+/// numbers or (as OTLP encodes 64-bit integers) numeric strings.
+fn span_duration_ns(span: &Value) -> Option<u64> {
+    let timestamp = |snake: &str, camel: &str| {
+        let value = span.get(snake).or_else(|| span.get(camel))?;
+        value
+            .as_u64()
+            .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+    };
+
+    let start = timestamp("start_time_unix_nano", "startTimeUnixNano")?;
+    let end = timestamp("end_time_unix_nano", "endTimeUnixNano")?;
+    end.checked_sub(start)
+}
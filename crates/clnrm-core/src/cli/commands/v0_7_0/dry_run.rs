@@ -20,23 +20,33 @@ pub struct ValidationResult {
 }
 
 /// Validate configuration files without execution
-pub fn dry_run_validate(files: Vec<&Path>, verbose: bool) -> Result<Vec<ValidationResult>> {
+///
+/// Every problem in a file is reported, up to `max_errors` per file.
+pub fn dry_run_validate(
+    files: Vec<&Path>,
+    verbose: bool,
+    max_errors: usize,
+) -> Result<Vec<ValidationResult>> {
     let mut results = Vec::new();
 
     for file in files {
-        let mut validator = ShapeValidator::new();
+        let mut validator = ShapeValidator::new().with_max_errors(max_errors);
         let validation_result = validator.validate_file(file)?;
 
         let errors: Vec<String> = validation_result
             .errors
             .iter()
-            .map(|e| format!("{:?}: {}", e.category, e.message))
+            .map(|e| match e.line {
+                Some(line) => format!("line {}: {:?}: {}", line, e.category, e.message),
+                None => format!("{:?}: {}", e.category, e.message),
+            })
             .collect();
+        let error_count = errors.len() + validation_result.suppressed;
 
         results.push(ValidationResult {
             file_path: validation_result.file_path.clone(),
             valid: validation_result.passed,
-            error_count: errors.len(),
+            error_count,
             errors: errors.clone(),
         });
 
@@ -44,11 +54,17 @@ pub fn dry_run_validate(files: Vec<&Path>, verbose: bool) -> Result<Vec<Validati
         if validation_result.passed {
             println!("✅ {} - VALID", file.display());
         } else {
-            println!("❌ {} - INVALID ({} errors)", file.display(), errors.len());
+            println!("❌ {} - INVALID ({} errors)", file.display(), error_count);
             if verbose {
                 for error in &errors {
                     println!("  - {}", error);
                 }
+                if validation_result.suppressed > 0 {
+                    println!(
+                        "  ... {} more error(s) not shown (--max-errors {})",
+                        validation_result.suppressed, max_errors
+                    );
+                }
             }
         }
     }
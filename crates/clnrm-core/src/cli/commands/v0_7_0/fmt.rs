@@ -1,9 +1,14 @@
 //! TOML formatting command for Cleanroom v0.7.0
 //!
-//! Provides deterministic TOML formatting with --check mode for CI integration.
+//! Provides deterministic TOML formatting with --check mode for CI integration,
+//! and --stdin for editors that format a buffer without a temp file.
 
 use crate::error::{CleanroomError, Result};
-use crate::formatting::{format_toml_file, needs_formatting, verify_idempotency};
+use crate::formatting::{
+    content_needs_formatting, format_toml_content, format_toml_file, needs_formatting,
+    verify_idempotency,
+};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
@@ -52,6 +57,54 @@ pub fn format_files(files: &[PathBuf], check: bool, verify: bool) -> Result<()>
     }
 }
 
+/// Format TOML read from stdin and write the result to stdout
+pub fn format_stdin(check: bool, verify: bool) -> Result<()> {
+    format_stream(
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+        check,
+        verify,
+    )
+}
+
+/// Format TOML read from `input` and write the result to `output`
+///
+/// In check mode nothing is written; an error is returned if the input
+/// isn't already formatted.
+pub fn format_stream(
+    mut input: impl Read,
+    mut output: impl Write,
+    check: bool,
+    verify: bool,
+) -> Result<()> {
+    let mut content = String::new();
+    input
+        .read_to_string(&mut content)
+        .map_err(|e| CleanroomError::io_error(format!("Failed to read TOML input: {}", e)))?;
+
+    if check {
+        if content_needs_formatting(&content)? {
+            return Err(CleanroomError::validation_error(
+                "stdin needs formatting. Run 'clnrm fmt --stdin' to format it.",
+            ));
+        }
+        return Ok(());
+    }
+
+    let formatted = format_toml_content(&content)?;
+
+    if verify && !verify_idempotency(&formatted)? {
+        return Err(CleanroomError::validation_error(
+            "Formatting is not idempotent for stdin",
+        ));
+    }
+
+    output
+        .write_all(formatted.as_bytes())
+        .and_then(|()| output.flush())
+        .map_err(|e| CleanroomError::io_error(format!("Failed to write formatted output: {}", e)))
+}
+
 /// Check if files need formatting (for CI)
 fn check_formatting(files: &[PathBuf]) -> Result<()> {
     let mut unformatted_files = Vec::new();
@@ -1,14 +1,37 @@
 //! Graph command - Visualize OpenTelemetry trace graphs
 //!
-//! Generates ASCII, DOT, JSON, or Mermaid visualizations of trace spans and their relationships.
+//! Generates ASCII, DOT, JSON, Mermaid, or SVG visualizations of trace spans and their relationships.
+//!
+//! A span whose parent is not in the trace has a missing edge. DOT output
+//! always draws it red and dashed to a placeholder for the absent parent;
+//! with `--highlight-missing` the ASCII tree annotates it and the SVG
+//! diagram draws it the same way.
 
 use crate::cli::types::GraphFormat;
 use crate::error::{CleanroomError, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::Path;
 use tracing::{debug, info};
 
+/// Height of a span box in the SVG diagram
+const SVG_NODE_HEIGHT: f64 = 40.0;
+
+/// Vertical distance between the tops of parent and child boxes
+const SVG_LEVEL_HEIGHT: f64 = 80.0;
+
+/// Horizontal space between neighbouring boxes
+const SVG_COLUMN_GAP: f64 = 20.0;
+
+/// Approximate width of a label character at the diagram's font size
+const SVG_CHAR_WIDTH: f64 = 7.2;
+
+/// Margin around the diagram
+const SVG_MARGIN: f64 = 20.0;
+
+/// Colour of missing edges and placeholder parents
+const SVG_MISSING_COLOR: &str = "#d32f2f";
+
 #[derive(Debug, Deserialize, Serialize)]
 struct Span {
     #[serde(default)]
@@ -21,6 +44,20 @@ struct Span {
     trace_id: String,
     #[serde(default)]
     kind: String,
+    #[serde(default)]
+    start_time_unix_nano: Option<u64>,
+    #[serde(default)]
+    end_time_unix_nano: Option<u64>,
+}
+
+impl Span {
+    /// Duration in milliseconds, when both timestamps are recorded
+    fn duration_ms(&self) -> Option<f64> {
+        match (self.start_time_unix_nano, self.end_time_unix_nano) {
+            (Some(start), Some(end)) => Some(end.saturating_sub(start) as f64 / 1_000_000.0),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -30,11 +67,14 @@ struct TraceData {
 }
 
 /// Visualize OpenTelemetry trace graph
+///
+/// Prints the visualization, or writes it to `output` when given.
 pub fn visualize_graph(
     trace_path: &Path,
     format: &GraphFormat,
     highlight_missing: bool,
     filter: Option<&str>,
+    output: Option<&Path>,
 ) -> Result<()> {
     info!("Loading trace from {}", trace_path.display());
 
@@ -60,26 +100,106 @@ pub fn visualize_graph(
     info!("Found {} span(s) to visualize", spans.len());
 
     // Generate visualization based on format
-    match format {
-        GraphFormat::Ascii => {
-            let output = generate_ascii_tree(&spans, highlight_missing)?;
-            println!("{}", output);
+    let rendered = match format {
+        GraphFormat::Ascii => generate_ascii_tree(&spans, highlight_missing)?,
+        GraphFormat::Dot => generate_dot_graph(&spans)?,
+        GraphFormat::Json => generate_json_graph(&spans)?,
+        GraphFormat::Mermaid => generate_mermaid_diagram(&spans)?,
+        GraphFormat::Svg => generate_svg_diagram(&spans, highlight_missing)?,
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &rendered).map_err(|e| {
+                CleanroomError::io_error(format!(
+                    "Failed to write graph to {}: {}",
+                    path.display(),
+                    e
+                ))
+            })?;
+            info!("Graph written to {}", path.display());
         }
-        GraphFormat::Dot => {
-            let output = generate_dot_graph(&spans)?;
-            println!("{}", output);
+        None => println!("{}", rendered),
+    }
+
+    Ok(())
+}
+
+/// Spans arranged as parent/child trees
+///
+/// Spans are referred to by their index in the trace. Each span is listed
+/// under exactly one parent, so walks that skip visited spans terminate
+/// even when span IDs repeat.
+struct SpanTree<'a> {
+    spans: &'a [Span],
+    /// Indexes of each span's children, by parent span ID
+    children: HashMap<&'a str, Vec<usize>>,
+    /// Spans without a parent
+    roots: Vec<usize>,
+    /// Spans whose parent is not in the trace, by the missing parent's ID
+    orphans: BTreeMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> SpanTree<'a> {
+    /// Link spans to their parents
+    ///
+    /// # Errors
+    /// Returns error if parent links form a cycle
+    fn new(spans: &'a [Span]) -> Result<Self> {
+        let mut by_id: HashMap<&str, usize> = HashMap::new();
+        for (index, span) in spans.iter().enumerate() {
+            by_id.entry(span.span_id.as_str()).or_insert(index);
         }
-        GraphFormat::Json => {
-            let output = generate_json_graph(&spans)?;
-            println!("{}", output);
+
+        let mut tree = Self {
+            spans,
+            children: HashMap::new(),
+            roots: Vec::new(),
+            orphans: BTreeMap::new(),
+        };
+        for (index, span) in spans.iter().enumerate() {
+            match span.parent_span_id.as_deref() {
+                None => tree.roots.push(index),
+                Some(parent) if by_id.contains_key(parent) => {
+                    tree.children.entry(parent).or_default().push(index)
+                }
+                Some(parent) => tree.orphans.entry(parent).or_default().push(index),
+            }
         }
-        GraphFormat::Mermaid => {
-            let output = generate_mermaid_diagram(&spans)?;
-            println!("{}", output);
+
+        // Every span must lead up to a root or an orphan
+        for start in 0..spans.len() {
+            let mut path = vec![start];
+            let mut seen = HashSet::from([start]);
+            let mut current = start;
+            while let Some(parent) = spans[current].parent_span_id.as_deref() {
+                let Some(&next) = by_id.get(parent) else {
+                    break;
+                };
+                path.push(next);
+                if !seen.insert(next) {
+                    let names: Vec<&str> = path
+                        .iter()
+                        .skip_while(|&&index| index != next)
+                        .map(|&index| spans[index].name.as_str())
+                        .collect();
+                    return Err(CleanroomError::validation_error(format!(
+                        "Span graph contains a cycle: {}",
+                        names.join(" -> ")
+                    )));
+                }
+                current = next;
+            }
         }
+
+        Ok(tree)
     }
 
-    Ok(())
+    fn children_of(&self, index: usize) -> &[usize] {
+        self.children
+            .get(self.spans[index].span_id.as_str())
+            .map_or(&[], Vec::as_slice)
+    }
 }
 
 /// Load trace data from file
@@ -106,25 +226,15 @@ fn generate_ascii_tree(spans: &[Span], highlight_missing: bool) -> Result<String
     output.push_str("=========================\n\n");
 
     // Build parent-child relationships
-    let mut children_map: HashMap<String, Vec<&Span>> = HashMap::new();
-    let mut root_spans = Vec::new();
-
-    for span in spans {
-        if let Some(parent_id) = &span.parent_span_id {
-            children_map
-                .entry(parent_id.clone())
-                .or_default()
-                .push(span);
-        } else {
-            root_spans.push(span);
-        }
-    }
+    let tree = SpanTree::new(spans)?;
+    let mut visited = vec![false; spans.len()];
 
     // Render tree starting from root spans
-    for root in root_spans {
+    for &root in &tree.roots {
         render_span_tree(
+            &tree,
             root,
-            &children_map,
+            &mut visited,
             &mut output,
             "",
             true,
@@ -132,6 +242,27 @@ fn generate_ascii_tree(spans: &[Span], highlight_missing: bool) -> Result<String
         );
     }
 
+    // Spans whose parent is missing from the trace
+    for (parent_id, orphans) in &tree.orphans {
+        let prefix = if highlight_missing {
+            output.push_str(&format!("└── (missing parent {})\n", parent_id));
+            "    "
+        } else {
+            ""
+        };
+        for (idx, &orphan) in orphans.iter().enumerate() {
+            render_span_tree(
+                &tree,
+                orphan,
+                &mut visited,
+                &mut output,
+                prefix,
+                !highlight_missing || idx == orphans.len() - 1,
+                highlight_missing,
+            );
+        }
+    }
+
     if output.trim().is_empty() {
         output.push_str("(no spans to display)\n");
     }
@@ -141,14 +272,20 @@ fn generate_ascii_tree(spans: &[Span], highlight_missing: bool) -> Result<String
 
 /// Recursively render span tree
 fn render_span_tree(
-    span: &Span,
-    children_map: &HashMap<String, Vec<&Span>>,
+    tree: &SpanTree,
+    index: usize,
+    visited: &mut [bool],
     output: &mut String,
     prefix: &str,
     is_last: bool,
     highlight_missing: bool,
 ) {
+    if std::mem::replace(&mut visited[index], true) {
+        return;
+    }
+
     // Render current span
+    let span = &tree.spans[index];
     let connector = if is_last { "└──" } else { "├──" };
     output.push_str(&format!(
         "{}{} {} ({})\n",
@@ -156,30 +293,44 @@ fn render_span_tree(
     ));
 
     // Render children
-    if let Some(children) = children_map.get(&span.span_id) {
+    let children = tree.children_of(index);
+    if !children.is_empty() {
         let child_prefix = format!("{}{}   ", prefix, if is_last { " " } else { "│" });
 
-        for (idx, child) in children.iter().enumerate() {
+        for (idx, &child) in children.iter().enumerate() {
             let is_last_child = idx == children.len() - 1;
             render_span_tree(
+                tree,
                 child,
-                children_map,
+                visited,
                 output,
                 &child_prefix,
                 is_last_child,
                 highlight_missing,
             );
         }
-    } else if highlight_missing && !children_map.is_empty() {
+    } else if highlight_missing && !tree.children.is_empty() {
         let child_prefix = format!("{}{}   ", prefix, if is_last { " " } else { "│" });
         output.push_str(&format!("{}└── (no children)\n", child_prefix));
     }
 }
 
 /// Generate DOT graph for Graphviz
+///
+/// Nodes are labelled with the span name and duration, edges with the
+/// child's span kind. A parent missing from the trace gets a placeholder
+/// node, and edges to it are drawn red and dashed. Output follows trace
+/// order, so a trace always renders to the same text.
 fn generate_dot_graph(spans: &[Span]) -> Result<String> {
     debug!("Generating DOT graph");
 
+    let span_ids: HashSet<&str> = spans.iter().map(|span| span.span_id.as_str()).collect();
+    let missing_parents: BTreeSet<&str> = spans
+        .iter()
+        .filter_map(|span| span.parent_span_id.as_deref())
+        .filter(|parent| !span_ids.contains(parent))
+        .collect();
+
     let mut output = String::new();
     output.push_str("digraph trace {\n");
     output.push_str("  rankdir=TB;\n");
@@ -187,8 +338,22 @@ fn generate_dot_graph(spans: &[Span]) -> Result<String> {
 
     // Add nodes
     for span in spans {
-        let label = format!("{}\\n{}", span.name, span.kind);
-        output.push_str(&format!("  \"{}\" [label=\"{}\"];\n", span.span_id, label));
+        let label = match span.duration_ms() {
+            Some(duration) => format!("{}\n{:.3}ms", span.name, duration),
+            None => span.name.clone(),
+        };
+        output.push_str(&format!(
+            "  \"{}\" [label=\"{}\"];\n",
+            escape_dot(&span.span_id),
+            escape_dot(&label)
+        ));
+    }
+    for parent_id in &missing_parents {
+        output.push_str(&format!(
+            "  \"{}\" [label=\"{}\", color=red, style=dashed];\n",
+            escape_dot(parent_id),
+            escape_dot(&format!("missing parent {}", parent_id))
+        ));
     }
 
     output.push('\n');
@@ -196,7 +361,24 @@ fn generate_dot_graph(spans: &[Span]) -> Result<String> {
     // Add edges
     for span in spans {
         if let Some(parent_id) = &span.parent_span_id {
-            output.push_str(&format!("  \"{}\" -> \"{}\";\n", parent_id, span.span_id));
+            let mut attributes = Vec::new();
+            if !span.kind.is_empty() {
+                attributes.push(format!("label=\"{}\"", escape_dot(&span.kind)));
+            }
+            if missing_parents.contains(parent_id.as_str()) {
+                attributes.push("color=red, style=dashed".to_string());
+            }
+            let attributes = if attributes.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attributes.join(", "))
+            };
+            output.push_str(&format!(
+                "  \"{}\" -> \"{}\"{};\n",
+                escape_dot(parent_id),
+                escape_dot(&span.span_id),
+                attributes
+            ));
         }
     }
 
@@ -205,6 +387,13 @@ fn generate_dot_graph(spans: &[Span]) -> Result<String> {
     Ok(output)
 }
 
+/// Escape text for a quoted DOT string; newlines become line breaks
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
 /// Generate JSON graph structure
 fn generate_json_graph(spans: &[Span]) -> Result<String> {
     debug!("Generating JSON graph");
@@ -285,3 +474,211 @@ fn sanitize_mermaid_id(id: &str) -> String {
         .map(|c| if c.is_alphanumeric() { c } else { '_' })
         .collect()
 }
+
+/// A box in the SVG diagram
+struct SvgNode {
+    label: String,
+    kind: String,
+    column: f64,
+    depth: usize,
+    missing: bool,
+}
+
+/// Boxes and edges of an SVG diagram being laid out
+///
+/// Leaves take the next free column and a parent is centred over its
+/// children. Edges are `(parent, child, missing)` node indexes.
+struct SvgLayout<'a> {
+    tree: &'a SpanTree<'a>,
+    nodes: Vec<SvgNode>,
+    edges: Vec<(usize, usize, bool)>,
+    visited: Vec<bool>,
+    next_column: usize,
+}
+
+impl<'a> SvgLayout<'a> {
+    fn new(tree: &'a SpanTree<'a>) -> Self {
+        Self {
+            tree,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+            visited: vec![false; tree.spans.len()],
+            next_column: 0,
+        }
+    }
+
+    fn take_column(&mut self) -> f64 {
+        self.next_column += 1;
+        (self.next_column - 1) as f64
+    }
+
+    /// Lay out the subtree of span `index`, returning its node; spans
+    /// already laid out are skipped
+    fn place_span(&mut self, index: usize, depth: usize) -> Option<usize> {
+        if std::mem::replace(&mut self.visited[index], true) {
+            return None;
+        }
+
+        let span = &self.tree.spans[index];
+        let node = self.nodes.len();
+        self.nodes.push(SvgNode {
+            label: span.name.clone(),
+            kind: span.kind.clone(),
+            column: 0.0,
+            depth,
+            missing: false,
+        });
+
+        let children: Vec<usize> = self
+            .tree
+            .children_of(index)
+            .iter()
+            .filter_map(|&child| self.place_span(child, depth + 1))
+            .collect();
+        self.nodes[node].column = self.center_over(&children);
+        for child in children {
+            self.edges.push((node, child, false));
+        }
+
+        Some(node)
+    }
+
+    /// Lay out spans whose parent `parent_id` is missing under a
+    /// placeholder, joined by missing edges
+    fn place_missing_parent(&mut self, parent_id: &str, orphans: &[usize]) {
+        let placeholder = self.nodes.len();
+        self.nodes.push(SvgNode {
+            label: format!("missing parent {}", parent_id),
+            kind: String::new(),
+            column: 0.0,
+            depth: 0,
+            missing: true,
+        });
+
+        let children: Vec<usize> = orphans
+            .iter()
+            .filter_map(|&orphan| self.place_span(orphan, 1))
+            .collect();
+        self.nodes[placeholder].column = self.center_over(&children);
+        for child in children {
+            self.edges.push((placeholder, child, true));
+        }
+    }
+
+    /// Column centred over `children`, or a new column for a leaf
+    fn center_over(&mut self, children: &[usize]) -> f64 {
+        match (children.first(), children.last()) {
+            (Some(&first), Some(&last)) => {
+                (self.nodes[first].column + self.nodes[last].column) / 2.0
+            }
+            _ => self.take_column(),
+        }
+    }
+}
+
+/// Generate an SVG diagram with spans as boxes and parent edges as lines
+///
+/// With `highlight_missing`, spans whose parent is absent hang off a red
+/// placeholder through a dashed red edge; otherwise they are drawn as roots.
+fn generate_svg_diagram(spans: &[Span], highlight_missing: bool) -> Result<String> {
+    debug!("Generating SVG diagram");
+
+    let tree = SpanTree::new(spans)?;
+    let mut layout = SvgLayout::new(&tree);
+    for &root in &tree.roots {
+        layout.place_span(root, 0);
+    }
+    for (parent_id, orphans) in &tree.orphans {
+        if highlight_missing {
+            layout.place_missing_parent(parent_id, orphans);
+        } else {
+            for &orphan in orphans {
+                layout.place_span(orphan, 0);
+            }
+        }
+    }
+    let SvgLayout { nodes, edges, .. } = layout;
+
+    let node_width = nodes
+        .iter()
+        .map(|node| node.label.chars().count().max(node.kind.chars().count()))
+        .max()
+        .map_or(0.0, |chars| chars as f64 * SVG_CHAR_WIDTH + 24.0)
+        .max(80.0);
+    let column_width = node_width + SVG_COLUMN_GAP;
+    let max_column = nodes.iter().map(|node| node.column).fold(0.0, f64::max);
+    let max_depth = nodes.iter().map(|node| node.depth).max().unwrap_or(0);
+    let width = 2.0 * SVG_MARGIN + (max_column + 1.0) * column_width - SVG_COLUMN_GAP;
+    let height = 2.0 * SVG_MARGIN + max_depth as f64 * SVG_LEVEL_HEIGHT + SVG_NODE_HEIGHT;
+
+    let left = |node: &SvgNode| SVG_MARGIN + node.column * column_width;
+    let top = |node: &SvgNode| SVG_MARGIN + node.depth as f64 * SVG_LEVEL_HEIGHT;
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.0}\" height=\"{h:.0}\" viewBox=\"0 0 {w:.0} {h:.0}\" font-family=\"monospace\" font-size=\"12\">\n",
+        w = width,
+        h = height
+    ));
+    output.push_str("  <rect width=\"100%\" height=\"100%\" fill=\"white\"/>\n");
+
+    for &(parent, child, missing) in &edges {
+        let (parent, child) = (&nodes[parent], &nodes[child]);
+        let (stroke, dash) = if missing {
+            (SVG_MISSING_COLOR, " stroke-dasharray=\"6 4\"")
+        } else {
+            ("#555555", "")
+        };
+        output.push_str(&format!(
+            "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\" stroke-width=\"1.5\"{}/>\n",
+            left(parent) + node_width / 2.0,
+            top(parent) + SVG_NODE_HEIGHT,
+            left(child) + node_width / 2.0,
+            top(child),
+            stroke,
+            dash
+        ));
+    }
+
+    for node in &nodes {
+        let (fill, stroke, dash) = if node.missing {
+            ("#ffebee", SVG_MISSING_COLOR, " stroke-dasharray=\"6 4\"")
+        } else {
+            ("#e3f2fd", "#1565c0", "")
+        };
+        let (x, y) = (left(node), top(node));
+        let center = x + node_width / 2.0;
+        output.push_str(&format!(
+            "  <rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"6\" fill=\"{}\" stroke=\"{}\"{}/>\n",
+            x, y, node_width, SVG_NODE_HEIGHT, fill, stroke, dash
+        ));
+
+        // Name on one line, or name above a smaller kind
+        let label_y = if node.kind.is_empty() { 24.0 } else { 17.0 };
+        output.push_str(&format!(
+            "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+            center,
+            y + label_y,
+            escape_xml(&node.label)
+        ));
+        if !node.kind.is_empty() {
+            output.push_str(&format!(
+                "  <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#666666\" font-size=\"10\">{}</text>\n",
+                center,
+                y + 31.0,
+                escape_xml(&node.kind)
+            ));
+        }
+    }
+
+    output.push_str("</svg>\n");
+    Ok(output)
+}
+
+/// Escape text for an SVG text node
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
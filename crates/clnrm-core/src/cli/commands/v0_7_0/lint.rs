@@ -1,28 +1,203 @@
 //! Lint command for static analysis of test configurations
 //!
 //! Provides linting and best practice checking for TOML test files.
+//! Every finding belongs to a rule with a stable ID, shown as `[rule-id]`
+//! and used as the SARIF `ruleId`.
+//!
+//! Templated files (`.toml.tera`) are additionally checked for variable
+//! usage against `[vars]` and `--map`:
+//! - `unused-var` (warning): a variable is defined but never referenced
+//! - `undefined-var` (error): the template reads a variable nothing defines
+//!
+//! Every file is checked for keys the configuration schema doesn't declare:
+//! - `unknown-key` (warning): a misspelt or stale key, with a suggestion when
+//!   a known field is close enough (`dry-run` reports these as errors)
 
 use crate::error::{CleanroomError, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
 use std::path::Path;
 
+/// Severity a lint rule reports at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintLevel {
+    /// Reported, but only fails the run with `--deny-warnings`
+    Warning,
+    /// Always fails the run
+    Error,
+}
+
+impl LintLevel {
+    /// SARIF `level` for findings at this severity
+    fn sarif_level(self) -> &'static str {
+        match self {
+            LintLevel::Warning => "warning",
+            LintLevel::Error => "error",
+        }
+    }
+}
+
+/// A lint rule
+#[derive(Debug)]
+pub struct LintRule {
+    /// Stable identifier
+    pub id: &'static str,
+    /// Severity every finding of this rule has
+    pub level: LintLevel,
+    /// Short description of what the rule expects
+    pub description: &'static str,
+}
+
+/// Lint rule: file isn't TOML matching the test configuration schema
+const RULE_PARSE_ERROR: LintRule = LintRule {
+    id: "parse-error",
+    level: LintLevel::Error,
+    description: "File must be valid TOML matching the test configuration schema",
+};
+/// Lint rule: template can't be scanned or rendered
+const RULE_TEMPLATE_ERROR: LintRule = LintRule {
+    id: "template-error",
+    level: LintLevel::Error,
+    description: "Template must be valid Tera and render successfully",
+};
+/// Lint rule: no `[meta]` or `[test.metadata]`
+const RULE_MISSING_META: LintRule = LintRule {
+    id: "missing-meta",
+    level: LintLevel::Error,
+    description: "Test must have a [meta] or [test.metadata] section",
+};
+/// Lint rule: nothing to run
+const RULE_NO_STEPS: LintRule = LintRule {
+    id: "no-steps",
+    level: LintLevel::Error,
+    description: "Test must define at least one scenario or step",
+};
+/// Lint rule: test has no description
+const RULE_MISSING_DESCRIPTION: LintRule = LintRule {
+    id: "missing-description",
+    level: LintLevel::Warning,
+    description: "Test should have a description",
+};
+/// Lint rule: `[otel]` relies on the default sample ratio
+const RULE_OTEL_SAMPLE_RATIO: LintRule = LintRule {
+    id: "otel-sample-ratio",
+    level: LintLevel::Warning,
+    description: "[otel] should set sample_ratio explicitly",
+};
+/// Lint rule: scenario name outside `[A-Za-z0-9_-]`
+const RULE_SCENARIO_NAME: LintRule = LintRule {
+    id: "scenario-name",
+    level: LintLevel::Warning,
+    description: "Scenario names should only use alphanumerics, '_' and '-'",
+};
+/// Lint rule: `[vars]` / `--map` entry the template never references
+const RULE_UNUSED_VAR: LintRule = LintRule {
+    id: "unused-var",
+    level: LintLevel::Warning,
+    description: "Template variables should be referenced by the template",
+};
+/// Lint rule: template references a variable nothing defines
+const RULE_UNDEFINED_VAR: LintRule = LintRule {
+    id: "undefined-var",
+    level: LintLevel::Error,
+    description: "Template variables must be defined in [vars], --map or the defaults",
+};
+/// Lint rule: key the configuration schema doesn't declare
+const RULE_UNKNOWN_KEY: LintRule = LintRule {
+    id: "unknown-key",
+    level: LintLevel::Warning,
+    description: "Configuration keys should be declared by the schema",
+};
+
+/// Every lint rule, in the order SARIF output lists them
+pub const LINT_RULES: &[&LintRule] = &[
+    &RULE_PARSE_ERROR,
+    &RULE_TEMPLATE_ERROR,
+    &RULE_MISSING_META,
+    &RULE_NO_STEPS,
+    &RULE_MISSING_DESCRIPTION,
+    &RULE_OTEL_SAMPLE_RATIO,
+    &RULE_SCENARIO_NAME,
+    &RULE_UNUSED_VAR,
+    &RULE_UNDEFINED_VAR,
+    &RULE_UNKNOWN_KEY,
+];
+
+/// A single lint finding
+#[derive(Debug, Clone)]
+pub struct LintDiagnostic {
+    /// Rule that produced the finding
+    pub rule: &'static LintRule,
+    /// 1-based line in the file, when the finding has one
+    pub line: Option<usize>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] ", self.rule.id)?;
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
 /// Lint result for a single file
 #[derive(Debug, Clone)]
 pub struct LintResult {
     /// File path
     pub file_path: String,
     /// Warnings found
-    pub warnings: Vec<String>,
+    pub warnings: Vec<LintDiagnostic>,
     /// Errors found
-    pub errors: Vec<String>,
+    pub errors: Vec<LintDiagnostic>,
+}
+
+impl LintResult {
+    fn new(file: &Path) -> Self {
+        Self {
+            file_path: file.to_string_lossy().into_owned(),
+            warnings: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Record a finding under the list matching its rule's level
+    fn report(&mut self, rule: &'static LintRule, line: Option<usize>, message: impl Into<String>) {
+        let diagnostic = LintDiagnostic {
+            rule,
+            line,
+            message: message.into(),
+        };
+        match rule.level {
+            LintLevel::Warning => self.warnings.push(diagnostic),
+            LintLevel::Error => self.errors.push(diagnostic),
+        }
+    }
 }
 
 /// Lint test configuration files
-pub fn lint_files(files: Vec<&Path>, format: &str, deny_warnings: bool) -> Result<()> {
+///
+/// `map` holds extra `key=value` template variables, as passed to `render`.
+/// `sarif` output is a single SARIF 2.1.0 log covering every file.
+pub fn lint_files(
+    files: Vec<&Path>,
+    format: &str,
+    deny_warnings: bool,
+    map: &[String],
+) -> Result<()> {
     let mut total_warnings = 0;
     let mut total_errors = 0;
+    let mut sarif_results = Vec::new();
+
+    let map_vars = parse_var_mappings(map)?;
 
     for file in files {
-        let result = lint_single_file(file)?;
+        let result = lint_single_file(file, &map_vars)?;
+        total_warnings += result.warnings.len();
+        total_errors += result.errors.len();
 
         // Display results based on format
         match format {
@@ -30,36 +205,46 @@ pub fn lint_files(files: Vec<&Path>, format: &str, deny_warnings: bool) -> Resul
                 // Print JSON format
                 let json = serde_json::json!({
                     "file": result.file_path,
-                    "warnings": result.warnings,
-                    "errors": result.errors,
+                    "warnings": result.warnings.iter().map(ToString::to_string).collect::<Vec<_>>(),
+                    "errors": result.errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
                 });
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&json).map_err(|e| {
-                        CleanroomError::serialization_error(format!(
-                            "Failed to serialize JSON: {}",
-                            e
-                        ))
-                    })?
-                );
+                println!("{}", to_pretty_json(&json)?);
+            }
+            "sarif" => sarif_results.push(result),
+            "github" => {
+                // GitHub Actions workflow commands (shown as PR annotations)
+                for (level, diagnostics) in
+                    [("warning", &result.warnings), ("error", &result.errors)]
+                {
+                    for diagnostic in diagnostics {
+                        let line = diagnostic
+                            .line
+                            .map(|line| format!(",line={}", line))
+                            .unwrap_or_default();
+                        println!(
+                            "::{} file={}{}::{}",
+                            level, result.file_path, line, diagnostic
+                        );
+                    }
+                }
             }
             _ => {
                 // Human-readable format
                 println!("{}", file.display());
                 for warning in &result.warnings {
                     println!("  ⚠️  {}", warning);
-                    total_warnings += 1;
                 }
                 for error in &result.errors {
                     println!("  ❌ {}", error);
-                    total_errors += 1;
                 }
             }
         }
     }
 
-    // Summary
-    if format != "json" {
+    if format == "sarif" {
+        println!("{}", to_pretty_json(&sarif_log(&sarif_results))?);
+    } else if format != "json" {
+        // Summary
         println!("\nLint summary:");
         println!("  Warnings: {}", total_warnings);
         println!("  Errors: {}", total_errors);
@@ -76,37 +261,169 @@ pub fn lint_files(files: Vec<&Path>, format: &str, deny_warnings: bool) -> Resul
     Ok(())
 }
 
+fn to_pretty_json(value: &serde_json::Value) -> Result<String> {
+    serde_json::to_string_pretty(value).map_err(|e| {
+        CleanroomError::serialization_error(format!("Failed to serialize JSON: {}", e))
+    })
+}
+
+/// Build a SARIF 2.1.0 log with one run covering every linted file
+///
+/// All rules are listed in `tool.driver.rules` so rule IDs keep stable
+/// indices. Findings without a line are reported against line 1, since
+/// code scanning requires a region on each result.
+fn sarif_log(results: &[LintResult]) -> serde_json::Value {
+    let rules: Vec<_> = LINT_RULES
+        .iter()
+        .map(|rule| {
+            serde_json::json!({
+                "id": rule.id,
+                "shortDescription": { "text": rule.description },
+                "defaultConfiguration": { "level": rule.level.sarif_level() },
+            })
+        })
+        .collect();
+
+    let findings: Vec<_> = results
+        .iter()
+        .flat_map(|result| {
+            result
+                .errors
+                .iter()
+                .chain(&result.warnings)
+                .map(move |diagnostic| {
+                    serde_json::json!({
+                        "ruleId": diagnostic.rule.id,
+                        "ruleIndex": LINT_RULES.iter().position(|rule| rule.id == diagnostic.rule.id),
+                        "level": diagnostic.rule.level.sarif_level(),
+                        "message": { "text": diagnostic.message },
+                        "locations": [{
+                            "physicalLocation": {
+                                "artifactLocation": sarif_artifact_location(&result.file_path),
+                                "region": { "startLine": diagnostic.line.unwrap_or(1) },
+                            }
+                        }],
+                    })
+                })
+        })
+        .collect();
+
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "clnrm",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": findings,
+        }]
+    })
+}
+
+/// SARIF artifact location for a linted path
+///
+/// Relative paths are resolved against the checkout root (`%SRCROOT%`),
+/// which is how code scanning matches results to repository files.
+fn sarif_artifact_location(file_path: &str) -> serde_json::Value {
+    let path = Path::new(file_path);
+    if path.is_absolute() {
+        if let Ok(url) = url::Url::from_file_path(path) {
+            return serde_json::json!({ "uri": url.as_str() });
+        }
+    }
+
+    let uri = file_path
+        .trim_start_matches("./")
+        .replace(std::path::MAIN_SEPARATOR, "/");
+    serde_json::json!({ "uri": uri, "uriBaseId": "%SRCROOT%" })
+}
+
+/// Parse `key=value` variable mappings
+fn parse_var_mappings(map: &[String]) -> Result<HashMap<String, serde_json::Value>> {
+    let mut vars = HashMap::new();
+    for mapping in map {
+        let (key, value) = mapping.split_once('=').ok_or_else(|| {
+            CleanroomError::validation_error(format!(
+                "Invalid variable mapping: '{}' (expected key=value format)",
+                mapping
+            ))
+        })?;
+        vars.insert(
+            key.to_string(),
+            serde_json::Value::String(value.to_string()),
+        );
+    }
+    Ok(vars)
+}
+
 /// Lint a single file
-fn lint_single_file(file: &Path) -> Result<LintResult> {
-    let mut warnings = Vec::new();
-    let mut errors = Vec::new();
+fn lint_single_file(
+    file: &Path,
+    map_vars: &HashMap<String, serde_json::Value>,
+) -> Result<LintResult> {
+    let mut result = LintResult::new(file);
 
     // Read file
     let content = std::fs::read_to_string(file).map_err(|e| {
         CleanroomError::io_error(format!("Failed to read file {}: {}", file.display(), e))
     })?;
 
+    // Templates get variable checks, then are rendered for the structural checks
+    let content = if crate::is_template(&content) {
+        match lint_template_vars(file, &content, map_vars, &mut result)? {
+            Some(rendered) => rendered,
+            None => return Ok(result),
+        }
+    } else {
+        content
+    };
+
     // Parse as TestConfig
-    let config: crate::config::TestConfig = toml::from_str(&content)
-        .map_err(|e| CleanroomError::config_error(format!("Failed to parse TOML: {}", e)))?;
+    let config: crate::config::TestConfig = match toml::from_str(&content) {
+        Ok(config) => config,
+        Err(e) => {
+            let line = e
+                .span()
+                .map(|span| crate::validation::shape::line_of(&content, span.start));
+            result.report(&RULE_PARSE_ERROR, line, e.message());
+            return Ok(result);
+        }
+    };
+
+    for unknown in crate::validation::find_unknown_keys(&content) {
+        result.report(&RULE_UNKNOWN_KEY, unknown.line, unknown.message);
+    }
 
     // Check for common issues
     if config.meta.is_none() && config.test.is_none() {
-        errors.push("Missing [meta] or [test.metadata] section".to_string());
+        result.report(
+            &RULE_MISSING_META,
+            None,
+            "Missing [meta] or [test.metadata] section",
+        );
     }
 
     if config.scenario.is_empty() && config.steps.is_empty() {
-        errors.push("No scenarios or steps defined".to_string());
+        result.report(&RULE_NO_STEPS, None, "No scenarios or steps defined");
     }
 
     // Check for best practices
     if config.get_description().is_none() {
-        warnings.push("Missing test description".to_string());
+        result.report(&RULE_MISSING_DESCRIPTION, None, "Missing test description");
     }
 
     if let Some(ref otel) = config.otel {
         if otel.sample_ratio.is_none() {
-            warnings.push("OTEL sample_ratio not specified (defaults to 1.0)".to_string());
+            result.report(
+                &RULE_OTEL_SAMPLE_RATIO,
+                None,
+                "OTEL sample_ratio not specified (defaults to 1.0)",
+            );
         }
     }
 
@@ -117,16 +434,134 @@ fn lint_single_file(file: &Path) -> Result<LintResult> {
             .chars()
             .all(|c| c.is_alphanumeric() || c == '_' || c == '-')
         {
-            warnings.push(format!(
-                "Scenario '{}' contains special characters (prefer alphanumeric + _-)",
-                scenario.name
-            ));
+            result.report(
+                &RULE_SCENARIO_NAME,
+                None,
+                format!(
+                    "Scenario '{}' contains special characters (prefer alphanumeric + _-)",
+                    scenario.name
+                ),
+            );
         }
     }
 
-    Ok(LintResult {
-        file_path: file.to_string_lossy().into_owned(),
-        warnings,
-        errors,
-    })
+    Ok(result)
+}
+
+/// Run the `unused-var` / `undefined-var` rules on a template
+///
+/// Returns the rendered TOML, or `None` if the template could not be
+/// rendered (the reason is recorded in `result`).
+fn lint_template_vars(
+    file: &Path,
+    content: &str,
+    map_vars: &HashMap<String, serde_json::Value>,
+    result: &mut LintResult,
+) -> Result<Option<String>> {
+    let refs = match clnrm_template::scan_variables(content) {
+        Ok(refs) => refs,
+        Err(e) => {
+            result.report(&RULE_TEMPLATE_ERROR, None, e.to_string());
+            return Ok(None);
+        }
+    };
+
+    let file_vars = vars_section(content);
+    let builtin_vars: BTreeSet<String> = crate::TemplateContext::with_defaults()
+        .vars
+        .into_keys()
+        .collect();
+
+    // Variables the author defined and should therefore be used
+    let defined: BTreeSet<&String> = file_vars.keys().chain(map_vars.keys()).collect();
+
+    for name in &defined {
+        if !refs.may_use(name) {
+            let source = if file_vars.contains_key(*name) {
+                "[vars]"
+            } else {
+                "--map"
+            };
+            result.report(
+                &RULE_UNUSED_VAR,
+                None,
+                format!(
+                    "Variable '{}' is defined in {} but never used",
+                    name, source
+                ),
+            );
+        }
+    }
+
+    let mut undefined = false;
+    for name in &refs.required {
+        if !defined.contains(name) && !builtin_vars.contains(name) {
+            undefined = true;
+            result.report(
+                &RULE_UNDEFINED_VAR,
+                None,
+                format!("Template references undefined variable '{}'", name),
+            );
+        }
+    }
+
+    let mut renderer = crate::TemplateRenderer::with_defaults()?;
+    renderer.merge_user_vars(
+        file_vars
+            .into_iter()
+            .filter_map(|(key, value)| Some((key, value?)))
+            .collect(),
+    );
+    renderer.merge_user_vars(map_vars.clone());
+    match renderer.render_file(file) {
+        Ok(rendered) => Ok(Some(rendered)),
+        Err(e) => {
+            // An undefined variable already explains the render failure
+            if !undefined {
+                result.report(
+                    &RULE_TEMPLATE_ERROR,
+                    None,
+                    format!("Template rendering failed: {}", e),
+                );
+            }
+            Ok(None)
+        }
+    }
+}
+
+/// Extract `[vars]` entries from raw template content
+///
+/// The file is a template, so it usually isn't valid TOML until rendered.
+/// Each `key = value` line in the `[vars]` table is parsed on its own; the
+/// value is `None` when it is itself templated (e.g. `seed = {{ seed }}`)
+/// or otherwise not a plain TOML value.
+fn vars_section(content: &str) -> HashMap<String, Option<serde_json::Value>> {
+    let mut vars = HashMap::new();
+    let mut in_vars = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_vars = trimmed == "[vars]";
+            continue;
+        }
+        if !in_vars || trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some((key, _)) = trimmed.split_once('=') {
+            let key = key.trim().trim_matches('"').to_string();
+            let value = if crate::is_template(trimmed) {
+                None
+            } else {
+                toml::from_str::<toml::Table>(trimmed)
+                    .ok()
+                    .and_then(|table| table.into_iter().next())
+                    .and_then(|(_, value)| serde_json::to_value(value).ok())
+            };
+            vars.insert(key, value);
+        }
+    }
+
+    vars
 }
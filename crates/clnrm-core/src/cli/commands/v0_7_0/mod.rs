@@ -16,6 +16,7 @@
 //! - redgreen: TDD validation (FULLY IMPLEMENTED)
 //! - render: Template rendering (implemented)
 //! - spans: Span filtering (IMPLEMENTED)
+//! - verify-digest: Normalized trace digest verification
 //! - collector: OTEL collector management (stub)
 
 pub mod analyze;
@@ -33,3 +34,4 @@ pub mod redgreen;
 pub mod redgreen_impl;
 pub mod repro;
 pub mod spans;
+pub mod verify_digest;
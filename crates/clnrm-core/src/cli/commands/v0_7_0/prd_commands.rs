@@ -3,8 +3,9 @@
 //! These are placeholder implementations for PRD v1.0 features.
 //! Full implementations to be added as PRD requirements are finalized.
 
-use crate::cli::types::OutputFormat;
+use crate::cli::types::{OutputFormat, RenderFormat};
 use crate::error::{CleanroomError, Result};
+use crate::validation::shape::ShapeValidator;
 use std::path::{Path, PathBuf};
 use tracing::info;
 
@@ -17,6 +18,13 @@ pub async fn pull_images(paths: Option<Vec<PathBuf>>, parallel: bool, jobs: usiz
     super::pull::pull_images(paths, parallel, jobs).await
 }
 
+/// Report which images declared by test configurations are missing locally
+///
+/// Inspects the local image store only; nothing is pulled.
+pub async fn check_images(paths: Option<Vec<PathBuf>>) -> Result<super::pull::ImageCheck> {
+    super::pull::check_images(paths).await
+}
+
 /// Visualize OpenTelemetry trace graph
 ///
 /// Generates visual representation of span relationships.
@@ -26,9 +34,10 @@ pub fn visualize_graph(
     format: &crate::cli::types::GraphFormat,
     highlight_missing: bool,
     filter: Option<&str>,
+    output: Option<&Path>,
 ) -> Result<()> {
     // Delegate to the actual implementation in graph module
-    super::graph::visualize_graph(trace, format, highlight_missing, filter)
+    super::graph::visualize_graph(trace, format, highlight_missing, filter, output)
 }
 
 /// Reproduce a previous test run from baseline
@@ -53,8 +62,10 @@ pub async fn reproduce_baseline(
     output: Option<&PathBuf>,
 ) -> Result<()> {
     use crate::cli::commands::run::run_tests_sequential_with_results;
-    use crate::cli::commands::v0_7_0::record::{BaselineRecord, BaselineTestResult};
-    use crate::cli::types::{CliConfig, OutputFormat};
+    use crate::cli::commands::v0_7_0::record::{
+        recorded_spans, BaselineRecord, BaselineTestResult,
+    };
+    use crate::cli::types::{CliConfig, OutputFormat, ShardStrategy};
 
     info!(
         "🔄 Reproducing test run from baseline: {}",
@@ -118,13 +129,28 @@ pub async fn reproduce_baseline(
         jobs: 1,
         format: OutputFormat::Auto,
         fail_fast: false,
+        bail: None,
         watch: false,
         verbose: 0,
         force: true,   // Force run all tests
         digest: false, // No digest needed for reproduction
+        junit_flat: false,
+        env_file: None,
+        vars_file: None,
+        seed: None,
+        scenario_filter: None,
+        scenario_only: false,
+        shard_strategy: ShardStrategy::default(),
+        keep_services: false,
+        timeout: None,
+        inherit_env: Vec::new(),
+        ..Default::default()
     };
 
     let results = run_tests_sequential_with_results(&test_paths, &config).await?;
+    let redactor = crate::config::load_record_config("cleanroom.toml")?
+        .redact
+        .redactor()?;
 
     // 4. Convert to baseline format for comparison
     let reproduction_results: Vec<BaselineTestResult> = results
@@ -134,6 +160,7 @@ pub async fn reproduce_baseline(
             passed: r.passed,
             duration_ms: r.duration_ms,
             file_path: extract_file_path_for_comparison(&r.name),
+            spans: recorded_spans(r, &redactor),
         })
         .collect();
 
@@ -299,32 +326,115 @@ pub async fn run_red_green_validation(
 
 /// Render Tera template with variable mappings
 ///
-/// Renders a template file with user-provided variables.
+/// Renders a template file with user-provided variables. With
+/// `RenderFormat::Json` the rendered TOML is parsed into a `TestConfig` and
+/// emitted as pretty-printed JSON. With `check` the rendered TOML must also
+/// pass shape validation, or nothing is written.
+#[allow(clippy::too_many_arguments)]
 pub fn render_template_with_vars(
     template: &Path,
     map: &[String],
     output: Option<&PathBuf>,
     show_vars: bool,
+    format: &RenderFormat,
+    vars_file: Option<&Path>,
+    env_file: Option<&Path>,
+    include_dir: Option<&Path>,
+    check: bool,
 ) -> Result<()> {
     info!("🎨 Rendering template: {}", template.display());
+    let vars = template_vars(map, vars_file, show_vars)?;
+
+    // Use existing template renderer
+    let rendered = if env_file.is_none() && include_dir.is_none() {
+        crate::render_template_file(template, vars)?
+    } else {
+        let mut renderer = configured_renderer(env_file, include_dir)?;
+        renderer.merge_user_vars(vars);
+        renderer.render_file(template)?
+    };
+
+    if check {
+        check_rendered_config(template, &rendered)?;
+    }
+
+    let rendered = match format {
+        RenderFormat::Toml => rendered,
+        RenderFormat::Json => rendered_config_to_json(&rendered)?,
+    };
+
+    // Write output or print to stdout
+    if let Some(out) = output {
+        std::fs::write(out, rendered)
+            .map_err(|e| CleanroomError::io_error(format!("Failed to write output: {}", e)))?;
+        info!("✓ Rendered template written to: {}", out.display());
+    } else {
+        println!("{}", rendered);
+    }
+
+    Ok(())
+}
+
+/// Render every `*.clnrm.toml.tera` template under `dir`
+///
+/// Each template becomes a `.clnrm.toml` file at the same relative path under
+/// `output` (default: next to the template). All templates share the same
+/// variables. Rendering stops at the first template that fails; with `check`
+/// each rendered file must also be a valid test config.
+#[allow(clippy::too_many_arguments)]
+pub fn render_template_dir(
+    dir: &Path,
+    output: Option<&Path>,
+    map: &[String],
+    show_vars: bool,
+    vars_file: Option<&Path>,
+    env_file: Option<&Path>,
+    include_dir: Option<&Path>,
+    check: bool,
+) -> Result<Vec<PathBuf>> {
+    info!("🎨 Rendering templates in: {}", dir.display());
+    let vars = template_vars(map, vars_file, show_vars)?;
+
+    let mut renderer = configured_renderer(env_file, include_dir)?;
+    renderer.merge_user_vars(vars);
+    let rendered = renderer.render_all(dir, output.unwrap_or(dir))?;
+
+    for file in &rendered {
+        if check {
+            let content = std::fs::read_to_string(file).map_err(|e| {
+                CleanroomError::io_error(format!("Failed to read {}: {}", file.display(), e))
+            })?;
+            check_rendered_config(file, &content)?;
+        }
+        info!("✓ Rendered {}", file.display());
+    }
+    info!("✓ Rendered {} template(s)", rendered.len());
+
+    Ok(rendered)
+}
+
+/// Load `--vars-file` and parse `--map` variable mappings on top of it,
+/// logging them with `show_vars`
+fn template_vars(
+    map: &[String],
+    vars_file: Option<&Path>,
+    show_vars: bool,
+) -> Result<std::collections::HashMap<String, serde_json::Value>> {
     info!("  Variable mappings: {:?}", map);
     info!("  Show vars: {}", show_vars);
 
-    // Parse variable mappings from key=value format
-    let mut vars = std::collections::HashMap::new();
-    for mapping in map {
-        let parts: Vec<&str> = mapping.splitn(2, '=').collect();
-        if parts.len() == 2 {
-            vars.insert(
-                parts[0].to_string(),
-                serde_json::Value::String(parts[1].to_string()),
-            );
-        } else {
-            return Err(CleanroomError::validation_error(format!(
-                "Invalid variable mapping: '{}' (expected key=value format)",
-                mapping
-            )));
+    let mut vars = match vars_file {
+        Some(vars_file) => {
+            info!("  Vars file: {}", vars_file.display());
+            crate::TemplateContext::from_json_file(vars_file)?.vars
         }
+        None => std::collections::HashMap::new(),
+    };
+
+    // Parse variable mappings from key=value / key:=json format
+    for mapping in map {
+        let (key, value) = parse_var_mapping(mapping)?;
+        vars.insert(key, value);
     }
 
     if show_vars {
@@ -334,19 +444,107 @@ pub fn render_template_with_vars(
         }
     }
 
-    // Use existing template renderer
-    let rendered = crate::render_template_file(template, vars)?;
+    Ok(vars)
+}
 
-    // Write output or print to stdout
-    if let Some(out) = output {
-        std::fs::write(out, rendered)
-            .map_err(|e| CleanroomError::io_error(format!("Failed to write output: {}", e)))?;
-        info!("✓ Rendered template written to: {}", out.display());
-    } else {
-        println!("{}", rendered);
+/// Renderer with PRD defaults plus an optional `.env` file and partials directory
+fn configured_renderer(
+    env_file: Option<&Path>,
+    include_dir: Option<&Path>,
+) -> Result<crate::TemplateRenderer> {
+    let mut renderer = crate::TemplateRenderer::with_defaults()?;
+    if let Some(env_file) = env_file {
+        info!("  Env file: {}", env_file.display());
+        renderer = renderer.with_env_file(env_file)?;
+    }
+    if let Some(include_dir) = include_dir {
+        info!("  Include dir: {}", include_dir.display());
+        renderer = renderer.with_include_dir(include_dir)?;
     }
+    Ok(renderer)
+}
 
-    Ok(())
+/// Parse a `--map` variable mapping
+///
+/// `key=value` maps `key` to the string `value`; `key:=value` parses `value`
+/// as JSON, so numbers, booleans, arrays and objects keep their type (e.g.
+/// `ports:=[8080,8081]`).
+pub fn parse_var_mapping(mapping: &str) -> Result<(String, serde_json::Value)> {
+    let Some((key, value)) = mapping.split_once('=') else {
+        return Err(CleanroomError::validation_error(format!(
+            "Invalid variable mapping: '{}' (expected key=value or key:=json format)",
+            mapping
+        )));
+    };
+
+    match key.strip_suffix(':') {
+        Some(key) => {
+            let value = serde_json::from_str(value).map_err(|e| {
+                CleanroomError::validation_error(format!(
+                    "Invalid JSON value for variable '{}': {}",
+                    key, e
+                ))
+            })?;
+            Ok((key.to_string(), value))
+        }
+        None => Ok((
+            key.to_string(),
+            serde_json::Value::String(value.to_string()),
+        )),
+    }
+}
+
+/// Shape-validate rendered TOML as a test config
+///
+/// # Errors
+/// Returns a validation error listing every problem with the rendered line
+/// it points at.
+fn check_rendered_config(template: &Path, rendered: &str) -> Result<()> {
+    let result = ShapeValidator::new().validate_content(rendered, &template.display().to_string());
+    if result.passed {
+        info!("✓ Rendered config is valid");
+        return Ok(());
+    }
+
+    let lines: Vec<&str> = rendered.lines().collect();
+    let mut message = format!(
+        "{} does not render to a valid test config:",
+        template.display()
+    );
+    for error in &result.errors {
+        match error.line {
+            Some(line) => {
+                message.push_str(&format!("\n  line {}: {}", line, error.message));
+                if let Some(text) = line.checked_sub(1).and_then(|i| lines.get(i)) {
+                    message.push_str(&format!("\n    {} | {}", line, text));
+                }
+            }
+            None => message.push_str(&format!("\n  {}", error.message)),
+        }
+    }
+    if result.suppressed > 0 {
+        message.push_str(&format!(
+            "\n  ... {} more error(s) not shown",
+            result.suppressed
+        ));
+    }
+
+    Err(CleanroomError::validation_error(message))
+}
+
+/// Convert rendered TOML into the fully-resolved `TestConfig` as JSON
+///
+/// The config is parsed and validated as `clnrm run` loads it, so only a
+/// runnable config is exported.
+fn rendered_config_to_json(rendered: &str) -> Result<String> {
+    let config = crate::config::parse_toml_config(rendered)?;
+    config
+        .validate()
+        .map_err(|e| e.with_code(crate::error::ErrorCode::InvalidConfig))?;
+
+    serde_json::to_string_pretty(&config).map_err(|e| {
+        CleanroomError::serialization_error(format!("Failed to serialize config to JSON: {}", e))
+    })
 }
 
 /// Filter and search OpenTelemetry spans
@@ -359,9 +557,10 @@ pub fn filter_spans(
     format: &OutputFormat,
     show_attrs: bool,
     show_events: bool,
+    stats: bool,
 ) -> Result<()> {
     // Delegate to the actual implementation in spans module
-    super::spans::filter_spans(trace, grep, format, show_attrs, show_events)
+    super::spans::filter_spans(trace, grep, format, show_attrs, show_events, stats)
 }
 
 /// Start local OTEL collector
@@ -372,10 +571,11 @@ pub async fn start_collector(
     image: &str,
     http_port: u16,
     grpc_port: u16,
+    config: Option<&Path>,
     detach: bool,
 ) -> Result<()> {
     // Delegate to the actual implementation in collector module
-    super::collector::start_collector(image, http_port, grpc_port, detach).await
+    super::collector::start_collector(image, http_port, grpc_port, config, detach).await
 }
 
 /// Stop local OTEL collector
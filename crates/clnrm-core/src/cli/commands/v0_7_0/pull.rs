@@ -1,6 +1,8 @@
 //! Pull command - Pre-pull Docker images from test configurations
 //!
 //! Scans test files for Docker images and pulls them in advance to avoid delays during test execution.
+//! With `--check`, only reports which images are missing locally, e.g. to know what to
+//! preload for an air-gapped environment.
 
 use crate::config::TestConfig;
 use crate::error::{CleanroomError, Result};
@@ -52,6 +54,77 @@ pub async fn pull_images(paths: Option<Vec<PathBuf>>, parallel: bool, jobs: usiz
     Ok(())
 }
 
+/// Local presence of the images declared by test configurations
+#[derive(Debug, Clone, Default)]
+pub struct ImageCheck {
+    /// Images already available locally
+    pub present: Vec<String>,
+    /// Images that would have to be pulled
+    pub missing: Vec<String>,
+}
+
+/// Report which declared images are present locally, without pulling any
+pub async fn check_images(paths: Option<Vec<PathBuf>>) -> Result<ImageCheck> {
+    info!("Scanning test files for Docker images to check");
+
+    let test_files =
+        discover_test_files_from_paths(&paths.unwrap_or_else(|| vec![PathBuf::from(".")]))?;
+    let mut images = extract_images_from_test_files(&test_files)?;
+    images.sort();
+
+    let mut check = ImageCheck::default();
+    for image in images {
+        if image_present(&image).await? {
+            println!("  ✓ present  {}", image);
+            check.present.push(image);
+        } else {
+            println!("  ✗ missing  {}", image);
+            check.missing.push(image);
+        }
+    }
+
+    if check.missing.is_empty() {
+        println!("\n✅ All {} image(s) present locally", check.present.len());
+    } else {
+        println!(
+            "\n❌ {} of {} image(s) missing locally",
+            check.missing.len(),
+            check.present.len() + check.missing.len()
+        );
+    }
+    Ok(check)
+}
+
+/// Check whether an image is in the local Docker image store
+async fn image_present(image: &str) -> Result<bool> {
+    let output = tokio::process::Command::new("docker")
+        .args(["image", "inspect", "--format", "{{.Id}}", image])
+        .output()
+        .await
+        .map_err(|e| {
+            CleanroomError::container_error(format!(
+                "Failed to execute docker image inspect: {}",
+                e
+            ))
+        })?;
+
+    if output.status.success() {
+        return Ok(true);
+    }
+
+    // Any other failure (e.g. the daemon is unreachable) says nothing about
+    // the image, so it must not be reported as missing
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.to_lowercase().contains("no such image") {
+        return Ok(false);
+    }
+    Err(CleanroomError::container_error(format!(
+        "Failed to inspect image {}: {}",
+        image,
+        stderr.trim()
+    )))
+}
+
 /// Discover test files from paths
 fn discover_test_files_from_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
     let mut test_files = Vec::new();
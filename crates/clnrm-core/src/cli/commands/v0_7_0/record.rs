@@ -6,15 +6,28 @@
 //! - Saves to `.clnrm/baseline.json`
 //! - Computes SHA-256 digest
 //!
+//! Spans collected by scenarios are stored with each test. Secrets in span
+//! attributes can be masked with `--redact <key>` / `--redact-regex <pattern>`
+//! or in cleanroom.toml:
+//!
+//! ```toml
+//! [record.redact]
+//! keys = ["authorization"]
+//! patterns = ["sk-[A-Za-z0-9]+"]
+//! ```
+//!
 //! Deferred to v0.7.1:
 //! - `repro` command (replay with same seed/clock)
 //! - `redgreen` command (compare two runs)
 //! - Baseline versioning and metadata
 
 use crate::cli::commands::run::run_tests_sequential_with_results;
-use crate::cli::types::{CliConfig, OutputFormat};
+use crate::cli::types::{CliConfig, CliTestResult, OutputFormat, ShardStrategy};
 use crate::cli::utils::discover_test_files;
+use crate::config::load_record_config;
 use crate::error::{CleanroomError, Result};
+use crate::otel::redact::SpanRedactor;
+use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use tracing::{info, warn};
@@ -43,6 +56,9 @@ pub struct BaselineTestResult {
     pub duration_ms: u64,
     /// Test file path
     pub file_path: String,
+    /// Spans collected by the test's scenarios, with redacted attributes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<SpanData>,
 }
 
 /// Run baseline recording command
@@ -50,6 +66,8 @@ pub struct BaselineTestResult {
 /// # Arguments
 /// * `paths` - Optional test paths to record (default: discover all)
 /// * `output` - Optional output path (default: `.clnrm/baseline.json`)
+/// * `redact` - Span attribute keys to mask, on top of `[record.redact] keys`
+/// * `redact_regex` - Patterns to mask, on top of `[record.redact] patterns`
 ///
 /// # Returns
 /// * `Result<()>` - Success or error
@@ -58,10 +76,22 @@ pub struct BaselineTestResult {
 /// * Returns error if test execution fails
 /// * Returns error if file writing fails
 /// * Returns error if digest computation fails
-pub async fn run_record(paths: Option<Vec<PathBuf>>, output: Option<PathBuf>) -> Result<()> {
+/// * Returns error if a redaction pattern is not a valid regex
+pub async fn run_record(
+    paths: Option<Vec<PathBuf>>,
+    output: Option<PathBuf>,
+    redact: &[String],
+    redact_regex: &[String],
+) -> Result<()> {
     // Arrange - Setup configuration and paths
     info!("Starting baseline recording");
 
+    // Validate redaction rules before running anything
+    let mut redact_config = load_record_config("cleanroom.toml")?.redact;
+    redact_config.keys.extend_from_slice(redact);
+    redact_config.patterns.extend_from_slice(redact_regex);
+    let redactor = redact_config.redactor()?;
+
     let output_path = output.unwrap_or_else(|| PathBuf::from(".clnrm/baseline.json"));
     let digest_path = output_path.with_extension("sha256");
 
@@ -107,10 +137,22 @@ pub async fn run_record(paths: Option<Vec<PathBuf>>, output: Option<PathBuf>) ->
         jobs: 1,
         format: OutputFormat::Auto,
         fail_fast: false,
+        bail: None,
         watch: false,
         verbose: 0,
         force: true,  // Force run all tests for baseline
         digest: true, // Generate digest for baseline
+        junit_flat: false,
+        env_file: None,
+        vars_file: None,
+        seed: None,
+        scenario_filter: None,
+        scenario_only: false,
+        shard_strategy: ShardStrategy::default(),
+        keep_services: false,
+        timeout: None,
+        inherit_env: Vec::new(),
+        ..Default::default()
     };
 
     let results = run_tests_sequential_with_results(&all_test_files, &config).await?;
@@ -123,6 +165,7 @@ pub async fn run_record(paths: Option<Vec<PathBuf>>, output: Option<PathBuf>) ->
             passed: r.passed,
             duration_ms: r.duration_ms,
             file_path: extract_file_path(&r.name),
+            spans: recorded_spans(r, &redactor),
         })
         .collect();
 
@@ -194,6 +237,22 @@ pub async fn run_record(paths: Option<Vec<PathBuf>>, output: Option<PathBuf>) ->
     Ok(())
 }
 
+/// Spans collected by a test's steps, with `redactor` applied
+///
+/// Only attribute values are masked, so the span tree can still be compared
+/// when reproducing the baseline.
+pub(crate) fn recorded_spans(result: &CliTestResult, redactor: &SpanRedactor) -> Vec<SpanData> {
+    result
+        .steps
+        .iter()
+        .flat_map(|step| step.spans.iter().cloned())
+        .map(|mut span| {
+            redactor.redact(&mut span);
+            span
+        })
+        .collect()
+}
+
 /// Compute SHA-256 digest of JSON data
 ///
 /// # Arguments
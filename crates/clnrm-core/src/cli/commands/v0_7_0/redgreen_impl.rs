@@ -14,7 +14,7 @@
 //! - Proper error handling with context
 
 use crate::cli::commands::run::run_tests_sequential_with_results;
-use crate::cli::types::{CliConfig, CliTestResult, OutputFormat, TddState};
+use crate::cli::types::{CliConfig, CliTestResult, OutputFormat, ShardStrategy, TddState};
 use crate::error::{CleanroomError, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -253,10 +253,22 @@ pub async fn run_red_green_validation(
         jobs: 1,
         format: OutputFormat::Auto,
         fail_fast: false,
+        bail: None,
         watch: false,
         verbose: 0,
         force: true,   // Force run all tests
         digest: false, // No digest needed for TDD validation
+        junit_flat: false,
+        env_file: None,
+        vars_file: None,
+        seed: None,
+        scenario_filter: None,
+        scenario_only: false,
+        shard_strategy: ShardStrategy::default(),
+        keep_services: false,
+        timeout: None,
+        inherit_env: Vec::new(),
+        ..Default::default()
     };
 
     let results = run_tests_sequential_with_results(paths, &config).await?;
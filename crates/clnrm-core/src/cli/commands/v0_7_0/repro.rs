@@ -4,10 +4,18 @@
 //!
 //! This module provides the public API for baseline reproduction.
 //! The actual implementation is in the prd_commands module.
+//!
+//! A directory of baselines (`*.json` files, searched recursively) can be
+//! reproduced in one sweep with [`reproduce_baselines`], which prints a
+//! pass/fail matrix and fails if any baseline does not reproduce.
 
 use crate::cli::commands::v0_7_0::prd_commands::reproduce_baseline as reproduce_baseline_impl;
-use crate::error::Result;
-use std::path::Path;
+use crate::error::{CleanroomError, Result};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::task::JoinSet;
+use walkdir::WalkDir;
 
 /// Reproduce a previous test run from baseline
 ///
@@ -49,3 +57,155 @@ pub async fn reproduce_baseline(
     // Delegate to the comprehensive implementation
     reproduce_baseline_impl(baseline, verify_digest, output_buf.as_ref()).await
 }
+
+/// Outcome of reproducing one baseline in a sweep
+#[derive(Debug, Clone, Serialize)]
+pub struct BaselineReproOutcome {
+    /// Baseline file
+    pub baseline: PathBuf,
+    /// Whether the baseline reproduced
+    pub passed: bool,
+    /// Time spent reproducing, in milliseconds
+    pub duration_ms: u64,
+    /// Why the reproduction failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Reproduce every baseline in a directory
+///
+/// Baselines are `*.json` files found recursively under `dir`, reproduced
+/// up to `jobs` at a time. A summary table is printed once all of them have
+/// finished; with `output`, the outcomes are also written there as JSON.
+///
+/// # Errors
+/// * No baselines are found in `dir`
+/// * Any baseline fails to reproduce
+/// * The summary cannot be written to `output`
+pub async fn reproduce_baselines(
+    dir: &Path,
+    verify_digest: bool,
+    output: Option<&Path>,
+    jobs: usize,
+) -> Result<()> {
+    let baselines = discover_baselines(dir);
+    if baselines.is_empty() {
+        return Err(CleanroomError::validation_error(format!(
+            "No baseline files (*.json) found in '{}'",
+            dir.display()
+        )));
+    }
+
+    let jobs = jobs.max(1);
+    println!(
+        "🔄 Reproducing {} baseline(s) from {} ({} at a time)",
+        baselines.len(),
+        dir.display(),
+        jobs
+    );
+
+    let mut join_set = JoinSet::new();
+    let mut pending = baselines.iter();
+    let mut outcomes = Vec::with_capacity(baselines.len());
+
+    let spawn_repro = |join_set: &mut JoinSet<_>, baseline: &PathBuf| {
+        let baseline = baseline.clone();
+        join_set.spawn(async move {
+            let start = Instant::now();
+            let result = reproduce_baseline_impl(&baseline, verify_digest, None).await;
+            BaselineReproOutcome {
+                passed: result.is_ok(),
+                duration_ms: start.elapsed().as_millis() as u64,
+                error: result.err().map(|e| e.to_string()),
+                baseline,
+            }
+        });
+    };
+
+    for baseline in pending.by_ref().take(jobs) {
+        spawn_repro(&mut join_set, baseline);
+    }
+
+    while let Some(joined) = join_set.join_next().await {
+        let outcome = joined.map_err(|e| {
+            CleanroomError::internal_error(format!("Reproduction task failed: {}", e))
+        })?;
+        outcomes.push(outcome);
+        if let Some(baseline) = pending.next() {
+            spawn_repro(&mut join_set, baseline);
+        }
+    }
+
+    outcomes.sort_by(|a, b| a.baseline.cmp(&b.baseline));
+    print_repro_matrix(&outcomes);
+
+    if let Some(out) = output {
+        let summary = serde_json::to_string_pretty(&outcomes).map_err(|e| {
+            CleanroomError::internal_error(format!(
+                "Failed to serialize reproduction summary: {}",
+                e
+            ))
+        })?;
+        std::fs::write(out, summary).map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to write reproduction summary to '{}': {}",
+                out.display(),
+                e
+            ))
+        })?;
+        println!("📄 Reproduction summary written to: {}", out.display());
+    }
+
+    let failed = outcomes.iter().filter(|o| !o.passed).count();
+    if failed > 0 {
+        return Err(CleanroomError::validation_error(format!(
+            "{} of {} baseline(s) failed to reproduce",
+            failed,
+            outcomes.len()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Baseline files under `dir`, sorted by path
+fn discover_baselines(dir: &Path) -> Vec<PathBuf> {
+    let mut baselines: Vec<PathBuf> = WalkDir::new(dir)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    baselines.sort();
+    baselines
+}
+
+fn print_repro_matrix(outcomes: &[BaselineReproOutcome]) {
+    println!();
+    println!("📊 Reproduction Matrix:");
+    println!(
+        "{:<50} {:<8} {:>10}  DETAILS",
+        "BASELINE", "RESULT", "DURATION"
+    );
+    println!("{}", "-".repeat(90));
+    for outcome in outcomes {
+        println!(
+            "{:<50} {:<8} {:>8}ms  {}",
+            outcome.baseline.display(),
+            if outcome.passed { "PASS" } else { "FAIL" },
+            outcome.duration_ms,
+            outcome.error.as_deref().unwrap_or("")
+        );
+    }
+
+    let passed = outcomes.iter().filter(|o| o.passed).count();
+    println!();
+    println!(
+        "   {} passed, {} failed ({} total)",
+        passed,
+        outcomes.len() - passed,
+        outcomes.len()
+    );
+}
@@ -207,6 +207,7 @@ pub struct OtlpStatus {
 /// * `format` - Output format
 /// * `show_attrs` - Show span attributes in output
 /// * `show_events` - Show span events in output
+/// * `stats` - Print aggregate statistics per span name instead of the spans
 ///
 /// # Core Team Standards
 ///
@@ -219,6 +220,7 @@ pub fn filter_spans(
     format: &OutputFormat,
     show_attrs: bool,
     show_events: bool,
+    stats: bool,
 ) -> Result<()> {
     // 1. Load and parse trace
     let trace_data = load_trace(trace)?;
@@ -249,6 +251,10 @@ pub fn filter_spans(
 
     // 4. Output in requested format
     match format {
+        OutputFormat::Json if stats => output_stats_json(&compute_stats(&filtered_spans))?,
+        OutputFormat::Human | OutputFormat::Auto if stats => {
+            output_stats_table(&compute_stats(&filtered_spans))
+        }
         OutputFormat::Json => output_json(&filtered_spans, show_attrs, show_events)?,
         OutputFormat::Human | OutputFormat::Auto => {
             output_table(&filtered_spans, show_attrs, show_events)?
@@ -497,6 +503,132 @@ fn output_table(spans: &[&OtelSpan], show_attrs: bool, show_events: bool) -> Res
     Ok(())
 }
 
+/// Aggregate metrics for all spans sharing a name
+#[derive(Debug, Clone, Serialize)]
+pub struct SpanNameStats {
+    /// Span name
+    pub name: String,
+    /// Number of spans, including those without timing data
+    pub count: usize,
+    /// Number of spans with error status
+    pub errors: usize,
+    /// Spans without timing data, excluded from the percentiles
+    pub untimed: usize,
+    /// Median duration in nanoseconds
+    pub p50_ns: Option<u64>,
+    /// 95th percentile duration in nanoseconds
+    pub p95_ns: Option<u64>,
+    /// 99th percentile duration in nanoseconds
+    pub p99_ns: Option<u64>,
+}
+
+impl SpanNameStats {
+    /// Fraction of spans with error status
+    pub fn error_rate(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.count as f64
+        }
+    }
+}
+
+/// Group spans by name and compute counts, latency percentiles and errors
+///
+/// Rows are ordered slowest first by p99, with untimed names last.
+pub fn compute_stats(spans: &[&OtelSpan]) -> Vec<SpanNameStats> {
+    let mut by_name: std::collections::BTreeMap<&str, Vec<&OtelSpan>> = Default::default();
+    for span in spans {
+        by_name.entry(span.name.as_str()).or_default().push(span);
+    }
+
+    let mut stats: Vec<SpanNameStats> = by_name
+        .into_iter()
+        .map(|(name, spans)| {
+            let mut durations: Vec<u64> = spans.iter().filter_map(|s| s.duration_ns).collect();
+            durations.sort_unstable();
+
+            SpanNameStats {
+                name: name.to_string(),
+                count: spans.len(),
+                errors: spans
+                    .iter()
+                    .filter(|s| s.status == Some(SpanStatus::Error))
+                    .count(),
+                untimed: spans.len() - durations.len(),
+                p50_ns: percentile(&durations, 50),
+                p95_ns: percentile(&durations, 95),
+                p99_ns: percentile(&durations, 99),
+            }
+        })
+        .collect();
+
+    stats.sort_by(|a, b| b.p99_ns.cmp(&a.p99_ns).then_with(|| a.name.cmp(&b.name)));
+    stats
+}
+
+/// Nearest-rank percentile of sorted durations
+fn percentile(sorted: &[u64], pct: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// Output span statistics as JSON
+fn output_stats_json(stats: &[SpanNameStats]) -> Result<()> {
+    let json = serde_json::to_string_pretty(stats).map_err(|e| {
+        CleanroomError::internal_error(format!("Failed to serialize JSON output: {}", e))
+    })?;
+
+    println!("{}", json);
+    Ok(())
+}
+
+/// Output span statistics as a table
+fn output_stats_table(stats: &[SpanNameStats]) {
+    if stats.is_empty() {
+        println!("No spans found matching filter criteria.");
+        return;
+    }
+
+    println!(
+        "{:<40} {:>7} {:>10} {:>10} {:>10} {:>10}",
+        "SPAN NAME", "COUNT", "P50", "P95", "P99", "ERRORS"
+    );
+    println!("{}", "-".repeat(92));
+
+    for row in stats {
+        let marker = if row.untimed > 0 { "*" } else { "" };
+        println!(
+            "{:<40} {:>7} {:>10} {:>10} {:>10} {:>9.1}%",
+            truncate(&format!("{}{}", row.name, marker), 40),
+            row.count,
+            format_duration(row.p50_ns),
+            format_duration(row.p95_ns),
+            format_duration(row.p99_ns),
+            row.error_rate() * 100.0
+        );
+    }
+
+    let total: usize = stats.iter().map(|row| row.count).sum();
+    let errors: usize = stats.iter().map(|row| row.errors).sum();
+    let untimed: usize = stats.iter().map(|row| row.untimed).sum();
+    println!(
+        "\nTotal spans: {} across {} name(s), {} error(s)",
+        total,
+        stats.len(),
+        errors
+    );
+    if untimed > 0 {
+        println!(
+            "* {} span(s) without timing data are counted in totals but excluded from latency stats",
+            untimed
+        );
+    }
+}
+
 /// Format duration from nanoseconds
 fn format_duration(duration_ns: Option<u64>) -> String {
     match duration_ns {
@@ -0,0 +1,109 @@
+//! Trace digest verification command
+//!
+//! Recomputes the normalized digest of a trace and compares it against a
+//! recorded baseline, reporting which normalized fields differ.
+
+use crate::config::load_config_from_file;
+use crate::determinism::digest::{DigestBaseline, NormalizedTrace};
+use crate::error::{CleanroomError, Result};
+use crate::validation::span_validator::{SpanData, SpanValidator};
+use std::path::Path;
+
+/// Verify `trace` against the digest recorded in `baseline`
+///
+/// Nondeterministic fields are stripped according to the `[determinism]`
+/// section of `config`, if given. With `update`, the baseline is rewritten
+/// from the trace instead of compared.
+///
+/// # Errors
+/// * The baseline or trace cannot be read or parsed
+/// * The recomputed digest does not match the baseline
+pub fn verify_digest(
+    baseline: &Path,
+    trace: &Path,
+    config: Option<&Path>,
+    update: bool,
+) -> Result<()> {
+    let determinism = match config {
+        Some(path) => load_config_from_file(path)?.determinism,
+        None => None,
+    };
+
+    let spans = load_trace_spans(trace)?;
+    let normalized = NormalizedTrace::from_spans(&spans, determinism.as_ref());
+    let actual = normalized.digest();
+
+    if update {
+        std::fs::write(baseline, normalized.to_baseline()).map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to write digest baseline {}: {}",
+                baseline.display(),
+                e
+            ))
+        })?;
+        println!("📝 Digest baseline written: {}", baseline.display());
+        println!("   Digest: {}", actual);
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(baseline).map_err(|e| {
+        CleanroomError::io_error(format!(
+            "Failed to read digest baseline {}: {}",
+            baseline.display(),
+            e
+        ))
+    })?;
+    let expected = DigestBaseline::parse(&content)?;
+
+    if expected.digest == actual {
+        println!("✅ Digest matches: {}", actual);
+        return Ok(());
+    }
+
+    println!("❌ Digest mismatch");
+    println!("   Expected: {}", expected.digest);
+    println!("   Actual:   {}", actual);
+
+    match &expected.fields {
+        Some(fields) => {
+            let diffs = normalized.diff(fields);
+            println!();
+            println!("Normalized fields differing ({}):", diffs.len());
+            for diff in &diffs {
+                println!("  {}", diff);
+            }
+        }
+        None => {
+            println!();
+            println!("Baseline records only a digest, so differing fields cannot be shown.");
+            println!("Re-record it with --update to store the normalized fields.");
+        }
+    }
+
+    Err(CleanroomError::validation_error(format!(
+        "Trace digest {} does not match baseline digest {}",
+        actual, expected.digest
+    )))
+}
+
+/// Load spans from a pretty-printed span array (as written by `run`) or any
+/// format accepted by [`SpanValidator`]
+fn load_trace_spans(path: &Path) -> Result<Vec<SpanData>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        CleanroomError::io_error(format!("Failed to read trace {}: {}", path.display(), e))
+    })?;
+
+    let spans = match serde_json::from_str::<Vec<SpanData>>(&content) {
+        Ok(spans) => spans,
+        Err(_) => SpanValidator::from_json(&content)?.spans().to_vec(),
+    };
+
+    if spans.is_empty() {
+        return Err(CleanroomError::validation_error(format!(
+            "Trace {} contains no spans",
+            path.display()
+        )));
+    }
+
+    Ok(spans)
+}
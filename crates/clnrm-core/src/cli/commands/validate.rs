@@ -5,6 +5,7 @@
 
 use crate::cli::types::ACCEPTED_EXTENSIONS;
 use crate::cli::utils::discover_test_files;
+use crate::config::test_config_schema;
 use crate::error::{CleanroomError, Result};
 use std::path::PathBuf;
 use tracing::{debug, info};
@@ -54,6 +55,15 @@ pub fn validate_config(path: &PathBuf) -> Result<()> {
     Ok(())
 }
 
+/// Print the JSON Schema for test configuration files to stdout
+pub fn print_config_schema() -> Result<()> {
+    let schema = serde_json::to_string_pretty(&test_config_schema()).map_err(|e| {
+        CleanroomError::serialization_error(format!("Failed to serialize config schema: {}", e))
+    })?;
+    println!("{}", schema);
+    Ok(())
+}
+
 /// Validate a single test configuration file
 pub fn validate_single_config(path: &PathBuf) -> Result<()> {
     // Check file exists
@@ -71,7 +81,7 @@ pub fn validate_single_config(path: &PathBuf) -> Result<()> {
         .any(|ext| path_str.ends_with(ext))
     {
         return Err(CleanroomError::validation_error(format!(
-            "File must have .toml or .clnrm.toml extension: {}",
+            "File must have .toml, .clnrm.toml, .clnrm.yaml or .clnrm.yml extension: {}",
             path.display()
         )));
     }
@@ -80,9 +90,8 @@ pub fn validate_single_config(path: &PathBuf) -> Result<()> {
     let content = std::fs::read_to_string(path)
         .map_err(|e| CleanroomError::config_error(format!("Failed to read config file: {}", e)))?;
 
-    // Parse TOML configuration using the config structure
-    let test_config: crate::config::TestConfig = toml::from_str(&content)
-        .map_err(|e| CleanroomError::config_error(format!("TOML parse error: {}", e)))?;
+    // Parse TOML or YAML configuration using the config structure
+    let test_config = crate::config::parse_config_for_path(path, &content)?;
 
     // Basic validation
     let test_name = test_config.get_name()?;
@@ -92,12 +101,15 @@ pub fn validate_single_config(path: &PathBuf) -> Result<()> {
         ));
     }
 
-    if test_config.steps.is_empty() {
+    if test_config.steps.is_empty() && test_config.scenario.is_empty() {
         return Err(CleanroomError::validation_error(
-            "At least one step is required",
+            "At least one step or scenario is required",
         ));
     }
 
+    // Scenario dependencies must reference known scenarios and be acyclic
+    test_config.scenario_stages()?;
+
     // Log success with service count
     let service_count = test_config.services.as_ref().map(|s| s.len()).unwrap_or(0);
     info!(
@@ -8,7 +8,6 @@
 #![allow(hidden_glob_reexports)]
 
 pub mod commands;
-pub mod noun_verb_integration;
 pub mod telemetry;
 pub mod types;
 pub mod utils;
@@ -19,14 +18,16 @@ use std::path::PathBuf;
 use tracing::error;
 
 // Import utilities - using explicit paths to avoid shadowing pub use exports
-use self::commands::run::run_tests_with_shard_and_report;
+use self::commands::run::{list_run_plan, run_tests_repeated, run_tests_with_shard_and_report};
 use self::types::{Cli, Commands};
-use self::utils::setup_logging;
+use self::utils::{discover_test_files, setup_logging};
 
 // Import all command functions - using self:: to avoid shadowing pub use exports
 use self::commands::health::system_health_check;
-use self::commands::init::init_project;
-use self::commands::report::generate_report;
+use self::commands::init::{init_from_template, init_project};
+use self::commands::report::{
+    check_coverage, compare_coverage, generate_coverage_report, generate_report,
+};
 use self::commands::validate::validate_config;
 
 // Remove global config - we'll load it per command as needed
@@ -36,7 +37,7 @@ pub async fn run_cli() -> Result<()> {
     let cli = Cli::parse();
 
     // Set up logging based on verbosity
-    setup_logging(cli.verbose)?;
+    setup_logging(cli.verbose, &cli.log_format)?;
 
     let result = match cli.command {
         Commands::Run {
@@ -44,21 +45,51 @@ pub async fn run_cli() -> Result<()> {
             parallel,
             jobs,
             fail_fast,
+            bail,
             watch,
             force,
             shard,
+            shard_strategy,
             digest,
             report_junit,
+            junit_flat,
+            env_file,
+            vars_file,
+            seed,
+            scenario,
+            scenario_only,
+            keep_services,
+            env_inherit,
+            timeout,
+            coverage_snapshot,
+            list,
+            repeat,
+            flaky_threshold,
         } => {
             let config = crate::cli::types::CliConfig {
                 parallel,
                 jobs,
                 format: cli.format.clone(),
                 fail_fast,
+                bail,
                 watch,
                 verbose: cli.verbose,
-                force,
+                // A filtered run doesn't exercise the whole file, so it must
+                // neither be skipped by nor recorded in the cache
+                force: force || scenario.is_some(),
                 digest,
+                junit_flat,
+                env_file,
+                vars_file,
+                seed,
+                scenario_filter: scenario,
+                scenario_only,
+                shard_strategy,
+                keep_services,
+                timeout: timeout.map(std::time::Duration::from_secs),
+                inherit_env: env_inherit,
+                coverage_snapshot,
+                ..Default::default()
             };
 
             // If no paths provided, discover all test files automatically
@@ -69,19 +100,45 @@ pub async fn run_cli() -> Result<()> {
                 vec![PathBuf::from(".")]
             };
 
-            run_tests_with_shard_and_report(&paths_to_run, &config, shard, report_junit.as_deref())
+            if list {
+                list_run_plan(&paths_to_run, &config, shard).await
+            } else if let Some(repeat) = repeat {
+                run_tests_repeated(
+                    &paths_to_run,
+                    &config,
+                    shard,
+                    repeat as usize,
+                    flaky_threshold,
+                )
+                .await
+            } else {
+                run_tests_with_shard_and_report(
+                    &paths_to_run,
+                    &config,
+                    shard,
+                    report_junit.as_deref(),
+                )
                 .await
+            }
         }
 
-        Commands::Validate { files } => {
-            for file in files {
-                validate_config(&file)?;
+        Commands::Validate { files, schema } => {
+            if schema {
+                print_config_schema()
+            } else {
+                files.iter().try_for_each(validate_config)
             }
-            Ok(())
         }
 
-        Commands::Init { force, config } => {
-            init_project(force, config)?;
+        Commands::Init {
+            force,
+            config,
+            template,
+        } => {
+            match template {
+                Some(template) => init_from_template(template, force)?,
+                None => init_project(force, config)?,
+            }
             Ok(())
         }
 
@@ -143,14 +200,27 @@ pub async fn run_cli() -> Result<()> {
                 show_service_status().await?;
                 Ok(())
             }
-            ServiceCommands::Logs { service, lines } => {
-                show_service_logs(&service, lines).await?;
+            ServiceCommands::Logs {
+                service,
+                lines,
+                follow,
+            } => {
+                show_service_logs(&service, lines, follow).await?;
                 Ok(())
             }
             ServiceCommands::Restart { service } => {
                 restart_service(&service).await?;
                 Ok(())
             }
+            ServiceCommands::Exec { service, command } => {
+                exec_in_service(&service, &command).await.map(|exit_code| {
+                    // Propagate the command's exit code
+                    if exit_code != 0 {
+                        std::process::exit(exit_code);
+                    }
+                })
+            }
+            ServiceCommands::Down { all } => services_down(all).await,
             #[cfg(feature = "ai")]
             ServiceCommands::AiManage {
                 auto_scale: _,
@@ -167,6 +237,10 @@ pub async fn run_cli() -> Result<()> {
             input,
             output,
             format,
+            coverage_baseline,
+            coverage,
+            manifest,
+            max_coverage_drop,
         } => {
             let format_str = match format {
                 ReportFormat::Html => "html",
@@ -174,17 +248,35 @@ pub async fn run_cli() -> Result<()> {
                 ReportFormat::Json => "json",
                 ReportFormat::Pdf => "pdf",
             };
-            generate_report(input.as_ref(), output.as_ref(), format_str).await?;
+            match (coverage_baseline, coverage, manifest) {
+                (Some(baseline), Some(current), Some(manifest)) => {
+                    compare_coverage(
+                        &baseline,
+                        &current,
+                        &manifest,
+                        max_coverage_drop,
+                        output.as_ref(),
+                        format_str,
+                    )
+                    .await?
+                }
+                (None, Some(current), Some(manifest)) => {
+                    generate_coverage_report(&current, &manifest, output.as_ref(), format_str)
+                        .await?
+                }
+                _ => generate_report(input.as_ref(), output.as_ref(), format_str).await?,
+            }
             Ok(())
         }
 
         Commands::SelfTest {
             suite,
+            filter,
             report,
             otel_exporter,
             otel_endpoint,
         } => {
-            run_self_tests(suite, report, otel_exporter, otel_endpoint).await?;
+            run_self_tests(suite, filter, report, otel_exporter, otel_endpoint).await?;
             Ok(())
         }
 
@@ -228,17 +320,26 @@ pub async fn run_cli() -> Result<()> {
 
         Commands::Fmt {
             files,
+            stdin,
             check,
             verify,
         } => {
-            format_files(&files, check, verify)?;
+            if stdin {
+                format_stdin(check, verify)?;
+            } else {
+                format_files(&files, check, verify)?;
+            }
             Ok(())
         }
 
-        Commands::DryRun { files, verbose } => {
+        Commands::DryRun {
+            files,
+            verbose,
+            max_errors,
+        } => {
             use crate::CleanroomError;
             let file_refs: Vec<_> = files.iter().map(|p| p.as_path()).collect();
-            let results = dry_run_validate(file_refs, verbose)?;
+            let results = dry_run_validate(file_refs, verbose, max_errors)?;
 
             // Count failures
             let failed_count = results.iter().filter(|r| !r.valid).count();
@@ -260,32 +361,55 @@ pub async fn run_cli() -> Result<()> {
             clear,
             only,
             timebox,
+            all_on_change,
+            keep_services,
         } => {
             let config = crate::cli::types::CliConfig {
                 format: cli.format.clone(),
                 verbose: cli.verbose,
+                keep_services,
                 ..Default::default()
             };
 
-            run_dev_mode_with_filters(paths, debounce_ms, clear, only, timebox, config).await
+            run_dev_mode_with_filters(
+                paths,
+                debounce_ms,
+                clear,
+                only,
+                timebox,
+                all_on_change,
+                config,
+            )
+            .await
         }
 
         Commands::Lint {
             files,
             format,
             deny_warnings,
+            map,
         } => {
-            let file_refs: Vec<_> = files.iter().map(|p| p.as_path()).collect();
+            // Directories are searched for test files; files are linted as given
+            let mut lint_paths = Vec::new();
+            for path in &files {
+                if path.is_dir() {
+                    lint_paths.extend(discover_test_files(path)?);
+                } else {
+                    lint_paths.push(path.clone());
+                }
+            }
+            let file_refs: Vec<_> = lint_paths.iter().map(|p| p.as_path()).collect();
 
             // Convert format enum to string
             let format_str = match format {
                 crate::cli::types::LintFormat::Human => "human",
                 crate::cli::types::LintFormat::Json => "json",
                 crate::cli::types::LintFormat::Github => "github",
+                crate::cli::types::LintFormat::Sarif => "sarif",
             };
 
             // This will print diagnostics and return error if needed
-            lint_files(file_refs, format_str, deny_warnings)?;
+            lint_files(file_refs, format_str, deny_warnings, &map)?;
 
             Ok(())
         }
@@ -295,6 +419,10 @@ pub async fn run_cli() -> Result<()> {
             current,
             format,
             only_changes,
+            match_by,
+            duration_threshold_ms,
+            duration_threshold_pct,
+            details,
         } => {
             // Convert format enum to string
             let format_str = match format {
@@ -303,17 +431,49 @@ pub async fn run_cli() -> Result<()> {
                 crate::cli::types::DiffFormat::SideBySide => "side-by-side",
             };
 
-            let result = diff_traces(&baseline, &current, format_str, only_changes)?;
+            let threshold = DurationThreshold {
+                ms: duration_threshold_ms,
+                pct: duration_threshold_pct,
+            };
+            let has_changes = match (baseline.is_dir(), current.is_dir()) {
+                (true, true) => diff_trace_dirs(
+                    &baseline,
+                    &current,
+                    format_str,
+                    only_changes,
+                    details,
+                    &match_by,
+                    &threshold,
+                )
+                .map(|result| result.has_changes()),
+                (false, false) => diff_traces(
+                    &baseline,
+                    &current,
+                    format_str,
+                    only_changes,
+                    &match_by,
+                    &threshold,
+                )
+                .map(|result| result.has_changes()),
+                _ => Err(crate::error::CleanroomError::validation_error(
+                    "Cannot diff a directory against a single trace file",
+                )),
+            };
 
             // Exit with error code if differences found
-            if result.added_count > 0 || result.removed_count > 0 || result.modified_count > 0 {
-                std::process::exit(1);
+            match has_changes {
+                Ok(true) => std::process::exit(1),
+                Ok(false) => Ok(()),
+                Err(e) => Err(e),
             }
-
-            Ok(())
         }
 
-        Commands::Record { paths, output } => run_record(paths, output).await,
+        Commands::Record {
+            paths,
+            output,
+            redact,
+            redact_regex,
+        } => run_record(paths, output, &redact, &redact_regex).await,
 
         #[cfg(feature = "ai")]
         Commands::AiMonitor {
@@ -332,20 +492,52 @@ pub async fn run_cli() -> Result<()> {
             paths,
             parallel,
             jobs,
-        } => pull_images(paths, parallel, jobs).await,
+            check,
+        } => {
+            if !check {
+                return pull_images(paths, parallel, jobs).await;
+            }
+            if !check_images(paths).await?.missing.is_empty() {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
 
         Commands::Graph {
             trace,
             format,
             highlight_missing,
             filter,
-        } => visualize_graph(&trace, &format, highlight_missing, filter.as_deref()),
+            output,
+        } => visualize_graph(
+            &trace,
+            &format,
+            highlight_missing,
+            filter.as_deref(),
+            output.as_deref(),
+        ),
 
         Commands::Repro {
             baseline,
             verify_digest,
             output,
-        } => reproduce_baseline(&baseline, verify_digest, output.as_ref()).await,
+            parallel,
+            jobs,
+        } => {
+            if baseline.is_dir() {
+                let jobs = jobs.unwrap_or(if parallel { 4 } else { 1 });
+                reproduce_baselines(&baseline, verify_digest, output.as_deref(), jobs).await
+            } else {
+                reproduce_baseline(&baseline, verify_digest, output.as_ref()).await
+            }
+        }
+
+        Commands::VerifyDigest {
+            baseline,
+            trace,
+            config,
+            update,
+        } => verify_digest(&baseline, &trace, config.as_deref(), update),
 
         Commands::RedGreen {
             paths,
@@ -364,10 +556,42 @@ pub async fn run_cli() -> Result<()> {
 
         Commands::Render {
             template,
+            dir,
             map,
             output,
             show_vars,
-        } => render_template_with_vars(&template, &map, output.as_ref(), show_vars),
+            as_format,
+            vars_file,
+            env_file,
+            include_dir,
+            check,
+        } => match (template, dir) {
+            (_, Some(dir)) => render_template_dir(
+                &dir,
+                output.as_deref(),
+                &map,
+                show_vars,
+                vars_file.as_deref(),
+                env_file.as_deref(),
+                include_dir.as_deref(),
+                check,
+            )
+            .map(|_| ()),
+            (Some(template), None) => render_template_with_vars(
+                &template,
+                &map,
+                output.as_ref(),
+                show_vars,
+                &as_format,
+                vars_file.as_deref(),
+                env_file.as_deref(),
+                include_dir.as_deref(),
+                check,
+            ),
+            (None, None) => Err(crate::error::CleanroomError::validation_error(
+                "Either a template file or --dir is required",
+            )),
+        },
 
         Commands::Spans {
             trace,
@@ -375,15 +599,24 @@ pub async fn run_cli() -> Result<()> {
             format,
             show_attrs,
             show_events,
-        } => filter_spans(&trace, grep.as_deref(), &format, show_attrs, show_events),
+            stats,
+        } => filter_spans(
+            &trace,
+            grep.as_deref(),
+            &format,
+            show_attrs,
+            show_events,
+            stats,
+        ),
 
         Commands::Collector { command } => match command {
             crate::cli::types::CollectorCommands::Up {
                 image,
                 http_port,
                 grpc_port,
+                config,
                 detach,
-            } => start_collector(&image, http_port, grpc_port, detach).await,
+            } => start_collector(&image, http_port, grpc_port, config.as_deref(), detach).await,
             crate::cli::types::CollectorCommands::Down { volumes } => stop_collector(volumes).await,
             crate::cli::types::CollectorCommands::Status => show_collector_status().await,
             crate::cli::types::CollectorCommands::Logs { lines, follow } => {
@@ -391,15 +624,33 @@ pub async fn run_cli() -> Result<()> {
             }
         },
 
-        Commands::Analyze { test_file, traces } => {
-            use crate::cli::commands::v0_7_0::analyze::analyze_traces;
+        Commands::Analyze {
+            test_file,
+            traces,
+            baseline,
+            output,
+        } => {
+            use crate::cli::commands::v0_7_0::analyze::{analyze_traces, AnalysisReport};
 
+            let baseline = baseline.as_deref().map(AnalysisReport::load).transpose()?;
             match analyze_traces(&test_file, traces.as_deref()) {
                 Ok(report) => {
                     println!("{}", report.format_report());
+                    if let Some(output) = &output {
+                        report.save(output)?;
+                    }
 
-                    // Exit with code 1 if any validator failed
-                    if !report.is_success() {
+                    // Exit with code 1 if any validator failed, or with a
+                    // baseline, if any validator newly failed
+                    let failed = match &baseline {
+                        Some(baseline) => {
+                            let comparison = report.compare_to(baseline);
+                            println!("{}", comparison.format_summary());
+                            comparison.has_regressions()
+                        }
+                        None => !report.is_success(),
+                    };
+                    if failed {
                         std::process::exit(1);
                     }
                     Ok(())
@@ -410,6 +661,18 @@ pub async fn run_cli() -> Result<()> {
                 }
             }
         }
+
+        Commands::Explain { code } => explain_error_code(code.as_deref()),
+
+        Commands::Matrix { command } => match command {
+            MatrixCommands::List { file } => list_matrix_cases(&file),
+        },
+        Commands::Coverage { command } => match command {
+            CoverageCommands::Check { manifest, traces } => check_coverage(&manifest, &traces),
+        },
+        Commands::Config { command } => match command {
+            ConfigCommands::Merge { files, output } => merge_configs(&files, output.as_deref()),
+        },
     };
 
     if let Err(e) = result {
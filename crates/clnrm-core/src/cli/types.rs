@@ -2,10 +2,13 @@
 //!
 //! Contains all the common types, enums, and structs used across CLI commands.
 
+use crate::config::CleanroomConfig;
+use crate::coverage::tracker::CoverageTracker;
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 /// Cleanroom Testing Platform - Hermetic Integration Testing
 #[derive(Parser)]
@@ -30,6 +33,10 @@ pub struct Cli {
     #[arg(short, long, default_value = "auto")]
     pub format: OutputFormat,
 
+    /// Log line format
+    #[arg(long, global = true, default_value = "human")]
+    pub log_format: LogFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -53,6 +60,10 @@ pub enum Commands {
         #[arg(short, long)]
         fail_fast: bool,
 
+        /// Stop scheduling new tests after N failures
+        #[arg(long, value_name = "N")]
+        bail: Option<usize>,
+
         /// Watch mode (rerun on file changes)
         #[arg(short, long)]
         watch: bool,
@@ -65,6 +76,10 @@ pub enum Commands {
         #[arg(long, value_parser = parse_shard)]
         shard: Option<(usize, usize)>,
 
+        /// How tests are split across shards (`duration` balances by recorded run times)
+        #[arg(long, alias = "shard-by", default_value = "modulo", requires = "shard")]
+        shard_strategy: ShardStrategy,
+
         /// Generate SHA-256 digest for reproducibility
         #[arg(long)]
         digest: bool,
@@ -72,6 +87,71 @@ pub enum Commands {
         /// Generate JUnit XML report to file
         #[arg(long, value_name = "FILE")]
         report_junit: Option<PathBuf>,
+
+        /// Emit one JUnit testcase per test file instead of per step
+        #[arg(long)]
+        junit_flat: bool,
+
+        /// Load a .env file so env(name=...) in templates can resolve its keys
+        #[arg(long, value_name = "FILE")]
+        env_file: Option<PathBuf>,
+
+        /// Load template variables from the keys of a JSON object file,
+        /// overriding the test's [vars]
+        #[arg(long, value_name = "FILE")]
+        vars_file: Option<PathBuf>,
+
+        /// Random seed for determinism, overriding `[determinism] seed`
+        #[arg(long, value_name = "U64")]
+        seed: Option<u64>,
+
+        /// Only run scenarios whose name equals or fully matches this regex
+        #[arg(long, value_name = "NAME_OR_REGEX")]
+        scenario: Option<String>,
+
+        /// With --scenario, also skip steps declared outside scenarios
+        #[arg(long, requires = "scenario")]
+        scenario_only: bool,
+
+        /// Leave a failed test's services running for inspection
+        /// (remove them with `clnrm services down`)
+        #[arg(long)]
+        keep_services: bool,
+
+        /// Pass these host environment variables through to step
+        /// containers (comma-separated); none are passed by default
+        #[arg(long, value_name = "VARS", value_delimiter = ',')]
+        env_inherit: Vec<String>,
+
+        /// Abort the whole run after this many seconds, cancelling tests
+        /// still running and reporting the ones that completed
+        #[arg(long, value_name = "SECS", conflicts_with = "watch")]
+        timeout: Option<u64>,
+
+        /// Save the behavior coverage recorded by the run's scenarios to
+        /// this file, for `clnrm report --coverage`
+        #[arg(long, value_name = "FILE", conflicts_with = "watch")]
+        coverage_snapshot: Option<PathBuf>,
+
+        /// Print the files and scenarios that would run, after cache
+        /// filtering and sharding, without running them
+        #[arg(long, conflicts_with_all = ["watch", "report_junit"])]
+        list: bool,
+
+        /// Run each test N times and report its pass rate, flagging tests
+        /// that sometimes pass and sometimes fail as flaky
+        #[arg(
+            long,
+            value_name = "N",
+            value_parser = clap::value_parser!(u32).range(1..),
+            conflicts_with_all = ["watch", "list", "report_junit", "timeout"]
+        )]
+        repeat: Option<u32>,
+
+        /// With --repeat, only fail for flaky tests whose failure rate is
+        /// above this percentage
+        #[arg(long, value_name = "PERCENT", default_value = "0", requires = "repeat")]
+        flaky_threshold: f64,
     },
 
     /// Initialize a new test project
@@ -83,6 +163,10 @@ pub enum Commands {
         /// Generate cleanroom.toml configuration file
         #[arg(long)]
         config: bool,
+
+        /// Scaffold a ready-to-run test for a service instead of a generic project
+        #[arg(long, value_enum)]
+        template: Option<InitTemplate>,
     },
 
     /// Generate project from template
@@ -103,8 +187,13 @@ pub enum Commands {
     /// Validate test configuration
     Validate {
         /// Files to validate
-        #[arg(required = true)]
+        #[arg(required_unless_present = "schema", conflicts_with = "schema")]
         files: Vec<PathBuf>,
+
+        /// Print the JSON Schema for test configuration files instead, for
+        /// editor completion (e.g. `clnrm validate --schema > clnrm.schema.json`)
+        #[arg(long)]
+        schema: bool,
     },
 
     /// List available plugins
@@ -129,6 +218,22 @@ pub enum Commands {
         /// Report format
         #[arg(short, long, default_value = "html")]
         format: ReportFormat,
+
+        /// Coverage snapshot from a previous run to compare against
+        #[arg(long, requires_all = ["coverage", "manifest"])]
+        coverage_baseline: Option<PathBuf>,
+
+        /// Coverage snapshot to report on instead of test results
+        #[arg(long, requires = "manifest")]
+        coverage: Option<PathBuf>,
+
+        /// Behavior manifest used to calculate coverage
+        #[arg(long, requires = "coverage")]
+        manifest: Option<PathBuf>,
+
+        /// Maximum allowed drop in overall coverage, in percentage points
+        #[arg(long, default_value = "0.0")]
+        max_coverage_drop: f64,
     },
 
     /// Run framework self-tests with optional OTEL export
@@ -137,11 +242,15 @@ pub enum Commands {
         #[arg(short, long)]
         suite: Option<String>,
 
+        /// Only run tests whose name matches this regex (e.g. "TOML")
+        #[arg(long, value_name = "REGEX")]
+        filter: Option<String>,
+
         /// Generate detailed report
         #[arg(short, long)]
         report: bool,
 
-        /// OTEL exporter type (none, stdout, otlp-http, otlp-grpc)
+        /// OTEL exporter type (none, stdout, otlp, otlp-http, otlp-grpc)
         #[arg(long, default_value = "none")]
         otel_exporter: String,
 
@@ -281,6 +390,15 @@ pub enum Commands {
         /// Maximum execution time per scenario in milliseconds
         #[arg(long)]
         timebox: Option<u64>,
+
+        /// Re-run every test on any change, not just the affected ones
+        #[arg(long)]
+        all_on_change: bool,
+
+        /// Leave a failed test's services running for inspection
+        /// (remove them with `clnrm services down`)
+        #[arg(long)]
+        keep_services: bool,
     },
 
     /// Dry-run validation without execution (v0.7.0)
@@ -292,13 +410,22 @@ pub enum Commands {
         /// Show detailed validation output
         #[arg(short, long)]
         verbose: bool,
+
+        /// Maximum number of errors reported per file
+        #[arg(long, default_value = "50")]
+        max_errors: usize,
     },
 
     /// Format Tera templates (v0.7.0)
     Fmt {
         /// Files to format
+        #[arg(conflicts_with = "stdin")]
         files: Vec<PathBuf>,
 
+        /// Read TOML from stdin and write the formatted result to stdout
+        #[arg(long)]
+        stdin: bool,
+
         /// Check formatting without modifying files
         #[arg(long)]
         check: bool,
@@ -310,7 +437,7 @@ pub enum Commands {
 
     /// Lint TOML test configurations (v0.7.0)
     Lint {
-        /// Files to lint
+        /// Files or directories to lint
         #[arg(required = true)]
         files: Vec<PathBuf>,
 
@@ -321,14 +448,21 @@ pub enum Commands {
         /// Fail on warnings
         #[arg(long)]
         deny_warnings: bool,
+
+        /// Template variables in key=value format (checked by unused-var/undefined-var)
+        #[arg(short, long)]
+        map: Vec<String>,
     },
 
     /// Diff OpenTelemetry traces (v0.7.0)
+    ///
+    /// Given two directories, compares every trace file in the baseline
+    /// directory with the file at the same relative path in the current one.
     Diff {
-        /// First trace file or test run
+        /// First trace file, or directory of baseline traces
         baseline: PathBuf,
 
-        /// Second trace file or test run to compare
+        /// Second trace file, or directory of current traces, to compare
         current: PathBuf,
 
         /// Output format
@@ -338,6 +472,22 @@ pub enum Commands {
         /// Show only differences
         #[arg(long)]
         only_changes: bool,
+
+        /// How spans are paired between the two traces
+        #[arg(long, default_value = "name")]
+        match_by: MatchBy,
+
+        /// Ignore span duration changes smaller than this many milliseconds
+        #[arg(long, value_name = "MS")]
+        duration_threshold_ms: Option<u64>,
+
+        /// Ignore span duration changes smaller than this percentage of the baseline
+        #[arg(long, value_name = "PCT")]
+        duration_threshold_pct: Option<f64>,
+
+        /// When comparing directories, list the span changes of each changed file
+        #[arg(long)]
+        details: bool,
     },
 
     /// Record baseline for test runs (v0.7.0)
@@ -348,6 +498,14 @@ pub enum Commands {
         /// Output path for baseline
         #[arg(short, long, default_value = ".clnrm/baseline.json")]
         output: Option<PathBuf>,
+
+        /// Mask values of span attributes with this key (repeatable)
+        #[arg(long = "redact", value_name = "KEY")]
+        redact: Vec<String>,
+
+        /// Mask matches of this regex in span attribute values (repeatable)
+        #[arg(long = "redact-regex", value_name = "PATTERN")]
+        redact_regex: Vec<String>,
     },
 
     /// Pre-pull Docker images from test configurations
@@ -362,6 +520,11 @@ pub enum Commands {
         /// Maximum parallel pulls
         #[arg(short = 'j', long, default_value = "4")]
         jobs: usize,
+
+        /// Only report which images are missing locally, without pulling
+        /// (exits non-zero if any are missing)
+        #[arg(long, conflicts_with_all = ["parallel", "jobs"])]
+        check: bool,
     },
 
     /// Visualize OpenTelemetry trace graph
@@ -380,11 +543,15 @@ pub enum Commands {
         /// Show only specific span names (filter)
         #[arg(long)]
         filter: Option<String>,
+
+        /// Write the graph to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
     },
 
     /// Reproduce a previous test run from baseline
     Repro {
-        /// Baseline file to reproduce
+        /// Baseline file, or directory of baselines, to reproduce
         baseline: PathBuf,
 
         /// Verify digest matches
@@ -394,6 +561,33 @@ pub enum Commands {
         /// Output file for reproduction results
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Reproduce a directory of baselines in parallel
+        #[arg(short, long)]
+        parallel: bool,
+
+        /// Maximum baselines reproduced at once (default: 4 with --parallel)
+        #[arg(short = 'j', long)]
+        jobs: Option<usize>,
+    },
+
+    /// Verify a trace against a recorded normalized digest
+    VerifyDigest {
+        /// Digest baseline file
+        #[arg(long)]
+        baseline: PathBuf,
+
+        /// Trace JSON file to verify
+        #[arg(long)]
+        trace: PathBuf,
+
+        /// Test config whose [determinism] section controls normalization
+        #[arg(long)]
+        config: Option<PathBuf>,
+
+        /// Write the baseline from the trace instead of comparing
+        #[arg(long)]
+        update: bool,
     },
 
     /// Run red/green TDD workflow validation
@@ -417,19 +611,46 @@ pub enum Commands {
     /// Render Tera templates with variable mapping
     Render {
         /// Template file to render
-        template: PathBuf,
+        #[arg(required_unless_present = "dir")]
+        template: Option<PathBuf>,
+
+        /// Render every *.clnrm.toml.tera under this directory to .clnrm.toml
+        /// files, written next to the templates or under --output
+        #[arg(long, value_name = "DIR", conflicts_with_all = ["template", "as_format"])]
+        dir: Option<PathBuf>,
 
-        /// Variable mappings in key=value format
+        /// Variable mappings: key=value for strings, key:=json for typed values
         #[arg(short, long)]
         map: Vec<String>,
 
-        /// Output file (default: stdout)
+        /// Output file (default: stdout), or output directory with --dir
         #[arg(short, long)]
         output: Option<PathBuf>,
 
         /// Show resolved variables
         #[arg(long)]
         show_vars: bool,
+
+        /// Output format for the rendered config
+        #[arg(long = "as", value_enum, default_value = "toml")]
+        as_format: RenderFormat,
+
+        /// Load variables from the keys of a JSON object file; --map
+        /// overrides them
+        #[arg(long, value_name = "FILE")]
+        vars_file: Option<PathBuf>,
+
+        /// Load a .env file so env(name=...) can resolve its keys
+        #[arg(long, value_name = "FILE")]
+        env_file: Option<PathBuf>,
+
+        /// Directory of *.tera partials available to {% include %} / {% extends %}
+        #[arg(long, value_name = "DIR")]
+        include_dir: Option<PathBuf>,
+
+        /// Fail unless the rendered output is a valid test config
+        #[arg(long, alias = "check-toml")]
+        check: bool,
     },
 
     /// Search and filter OpenTelemetry spans
@@ -452,6 +673,10 @@ pub enum Commands {
         /// Show span events
         #[arg(long)]
         show_events: bool,
+
+        /// Show per-name counts, p50/p95/p99 durations and error rates
+        #[arg(long)]
+        stats: bool,
     },
 
     /// Manage local OTEL collector
@@ -479,6 +704,40 @@ pub enum Commands {
         /// OTEL traces JSON file (optional, will auto-load from artifacts if not provided)
         #[arg(long, value_name = "TRACES")]
         traces: Option<PathBuf>,
+
+        /// Earlier report (from --output) to compare against; only validators
+        /// that newly fail make the command exit non-zero
+        #[arg(long, value_name = "REPORT")]
+        baseline: Option<PathBuf>,
+
+        /// Write the analysis report as JSON
+        #[arg(short, long, value_name = "REPORT")]
+        output: Option<PathBuf>,
+    },
+
+    /// Explain an error code such as CLNRM-V010 and how to fix it
+    Explain {
+        /// Error code (lists all codes when omitted)
+        #[arg(value_name = "CODE")]
+        code: Option<String>,
+    },
+
+    /// Inspect how a test's [matrix] expands
+    Matrix {
+        #[command(subcommand)]
+        command: MatrixCommands,
+    },
+
+    /// Check behavior coverage against a manifest
+    Coverage {
+        #[command(subcommand)]
+        command: CoverageCommands,
+    },
+
+    /// Work with layered test configurations
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
     },
 }
 
@@ -498,6 +757,10 @@ pub enum CollectorCommands {
         #[arg(long, default_value = "4317")]
         grpc_port: u16,
 
+        /// Collector config to mount instead of the built-in one
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+
         /// Detach (run in background)
         #[arg(short, long)]
         detach: bool,
@@ -525,6 +788,51 @@ pub enum CollectorCommands {
     },
 }
 
+#[derive(Subcommand)]
+pub enum MatrixCommands {
+    /// List the cases a test's matrix expands to, without running them
+    List {
+        /// Test file with a [matrix] section
+        file: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Deep-merge configs, later files overriding earlier ones
+    ///
+    /// Tables are merged key by key; the later file wins on scalar values and
+    /// on arrays of plain values. Arrays of tables whose entries all have a
+    /// `name`, such as `[[scenario]]`, `[[steps]]` and `[[scenario.steps]]`,
+    /// are merged by name: an entry named like an earlier one is merged into
+    /// it (e.g. to override a scenario's `timeout_ms`), entries with new names
+    /// are appended, and entries only in earlier files are kept. If any entry
+    /// has no `name`, the later file's array replaces the earlier one.
+    Merge {
+        /// Config files in merge order (e.g. base.toml override.toml)
+        #[arg(required = true, num_args = 2..)]
+        files: Vec<PathBuf>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum CoverageCommands {
+    /// Fail if recorded traces leave any behavior in the manifest uncovered
+    Check {
+        /// Behavior manifest declaring the expected behaviors
+        #[arg(long, value_name = "FILE")]
+        manifest: PathBuf,
+
+        /// Spans JSON file, or directory of them, recorded by test runs
+        #[arg(long, value_name = "PATH")]
+        traces: PathBuf,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ServiceCommands {
     /// Show status of all services
@@ -538,6 +846,10 @@ pub enum ServiceCommands {
         /// Number of lines to show
         #[arg(short, long, default_value = "50")]
         lines: usize,
+
+        /// Stream new log lines until Ctrl+C
+        #[arg(short, long)]
+        follow: bool,
     },
 
     /// Restart a service
@@ -546,6 +858,24 @@ pub enum ServiceCommands {
         service: String,
     },
 
+    /// Execute a command in a running service for debugging
+    Exec {
+        /// Service name
+        service: String,
+
+        /// Command to run, after `--` (e.g. `-- psql -c "SELECT 1"`)
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
+
+    /// Remove service containers this directory's runs left behind via
+    /// `--keep-services` or an interruption
+    Down {
+        /// Remove clnrm containers started from any directory, not just this one
+        #[arg(long)]
+        all: bool,
+    },
+
     /// AI-driven service lifecycle management [EXPERIMENTAL - requires 'ai' feature]
     #[cfg(feature = "ai")]
     #[command(about = "AI-driven service lifecycle management [EXPERIMENTAL]")]
@@ -586,6 +916,32 @@ pub enum OutputFormat {
     Tap,
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum InitTemplate {
+    /// PostgreSQL database
+    Postgres,
+    /// Redis key-value store
+    Redis,
+    /// HTTP echo server
+    Http,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable log lines
+    Human,
+    /// One JSON object per line for CI log processing
+    Json,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum RenderFormat {
+    /// Rendered TOML as produced by the template
+    Toml,
+    /// Fully-resolved test config as JSON
+    Json,
+}
+
 #[derive(Clone, Debug, ValueEnum)]
 pub enum ReportFormat {
     /// HTML report
@@ -618,6 +974,26 @@ pub enum LintFormat {
     Json,
     /// GitHub Actions annotations
     Github,
+    /// SARIF 2.1.0 log for code scanning
+    Sarif,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ShardStrategy {
+    /// Deal tests round-robin in discovery order
+    #[default]
+    Modulo,
+    /// Balance shards by test durations recorded in the cache
+    Duration,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum MatchBy {
+    /// Span name under its ancestors' names, ignoring span IDs
+    #[default]
+    Name,
+    /// Span ID
+    Id,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
@@ -640,6 +1016,8 @@ pub enum GraphFormat {
     Json,
     /// Mermaid diagram format
     Mermaid,
+    /// SVG image
+    Svg,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, ValueEnum)]
@@ -661,6 +1039,8 @@ pub struct CliConfig {
     pub format: OutputFormat,
     /// Fail fast mode
     pub fail_fast: bool,
+    /// Stop scheduling new tests after this many failures
+    pub bail: Option<usize>,
     /// Watch mode
     pub watch: bool,
     /// Verbosity level
@@ -669,6 +1049,32 @@ pub struct CliConfig {
     pub force: bool,
     /// Generate SHA-256 digest for reproducibility
     pub digest: bool,
+    /// Emit one JUnit testcase per test file instead of per step
+    pub junit_flat: bool,
+    /// Dotenv file whose keys `env()` resolves in templates
+    pub env_file: Option<PathBuf>,
+    /// JSON object file whose keys are template variables
+    pub vars_file: Option<PathBuf>,
+    /// Determinism seed overriding the test file's `[determinism] seed`
+    pub seed: Option<u64>,
+    /// Only run scenarios matching this name or regex
+    pub scenario_filter: Option<String>,
+    /// Skip steps declared outside scenarios
+    pub scenario_only: bool,
+    /// How `--shard` splits tests between shards
+    pub shard_strategy: ShardStrategy,
+    /// Leave a failed test's services running instead of stopping them
+    pub keep_services: bool,
+    /// Wall-clock budget for the whole run
+    pub timeout: Option<Duration>,
+    /// Host environment variables passed through to step containers
+    pub inherit_env: Vec<String>,
+    /// File the run's behavior coverage is saved to once it finishes
+    pub coverage_snapshot: Option<PathBuf>,
+    /// Behavior coverage recorded by the tests of this run
+    pub coverage: CoverageTracker,
+    /// Project configuration to run with instead of loading cleanroom.toml
+    pub cleanroom: Option<CleanroomConfig>,
 }
 
 impl Default for CliConfig {
@@ -678,10 +1084,24 @@ impl Default for CliConfig {
             jobs: 4,
             format: OutputFormat::Auto,
             fail_fast: false,
+            bail: None,
             watch: false,
             verbose: 0,
             force: false,
             digest: false,
+            junit_flat: false,
+            env_file: None,
+            vars_file: None,
+            seed: None,
+            scenario_filter: None,
+            scenario_only: false,
+            shard_strategy: ShardStrategy::default(),
+            keep_services: false,
+            timeout: None,
+            inherit_env: Vec::new(),
+            coverage_snapshot: None,
+            coverage: CoverageTracker::new(),
+            cleanroom: None,
         }
     }
 }
@@ -697,9 +1117,34 @@ pub struct CliTestResults {
 #[derive(Debug, Clone)]
 pub struct CliTestResult {
     pub name: String,
+    /// Test file the result is for, as discovered
+    pub path: PathBuf,
     pub passed: bool,
     pub duration_ms: u64,
     pub error: Option<String>,
+    pub steps: Vec<crate::scenario::StepResult>,
+}
+
+impl CliTestResult {
+    /// Result of running the test file at `path`
+    pub fn new(path: &std::path::Path, result: crate::testing::TestResult) -> Self {
+        Self {
+            name: result.name,
+            path: path.to_path_buf(),
+            passed: result.passed,
+            duration_ms: result.duration_ms,
+            error: result.error,
+            steps: result.steps,
+        }
+    }
+
+    /// Why the test failed: the failure reason of its first failed step
+    pub fn failure_reason(&self) -> Option<crate::cleanroom::FailureReason> {
+        self.steps
+            .iter()
+            .find(|step| !step.success)
+            .and_then(|step| step.failure_reason)
+    }
 }
 
 /// TOML test configuration structure - matches the existing config module
@@ -754,7 +1199,7 @@ pub struct TestStep {
 /// File extension constants
 pub const TOML_FILE_EXTENSION: &str = ".toml";
 pub const CLNRM_TOML_EXTENSION: &str = ".clnrm.toml";
-pub const ACCEPTED_EXTENSIONS: &[&str] = &[".toml", ".clnrm.toml"];
+pub const ACCEPTED_EXTENSIONS: &[&str] = &[".toml", ".clnrm.toml", ".clnrm.yaml", ".clnrm.yml"];
 
 /// Parse shard argument in format "i/m" where i is 1-based index and m is total shards
 ///
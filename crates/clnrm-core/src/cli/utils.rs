@@ -2,14 +2,19 @@
 //!
 //! Contains shared utility functions used across CLI commands.
 
-use crate::cli::types::{CliTestResults, ACCEPTED_EXTENSIONS};
+use crate::cli::types::{CliTestResult, CliTestResults, LogFormat, ACCEPTED_EXTENSIONS};
 use crate::config::load_config_from_file;
 use crate::error::{CleanroomError, Result};
+use crate::scenario::StepResult;
 use std::path::{Path, PathBuf};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 use walkdir::WalkDir;
 
-/// Discover all .clnrm.toml test files in a directory
+/// Discover all .clnrm.toml (or .clnrm.yaml) test files in a directory
+///
+/// A path that doesn't exist but contains glob syntax (e.g.
+/// `tests/**/smoke_*.toml`) is expanded with the `glob` crate, so subsets can
+/// be selected without relying on the shell's globbing.
 ///
 /// Core Team Compliance:
 /// - ✅ Proper error handling with CleanroomError
@@ -18,8 +23,12 @@ use walkdir::WalkDir;
 pub fn discover_test_files(path: &PathBuf) -> Result<Vec<PathBuf>> {
     let mut test_files = Vec::new();
 
+    if !path.exists() && is_glob_pattern(path) {
+        return discover_glob_matches(path);
+    }
+
     if path.is_file() {
-        // If single file, check extension - accept both .toml and .clnrm.toml
+        // If single file, check extension - accept .toml, .clnrm.toml and .clnrm.yaml/.yml
         let path_str = path.to_str().unwrap_or("");
         if ACCEPTED_EXTENSIONS
             .iter()
@@ -28,7 +37,7 @@ pub fn discover_test_files(path: &PathBuf) -> Result<Vec<PathBuf>> {
             test_files.push(path.clone());
         } else {
             return Err(CleanroomError::validation_error(format!(
-                "File must have .toml or .clnrm.toml extension: {}",
+                "File must have .toml, .clnrm.toml, .clnrm.yaml or .clnrm.yml extension: {}",
                 path.display()
             )));
         }
@@ -44,7 +53,7 @@ pub fn discover_test_files(path: &PathBuf) -> Result<Vec<PathBuf>> {
             let entry_path = entry.path();
             let path_str = entry_path.to_str().unwrap_or("");
 
-            // Accept both .toml and .clnrm.toml files
+            // Accept .toml, .clnrm.toml and .clnrm.yaml/.yml files
             if ACCEPTED_EXTENSIONS
                 .iter()
                 .any(|ext| path_str.ends_with(ext))
@@ -57,7 +66,7 @@ pub fn discover_test_files(path: &PathBuf) -> Result<Vec<PathBuf>> {
 
         if test_files.is_empty() {
             return Err(CleanroomError::validation_error(format!(
-                "No test files (.toml or .clnrm.toml) found in directory: {}",
+                "No test files (.toml, .clnrm.toml, .clnrm.yaml or .clnrm.yml) found in directory: {}",
                 path.display()
             )));
         }
@@ -73,13 +82,71 @@ pub fn discover_test_files(path: &PathBuf) -> Result<Vec<PathBuf>> {
     Ok(test_files)
 }
 
+/// Whether a path contains glob metacharacters
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_str().is_some_and(|s| s.contains(['*', '?', '[']))
+}
+
+/// Expand a glob pattern to the test files it matches
+fn discover_glob_matches(pattern: &Path) -> Result<Vec<PathBuf>> {
+    let pattern_str = pattern.to_str().unwrap_or("");
+    let entries = glob::glob(pattern_str).map_err(|e| {
+        CleanroomError::validation_error(format!(
+            "Invalid glob pattern '{}': {} (at position {})",
+            pattern_str, e.msg, e.pos
+        ))
+    })?;
+
+    let mut test_files = Vec::new();
+    for entry in entries {
+        let entry_path = match entry {
+            Ok(entry_path) => entry_path,
+            Err(e) => {
+                warn!(
+                    "Skipping unreadable path while expanding '{}': {}",
+                    pattern_str, e
+                );
+                continue;
+            }
+        };
+
+        let path_str = entry_path.to_str().unwrap_or("");
+        if ACCEPTED_EXTENSIONS
+            .iter()
+            .any(|ext| path_str.ends_with(ext))
+            && entry_path.is_file()
+        {
+            debug!("Found test file: {}", entry_path.display());
+            test_files.push(entry_path);
+        }
+    }
+
+    if test_files.is_empty() {
+        return Err(CleanroomError::validation_error(format!(
+            "No test files (.toml, .clnrm.toml, .clnrm.yaml or .clnrm.yml) match pattern: {}",
+            pattern_str
+        )));
+    }
+
+    info!(
+        "Pattern '{}' matched {} test file(s)",
+        pattern_str,
+        test_files.len()
+    );
+    Ok(test_files)
+}
+
 /// Parse a TOML test configuration file
 pub fn parse_toml_test(path: &Path) -> Result<crate::config::TestConfig> {
     load_config_from_file(path)
 }
 
-/// Set up logging based on verbosity level
-pub fn setup_logging(verbosity: u8) -> Result<()> {
+/// Set up logging based on verbosity level and log line format
+///
+/// JSON logs put event fields (e.g. `test`, `status`, `duration_ms` on test
+/// results) at the top level of each object next to `message`. Logs go to
+/// stderr so stdout stays parseable for `--format json` / `sarif` output.
+pub fn setup_logging(verbosity: u8, format: &LogFormat) -> Result<()> {
     use tracing_subscriber::{fmt, EnvFilter};
 
     let filter = match verbosity {
@@ -88,11 +155,17 @@ pub fn setup_logging(verbosity: u8) -> Result<()> {
         _ => "trace",
     };
 
-    let subscriber = fmt::Subscriber::builder()
+    let builder = fmt::Subscriber::builder()
         .with_env_filter(EnvFilter::new(filter))
-        .finish();
+        .with_writer(std::io::stderr);
+    let result = match format {
+        LogFormat::Human => tracing::subscriber::set_global_default(builder.finish()),
+        LogFormat::Json => {
+            tracing::subscriber::set_global_default(builder.json().flatten_event(true).finish())
+        }
+    };
 
-    tracing::subscriber::set_global_default(subscriber).map_err(|e| {
+    result.map_err(|e| {
         CleanroomError::internal_error("Failed to set up logging").with_source(e.to_string())
     })?;
 
@@ -101,44 +174,58 @@ pub fn setup_logging(verbosity: u8) -> Result<()> {
 
 /// Generate JUnit XML output for CI/CD integration
 ///
+/// By default each test file becomes a `<testsuite>` and each recorded step a
+/// `<testcase>` (classname = scenario, `<system-out>` = captured stdout), so
+/// CI dashboards show which step failed. Failures that happen outside any
+/// step (service startup, span validation) get a testcase named after the
+/// file. With `flat` set, each test file is a single `<testcase>` instead.
+/// Steps that needed retries say so in their `<system-err>`.
+///
 /// # Core Team Compliance
 /// - ✅ Proper error handling with CleanroomError
 /// - ✅ No unwrap() or expect() calls
 /// - ✅ Returns Result<String, CleanroomError>
 /// - ✅ Includes timestamp information
-pub fn generate_junit_xml(results: &CliTestResults) -> Result<String> {
-    use junit_report::{Duration, OffsetDateTime, Report, TestCase, TestSuite};
-
-    let mut test_suite = TestSuite::new("cleanroom_tests");
-    test_suite.set_timestamp(OffsetDateTime::now_utc());
-
-    for test in &results.tests {
-        let duration_secs = test.duration_ms as f64 / 1000.0;
-        let test_case = if !test.passed {
-            if let Some(error) = &test.error {
-                TestCase::failure(
-                    &test.name,
-                    Duration::seconds(duration_secs as i64),
-                    "test_failure",
-                    error,
-                )
-            } else {
-                TestCase::failure(
-                    &test.name,
-                    Duration::seconds(duration_secs as i64),
-                    "test_failure",
-                    "Test failed without error message",
-                )
+pub fn generate_junit_xml(results: &CliTestResults, flat: bool) -> Result<String> {
+    use junit_report::{OffsetDateTime, Report, TestSuite};
+
+    let mut report = Report::new();
+
+    if flat {
+        let mut test_suite = TestSuite::new("cleanroom_tests");
+        test_suite.set_timestamp(OffsetDateTime::now_utc());
+
+        for test in &results.tests {
+            let mut test_case = junit_test_case(test);
+            let retries: u32 = test.steps.iter().map(|s| s.retries).sum();
+            if retries > 0 {
+                test_case.set_system_err(&format!("Steps retried {} times", retries));
             }
-        } else {
-            TestCase::success(&test.name, Duration::seconds(duration_secs as i64))
-        };
+            test_suite.add_testcase(test_case);
 
-        test_suite.add_testcase(test_case);
-    }
+            for step in test.steps.iter().filter(|s| s.skipped) {
+                test_suite.add_testcase(junit_step_case(step));
+            }
+        }
 
-    let mut report = Report::new();
-    report.add_testsuite(test_suite);
+        report.add_testsuite(test_suite);
+    } else {
+        for test in &results.tests {
+            let mut test_suite = TestSuite::new(&test.name);
+            test_suite.set_timestamp(OffsetDateTime::now_utc());
+
+            for step in &test.steps {
+                test_suite.add_testcase(junit_step_case(step));
+            }
+
+            let step_failed = test.steps.iter().any(|s| !s.success);
+            if test.steps.is_empty() || (!test.passed && !step_failed) {
+                test_suite.add_testcase(junit_test_case(test));
+            }
+
+            report.add_testsuite(test_suite);
+        }
+    }
 
     let mut xml_output = Vec::new();
     report.write_xml(&mut xml_output).map_err(|e| {
@@ -153,3 +240,77 @@ pub fn generate_junit_xml(results: &CliTestResults) -> Result<String> {
             .with_source(e.to_string())
     })
 }
+
+/// Build a JUnit testcase covering a whole test file
+fn junit_test_case(test: &CliTestResult) -> junit_report::TestCase {
+    use junit_report::{Duration, TestCase};
+
+    let duration_secs = test.duration_ms as f64 / 1000.0;
+    if test.passed {
+        TestCase::success(&test.name, Duration::seconds(duration_secs as i64))
+    } else {
+        TestCase::failure(
+            &test.name,
+            Duration::seconds(duration_secs as i64),
+            test.failure_reason()
+                .map_or("test_failure", |reason| reason.as_str()),
+            test.error
+                .as_deref()
+                .unwrap_or("Test failed without error message"),
+        )
+    }
+}
+
+/// Build a JUnit testcase for a single step, classed under its scenario
+fn junit_step_case(step: &StepResult) -> junit_report::TestCase {
+    use junit_report::{Duration, TestCase};
+
+    let duration = Duration::milliseconds(step.duration_ms as i64);
+    let mut test_case = if step.skipped {
+        TestCase::skipped(&step.name)
+    } else if step.success {
+        TestCase::success(&step.name, duration)
+    } else {
+        TestCase::failure(
+            &step.name,
+            duration,
+            step.failure_reason
+                .map_or("step_failure", |reason| reason.as_str()),
+            &format!(
+                "Command `{}` failed (exit code {}){}",
+                step.command,
+                step.exit_code,
+                retries_note(step.retries)
+            ),
+        )
+    };
+
+    test_case.set_classname(&step.source);
+    if !step.skipped {
+        test_case.set_system_out(&step.stdout);
+        // Flaky passes are only visible here, so note the retries first
+        let stderr = match step.retries {
+            0 => step.stderr.clone(),
+            _ => format!(
+                "{}{}\n{}",
+                if step.success { "Passed" } else { "Failed" },
+                retries_note(step.retries),
+                step.stderr
+            ),
+        };
+        if !stderr.is_empty() {
+            test_case.set_system_err(&stderr);
+        }
+    }
+
+    test_case
+}
+
+/// `" after N retries"` for a step that was retried, empty otherwise
+fn retries_note(retries: u32) -> String {
+    if retries == 0 {
+        String::new()
+    } else {
+        format!(" after {} retries", retries)
+    }
+}
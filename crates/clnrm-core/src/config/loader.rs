@@ -1,24 +1,77 @@
 //! Configuration loading and parsing functions
+//!
+//! Test files are TOML by default; files ending in `.yaml` or `.yml` are
+//! parsed as YAML into the same [`TestConfig`] structure.
 
-use crate::error::{CleanroomError, Result};
-use std::path::Path;
+use crate::error::{CleanroomError, ErrorCode, Result};
+use std::path::{Path, PathBuf};
 
 use super::types::TestConfig;
 
 /// Parse TOML configuration from string
 pub fn parse_toml_config(content: &str) -> Result<TestConfig> {
-    toml::from_str::<TestConfig>(content)
-        .map_err(|e| CleanroomError::config_error(format!("TOML parse error: {}", e)))
+    toml::from_str::<TestConfig>(content).map_err(|e| {
+        CleanroomError::config_error(format!("TOML parse error: {}", e))
+            .with_code(ErrorCode::TomlParse)
+    })
+}
+
+/// Parse YAML configuration from string
+///
+/// Parse errors include the YAML line and column they occurred at.
+pub fn parse_yaml_config(content: &str) -> Result<TestConfig> {
+    serde_yaml::from_str::<TestConfig>(content).map_err(|e| {
+        CleanroomError::config_error(format!("YAML parse error: {}", e))
+            .with_code(ErrorCode::YamlParse)
+    })
+}
+
+/// Whether `path` is a YAML test file (`.yaml` or `.yml` extension)
+pub fn is_yaml_config(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("yaml") | Some("yml")
+    )
+}
+
+/// Parse configuration content in the format implied by `path`'s extension
+pub fn parse_config_for_path(path: &Path, content: &str) -> Result<TestConfig> {
+    if is_yaml_config(path) {
+        parse_yaml_config(content)
+    } else {
+        parse_toml_config(content)
+    }
 }
 
 /// Load configuration from file with template rendering support
 ///
+/// Dispatches to [`load_config_from_yaml_file`] for `.yaml`/`.yml` files and
+/// parses everything else as TOML.
+///
 /// This function performs two-pass template rendering when determinism is configured:
 /// 1. First pass: render without determinism to parse config and extract [determinism] section
 /// 2. Second pass: if determinism is configured, re-render with DeterminismEngine
 pub fn load_config_from_file(path: &Path) -> Result<TestConfig> {
-    use crate::{is_template, TemplateRenderer};
-    use clnrm_template::functions::TimestampProvider;
+    if is_yaml_config(path) {
+        return load_config_from_yaml_file(path);
+    }
+    load_config_with_parser(path, parse_toml_config)
+}
+
+/// Load YAML configuration from file with template rendering support
+///
+/// Rendering works exactly as in [`load_config_from_file`]; only the final
+/// parse step differs.
+pub fn load_config_from_yaml_file(path: &Path) -> Result<TestConfig> {
+    load_config_with_parser(path, parse_yaml_config)
+}
+
+/// Read, render and parse a config file with the given format parser
+fn load_config_with_parser(
+    path: &Path,
+    parse: fn(&str) -> Result<TestConfig>,
+) -> Result<TestConfig> {
+    use crate::is_template;
 
     // Read file content
     let content = std::fs::read_to_string(path)
@@ -29,19 +82,30 @@ pub fn load_config_from_file(path: &Path) -> Result<TestConfig> {
 
     if !is_templated {
         // No templates - parse directly
-        let config = parse_toml_config(&content)?;
-        config.validate()?;
+        let config = parse(&content)?;
+        config
+            .validate()
+            .map_err(|e| e.with_code(ErrorCode::InvalidConfig))?;
         return Ok(config);
     }
 
+    // file_sha256() paths resolve against the directory holding the config
+    let template_dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+    // {{ matrix.* }} expressions are left for expand_matrix to render per case
+    let content = super::matrix::defer_matrix_refs(&content);
+
     // First pass: render template without determinism to get config structure
-    let mut renderer = TemplateRenderer::new()
-        .map_err(|e| CleanroomError::template_error(format!("Failed to create template renderer: {}", e)))?;
-    let first_pass_toml = renderer.render_str(&content, path.to_str().unwrap_or("config"))
-        .map_err(|e| CleanroomError::template_error(format!("Template rendering failed: {}", e)))?;
+    let mut renderer = config_renderer(&content, template_dir)?;
+    let first_pass_toml = renderer
+        .render_str(&content, path.to_str().unwrap_or("config"))
+        .map_err(|e| {
+            CleanroomError::template_error(format!("Template rendering failed: {}", e))
+                .with_code(ErrorCode::TemplateRender)
+        })?;
 
     // Parse to extract determinism config
-    let first_pass_config = parse_toml_config(&first_pass_toml)?;
+    let first_pass_config = parse(&first_pass_toml)?;
 
     // Parse final TOML and validate
     // Second pass: if determinism is configured, re-render with DeterminismEngine
@@ -51,20 +115,14 @@ pub fn load_config_from_file(path: &Path) -> Result<TestConfig> {
             let engine = crate::determinism::DeterminismEngine::new(det_config.clone())?;
 
             // Re-render with determinism
-            // Create adapter for DeterminismEngine to TimestampProvider
-            struct DeterminismAdapter(crate::determinism::DeterminismEngine);
-            impl TimestampProvider for DeterminismAdapter {
-                fn get_timestamp_rfc3339(&self) -> String {
-                    self.0.get_timestamp_rfc3339()
-                }
-            }
-            
-            let adapter = std::sync::Arc::new(DeterminismAdapter(engine));
-            let mut renderer_with_det = TemplateRenderer::new()
-                .map_err(|e| CleanroomError::template_error(format!("Failed to create template renderer: {}", e)))?
-                .with_determinism(adapter);
-            renderer_with_det.render_str(&content, path.to_str().unwrap_or("config"))
-                .map_err(|e| CleanroomError::template_error(format!("Template rendering failed: {}", e)))?
+            let mut renderer_with_det = config_renderer(&content, template_dir)?
+                .with_determinism(std::sync::Arc::new(engine));
+            renderer_with_det
+                .render_str(&content, path.to_str().unwrap_or("config"))
+                .map_err(|e| {
+                    CleanroomError::template_error(format!("Template rendering failed: {}", e))
+                        .with_code(ErrorCode::TemplateRender)
+                })?
         } else {
             // Determinism section exists but is empty - use first pass
             first_pass_toml
@@ -73,8 +131,47 @@ pub fn load_config_from_file(path: &Path) -> Result<TestConfig> {
         // No determinism - use first pass
         first_pass_toml
     };
-    let config = parse_toml_config(&final_toml)?;
-    config.validate()?;
+    let config = parse(&final_toml)?;
+    config
+        .validate()
+        .map_err(|e| e.with_code(ErrorCode::InvalidConfig))?;
 
     Ok(config)
 }
+
+/// Create a renderer for a config template whose `file_sha256()` paths and
+/// `{% include %}` partials resolve against `template_dir`
+fn config_renderer(content: &str, template_dir: &Path) -> Result<crate::TemplateRenderer> {
+    let mut renderer = crate::TemplateRenderer::new()
+        .map_err(|e| {
+            CleanroomError::template_error(format!("Failed to create template renderer: {}", e))
+        })?
+        .with_template_dir(template_dir);
+    renderer
+        .load_template_dependencies(content, template_dir)
+        .map_err(|e| {
+            CleanroomError::template_error(format!("Failed to load template partials: {}", e))
+        })?;
+    Ok(renderer)
+}
+
+/// Partial files a config template includes, imports or extends
+///
+/// Follows nested partials; non-templated configs have no dependencies.
+pub fn template_dependencies(path: &Path) -> Result<Vec<PathBuf>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| CleanroomError::config_error(format!("Failed to read config file: {}", e)))?;
+    if !crate::is_template(&content) {
+        return Ok(Vec::new());
+    }
+
+    let template_dir = path.parent().unwrap_or_else(|| Path::new(""));
+    crate::TemplateRenderer::new()
+        .map_err(|e| {
+            CleanroomError::template_error(format!("Failed to create template renderer: {}", e))
+        })?
+        .load_template_dependencies(&content, template_dir)
+        .map_err(|e| {
+            CleanroomError::template_error(format!("Failed to load template partials: {}", e))
+        })
+}
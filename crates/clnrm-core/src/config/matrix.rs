@@ -0,0 +1,226 @@
+//! Matrix expansion
+//!
+//! A `[matrix]` table maps dimension names to lists of values, and the test
+//! runs once per combination (the cartesian product of all dimensions).
+//! Strings in the test reference the current combination as
+//! `{{ matrix.<dimension> }}`; loading a test leaves those references in place
+//! so [`expand_matrix`] can fill them in per case.
+
+use crate::error::{CleanroomError, Result};
+use crate::{TemplateContext, TemplateRenderer};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+use super::types::TestConfig;
+
+/// One combination of matrix values, ordered by dimension name
+pub type MatrixCombination = Vec<(String, String)>;
+
+/// Materialize every combination of `config`'s matrix into a concrete config
+///
+/// Each case has its `{{ matrix.* }}` references rendered and no `[matrix]`
+/// table. Case names are the rendered test name, suffixed with the
+/// combination (e.g. `smoke [os=alpine, version=3.18]`) when that alone
+/// doesn't tell the cases apart. A config without a matrix expands to itself.
+///
+/// # Errors
+/// Returns error if a dimension is invalid (see [`matrix_combinations`]), or
+/// a case fails to render or parse, e.g. because it references a dimension
+/// the matrix doesn't declare.
+pub fn expand_matrix(config: &TestConfig) -> Result<Vec<TestConfig>> {
+    let Some(ref matrix) = config.matrix else {
+        return Ok(vec![config.clone()]);
+    };
+    let combinations = matrix_combinations(matrix)?;
+
+    let mut template = config.clone();
+    template.matrix = None;
+    let template = serde_json::to_value(&template).map_err(|e| {
+        CleanroomError::serialization_error(format!("Failed to serialize matrix test: {}", e))
+    })?;
+
+    let mut cases = combinations
+        .iter()
+        .map(|combination| {
+            render_case(template.clone(), combination).map_err(|e| {
+                e.with_context(format!("Matrix case [{}]", combination_label(combination)))
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut names = HashSet::new();
+    let distinct = cases
+        .iter()
+        .map(TestConfig::get_name)
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .all(|name| names.insert(name));
+    if !distinct {
+        for (case, combination) in cases.iter_mut().zip(&combinations) {
+            let name = format!("{} [{}]", case.get_name()?, combination_label(combination));
+            set_name(case, name);
+        }
+    }
+
+    Ok(cases)
+}
+
+/// Cartesian product of a matrix's dimensions
+///
+/// Dimensions are taken in name order and values in declared order, so the
+/// same matrix always expands to the same sequence of cases.
+///
+/// # Errors
+/// Returns error if the matrix has no dimensions, a dimension name can't be
+/// referenced as `matrix.<name>`, or a dimension has no or duplicate values.
+pub fn matrix_combinations(
+    matrix: &HashMap<String, Vec<String>>,
+) -> Result<Vec<MatrixCombination>> {
+    if matrix.is_empty() {
+        return Err(CleanroomError::validation_error(
+            "[matrix] must declare at least one dimension",
+        ));
+    }
+
+    let mut dimensions: Vec<_> = matrix.iter().collect();
+    dimensions.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut combinations = vec![MatrixCombination::new()];
+    for (name, values) in dimensions {
+        if !is_identifier(name) {
+            return Err(CleanroomError::validation_error(format!(
+                "Matrix dimension '{}' is not a valid name: use letters, digits and underscores \
+                 so it can be referenced as {{{{ matrix.<name> }}}}",
+                name
+            )));
+        }
+        if values.is_empty() {
+            return Err(CleanroomError::validation_error(format!(
+                "Matrix dimension '{}' has no values",
+                name
+            )));
+        }
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = values.iter().find(|value| !seen.insert(*value)) {
+            return Err(CleanroomError::validation_error(format!(
+                "Matrix dimension '{}' lists '{}' more than once",
+                name, duplicate
+            )));
+        }
+
+        combinations = combinations
+            .into_iter()
+            .flat_map(|combination| {
+                values.iter().map(move |value| {
+                    let mut combination = combination.clone();
+                    combination.push((name.clone(), value.clone()));
+                    combination
+                })
+            })
+            .collect();
+    }
+
+    Ok(combinations)
+}
+
+/// `name=value` pairs of a combination, e.g. `os=alpine, version=3.18`
+pub fn combination_label(combination: &MatrixCombination) -> String {
+    combination
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Wrap every `{{ ... }}` expression that references the matrix in
+/// `{% raw %}`, so rendering a matrix test keeps those expressions intact
+pub(crate) fn defer_matrix_refs(content: &str) -> String {
+    let mut deferred = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}").map(|end| end + 2) else {
+            break;
+        };
+        let expression = &rest[start..start + len];
+
+        deferred.push_str(&rest[..start]);
+        if references_matrix(expression) {
+            deferred.push_str("{% raw %}");
+            deferred.push_str(expression);
+            deferred.push_str("{% endraw %}");
+        } else {
+            deferred.push_str(expression);
+        }
+        rest = &rest[start + len..];
+    }
+
+    deferred.push_str(rest);
+    deferred
+}
+
+/// Whether a template expression reads a `matrix.<dimension>` value
+fn references_matrix(expression: &str) -> bool {
+    expression.match_indices("matrix.").any(|(start, prefix)| {
+        let preceded_by_ident = expression[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+        let followed_by_ident = expression[start + prefix.len()..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+        !preceded_by_ident && followed_by_ident
+    })
+}
+
+fn render_case(mut template: Value, combination: &MatrixCombination) -> Result<TestConfig> {
+    let matrix = combination
+        .iter()
+        .map(|(name, value)| (name.clone(), Value::String(value.clone())))
+        .collect();
+    let mut renderer =
+        TemplateRenderer::new()?.with_context(TemplateContext::new().with_matrix(matrix));
+    render_matrix_refs(&mut template, &mut renderer)?;
+
+    serde_json::from_value(template)
+        .map_err(|e| CleanroomError::serialization_error(format!("Invalid matrix case: {}", e)))
+}
+
+/// Render the strings that reference the matrix, leaving other template
+/// syntax (e.g. `{{ row.* }}` for data-driven scenarios) for later
+fn render_matrix_refs(value: &mut Value, renderer: &mut TemplateRenderer) -> Result<()> {
+    match value {
+        Value::String(s) if references_matrix(s) => {
+            *s = renderer.render_str(s, "matrix")?;
+        }
+        Value::Array(items) => {
+            for item in items {
+                render_matrix_refs(item, renderer)?;
+            }
+        }
+        Value::Object(fields) => {
+            for field in fields.values_mut() {
+                render_matrix_refs(field, renderer)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn set_name(config: &mut TestConfig, name: String) {
+    if let Some(ref mut meta) = config.meta {
+        meta.name = name;
+    } else if let Some(ref mut test) = config.test {
+        test.metadata.name = name;
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
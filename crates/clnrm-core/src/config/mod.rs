@@ -9,35 +9,51 @@
 //! - `otel` - OpenTelemetry-related structures
 //! - `project` - Project-level cleanroom configuration
 //! - `loader` - File loading and parsing functions
+//! - `matrix` - Matrix expansion into concrete test configs
+//! - `schema` - JSON Schema for test configuration files
 //! - `deserializers` - Custom serde deserializers
 
 pub mod deserializers;
 pub mod loader;
+pub mod matrix;
 pub mod otel;
 pub mod project;
+pub mod schema;
 pub mod services;
 pub mod types;
 
 // Re-export commonly used types for backward compatibility
 pub use types::{
-    ArtifactsConfig, DeterminismConfig, LimitsConfig, MetaConfig, PolicyConfig, ReportConfig,
-    ScenarioConfig, StepConfig, TestConfig, TestMetadata, TestMetadataSection, TimeoutConfig,
+    ArtifactsConfig, DataConfig, DataFormat, DeterminismConfig, LimitsConfig, MetaConfig,
+    PolicyConfig, QueryAssertionConfig, ReportConfig, ScenarioConfig, StepConfig, TestConfig,
+    TestMetadata, TestMetadataSection, TimeoutConfig,
 };
 
-pub use services::{HealthCheckConfig, ServiceConfig, VolumeConfig};
+pub use services::{HealthCheck, HealthCheckConfig, ServiceConfig, VolumeConfig};
 
 pub use otel::{
-    CountBoundConfig, CountExpectationConfig, DurationBoundConfig, ExpectationsConfig,
-    ExpectedSpanConfig, ExpectedTraceConfig, GraphExpectationConfig, HermeticityExpectationConfig,
+    AttributeExpectationConfig, CountBoundConfig, CountExpectationConfig, DurationBoundConfig,
+    DurationExpectationConfig, ExpectationsConfig, ExpectedSpanConfig, ExpectedTraceConfig,
+    GraphExpectationConfig, HermeticityExpectationConfig, MetricExpectationConfig,
     OrderExpectationConfig, OtelConfig, OtelHeadersConfig, OtelPropagatorsConfig,
     OtelValidationSection, ResourceAttrsConfig, SpanAttributesConfig, SpanAttrsConfig,
     SpanEventsConfig, SpanExpectationConfig, StatusExpectationConfig, WindowExpectationConfig,
+    SUPPORTED_PROPAGATORS,
 };
 
 pub use project::{
-    load_cleanroom_config, load_cleanroom_config_from_file, CleanroomConfig, CliConfig,
-    ContainerConfig, ObservabilityConfig, PerformanceConfig, PluginConfig, ProjectConfig,
-    ReportingConfig, SecurityConfig, ServiceDefaultsConfig, TestExecutionConfig,
+    find_project_config, load_cleanroom_config, load_cleanroom_config_from_file,
+    load_coverage_config, load_record_config, CleanroomConfig, CliConfig, ContainerConfig,
+    CoverageConfig, ObservabilityConfig, PerformanceConfig, PluginConfig, ProjectConfig,
+    RecordConfig, RedactConfig, ReportingConfig, SecurityConfig, ServiceDefaultsConfig,
+    TestExecutionConfig,
 };
 
-pub use loader::{load_config_from_file, parse_toml_config};
+pub use loader::{
+    is_yaml_config, load_config_from_file, load_config_from_yaml_file, parse_config_for_path,
+    parse_toml_config, parse_yaml_config, template_dependencies,
+};
+
+pub use schema::test_config_schema;
+
+pub use matrix::{combination_label, expand_matrix, matrix_combinations, MatrixCombination};
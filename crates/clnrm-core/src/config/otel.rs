@@ -1,10 +1,11 @@
 //! OpenTelemetry configuration types
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// OTEL configuration (v0.6.0 - v1.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct OtelConfig {
     /// OTEL exporter type (e.g., "stdout", "otlp")
     pub exporter: String,
@@ -29,7 +30,7 @@ pub struct OtelConfig {
 }
 
 /// Expectations configuration (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, JsonSchema)]
 pub struct ExpectationsConfig {
     /// Span expectations
     #[serde(default)]
@@ -52,10 +53,22 @@ pub struct ExpectationsConfig {
     /// Hermeticity expectations
     #[serde(default)]
     pub hermeticity: Option<HermeticityExpectationConfig>,
+    /// Span attribute expectations
+    #[serde(default)]
+    pub attributes: Vec<AttributeExpectationConfig>,
+    /// Span duration expectations
+    #[serde(default)]
+    pub duration: Vec<DurationExpectationConfig>,
+    /// Span event expectations
+    #[serde(default)]
+    pub event: Vec<EventExpectationConfig>,
+    /// Metric expectations
+    #[serde(default)]
+    pub metric: Vec<MetricExpectationConfig>,
 }
 
 /// Span expectation configuration (v0.6.0 - v1.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct SpanExpectationConfig {
     /// Span name (can be glob pattern)
     pub name: String,
@@ -77,7 +90,7 @@ pub struct SpanExpectationConfig {
 }
 
 /// Span events configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct SpanEventsConfig {
     /// Any of these events must be present
     #[serde(default)]
@@ -88,7 +101,7 @@ pub struct SpanEventsConfig {
 }
 
 /// Duration bound configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct DurationBoundConfig {
     /// Minimum duration in milliseconds
     #[serde(default)]
@@ -99,7 +112,7 @@ pub struct DurationBoundConfig {
 }
 
 /// Span attributes configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct SpanAttributesConfig {
     /// All attributes must match
     pub all: Option<HashMap<String, String>>,
@@ -108,7 +121,7 @@ pub struct SpanAttributesConfig {
 }
 
 /// OpenTelemetry validation section in TOML
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct OtelValidationSection {
     /// Enable OTEL validation
     pub enabled: bool,
@@ -154,7 +167,7 @@ pub struct OtelValidationSection {
 }
 
 /// Expected span configuration from TOML
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct ExpectedSpanConfig {
     /// Span name (operation name)
     pub name: String,
@@ -169,7 +182,7 @@ pub struct ExpectedSpanConfig {
 }
 
 /// Expected trace configuration from TOML
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct ExpectedTraceConfig {
     /// Trace ID (optional, for specific trace validation)
     pub trace_id: Option<String>,
@@ -182,7 +195,7 @@ pub struct ExpectedTraceConfig {
 }
 
 /// Graph topology expectation from TOML (v1.0 schema)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct GraphExpectationConfig {
     /// Edges that must be present in the span graph (parent, child)
     /// Format: [["parent", "child"], ...]
@@ -198,7 +211,7 @@ pub struct GraphExpectationConfig {
 }
 
 /// Count bound configuration for cardinality expectations
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct CountBoundConfig {
     /// Greater than or equal to (>=)
     #[serde(default)]
@@ -212,7 +225,7 @@ pub struct CountBoundConfig {
 }
 
 /// Count expectations from TOML for span cardinalities
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct CountExpectationConfig {
     /// Total span count bounds
     #[serde(default)]
@@ -229,7 +242,7 @@ pub struct CountExpectationConfig {
 }
 
 /// Temporal window expectation from TOML
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct WindowExpectationConfig {
     /// Outer span name that defines the temporal window
     pub outer: String,
@@ -237,8 +250,80 @@ pub struct WindowExpectationConfig {
     pub contains: Vec<String>,
 }
 
+/// Span attribute expectation from TOML
+///
+/// Exactly one of `value` or `value_regex` must be set.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+pub struct AttributeExpectationConfig {
+    /// Span name that must carry the attribute
+    pub span: String,
+    /// Attribute key
+    pub key: String,
+    /// Exact expected value
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Regular expression the value must match
+    #[serde(default)]
+    pub value_regex: Option<String>,
+}
+
+/// Span duration expectation from TOML
+///
+/// At least one of `min_ms` or `max_ms` must be set.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+pub struct DurationExpectationConfig {
+    /// Span name whose duration is checked
+    pub span: String,
+    /// Minimum duration in milliseconds
+    #[serde(default)]
+    pub min_ms: Option<f64>,
+    /// Maximum duration in milliseconds
+    #[serde(default)]
+    pub max_ms: Option<f64>,
+}
+
+/// Span event expectation from TOML
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+pub struct EventExpectationConfig {
+    /// Span name that must emit the event
+    pub span: String,
+    /// Event name (e.g., "exception")
+    pub name: String,
+    /// Event attributes that must match exactly
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+}
+
+/// Metric expectation from TOML
+///
+/// At least one of `value`, `min_value`, `max_value`, `count` or `min_count`
+/// must be set; `count` and `min_count` apply to histograms.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+pub struct MetricExpectationConfig {
+    /// Metric name (e.g., "http.server.requests")
+    pub name: String,
+    /// Attributes data points must have to be counted
+    #[serde(default)]
+    pub attributes: HashMap<String, String>,
+    /// Exact value: a counter or histogram total, or the latest gauge reading
+    #[serde(default)]
+    pub value: Option<f64>,
+    /// Minimum value
+    #[serde(default)]
+    pub min_value: Option<f64>,
+    /// Maximum value
+    #[serde(default)]
+    pub max_value: Option<f64>,
+    /// Exact number of histogram samples
+    #[serde(default)]
+    pub count: Option<u64>,
+    /// Minimum number of histogram samples
+    #[serde(default)]
+    pub min_count: Option<u64>,
+}
+
 /// Hermeticity expectation from TOML (v1.0 schema)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct HermeticityExpectationConfig {
     /// Whether external service calls are forbidden
     #[serde(default)]
@@ -249,10 +334,14 @@ pub struct HermeticityExpectationConfig {
     /// Span attribute keys that are forbidden (e.g., "net.peer.name")
     #[serde(default, alias = "span_attrs_forbid_keys")]
     pub span_attrs: Option<SpanAttrsConfig>,
+    /// Run step containers without network egress, failing a step that tries
+    /// to reach anything but the declared services
+    #[serde(default)]
+    pub enforce_network: Option<bool>,
 }
 
 /// Resource attributes configuration for hermeticity validation
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct ResourceAttrsConfig {
     /// Attributes that must match exactly
     #[serde(default)]
@@ -260,7 +349,7 @@ pub struct ResourceAttrsConfig {
 }
 
 /// Span attributes configuration for hermeticity validation
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct SpanAttrsConfig {
     /// Attribute keys that are forbidden
     #[serde(default)]
@@ -268,7 +357,7 @@ pub struct SpanAttrsConfig {
 }
 
 /// Temporal ordering expectations (v1.0 schema)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct OrderExpectationConfig {
     /// Edges where first must temporally precede second
     /// Format: [["first", "second"], ...]
@@ -281,7 +370,7 @@ pub struct OrderExpectationConfig {
 }
 
 /// Status code expectations (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct StatusExpectationConfig {
     /// Expected status for all spans ("OK", "ERROR", "UNSET")
     #[serde(default)]
@@ -292,7 +381,7 @@ pub struct StatusExpectationConfig {
 }
 
 /// OTEL headers configuration (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+#[derive(Debug, Deserialize, Serialize, Clone, Default, JsonSchema)]
 pub struct OtelHeadersConfig {
     /// Custom OTLP headers (e.g., Authorization)
     #[serde(flatten)]
@@ -300,12 +389,73 @@ pub struct OtelHeadersConfig {
 }
 
 /// OTEL propagators configuration (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct OtelPropagatorsConfig {
     /// Propagators to use (e.g., ["tracecontext", "baggage"])
     pub r#use: Vec<String>,
 }
 
+/// Propagator names accepted in `[otel_propagators] use`, as in `OTEL_PROPAGATORS`
+pub const SUPPORTED_PROPAGATORS: &[&str] = &["tracecontext", "baggage", "b3", "b3multi"];
+
+impl OtelHeadersConfig {
+    /// Validate that every header can be sent with OTLP exports
+    pub fn validate(&self) -> crate::error::Result<()> {
+        validate_otlp_headers(&self.headers)
+    }
+}
+
+impl OtelPropagatorsConfig {
+    /// Validate that every propagator is supported
+    pub fn validate(&self) -> crate::error::Result<()> {
+        if self.r#use.is_empty() {
+            return Err(crate::error::CleanroomError::validation_error(
+                "Propagators list cannot be empty",
+            ));
+        }
+        for name in &self.r#use {
+            if !SUPPORTED_PROPAGATORS.contains(&name.as_str()) {
+                return Err(crate::error::CleanroomError::validation_error(format!(
+                    "Invalid propagator '{}'. Must be one of: {}",
+                    name,
+                    SUPPORTED_PROPAGATORS.join(", ")
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Validate OTLP header names and values
+///
+/// Names must be non-empty HTTP header tokens and values must be valid
+/// header values, so a misconfigured header fails at load rather than
+/// silently at export.
+pub fn validate_otlp_headers(headers: &HashMap<String, String>) -> crate::error::Result<()> {
+    use reqwest::header::{HeaderName, HeaderValue};
+
+    for (name, value) in headers {
+        if name.trim().is_empty() {
+            return Err(crate::error::CleanroomError::validation_error(
+                "OTLP header name cannot be empty",
+            ));
+        }
+        HeaderName::from_bytes(name.as_bytes()).map_err(|_| {
+            crate::error::CleanroomError::validation_error(format!(
+                "Invalid OTLP header name '{}': use letters, digits and !#$%&'*+-.^_`|~",
+                name
+            ))
+        })?;
+        HeaderValue::from_str(value).map_err(|_| {
+            crate::error::CleanroomError::validation_error(format!(
+                "Invalid value for OTLP header '{}': control characters are not allowed",
+                name
+            ))
+        })?;
+    }
+    Ok(())
+}
+
 impl OtelConfig {
     /// Validate the OTEL configuration
     pub fn validate(&self) -> crate::error::Result<()> {
@@ -342,6 +492,14 @@ impl OtelConfig {
             }
         }
 
+        if let Some(ref headers) = self.headers {
+            validate_otlp_headers(headers)?;
+        }
+
+        if let Some(ref propagators) = self.propagators {
+            propagators.validate()?;
+        }
+
         Ok(())
     }
 }
@@ -2,9 +2,11 @@
 //!
 //! Controls framework behavior, CLI defaults, and feature toggles.
 
+use crate::coverage::DimensionWeights;
 use crate::error::{CleanroomError, Result};
+use crate::otel::redact::SpanRedactor;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 /// Cleanroom project configuration structure
@@ -30,6 +32,72 @@ pub struct CleanroomConfig {
     pub reporting: ReportingConfig,
     /// Security and isolation settings
     pub security: SecurityConfig,
+    /// Behavior coverage settings
+    #[serde(default)]
+    pub coverage: CoverageConfig,
+    /// `clnrm record` settings
+    #[serde(default)]
+    pub record: RecordConfig,
+}
+
+/// Behavior coverage configuration
+///
+/// ```toml
+/// [coverage.weights]
+/// api_surface = 0.15
+/// state_transitions = 0.20
+/// error_scenarios = 0.15
+/// data_flows = 0.30
+/// integrations = 0.15
+/// span_coverage = 0.05
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct CoverageConfig {
+    /// Dimension weights used when scoring coverage; must sum to 1.0
+    #[serde(default)]
+    pub weights: DimensionWeights,
+}
+
+/// `clnrm record` configuration
+///
+/// ```toml
+/// [record.redact]
+/// keys = ["authorization", "cookie"]
+/// patterns = ["sk-[A-Za-z0-9]+"]
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RecordConfig {
+    /// Attribute redaction applied to recorded spans
+    #[serde(default)]
+    pub redact: RedactConfig,
+}
+
+/// Span attribute redaction rules
+#[derive(Debug, Deserialize, Serialize, Clone, Default)]
+pub struct RedactConfig {
+    /// Attribute keys whose values are masked entirely (case-insensitive,
+    /// also matching namespaced keys like `http.request.header.authorization`)
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Regexes whose matches are masked inside string attribute values
+    #[serde(default)]
+    pub patterns: Vec<String>,
+}
+
+impl RedactConfig {
+    /// Build the redactor for these rules
+    ///
+    /// # Errors
+    /// Returns error if a pattern is not a valid regex
+    pub fn redactor(&self) -> Result<SpanRedactor> {
+        let redactor = self
+            .keys
+            .iter()
+            .fold(SpanRedactor::new(), |redactor, key| redactor.with_key(key));
+        self.patterns
+            .iter()
+            .try_fold(redactor, |redactor, pattern| redactor.with_pattern(pattern))
+    }
 }
 
 /// Project metadata configuration
@@ -76,6 +144,14 @@ pub struct ContainerConfig {
     /// Container startup timeout
     #[serde(deserialize_with = "super::deserializers::deserialize_duration")]
     pub startup_timeout: Duration,
+    /// Container runtime: "docker", "podman", or "process" to run steps on the
+    /// host without isolation (`CLNRM_BACKEND` overrides)
+    #[serde(default = "default_container_backend")]
+    pub backend: String,
+}
+
+fn default_container_backend() -> String {
+    crate::backend::runtime::DEFAULT_BACKEND.to_string()
 }
 
 /// Service defaults configuration
@@ -203,6 +279,7 @@ impl Default for CleanroomConfig {
                 cleanup_policy: "on-success".to_string(),
                 max_containers: 10,
                 startup_timeout: Duration::from_secs(60),
+                backend: default_container_backend(),
             },
             services: ServiceDefaultsConfig {
                 default_timeout: Duration::from_secs(30),
@@ -253,6 +330,8 @@ impl Default for CleanroomConfig {
                 file_system_isolation: true,
                 security_level: "medium".to_string(),
             },
+            coverage: CoverageConfig::default(),
+            record: RecordConfig::default(),
         }
     }
 }
@@ -287,6 +366,8 @@ impl CleanroomConfig {
             ));
         }
 
+        crate::backend::runtime::backend_from_name(&self.containers.backend)?;
+
         // Validate observability settings
         if self.observability.metrics_port == 0 {
             return Err(CleanroomError::validation_error(
@@ -304,6 +385,18 @@ impl CleanroomConfig {
             }
         }
 
+        // Validate coverage weights
+        self.coverage
+            .weights
+            .validate()
+            .map_err(|e| e.with_context("Invalid [coverage.weights]"))?;
+
+        // Validate redaction patterns
+        self.record
+            .redact
+            .redactor()
+            .map_err(|e| e.with_context("Invalid [record.redact]"))?;
+
         // Validate log level
         match self.observability.log_level.to_lowercase().as_str() {
             "debug" | "info" | "warn" | "error" => {}
@@ -333,6 +426,89 @@ pub fn load_cleanroom_config_from_file<P: AsRef<Path>>(path: P) -> Result<Cleanr
     Ok(config)
 }
 
+/// Find the project's cleanroom.toml in `dir` or the nearest ancestor that
+/// has one
+pub fn find_project_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .map(|dir| dir.join("cleanroom.toml"))
+        .find(|path| path.is_file())
+}
+
+/// Load the `[coverage]` section of a cleanroom.toml
+///
+/// Only the coverage table is read, so this works for partial config files
+/// that [`load_cleanroom_config_from_file`] would reject. Returns the default
+/// coverage config if the file does not exist.
+///
+/// # Errors
+/// * The file cannot be read or parsed
+/// * `[coverage.weights]` does not sum to 1.0
+pub fn load_coverage_config<P: AsRef<Path>>(path: P) -> Result<CoverageConfig> {
+    #[derive(Deserialize)]
+    struct CoverageSection {
+        #[serde(default)]
+        coverage: CoverageConfig,
+    }
+
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(CoverageConfig::default());
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        CleanroomError::config_error(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+
+    let section: CoverageSection = toml::from_str(&content).map_err(|e| {
+        CleanroomError::config_error(format!("Invalid [coverage] in {}: {}", path.display(), e))
+    })?;
+
+    section
+        .coverage
+        .weights
+        .validate()
+        .map_err(|e| e.with_context("Invalid [coverage.weights]"))?;
+
+    Ok(section.coverage)
+}
+
+/// Load the `[record]` section of a cleanroom.toml
+///
+/// Like [`load_coverage_config`], only the record table is read. Returns the
+/// default record config if the file does not exist.
+///
+/// # Errors
+/// * The file cannot be read or parsed
+/// * A `[record.redact]` pattern is not a valid regex
+pub fn load_record_config<P: AsRef<Path>>(path: P) -> Result<RecordConfig> {
+    #[derive(Deserialize)]
+    struct RecordSection {
+        #[serde(default)]
+        record: RecordConfig,
+    }
+
+    let path = path.as_ref();
+    if !path.exists() {
+        return Ok(RecordConfig::default());
+    }
+
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        CleanroomError::config_error(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+
+    let section: RecordSection = toml::from_str(&content).map_err(|e| {
+        CleanroomError::config_error(format!("Invalid [record] in {}: {}", path.display(), e))
+    })?;
+
+    section
+        .record
+        .redact
+        .redactor()
+        .map_err(|e| e.with_context("Invalid [record.redact]"))?;
+
+    Ok(section.record)
+}
+
 /// Load CleanroomConfig from user directory
 fn load_cleanroom_config_from_user_dir() -> Result<CleanroomConfig> {
     let user_config_dir = std::env::var("HOME")
@@ -456,6 +632,8 @@ fn merge_configs(mut base: CleanroomConfig, override_config: CleanroomConfig) ->
     base.test_execution = override_config.test_execution;
     base.reporting = override_config.reporting;
     base.security = override_config.security;
+    base.coverage = override_config.coverage;
+    base.record = override_config.record;
 
     base
 }
@@ -0,0 +1,67 @@
+//! JSON Schema for test configuration files
+//!
+//! Derived from [`TestConfig`](super::TestConfig) and everything nested in it
+//! (services, steps, scenarios, `[expect]` blocks, ...) so editors can offer
+//! completion and inline validation for hand-edited `.clnrm.toml` files.
+//! `clnrm validate --schema` prints it; point the TOML language server at
+//! the output, e.g. with a `#:schema ./clnrm.schema.json` first line.
+//!
+//! The top level, `[meta]`, services, steps and scenarios declare
+//! `additionalProperties: false`, matching the unknown-key check `lint` and
+//! `dry-run` apply to those tables.
+
+use super::TestConfig;
+use schemars::generate::SchemaSettings;
+use schemars::transform::RecursiveTransform;
+use schemars::Schema;
+use serde_json::Value;
+
+/// JSON Schema for a test configuration file
+pub fn test_config_schema() -> Value {
+    let mut schema = SchemaSettings::draft07()
+        .with(|settings| {
+            // Runs first so the draft-07 transforms see the unwrapped `$ref`s
+            settings
+                .transforms
+                .insert(0, Box::new(RecursiveTransform(disallow_null)));
+        })
+        .into_generator()
+        .into_root_schema_for::<TestConfig>();
+    schema.insert("title".into(), "clnrm test configuration".into());
+    schema.to_value()
+}
+
+/// Drop the `null` alternatives and defaults schemars adds for `Option`
+/// fields
+///
+/// TOML has no null, so an optional key is either absent or of the inner
+/// type.
+fn disallow_null(schema: &mut Schema) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+
+    if object.get("default").is_some_and(Value::is_null) {
+        object.remove("default");
+    }
+
+    if let Some(Value::Array(types)) = object.get_mut("type") {
+        types.retain(|ty| ty != "null");
+        if let [ty] = types.as_slice() {
+            let ty = ty.clone();
+            object.insert("type".into(), ty);
+        }
+    }
+
+    if let Some(Value::Array(alternatives)) = object.get_mut("anyOf") {
+        alternatives.retain(|alternative| alternative.get("type").is_none_or(|ty| ty != "null"));
+        if alternatives.len() == 1 {
+            if let Some(Value::Object(only)) = alternatives.pop() {
+                object.remove("anyOf");
+                for (key, value) in only {
+                    object.entry(key).or_insert(value);
+                }
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Service and volume configuration types
 
 use crate::error::{CleanroomError, Result};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -10,7 +11,8 @@ fn default_plugin() -> String {
 }
 
 /// Service configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct ServiceConfig {
     /// Service plugin (generic_container, surrealdb, ollama, etc.)
     #[serde(default = "default_plugin")]
@@ -29,21 +31,37 @@ pub struct ServiceConfig {
     pub volumes: Option<Vec<VolumeConfig>>,
     /// Service health check
     pub health_check: Option<HealthCheckConfig>,
-    /// SurrealDB username (optional, defaults to root)
+    /// Database username (SurrealDB defaults to root, Postgres to postgres)
     pub username: Option<String>,
-    /// SurrealDB password (optional, defaults to root)
+    /// Database password (SurrealDB defaults to root, Postgres to postgres)
     pub password: Option<String>,
     /// SurrealDB strict mode (optional, defaults to false)
     pub strict: Option<bool>,
+    /// Postgres database name (optional, defaults to postgres)
+    pub database: Option<String>,
+    /// Path to a SQL file executed once the Postgres server is ready
+    pub init_sql: Option<String>,
     /// Span name to wait for before marking service as ready
     /// Service will poll for this span in OTEL output until detected or timeout
     pub wait_for_span: Option<String>,
     /// Timeout in seconds for waiting for span (default: 30)
     pub wait_for_span_timeout_secs: Option<u64>,
+    /// Startup stage (default: 0). Services in the same stage start
+    /// concurrently; stages start in ascending order, so a service that
+    /// depends on another should use a higher value.
+    pub startup_order: Option<u32>,
+    /// Memory limit (generic_container), in bytes or with a `k`, `m` or `g`
+    /// suffix, e.g. `"512m"`
+    pub memory: Option<String>,
+    /// CPU limit (generic_container) in cores; fractions are allowed, e.g. `0.5`
+    pub cpus: Option<f64>,
+    /// Directory the web_server plugin serves; without it, the server echoes
+    /// each request back as JSON
+    pub static_dir: Option<String>,
 }
 
 /// Volume configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct VolumeConfig {
     /// Host path
     pub host_path: String,
@@ -110,19 +128,101 @@ impl VolumeConfig {
     }
 }
 
+/// Default HTTP status a health check endpoint must answer with
+fn default_expect_status() -> u16 {
+    200
+}
+
 /// Health check configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+///
+/// The service is only considered started once the probe succeeds:
+///
+/// ```toml
+/// [service.web.health_check]
+/// http = "http://localhost:8080/health"
+/// expect_status = 200
+/// timeout = 30
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct HealthCheckConfig {
-    /// Health check command
-    pub cmd: Vec<String>,
-    /// Health check interval in seconds
+    /// What to probe
+    #[serde(flatten)]
+    pub check: HealthCheck,
+    /// Seconds between probes (default: 1)
     pub interval: Option<u64>,
-    /// Health check timeout in seconds
+    /// Seconds to wait for a successful probe before startup fails (default: 30)
     pub timeout: Option<u64>,
-    /// Number of retries
+    /// Maximum number of failed probes before startup fails
     pub retries: Option<u32>,
 }
 
+/// Readiness probe for a service
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq, JsonSchema)]
+#[serde(untagged)]
+pub enum HealthCheck {
+    /// HTTP GET that must answer with `expect_status`
+    Http {
+        /// URL to request; ports are mapped 1:1, so `localhost:<port>`
+        /// reaches the container
+        http: String,
+        /// Expected response status (default: 200)
+        #[serde(default = "default_expect_status")]
+        expect_status: u16,
+    },
+    /// Command run inside the container that must exit with code 0
+    Command {
+        /// Command and arguments
+        #[serde(alias = "cmd")]
+        command: Vec<String>,
+    },
+}
+
+impl HealthCheckConfig {
+    /// Validate the probe definition
+    pub fn validate(&self) -> Result<()> {
+        match &self.check {
+            HealthCheck::Http {
+                http,
+                expect_status,
+            } => {
+                let url = url::Url::parse(http).map_err(|e| {
+                    CleanroomError::validation_error(format!(
+                        "Invalid health check URL '{}': {}",
+                        http, e
+                    ))
+                })?;
+                if !matches!(url.scheme(), "http" | "https") {
+                    return Err(CleanroomError::validation_error(format!(
+                        "Health check URL '{}' must use http or https",
+                        http
+                    )));
+                }
+                if !(100..=599).contains(expect_status) {
+                    return Err(CleanroomError::validation_error(format!(
+                        "Health check expect_status must be an HTTP status code, got {}",
+                        expect_status
+                    )));
+                }
+            }
+            HealthCheck::Command { command } => {
+                if command.is_empty() {
+                    return Err(CleanroomError::validation_error(
+                        "Health check command cannot be empty",
+                    ));
+                }
+            }
+        }
+
+        if self.interval == Some(0) || self.timeout == Some(0) {
+            return Err(CleanroomError::validation_error(
+                "Health check interval and timeout must be greater than 0",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
 impl ServiceConfig {
     /// Validate the service configuration
     pub fn validate(&self) -> Result<()> {
@@ -138,7 +238,11 @@ impl ServiceConfig {
                     "Service image cannot be empty",
                 ));
             }
-        } else if self.plugin != "network_service" && self.plugin != "ollama" {
+        } else if self.plugin != "network_service"
+            && self.plugin != "ollama"
+            && self.plugin != "postgres"
+            && self.plugin != "web_server"
+        {
             // For container-based services, image is required
             return Err(CleanroomError::validation_error(
                 "Service image is required for container-based services",
@@ -154,6 +258,54 @@ impl ServiceConfig {
             }
         }
 
+        if let Some(ref health_check) = self.health_check {
+            health_check.validate()?;
+        }
+
+        self.memory_limit_bytes()?;
+        if let Some(cpus) = self.cpus {
+            if !cpus.is_finite() || cpus <= 0.0 {
+                return Err(CleanroomError::validation_error(format!(
+                    "Service cpus must be a positive number, got {}",
+                    cpus
+                )));
+            }
+        }
+
         Ok(())
     }
+
+    /// Memory limit in bytes, parsed from `memory`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the value is not a positive integer with an optional
+    /// `b`, `k`, `m` or `g` suffix
+    pub fn memory_limit_bytes(&self) -> Result<Option<u64>> {
+        let Some(ref memory) = self.memory else {
+            return Ok(None);
+        };
+
+        let value = memory.trim().to_ascii_lowercase();
+        let (digits, multiplier) = match value.char_indices().last() {
+            Some((i, 'b')) => (&value[..i], 1),
+            Some((i, 'k')) => (&value[..i], 1 << 10),
+            Some((i, 'm')) => (&value[..i], 1 << 20),
+            Some((i, 'g')) => (&value[..i], 1 << 30),
+            _ => (value.as_str(), 1),
+        };
+
+        digits
+            .parse::<u64>()
+            .ok()
+            .filter(|amount| *amount > 0)
+            .and_then(|amount| amount.checked_mul(multiplier))
+            .map(Some)
+            .ok_or_else(|| {
+                CleanroomError::validation_error(format!(
+                    "Invalid service memory limit '{}': expected a size such as '512m' or '2g'",
+                    memory
+                ))
+            })
+    }
 }
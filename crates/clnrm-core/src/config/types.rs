@@ -3,6 +3,9 @@
 //! Defines the main TestConfig structure and related metadata types.
 
 use crate::error::{CleanroomError, Result};
+use crate::otel::redact::SpanRedactor;
+use crate::policy::Policy;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -96,7 +99,8 @@ pub fn parse_shell_command(cmd: &str) -> Result<Vec<String>> {
 }
 
 /// Main test configuration structure
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct TestConfig {
     /// Test metadata section (v0.4.x format)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -149,10 +153,17 @@ pub struct TestConfig {
     /// OTEL propagators (v0.6.0)
     #[serde(default)]
     pub otel_propagators: Option<OtelPropagatorsConfig>,
+    /// External data rows that parameterize scenarios
+    #[serde(default)]
+    pub data: Option<DataConfig>,
+    /// Policy enforced on the test's steps
+    #[serde(default)]
+    pub policy: Option<PolicyConfig>,
 }
 
 /// Meta configuration (v0.6.0 - simplified metadata section)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct MetaConfig {
     /// Test name
     pub name: String,
@@ -160,17 +171,23 @@ pub struct MetaConfig {
     pub version: String,
     /// Test description
     pub description: Option<String>,
+    /// Regexes whose matches are masked in every step's captured output
+    /// before it is logged or reported
+    pub redact: Option<Vec<String>>,
+    /// Host environment variables passed through to step containers, in
+    /// addition to `clnrm run --env-inherit`; none are by default
+    pub inherit_env: Option<Vec<String>>,
 }
 
 /// Test metadata section
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct TestMetadataSection {
     /// Test metadata
     pub metadata: TestMetadata,
 }
 
 /// Test metadata configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct TestMetadata {
     /// Test name
     pub name: String,
@@ -181,7 +198,8 @@ pub struct TestMetadata {
 }
 
 /// Individual test scenario configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct ScenarioConfig {
     /// Scenario name
     pub name: String,
@@ -195,27 +213,63 @@ pub struct ScenarioConfig {
     /// Format: shell command string like "sh -lc 'echo test'"
     #[serde(default)]
     pub run: Option<String>,
-    /// Whether to run steps concurrently
+    /// Directory inside the container to run `run` from; the scenario fails
+    /// if it doesn't exist
+    pub workdir: Option<String>,
+    /// Whether to run in parallel with the concurrent scenarios declared
+    /// next to it in the same dependency stage
     pub concurrent: Option<bool>,
     /// Scenario-specific timeout
     pub timeout_ms: Option<u64>,
-    /// Scenario-specific policy
+    /// Scenario-specific policy, checked on `run` together with the test's
     pub policy: Option<PolicyConfig>,
     /// Artifact collection configuration
     #[serde(default)]
     pub artifacts: Option<ArtifactsConfig>,
+    /// Names of scenarios that must succeed before this one runs
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// Keep running the remaining scenarios if this one fails; scenarios
+    /// that depend on it are still skipped and the test is failed either way
+    pub continue_on_failure: Option<bool>,
+    /// Database queries checked once the scenario's command succeeds
+    #[serde(default)]
+    pub assertions: Vec<QueryAssertionConfig>,
+}
+
+/// Database query assertion on a scenario (`[[scenario.assertions]]`)
+///
+/// Exactly one of `row_count` and `equals` must be set.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+pub struct QueryAssertionConfig {
+    /// Database service to query; defaults to the scenario's service
+    pub service: Option<String>,
+    /// SQL query to run
+    pub query: String,
+    /// Number of rows the query must select
+    pub row_count: Option<usize>,
+    /// Single value the query must select
+    pub equals: Option<String>,
 }
 
 /// Artifact collection configuration for scenarios
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct ArtifactsConfig {
     /// List of artifact types to collect
     /// Format: ["spans:default", "logs:stderr", "files:/tmp/output"]
+    ///
+    /// `spans:otlp` receives spans over OTLP/HTTP (JSON) while the scenario
+    /// runs instead of parsing them from stdout, and `spans:file:<path>` reads
+    /// them from a JSON or NDJSON trace file. `metrics:otlp` and
+    /// `metrics:file:<path>` collect OTLP metrics for `[[expect.metric]]` the
+    /// same ways. `logs:<service>` saves that service's container output as
+    /// `<service>.log` once the scenario ends.
     pub collect: Vec<String>,
 }
 
 /// Individual test step configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+#[schemars(deny_unknown_fields)]
 pub struct StepConfig {
     /// Step name
     pub name: String,
@@ -223,20 +277,39 @@ pub struct StepConfig {
     pub command: Vec<String>,
     /// Expected output regex pattern
     pub expected_output_regex: Option<String>,
-    /// Working directory
+    /// Directory inside the container to run the command from; the step
+    /// fails if it doesn't exist
     pub workdir: Option<String>,
     /// Step-specific environment variables
     pub env: Option<HashMap<String, String>>,
-    /// Expected exit code (default: 0)
+    /// Exit code the command must exit with for the step to pass (default: 0)
     pub expected_exit_code: Option<i32>,
-    /// Whether to continue on failure
+    /// Record a failure of this step and run the remaining steps; the test
+    /// is failed once they have finished
     pub continue_on_failure: Option<bool>,
     /// Service to execute command on (optional)
     pub service: Option<String>,
+    /// Skip this step unconditionally
+    pub skip: Option<bool>,
+    /// Skip this step when the Tera expression evaluates to true
+    /// (e.g. `"env(name='CI') == 'true'"`)
+    pub skip_if: Option<String>,
+    /// Number of times to re-run the command on a nonzero exit code (default: 0)
+    pub retries: Option<u32>,
+    /// Delay before the first retry in milliseconds, doubled on each
+    /// subsequent retry (default: 500)
+    pub retry_delay_ms: Option<u64>,
+    /// Kill the command and fail the step if an attempt runs longer than
+    /// this many milliseconds (default: no timeout)
+    pub timeout_ms: Option<u64>,
+    /// Regexes whose matches are masked in this step's captured output, in
+    /// addition to `[meta] redact`; `expected_output_regex` still sees the
+    /// unmasked output
+    pub redact: Option<Vec<String>>,
 }
 
 /// Security policy configuration
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct PolicyConfig {
     /// Security level
     pub security_level: Option<String>,
@@ -250,6 +323,10 @@ pub struct PolicyConfig {
     pub allowed_network_hosts: Option<Vec<String>>,
     /// Disallowed commands
     pub disallowed_commands: Option<Vec<String>>,
+    /// Binaries steps may run; any binary not denied if unset
+    pub allowed_binaries: Option<Vec<String>>,
+    /// Binaries steps may never run (together with `disallowed_commands`)
+    pub denied_binaries: Option<Vec<String>>,
 }
 
 /// Timeout configuration
@@ -264,7 +341,7 @@ pub struct TimeoutConfig {
 }
 
 /// Report output configuration (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct ReportConfig {
     /// Path to JSON report output
     #[serde(default)]
@@ -275,10 +352,60 @@ pub struct ReportConfig {
     /// Path to SHA-256 digest file
     #[serde(default)]
     pub digest: Option<String>,
+    /// Path to consolidated JSON report (metadata, spans and validations)
+    #[serde(default)]
+    pub consolidated: Option<String>,
+}
+
+/// Data provider for data-driven scenarios
+///
+/// Every scenario runs once per row of `file`, reported as
+/// `<scenario>[<row>]` (0-based), with the row's fields available to its
+/// `run` command as `{{ row.<field> }}`.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
+pub struct DataConfig {
+    /// CSV file with a header row, or JSON file holding an array of objects,
+    /// relative to the test file
+    pub file: String,
+    /// File format; inferred from the file extension when omitted
+    #[serde(default)]
+    pub format: Option<DataFormat>,
+}
+
+/// Format of a [`DataConfig`] file
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DataFormat {
+    /// Comma-separated values with a header row
+    Csv,
+    /// A JSON array of objects
+    Json,
+}
+
+impl DataConfig {
+    /// The declared format, or the one implied by the file extension
+    pub fn resolved_format(&self) -> Result<DataFormat> {
+        if let Some(format) = self.format {
+            return Ok(format);
+        }
+
+        let extension = std::path::Path::new(&self.file)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("csv") => Ok(DataFormat::Csv),
+            Some("json") => Ok(DataFormat::Json),
+            _ => Err(CleanroomError::validation_error(format!(
+                "Cannot infer the format of data file '{}': set data.format to \"csv\" or \"json\"",
+                self.file
+            ))),
+        }
+    }
 }
 
 /// Determinism configuration for reproducible tests (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct DeterminismConfig {
     /// Random seed for deterministic ordering
     #[serde(default)]
@@ -295,7 +422,41 @@ impl DeterminismConfig {
 }
 
 /// Resource limits configuration (v0.6.0)
-#[derive(Debug, Deserialize, Serialize, Clone)]
+///
+/// `max_steps` is checked when the test is loaded, `max_duration_ms` while
+/// it runs, and `max_memory_bytes` caps every generic container service.
+///
+/// ```
+/// use clnrm_core::config::parse_toml_config;
+///
+/// let config = parse_toml_config(
+///     r#"
+/// [meta]
+/// name = "too_many_steps"
+/// version = "1.0"
+///
+/// [limits]
+/// max_steps = 2
+///
+/// [[steps]]
+/// name = "one"
+/// command = ["true"]
+///
+/// [[steps]]
+/// name = "two"
+/// command = ["true"]
+///
+/// [[steps]]
+/// name = "three"
+/// command = ["true"]
+/// "#,
+/// )?;
+///
+/// let err = config.validate().unwrap_err();
+/// assert!(err.to_string().contains("limits.max_steps = 2"));
+/// # Ok::<(), clnrm_core::error::CleanroomError>(())
+/// ```
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema)]
 pub struct LimitsConfig {
     /// CPU limit in millicores
     #[serde(default)]
@@ -303,6 +464,52 @@ pub struct LimitsConfig {
     /// Memory limit in megabytes
     #[serde(default)]
     pub memory_mb: Option<u32>,
+    /// Abort the test once it has run for this many milliseconds
+    #[serde(default)]
+    pub max_duration_ms: Option<u64>,
+    /// Memory limit applied to generic container services, in bytes
+    #[serde(default)]
+    pub max_memory_bytes: Option<u64>,
+    /// Maximum number of steps, counting scenario steps
+    #[serde(default)]
+    pub max_steps: Option<usize>,
+}
+
+impl LimitsConfig {
+    /// Validate the limits against the test they belong to
+    pub fn validate(&self, config: &TestConfig) -> Result<()> {
+        if let Some(max_steps) = self.max_steps {
+            let step_count = config.step_count();
+            if step_count > max_steps {
+                return Err(CleanroomError::validation_error(format!(
+                    "Test has {} steps, exceeding limits.max_steps = {}",
+                    step_count, max_steps
+                )));
+            }
+        }
+
+        if self.max_duration_ms == Some(0) {
+            return Err(CleanroomError::validation_error(
+                "limits.max_duration_ms must be greater than 0",
+            ));
+        }
+
+        if let Some(max_memory) = self.max_memory_bytes {
+            let services = config.services.iter().chain(config.service.iter());
+            for (name, service) in services.flat_map(|services| services.iter()) {
+                if let Some(memory) = service.memory_limit_bytes()? {
+                    if memory > max_memory {
+                        return Err(CleanroomError::validation_error(format!(
+                            "Service {} memory limit of {} bytes exceeds limits.max_memory_bytes = {}",
+                            name, memory, max_memory
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl TestConfig {
@@ -352,6 +559,10 @@ impl TestConfig {
             ));
         }
 
+        if let Some(meta) = &self.meta {
+            meta.output_redactor()?;
+        }
+
         // Validate each step
         for (i, step) in self.steps.iter().enumerate() {
             step.validate()
@@ -364,6 +575,7 @@ impl TestConfig {
                 .validate()
                 .map_err(|e| CleanroomError::validation_error(format!("Scenario {}: {}", i, e)))?;
         }
+        self.scenario_stages()?;
 
         // Validate services if present
         if let Some(services) = &self.services {
@@ -398,6 +610,16 @@ impl TestConfig {
             otel.validate()
                 .map_err(|e| CleanroomError::validation_error(format!("OTEL config: {}", e)))?;
         }
+        if let Some(ref headers) = self.otel_headers {
+            headers
+                .validate()
+                .map_err(|e| CleanroomError::validation_error(format!("OTEL headers: {}", e)))?;
+        }
+        if let Some(ref propagators) = self.otel_propagators {
+            propagators.validate().map_err(|e| {
+                CleanroomError::validation_error(format!("OTEL propagators: {}", e))
+            })?;
+        }
 
         // Validate expectations if present
         if let Some(ref expect) = self.expect {
@@ -415,6 +637,27 @@ impl TestConfig {
                 })?;
             }
         }
+        self.validate_hermetic_network()?;
+
+        // Validate policy if present
+        if let Some(ref policy) = self.policy {
+            policy.validate()?;
+        }
+
+        // Validate limits if present
+        if let Some(ref limits) = self.limits {
+            limits.validate(self)?;
+        }
+
+        // Validate data provider if present
+        if let Some(ref data) = self.data {
+            if self.scenario.is_empty() {
+                return Err(CleanroomError::validation_error(
+                    "[data] requires at least one [[scenario]] to parameterize",
+                ));
+            }
+            data.resolved_format()?;
+        }
 
         // Validate meta config if present
         if let Some(ref meta) = self.meta {
@@ -432,6 +675,152 @@ impl TestConfig {
 
         Ok(())
     }
+
+    /// Whether `[expect.hermeticity] enforce_network` runs the test on a
+    /// hermetic network
+    pub fn enforces_network(&self) -> bool {
+        self.expect
+            .as_ref()
+            .and_then(|expect| expect.hermeticity.as_ref())
+            .and_then(|hermeticity| hermeticity.enforce_network)
+            .unwrap_or(false)
+    }
+
+    /// Reject probes run from the host against generic containers on the
+    /// hermetic network, which publishes no ports for them to reach: HTTP
+    /// health checks, and the readiness check on each of `ports`
+    fn validate_hermetic_network(&self) -> Result<()> {
+        if !self.enforces_network() {
+            return Ok(());
+        }
+
+        let services = self.services.iter().chain(self.service.iter());
+        for (name, service) in services.flat_map(|services| services.iter()) {
+            if service.plugin != "generic_container" {
+                continue;
+            }
+            if let Some(HealthCheck::Http { http, .. }) = service
+                .health_check
+                .as_ref()
+                .map(|health_check| &health_check.check)
+            {
+                return Err(CleanroomError::validation_error(format!(
+                    "Service {}: HTTP health check '{}' runs from the host and can't reach a service on the hermetic network (expect.hermeticity.enforce_network); use a `command` health check instead",
+                    name, http
+                )));
+            }
+            if service
+                .ports
+                .as_ref()
+                .is_some_and(|ports| !ports.is_empty())
+            {
+                return Err(CleanroomError::validation_error(format!(
+                    "Service {}: ports are published to the host for readiness checks, which can't reach a service on the hermetic network (expect.hermeticity.enforce_network); remove `ports` (steps reach the service by name) and use a `command` health check instead",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Number of steps the test runs
+    ///
+    /// Counts top-level steps plus the steps of every scenario; a v1.0
+    /// scenario with only `service`/`run` counts as one step.
+    pub fn step_count(&self) -> usize {
+        let scenario_steps: usize = self
+            .scenario
+            .iter()
+            .map(|scenario| scenario.steps.len().max(1))
+            .sum();
+        self.steps.len() + scenario_steps
+    }
+
+    /// Group scenarios into execution stages according to `depends_on`
+    ///
+    /// A scenario's stage is one past the latest stage of its dependencies,
+    /// so every scenario runs after everything it depends on. Scenarios keep
+    /// their declaration order within a stage.
+    ///
+    /// # Errors
+    /// * A scenario depends on a scenario that does not exist
+    /// * The dependencies form a cycle (the error names the cycle path)
+    pub fn scenario_stages(&self) -> Result<Vec<Vec<&ScenarioConfig>>> {
+        let index: HashMap<&str, usize> = self
+            .scenario
+            .iter()
+            .enumerate()
+            .map(|(i, scenario)| (scenario.name.as_str(), i))
+            .collect();
+
+        let mut deps = Vec::with_capacity(self.scenario.len());
+        for scenario in &self.scenario {
+            let mut scenario_deps = Vec::with_capacity(scenario.depends_on.len());
+            for dep in &scenario.depends_on {
+                let dep_index = index.get(dep.as_str()).ok_or_else(|| {
+                    CleanroomError::validation_error(format!(
+                        "Scenario '{}' depends on unknown scenario '{}'",
+                        scenario.name, dep
+                    ))
+                })?;
+                scenario_deps.push(*dep_index);
+            }
+            deps.push(scenario_deps);
+        }
+
+        let mut stage_of = vec![None; self.scenario.len()];
+        let mut path = Vec::new();
+        for i in 0..self.scenario.len() {
+            self.scenario_stage(i, &deps, &mut stage_of, &mut path)?;
+        }
+
+        let mut stages: Vec<Vec<&ScenarioConfig>> = Vec::new();
+        for (scenario, stage) in self.scenario.iter().zip(stage_of) {
+            let stage = stage.unwrap_or(0);
+            if stages.len() <= stage {
+                stages.resize_with(stage + 1, Vec::new);
+            }
+            stages[stage].push(scenario);
+        }
+
+        Ok(stages)
+    }
+
+    /// Depth-first stage assignment; `path` holds the scenarios being visited
+    fn scenario_stage(
+        &self,
+        i: usize,
+        deps: &[Vec<usize>],
+        stage_of: &mut [Option<usize>],
+        path: &mut Vec<usize>,
+    ) -> Result<usize> {
+        if let Some(stage) = stage_of[i] {
+            return Ok(stage);
+        }
+
+        if let Some(start) = path.iter().position(|&p| p == i) {
+            let cycle: Vec<&str> = path[start..]
+                .iter()
+                .chain(std::iter::once(&i))
+                .map(|&p| self.scenario[p].name.as_str())
+                .collect();
+            return Err(CleanroomError::validation_error(format!(
+                "Scenario dependency cycle detected: {}",
+                cycle.join(" -> ")
+            )));
+        }
+
+        path.push(i);
+        let mut stage = 0;
+        for &dep in &deps[i] {
+            stage = stage.max(self.scenario_stage(dep, deps, stage_of, path)? + 1);
+        }
+        path.pop();
+
+        stage_of[i] = Some(stage);
+        Ok(stage)
+    }
 }
 
 impl ScenarioConfig {
@@ -459,10 +848,54 @@ impl ScenarioConfig {
                 .map_err(|e| CleanroomError::validation_error(format!("Step {}: {}", i, e)))?;
         }
 
+        if let Some(ref policy) = self.policy {
+            policy.validate()?;
+        }
+
+        for (i, assertion) in self.assertions.iter().enumerate() {
+            if assertion.service.is_none() && self.service.is_none() {
+                return Err(CleanroomError::validation_error(format!(
+                    "Assertion {}: 'service' is required when the scenario has none",
+                    i
+                )));
+            }
+            assertion
+                .validate()
+                .map_err(|e| CleanroomError::validation_error(format!("Assertion {}: {}", i, e)))?;
+        }
+
         Ok(())
     }
 }
 
+impl QueryAssertionConfig {
+    /// Validate the assertion configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.query.trim().is_empty() {
+            return Err(CleanroomError::validation_error(
+                "Assertion query cannot be empty",
+            ));
+        }
+
+        match (self.row_count, &self.equals) {
+            (Some(_), None) | (None, Some(_)) => Ok(()),
+            _ => Err(CleanroomError::validation_error(
+                "Assertion must set exactly one of 'row_count' or 'equals'",
+            )),
+        }
+    }
+}
+
+impl MetaConfig {
+    /// Redactor for the captured output of every step in the test
+    ///
+    /// # Errors
+    /// Returns error if a pattern is not a valid regex
+    pub fn output_redactor(&self) -> Result<SpanRedactor> {
+        text_redactor(self.redact.iter().flatten())
+    }
+}
+
 impl StepConfig {
     /// Validate the step configuration
     pub fn validate(&self) -> Result<()> {
@@ -478,10 +911,51 @@ impl StepConfig {
             ));
         }
 
+        text_redactor(self.redact.iter().flatten())?;
+
+        Ok(())
+    }
+
+    /// Redactor for this step's captured output, combining `[meta] redact`
+    /// with the step's own `redact` patterns
+    ///
+    /// # Errors
+    /// Returns error if a pattern is not a valid regex
+    pub fn output_redactor(&self, meta: Option<&MetaConfig>) -> Result<SpanRedactor> {
+        let meta_patterns = meta.and_then(|meta| meta.redact.as_ref());
+        text_redactor(meta_patterns.into_iter().chain(&self.redact).flatten())
+    }
+
+    /// Exit code the command must exit with (`expected_exit_code`, default 0)
+    pub fn expected_exit_code(&self) -> i32 {
+        self.expected_exit_code.unwrap_or(0)
+    }
+
+    /// Check the command's exit code against [`Self::expected_exit_code`]
+    ///
+    /// # Errors
+    /// Returns a validation error such as "expected exit 3, got 0" on any
+    /// other exit code
+    pub fn check_exit_code(&self, exit_code: i32) -> Result<()> {
+        let expected = self.expected_exit_code();
+        if exit_code != expected {
+            return Err(CleanroomError::validation_error(format!(
+                "Step '{}' failed: expected exit {}, got {}",
+                self.name, expected, exit_code
+            )));
+        }
         Ok(())
     }
 }
 
+fn text_redactor<'a>(patterns: impl IntoIterator<Item = &'a String>) -> Result<SpanRedactor> {
+    patterns
+        .into_iter()
+        .try_fold(SpanRedactor::new(), |redactor, pattern| {
+            redactor.with_pattern(pattern)
+        })
+}
+
 impl PolicyConfig {
     /// Validate the policy configuration
     pub fn validate(&self) -> Result<()> {
@@ -504,6 +978,25 @@ impl PolicyConfig {
             }
         }
 
+        if self.allowed_binaries.as_ref().is_some_and(Vec::is_empty) {
+            return Err(CleanroomError::validation_error(
+                "Policy allowed_binaries cannot be empty; omit it to allow any binary",
+            ));
+        }
+
         Ok(())
     }
+
+    /// Runtime policy for the command allow/deny lists
+    pub fn to_policy(&self) -> Policy {
+        let denied = self
+            .denied_binaries
+            .iter()
+            .chain(self.disallowed_commands.iter())
+            .flatten()
+            .cloned();
+        Policy::default()
+            .with_allowed_binaries(self.allowed_binaries.iter().flatten().cloned())
+            .with_denied_binaries(denied)
+    }
 }
@@ -80,7 +80,16 @@ impl BehaviorManifest {
         &self,
         coverage: &BehaviorCoverage,
     ) -> Result<BehaviorCoverageReport> {
-        let weights = self.get_weights()?;
+        self.calculate_coverage_with_weights(coverage, self.get_weights()?)
+    }
+
+    /// Calculate coverage report using the given dimension weights
+    pub fn calculate_coverage_with_weights(
+        &self,
+        coverage: &BehaviorCoverage,
+        weights: DimensionWeights,
+    ) -> Result<BehaviorCoverageReport> {
+        weights.validate()?;
 
         // Calculate API surface coverage
         let api_covered = coverage.api_endpoints_covered.len();
@@ -4,8 +4,9 @@
 //! to measure what percentage of a system's behaviors are actually validated.
 
 use crate::error::{CleanroomError, Result};
+use crate::validation::span_validator::{SpanData, SpanKind};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 
 pub mod manifest;
 pub mod report;
@@ -79,6 +80,23 @@ impl BehaviorCoverage {
         self.spans_observed.insert(span_name);
     }
 
+    /// Record spans collected from a test run
+    ///
+    /// Every span name is observed, each HTTP request served is recorded as
+    /// an API endpoint (see [`api_endpoint_from_span`]) and each outgoing
+    /// call as an integration (see [`integration_from_span`]).
+    pub fn record_spans(&mut self, spans: &[SpanData]) {
+        for span in spans {
+            self.record_span(span.name.clone());
+            if let Some(endpoint) = api_endpoint_from_span(span) {
+                self.record_api_endpoint(endpoint);
+            }
+            if let Some((service, operation)) = integration_from_span(span) {
+                self.record_integration(service, operation);
+            }
+        }
+    }
+
     /// Merge another coverage tracker into this one
     pub fn merge(&mut self, other: &BehaviorCoverage) {
         self.api_endpoints_covered
@@ -106,6 +124,83 @@ impl Default for BehaviorCoverage {
     }
 }
 
+/// String value of a span attribute, if set
+fn span_attribute(span: &SpanData, key: &str) -> Option<String> {
+    span.attributes.get(key).map(|value| match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+/// HTTP method and route of a span, e.g. `GET /users/{id}`
+///
+/// Falls back to the method alone when the span has no route or path.
+fn http_operation(span: &SpanData) -> Option<String> {
+    let method =
+        span_attribute(span, "http.request.method").or_else(|| span_attribute(span, "http.method"));
+    let target = span_attribute(span, "http.route")
+        .or_else(|| span_attribute(span, "url.path"))
+        .or_else(|| span_attribute(span, "http.target"));
+    match (method, target) {
+        (Some(method), Some(target)) => Some(format!("{} {}", method, target)),
+        (method, _) => method,
+    }
+}
+
+/// Derive the API endpoint a server span handled, e.g. `GET /users/{id}`
+///
+/// Only server spans with both an HTTP method and a route or path count, so
+/// the endpoint can be matched against a manifest's `api_surface.endpoints`.
+pub fn api_endpoint_from_span(span: &SpanData) -> Option<String> {
+    if span.kind != Some(SpanKind::Server) {
+        return None;
+    }
+    http_operation(span).filter(|operation| operation.contains(' '))
+}
+
+/// Attributes naming the service a span calls, most specific first
+const INTEGRATION_SERVICE_ATTRIBUTES: &[&str] = &[
+    "peer.service",
+    "rpc.service",
+    "db.system",
+    "messaging.system",
+    "server.address",
+    "net.peer.name",
+];
+
+/// Attributes naming the operation a span performs on that service
+const INTEGRATION_OPERATION_ATTRIBUTES: &[&str] = &[
+    "rpc.method",
+    "db.operation.name",
+    "db.operation",
+    "messaging.operation.name",
+    "messaging.operation",
+];
+
+/// Derive the (service, operation) integration a span exercises
+///
+/// The service comes from OpenTelemetry semantic convention attributes such
+/// as `peer.service` or `db.system`, and the operation from `rpc.method`,
+/// `db.operation`, the HTTP method and route, or else the span name. Server
+/// and consumer spans handle the system's own requests rather than calling
+/// out, so they yield no integration.
+pub fn integration_from_span(span: &SpanData) -> Option<(String, String)> {
+    if matches!(span.kind, Some(SpanKind::Server) | Some(SpanKind::Consumer)) {
+        return None;
+    }
+
+    let service = INTEGRATION_SERVICE_ATTRIBUTES
+        .iter()
+        .find_map(|key| span_attribute(span, key))?;
+    let operation = INTEGRATION_OPERATION_ATTRIBUTES
+        .iter()
+        .find_map(|key| span_attribute(span, key))
+        .or_else(|| http_operation(span))
+        .unwrap_or_else(|| span.name.clone());
+
+    Some((service, operation))
+}
+
 /// State transition identifier
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct StateTransition {
@@ -227,6 +322,119 @@ impl BehaviorCoverageReport {
 
         output
     }
+
+    /// Compare this report against a previous one
+    ///
+    /// A behavior is newly covered when it was uncovered in `previous` and is
+    /// no longer uncovered here, and newly uncovered in the opposite case.
+    /// Both reports should be calculated from the same behavior manifest.
+    pub fn diff(&self, previous: &BehaviorCoverageReport) -> CoverageDiff {
+        let current_uncovered = self.uncovered_behaviors.by_dimension();
+        let previous_uncovered = previous.uncovered_behaviors.by_dimension();
+
+        let dimensions = current_uncovered
+            .into_iter()
+            .zip(previous_uncovered)
+            .map(|((name, current), (_, previous_set))| DimensionDiff {
+                name: name.to_string(),
+                previous_coverage: previous.dimension_coverage(name),
+                current_coverage: self.dimension_coverage(name),
+                newly_covered: previous_set.difference(&current).cloned().collect(),
+                newly_uncovered: current.difference(&previous_set).cloned().collect(),
+            })
+            .collect();
+
+        CoverageDiff {
+            previous_coverage: previous.total_coverage,
+            current_coverage: self.total_coverage,
+            dimensions,
+        }
+    }
+
+    /// Coverage (0.0 to 1.0) of the named dimension, 1.0 if not present
+    fn dimension_coverage(&self, name: &str) -> f64 {
+        self.dimensions
+            .iter()
+            .find(|d| d.name == name)
+            .map(|d| d.coverage)
+            .unwrap_or(1.0)
+    }
+}
+
+/// Change in behavior coverage between two reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoverageDiff {
+    /// Overall coverage of the previous report (0.0 to 100.0)
+    pub previous_coverage: f64,
+    /// Overall coverage of the current report (0.0 to 100.0)
+    pub current_coverage: f64,
+    /// Changes per dimension
+    pub dimensions: Vec<DimensionDiff>,
+}
+
+impl CoverageDiff {
+    /// Change in overall coverage, in percentage points
+    pub fn coverage_delta(&self) -> f64 {
+        self.current_coverage - self.previous_coverage
+    }
+
+    /// Whether overall coverage dropped by more than `max_drop` percentage points
+    ///
+    /// Differences below floating point noise are ignored so identical
+    /// coverage never counts as a drop.
+    pub fn exceeds_drop(&self, max_drop: f64) -> bool {
+        -self.coverage_delta() > max_drop + 1e-9
+    }
+
+    /// Format as human-readable text
+    pub fn format_text(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "Behavior Coverage Diff\n\
+             ======================\n\n\
+             Overall Coverage: {:.1}% -> {:.1}% ({:+.1} points)\n",
+            self.previous_coverage,
+            self.current_coverage,
+            self.coverage_delta()
+        ));
+
+        for dim in &self.dimensions {
+            if dim.newly_covered.is_empty() && dim.newly_uncovered.is_empty() {
+                continue;
+            }
+
+            output.push_str(&format!(
+                "\n{}: {:.1}% -> {:.1}%\n",
+                dim.name,
+                dim.previous_coverage * 100.0,
+                dim.current_coverage * 100.0
+            ));
+            for behavior in &dim.newly_covered {
+                output.push_str(&format!("  + {}\n", behavior));
+            }
+            for behavior in &dim.newly_uncovered {
+                output.push_str(&format!("  - {}\n", behavior));
+            }
+        }
+
+        output
+    }
+}
+
+/// Change in coverage for a single dimension
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DimensionDiff {
+    /// Dimension name
+    pub name: String,
+    /// Previous coverage (0.0 to 1.0)
+    pub previous_coverage: f64,
+    /// Current coverage (0.0 to 1.0)
+    pub current_coverage: f64,
+    /// Behaviors uncovered previously and covered now
+    pub newly_covered: Vec<String>,
+    /// Behaviors covered previously and uncovered now
+    pub newly_uncovered: Vec<String>,
 }
 
 /// Coverage for a single dimension
@@ -317,6 +525,38 @@ impl UncoveredBehaviors {
             + self.missing_spans.len()
     }
 
+    /// Uncovered behavior names grouped by dimension
+    ///
+    /// Dimension names match those in [`BehaviorCoverageReport::dimensions`].
+    pub fn by_dimension(&self) -> Vec<(&'static str, BTreeSet<String>)> {
+        let integrations = self
+            .integrations
+            .iter()
+            .flat_map(|(service, ops)| ops.iter().map(move |op| format!("{}.{}", service, op)))
+            .collect();
+
+        vec![
+            ("API Surface", self.api_endpoints.iter().cloned().collect()),
+            (
+                "State Transitions",
+                self.state_transitions
+                    .iter()
+                    .map(|t| t.describe())
+                    .collect(),
+            ),
+            (
+                "Error Scenarios",
+                self.error_scenarios.iter().cloned().collect(),
+            ),
+            ("Data Flows", self.data_flows.iter().cloned().collect()),
+            ("Integrations", integrations),
+            (
+                "Span Coverage",
+                self.missing_spans.iter().cloned().collect(),
+            ),
+        ]
+    }
+
     /// Get top priority uncovered behaviors
     pub fn top_priority(&self, limit: usize) -> Vec<UncoveredBehavior> {
         let mut behaviors = Vec::new();
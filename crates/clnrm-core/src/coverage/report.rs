@@ -149,6 +149,10 @@ impl ReportGenerator {
     }
 
     /// Generate Markdown report
+    ///
+    /// Emits GitHub-flavored Markdown suitable for PR comments: the grade at
+    /// the top, a dimension table with the same columns as the text report,
+    /// and the top uncovered behaviors in a collapsible `<details>` block.
     fn generate_markdown(report: &BehaviorCoverageReport) -> Result<String> {
         let mut md = String::new();
 
@@ -167,37 +171,37 @@ impl ReportGenerator {
 
         // Dimensions table
         md.push_str("## Dimension Breakdown\n\n");
-        md.push_str("| Dimension | Coverage | Weight | Score | Covered/Total |\n");
-        md.push_str("|-----------|----------|--------|-------|---------------|\n");
+        md.push_str("| Dimension | Coverage | Weight | Score |\n");
+        md.push_str("|-----------|---------:|-------:|------:|\n");
         for dim in &report.dimensions {
             md.push_str(&format!(
-                "| {} | {:.1}% | {:.0}% | {:.2}% | {}/{} |\n",
-                dim.name,
+                "| {} | {:.1}% | {:.0}% | {:.2}% |\n",
+                escape_markdown_cell(&dim.name),
                 dim.coverage * 100.0,
                 dim.weight * 100.0,
-                dim.weighted_score * 100.0,
-                dim.covered,
-                dim.total
+                dim.weighted_score * 100.0
             ));
         }
         md.push('\n');
 
         // Uncovered behaviors
         if !report.uncovered_behaviors.is_empty() {
-            md.push_str("## Uncovered Behaviors\n\n");
-            for (i, behavior) in report
-                .uncovered_behaviors
-                .top_priority(10)
-                .iter()
-                .enumerate()
-            {
+            let top = report.uncovered_behaviors.top_priority(10);
+            md.push_str("<details>\n");
+            md.push_str(&format!(
+                "<summary>Top {} of {} uncovered behaviors</summary>\n\n",
+                top.len(),
+                report.uncovered_behaviors.count()
+            ));
+            for (i, behavior) in top.iter().enumerate() {
                 md.push_str(&format!(
-                    "{}. **{}**: {}\n",
+                    "{}. **{}**: `{}`\n",
                     i + 1,
                     behavior.dimension,
-                    behavior.name
+                    behavior.name.replace('`', "'")
                 ));
             }
+            md.push_str("\n</details>\n");
         }
 
         Ok(md)
@@ -219,3 +223,8 @@ impl ReportGenerator {
         })
     }
 }
+
+/// Escape characters that would break a Markdown table cell
+fn escape_markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
@@ -1,6 +1,10 @@
 //! Coverage tracker for CleanroomEnvironment integration
 
-use crate::coverage::BehaviorCoverage;
+use crate::coverage::manifest::BehaviorManifest;
+use crate::coverage::{BehaviorCoverage, BehaviorCoverageReport, DimensionWeights};
+use crate::error::{CleanroomError, Result};
+use crate::validation::span_validator::SpanData;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
@@ -8,6 +12,7 @@ use tokio::sync::RwLock;
 #[derive(Debug, Clone)]
 pub struct CoverageTracker {
     coverage: Arc<RwLock<BehaviorCoverage>>,
+    weights: DimensionWeights,
 }
 
 impl CoverageTracker {
@@ -15,14 +20,79 @@ impl CoverageTracker {
     pub fn new() -> Self {
         Self {
             coverage: Arc::new(RwLock::new(BehaviorCoverage::new())),
+            weights: DimensionWeights::default(),
         }
     }
 
+    /// Use custom dimension weights when producing reports
+    ///
+    /// Typically the `[coverage.weights]` section of `cleanroom.toml`.
+    pub fn with_weights(mut self, weights: DimensionWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Dimension weights used when producing reports
+    pub fn weights(&self) -> DimensionWeights {
+        self.weights
+    }
+
+    /// Produce a coverage report for the current coverage against `manifest`
+    ///
+    /// Weights declared in the manifest take precedence over the tracker's.
+    pub async fn report(&self, manifest: &BehaviorManifest) -> Result<BehaviorCoverageReport> {
+        let weights = if manifest.weights.is_some() {
+            manifest.get_weights()?
+        } else {
+            self.weights
+        };
+        manifest.calculate_coverage_with_weights(&self.snapshot().await, weights)
+    }
+
     /// Get a snapshot of current coverage
     pub async fn snapshot(&self) -> BehaviorCoverage {
         self.coverage.read().await.clone()
     }
 
+    /// Save a snapshot of current coverage to a JSON file
+    pub async fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<()> {
+        let snapshot = self.snapshot().await;
+        let content = serde_json::to_string_pretty(&snapshot).map_err(|e| {
+            CleanroomError::serialization_error(format!(
+                "Failed to serialize coverage snapshot: {}",
+                e
+            ))
+        })?;
+
+        tokio::fs::write(path.as_ref(), content).await.map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to write coverage snapshot {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })
+    }
+
+    /// Create a tracker from a JSON snapshot saved by [`Self::save_snapshot`]
+    pub fn load_snapshot(path: impl AsRef<Path>) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref()).map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to read coverage snapshot {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+
+        let coverage: BehaviorCoverage = serde_json::from_str(&content).map_err(|e| {
+            CleanroomError::serialization_error(format!("Failed to parse coverage snapshot: {}", e))
+        })?;
+
+        Ok(Self {
+            coverage: Arc::new(RwLock::new(coverage)),
+            weights: DimensionWeights::default(),
+        })
+    }
+
     /// Record API endpoint coverage
     pub async fn record_api(&self, endpoint: String) {
         self.coverage.write().await.record_api_endpoint(endpoint);
@@ -61,6 +131,11 @@ impl CoverageTracker {
         self.coverage.write().await.record_span(span_name);
     }
 
+    /// Record span names and the integrations the spans exercise
+    pub async fn record_spans(&self, spans: &[SpanData]) {
+        self.coverage.write().await.record_spans(spans);
+    }
+
     /// Merge another coverage tracker
     pub async fn merge(&self, other: &CoverageTracker) {
         let other_coverage = other.snapshot().await;
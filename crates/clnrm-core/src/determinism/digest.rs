@@ -1,8 +1,13 @@
 //! Digest generation for trace verification
 //!
-//! Provides SHA-256 digest generation for trace verification.
+//! Provides SHA-256 digest generation for trace verification, including
+//! digests of traces normalized to their reproducible fields.
 
+use crate::config::DeterminismConfig;
+use crate::error::{CleanroomError, Result};
+use crate::validation::span_validator::SpanData;
 use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
 /// Generate SHA-256 digest from byte data
 ///
@@ -30,3 +35,239 @@ pub fn verify_digest(data: &[u8], expected_digest: &str) -> bool {
     let actual_digest = generate_digest(data);
     actual_digest == expected_digest
 }
+
+/// First line after the digest in a normalized trace baseline
+const NORMALIZED_HEADER: &str = "# clnrm normalized trace v1";
+
+/// Resource attributes that differ between otherwise identical runs
+const VOLATILE_RESOURCE_KEYS: &[&str] = &[
+    "service.instance.id",
+    "process.pid",
+    "process.parent_pid",
+    "host.name",
+    "host.id",
+];
+
+/// A trace reduced to the fields that should be reproducible across runs
+///
+/// Trace and span IDs are random per run, so spans are keyed by the path of
+/// span names from their root (e.g. `clnrm.run > clnrm.test[0]`) instead.
+/// Timestamps are kept only when the determinism config freezes the clock,
+/// and volatile resource attributes such as `process.pid` are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedTrace {
+    fields: BTreeMap<String, String>,
+}
+
+impl NormalizedTrace {
+    /// Normalize `spans` according to `config`
+    pub fn from_spans(spans: &[SpanData], config: Option<&DeterminismConfig>) -> Self {
+        let keep_timestamps = config.is_some_and(|c| c.freeze_clock.is_some());
+
+        let by_id: HashMap<&str, &SpanData> =
+            spans.iter().map(|s| (s.span_id.as_str(), s)).collect();
+
+        let mut keyed: Vec<(String, BTreeMap<String, String>)> = spans
+            .iter()
+            .map(|span| (span_path(span, &by_id), span_fields(span, keep_timestamps)))
+            .collect();
+        keyed.sort();
+
+        let mut fields = BTreeMap::new();
+        let mut occurrences: HashMap<String, usize> = HashMap::new();
+        for (path, span_fields) in keyed {
+            let index = occurrences.entry(path.clone()).or_insert(0);
+            let key = format!("{}[{}]", path, index);
+            *index += 1;
+
+            fields.insert(format!("{}.present", key), "true".to_string());
+            for (field, value) in span_fields {
+                fields.insert(format!("{}.{}", key, field), value);
+            }
+        }
+
+        Self { fields }
+    }
+
+    /// Normalized fields, keyed by `<span path>[<n>].<field>`
+    pub fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// Hex-encoded SHA-256 digest of the normalized fields
+    pub fn digest(&self) -> String {
+        generate_digest(self.canonical_lines().as_bytes())
+    }
+
+    /// Baseline file content: the digest followed by the normalized fields
+    pub fn to_baseline(&self) -> String {
+        format!(
+            "{}\n{}\n{}",
+            self.digest(),
+            NORMALIZED_HEADER,
+            self.canonical_lines()
+        )
+    }
+
+    /// Fields that differ from `baseline`, in key order
+    pub fn diff(&self, baseline: &BTreeMap<String, String>) -> Vec<FieldDiff> {
+        let keys: BTreeSet<&String> = baseline.keys().chain(self.fields.keys()).collect();
+
+        keys.into_iter()
+            .filter_map(|key| match (baseline.get(key), self.fields.get(key)) {
+                (Some(expected), Some(actual)) if expected != actual => Some(FieldDiff::Changed {
+                    key: key.clone(),
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                }),
+                (Some(expected), None) => Some(FieldDiff::Missing {
+                    key: key.clone(),
+                    expected: expected.clone(),
+                }),
+                (None, Some(actual)) => Some(FieldDiff::Unexpected {
+                    key: key.clone(),
+                    actual: actual.clone(),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn canonical_lines(&self) -> String {
+        self.fields
+            .iter()
+            .map(|(key, value)| format!("{} = {}\n", key, value))
+            .collect()
+    }
+}
+
+/// A normalized field that differs between a baseline and a fresh trace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldDiff {
+    /// Field present in both with different values
+    Changed {
+        key: String,
+        expected: String,
+        actual: String,
+    },
+    /// Field only in the baseline
+    Missing { key: String, expected: String },
+    /// Field only in the fresh trace
+    Unexpected { key: String, actual: String },
+}
+
+impl std::fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldDiff::Changed {
+                key,
+                expected,
+                actual,
+            } => write!(f, "~ {}: {} -> {}", key, expected, actual),
+            FieldDiff::Missing { key, expected } => write!(f, "- {} = {}", key, expected),
+            FieldDiff::Unexpected { key, actual } => write!(f, "+ {} = {}", key, actual),
+        }
+    }
+}
+
+/// A recorded digest, with normalized fields if the baseline includes them
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestBaseline {
+    /// Hex-encoded SHA-256 digest
+    pub digest: String,
+    /// Normalized fields, absent for digest-only files such as `digest.txt`
+    pub fields: Option<BTreeMap<String, String>>,
+}
+
+impl DigestBaseline {
+    /// Parse a baseline written by [`NormalizedTrace::to_baseline`] or a
+    /// file containing only a digest
+    ///
+    /// # Errors
+    /// * The file is empty
+    /// * A field line is not `key = value`
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut lines = content.lines();
+        let digest = lines
+            .next()
+            .map(|line| line.trim().trim_start_matches("sha256:").to_string())
+            .filter(|digest| !digest.is_empty())
+            .ok_or_else(|| CleanroomError::validation_error("Digest baseline is empty"))?;
+
+        let rest: Vec<&str> = lines.filter(|line| !line.trim().is_empty()).collect();
+        if rest.first() != Some(&NORMALIZED_HEADER) {
+            return Ok(Self {
+                digest,
+                fields: None,
+            });
+        }
+
+        let mut fields = BTreeMap::new();
+        for line in &rest[1..] {
+            let (key, value) = line.split_once(" = ").ok_or_else(|| {
+                CleanroomError::validation_error(format!(
+                    "Invalid digest baseline line (expected 'key = value'): {}",
+                    line
+                ))
+            })?;
+            fields.insert(key.to_string(), value.to_string());
+        }
+
+        Ok(Self {
+            digest,
+            fields: Some(fields),
+        })
+    }
+}
+
+/// Names from the root span down to `span`, joined with ` > `
+fn span_path(span: &SpanData, by_id: &HashMap<&str, &SpanData>) -> String {
+    let mut names = vec![span.name.as_str()];
+    let mut current = span;
+    // Bounded by the span count so a malformed parent cycle cannot loop forever
+    for _ in 0..by_id.len() {
+        match current
+            .parent_span_id
+            .as_deref()
+            .and_then(|id| by_id.get(id))
+        {
+            Some(parent) => {
+                names.push(parent.name.as_str());
+                current = parent;
+            }
+            None => break,
+        }
+    }
+    names.reverse();
+    names.join(" > ")
+}
+
+/// Reproducible fields of a single span
+fn span_fields(span: &SpanData, keep_timestamps: bool) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+
+    if let Some(kind) = &span.kind {
+        fields.insert("kind".to_string(), format!("{:?}", kind).to_lowercase());
+    }
+    for (key, value) in &span.attributes {
+        fields.insert(format!("attr.{}", key), value.to_string());
+    }
+    for (key, value) in &span.resource_attributes {
+        if !VOLATILE_RESOURCE_KEYS.contains(&key.as_str()) {
+            fields.insert(format!("resource.{}", key), value.to_string());
+        }
+    }
+    if span.events.is_some() {
+        fields.insert("events".to_string(), span.event_names().join(", "));
+    }
+    if keep_timestamps {
+        if let Some(start) = span.start_time_unix_nano {
+            fields.insert("start_time_unix_nano".to_string(), start.to_string());
+        }
+        if let Some(end) = span.end_time_unix_nano {
+            fields.insert("end_time_unix_nano".to_string(), end.to_string());
+        }
+    }
+
+    fields
+}
@@ -204,6 +204,22 @@ impl DeterminismEngine {
     }
 }
 
+// Lets templates render with the engine's frozen clock and seeded RNG stream
+impl clnrm_template::functions::TimestampProvider for DeterminismEngine {
+    fn get_timestamp_rfc3339(&self) -> String {
+        DeterminismEngine::get_timestamp_rfc3339(self)
+    }
+
+    fn seed(&self) -> Option<u64> {
+        self.get_seed()
+    }
+
+    fn next_seed(&self) -> Option<u64> {
+        self.rng.as_ref()?;
+        self.next_u64().ok()
+    }
+}
+
 // Implement Clone for DeterminismEngine
 // Note: RNG state is not cloned; instead, each clone gets a fresh RNG with the same seed
 impl Clone for DeterminismEngine {
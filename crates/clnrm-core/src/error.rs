@@ -26,6 +26,9 @@ pub struct CleanroomError {
     pub source: Option<String>,
     /// Timestamp when error occurred
     pub timestamp: chrono::DateTime<chrono::Utc>,
+    /// Stable code users can look up with `clnrm explain`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<ErrorCode>,
 }
 
 /// Error kinds for different failure scenarios
@@ -69,6 +72,107 @@ pub enum ErrorKind {
     TemplateError,
 }
 
+/// Stable, documented error codes
+///
+/// `CLNRM-E*` codes cover loading test files, `CLNRM-V*` codes cover trace
+/// validation failures. Codes never change meaning once released; run
+/// `clnrm explain <code>` for a description and remediation hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// Test file is not valid TOML or does not match the schema
+    TomlParse,
+    /// Test file is not valid YAML or does not match the schema
+    YamlParse,
+    /// Tera template failed to render
+    TemplateRender,
+    /// Parsed test configuration is semantically invalid
+    InvalidConfig,
+    /// An expected span is missing from the trace
+    SpanNotFound,
+    /// Span, event or error count outside the expected bounds
+    SpanCountMismatch,
+    /// Spans did not occur in the expected order
+    SpanOrder,
+    /// A child span is not contained in its outer span's time window
+    SpanWindow,
+    /// A span attribute has an unexpected value
+    SpanAttribute,
+    /// A span has an unexpected status
+    SpanStatus,
+    /// A span's duration is outside the expected bounds
+    SpanDuration,
+    /// A span did not emit an expected event
+    SpanEvent,
+    /// Parent/child relationships do not match the expected graph
+    SpanGraph,
+    /// Spans show the test was not hermetic
+    Hermeticity,
+    /// An expected metric was not exported
+    MetricNotFound,
+    /// A metric's value or histogram sample count is outside the expected bounds
+    MetricValue,
+}
+
+impl ErrorCode {
+    /// Every error code, in catalog order
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::TomlParse,
+        ErrorCode::YamlParse,
+        ErrorCode::TemplateRender,
+        ErrorCode::InvalidConfig,
+        ErrorCode::SpanNotFound,
+        ErrorCode::SpanCountMismatch,
+        ErrorCode::SpanOrder,
+        ErrorCode::SpanWindow,
+        ErrorCode::SpanAttribute,
+        ErrorCode::SpanStatus,
+        ErrorCode::SpanDuration,
+        ErrorCode::SpanEvent,
+        ErrorCode::SpanGraph,
+        ErrorCode::Hermeticity,
+        ErrorCode::MetricNotFound,
+        ErrorCode::MetricValue,
+    ];
+
+    /// The code as printed, e.g. `CLNRM-V010`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::TomlParse => "CLNRM-E001",
+            ErrorCode::YamlParse => "CLNRM-E002",
+            ErrorCode::TemplateRender => "CLNRM-E003",
+            ErrorCode::InvalidConfig => "CLNRM-E004",
+            ErrorCode::SpanNotFound => "CLNRM-V001",
+            ErrorCode::SpanCountMismatch => "CLNRM-V010",
+            ErrorCode::SpanOrder => "CLNRM-V020",
+            ErrorCode::SpanWindow => "CLNRM-V021",
+            ErrorCode::SpanAttribute => "CLNRM-V030",
+            ErrorCode::SpanStatus => "CLNRM-V031",
+            ErrorCode::SpanDuration => "CLNRM-V032",
+            ErrorCode::SpanEvent => "CLNRM-V033",
+            ErrorCode::SpanGraph => "CLNRM-V040",
+            ErrorCode::Hermeticity => "CLNRM-V050",
+            ErrorCode::MetricNotFound => "CLNRM-V060",
+            ErrorCode::MetricValue => "CLNRM-V061",
+        }
+    }
+
+    /// Look up a code, case-insensitively and with or without the `CLNRM-` prefix
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim().to_ascii_uppercase();
+        let code = code.strip_prefix("CLNRM-").unwrap_or(&code);
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|known| known.as_str().trim_start_matches("CLNRM-") == code)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl CleanroomError {
     /// Create a new cleanroom error
     pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
@@ -78,9 +182,16 @@ impl CleanroomError {
             context: None,
             source: None,
             timestamp: chrono::Utc::now(),
+            code: None,
         }
     }
 
+    /// Attach a stable error code, shown in the error message
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = Some(code);
+        self
+    }
+
     /// Create a new cleanroom error with context
     pub fn with_context(mut self, context: impl Into<String>) -> Self {
         self.context = Some(context.into());
@@ -201,6 +312,9 @@ impl CleanroomError {
 
 impl fmt::Display for CleanroomError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(code) = &self.code {
+            write!(f, "[{}] ", code)?;
+        }
         write!(f, "{:?}: {}", self.kind, self.message)?;
         if let Some(context) = &self.context {
             write!(f, " (Context: {})", context)?;
@@ -233,6 +347,7 @@ impl From<clnrm_template::TemplateError> for CleanroomError {
             clnrm_template::TemplateError::ConfigError(msg) => CleanroomError::config_error(msg),
             clnrm_template::TemplateError::IoError(msg) => CleanroomError::io_error(msg),
             clnrm_template::TemplateError::ValidationError(msg) => CleanroomError::validation_error(msg),
+            other => CleanroomError::internal_error(other.to_string()),
         }
     }
 }
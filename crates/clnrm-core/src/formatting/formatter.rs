@@ -17,6 +17,8 @@ pub enum FormatterType {
     Junit,
     /// Test Anything Protocol (TAP) format
     Tap,
+    /// TAP version 14 with steps nested as subtests
+    Tap14,
 }
 
 impl FormatterType {
@@ -27,6 +29,7 @@ impl FormatterType {
             "json" | "j" => Some(Self::Json),
             "junit" | "xml" => Some(Self::Junit),
             "tap" | "t" => Some(Self::Tap),
+            "tap14" => Some(Self::Tap14),
             _ => None,
         }
     }
@@ -37,7 +40,7 @@ impl FormatterType {
             Self::Human => "txt",
             Self::Json => "json",
             Self::Junit => "xml",
-            Self::Tap => "tap",
+            Self::Tap | Self::Tap14 => "tap",
         }
     }
 
@@ -48,6 +51,7 @@ impl FormatterType {
             Self::Json => "json",
             Self::Junit => "junit",
             Self::Tap => "tap",
+            Self::Tap14 => "tap14",
         }
     }
 }
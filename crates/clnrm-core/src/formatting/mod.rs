@@ -6,6 +6,7 @@
 
 // TOML formatting submodule
 pub mod toml_fmt;
+pub mod toml_scan;
 
 // Test output formatting submodules
 pub mod formatter;
@@ -18,7 +19,11 @@ pub mod test_result;
 use crate::error::Result;
 
 // Re-export TOML formatting functions for backward compatibility
-pub use toml_fmt::{format_toml_content, format_toml_file, needs_formatting, verify_idempotency};
+pub use toml_fmt::{
+    content_needs_formatting, format_toml_content, format_toml_file, needs_formatting,
+    verify_idempotency,
+};
+pub use toml_scan::is_canonical;
 
 // Re-export test output formatting
 pub use formatter::{Formatter, FormatterType};
@@ -45,6 +50,7 @@ pub fn format_test_results(formatter_type: FormatterType, suite: &TestSuite) ->
         FormatterType::Json => Box::new(JsonFormatter::new()),
         FormatterType::Junit => Box::new(JunitFormatter::new()),
         FormatterType::Tap => Box::new(TapFormatter::new()),
+        FormatterType::Tap14 => Box::new(TapFormatter::tap14()),
     };
 
     formatter.format(suite)
@@ -1,25 +1,41 @@
 //! TAP (Test Anything Protocol) Formatter
 //!
-//! Generates TAP version 13 compatible output.
+//! Generates TAP version 13 compatible output, or TAP version 14 with each
+//! result's steps nested as a subtest block.
 //! Widely used in Perl and other testing ecosystems.
 
 use crate::error::Result;
 use crate::formatting::formatter::{Formatter, FormatterType};
-use crate::formatting::test_result::{TestStatus, TestSuite};
+use crate::formatting::test_result::{TestResult, TestStatus, TestSuite};
+
+/// Indentation for subtest blocks (TAP 14)
+const SUBTEST_INDENT: &str = "    ";
 
 /// TAP formatter for test results
 #[derive(Debug, Default)]
-pub struct TapFormatter;
+pub struct TapFormatter {
+    /// Emit TAP version 14 with steps nested as subtests
+    subtests: bool,
+}
 
 impl TapFormatter {
-    /// Create a new TAP formatter
+    /// Create a new TAP version 13 formatter
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    /// Create a TAP version 14 formatter that nests steps as subtests
+    pub fn tap14() -> Self {
+        Self { subtests: true }
     }
 
     /// Generate TAP version header
-    fn generate_header() -> String {
-        "TAP version 13".to_string()
+    fn generate_header(&self) -> String {
+        if self.subtests {
+            "TAP version 14".to_string()
+        } else {
+            "TAP version 13".to_string()
+        }
     }
 
     /// Generate TAP plan line
@@ -28,10 +44,7 @@ impl TapFormatter {
     }
 
     /// Generate TAP test line
-    fn generate_test_line(
-        index: usize,
-        result: &crate::formatting::test_result::TestResult,
-    ) -> Vec<String> {
+    fn generate_test_line(index: usize, result: &TestResult) -> Vec<String> {
         let mut output = Vec::new();
 
         let status = match result.status {
@@ -82,6 +95,53 @@ impl TapFormatter {
         output
     }
 
+    /// Generate a TAP 14 subtest block followed by its parent test line
+    ///
+    /// The parent fails if the result itself failed or any step failed. A
+    /// result with no steps gets a plain test line carrying its own status.
+    fn generate_subtest(index: usize, result: &TestResult) -> Vec<String> {
+        if result.steps.is_empty() {
+            return Self::generate_test_line(index, result);
+        }
+
+        let mut output = vec![format!("# Subtest: {}", result.name)];
+        output.push(format!(
+            "{}{}",
+            SUBTEST_INDENT,
+            Self::generate_plan(result.steps.len())
+        ));
+        for (step_index, step) in result.steps.iter().enumerate() {
+            for line in Self::generate_test_line(step_index + 1, step) {
+                output.push(format!("{}{}", SUBTEST_INDENT, line));
+            }
+        }
+
+        let passed = result.steps.iter().filter(|s| s.is_passed()).count();
+        let failed = result.steps.iter().filter(|s| s.is_failed()).count();
+        let skipped = result.steps.iter().filter(|s| s.is_skipped()).count();
+        output.push(format!(
+            "{}# steps {}, passed {}, failed {}, skipped {}",
+            SUBTEST_INDENT,
+            result.steps.len(),
+            passed,
+            failed,
+            skipped
+        ));
+
+        let mut rolled_up = result.clone();
+        if failed > 0 && !rolled_up.is_failed() {
+            rolled_up.status = TestStatus::Failed;
+            rolled_up.error = Some(format!(
+                "{} of {} step(s) failed",
+                failed,
+                result.steps.len()
+            ));
+        }
+        output.extend(Self::generate_test_line(index, &rolled_up));
+
+        output
+    }
+
     /// Escape YAML string for TAP diagnostics
     fn escape_yaml_string(s: &str) -> String {
         // Simple escaping for YAML values in TAP
@@ -98,14 +158,18 @@ impl Formatter for TapFormatter {
         let mut output = Vec::new();
 
         // TAP version header
-        output.push(Self::generate_header());
+        output.push(self.generate_header());
 
         // TAP plan
         output.push(Self::generate_plan(suite.total_count()));
 
         // Test lines
         for (index, result) in suite.results.iter().enumerate() {
-            let test_lines = Self::generate_test_line(index + 1, result);
+            let test_lines = if self.subtests {
+                Self::generate_subtest(index + 1, result)
+            } else {
+                Self::generate_test_line(index + 1, result)
+            };
             output.extend(test_lines);
         }
 
@@ -126,10 +190,18 @@ impl Formatter for TapFormatter {
     }
 
     fn name(&self) -> &'static str {
-        "tap"
+        if self.subtests {
+            "tap14"
+        } else {
+            "tap"
+        }
     }
 
     fn formatter_type(&self) -> FormatterType {
-        FormatterType::Tap
+        if self.subtests {
+            FormatterType::Tap14
+        } else {
+            FormatterType::Tap
+        }
     }
 }
@@ -35,6 +35,8 @@ pub struct TestResult {
     pub stderr: Option<String>,
     /// Test metadata
     pub metadata: std::collections::HashMap<String, String>,
+    /// Nested step results (e.g. the steps of a scenario)
+    pub steps: Vec<TestResult>,
 }
 
 impl TestResult {
@@ -48,6 +50,7 @@ impl TestResult {
             stdout: None,
             stderr: None,
             metadata: std::collections::HashMap::new(),
+            steps: Vec::new(),
         }
     }
 
@@ -61,6 +64,7 @@ impl TestResult {
             stdout: None,
             stderr: None,
             metadata: std::collections::HashMap::new(),
+            steps: Vec::new(),
         }
     }
 
@@ -74,6 +78,7 @@ impl TestResult {
             stdout: None,
             stderr: None,
             metadata: std::collections::HashMap::new(),
+            steps: Vec::new(),
         }
     }
 
@@ -101,6 +106,12 @@ impl TestResult {
         self
     }
 
+    /// Add a nested step result
+    pub fn with_step(mut self, step: TestResult) -> Self {
+        self.steps.push(step);
+        self
+    }
+
     /// Check if test passed
     pub fn is_passed(&self) -> bool {
         self.status == TestStatus::Passed
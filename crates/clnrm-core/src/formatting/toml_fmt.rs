@@ -1,15 +1,15 @@
 //! TOML formatting module for Cleanroom v0.7.0
 //!
 //! Provides deterministic TOML formatting with:
-//! - Alphabetically sorted keys within sections
-//! - Comment preservation using toml_edit
-//! - Consistent indentation and spacing
+//! - Comment, blank-line and key order preservation using toml_edit
+//! - Consistent spacing around `=` and before end-of-line comments
+//! - No trailing whitespace and a single final newline
 //! - Idempotent formatting (fmt(fmt(x)) == fmt(x))
 
+use super::toml_scan::is_canonical;
 use crate::error::{CleanroomError, Result};
-use std::collections::BTreeMap;
 use std::path::Path;
-use toml_edit::{DocumentMut, Item};
+use toml_edit::{Decor, DocumentMut, Item, RawString, Table};
 
 /// Format a TOML file with deterministic rules
 pub fn format_toml_file(path: &Path) -> Result<String> {
@@ -21,118 +21,121 @@ pub fn format_toml_file(path: &Path) -> Result<String> {
 }
 
 /// Format TOML content string
+///
+/// Edits the parsed document in place, so keys, tables and
+/// array-of-tables entries keep their order and comments and blank lines
+/// stay where they were.
 pub fn format_toml_content(content: &str) -> Result<String> {
-    let mut doc = content
+    Ok(format_document(parse_document(content)?))
+}
+
+fn parse_document(content: &str) -> Result<DocumentMut> {
+    content
         .parse::<DocumentMut>()
-        .map_err(|e| CleanroomError::serialization_error(format!("Failed to parse TOML: {}", e)))?;
+        .map_err(|e| CleanroomError::serialization_error(format!("Failed to parse TOML: {}", e)))
+}
 
-    // Sort all tables recursively
-    sort_document(&mut doc)?;
+fn format_document(mut doc: DocumentMut) -> String {
+    format_table(doc.as_table_mut());
 
-    // Convert to string with proper formatting
-    let formatted = doc.to_string();
+    let trailing = trim_line_ends(doc.trailing().as_str().unwrap_or_default());
+    doc.set_trailing(trailing);
 
-    // Apply additional formatting rules
-    apply_formatting_rules(&formatted)
-}
+    // End the file with exactly one newline
+    let mut formatted = doc.to_string();
+    formatted.truncate(formatted.trim_end().len());
+    formatted.push('\n');
 
-/// Sort all tables in the document recursively
-fn sort_document(doc: &mut DocumentMut) -> Result<()> {
-    // Sort root table
-    let root = doc.as_table_mut();
-    sort_table_recursive(root)?;
-
-    Ok(())
+    formatted
 }
 
-/// Sort a table and all nested tables recursively
-fn sort_table_recursive(table: &mut toml_edit::Table) -> Result<()> {
-    // Collect all keys and values
-    let mut entries: Vec<(String, Item)> = table
-        .iter()
-        .map(|(k, v)| (k.to_string(), v.clone()))
-        .collect();
-
-    // Sort entries by key
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
-
-    // Clear the table
-    let keys: Vec<String> = table.iter().map(|(k, _)| k.to_string()).collect();
-    for key in keys {
-        table.remove(&key);
-    }
-
-    // Re-insert in sorted order and recurse into nested tables
-    for (key, mut value) in entries {
-        // If value is a table, sort it recursively
-        if let Some(nested_table) = value.as_table_mut() {
-            sort_table_recursive(nested_table)?;
-        } else if let Some(inline_table) = value.as_inline_table_mut() {
-            sort_inline_table(inline_table)?;
-        } else if let Some(array) = value.as_array_mut() {
-            // Sort inline tables within arrays
-            for item in array.iter_mut() {
-                if let Some(inline_table) = item.as_inline_table_mut() {
-                    sort_inline_table(inline_table)?;
+/// Normalize whitespace around a table's header, keys and values, recursing
+/// into nested tables
+fn format_table(table: &mut Table) {
+    format_header_decor(table.decor_mut());
+
+    for (mut key, item) in table.iter_mut() {
+        match item {
+            Item::Value(value) => {
+                // `key = value  # comment`
+                let decor = key.leaf_decor_mut();
+                trim_prefix(decor);
+                decor.set_suffix(" ");
+                format_value_decor(value.decor_mut());
+            }
+            Item::Table(nested) => {
+                // `[ table ]` -> `[table]`; dotted keys keep their spelling
+                if !nested.is_dotted() {
+                    key.leaf_decor_mut().clear();
+                }
+                format_table(nested);
+            }
+            Item::ArrayOfTables(tables) => {
+                for nested in tables.iter_mut() {
+                    format_table(nested);
                 }
             }
+            Item::None => {}
         }
-
-        table.insert(&key, value);
     }
-
-    Ok(())
 }
 
-/// Sort an inline table
-fn sort_inline_table(table: &mut toml_edit::InlineTable) -> Result<()> {
-    let mut entries: BTreeMap<String, toml_edit::Value> = BTreeMap::new();
-
-    // Collect entries
-    for (key, value) in table.iter() {
-        entries.insert(key.to_string(), value.clone());
+/// Normalize the lines before a table header and its end-of-line comment
+fn format_header_decor(decor: &mut Decor) {
+    trim_prefix(decor);
+    if let Some(suffix) = decor
+        .suffix()
+        .and_then(RawString::as_str)
+        .map(trailing_comment)
+    {
+        decor.set_suffix(suffix);
     }
+}
 
-    // Clear and re-insert in sorted order
-    table.clear();
-    for (key, value) in entries {
-        table.insert(&key, value);
+/// Strip trailing whitespace from the comment and blank lines before a
+/// key or header
+fn trim_prefix(decor: &mut Decor) {
+    if let Some(prefix) = decor
+        .prefix()
+        .and_then(RawString::as_str)
+        .map(trim_line_ends)
+    {
+        decor.set_prefix(prefix);
     }
-
-    Ok(())
 }
 
-/// Apply additional formatting rules
-fn apply_formatting_rules(content: &str) -> Result<String> {
-    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+/// One space after `=`, and before an end-of-line comment if there is one
+fn format_value_decor(decor: &mut Decor) {
+    let suffix = decor
+        .suffix()
+        .and_then(RawString::as_str)
+        .map(trailing_comment)
+        .unwrap_or_default();
+    decor.set_prefix(" ");
+    decor.set_suffix(suffix);
+}
 
-    // Remove trailing whitespace from all lines
-    for line in &mut lines {
-        *line = line.trim_end().to_string();
+/// Normalize the text between a value or header and the end of its line
+fn trailing_comment(suffix: &str) -> String {
+    let comment = suffix.trim();
+    if comment.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", comment)
     }
+}
 
-    // Ensure spacing around = in key-value pairs
-    for line in &mut lines {
-        if line.contains('=') && !line.trim_start().starts_with('#') {
-            // Split on = and rejoin with proper spacing
-            let parts: Vec<&str> = line.splitn(2, '=').collect();
-            if parts.len() == 2 {
-                let key = parts[0].trim_end();
-                let value = parts[1].trim_start();
-                *line = format!("{} = {}", key, value);
-            }
+/// Strip trailing whitespace from every complete line, keeping the
+/// indentation after the last newline
+fn trim_line_ends(text: &str) -> String {
+    match text.rsplit_once('\n') {
+        Some((lines, indent)) => {
+            let mut trimmed: Vec<&str> = lines.split('\n').map(str::trim_end).collect();
+            trimmed.push(indent);
+            trimmed.join("\n")
         }
+        None => text.to_string(),
     }
-
-    // Join lines back together
-    let mut result = lines.join("\n");
-
-    // Ensure file ends with newline
-    if !result.ends_with('\n') {
-        result.push('\n');
-    }
-
-    Ok(result)
 }
 
 /// Check if a file needs formatting
@@ -141,9 +144,21 @@ pub fn needs_formatting(path: &Path) -> Result<bool> {
         CleanroomError::io_error(format!("Failed to read file {}: {}", path.display(), e))
     })?;
 
-    let formatted = format_toml_content(&original)?;
+    content_needs_formatting(&original)
+}
+
+/// Check if TOML content needs formatting
+///
+/// The content is always parsed, so invalid TOML is an error. Content a
+/// line scan recognises as already formatted then skips re-emitting;
+/// everything else is formatted and compared.
+pub fn content_needs_formatting(content: &str) -> Result<bool> {
+    let doc = parse_document(content)?;
+    if is_canonical(content) {
+        return Ok(false);
+    }
 
-    Ok(original != formatted)
+    Ok(format_document(doc) != content)
 }
 
 /// Verify idempotency: formatting twice should produce same result
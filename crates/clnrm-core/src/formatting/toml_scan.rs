@@ -0,0 +1,221 @@
+//! Line scan recognising TOML that `format_toml_content` would leave as is
+//!
+//! Parsing and re-emitting a document dominates `clnrm fmt --check` on large
+//! directories. Most checked files are already formatted, so a scan over the
+//! raw text that confirms the canonical layout lets the check skip the
+//! formatter entirely.
+//!
+//! The scan is deliberately conservative: it only accepts bare keys,
+//! `key = value` with single spaces, `[table]`/`[[table]]` headers without
+//! inner spaces and end-of-line comments after a single space. Anything else
+//! (quoted or dotted keys, CRLF line endings, trailing whitespace, ...) is
+//! reported as not canonical and left to the formatter to decide.
+
+/// Whether `content` is already in the layout the formatter produces
+///
+/// `true` guarantees formatting would not change valid content; `false`
+/// only means the scan couldn't tell. The content is not parsed, so
+/// callers must parse it to reject invalid TOML in a canonical layout.
+pub fn is_canonical(content: &str) -> bool {
+    if !content.ends_with('\n') || content.ends_with("\n\n") || content.contains('\r') {
+        return false;
+    }
+
+    let mut value = ValueScanner::default();
+    for line in content.lines() {
+        if line.ends_with([' ', '\t']) {
+            return false;
+        }
+
+        // Continuation of a multi-line array or string
+        if value.is_open() {
+            match value.scan(line) {
+                Scan::Done(rest) if is_line_end(rest) => continue,
+                Scan::Continues => continue,
+                _ => return false,
+            }
+        }
+
+        let body = line.trim_start_matches([' ', '\t']);
+        if body.is_empty() || body.starts_with('#') {
+            continue;
+        }
+        if body.starts_with('[') {
+            if !is_canonical_header(body) {
+                return false;
+            }
+            continue;
+        }
+
+        let Some((key, rest)) = body.split_once(" = ") else {
+            return false;
+        };
+        if !is_bare_key(key) {
+            return false;
+        }
+        match value.scan(rest) {
+            Scan::Done(rest) if is_line_end(rest) => {}
+            Scan::Continues => {}
+            _ => return false,
+        }
+    }
+
+    !value.is_open()
+}
+
+/// `[name]` or `[[name]]` with a bare, possibly dotted, name
+fn is_canonical_header(body: &str) -> bool {
+    let (inner, close) = match body.strip_prefix("[[") {
+        Some(inner) => (inner, "]]"),
+        None => (&body[1..], "]"),
+    };
+    let Some((name, rest)) = inner.split_once(close) else {
+        return false;
+    };
+    name.split('.').all(is_bare_key) && is_line_end(rest)
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Nothing, or an end-of-line comment after exactly one space
+fn is_line_end(rest: &str) -> bool {
+    rest.is_empty() || rest.starts_with(" #")
+}
+
+/// Result of scanning part of a value
+enum Scan<'a> {
+    /// The value ended; the text after it on the line
+    Done(&'a str),
+    /// The value continues on the next line
+    Continues,
+    /// Not a value the scan recognises
+    Unknown,
+}
+
+/// Open string delimiter
+#[derive(Clone, Copy)]
+enum Quote {
+    Basic,
+    Literal,
+    MultiBasic,
+    MultiLiteral,
+}
+
+impl Quote {
+    fn delimiter(self) -> &'static str {
+        match self {
+            Quote::Basic => "\"",
+            Quote::Literal => "'",
+            Quote::MultiBasic => "\"\"\"",
+            Quote::MultiLiteral => "'''",
+        }
+    }
+
+    fn is_multiline(self) -> bool {
+        matches!(self, Quote::MultiBasic | Quote::MultiLiteral)
+    }
+
+    fn has_escapes(self) -> bool {
+        matches!(self, Quote::Basic | Quote::MultiBasic)
+    }
+}
+
+/// Tracks strings, arrays and inline tables across the lines of a value
+#[derive(Default)]
+struct ValueScanner {
+    depth: usize,
+    quote: Option<Quote>,
+}
+
+impl ValueScanner {
+    fn is_open(&self) -> bool {
+        self.depth > 0 || self.quote.is_some()
+    }
+
+    fn scan<'a>(&mut self, text: &'a str) -> Scan<'a> {
+        let bytes = text.as_bytes();
+        let mut i = 0;
+
+        // A bare scalar (number, boolean, date) runs to the first separator
+        if !self.is_open() && !text.starts_with(['"', '\'', '[', '{']) {
+            let end = text
+                .find([' ', '\t', '#', ',', ']', '}'])
+                .unwrap_or(text.len());
+            return if end == 0 {
+                Scan::Unknown
+            } else {
+                Scan::Done(&text[end..])
+            };
+        }
+
+        while i < bytes.len() {
+            if let Some(quote) = self.quote {
+                if quote.has_escapes() && bytes[i] == b'\\' {
+                    i += 2;
+                    continue;
+                }
+                let delimiter = quote.delimiter().as_bytes();
+                if bytes[i..].starts_with(delimiter) {
+                    i += delimiter.len();
+                    // `""""` closes after a literal quote; up to two are allowed
+                    if quote.is_multiline() {
+                        let mut extra = 0;
+                        while extra < 2 && bytes.get(i) == Some(&delimiter[0]) {
+                            i += 1;
+                            extra += 1;
+                        }
+                    }
+                    self.quote = None;
+                    if self.depth == 0 {
+                        return Scan::Done(&text[i..]);
+                    }
+                    continue;
+                }
+                i += 1;
+                continue;
+            }
+
+            match bytes[i] {
+                b'"' | b'\'' => {
+                    let basic = bytes[i] == b'"';
+                    let triple: &[u8] = if basic { b"\"\"\"" } else { b"'''" };
+                    let quote = match (basic, bytes[i..].starts_with(triple)) {
+                        (true, true) => Quote::MultiBasic,
+                        (true, false) => Quote::Basic,
+                        (false, true) => Quote::MultiLiteral,
+                        (false, false) => Quote::Literal,
+                    };
+                    i += quote.delimiter().len();
+                    self.quote = Some(quote);
+                }
+                b'[' | b'{' => {
+                    self.depth += 1;
+                    i += 1;
+                }
+                b']' | b'}' => {
+                    if self.depth == 0 {
+                        return Scan::Unknown;
+                    }
+                    self.depth -= 1;
+                    i += 1;
+                    if self.depth == 0 {
+                        return Scan::Done(&text[i..]);
+                    }
+                }
+                // Comment inside a multi-line array
+                b'#' if self.depth > 0 => return Scan::Continues,
+                _ => i += 1,
+            }
+        }
+
+        match self.quote {
+            Some(quote) if !quote.is_multiline() => Scan::Unknown,
+            _ => Scan::Continues,
+        }
+    }
+}
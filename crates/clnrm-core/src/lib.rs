@@ -43,12 +43,13 @@ pub use telemetry::{Export, OtelConfig, OtelGuard};
 pub use assertions::{cache, database, email_service, UserAssertions};
 pub use cache::{Cache, CacheManager, CacheStats, FileCache, MemoryCache};
 pub use cleanroom::{
-    CleanroomEnvironment, ExecutionResult, HealthStatus, ServiceHandle, ServicePlugin,
-    ServiceRegistry,
+    CleanroomEnvironment, ExecutionResult, FailureReason, HealthStatus, ServiceHandle, ServiceLogs,
+    ServicePlugin, ServiceRegistry,
 };
 pub use config::{
     load_cleanroom_config, load_cleanroom_config_from_file, load_config_from_file,
-    parse_toml_config, CleanroomConfig, DeterminismConfig, ScenarioConfig, StepConfig, TestConfig,
+    load_config_from_yaml_file, parse_toml_config, parse_yaml_config, CleanroomConfig,
+    DeterminismConfig, ScenarioConfig, StepConfig, TestConfig,
 };
 pub use determinism::DeterminismEngine;
 pub use formatting::{
@@ -57,7 +58,10 @@ pub use formatting::{
     TestStatus, TestSuite,
 };
 pub use macros::{with_cache, with_database, with_message_queue, with_web_server};
-pub use reporting::{generate_reports, DigestReporter, JsonReporter, JunitReporter, ReportConfig};
+pub use reporting::{
+    generate_reports, ConsolidatedReporter, DigestReporter, JsonReporter, JunitReporter,
+    ReportConfig, ReportMeta,
+};
 pub use services::generic::GenericContainerPlugin;
 pub use services::surrealdb::SurrealDbPlugin;
 
@@ -76,8 +80,8 @@ pub use coverage::manifest::{BehaviorManifest, Dimensions, SystemInfo};
 pub use coverage::report::{ReportFormat, ReportGenerator};
 pub use coverage::tracker::CoverageTracker;
 pub use coverage::{
-    BehaviorCoverage, BehaviorCoverageReport, DimensionCoverage, DimensionWeights, StateTransition,
-    UncoveredBehaviors,
+    BehaviorCoverage, BehaviorCoverageReport, CoverageDiff, DimensionCoverage, DimensionDiff,
+    DimensionWeights, StateTransition, UncoveredBehaviors,
 };
 
 // The cleanroom_test macro is already exported via #[macro_export] in macros.rs
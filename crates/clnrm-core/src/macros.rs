@@ -6,6 +6,7 @@
 
 use crate::cleanroom::{CleanroomEnvironment, HealthStatus, ServiceHandle, ServicePlugin};
 use crate::error::{CleanroomError, Result};
+use crate::services::web_server::WebServerPlugin;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -124,11 +125,14 @@ impl ServiceSetup {
     }
 
     /// Set up a web server service
+    ///
+    /// Runs [`WebServerPlugin`] from `image`, which must provide `python3`,
+    /// serving its echo endpoint on port 8080.
     pub async fn with_web_server(&self, image: &str) -> Result<()> {
         self.with_service(
             "web_server",
             image,
-            Box::new(WebServerServicePlugin::new(image)),
+            Box::new(WebServerPlugin::new("web_server").with_image(image)),
         )
         .await
     }
@@ -193,6 +197,19 @@ impl ServiceSetup {
             ))
         }
     }
+
+    /// Get web server base URL
+    pub async fn get_web_server_url(&self) -> Result<String> {
+        let services = self.services.read().await;
+        services
+            .get("web_server")
+            .and_then(|handle| handle.metadata.get("url").cloned())
+            .ok_or_else(|| {
+                CleanroomError::internal_error(
+                    "Web server service not started. Call with_web_server() first.",
+                )
+            })
+    }
 }
 
 /// Test context that provides Jane-friendly APIs
@@ -354,49 +371,6 @@ impl ServicePlugin for MessageQueueServicePlugin {
     }
 }
 
-/// Web server service plugin implementation
-#[derive(Debug)]
-pub struct WebServerServicePlugin {
-    name: String,
-    image: String,
-}
-
-impl WebServerServicePlugin {
-    pub fn new(image: &str) -> Self {
-        Self {
-            name: "web_server".to_string(),
-            image: image.to_string(),
-        }
-    }
-}
-
-impl ServicePlugin for WebServerServicePlugin {
-    fn name(&self) -> &str {
-        &self.name
-    }
-
-    fn start(&self) -> Result<ServiceHandle> {
-        Ok(ServiceHandle {
-            id: format!("web_{}", uuid::Uuid::new_v4()),
-            service_name: self.name.clone(),
-            metadata: HashMap::from([
-                ("type".to_string(), "web_server".to_string()),
-                ("image".to_string(), self.image.clone()),
-                ("port".to_string(), "8080".to_string()),
-                ("status".to_string(), "running".to_string()),
-            ]),
-        })
-    }
-
-    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
-        Ok(())
-    }
-
-    fn health_check(&self, _handle: &ServiceHandle) -> HealthStatus {
-        HealthStatus::Healthy
-    }
-}
-
 /// Jane-friendly service setup functions
 /// These provide the simple, declarative API that Jane wants
 ///
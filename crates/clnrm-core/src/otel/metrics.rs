@@ -0,0 +1,276 @@
+//! OTLP metrics parsing
+//!
+//! Converts the OTLP JSON encoding of an `ExportMetricsServiceRequest`, as
+//! posted to `/v1/metrics` or written by a collector file exporter, into
+//! [`MetricData`]. Counters (sums), gauges and histograms keep the values
+//! metric expectations check; other metric types are kept by name only.
+
+use crate::error::{CleanroomError, Result};
+use crate::otel::otlp_receiver::{array, key_values};
+use crate::validation::common::extract_string_value;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Kind of an OTLP metric
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetricKind {
+    /// A sum, e.g. a request counter
+    Sum,
+    /// A gauge, e.g. current queue depth
+    Gauge,
+    /// An explicit-bucket or exponential histogram
+    Histogram,
+    /// A summary, or a metric type without data clnrm reads
+    Other,
+}
+
+impl MetricKind {
+    /// Lowercase name, as used in messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MetricKind::Sum => "sum",
+            MetricKind::Gauge => "gauge",
+            MetricKind::Histogram => "histogram",
+            MetricKind::Other => "other",
+        }
+    }
+}
+
+/// One data point of a metric
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricPoint {
+    /// Point attributes, identifying the series it belongs to
+    #[serde(default)]
+    pub attributes: HashMap<String, Value>,
+    /// Time the point was recorded
+    #[serde(default)]
+    pub time_unix_nano: Option<u64>,
+    /// Value of a sum or gauge point, or the sum of a histogram point
+    #[serde(default)]
+    pub value: Option<f64>,
+    /// Number of samples in a histogram point
+    #[serde(default)]
+    pub count: Option<u64>,
+}
+
+/// A metric with its data points
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricData {
+    /// Metric name, e.g. `http.server.requests`
+    pub name: String,
+    /// Metric kind
+    pub kind: MetricKind,
+    /// Unit, e.g. `ms` or `{request}`
+    #[serde(default)]
+    pub unit: Option<String>,
+    /// Whether points are deltas since the previous point rather than
+    /// cumulative totals
+    #[serde(default)]
+    pub delta: bool,
+    /// Data points
+    #[serde(default)]
+    pub points: Vec<MetricPoint>,
+    /// Attributes of the resource that exported the metric
+    #[serde(default)]
+    pub resource_attributes: HashMap<String, Value>,
+}
+
+impl MetricData {
+    /// Data points whose attributes include every `attributes` entry
+    pub fn points_matching<'a>(
+        &'a self,
+        attributes: &'a HashMap<String, String>,
+    ) -> impl Iterator<Item = &'a MetricPoint> + 'a {
+        self.points.iter().filter(move |point| {
+            attributes.iter().all(|(key, expected)| {
+                point
+                    .attributes
+                    .get(key)
+                    .is_some_and(|actual| extract_string_value(actual) == *expected)
+            })
+        })
+    }
+
+    /// Latest point of each series among `points`, or every point for delta
+    /// metrics, where each point adds to the total
+    fn contributing<'a>(&self, points: Vec<&'a MetricPoint>) -> Vec<&'a MetricPoint> {
+        if self.delta {
+            return points;
+        }
+
+        let mut latest: Vec<&MetricPoint> = Vec::new();
+        for point in points {
+            match latest
+                .iter_mut()
+                .find(|seen| seen.attributes == point.attributes)
+            {
+                Some(seen) if point.time_unix_nano >= seen.time_unix_nano => *seen = point,
+                Some(_) => {}
+                None => latest.push(point),
+            }
+        }
+        latest
+    }
+
+    /// Value of the metric over the points matching `attributes`: the total
+    /// of a sum or histogram across series, or the latest gauge reading
+    ///
+    /// `None` if no point matches or the metric has no values.
+    pub fn value(&self, attributes: &HashMap<String, String>) -> Option<f64> {
+        let points: Vec<&MetricPoint> = self.points_matching(attributes).collect();
+        if self.kind == MetricKind::Gauge {
+            return points
+                .into_iter()
+                .filter(|point| point.value.is_some())
+                .max_by_key(|point| point.time_unix_nano)
+                .and_then(|point| point.value);
+        }
+
+        let values: Vec<f64> = self
+            .contributing(points)
+            .into_iter()
+            .filter_map(|point| point.value)
+            .collect();
+        (!values.is_empty()).then(|| values.iter().sum())
+    }
+
+    /// Number of histogram samples over the points matching `attributes`
+    ///
+    /// `None` if no point matches or the metric isn't a histogram.
+    pub fn sample_count(&self, attributes: &HashMap<String, String>) -> Option<u64> {
+        let points: Vec<&MetricPoint> = self.points_matching(attributes).collect();
+        let counts: Vec<u64> = self
+            .contributing(points)
+            .into_iter()
+            .filter_map(|point| point.count)
+            .collect();
+        (!counts.is_empty()).then(|| counts.iter().sum())
+    }
+}
+
+/// Convert an OTLP JSON `ExportMetricsServiceRequest` into metrics
+pub fn parse_metrics_request(request: &Value) -> Vec<MetricData> {
+    let mut metrics = Vec::new();
+
+    for resource_metrics in array(request, "resourceMetrics") {
+        let resource_attributes = key_values(
+            resource_metrics
+                .get("resource")
+                .unwrap_or(&Value::Null)
+                .get("attributes"),
+        );
+
+        for scope_metrics in array(resource_metrics, "scopeMetrics") {
+            for metric in array(scope_metrics, "metrics") {
+                if let Some(mut metric) = parse_metric(metric) {
+                    metric.resource_attributes = resource_attributes.clone();
+                    metrics.push(metric);
+                }
+            }
+        }
+    }
+
+    metrics
+}
+
+/// Parse a metrics file: one export request, or one request per line as
+/// written by the collector's file exporter
+///
+/// # Errors
+/// * The content is neither a JSON document nor JSON lines
+pub fn parse_metrics_json(content: &str) -> Result<Vec<MetricData>> {
+    if let Ok(request) = serde_json::from_str::<Value>(content) {
+        return Ok(parse_metrics_request(&request));
+    }
+
+    let mut metrics = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let request: Value = serde_json::from_str(line).map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Invalid OTLP metrics JSON on line {}: {}",
+                index + 1,
+                e
+            ))
+        })?;
+        metrics.extend(parse_metrics_request(&request));
+    }
+    Ok(metrics)
+}
+
+/// Convert a single OTLP JSON metric, skipping metrics without a name
+fn parse_metric(metric: &Value) -> Option<MetricData> {
+    let name = metric
+        .get("name")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())?
+        .to_string();
+    let unit = metric
+        .get("unit")
+        .and_then(Value::as_str)
+        .filter(|s| !s.is_empty())
+        .map(String::from);
+
+    let (kind, data) = [
+        ("sum", MetricKind::Sum),
+        ("gauge", MetricKind::Gauge),
+        ("histogram", MetricKind::Histogram),
+        ("exponentialHistogram", MetricKind::Histogram),
+    ]
+    .into_iter()
+    .find_map(|(key, kind)| metric.get(key).map(|data| (kind, data)))
+    .unwrap_or((MetricKind::Other, &Value::Null));
+
+    // AGGREGATION_TEMPORALITY_DELTA is 1
+    let delta = match data.get("aggregationTemporality") {
+        Some(Value::Number(n)) => n.as_i64() == Some(1),
+        Some(Value::String(s)) => s == "AGGREGATION_TEMPORALITY_DELTA",
+        _ => false,
+    };
+
+    let points = array(data, "dataPoints")
+        .map(|point| parse_point(point, kind))
+        .collect();
+
+    Some(MetricData {
+        name,
+        kind,
+        unit,
+        delta,
+        points,
+        resource_attributes: HashMap::new(),
+    })
+}
+
+fn parse_point(point: &Value, kind: MetricKind) -> MetricPoint {
+    // int64 and uint64 fields are strings in OTLP JSON, but some exporters
+    // send numbers
+    let number = |key: &str| {
+        point.get(key).and_then(|v| {
+            v.as_f64()
+                .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        })
+    };
+    let unsigned = |key: &str| {
+        point.get(key).and_then(|v| {
+            v.as_u64()
+                .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        })
+    };
+
+    let (value, count) = match kind {
+        MetricKind::Histogram => (number("sum"), unsigned("count")),
+        _ => (number("asDouble").or_else(|| number("asInt")), None),
+    };
+
+    MetricPoint {
+        attributes: key_values(point.get("attributes")),
+        time_unix_nano: unsigned("timeUnixNano"),
+        value,
+        count,
+    }
+}
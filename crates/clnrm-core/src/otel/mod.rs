@@ -22,6 +22,8 @@
 //! ## Core Functionality
 //!
 //! - **Span Parsing**: Extract OTEL spans from container stdout
+//! - **OTLP Ingestion**: Receive spans over OTLP/HTTP (JSON) in the same shape
+//! - **Metrics**: Parse OTLP metrics (counters, gauges, histograms) for `[[expect.metric]]`
 //! - **Validation Integration**: Works with the main validation system in `/validation/`
 //! - **Fake-Green Detection**: Identifies tests that report success without actual execution
 //!
@@ -75,7 +77,17 @@
 //! - Reusable span parsing logic
 //! - Integration with the comprehensive validation framework
 
+pub mod metrics;
+pub mod otlp_receiver;
+pub mod redact;
+pub mod span_source;
 pub mod stdout_parser;
 
-// Re-export stdout parser for convenience
+// Re-export span sources for convenience
+pub use metrics::{parse_metrics_json, MetricData, MetricKind, MetricPoint};
+pub use otlp_receiver::{collect_spans, OtlpReceiver};
+pub use redact::SpanRedactor;
+pub use span_source::{
+    JsonFileSpanSource, OtlpSpanSource, SpanSource, SpanSourceSpec, StdoutSpanSource,
+};
 pub use stdout_parser::StdoutSpanParser;
@@ -0,0 +1,378 @@
+//! Minimal OTLP/HTTP span and metrics receiver
+//!
+//! Accepts `POST /v1/traces` requests with the OTLP JSON encoding and
+//! converts the received spans into [`SpanData`] in the same shape that
+//! [`super::StdoutSpanParser`] produces, so spans exported over OTLP can be
+//! validated without the application printing them to stdout. Metrics posted
+//! to `/v1/metrics` are kept as [`MetricData`].
+//!
+//! Only what exporters need is implemented: HTTP/1.1 with keep-alive and a
+//! `Content-Length` body. Protobuf and compressed payloads are rejected with
+//! `415 Unsupported Media Type`; configure exporters with
+//! `OTEL_EXPORTER_OTLP_PROTOCOL=http/json`.
+
+use crate::error::{CleanroomError, Result};
+use crate::otel::metrics::{parse_metrics_request, MetricData};
+use crate::validation::span_validator::{SpanData, SpanEvent, SpanKind};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use tracing::{debug, warn};
+
+/// Default OTLP/HTTP receiver address
+pub const DEFAULT_OTLP_HTTP_ENDPOINT: &str = "0.0.0.0:4318";
+
+/// Largest request body accepted from an exporter
+const MAX_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// Start a receiver on `endpoint`, capture spans for `duration`, and return them
+///
+/// `endpoint` is either `host:port` or a URL such as `http://0.0.0.0:4318`.
+///
+/// # Errors
+/// * The endpoint cannot be parsed or bound
+pub async fn collect_spans(endpoint: &str, duration: Duration) -> Result<Vec<SpanData>> {
+    let receiver = OtlpReceiver::start(endpoint).await?;
+    Ok(receiver.finish(duration).await)
+}
+
+/// A running OTLP/HTTP receiver collecting spans and metrics in the background
+#[derive(Debug)]
+pub struct OtlpReceiver {
+    local_addr: SocketAddr,
+    received: Arc<Mutex<Received>>,
+    accept_task: JoinHandle<()>,
+}
+
+/// Everything exporters have sent so far
+#[derive(Debug, Default)]
+struct Received {
+    spans: Vec<SpanData>,
+    metrics: Vec<MetricData>,
+}
+
+impl OtlpReceiver {
+    /// Bind `endpoint` and start accepting exporter connections
+    ///
+    /// # Errors
+    /// * The endpoint cannot be parsed or bound
+    pub async fn start(endpoint: &str) -> Result<Self> {
+        let addr = bind_address(endpoint)?;
+        let listener = TcpListener::bind(&addr).await.map_err(|e| {
+            CleanroomError::network_error(format!(
+                "Failed to bind OTLP receiver on {}: {}",
+                addr, e
+            ))
+        })?;
+        let local_addr = listener.local_addr().map_err(|e| {
+            CleanroomError::network_error(format!("Failed to read OTLP receiver address: {}", e))
+        })?;
+        debug!("OTLP receiver listening on {}", local_addr);
+
+        let received = Arc::new(Mutex::new(Received::default()));
+        let accept_received = Arc::clone(&received);
+        let accept_task = tokio::spawn(async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, peer)) => {
+                        let received = Arc::clone(&accept_received);
+                        tokio::spawn(async move {
+                            if let Err(e) = serve_connection(stream, received).await {
+                                debug!("OTLP connection from {} closed: {}", peer, e);
+                            }
+                        });
+                    }
+                    Err(e) => warn!("OTLP receiver failed to accept connection: {}", e),
+                }
+            }
+        });
+
+        Ok(Self {
+            local_addr,
+            received,
+            accept_task,
+        })
+    }
+
+    /// Address the receiver is bound to
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Spans received so far
+    pub fn spans(&self) -> Vec<SpanData> {
+        lock(&self.received).spans.clone()
+    }
+
+    /// Metrics received so far
+    pub fn metrics(&self) -> Vec<MetricData> {
+        lock(&self.received).metrics.clone()
+    }
+
+    /// Keep receiving for `grace` so in-flight exports land, then stop and
+    /// return every span received
+    pub async fn finish(self, grace: Duration) -> Vec<SpanData> {
+        tokio::time::sleep(grace).await;
+        self.accept_task.abort();
+        let spans = std::mem::take(&mut lock(&self.received).spans);
+        spans
+    }
+}
+
+impl Drop for OtlpReceiver {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+fn lock(received: &Mutex<Received>) -> std::sync::MutexGuard<'_, Received> {
+    // A panicking connection task cannot leave the Vecs half-written
+    received
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Turn `host:port` or `http://host:port/...` into a bindable address
+fn bind_address(endpoint: &str) -> Result<String> {
+    if !endpoint.contains("://") {
+        return Ok(endpoint.to_string());
+    }
+
+    let url = url::Url::parse(endpoint).map_err(|e| {
+        CleanroomError::validation_error(format!("Invalid OTLP endpoint '{}': {}", endpoint, e))
+    })?;
+    let host = url.host_str().ok_or_else(|| {
+        CleanroomError::validation_error(format!("OTLP endpoint '{}' has no host", endpoint))
+    })?;
+    let port = url.port().unwrap_or(4318);
+    Ok(format!("{}:{}", host, port))
+}
+
+/// Serve OTLP requests on one connection until the exporter closes it
+async fn serve_connection(stream: TcpStream, received: Arc<Mutex<Received>>) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+
+    loop {
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await.map_err(io)? == 0 {
+            return Ok(());
+        }
+
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.map_err(io)? == 0 {
+                return Ok(());
+            }
+            let line = line.trim_end();
+            if line.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+            }
+        }
+
+        let header = |name: &str| headers.get(name).map(String::as_str).unwrap_or("");
+        let content_length: usize = header("content-length").parse().unwrap_or(0);
+        if content_length > MAX_BODY_BYTES {
+            respond(reader.get_mut(), "413 Payload Too Large", "").await?;
+            return Ok(());
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).await.map_err(io)?;
+
+        let mut parts = request_line.split_whitespace();
+        let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+
+        let is_traces = path.starts_with("/v1/traces");
+        let status = if method != "POST" || !(is_traces || path.starts_with("/v1/metrics")) {
+            "404 Not Found"
+        } else if !header("content-type").starts_with("application/json")
+            || !matches!(header("content-encoding"), "" | "identity")
+        {
+            warn!(
+                "OTLP receiver only accepts uncompressed JSON; got content-type '{}'",
+                header("content-type")
+            );
+            "415 Unsupported Media Type"
+        } else {
+            match serde_json::from_slice::<Value>(&body) {
+                Ok(request) if is_traces => {
+                    let spans = parse_export_request(&request);
+                    debug!("OTLP receiver got {} span(s)", spans.len());
+                    lock(&received).spans.extend(spans);
+                    "200 OK"
+                }
+                Ok(request) => {
+                    let metrics = parse_metrics_request(&request);
+                    debug!("OTLP receiver got {} metric(s)", metrics.len());
+                    lock(&received).metrics.extend(metrics);
+                    "200 OK"
+                }
+                Err(e) => {
+                    warn!("OTLP receiver got invalid JSON: {}", e);
+                    "400 Bad Request"
+                }
+            }
+        };
+
+        let body = if status == "200 OK" { "{}" } else { "" };
+        respond(reader.get_mut(), status, body).await?;
+
+        if header("connection").eq_ignore_ascii_case("close") {
+            return Ok(());
+        }
+    }
+}
+
+async fn respond(stream: &mut TcpStream, status: &str, body: &str) -> Result<()> {
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await.map_err(io)
+}
+
+fn io(e: std::io::Error) -> CleanroomError {
+    CleanroomError::network_error(format!("OTLP receiver I/O error: {}", e))
+}
+
+/// Convert an OTLP JSON `ExportTraceServiceRequest` into spans
+pub fn parse_export_request(request: &Value) -> Vec<SpanData> {
+    let mut spans = Vec::new();
+
+    for resource_spans in array(request, "resourceSpans") {
+        let resource_attributes = key_values(
+            resource_spans
+                .get("resource")
+                .unwrap_or(&Value::Null)
+                .get("attributes"),
+        );
+
+        for scope_spans in array(resource_spans, "scopeSpans") {
+            for span in array(scope_spans, "spans") {
+                if let Some(mut span) = parse_span(span) {
+                    span.resource_attributes = resource_attributes.clone();
+                    spans.push(span);
+                }
+            }
+        }
+    }
+
+    spans
+}
+
+pub(crate) fn array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+}
+
+/// Convert a single OTLP JSON span, skipping spans without name or IDs
+fn parse_span(span: &Value) -> Option<SpanData> {
+    let text = |key: &str| {
+        span.get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    // uint64 fields are strings in OTLP JSON, but some exporters send numbers
+    let nanos = |key: &str| {
+        span.get(key).and_then(|v| {
+            v.as_u64()
+                .or_else(|| v.as_str().and_then(|s| s.parse().ok()))
+        })
+    };
+
+    let kind = span.get("kind").and_then(|v| match v {
+        Value::Number(n) => n
+            .as_i64()
+            .and_then(|i| SpanKind::from_otel_int(i as i32).ok()),
+        Value::String(s) => {
+            SpanKind::parse_kind(&s.trim_start_matches("SPAN_KIND_").to_ascii_lowercase()).ok()
+        }
+        _ => None,
+    });
+
+    Some(SpanData {
+        name: text("name")?,
+        attributes: key_values(span.get("attributes")),
+        trace_id: text("traceId")?,
+        span_id: text("spanId")?,
+        parent_span_id: text("parentSpanId"),
+        start_time_unix_nano: nanos("startTimeUnixNano"),
+        end_time_unix_nano: nanos("endTimeUnixNano"),
+        kind,
+        events: parse_span_events(span),
+        resource_attributes: HashMap::new(),
+    })
+}
+
+/// Events of an OTLP JSON span with their attributes unwrapped, or `None`
+/// if the span has no events array
+pub(crate) fn parse_span_events(span: &Value) -> Option<Vec<SpanEvent>> {
+    let events = span.get("events")?.as_array()?;
+    Some(
+        events
+            .iter()
+            .filter_map(|event| {
+                Some(SpanEvent {
+                    name: event.get("name")?.as_str()?.to_string(),
+                    attributes: key_values(event.get("attributes")),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Convert an OTLP `KeyValue` list into plain JSON values
+pub(crate) fn key_values(list: Option<&Value>) -> HashMap<String, Value> {
+    list.and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|kv| {
+            let key = kv.get("key")?.as_str()?.to_string();
+            Some((key, any_value(kv.get("value")?)))
+        })
+        .collect()
+}
+
+/// Unwrap an OTLP `AnyValue` (`{"stringValue": "x"}`) into a plain JSON value
+fn any_value(value: &Value) -> Value {
+    let Some(object) = value.as_object() else {
+        return value.clone();
+    };
+
+    if let Some(v) = object
+        .get("stringValue")
+        .or_else(|| object.get("bytesValue"))
+    {
+        v.clone()
+    } else if let Some(v) = object.get("boolValue") {
+        v.clone()
+    } else if let Some(v) = object.get("intValue") {
+        // int64 is a string in OTLP JSON
+        v.as_str()
+            .and_then(|s| s.parse::<i64>().ok())
+            .map(Value::from)
+            .unwrap_or_else(|| v.clone())
+    } else if let Some(v) = object.get("doubleValue") {
+        v.clone()
+    } else if let Some(v) = object.get("arrayValue") {
+        Value::Array(array(v, "values").map(any_value).collect())
+    } else if let Some(v) = object.get("kvlistValue") {
+        Value::Object(key_values(v.get("values")).into_iter().collect())
+    } else {
+        Value::Null
+    }
+}
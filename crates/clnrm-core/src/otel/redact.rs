@@ -0,0 +1,127 @@
+//! Span attribute redaction
+//!
+//! Masks secrets in span attributes before spans are written to disk, e.g.
+//! by `clnrm record`. Only attribute values change; span names, IDs, parent
+//! links and timing are kept so recorded traces keep their structure.
+//! The same patterns mask step output for `[meta] redact`.
+//!
+//! ```rust
+//! use clnrm_core::otel::redact::SpanRedactor;
+//!
+//! let redactor = SpanRedactor::new()
+//!     .with_key("authorization")
+//!     .with_pattern(r"sk-[A-Za-z0-9]+")?;
+//! # Ok::<(), clnrm_core::error::CleanroomError>(())
+//! ```
+
+use crate::error::{CleanroomError, Result};
+use crate::validation::span_validator::SpanData;
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Replacement for redacted values
+pub const REDACTED: &str = "***";
+
+/// Rules for masking span attribute values
+#[derive(Debug, Clone, Default)]
+pub struct SpanRedactor {
+    keys: Vec<String>,
+    patterns: Vec<Regex>,
+}
+
+impl SpanRedactor {
+    /// Create a redactor that masks nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mask the whole value of attributes named `key`
+    ///
+    /// Matching is case-insensitive and also covers namespaced keys ending
+    /// in `.<key>`, so `authorization` masks `http.request.header.authorization`.
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.push(key.into().to_lowercase());
+        self
+    }
+
+    /// Mask every match of `pattern` inside string attribute values
+    ///
+    /// # Errors
+    /// Returns error if `pattern` is not a valid regex
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Invalid redaction pattern '{}': {}",
+                pattern, e
+            ))
+        })?;
+        self.patterns.push(regex);
+        Ok(self)
+    }
+
+    /// Whether the redactor has no rules
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty() && self.patterns.is_empty()
+    }
+
+    /// Mask matching values in a span's, its resource's and its events'
+    /// attributes
+    pub fn redact(&self, span: &mut SpanData) {
+        if self.is_empty() {
+            return;
+        }
+        self.redact_attributes(&mut span.attributes);
+        self.redact_attributes(&mut span.resource_attributes);
+        for event in span.events.iter_mut().flatten() {
+            self.redact_attributes(&mut event.attributes);
+        }
+    }
+
+    /// Mask every pattern match in free text such as command output
+    pub fn redact_text(&self, text: &str) -> String {
+        self.patterns
+            .iter()
+            .fold(text.to_string(), |text, pattern| {
+                pattern.replace_all(&text, REDACTED).into_owned()
+            })
+    }
+
+    fn redact_attributes(&self, attributes: &mut HashMap<String, Value>) {
+        for (key, value) in attributes.iter_mut() {
+            if self.matches_key(key) {
+                *value = Value::String(REDACTED.to_string());
+            } else {
+                self.redact_value(value);
+            }
+        }
+    }
+
+    fn matches_key(&self, key: &str) -> bool {
+        let key = key.to_lowercase();
+        self.keys.iter().any(|redacted| {
+            key == *redacted
+                || key
+                    .strip_suffix(redacted.as_str())
+                    .is_some_and(|prefix| prefix.ends_with('.'))
+        })
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => {
+                for pattern in &self.patterns {
+                    if pattern.is_match(s) {
+                        *s = pattern.replace_all(s, REDACTED).into_owned();
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.redact_value(item);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,137 @@
+//! Pluggable sources of spans for scenario validation
+//!
+//! Validation only sees the `Vec<SpanData>` a [`SpanSource`] collects, so
+//! where spans come from is decided by the scenario's `artifacts.collect`:
+//!
+//! - `spans:otlp` - [`OtlpSpanSource`], spans exported to the built-in receiver
+//! - `spans:file:<path>` - [`JsonFileSpanSource`], a JSON or NDJSON trace file
+//! - any other `spans:<name>` - [`StdoutSpanSource`], spans printed to stdout
+
+use crate::error::{CleanroomError, Result};
+use crate::otel::otlp_receiver::OtlpReceiver;
+use crate::otel::stdout_parser::StdoutSpanParser;
+use crate::validation::span_validator::{SpanData, SpanValidator};
+use std::future::Future;
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Something spans can be collected from once a scenario command has run
+pub trait SpanSource: Send + Sync {
+    /// Collect every span the source has
+    fn collect(&self) -> impl Future<Output = Result<Vec<SpanData>>> + Send;
+}
+
+/// Where a `spans:<source>` artifact says to collect spans from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpanSourceSpec {
+    /// Spans printed to the scenario command's stdout
+    Stdout,
+    /// Spans exported to the OTLP/HTTP receiver
+    Otlp,
+    /// Spans read from a trace file
+    File(PathBuf),
+}
+
+impl SpanSourceSpec {
+    /// Parse an `artifacts.collect` entry, or `None` if it isn't `spans:<source>`
+    pub fn parse(artifact: &str) -> Option<Self> {
+        let source = artifact.strip_prefix("spans:")?;
+        Some(match source {
+            "otlp" => Self::Otlp,
+            _ => match source.strip_prefix("file:") {
+                Some(path) => Self::File(PathBuf::from(path)),
+                None => Self::Stdout,
+            },
+        })
+    }
+}
+
+/// Spans printed by the scenario command, one JSON object per line, read
+/// from the file its stdout was written to
+///
+/// The file is parsed a line at a time, so only the spans are held in memory.
+pub struct StdoutSpanSource {
+    path: PathBuf,
+}
+
+impl StdoutSpanSource {
+    /// Collect spans from the command stdout written to `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SpanSource for StdoutSpanSource {
+    async fn collect(&self) -> Result<Vec<SpanData>> {
+        let file = std::fs::File::open(&self.path).map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to read stdout file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+
+        tokio::task::spawn_blocking(move || {
+            StdoutSpanParser::parse_reader(BufReader::new(file)).collect()
+        })
+        .await
+        .map_err(|e| {
+            CleanroomError::internal_error(format!("Failed to parse stdout spans: {}", e))
+        })?
+    }
+}
+
+/// Spans in a trace file: a span array as written by `run`, an OTEL
+/// collector file export, or span lines as printed to stdout
+pub struct JsonFileSpanSource {
+    path: PathBuf,
+}
+
+impl JsonFileSpanSource {
+    /// Collect spans from the trace file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SpanSource for JsonFileSpanSource {
+    async fn collect(&self) -> Result<Vec<SpanData>> {
+        let content = tokio::fs::read_to_string(&self.path).await.map_err(|e| {
+            CleanroomError::io_error(format!(
+                "Failed to read spans file {}: {}",
+                self.path.display(),
+                e
+            ))
+        })?;
+
+        if let Ok(spans) = serde_json::from_str::<Vec<SpanData>>(&content) {
+            return Ok(spans);
+        }
+        let spans = SpanValidator::from_json(&content)?.spans().to_vec();
+        if !spans.is_empty() {
+            return Ok(spans);
+        }
+        StdoutSpanParser::parse(&content)
+    }
+}
+
+/// Spans exported to a running [`OtlpReceiver`]
+pub struct OtlpSpanSource<'a> {
+    receiver: &'a OtlpReceiver,
+    grace: Duration,
+}
+
+impl<'a> OtlpSpanSource<'a> {
+    /// Collect from `receiver`, waiting `grace` first so in-flight exports land
+    pub fn new(receiver: &'a OtlpReceiver, grace: Duration) -> Self {
+        Self { receiver, grace }
+    }
+}
+
+impl SpanSource for OtlpSpanSource<'_> {
+    async fn collect(&self) -> Result<Vec<SpanData>> {
+        tokio::time::sleep(self.grace).await;
+        Ok(self.receiver.spans())
+    }
+}
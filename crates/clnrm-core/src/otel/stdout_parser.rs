@@ -2,10 +2,15 @@
 //!
 //! Parses OpenTelemetry spans from container stdout mixed with other log output.
 //! Supports OTEL stdout exporter format (JSON lines).
+//!
+//! [`StdoutSpanParser::parse_reader`] reads the same format from any
+//! [`BufRead`] one line at a time, for trace dumps too large to hold in memory.
 
 use crate::error::{CleanroomError, Result};
-use crate::validation::span_validator::SpanData;
+use crate::validation::span_validator::{SpanData, SpanEvent};
+use serde::Deserialize;
 use serde_json::Value;
+use std::io::BufRead;
 
 /// Parser for extracting OTEL spans from container stdout
 pub struct StdoutSpanParser;
@@ -38,43 +43,70 @@ impl StdoutSpanParser {
     /// assert_eq!(spans.len(), 2);
     /// ```
     pub fn parse(stdout: &str) -> Result<Vec<SpanData>> {
-        let mut spans = Vec::new();
+        Ok(stdout
+            .lines()
+            .enumerate()
+            .filter_map(|(line_num, line)| Self::parse_line(line_num + 1, line))
+            .collect())
+    }
 
-        for (line_num, line) in stdout.lines().enumerate() {
-            let line = line.trim();
+    /// Parse OTEL spans from a reader, one line at a time
+    ///
+    /// Accepts the same mixed output as [`parse`](Self::parse) but holds
+    /// only the current line in memory, so multi-gigabyte NDJSON dumps can
+    /// be read from a file or pipe. Lines that aren't valid UTF-8 are read
+    /// lossily; read failures are yielded as errors.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clnrm_core::otel::stdout_parser::StdoutSpanParser;
+    ///
+    /// let ndjson = b"{\"name\":\"a\",\"trace_id\":\"t\",\"span_id\":\"1\"}\nlog line\n";
+    /// let spans: Vec<_> = StdoutSpanParser::parse_reader(&ndjson[..])
+    ///     .collect::<Result<_, _>>()
+    ///     .unwrap();
+    /// assert_eq!(spans.len(), 1);
+    /// ```
+    pub fn parse_reader<R: BufRead>(reader: R) -> SpanReader<R> {
+        SpanReader {
+            reader,
+            line: Vec::new(),
+            line_num: 0,
+        }
+    }
 
-            // Skip empty lines
-            if line.is_empty() {
-                continue;
-            }
+    /// Parse a span from one line of output
+    ///
+    /// Non-JSON lines and JSON that isn't span-like yield `None`; span-like
+    /// objects that fail to parse are logged and skipped.
+    fn parse_line(line_num: usize, line: &str) -> Option<SpanData> {
+        let line = line.trim();
+
+        // Skip empty lines
+        if line.is_empty() {
+            return None;
+        }
 
-            // Try to parse as JSON
-            match serde_json::from_str::<Value>(line) {
-                Ok(value) => {
-                    // Check if this looks like a span object
-                    if Self::is_span_like(&value) {
-                        match Self::parse_span(&value) {
-                            Ok(span) => spans.push(span),
-                            Err(e) => {
-                                // Log warning but don't fail - malformed span
-                                tracing::warn!(
-                                    line = line_num + 1,
-                                    error = %e,
-                                    "Failed to parse span-like JSON object"
-                                );
-                            }
-                        }
-                    }
-                    // Otherwise, it's valid JSON but not a span - ignore silently
-                }
-                Err(_) => {
-                    // Not JSON - ignore silently (likely a log line)
-                    continue;
-                }
-            }
+        // Not JSON - ignore silently (likely a log line)
+        let value = serde_json::from_str::<Value>(line).ok()?;
+
+        // Valid JSON but not a span - ignore silently
+        if !Self::is_span_like(&value) {
+            return None;
         }
 
-        Ok(spans)
+        match Self::parse_span(&value) {
+            Ok(span) => Some(span),
+            Err(e) => {
+                // Log warning but don't fail - malformed span
+                tracing::warn!(
+                    line = line_num,
+                    error = %e,
+                    "Failed to parse span-like JSON object"
+                );
+                None
+            }
+        }
     }
 
     /// Check if a JSON value looks like a span
@@ -150,16 +182,11 @@ impl StdoutSpanParser {
             .map(|obj| obj.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
             .unwrap_or_default();
 
-        // Parse events (array of event names or event objects)
+        // Parse events: event names, or event objects whose attributes
+        // are a plain JSON object
         let events = value.get("events").and_then(|v| v.as_array()).map(|arr| {
             arr.iter()
-                .filter_map(|event| {
-                    // Support both string arrays and event objects with "name" field
-                    event
-                        .as_str()
-                        .map(String::from)
-                        .or_else(|| event.get("name").and_then(|n| n.as_str()).map(String::from))
-                })
+                .filter_map(|event| SpanEvent::deserialize(event).ok())
                 .collect()
         });
 
@@ -177,3 +204,37 @@ impl StdoutSpanParser {
         })
     }
 }
+
+/// Iterator over the spans in a reader, from [`StdoutSpanParser::parse_reader`]
+pub struct SpanReader<R> {
+    reader: R,
+    /// Buffer for the current line, reused between lines
+    line: Vec<u8>,
+    line_num: usize,
+}
+
+impl<R: BufRead> Iterator for SpanReader<R> {
+    type Item = Result<SpanData>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_until(b'\n', &mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {
+                    self.line_num += 1;
+                    let line = String::from_utf8_lossy(&self.line);
+                    if let Some(span) = StdoutSpanParser::parse_line(self.line_num, &line) {
+                        return Some(Ok(span));
+                    }
+                }
+                Err(e) => {
+                    return Some(Err(CleanroomError::io_error(format!(
+                        "Failed to read spans after line {}: {}",
+                        self.line_num, e
+                    ))))
+                }
+            }
+        }
+    }
+}
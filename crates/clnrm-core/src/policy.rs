@@ -46,6 +46,19 @@
 //! policy.security.blocked_addresses = vec!["127.0.0.1".to_string()];
 //! ```
 //!
+//! ### Command Allow/Deny Lists
+//!
+//! ```no_run
+//! use clnrm::Policy;
+//!
+//! let policy = Policy::default().with_denied_binaries(["rm", "dd"]);
+//!
+//! // Fails before anything runs: `rm` is denied
+//! assert!(policy
+//!     .check_command(&["rm".to_string(), "-rf".to_string(), "/data".to_string()])
+//!     .is_err());
+//! ```
+//!
 //! ### Policy Validation
 //!
 //! ```no_run
@@ -99,6 +112,12 @@ pub struct SecurityPolicy {
     pub enable_audit_logging: bool,
     /// Security level
     pub security_level: SecurityLevel,
+    /// Binaries commands may run; empty allows any binary not denied
+    #[serde(default)]
+    pub allowed_binaries: Vec<String>,
+    /// Binaries commands may never run
+    #[serde(default)]
+    pub denied_binaries: Vec<String>,
 }
 
 /// Resource policy configuration
@@ -300,6 +319,8 @@ impl Default for SecurityPolicy {
             ],
             enable_audit_logging: true,
             security_level: SecurityLevel::Standard,
+            allowed_binaries: Vec::new(),
+            denied_binaries: Vec::new(),
         }
     }
 }
@@ -433,6 +454,74 @@ impl Policy {
         !self.security.enable_network_isolation
     }
 
+    /// Only allow commands running these binaries
+    pub fn with_allowed_binaries<I, S>(mut self, binaries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.security
+            .allowed_binaries
+            .extend(binaries.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reject commands running any of these binaries
+    pub fn with_denied_binaries<I, S>(mut self, binaries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.security
+            .denied_binaries
+            .extend(binaries.into_iter().map(Into::into));
+        self
+    }
+
+    /// Check a command against the allowed and denied binaries
+    ///
+    /// The command is checked as the shell line it runs as: the first word
+    /// of every `;`, `&&`, `||`, `|` or `&` separated part and of every
+    /// `$(...)` or backtick substitution names a binary, as does the script
+    /// of a `sh -c`/`bash -lc`. Wrappers such as `env`, `timeout`, `xargs`
+    /// and `sudo` are looked through to the binary they run. Binaries are
+    /// compared by file name, so denying `rm` also rejects `/bin/rm`.
+    ///
+    /// This is a best-effort check, not a shell parser: a command that
+    /// builds the binary name at runtime, e.g. from a variable, or a wrapper
+    /// option that takes a separate value gets past it.
+    ///
+    /// # Errors
+    /// Returns a validation error naming the binary and the list that
+    /// rejected it
+    pub fn check_command(&self, command: &[String]) -> Result<()> {
+        let allowed = &self.security.allowed_binaries;
+        let denied = &self.security.denied_binaries;
+        if allowed.is_empty() && denied.is_empty() {
+            return Ok(());
+        }
+
+        for binary in command_binaries(&command.join(" ")) {
+            if denied.iter().any(|d| d == &binary) {
+                return Err(CleanroomError::validation_error(format!(
+                    "Command '{}' violates policy: binary '{}' is in [policy] denied_binaries",
+                    command.join(" "),
+                    binary
+                )));
+            }
+            if !allowed.is_empty() && !allowed.iter().any(|a| a == &binary) {
+                return Err(CleanroomError::validation_error(format!(
+                    "Command '{}' violates policy: binary '{}' is not in [policy] allowed_binaries ({})",
+                    command.join(" "),
+                    binary,
+                    allowed.join(", ")
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Validate policy configuration
     pub fn validate(&self) -> Result<()> {
         // Validate security policy
@@ -626,3 +715,66 @@ impl Policy {
         )
     }
 }
+
+/// Shells whose `-c` script is checked too
+const SHELLS: [&str; 4] = ["sh", "bash", "ash", "zsh"];
+
+/// Commands that run their arguments as another command
+const WRAPPERS: [&str; 12] = [
+    "env", "timeout", "xargs", "nice", "nohup", "exec", "command", "sudo", "doas", "stdbuf",
+    "time", "setsid",
+];
+
+/// File names of the binaries a shell command line runs
+fn command_binaries(line: &str) -> Vec<String> {
+    let mut binaries = Vec::new();
+    let line = line
+        .replace("&&", ";")
+        .replace("||", ";")
+        .replace("$(", ";");
+    for part in line.split([';', '|', '&', '`', '\n']) {
+        let mut words = part
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| matches!(c, '\'' | '"' | '(' | ')')));
+
+        // Look through wrappers to the binary they run
+        while let Some(word) = words.find(|word| !is_wrapper_argument(word)) {
+            let binary = word.rsplit('/').next().unwrap_or(word).to_string();
+            let is_wrapper = WRAPPERS.contains(&binary.as_str());
+
+            // `sh -c 'script'` runs the script's first word too
+            if SHELLS.contains(&binary.as_str()) {
+                if let Some(script) = words
+                    .by_ref()
+                    .skip_while(|word| !is_script_flag(word))
+                    .nth(1)
+                {
+                    binaries.extend(command_binaries(script));
+                }
+            }
+            binaries.push(binary);
+
+            if !is_wrapper {
+                break;
+            }
+        }
+    }
+    binaries
+}
+
+/// Whether a word is an assignment, option or count rather than a command,
+/// e.g. `FOO=1` for `env`, `-n` for `nice` or `5` for `timeout`
+fn is_wrapper_argument(word: &str) -> bool {
+    word.is_empty()
+        || word.contains('=')
+        || word.starts_with('-')
+        || word
+            .trim_end_matches(['s', 'm', 'h', 'd'])
+            .parse::<f64>()
+            .is_ok()
+}
+
+/// Whether a shell option introduces a `-c` script, e.g. `-c` or `-lc`
+fn is_script_flag(word: &str) -> bool {
+    word.starts_with('-') && !word.starts_with("--") && word.contains('c')
+}
@@ -0,0 +1,124 @@
+//! Consolidated JSON report format
+//!
+//! Generates one self-contained JSON document with the test metadata, every
+//! collected span and the validation outcomes, so downstream tooling doesn't
+//! have to join the separate JSON, JUnit and digest reports.
+
+use crate::error::{CleanroomError, Result};
+use crate::reporting::json::{JsonReport, JsonReporter};
+use crate::validation::ValidationReport;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::Path;
+
+/// Test metadata recorded in a consolidated report
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportMeta {
+    /// Test name
+    pub name: String,
+    /// Test version, if declared
+    pub version: Option<String>,
+    /// Test description, if declared
+    pub description: Option<String>,
+    /// Scenario the spans were collected in
+    pub scenario: Option<String>,
+    /// Determinism seed the test ran with
+    pub seed: Option<u64>,
+    /// Version of clnrm that produced the report
+    pub clnrm_version: String,
+    /// When the report was generated
+    pub generated_at: DateTime<Utc>,
+}
+
+impl ReportMeta {
+    /// Create metadata for a test, stamped with this clnrm version and the current time
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: None,
+            description: None,
+            scenario: None,
+            seed: None,
+            clnrm_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Set test version
+    pub fn with_version(mut self, version: Option<String>) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Set test description
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    /// Set scenario name
+    pub fn with_scenario(mut self, scenario: impl Into<String>) -> Self {
+        self.scenario = Some(scenario.into());
+        self
+    }
+
+    /// Set determinism seed
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// Consolidated report structure
+#[derive(Debug, Serialize)]
+pub struct ConsolidatedReport<'a> {
+    /// Test metadata
+    pub meta: &'a ReportMeta,
+    /// Every collected span, as serialized for the digest
+    pub spans: serde_json::Value,
+    /// Validation outcomes, in the same shape as the JSON report
+    pub validations: JsonReport,
+}
+
+/// Consolidated JSON report generator
+pub struct ConsolidatedReporter;
+
+impl ConsolidatedReporter {
+    /// Write consolidated report to file
+    ///
+    /// # Arguments
+    /// * `path` - File path for JSON output
+    /// * `meta` - Test metadata
+    /// * `spans_json` - JSON array of collected spans
+    /// * `report` - Validation report to include
+    ///
+    /// # Errors
+    /// Returns error if:
+    /// - `spans_json` isn't valid JSON
+    /// - JSON serialization fails
+    /// - File write fails
+    pub fn write(
+        path: &Path,
+        meta: &ReportMeta,
+        spans_json: &str,
+        report: &ValidationReport,
+    ) -> Result<()> {
+        let spans = serde_json::from_str(spans_json).map_err(|e| {
+            CleanroomError::serialization_error(format!("Invalid spans JSON: {}", e))
+        })?;
+
+        let consolidated = ConsolidatedReport {
+            meta,
+            spans,
+            validations: JsonReporter::convert_report(report),
+        };
+
+        let json_str = serde_json::to_string_pretty(&consolidated).map_err(|e| {
+            CleanroomError::serialization_error(format!("JSON serialization failed: {}", e))
+        })?;
+
+        std::fs::write(path, json_str).map_err(|e| {
+            CleanroomError::report_error(format!("Failed to write consolidated report: {}", e))
+        })
+    }
+}
@@ -55,7 +55,7 @@ impl JsonReporter {
     }
 
     /// Convert ValidationReport to JsonReport
-    fn convert_report(report: &ValidationReport) -> JsonReport {
+    pub(crate) fn convert_report(report: &ValidationReport) -> JsonReport {
         JsonReport {
             passed: report.is_success(),
             total_passes: report.passes().len(),
@@ -1,8 +1,10 @@
 //! Report generation for test results
 //!
-//! Provides multi-format report generation including JSON, JUnit XML, and SHA-256 digest.
+//! Provides multi-format report generation including JSON, JUnit XML, SHA-256 digest,
+//! and a consolidated JSON report combining metadata, spans and validations.
 //! All reports support proper error handling and follow core team standards.
 
+pub mod consolidated;
 pub mod digest;
 pub mod json;
 pub mod junit;
@@ -11,6 +13,7 @@ use crate::error::Result;
 use crate::validation::ValidationReport;
 use std::path::Path;
 
+pub use consolidated::{ConsolidatedReporter, ReportMeta};
 pub use digest::DigestReporter;
 pub use json::JsonReporter;
 pub use junit::JunitReporter;
@@ -24,6 +27,8 @@ pub struct ReportConfig {
     pub junit_path: Option<String>,
     /// Path for SHA-256 digest output
     pub digest_path: Option<String>,
+    /// Path for consolidated JSON report output
+    pub consolidated_path: Option<String>,
 }
 
 impl ReportConfig {
@@ -49,6 +54,12 @@ impl ReportConfig {
         self.digest_path = Some(path.into());
         self
     }
+
+    /// Set consolidated JSON report path
+    pub fn with_consolidated(mut self, path: impl Into<String>) -> Self {
+        self.consolidated_path = Some(path.into());
+        self
+    }
 }
 
 /// Generate all configured reports
@@ -57,6 +68,7 @@ impl ReportConfig {
 /// * `config` - Report configuration specifying which reports to generate
 /// * `report` - Validation report containing test results
 /// * `spans_json` - Raw JSON string of spans for digest calculation
+/// * `meta` - Test metadata for the consolidated report
 ///
 /// # Returns
 /// * `Result<()>` - Success or first encountered error
@@ -67,6 +79,7 @@ pub fn generate_reports(
     config: &ReportConfig,
     report: &ValidationReport,
     spans_json: &str,
+    meta: &ReportMeta,
 ) -> Result<()> {
     if let Some(ref json_path) = config.json_path {
         JsonReporter::write(Path::new(json_path), report)?;
@@ -80,5 +93,9 @@ pub fn generate_reports(
         DigestReporter::write(Path::new(digest_path), spans_json)?;
     }
 
+    if let Some(ref consolidated_path) = config.consolidated_path {
+        ConsolidatedReporter::write(Path::new(consolidated_path), meta, spans_json, report)?;
+    }
+
     Ok(())
 }
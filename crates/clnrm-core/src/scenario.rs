@@ -108,6 +108,9 @@ pub struct RunResult {
 pub struct StepResult {
     /// Step name/label
     pub name: String,
+    /// Command line that was executed
+    #[serde(default)]
+    pub command: String,
     /// Exit code
     pub exit_code: i32,
     /// Standard output
@@ -122,6 +125,63 @@ pub struct StepResult {
     pub success: bool,
     /// Source of the step
     pub source: String,
+    /// Number of retries needed before the final attempt
+    #[serde(default)]
+    pub retries: u32,
+    /// Whether the step was skipped rather than executed
+    #[serde(default)]
+    pub skipped: bool,
+    /// Why the step failed, when it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<crate::cleanroom::FailureReason>,
+    /// Spans collected while the step ran (a scenario's `run` step, when
+    /// the scenario collects `spans:` artifacts)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spans: Vec<crate::validation::span_validator::SpanData>,
+}
+
+impl StepResult {
+    /// Create a result for a step that was skipped without executing
+    pub fn skipped(name: impl Into<String>, source: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: String::new(),
+            exit_code: 0,
+            stdout: String::new(),
+            stderr: String::new(),
+            duration_ms: 0,
+            start_ts: 0,
+            success: true,
+            source: source.into(),
+            retries: 0,
+            skipped: true,
+            failure_reason: None,
+            spans: Vec::new(),
+        }
+    }
+
+    /// Create a failed result for a step that could not be executed
+    pub fn failed(
+        name: impl Into<String>,
+        source: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            command: String::new(),
+            exit_code: -1,
+            stdout: String::new(),
+            stderr: message.into(),
+            duration_ms: 0,
+            start_ts: 0,
+            success: false,
+            source: source.into(),
+            retries: 0,
+            skipped: false,
+            failure_reason: None,
+            spans: Vec::new(),
+        }
+    }
 }
 
 /// A single execution step in a scenario
@@ -293,12 +353,17 @@ impl Scenario {
         for step in self.steps {
             step_order.push(step.name.clone());
 
+            let command = std::iter::once(step.cmd.bin.as_str())
+                .chain(step.cmd.args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ");
             let step_start = std::time::Instant::now();
             let result = backend.run_cmd(step.cmd)?;
             let step_duration = step_start.elapsed().as_millis() as u64;
 
             let step_result = StepResult {
                 name: step.name,
+                command,
                 exit_code: result.exit_code,
                 stdout: result.stdout.clone(),
                 stderr: result.stderr.clone(),
@@ -306,6 +371,13 @@ impl Scenario {
                 start_ts: step_start.elapsed().as_millis() as u64,
                 success: result.exit_code == 0,
                 source: step.source.to_string(),
+                retries: 0,
+                skipped: false,
+                failure_reason: crate::cleanroom::FailureReason::from_exit(
+                    result.exit_code,
+                    result.oom_killed,
+                ),
+                spans: Vec::new(),
             };
 
             steps.push(step_result);
@@ -341,8 +341,11 @@ impl ArtifactCollector {
         Ok(path)
     }
 
-    /// Collect logs from stdout or stderr
-    async fn collect_logs(&self, content: &str, stream: &str) -> Result<PathBuf> {
+    /// Save log output as `<artifact_dir>/<stream>.log`
+    ///
+    /// `stream` is `stdout`, `stderr`, or the name of a service whose
+    /// container output is being saved.
+    pub async fn collect_logs(&self, content: &str, stream: &str) -> Result<PathBuf> {
         let filename = format!("{}.log", stream);
         let path = self.artifact_dir.join(filename);
 
@@ -9,9 +9,11 @@ use crate::error::{CleanroomError, Result};
 use crate::services::{
     generic::GenericContainerPlugin,
     ollama::{OllamaConfig, OllamaPlugin},
+    postgres::PostgresPlugin,
     surrealdb::SurrealDbPlugin,
     tgi::{TgiConfig, TgiPlugin},
     vllm::{VllmConfig, VllmPlugin},
+    web_server::WebServerPlugin,
 };
 
 /// Service factory for creating plugins from configuration
@@ -66,11 +68,13 @@ impl ServiceFactory {
         match service_type.as_str() {
             "surrealdb" => Self::create_surrealdb_plugin(name, config),
             "generic_container" => Self::create_generic_plugin(name, config),
+            "postgres" => Self::create_postgres_plugin(name, config),
             "ollama" => Self::create_ollama_plugin(name, config),
             "tgi" => Self::create_tgi_plugin(name, config),
             "vllm" => Self::create_vllm_plugin(name, config),
+            "web_server" => Self::create_web_server_plugin(name, config),
             _ => Err(CleanroomError::configuration_error(format!(
-                "Unknown service type: '{}'. Supported types: surrealdb, generic_container, ollama, tgi, vllm",
+                "Unknown service type: '{}'. Supported types: surrealdb, generic_container, postgres, ollama, tgi, vllm, web_server",
                 config.plugin
             ))),
         }
@@ -97,6 +101,61 @@ impl ServiceFactory {
         Ok(Box::new(plugin))
     }
 
+    /// Create a PostgreSQL plugin from configuration
+    fn create_postgres_plugin(
+        name: &str,
+        config: &ServiceConfig,
+    ) -> Result<Box<dyn ServicePlugin>> {
+        let mut plugin = PostgresPlugin::new(name);
+
+        if let Some(ref image) = config.image {
+            plugin = plugin.with_image(image);
+        }
+
+        if let Some(ref database) = config.database {
+            plugin = plugin.with_database(database);
+        }
+
+        if let Some(ref username) = config.username {
+            plugin = plugin.with_user(username);
+        }
+
+        if let Some(ref password) = config.password {
+            plugin = plugin.with_password(password);
+        }
+
+        if let Some(ref init_sql) = config.init_sql {
+            plugin = plugin.with_init_sql(init_sql);
+        }
+
+        Ok(Box::new(plugin))
+    }
+
+    /// Create a web server plugin from configuration
+    ///
+    /// Listens on the first of `ports` (default 8080) and serves `static_dir`
+    /// when set, or an echo endpoint otherwise.
+    fn create_web_server_plugin(
+        name: &str,
+        config: &ServiceConfig,
+    ) -> Result<Box<dyn ServicePlugin>> {
+        let mut plugin = WebServerPlugin::new(name);
+
+        if let Some(ref image) = config.image {
+            plugin = plugin.with_image(image);
+        }
+
+        if let Some(port) = config.ports.as_ref().and_then(|ports| ports.first()) {
+            plugin = plugin.with_port(*port);
+        }
+
+        if let Some(ref static_dir) = config.static_dir {
+            plugin = plugin.with_static_root(static_dir);
+        }
+
+        Ok(Box::new(plugin))
+    }
+
     /// Create a generic container plugin from configuration
     fn create_generic_plugin(name: &str, config: &ServiceConfig) -> Result<Box<dyn ServicePlugin>> {
         // Image is required for generic containers
@@ -141,6 +200,17 @@ impl ServiceFactory {
             }
         }
 
+        // Add resource limits if present
+        if let Some(bytes) = config.memory_limit_bytes()? {
+            plugin = plugin.with_memory_limit(bytes);
+        }
+        if let Some(cpus) = config.cpus {
+            plugin = plugin.with_cpu_limit(cpus);
+        }
+        if let Some(ref health_check) = config.health_check {
+            plugin = plugin.with_health_check(health_check.clone());
+        }
+
         Ok(Box::new(plugin))
     }
 
@@ -3,25 +3,50 @@
 //! Provides a generic container service that can run any Docker image
 //! with configurable environment variables, ports, and commands.
 
+use crate::backend::runtime::{active_backend, ContainerBackend};
 use crate::backend::volume::VolumeMount;
-use crate::cleanroom::{HealthStatus, ServiceHandle, ServicePlugin};
+use crate::cleanroom::{HealthStatus, ServiceHandle, ServiceLogs, ServicePlugin};
+use crate::config::{HealthCheck, HealthCheckConfig};
 use crate::error::{CleanroomError, Result};
+use crate::services::keep::service_labels;
+use crate::services::readiness::wait_for_port;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use testcontainers::bollard::models::{
+    ContainerUpdateBody, EndpointSettings, NetworkConnectRequest, NetworkDisconnectRequest,
+};
+use testcontainers::bollard::query_parameters::InspectContainerOptions;
+use testcontainers::bollard::Docker;
+use testcontainers::core::{CmdWaitFor, ExecCommand};
 use testcontainers::runners::AsyncRunner;
-use testcontainers::{GenericImage, ImageExt};
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Default seconds between health check probes
+const DEFAULT_HEALTH_CHECK_INTERVAL_SECS: u64 = 1;
+
+/// Default seconds to wait for a healthy probe before startup fails
+const DEFAULT_HEALTH_CHECK_TIMEOUT_SECS: u64 = 30;
+
+/// Longest response body kept from a failed probe for the startup error
+const MAX_PROBE_BODY_CHARS: usize = 1024;
+
 #[derive(Debug)]
 pub struct GenericContainerPlugin {
     name: String,
     image: String,
     tag: String,
-    container_id: Arc<RwLock<Option<String>>>,
+    container: Arc<RwLock<Option<ContainerAsync<GenericImage>>>>,
     env_vars: HashMap<String, String>,
     ports: Vec<u16>,
     volumes: Vec<VolumeMount>,
+    memory_limit: Option<u64>,
+    cpu_limit: Option<f64>,
+    health_check: Option<HealthCheckConfig>,
+    network: Option<String>,
+    backend: Arc<dyn ContainerBackend>,
 }
 
 impl GenericContainerPlugin {
@@ -36,10 +61,15 @@ impl GenericContainerPlugin {
             name: name.to_string(),
             image: image_name,
             tag: image_tag,
-            container_id: Arc::new(RwLock::new(None)),
+            container: Arc::new(RwLock::new(None)),
             env_vars: HashMap::new(),
             ports: Vec::new(),
             volumes: Vec::new(),
+            memory_limit: None,
+            cpu_limit: None,
+            health_check: None,
+            network: None,
+            backend: active_backend(),
         }
     }
 
@@ -81,6 +111,263 @@ impl GenericContainerPlugin {
     pub fn with_volume_ro(self, host_path: &str, container_path: &str) -> Result<Self> {
         self.with_volume(host_path, container_path, true)
     }
+
+    /// Limit container memory (swap included) to `bytes`
+    ///
+    /// A container that exceeds the limit is killed by the kernel, and
+    /// stopping the service reports an OOM error.
+    pub fn with_memory_limit(mut self, bytes: u64) -> Self {
+        self.memory_limit = Some(bytes);
+        self
+    }
+
+    /// Limit the container to `cores` CPUs (fractions allowed, e.g. `0.5`)
+    pub fn with_cpu_limit(mut self, cores: f64) -> Self {
+        self.cpu_limit = Some(cores);
+        self
+    }
+
+    /// Only report the service as started once `health_check` succeeds
+    ///
+    /// Startup fails with the last probe's result if the check doesn't pass
+    /// within its timeout.
+    pub fn with_health_check(mut self, health_check: HealthCheckConfig) -> Self {
+        self.health_check = Some(health_check);
+        self
+    }
+
+    /// Run the container on `network` only, reachable there by the service
+    /// name
+    ///
+    /// The container never joins the default bridge, so on an internal
+    /// network it has no outbound access. Ports are not published, so HTTP
+    /// health checks against the host can't reach it; test validation
+    /// rejects them in favour of a `cmd` check.
+    pub fn with_network(mut self, network: &str) -> Self {
+        self.network = Some(network.to_string());
+        self
+    }
+
+    /// Run the container on `backend` instead of the active one
+    pub fn with_backend(mut self, backend: Arc<dyn ContainerBackend>) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Give a freshly started container its service name as an alias on the
+    /// configured network
+    ///
+    /// testcontainers can't set aliases on the create request, so the
+    /// container is reconnected with one.
+    async fn alias_on_network(&self, container: &ContainerAsync<GenericImage>) -> Result<()> {
+        let Some(ref network) = self.network else {
+            return Ok(());
+        };
+
+        let docker = self.backend.client()?;
+        let request = NetworkDisconnectRequest {
+            container: Some(container.id().to_string()),
+            force: Some(true),
+        };
+        docker
+            .disconnect_network(network, request)
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error(format!(
+                    "Failed to reconnect service '{}' to network '{}'",
+                    self.name, network
+                ))
+                .with_source(e.to_string())
+            })?;
+
+        let request = NetworkConnectRequest {
+            container: Some(container.id().to_string()),
+            endpoint_config: Some(EndpointSettings {
+                aliases: Some(vec![self.name.clone()]),
+                ..Default::default()
+            }),
+        };
+        docker.connect_network(network, request).await.map_err(|e| {
+            CleanroomError::container_error(format!(
+                "Failed to connect service '{}' to network '{}'",
+                self.name, network
+            ))
+            .with_source(e.to_string())
+        })
+    }
+
+    /// Apply memory and CPU limits to a freshly started container
+    ///
+    /// testcontainers cannot set resource limits on the create request, so
+    /// they are applied through the Docker update API right after start.
+    async fn apply_resource_limits(&self, container: &ContainerAsync<GenericImage>) -> Result<()> {
+        if self.memory_limit.is_none() && self.cpu_limit.is_none() {
+            return Ok(());
+        }
+
+        let memory = self.memory_limit.map(|bytes| bytes as i64);
+        let update = ContainerUpdateBody {
+            memory,
+            // Equal to memory so the container cannot swap past its limit
+            memory_swap: memory,
+            nano_cpus: self.cpu_limit.map(|cores| (cores * 1e9) as i64),
+            ..Default::default()
+        };
+
+        self.backend
+            .client()?
+            .update_container(container.id(), update)
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error(format!(
+                    "Failed to apply resource limits to service '{}'",
+                    self.name
+                ))
+                .with_context(format!(
+                    "memory: {:?} bytes, cpus: {:?}",
+                    self.memory_limit, self.cpu_limit
+                ))
+                .with_source(e.to_string())
+            })
+    }
+
+    /// Poll the configured health check until it passes
+    async fn wait_until_healthy(&self, container: &ContainerAsync<GenericImage>) -> Result<()> {
+        let Some(ref health_check) = self.health_check else {
+            return Ok(());
+        };
+
+        let interval = Duration::from_secs(
+            health_check
+                .interval
+                .unwrap_or(DEFAULT_HEALTH_CHECK_INTERVAL_SECS),
+        );
+        let timeout_secs = health_check
+            .timeout
+            .unwrap_or(DEFAULT_HEALTH_CHECK_TIMEOUT_SECS);
+        let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+        let client = reqwest::Client::new();
+
+        let mut failures = 0;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            let last_probe =
+                match Self::probe(&health_check.check, container, &client, remaining).await {
+                    Ok(()) => return Ok(()),
+                    Err(outcome) => outcome,
+                };
+            failures += 1;
+
+            let out_of_retries = health_check.retries.is_some_and(|r| failures > r);
+            if out_of_retries || Instant::now() + interval >= deadline {
+                return Err(CleanroomError::timeout_error(format!(
+                    "Service '{}' failed its health check after {} probe(s) ({}s timeout)",
+                    self.name, failures, timeout_secs
+                ))
+                .with_context(format!("Last probe: {}", last_probe)));
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    }
+
+    /// Run one probe, describing the outcome if it didn't pass
+    ///
+    /// HTTP requests give up after `timeout`.
+    async fn probe(
+        check: &HealthCheck,
+        container: &ContainerAsync<GenericImage>,
+        client: &reqwest::Client,
+        timeout: Duration,
+    ) -> std::result::Result<(), String> {
+        match check {
+            HealthCheck::Http {
+                http,
+                expect_status,
+            } => {
+                let response = client
+                    .get(http)
+                    .timeout(timeout)
+                    .send()
+                    .await
+                    .map_err(|e| format!("GET {} failed: {}", http, e))?;
+
+                let status = response.status().as_u16();
+                if status == *expect_status {
+                    return Ok(());
+                }
+
+                let body = response.text().await.unwrap_or_default();
+                Err(format!(
+                    "GET {} returned {} (expected {}): {}",
+                    http,
+                    status,
+                    expect_status,
+                    truncate_body(body.trim())
+                ))
+            }
+            HealthCheck::Command { command } => {
+                let mut result = container
+                    .exec(
+                        ExecCommand::new(command.clone())
+                            .with_cmd_ready_condition(CmdWaitFor::exit()),
+                    )
+                    .await
+                    .map_err(|e| format!("`{}` could not run: {}", command.join(" "), e))?;
+
+                let stdout = result.stdout_to_vec().await.unwrap_or_default();
+                let stderr = result.stderr_to_vec().await.unwrap_or_default();
+                let exit_code = result.exit_code().await.ok().flatten().unwrap_or(-1);
+                if exit_code == 0 {
+                    return Ok(());
+                }
+
+                let output = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&stdout),
+                    String::from_utf8_lossy(&stderr)
+                );
+                Err(format!(
+                    "`{}` exited with code {}: {}",
+                    command.join(" "),
+                    exit_code,
+                    truncate_body(output.trim())
+                ))
+            }
+        }
+    }
+
+    /// Whether the kernel killed the container for exceeding its memory limit
+    async fn was_oom_killed(&self, container: &ContainerAsync<GenericImage>) -> Result<bool> {
+        let inspect = self
+            .backend
+            .client()?
+            .inspect_container(container.id(), None::<InspectContainerOptions>)
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error("Failed to inspect generic container")
+                    .with_source(e.to_string())
+            })?;
+
+        Ok(inspect
+            .state
+            .and_then(|state| state.oom_killed)
+            .unwrap_or(false))
+    }
+}
+
+/// Cap a probe's output so a large error page doesn't swamp the report
+fn truncate_body(body: &str) -> String {
+    match body.char_indices().nth(MAX_PROBE_BODY_CHARS) {
+        Some((end, _)) => format!("{}…", &body[..end]),
+        None => body.to_string(),
+    }
+}
+
+/// API client of the active container backend, for operations testcontainers
+/// does not expose
+pub(crate) fn docker_client() -> Result<Docker> {
+    active_backend().client()
 }
 
 impl ServicePlugin for GenericContainerPlugin {
@@ -92,22 +379,35 @@ impl ServicePlugin for GenericContainerPlugin {
         // Use tokio::task::block_in_place for async operations
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
+                // testcontainers starts the container on whichever runtime
+                // the backend points it at
+                self.backend.connect()?;
+
                 // Create container configuration
                 let image = GenericImage::new(self.image.clone(), self.tag.clone());
 
                 // Build container request with environment variables and ports
                 let mut container_request: testcontainers::core::ContainerRequest<GenericImage> =
-                    image.into();
+                    image.with_labels(service_labels(&self.name));
 
                 // Add environment variables
                 for (key, value) in &self.env_vars {
                     container_request = container_request.with_env_var(key, value);
                 }
 
-                // Add port mappings
-                for port in &self.ports {
-                    container_request = container_request
-                        .with_mapped_port(*port, testcontainers::core::ContainerPort::Tcp(*port));
+                // Add port mappings; nothing is published from a dedicated network
+                match &self.network {
+                    Some(network) => {
+                        container_request = container_request.with_network(network.clone());
+                    }
+                    None => {
+                        for port in &self.ports {
+                            container_request = container_request.with_mapped_port(
+                                *port,
+                                testcontainers::core::ContainerPort::Tcp(*port),
+                            );
+                        }
+                    }
                 }
 
                 // Add volume mounts
@@ -136,6 +436,10 @@ impl ServicePlugin for GenericContainerPlugin {
                         .with_source(e.to_string())
                 })?;
 
+                self.apply_resource_limits(&node).await?;
+                self.alias_on_network(&node).await?;
+                self.wait_until_healthy(&node).await?;
+
                 let mut metadata = HashMap::new();
                 metadata.insert("image".to_string(), format!("{}:{}", self.image, self.tag));
                 metadata.insert("container_type".to_string(), "generic".to_string());
@@ -147,9 +451,9 @@ impl ServicePlugin for GenericContainerPlugin {
                     }
                 }
 
-                // Store container reference
-                let mut container_guard = self.container_id.write().await;
-                *container_guard = Some(format!("generic-{}", Uuid::new_v4()));
+                // Keep the container alive until the service is stopped
+                let mut container_guard = self.container.write().await;
+                *container_guard = Some(node);
 
                 Ok(ServiceHandle {
                     id: Uuid::new_v4().to_string(),
@@ -164,15 +468,43 @@ impl ServicePlugin for GenericContainerPlugin {
         // Use tokio::task::block_in_place for async operations
         tokio::task::block_in_place(|| {
             tokio::runtime::Handle::current().block_on(async {
-                let mut container_guard = self.container_id.write().await;
-                if container_guard.is_some() {
-                    *container_guard = None; // Drop triggers container cleanup
+                let mut container_guard = self.container.write().await;
+                // Drop triggers container cleanup
+                let Some(container) = container_guard.take() else {
+                    return Ok(());
+                };
+
+                if let Some(limit) = self.memory_limit {
+                    if self.was_oom_killed(&container).await? {
+                        return Err(CleanroomError::container_error(format!(
+                            "Service '{}' was OOM-killed: it exceeded its memory limit of {} bytes",
+                            self.name, limit
+                        ))
+                        .with_context("Raise the service's `memory` limit or reduce its usage"));
+                    }
                 }
                 Ok(())
             })
         })
     }
 
+    fn ready_check(&self, handle: &ServiceHandle, timeout: Duration) -> Result<()> {
+        // Every published port must accept connections, within one budget
+        let start_time = Instant::now();
+        for port in &self.ports {
+            let Some(host_port) = handle
+                .metadata
+                .get(&format!("port_{}", port))
+                .and_then(|host_port| host_port.parse().ok())
+            else {
+                continue;
+            };
+            let remaining = timeout.saturating_sub(start_time.elapsed());
+            wait_for_port(&self.name, "127.0.0.1", host_port, remaining)?;
+        }
+        Ok(())
+    }
+
     fn health_check(&self, handle: &ServiceHandle) -> HealthStatus {
         if handle.metadata.contains_key("image") && handle.metadata.contains_key("container_type") {
             HealthStatus::Healthy
@@ -180,4 +512,30 @@ impl ServicePlugin for GenericContainerPlugin {
             HealthStatus::Unknown
         }
     }
+
+    fn logs(&self, _handle: &ServiceHandle) -> Result<ServiceLogs> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let container_guard = self.container.read().await;
+                let container = container_guard.as_ref().ok_or_else(|| {
+                    CleanroomError::service_error(format!("Service '{}' is not running", self.name))
+                })?;
+                ServiceLogs::from_container(container).await
+            })
+        })
+    }
+
+    fn keep_running(&self, _handle: &ServiceHandle) -> Result<Option<String>> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut container_guard = self.container.write().await;
+                Ok(container_guard.take().map(|container| {
+                    let container_id = container.id().to_string();
+                    // Dropping the container would remove it
+                    std::mem::forget(container);
+                    container_id
+                }))
+            })
+        })
+    }
 }
@@ -0,0 +1,297 @@
+//! Service containers kept running after a failed test
+//!
+//! `clnrm run --keep-services` leaves a failed test's containers up for
+//! inspection, and an interrupted run can leave them behind too.
+//! Container-backed plugins label what they start with [`SERVICE_LABEL`] and
+//! [`PROJECT_LABEL`], so `clnrm services down` can find and remove those
+//! containers from a later process.
+
+use crate::cleanroom::ExecutionResult;
+use crate::error::{CleanroomError, Result};
+use crate::services::generic::docker_client;
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Instant;
+use testcontainers::bollard::container::LogOutput;
+use testcontainers::bollard::exec::StartExecResults;
+use testcontainers::bollard::models::{ContainerSummaryStateEnum, ExecConfig};
+use testcontainers::bollard::query_parameters::{
+    InspectContainerOptions, ListContainersOptions, RemoveContainerOptions,
+};
+
+/// Docker label holding the service name on containers clnrm starts
+pub const SERVICE_LABEL: &str = "clnrm.service";
+
+/// Docker label holding the directory clnrm was run from
+pub const PROJECT_LABEL: &str = "clnrm.project";
+
+/// Labels for a service container started by this process
+pub(crate) fn service_labels(service_name: &str) -> [(&'static str, String); 2] {
+    [
+        (SERVICE_LABEL, service_name.to_string()),
+        (PROJECT_LABEL, current_project()),
+    ]
+}
+
+fn current_project() -> String {
+    std::env::current_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_default()
+}
+
+/// Abbreviated form of a container ID, as shown by `docker ps`
+pub fn short_container_id(container_id: &str) -> &str {
+    container_id.get(..12).unwrap_or(container_id)
+}
+
+/// A service container left running
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeptService {
+    /// Service name from the test configuration
+    pub service_name: String,
+    /// Docker container ID
+    pub container_id: String,
+    /// Directory the run that started it was in
+    pub project: String,
+    /// Whether the container is running
+    pub running: bool,
+}
+
+impl KeptService {
+    /// Abbreviated container ID, as shown by `docker ps`
+    pub fn short_id(&self) -> &str {
+        short_container_id(&self.container_id)
+    }
+
+    /// Whether it was started from the current directory
+    pub fn is_in_current_project(&self) -> bool {
+        self.project == current_project()
+    }
+}
+
+/// Every labelled service container, running or not
+///
+/// # Errors
+/// Returns error if the container runtime can't be reached
+pub async fn list_kept_services() -> Result<Vec<KeptService>> {
+    let options = ListContainersOptions {
+        all: true,
+        filters: Some(HashMap::from([(
+            "label".to_string(),
+            vec![SERVICE_LABEL.to_string()],
+        )])),
+        ..Default::default()
+    };
+    let containers = docker_client()?
+        .list_containers(Some(options))
+        .await
+        .map_err(|e| {
+            CleanroomError::container_error("Failed to list service containers")
+                .with_source(e.to_string())
+        })?;
+
+    Ok(containers
+        .into_iter()
+        .filter_map(|container| {
+            let labels = container.labels.unwrap_or_default();
+            Some(KeptService {
+                container_id: container.id?,
+                service_name: labels.get(SERVICE_LABEL).cloned().unwrap_or_default(),
+                project: labels.get(PROJECT_LABEL).cloned().unwrap_or_default(),
+                running: container.state == Some(ContainerSummaryStateEnum::RUNNING),
+            })
+        })
+        .collect())
+}
+
+/// The running container for `service_name`, preferring one started from the
+/// current directory
+///
+/// # Errors
+/// Returns a validation error naming the running services if none matches
+pub fn find_running_service<'a>(
+    services: &'a [KeptService],
+    service_name: &str,
+) -> Result<&'a KeptService> {
+    let mut candidates = services
+        .iter()
+        .filter(|service| service.running && service.service_name == service_name);
+    let found = candidates
+        .clone()
+        .find(|service| service.is_in_current_project())
+        .or_else(|| candidates.next());
+    if let Some(found) = found {
+        return Ok(found);
+    }
+
+    let mut running: Vec<&str> = services
+        .iter()
+        .filter(|service| service.running)
+        .map(|service| service.service_name.as_str())
+        .collect();
+    running.sort_unstable();
+    running.dedup();
+    let hint = if running.is_empty() {
+        "no services are currently running".to_string()
+    } else {
+        format!("running services: {}", running.join(", "))
+    };
+    Err(CleanroomError::validation_error(format!(
+        "Service '{}' is not running ({})",
+        service_name, hint
+    )))
+}
+
+/// Run `command` in a service container and collect its output
+///
+/// The command runs as given, without a shell.
+///
+/// # Errors
+/// Returns error if the exec can't be created or its output can't be read
+pub async fn exec_in_kept_service(
+    service: &KeptService,
+    command: &[String],
+) -> Result<ExecutionResult> {
+    let docker = docker_client()?;
+    let exec_error = |e: testcontainers::bollard::errors::Error| {
+        CleanroomError::container_error(format!(
+            "Failed to execute command in service '{}'",
+            service.service_name
+        ))
+        .with_source(e.to_string())
+    };
+
+    let start = Instant::now();
+    let config = ExecConfig {
+        attach_stdout: Some(true),
+        attach_stderr: Some(true),
+        cmd: Some(command.to_vec()),
+        ..Default::default()
+    };
+    let exec = docker
+        .create_exec(&service.container_id, config)
+        .await
+        .map_err(exec_error)?;
+
+    let mut stdout = String::new();
+    let mut stderr = String::new();
+    if let StartExecResults::Attached { mut output, .. } = docker
+        .start_exec(&exec.id, None)
+        .await
+        .map_err(exec_error)?
+    {
+        while let Some(chunk) = output.next().await {
+            match chunk.map_err(exec_error)? {
+                LogOutput::StdOut { message } => {
+                    stdout.push_str(&String::from_utf8_lossy(&message))
+                }
+                LogOutput::StdErr { message } => {
+                    stderr.push_str(&String::from_utf8_lossy(&message))
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let exit_code = docker
+        .inspect_exec(&exec.id)
+        .await
+        .map_err(exec_error)?
+        .exit_code
+        .map_or(-1, |code| code as i32);
+    let oom_killed = exit_code != 0
+        && docker
+            .inspect_container(&service.container_id, None::<InspectContainerOptions>)
+            .await
+            .map_err(exec_error)?
+            .state
+            .and_then(|state| state.oom_killed)
+            .unwrap_or(false);
+
+    Ok(ExecutionResult {
+        exit_code,
+        oom_killed,
+        stdout,
+        stderr,
+        duration: start.elapsed(),
+        command: command.to_vec(),
+        container_name: service.container_id.clone(),
+    })
+}
+
+/// Whether the container runtime's API answers
+///
+/// Nothing can be left running without one, so callers cleaning up treat an
+/// unreachable runtime as nothing to do.
+pub async fn runtime_reachable() -> bool {
+    match docker_client() {
+        Ok(docker) => docker.ping().await.is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// Outcome of [`remove_kept_services`]
+#[derive(Debug, Default)]
+pub struct RemovedServices {
+    /// Containers that were removed
+    pub removed: Vec<KeptService>,
+    /// Containers that couldn't be removed, with the reason
+    pub failed: Vec<(KeptService, CleanroomError)>,
+}
+
+/// Force-remove labelled service containers started from the current
+/// directory, or from anywhere with `all`
+///
+/// A container that can't be removed doesn't stop the others from being
+/// removed; it is reported in [`RemovedServices::failed`]. This includes
+/// containers a concurrent clnrm run is still using.
+///
+/// # Errors
+/// Returns error if the container runtime can't be reached
+pub async fn remove_kept_services(all: bool) -> Result<RemovedServices> {
+    let docker = docker_client()?;
+    let services = list_kept_services()
+        .await?
+        .into_iter()
+        .filter(|service| all || service.is_in_current_project());
+
+    Ok(remove_each(services, |service| {
+        let docker = docker.clone();
+        let container_id = service.container_id.clone();
+        async move {
+            let options = RemoveContainerOptions {
+                force: true,
+                v: true,
+                ..Default::default()
+            };
+            docker
+                .remove_container(&container_id, Some(options))
+                .await
+                .map_err(|e| {
+                    CleanroomError::container_error("Failed to remove container")
+                        .with_source(e.to_string())
+                })
+        }
+    })
+    .await)
+}
+
+/// Remove each of `services` with `remove`, carrying on past failures
+pub async fn remove_each<F, Fut>(
+    services: impl IntoIterator<Item = KeptService>,
+    mut remove: F,
+) -> RemovedServices
+where
+    F: FnMut(&KeptService) -> Fut,
+    Fut: Future<Output = Result<()>>,
+{
+    let mut outcome = RemovedServices::default();
+    for service in services {
+        match remove(&service).await {
+            Ok(()) => outcome.removed.push(service),
+            Err(e) => outcome.failed.push((service, e)),
+        }
+    }
+    outcome
+}
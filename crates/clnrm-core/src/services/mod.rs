@@ -1,10 +1,13 @@
 pub mod chaos_engine;
 pub mod factory;
 pub mod generic;
+pub mod keep;
 pub mod ollama;
 pub mod otel_collector;
+pub mod postgres;
 pub mod readiness;
 pub mod service_manager;
 pub mod surrealdb;
 pub mod tgi;
 pub mod vllm;
+pub mod web_server;
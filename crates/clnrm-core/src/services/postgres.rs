@@ -0,0 +1,464 @@
+//! PostgreSQL service plugin
+//!
+//! Runs a PostgreSQL container with configurable credentials and an optional
+//! initialization SQL file that is applied once the server accepts connections.
+//!
+//! Readiness is determined by running `pg_isready` inside the container over
+//! TCP, which only succeeds once the entrypoint's temporary init server has
+//! shut down and the real server is listening.
+//!
+//! Queries from database assertions run through `psql` in the container too,
+//! so they need neither a client driver nor the mapped port.
+
+use crate::cleanroom::{HealthStatus, ServiceHandle, ServiceLogs, ServicePlugin};
+use crate::error::{CleanroomError, Result};
+use crate::services::keep::service_labels;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use testcontainers::core::{CmdWaitFor, ExecCommand, IntoContainerPort};
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Default PostgreSQL image
+pub(crate) const DEFAULT_POSTGRES_IMAGE: &str = "postgres";
+
+/// Default PostgreSQL image tag
+pub(crate) const DEFAULT_POSTGRES_TAG: &str = "15";
+
+/// PostgreSQL server port
+const POSTGRES_PORT: u16 = 5432;
+
+/// Location the init SQL file is copied to inside the container
+const INIT_SQL_CONTAINER_PATH: &str = "/tmp/clnrm-init.sql";
+
+/// Maximum time to wait for the server to accept connections
+const READY_TIMEOUT_SECS: u64 = 60;
+
+/// Poll interval for readiness checks
+const READY_POLL_INTERVAL_MS: u64 = 500;
+
+/// Column separator in `psql` query output; the ASCII unit separator, which
+/// doesn't occur in ordinary column values
+const QUERY_FIELD_SEPARATOR: char = '\u{1f}';
+
+/// Output of a command run in the container
+#[derive(Debug)]
+struct ExecOutput {
+    exit_code: i64,
+    stdout: String,
+    stderr: String,
+}
+
+/// Running container state
+#[derive(Debug)]
+struct PostgresInstance {
+    container: ContainerAsync<GenericImage>,
+    host_port: u16,
+}
+
+/// PostgreSQL service plugin
+///
+/// Init SQL is applied at most once per running container: calling `start`
+/// again while the container is up reuses it instead of re-applying the
+/// migrations.
+#[derive(Debug)]
+pub struct PostgresPlugin {
+    name: String,
+    image: String,
+    tag: String,
+    database: String,
+    user: String,
+    password: String,
+    init_sql: Option<PathBuf>,
+    instance: Arc<RwLock<Option<PostgresInstance>>>,
+}
+
+impl Default for PostgresPlugin {
+    fn default() -> Self {
+        Self::new("postgres")
+    }
+}
+
+impl PostgresPlugin {
+    /// Create a new PostgreSQL plugin using `postgres:15`
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            image: DEFAULT_POSTGRES_IMAGE.to_string(),
+            tag: DEFAULT_POSTGRES_TAG.to_string(),
+            database: "postgres".to_string(),
+            user: "postgres".to_string(),
+            password: "postgres".to_string(),
+            init_sql: None,
+            instance: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Use a custom image (e.g. `postgres:16-alpine`)
+    ///
+    /// The tag follows the last `:` after the last `/`, so registry ports
+    /// (`host:5000/postgres:16`) are kept in the image name.
+    pub fn with_image(mut self, image: &str) -> Self {
+        let (image_name, image_tag) = match image.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, tag),
+            _ => (image, "latest"),
+        };
+        self.image = image_name.to_string();
+        self.tag = image_tag.to_string();
+        self
+    }
+
+    /// Image name and tag the container is started from
+    pub fn image(&self) -> (&str, &str) {
+        (&self.image, &self.tag)
+    }
+
+    /// Set the database created on startup
+    pub fn with_database(mut self, database: &str) -> Self {
+        self.database = database.to_string();
+        self
+    }
+
+    /// Set the superuser name
+    pub fn with_user(mut self, user: &str) -> Self {
+        self.user = user.to_string();
+        self
+    }
+
+    /// Set the superuser password
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.password = password.to_string();
+        self
+    }
+
+    /// Set a `.sql` file to execute once the server is ready
+    ///
+    /// A relative path is resolved against the working directory; `clnrm run`
+    /// passes paths already resolved against the test file's directory.
+    pub fn with_init_sql(mut self, path: impl AsRef<Path>) -> Self {
+        self.init_sql = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    fn connection_string(&self, host_port: u16) -> String {
+        format!(
+            "postgresql://{}:{}@127.0.0.1:{}/{}",
+            self.user, self.password, host_port, self.database
+        )
+    }
+
+    fn build_handle(&self, host_port: u16) -> ServiceHandle {
+        let mut metadata = HashMap::new();
+        metadata.insert("host".to_string(), "127.0.0.1".to_string());
+        metadata.insert("port".to_string(), host_port.to_string());
+        metadata.insert("database".to_string(), self.database.clone());
+        metadata.insert("username".to_string(), self.user.clone());
+        metadata.insert("database_type".to_string(), "postgres".to_string());
+        metadata.insert("image".to_string(), format!("{}:{}", self.image, self.tag));
+        metadata.insert(
+            "connection_string".to_string(),
+            self.connection_string(host_port),
+        );
+        if let Some(path) = &self.init_sql {
+            metadata.insert("init_sql".to_string(), path.display().to_string());
+        }
+
+        ServiceHandle {
+            id: Uuid::new_v4().to_string(),
+            service_name: self.name.clone(),
+            metadata,
+        }
+    }
+
+    /// Run a command in the container and wait for it to exit
+    async fn exec(
+        container: &ContainerAsync<GenericImage>,
+        cmd: Vec<String>,
+    ) -> Result<ExecOutput> {
+        let mut result = container
+            .exec(ExecCommand::new(cmd).with_cmd_ready_condition(CmdWaitFor::exit()))
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error("Failed to execute command in postgres container")
+                    .with_source(e.to_string())
+            })?;
+
+        let stdout = result.stdout_to_vec().await.map_err(|e| {
+            CleanroomError::container_error("Failed to read command stdout")
+                .with_source(e.to_string())
+        })?;
+        let stderr = result.stderr_to_vec().await.map_err(|e| {
+            CleanroomError::container_error("Failed to read command stderr")
+                .with_source(e.to_string())
+        })?;
+
+        let exit_code = result
+            .exit_code()
+            .await
+            .map_err(|e| {
+                CleanroomError::container_error("Failed to read command exit code")
+                    .with_source(e.to_string())
+            })?
+            .unwrap_or(-1);
+
+        Ok(ExecOutput {
+            exit_code,
+            stdout: String::from_utf8_lossy(&stdout).to_string(),
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
+        })
+    }
+
+    fn pg_isready_cmd(&self) -> Vec<String> {
+        vec![
+            "pg_isready".to_string(),
+            "-h".to_string(),
+            "127.0.0.1".to_string(),
+            "-p".to_string(),
+            POSTGRES_PORT.to_string(),
+            "-U".to_string(),
+            self.user.clone(),
+            "-d".to_string(),
+            self.database.clone(),
+        ]
+    }
+
+    /// Poll `pg_isready` until the server accepts TCP connections
+    async fn wait_until_ready(&self, container: &ContainerAsync<GenericImage>) -> Result<()> {
+        let start = Instant::now();
+        let timeout = Duration::from_secs(READY_TIMEOUT_SECS);
+
+        loop {
+            let output = Self::exec(container, self.pg_isready_cmd()).await?;
+            if output.exit_code == 0 {
+                return Ok(());
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(CleanroomError::timeout_error(format!(
+                    "PostgreSQL service '{}' not ready within {} seconds",
+                    self.name, READY_TIMEOUT_SECS
+                ))
+                .with_context("Service readiness check"));
+            }
+
+            tokio::time::sleep(Duration::from_millis(READY_POLL_INTERVAL_MS)).await;
+        }
+    }
+
+    /// Execute the copied init SQL file with `psql`, stopping on the first error
+    async fn apply_init_sql(&self, container: &ContainerAsync<GenericImage>) -> Result<()> {
+        let cmd = vec![
+            "psql".to_string(),
+            "-v".to_string(),
+            "ON_ERROR_STOP=1".to_string(),
+            "-U".to_string(),
+            self.user.clone(),
+            "-d".to_string(),
+            self.database.clone(),
+            "-f".to_string(),
+            INIT_SQL_CONTAINER_PATH.to_string(),
+        ];
+
+        let output = Self::exec(container, cmd).await?;
+        if output.exit_code != 0 {
+            return Err(CleanroomError::service_error(format!(
+                "PostgreSQL service '{}': init SQL failed with exit code {}",
+                self.name, output.exit_code
+            ))
+            .with_context("Init SQL execution")
+            .with_source(output.stderr.trim().to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Run one SQL statement with `psql`, printing unaligned rows without
+    /// headers or footers
+    fn psql_query_cmd(&self, sql: &str) -> Vec<String> {
+        vec![
+            "psql".to_string(),
+            "-X".to_string(),
+            "-q".to_string(),
+            "-A".to_string(),
+            "-t".to_string(),
+            "-v".to_string(),
+            "ON_ERROR_STOP=1".to_string(),
+            "-F".to_string(),
+            QUERY_FIELD_SEPARATOR.to_string(),
+            "-U".to_string(),
+            self.user.clone(),
+            "-d".to_string(),
+            self.database.clone(),
+            "-c".to_string(),
+            sql.to_string(),
+        ]
+    }
+}
+
+impl ServicePlugin for PostgresPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        // Use tokio::task::block_in_place for async operations
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut instance_guard = self.instance.write().await;
+
+                // Reuse the running container so init SQL is never applied twice
+                if let Some(instance) = instance_guard.as_ref() {
+                    return Ok(self.build_handle(instance.host_port));
+                }
+
+                let image = GenericImage::new(self.image.clone(), self.tag.clone())
+                    .with_exposed_port(POSTGRES_PORT.tcp());
+
+                let mut container_request = image
+                    .with_env_var("POSTGRES_DB", &self.database)
+                    .with_env_var("POSTGRES_USER", &self.user)
+                    .with_env_var("POSTGRES_PASSWORD", &self.password)
+                    .with_labels(service_labels(&self.name));
+
+                if let Some(path) = &self.init_sql {
+                    if !path.is_file() {
+                        return Err(CleanroomError::service_error(format!(
+                            "PostgreSQL service '{}': init SQL file not found: {}",
+                            self.name,
+                            path.display()
+                        )));
+                    }
+                    container_request =
+                        container_request.with_copy_to(INIT_SQL_CONTAINER_PATH, path.clone());
+                }
+
+                let container = container_request.start().await.map_err(|e| {
+                    CleanroomError::container_error("Failed to start PostgreSQL container")
+                        .with_context("Container startup failed")
+                        .with_source(e.to_string())
+                })?;
+
+                let host_port = container
+                    .get_host_port_ipv4(POSTGRES_PORT)
+                    .await
+                    .map_err(|e| {
+                        CleanroomError::container_error("Failed to get container port")
+                            .with_source(e.to_string())
+                    })?;
+
+                self.wait_until_ready(&container).await?;
+
+                if self.init_sql.is_some() {
+                    self.apply_init_sql(&container).await?;
+                }
+
+                *instance_guard = Some(PostgresInstance {
+                    container,
+                    host_port,
+                });
+
+                Ok(self.build_handle(host_port))
+            })
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        // Use tokio::task::block_in_place for async operations
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut instance_guard = self.instance.write().await;
+                if instance_guard.is_some() {
+                    *instance_guard = None; // Drop triggers container cleanup
+                }
+                Ok(())
+            })
+        })
+    }
+
+    fn health_check(&self, handle: &ServiceHandle) -> HealthStatus {
+        if !handle.metadata.contains_key("connection_string") {
+            return HealthStatus::Unknown;
+        }
+
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let instance_guard = self.instance.read().await;
+                let Some(instance) = instance_guard.as_ref() else {
+                    return HealthStatus::Unknown;
+                };
+
+                match Self::exec(&instance.container, self.pg_isready_cmd()).await {
+                    Ok(output) if output.exit_code == 0 => HealthStatus::Healthy,
+                    Ok(_) | Err(_) => HealthStatus::Unhealthy,
+                }
+            })
+        })
+    }
+
+    fn logs(&self, _handle: &ServiceHandle) -> Result<ServiceLogs> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let instance_guard = self.instance.read().await;
+                let instance = instance_guard.as_ref().ok_or_else(|| {
+                    CleanroomError::service_error(format!(
+                        "PostgreSQL service '{}' is not running",
+                        self.name
+                    ))
+                })?;
+                ServiceLogs::from_container(&instance.container).await
+            })
+        })
+    }
+
+    fn query(&self, _handle: &ServiceHandle, sql: &str) -> Result<Vec<Vec<String>>> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let instance_guard = self.instance.read().await;
+                let instance = instance_guard.as_ref().ok_or_else(|| {
+                    CleanroomError::service_error(format!(
+                        "PostgreSQL service '{}' is not running",
+                        self.name
+                    ))
+                })?;
+
+                let output = Self::exec(&instance.container, self.psql_query_cmd(sql)).await?;
+                if output.exit_code != 0 {
+                    return Err(CleanroomError::service_error(format!(
+                        "PostgreSQL service '{}': query failed with exit code {}",
+                        self.name, output.exit_code
+                    ))
+                    .with_context(format!("Query: {}", sql))
+                    .with_source(output.stderr.trim().to_string()));
+                }
+
+                Ok(output
+                    .stdout
+                    .lines()
+                    .map(|line| {
+                        line.split(QUERY_FIELD_SEPARATOR)
+                            .map(str::to_string)
+                            .collect()
+                    })
+                    .collect())
+            })
+        })
+    }
+
+    fn keep_running(&self, _handle: &ServiceHandle) -> Result<Option<String>> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut instance_guard = self.instance.write().await;
+                Ok(instance_guard.take().map(|instance| {
+                    let container_id = instance.container.id().to_string();
+                    // Dropping the container would remove it
+                    std::mem::forget(instance.container);
+                    container_id
+                }))
+            })
+        })
+    }
+}
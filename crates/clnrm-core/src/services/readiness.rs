@@ -1,10 +1,16 @@
-//! Service readiness checks based on OTEL span detection
+//! Service readiness checks
 //!
 //! This module provides span-based health checking for services.
 //! Services can specify a span name to wait for before being marked ready,
 //! enabling precise synchronization based on actual service behavior.
+//!
+//! It also provides [`wait_for_port`], which plugins use in
+//! `ServicePlugin::ready_check` to block until a published port accepts
+//! connections.
 
 use crate::error::{CleanroomError, Result};
+use std::io::Read;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
@@ -215,3 +221,70 @@ async fn check_span_in_otlp_grpc(_span_name: &str, endpoint: &str) -> Result<boo
     );
     Ok(false)
 }
+
+/// Poll interval for checking whether a port accepts connections
+const PORT_POLL_INTERVAL_MS: u64 = 100;
+
+/// How long a connection must stay open to count as accepted
+const PORT_ACCEPT_GRACE_MS: u64 = 100;
+
+/// Block until `host:port` accepts connections, or fail after `timeout`
+///
+/// # Errors
+///
+/// Returns a timeout error naming `service` if the port doesn't accept a
+/// connection in time.
+pub fn wait_for_port(service: &str, host: &str, port: u16, timeout: Duration) -> Result<()> {
+    let start_time = Instant::now();
+    loop {
+        let addrs: Vec<SocketAddr> = (host, port)
+            .to_socket_addrs()
+            .map(Iterator::collect)
+            .unwrap_or_default();
+        if addrs.iter().any(accepts_connection) {
+            tracing::debug!(
+                service = %service,
+                port,
+                elapsed_ms = start_time.elapsed().as_millis(),
+                "Service ready: port accepts connections"
+            );
+            return Ok(());
+        }
+
+        if start_time.elapsed() >= timeout {
+            return Err(CleanroomError::timeout_error(format!(
+                "Service '{}' did not accept connections on {}:{} within {}ms",
+                service,
+                host,
+                port,
+                timeout.as_millis()
+            ))
+            .with_context("Service readiness check"));
+        }
+        std::thread::sleep(Duration::from_millis(PORT_POLL_INTERVAL_MS));
+    }
+}
+
+/// Whether a connection to `addr` is accepted by the service itself
+///
+/// Docker's userland proxy accepts connections on a published port before
+/// anything listens in the container, then closes them straight away. So a
+/// connection only counts once it stays open briefly or receives data.
+fn accepts_connection(addr: &SocketAddr) -> bool {
+    let grace = Duration::from_millis(PORT_ACCEPT_GRACE_MS);
+    let Ok(mut stream) = TcpStream::connect_timeout(addr, grace * 10) else {
+        return false;
+    };
+    if stream.set_read_timeout(Some(grace)).is_err() {
+        return false;
+    }
+
+    match stream.read(&mut [0u8; 1]) {
+        Ok(0) => false,
+        Ok(_) => true,
+        Err(e) => matches!(
+            e.kind(),
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+        ),
+    }
+}
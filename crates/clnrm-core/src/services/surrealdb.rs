@@ -7,16 +7,27 @@ use crate::cleanroom::{HealthStatus, ServiceHandle, ServicePlugin};
 use crate::error::{CleanroomError, Result};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use surrealdb::{
     engine::remote::ws::{Client, Ws},
     opt::auth::Root,
     Surreal,
 };
 use testcontainers::runners::AsyncRunner;
+use testcontainers::Image;
 use testcontainers_modules::surrealdb::{SurrealDb, SURREALDB_PORT};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// Delay between connection attempts while waiting for SurrealDB to be ready
+const READY_POLL_INTERVAL_MS: u64 = 200;
+
+/// Image the SurrealDB container is started from
+pub(crate) fn default_image() -> String {
+    let image = SurrealDb::default();
+    format!("{}:{}", image.name(), image.tag())
+}
+
 #[derive(Debug)]
 pub struct SurrealDbPlugin {
     name: String,
@@ -105,9 +116,6 @@ impl ServicePlugin for SurrealDbPlugin {
                         .with_source(e.to_string())
                 })?;
 
-                // Verify connection works
-                self.verify_connection(host_port).await?;
-
                 let mut container_guard = self.container_id.write().await;
                 *container_guard = Some(format!("container-{}", host_port));
 
@@ -143,6 +151,42 @@ impl ServicePlugin for SurrealDbPlugin {
         })
     }
 
+    fn ready_check(&self, handle: &ServiceHandle, timeout: Duration) -> Result<()> {
+        let host_port: u16 = handle
+            .metadata
+            .get("port")
+            .and_then(|port| port.parse().ok())
+            .ok_or_else(|| {
+                CleanroomError::service_error(format!(
+                    "Service '{}' has no port to check readiness on",
+                    self.name
+                ))
+            })?;
+
+        // Ready once a client can connect and sign in
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let start_time = Instant::now();
+                loop {
+                    let error = match self.verify_connection(host_port).await {
+                        Ok(()) => return Ok(()),
+                        Err(e) => e,
+                    };
+                    if start_time.elapsed() >= timeout {
+                        return Err(CleanroomError::timeout_error(format!(
+                            "Service '{}' was not ready within {}ms",
+                            self.name,
+                            timeout.as_millis()
+                        ))
+                        .with_context("Service readiness check")
+                        .with_source(error.to_string()));
+                    }
+                    tokio::time::sleep(Duration::from_millis(READY_POLL_INTERVAL_MS)).await;
+                }
+            })
+        })
+    }
+
     fn health_check(&self, handle: &ServiceHandle) -> HealthStatus {
         if handle.metadata.contains_key("port") && handle.metadata.contains_key("connection_string")
         {
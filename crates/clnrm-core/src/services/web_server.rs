@@ -0,0 +1,347 @@
+//! Web server service plugin
+//!
+//! Runs a small HTTP server in a Python container, as a fixture for tests
+//! that exercise HTTP clients or response assertions. It serves either a
+//! static directory copied into the container, or, by default, an echo
+//! endpoint that answers every request with a JSON description of it:
+//!
+//! ```json
+//! {"method": "POST", "path": "/users?id=1", "headers": {...}, "body": "..."}
+//! ```
+//!
+//! The server listens on the chosen port in the container, published on a
+//! free host port, and readiness is determined by an HTTP GET from the host.
+
+use crate::cleanroom::{HealthStatus, ServiceHandle, ServiceLogs, ServicePlugin};
+use crate::error::{CleanroomError, Result};
+use crate::services::keep::service_labels;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use testcontainers::core::IntoContainerPort;
+use testcontainers::runners::AsyncRunner;
+use testcontainers::{ContainerAsync, GenericImage, ImageExt};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Default web server image
+pub(crate) const DEFAULT_WEB_SERVER_IMAGE: &str = "python";
+
+/// Default web server image tag
+pub(crate) const DEFAULT_WEB_SERVER_TAG: &str = "3.12-alpine";
+
+/// Default port the server listens on
+pub const DEFAULT_WEB_SERVER_PORT: u16 = 8080;
+
+/// Location the static directory is copied to inside the container
+const STATIC_ROOT_CONTAINER_PATH: &str = "/srv/www";
+
+/// Maximum time to wait for the server to answer requests
+const READY_TIMEOUT_SECS: u64 = 60;
+
+/// Poll interval for readiness checks
+const READY_POLL_INTERVAL_MS: u64 = 500;
+
+/// Timeout for a single readiness or health check request
+const PROBE_TIMEOUT_MS: u64 = 2000;
+
+/// Echo server, run with `python3 -c`; reads its port from `PORT`
+const ECHO_SERVER_SCRIPT: &str = r#"
+import json, os
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+
+class Echo(BaseHTTPRequestHandler):
+    def echo(self):
+        length = int(self.headers.get("Content-Length") or 0)
+        body = json.dumps({
+            "method": self.command,
+            "path": self.path,
+            "headers": dict(self.headers),
+            "body": self.rfile.read(length).decode("utf-8", "replace"),
+        }).encode()
+        self.send_response(200)
+        self.send_header("Content-Type", "application/json")
+        self.send_header("Content-Length", str(len(body)))
+        self.end_headers()
+        self.wfile.write(body)
+
+    do_GET = do_POST = do_PUT = do_PATCH = do_DELETE = echo
+
+ThreadingHTTPServer(("0.0.0.0", int(os.environ["PORT"])), Echo).serve_forever()
+"#;
+
+/// Running container state
+#[derive(Debug)]
+struct WebServerInstance {
+    container: ContainerAsync<GenericImage>,
+    host_port: u16,
+}
+
+/// Web server service plugin
+#[derive(Debug)]
+pub struct WebServerPlugin {
+    name: String,
+    image: String,
+    tag: String,
+    port: u16,
+    static_root: Option<PathBuf>,
+    instance: Arc<RwLock<Option<WebServerInstance>>>,
+}
+
+impl Default for WebServerPlugin {
+    fn default() -> Self {
+        Self::new("web_server")
+    }
+}
+
+impl WebServerPlugin {
+    /// Create a new web server plugin serving the echo endpoint on port 8080
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            image: DEFAULT_WEB_SERVER_IMAGE.to_string(),
+            tag: DEFAULT_WEB_SERVER_TAG.to_string(),
+            port: DEFAULT_WEB_SERVER_PORT,
+            static_root: None,
+            instance: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    /// Use a custom image (e.g. `python:3.13-slim`); it must provide `python3`
+    ///
+    /// The tag follows the last `:` after the last `/`, so registry ports
+    /// (`host:5000/img:tag`) are kept in the image name.
+    pub fn with_image(mut self, image: &str) -> Self {
+        let (image_name, image_tag) = match image.rsplit_once(':') {
+            Some((name, tag)) if !tag.contains('/') => (name, tag),
+            _ => (image, "latest"),
+        };
+        self.image = image_name.to_string();
+        self.tag = image_tag.to_string();
+        self
+    }
+
+    /// Image name and tag the container is started from
+    pub fn image(&self) -> (&str, &str) {
+        (&self.image, &self.tag)
+    }
+
+    /// Set the port the server listens on in the container
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Serve the files in `dir` instead of the echo endpoint
+    pub fn with_static_root(mut self, dir: impl AsRef<Path>) -> Self {
+        self.static_root = Some(dir.as_ref().to_path_buf());
+        self
+    }
+
+    fn url(host_port: u16) -> String {
+        format!("http://127.0.0.1:{}", host_port)
+    }
+
+    fn server_cmd(&self) -> Vec<String> {
+        match self.static_root {
+            Some(_) => vec![
+                "python3".to_string(),
+                "-m".to_string(),
+                "http.server".to_string(),
+                self.port.to_string(),
+                "--bind".to_string(),
+                "0.0.0.0".to_string(),
+                "--directory".to_string(),
+                STATIC_ROOT_CONTAINER_PATH.to_string(),
+            ],
+            None => vec![
+                "python3".to_string(),
+                "-c".to_string(),
+                ECHO_SERVER_SCRIPT.to_string(),
+            ],
+        }
+    }
+
+    fn build_handle(&self, host_port: u16) -> ServiceHandle {
+        let mut metadata = HashMap::new();
+        metadata.insert("host".to_string(), "127.0.0.1".to_string());
+        metadata.insert("port".to_string(), host_port.to_string());
+        metadata.insert("container_port".to_string(), self.port.to_string());
+        metadata.insert("url".to_string(), Self::url(host_port));
+        metadata.insert("image".to_string(), format!("{}:{}", self.image, self.tag));
+        match &self.static_root {
+            Some(dir) => {
+                metadata.insert("mode".to_string(), "static".to_string());
+                metadata.insert("static_root".to_string(), dir.display().to_string());
+            }
+            None => {
+                metadata.insert("mode".to_string(), "echo".to_string());
+            }
+        }
+
+        ServiceHandle {
+            id: Uuid::new_v4().to_string(),
+            service_name: self.name.clone(),
+            metadata,
+        }
+    }
+
+    /// GET `/` from the host, succeeding on any response below 500
+    async fn probe(client: &reqwest::Client, url: &str) -> std::result::Result<(), String> {
+        let response = client
+            .get(url)
+            .timeout(Duration::from_millis(PROBE_TIMEOUT_MS))
+            .send()
+            .await
+            .map_err(|e| format!("GET {} failed: {}", url, e))?;
+
+        let status = response.status();
+        if status.is_server_error() {
+            return Err(format!("GET {} returned {}", url, status.as_u16()));
+        }
+        Ok(())
+    }
+
+    /// Poll the server until it answers requests
+    async fn wait_until_ready(&self, host_port: u16) -> Result<()> {
+        let url = Self::url(host_port);
+        let start = Instant::now();
+        let timeout = Duration::from_secs(READY_TIMEOUT_SECS);
+        let client = reqwest::Client::new();
+
+        loop {
+            let last_probe = match Self::probe(&client, &url).await {
+                Ok(()) => return Ok(()),
+                Err(outcome) => outcome,
+            };
+
+            if start.elapsed() >= timeout {
+                return Err(CleanroomError::timeout_error(format!(
+                    "Web server service '{}' not ready within {} seconds",
+                    self.name, READY_TIMEOUT_SECS
+                ))
+                .with_context(format!("Last probe: {}", last_probe)));
+            }
+
+            tokio::time::sleep(Duration::from_millis(READY_POLL_INTERVAL_MS)).await;
+        }
+    }
+}
+
+impl ServicePlugin for WebServerPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        // Use tokio::task::block_in_place for async operations
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut instance_guard = self.instance.write().await;
+                if let Some(instance) = instance_guard.as_ref() {
+                    return Ok(self.build_handle(instance.host_port));
+                }
+
+                let mut container_request = GenericImage::new(self.image.clone(), self.tag.clone())
+                    .with_exposed_port(self.port.tcp())
+                    .with_cmd(self.server_cmd())
+                    .with_env_var("PORT", self.port.to_string())
+                    .with_labels(service_labels(&self.name));
+
+                if let Some(dir) = &self.static_root {
+                    if !dir.is_dir() {
+                        return Err(CleanroomError::service_error(format!(
+                            "Web server service '{}': static root is not a directory: {}",
+                            self.name,
+                            dir.display()
+                        )));
+                    }
+                    container_request =
+                        container_request.with_copy_to(STATIC_ROOT_CONTAINER_PATH, dir.clone());
+                }
+
+                let container = container_request.start().await.map_err(|e| {
+                    CleanroomError::container_error("Failed to start web server container")
+                        .with_context("Container startup failed")
+                        .with_source(e.to_string())
+                })?;
+
+                let host_port = container.get_host_port_ipv4(self.port).await.map_err(|e| {
+                    CleanroomError::container_error("Failed to get container port")
+                        .with_source(e.to_string())
+                })?;
+
+                self.wait_until_ready(host_port).await?;
+
+                *instance_guard = Some(WebServerInstance {
+                    container,
+                    host_port,
+                });
+
+                Ok(self.build_handle(host_port))
+            })
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        // Use tokio::task::block_in_place for async operations
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut instance_guard = self.instance.write().await;
+                if instance_guard.is_some() {
+                    *instance_guard = None; // Drop triggers container cleanup
+                }
+                Ok(())
+            })
+        })
+    }
+
+    fn health_check(&self, handle: &ServiceHandle) -> HealthStatus {
+        let Some(url) = handle.metadata.get("url") else {
+            return HealthStatus::Unknown;
+        };
+
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                if self.instance.read().await.is_none() {
+                    return HealthStatus::Unknown;
+                }
+
+                match Self::probe(&reqwest::Client::new(), url).await {
+                    Ok(()) => HealthStatus::Healthy,
+                    Err(_) => HealthStatus::Unhealthy,
+                }
+            })
+        })
+    }
+
+    fn logs(&self, _handle: &ServiceHandle) -> Result<ServiceLogs> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let instance_guard = self.instance.read().await;
+                let instance = instance_guard.as_ref().ok_or_else(|| {
+                    CleanroomError::service_error(format!(
+                        "Web server service '{}' is not running",
+                        self.name
+                    ))
+                })?;
+                ServiceLogs::from_container(&instance.container).await
+            })
+        })
+    }
+
+    fn keep_running(&self, _handle: &ServiceHandle) -> Result<Option<String>> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async {
+                let mut instance_guard = self.instance.write().await;
+                Ok(instance_guard.take().map(|instance| {
+                    let container_id = instance.container.id().to_string();
+                    // Dropping the container would remove it
+                    std::mem::forget(instance.container);
+                    container_id
+                }))
+            })
+        })
+    }
+}
@@ -9,6 +9,7 @@ pub mod json_exporter;
 pub mod config;
 pub mod exporters;
 pub mod init;
+pub mod test_run;
 pub mod testing;
 
 use {
@@ -103,6 +104,18 @@ impl Drop for OtelGuard {
 
 /// Install OTel + tracing-subscriber. Call once at process start.
 pub fn init_otel(cfg: OtelConfig) -> Result<OtelGuard, CleanroomError> {
+    let (guard, dispatch) = otel_dispatch(cfg)?;
+    tracing::dispatcher::set_global_default(dispatch).ok();
+    Ok(guard)
+}
+
+/// Set up OTel and return the tracing dispatcher that exports to it,
+/// without installing it globally.
+///
+/// For commands that start exporting after logging already owns the global
+/// subscriber: attach the dispatcher to a future with
+/// [`tracing::instrument::WithSubscriber`].
+pub fn otel_dispatch(cfg: OtelConfig) -> Result<(OtelGuard, tracing::Dispatch), CleanroomError> {
     // Propagators: W3C tracecontext + baggage.
     global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
         Box::new(TraceContextPropagator::new()),
@@ -127,19 +140,14 @@ pub fn init_otel(cfg: OtelConfig) -> Result<OtelGuard, CleanroomError> {
     // Exporter (traces).
     let span_exporter = match cfg.export {
         Export::OtlpHttp { endpoint } => {
+            use opentelemetry_otlp::WithHttpConfig;
+
             // OTLP HTTP exporter - use environment variables for configuration
             std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", endpoint);
 
-            // Set custom headers if provided
-            if let Some(ref headers) = cfg.headers {
-                for (key, value) in headers {
-                    let env_key = format!("OTEL_EXPORTER_OTLP_HEADERS_{}", key.to_uppercase());
-                    std::env::set_var(env_key, value);
-                }
-            }
-
             let exporter = opentelemetry_otlp::SpanExporter::builder()
                 .with_http()
+                .with_headers(cfg.headers.clone().unwrap_or_default())
                 .build()
                 .map_err(|e| {
                     CleanroomError::internal_error(format!(
@@ -150,19 +158,15 @@ pub fn init_otel(cfg: OtelConfig) -> Result<OtelGuard, CleanroomError> {
             SpanExporterType::Otlp(Box::new(exporter))
         }
         Export::OtlpGrpc { endpoint } => {
+            use opentelemetry_otlp::WithTonicConfig;
+
             // OTLP gRPC exporter - use environment variables for configuration
             std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", endpoint);
 
-            // Set custom headers if provided
-            if let Some(ref headers) = cfg.headers {
-                for (key, value) in headers {
-                    let env_key = format!("OTEL_EXPORTER_OTLP_HEADERS_{}", key.to_uppercase());
-                    std::env::set_var(env_key, value);
-                }
-            }
-
+            let metadata = exporters::otlp_grpc_metadata(&cfg.headers.clone().unwrap_or_default())?;
             let exporter = opentelemetry_otlp::SpanExporter::builder()
                 .with_tonic()
+                .with_metadata(metadata)
                 .build()
                 .map_err(|e| {
                     CleanroomError::internal_error(format!(
@@ -200,8 +204,6 @@ pub fn init_otel(cfg: OtelConfig) -> Result<OtelGuard, CleanroomError> {
         .with(otel_layer)
         .with(fmt_layer);
 
-    tracing::subscriber::set_global_default(subscriber).ok();
-
     // Initialize metrics provider if enabled
     let meter_provider = {
         use opentelemetry_sdk::metrics::SdkMeterProvider;
@@ -232,11 +234,14 @@ pub fn init_otel(cfg: OtelConfig) -> Result<OtelGuard, CleanroomError> {
     // Note: For logs, we use the logger provider through the OtelGuard
     // The global logger provider is set when needed through specific log operations
 
-    Ok(OtelGuard {
-        tracer_provider: tp,
-        meter_provider,
-        logger_provider,
-    })
+    Ok((
+        OtelGuard {
+            tracer_provider: tp,
+            meter_provider,
+            logger_provider,
+        },
+        tracing::Dispatch::new(subscriber),
+    ))
 }
 
 /// Validation utilities for OpenTelemetry testing
@@ -412,6 +417,32 @@ pub mod spans {
         )
     }
 
+    /// Create span for a framework self-test suite
+    /// Parent of the spans of the suite's tests
+    pub fn self_test_suite_span(suite_name: &str) -> tracing::Span {
+        span!(
+            Level::INFO,
+            "clnrm.self_test.suite",
+            suite.name = suite_name,
+            otel.kind = "internal",
+            component = "self_test",
+        )
+    }
+
+    /// Create span for a single framework self-test
+    /// `test.result` is recorded as "pass" or "fail" once the test finishes
+    pub fn self_test_span(test_name: &str) -> tracing::Span {
+        span!(
+            Level::INFO,
+            "clnrm.self_test.test",
+            test.name = test_name,
+            test.result = tracing::field::Empty,
+            otel.status_code = tracing::field::Empty,
+            otel.kind = "internal",
+            component = "self_test",
+        )
+    }
+
     /// Create span for plugin registry initialization
     /// Proves plugin system works correctly
     pub fn plugin_registry_span(plugin_count: usize) -> tracing::Span {
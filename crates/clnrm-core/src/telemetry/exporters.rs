@@ -43,6 +43,14 @@ impl opentelemetry_sdk::trace::SpanExporter for SpanExporterType {
             SpanExporterType::NdjsonStdout(exporter) => exporter.shutdown(),
         }
     }
+
+    fn set_resource(&mut self, resource: &opentelemetry_sdk::Resource) {
+        match self {
+            SpanExporterType::Otlp(exporter) => exporter.set_resource(resource),
+            SpanExporterType::Stdout(exporter) => exporter.set_resource(resource),
+            SpanExporterType::NdjsonStdout(exporter) => exporter.set_resource(resource),
+        }
+    }
 }
 
 /// Create a span exporter from configuration
@@ -117,18 +125,13 @@ fn create_otlp_http_exporter(
     url: &url::Url,
     headers: &HashMap<String, String>,
 ) -> Result<SpanExporterType> {
-    // Set environment variables for OTLP configuration
-    std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", url.as_str());
-
-    // Set custom headers if provided
-    for (key, value) in headers {
-        let env_key = format!("OTEL_EXPORTER_OTLP_HEADERS_{}", key.to_uppercase());
-        std::env::set_var(env_key, value);
-    }
+    use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
 
     // Create HTTP exporter using the correct API for opentelemetry-otlp 0.31.0
     let exporter = opentelemetry_otlp::SpanExporter::builder()
         .with_http()
+        .with_endpoint(otlp_http_traces_endpoint(url))
+        .with_headers(headers.clone())
         .build()
         .map_err(|e| {
             CleanroomError::internal_error(format!("Failed to create OTLP HTTP exporter: {}", e))
@@ -142,18 +145,13 @@ fn create_otlp_grpc_exporter(
     url: &url::Url,
     headers: &HashMap<String, String>,
 ) -> Result<SpanExporterType> {
-    // Set environment variables for OTLP configuration
-    std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", url.as_str());
-
-    // Set custom headers if provided
-    for (key, value) in headers {
-        let env_key = format!("OTEL_EXPORTER_OTLP_HEADERS_{}", key.to_uppercase());
-        std::env::set_var(env_key, value);
-    }
+    use opentelemetry_otlp::{WithExportConfig, WithTonicConfig};
 
     // Create gRPC exporter using the correct API for opentelemetry-otlp 0.31.0
     let exporter = opentelemetry_otlp::SpanExporter::builder()
         .with_tonic()
+        .with_endpoint(url.as_str())
+        .with_metadata(otlp_grpc_metadata(headers)?)
         .build()
         .map_err(|e| {
             CleanroomError::internal_error(format!("Failed to create OTLP gRPC exporter: {}", e))
@@ -162,7 +160,42 @@ fn create_otlp_grpc_exporter(
     Ok(SpanExporterType::Otlp(exporter))
 }
 
-/// Create a Jaeger exporter
+/// OTLP/HTTP traces URL for a collector endpoint
+///
+/// An endpoint set on the exporter is used verbatim, so the `/v1/traces`
+/// path that `OTEL_EXPORTER_OTLP_ENDPOINT` would get is appended here.
+pub fn otlp_http_traces_endpoint(url: &url::Url) -> String {
+    let endpoint = url.as_str().trim_end_matches('/');
+    if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    }
+}
+
+/// Convert OTLP headers to gRPC request metadata
+pub(crate) fn otlp_grpc_metadata(
+    headers: &HashMap<String, String>,
+) -> Result<opentelemetry_otlp::tonic_types::metadata::MetadataMap> {
+    use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+    let mut header_map = HeaderMap::new();
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+            CleanroomError::validation_error(format!("Invalid OTLP header name '{}': {}", name, e))
+        })?;
+        let value = HeaderValue::from_str(value).map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Invalid value for OTLP header '{}': {}",
+                name, e
+            ))
+        })?;
+        header_map.insert(name, value);
+    }
+
+    Ok(opentelemetry_otlp::tonic_types::metadata::MetadataMap::from_headers(header_map))
+}
+
 ///
 /// Creates a Jaeger exporter that sends spans to a Jaeger collector or agent.
 /// Supports both collector and agent modes.
@@ -0,0 +1,216 @@
+//! Telemetry for a single test run
+//!
+//! A test file can ask clnrm to export the spans it emits while running the
+//! test (container lifecycle, command execution) to its own collector:
+//!
+//! ```toml
+//! [otel]
+//! exporter = "otlp"
+//! endpoint = "http://collector:4318"
+//!
+//! [otel_headers]
+//! "x-api-key" = "{{ env(name=\"COLLECTOR_API_KEY\") }}"
+//!
+//! [otel_propagators]
+//! use = ["tracecontext", "b3"]
+//! ```
+//!
+//! Headers from `[otel] headers` and `[otel_headers]` are sent with every
+//! export request (the latter wins on conflicts); `[otel_propagators]` (or
+//! `[otel.propagators]`) selects the globally installed propagators.
+//!
+//! Each test gets its own tracer provider; the global provider installed by
+//! the CLI is left alone, so tests running in parallel export to their own
+//! collectors.
+
+use crate::config::{TestConfig, SUPPORTED_PROPAGATORS};
+use crate::error::{CleanroomError, Result};
+use crate::telemetry::config::{ExporterConfig, OtlpProtocol};
+use crate::telemetry::exporters::create_span_exporter;
+use crate::telemetry::exporters::SpanExporterType;
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::propagation::{TextMapCompositePropagator, TextMapPropagator};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::propagation::{BaggagePropagator, TraceContextPropagator};
+use opentelemetry_sdk::trace::{
+    InMemorySpanExporter, Sampler, SdkTracerProvider, SimpleSpanProcessor, SpanExporter,
+};
+use opentelemetry_sdk::Resource;
+use std::collections::HashMap;
+
+/// Default OTLP/HTTP endpoint when `[otel] endpoint` is not set
+const DEFAULT_OTLP_HTTP_ENDPOINT: &str = "http://localhost:4318";
+
+/// Default OTLP/gRPC endpoint when `[otel] endpoint` is not set
+const DEFAULT_OTLP_GRPC_ENDPOINT: &str = "http://localhost:4317";
+
+/// Tracer provider exporting one test's spans
+///
+/// Spans are collected in memory while the test runs; dropping the guard
+/// exports them to the test's collector on the Tokio runtime, where the
+/// async OTLP clients can run.
+#[derive(Debug)]
+pub struct TestTelemetryGuard {
+    tracer_provider: SdkTracerProvider,
+    finished: InMemorySpanExporter,
+    exporter: SpanExporterType,
+}
+
+impl TestTelemetryGuard {
+    /// Provider the test's spans are emitted through
+    pub fn tracer_provider(&self) -> &SdkTracerProvider {
+        &self.tracer_provider
+    }
+}
+
+impl Drop for TestTelemetryGuard {
+    fn drop(&mut self) {
+        let _ = self.tracer_provider.force_flush();
+        let spans = match self.finished.get_finished_spans() {
+            Ok(spans) if !spans.is_empty() => spans,
+            _ => return,
+        };
+
+        // Exporting must not block the worker the exporter runs on
+        tokio::task::block_in_place(|| {
+            let handle = tokio::runtime::Handle::current();
+            if let Err(e) = handle.block_on(self.exporter.export(spans)) {
+                tracing::warn!("Failed to export test spans: {}", e);
+            }
+        });
+        let _ = self.exporter.shutdown();
+    }
+}
+
+/// Tracer from the test's own provider, or the global one without it
+pub fn tracer(provider: Option<&SdkTracerProvider>, name: &'static str) -> BoxedTracer {
+    match provider {
+        Some(provider) => BoxedTracer::new(Box::new(provider.tracer(name))),
+        None => global::tracer(name),
+    }
+}
+
+/// Install the propagators and OTLP exporter configured by a test
+///
+/// Propagators are installed whenever the test configures them. Spans are
+/// exported only for `[otel] exporter = "otlp"`; clnrm itself always exports
+/// OTLP/HTTP as protobuf, or gRPC for `protocol = "grpc"`.
+///
+/// # Returns
+/// The guard owning the test's tracer provider, or `None` if the test
+/// doesn't export spans
+///
+/// # Errors
+/// Returns error if a propagator is unknown or the exporter can't be built
+pub fn init_test_telemetry(config: &TestConfig) -> Result<Option<TestTelemetryGuard>> {
+    if let Some(names) = configured_propagators(config) {
+        global::set_text_map_propagator(text_map_propagator(names)?);
+    }
+
+    let Some(otel) = config
+        .otel
+        .as_ref()
+        .filter(|otel| otel.exporter.eq_ignore_ascii_case("otlp"))
+    else {
+        return Ok(None);
+    };
+
+    let protocol = match otel.protocol.as_deref() {
+        Some(protocol) if protocol.eq_ignore_ascii_case("grpc") => OtlpProtocol::Grpc,
+        _ => OtlpProtocol::HttpProto,
+    };
+    let endpoint = otel.endpoint.clone().unwrap_or_else(|| {
+        match protocol {
+            OtlpProtocol::Grpc => DEFAULT_OTLP_GRPC_ENDPOINT,
+            OtlpProtocol::HttpProto => DEFAULT_OTLP_HTTP_ENDPOINT,
+        }
+        .to_string()
+    });
+    let mut exporter = create_span_exporter(&ExporterConfig::Otlp {
+        endpoint,
+        protocol,
+        headers: otlp_headers(config),
+    })?;
+
+    let mut resource = Resource::builder_empty()
+        .with_service_name("clnrm")
+        .with_attributes([
+            KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+            KeyValue::new("test.name", config.get_name()?),
+        ]);
+    for (key, value) in otel.resources.iter().flatten() {
+        resource = resource.with_attributes([KeyValue::new(key.clone(), value.clone())]);
+    }
+
+    let resource = resource.build();
+    exporter.set_resource(&resource);
+
+    let finished = InMemorySpanExporter::default();
+    let tracer_provider = SdkTracerProvider::builder()
+        .with_span_processor(SimpleSpanProcessor::new(finished.clone()))
+        .with_sampler(Sampler::TraceIdRatioBased(otel.sample_ratio.unwrap_or(1.0)))
+        .with_resource(resource)
+        .build();
+
+    Ok(Some(TestTelemetryGuard {
+        tracer_provider,
+        finished,
+        exporter,
+    }))
+}
+
+/// Headers sent with a test's OTLP exports
+///
+/// `[otel] headers` merged with `[otel_headers]`, which wins on conflicts.
+pub fn otlp_headers(config: &TestConfig) -> HashMap<String, String> {
+    let mut headers = config
+        .otel
+        .as_ref()
+        .and_then(|otel| otel.headers.clone())
+        .unwrap_or_default();
+    if let Some(ref otel_headers) = config.otel_headers {
+        headers.extend(otel_headers.headers.clone());
+    }
+    headers
+}
+
+/// Propagators from `[otel_propagators]`, falling back to `[otel.propagators]`
+fn configured_propagators(config: &TestConfig) -> Option<&[String]> {
+    config
+        .otel_propagators
+        .as_ref()
+        .or_else(|| config.otel.as_ref()?.propagators.as_ref())
+        .map(|propagators| propagators.r#use.as_slice())
+}
+
+/// Build a composite propagator from `OTEL_PROPAGATORS`-style names
+///
+/// `b3` uses the single `b3` header and `b3multi` the `X-B3-*` headers.
+///
+/// # Errors
+/// Returns error if a name is not one of [`SUPPORTED_PROPAGATORS`]
+pub fn text_map_propagator(names: &[String]) -> Result<TextMapCompositePropagator> {
+    let propagators = names
+        .iter()
+        .map(|name| -> Result<Box<dyn TextMapPropagator + Send + Sync>> {
+            match name.as_str() {
+                "tracecontext" => Ok(Box::new(TraceContextPropagator::new())),
+                "baggage" => Ok(Box::new(BaggagePropagator::new())),
+                "b3" => Ok(Box::new(opentelemetry_zipkin::Propagator::with_encoding(
+                    opentelemetry_zipkin::B3Encoding::SingleHeader,
+                ))),
+                "b3multi" => Ok(Box::new(opentelemetry_zipkin::Propagator::with_encoding(
+                    opentelemetry_zipkin::B3Encoding::MultipleHeader,
+                ))),
+                _ => Err(CleanroomError::validation_error(format!(
+                    "Invalid propagator '{}'. Must be one of: {}",
+                    name,
+                    SUPPORTED_PROPAGATORS.join(", ")
+                ))),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(TextMapCompositePropagator::new(propagators))
+}
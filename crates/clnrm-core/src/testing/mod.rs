@@ -5,8 +5,11 @@
 
 // Re-export framework test types and functions for CLI commands
 use crate::error::{CleanroomError, Result};
+use crate::telemetry::spans;
+use regex::Regex;
 use std::collections::HashMap;
 use std::sync::OnceLock;
+use tracing::Instrument;
 
 /// Framework test results
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -34,6 +37,9 @@ pub struct TestResult {
     pub duration_ms: u64,
     /// Error message if failed
     pub error: Option<String>,
+    /// Per-step results, including skipped steps
+    #[serde(default)]
+    pub steps: Vec<crate::scenario::StepResult>,
 }
 
 /// Suite results for organized test reporting
@@ -89,6 +95,17 @@ pub async fn run_framework_tests() -> Result<FrameworkTestResults> {
 /// Run framework self-tests with optional suite filter
 pub async fn run_framework_tests_by_suite(
     suite_filter: Option<&str>,
+) -> Result<FrameworkTestResults> {
+    run_framework_tests_filtered(suite_filter, None).await
+}
+
+/// Run framework self-tests with optional suite and test name filters
+///
+/// Within the selected suites, only tests whose name matches `test_filter`
+/// (e.g. "TOML" for "TOML Config Parsing") are run.
+pub async fn run_framework_tests_filtered(
+    suite_filter: Option<&str>,
+    test_filter: Option<&Regex>,
 ) -> Result<FrameworkTestResults> {
     let start_time = std::time::Instant::now();
     let mut all_results = FrameworkTestResults {
@@ -104,35 +121,45 @@ pub async fn run_framework_tests_by_suite(
         (
             "framework",
             run_framework_suite
-                as fn() -> std::pin::Pin<
+                as fn(
+                    Option<Regex>,
+                ) -> std::pin::Pin<
                     Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>,
                 >,
         ),
         (
             "container",
             run_container_suite
-                as fn() -> std::pin::Pin<
+                as fn(
+                    Option<Regex>,
+                ) -> std::pin::Pin<
                     Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>,
                 >,
         ),
         (
             "plugin",
             run_plugin_suite
-                as fn() -> std::pin::Pin<
+                as fn(
+                    Option<Regex>,
+                ) -> std::pin::Pin<
                     Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>,
                 >,
         ),
         (
             "cli",
             run_cli_suite
-                as fn() -> std::pin::Pin<
+                as fn(
+                    Option<Regex>,
+                ) -> std::pin::Pin<
                     Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>,
                 >,
         ),
         (
             "otel",
             run_otel_suite
-                as fn() -> std::pin::Pin<
+                as fn(
+                    Option<Regex>,
+                ) -> std::pin::Pin<
                     Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>,
                 >,
         ),
@@ -146,7 +173,10 @@ pub async fn run_framework_tests_by_suite(
             }
         }
 
-        match suite_fn().await {
+        match suite_fn(test_filter.cloned())
+            .instrument(spans::self_test_suite_span(suite_name))
+            .await
+        {
             Ok(suite_result) => {
                 all_results.total_tests += suite_result.test_count;
                 if suite_result.passed {
@@ -168,6 +198,7 @@ pub async fn run_framework_tests_by_suite(
                     passed: false,
                     duration_ms: 0,
                     error: Some(e.to_string()),
+                    steps: Vec::new(),
                 });
             }
         }
@@ -183,25 +214,27 @@ pub async fn run_framework_tests_by_suite(
 
 /// Framework suite: TOML parsing, validation, configuration
 fn run_framework_suite(
+    filter: Option<Regex>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>> {
-    Box::pin(async {
+    Box::pin(async move {
+        let filter = filter.as_ref();
         let start = std::time::Instant::now();
         let mut tests = Vec::new();
 
         // Test 1: TOML parsing
-        tests.push(run_test("TOML Config Parsing", test_toml_parsing).await);
+        tests.extend(run_test(filter, "TOML Config Parsing", test_toml_parsing).await);
 
         // Test 2: Configuration validation
-        tests.push(run_test("Config Validation", test_config_validation).await);
+        tests.extend(run_test(filter, "Config Validation", test_config_validation).await);
 
         // Test 3: Template rendering
-        tests.push(run_test("Template Rendering", test_template_rendering).await);
+        tests.extend(run_test(filter, "Template Rendering", test_template_rendering).await);
 
         // Test 4: Service configuration
-        tests.push(run_test("Service Config", test_service_configuration).await);
+        tests.extend(run_test(filter, "Service Config", test_service_configuration).await);
 
         // Test 5: Error handling
-        tests.push(run_test("Error Handling", test_error_handling).await);
+        tests.extend(run_test(filter, "Error Handling", test_error_handling).await);
 
         let passed = tests.iter().all(|t| t.passed);
         Ok(SuiteResult {
@@ -216,19 +249,21 @@ fn run_framework_suite(
 
 /// Container suite: Container creation and execution
 fn run_container_suite(
+    filter: Option<Regex>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>> {
-    Box::pin(async {
+    Box::pin(async move {
+        let filter = filter.as_ref();
         let start = std::time::Instant::now();
         let mut tests = Vec::new();
 
         // Test 1: Container creation
-        tests.push(run_test("Container Creation", test_container_creation).await);
+        tests.extend(run_test(filter, "Container Creation", test_container_creation).await);
 
         // Test 2: Command execution
-        tests.push(run_test("Command Execution", test_container_execution).await);
+        tests.extend(run_test(filter, "Command Execution", test_container_execution).await);
 
         // Test 3: Container cleanup
-        tests.push(run_test("Container Cleanup", test_container_cleanup).await);
+        tests.extend(run_test(filter, "Container Cleanup", test_container_cleanup).await);
 
         let passed = tests.iter().all(|t| t.passed);
         Ok(SuiteResult {
@@ -243,34 +278,50 @@ fn run_container_suite(
 
 /// Plugin suite: Service plugin lifecycle
 fn run_plugin_suite(
+    filter: Option<Regex>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>> {
-    Box::pin(async {
+    Box::pin(async move {
+        let filter = filter.as_ref();
         let start = std::time::Instant::now();
         let mut tests = Vec::new();
 
         // Test 1: Plugin registration
-        tests.push(run_test("Plugin Registration", test_plugin_registration).await);
+        tests.extend(run_test(filter, "Plugin Registration", test_plugin_registration).await);
 
         // Test 2: Plugin lifecycle
-        tests.push(run_test("Plugin Lifecycle", test_plugin_system).await);
+        tests.extend(run_test(filter, "Plugin Lifecycle", test_plugin_system).await);
 
         // Test 3: Plugin coordination
-        tests.push(run_test("Plugin Coordination", test_plugin_coordination).await);
+        tests.extend(run_test(filter, "Plugin Coordination", test_plugin_coordination).await);
 
         // Test 4: GenericContainerPlugin
-        tests.push(run_test("GenericContainer Plugin", test_generic_container_plugin).await);
+        tests.extend(
+            run_test(
+                filter,
+                "GenericContainer Plugin",
+                test_generic_container_plugin,
+            )
+            .await,
+        );
 
         // Test 5: SurrealDB plugin
-        tests.push(run_test("SurrealDB Plugin", test_surrealdb_plugin).await);
+        tests.extend(run_test(filter, "SurrealDB Plugin", test_surrealdb_plugin).await);
 
         // Test 6: Plugin health checks
-        tests.push(run_test("Plugin Health Checks", test_plugin_health_checks).await);
+        tests.extend(run_test(filter, "Plugin Health Checks", test_plugin_health_checks).await);
 
         // Test 7: Plugin error handling
-        tests.push(run_test("Plugin Error Handling", test_plugin_error_handling).await);
+        tests.extend(run_test(filter, "Plugin Error Handling", test_plugin_error_handling).await);
 
         // Test 8: Multi-plugin coordination
-        tests.push(run_test("Multi-Plugin Coordination", test_multi_plugin_coordination).await);
+        tests.extend(
+            run_test(
+                filter,
+                "Multi-Plugin Coordination",
+                test_multi_plugin_coordination,
+            )
+            .await,
+        );
 
         let passed = tests.iter().all(|t| t.passed);
         Ok(SuiteResult {
@@ -285,46 +336,48 @@ fn run_plugin_suite(
 
 /// CLI suite: Command-line interface
 fn run_cli_suite(
+    filter: Option<Regex>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>> {
-    Box::pin(async {
+    Box::pin(async move {
+        let filter = filter.as_ref();
         let start = std::time::Instant::now();
         let mut tests = Vec::new();
 
         // Test 1: CLI argument parsing
-        tests.push(run_test("CLI Argument Parsing", test_cli_parsing).await);
+        tests.extend(run_test(filter, "CLI Argument Parsing", test_cli_parsing).await);
 
         // Test 2: Config validation command
-        tests.push(run_test("Config Validation Command", test_cli_validation).await);
+        tests.extend(run_test(filter, "Config Validation Command", test_cli_validation).await);
 
         // Test 3: Report generation
-        tests.push(run_test("Report Generation", test_cli_report_generation).await);
+        tests.extend(run_test(filter, "Report Generation", test_cli_report_generation).await);
 
         // Test 4: Format command
-        tests.push(run_test("Format Command", test_cli_format).await);
+        tests.extend(run_test(filter, "Format Command", test_cli_format).await);
 
         // Test 5: Init command
-        tests.push(run_test("Init Command", test_cli_init).await);
+        tests.extend(run_test(filter, "Init Command", test_cli_init).await);
 
         // Test 6: Run command
-        tests.push(run_test("Run Command", test_cli_run).await);
+        tests.extend(run_test(filter, "Run Command", test_cli_run).await);
 
         // Test 7: Dry-run command
-        tests.push(run_test("Dry-Run Command", test_cli_dry_run).await);
+        tests.extend(run_test(filter, "Dry-Run Command", test_cli_dry_run).await);
 
         // Test 8: Error messages
-        tests.push(run_test("Error Message Quality", test_cli_error_messages).await);
+        tests.extend(run_test(filter, "Error Message Quality", test_cli_error_messages).await);
 
         // Test 9: Help text
-        tests.push(run_test("Help Text", test_cli_help).await);
+        tests.extend(run_test(filter, "Help Text", test_cli_help).await);
 
         // Test 10: Version command
-        tests.push(run_test("Version Command", test_cli_version).await);
+        tests.extend(run_test(filter, "Version Command", test_cli_version).await);
 
         // Test 11: Multiple config files
-        tests.push(run_test("Multiple Config Files", test_cli_multiple_configs).await);
+        tests.extend(run_test(filter, "Multiple Config Files", test_cli_multiple_configs).await);
 
         // Test 12: Output formats
-        tests.push(run_test("Output Formats", test_cli_output_formats).await);
+        tests.extend(run_test(filter, "Output Formats", test_cli_output_formats).await);
 
         let passed = tests.iter().all(|t| t.passed);
         Ok(SuiteResult {
@@ -339,22 +392,24 @@ fn run_cli_suite(
 
 /// OTEL suite: OpenTelemetry integration
 fn run_otel_suite(
+    filter: Option<Regex>,
 ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<SuiteResult>> + Send>> {
-    Box::pin(async {
+    Box::pin(async move {
+        let filter = filter.as_ref();
         let start = std::time::Instant::now();
         let mut tests = Vec::new();
 
         // Test 1: OTEL initialization
-        tests.push(run_test("OTEL Initialization", test_otel_init).await);
+        tests.extend(run_test(filter, "OTEL Initialization", test_otel_init).await);
 
         // Test 2: Span creation
-        tests.push(run_test("Span Creation", test_otel_span_creation).await);
+        tests.extend(run_test(filter, "Span Creation", test_otel_span_creation).await);
 
         // Test 3: Trace context
-        tests.push(run_test("Trace Context", test_otel_trace_context).await);
+        tests.extend(run_test(filter, "Trace Context", test_otel_trace_context).await);
 
         // Test 4: Exporters
-        tests.push(run_test("OTEL Exporters", test_otel_exporters).await);
+        tests.extend(run_test(filter, "OTEL Exporters", test_otel_exporters).await);
 
         let passed = tests.iter().all(|t| t.passed);
         Ok(SuiteResult {
@@ -371,27 +426,40 @@ fn run_otel_suite(
 // Test Execution Helper
 // ============================================================================
 
-/// Run a single test and capture results
-async fn run_test<F, Fut>(name: &str, test_fn: F) -> TestResult
+/// Run a single test and capture results, unless `filter` excludes it
+async fn run_test<F, Fut>(filter: Option<&Regex>, name: &str, test_fn: F) -> Option<TestResult>
 where
     F: FnOnce() -> Fut,
     Fut: std::future::Future<Output = Result<()>>,
 {
+    if filter.is_some_and(|filter| !filter.is_match(name)) {
+        return None;
+    }
+
+    let span = spans::self_test_span(name);
     let start = std::time::Instant::now();
-    match test_fn().await {
+    let outcome = test_fn().instrument(span.clone()).await;
+    span.record("test.result", if outcome.is_ok() { "pass" } else { "fail" });
+    if outcome.is_err() {
+        span.record("otel.status_code", "ERROR");
+    }
+
+    Some(match outcome {
         Ok(_) => TestResult {
             name: name.to_string(),
             passed: true,
             duration_ms: start.elapsed().as_millis() as u64,
             error: None,
+            steps: Vec::new(),
         },
         Err(e) => TestResult {
             name: name.to_string(),
             passed: false,
             duration_ms: start.elapsed().as_millis() as u64,
             error: Some(e.to_string()),
+            steps: Vec::new(),
         },
-    }
+    })
 }
 
 // ============================================================================
@@ -53,6 +53,8 @@ pub fn arb_security_policy() -> impl Strategy<Value = SecurityPolicy> {
                     redaction_patterns: patterns,
                     enable_audit_logging: audit,
                     security_level: level,
+                    allowed_binaries: Vec::new(),
+                    denied_binaries: Vec::new(),
                 }
             },
         )
@@ -0,0 +1,155 @@
+//! Span attribute validator for OTEL key/value assertions
+//!
+//! Validates that a named span carries an attribute whose value equals an
+//! expected string or matches a regular expression.
+
+use crate::error::{CleanroomError, ErrorCode, Result};
+use crate::validation::common::extract_string_value;
+use crate::validation::span_validator::SpanData;
+use regex::Regex;
+
+/// How an attribute value is compared against the expectation
+#[derive(Debug, Clone)]
+pub enum AttributeMatcher {
+    /// Value must equal this string exactly
+    Equals(String),
+    /// Value must match this regular expression
+    Regex(Regex),
+}
+
+impl AttributeMatcher {
+    /// Check whether `actual` satisfies this matcher
+    pub fn matches(&self, actual: &str) -> bool {
+        match self {
+            AttributeMatcher::Equals(expected) => actual == expected,
+            AttributeMatcher::Regex(regex) => regex.is_match(actual),
+        }
+    }
+
+    /// Human-readable description of the expected value
+    pub fn describe(&self) -> String {
+        match self {
+            AttributeMatcher::Equals(expected) => format!("'{}'", expected),
+            AttributeMatcher::Regex(regex) => format!("value matching /{}/", regex.as_str()),
+        }
+    }
+}
+
+/// Represents a span attribute expectation
+///
+/// The expectation passes when at least one span with the given name has
+/// the attribute and its value satisfies the matcher.
+///
+/// # Example
+///
+/// ```toml
+/// [[expect.attributes]]
+/// span = "clnrm.run"
+/// key = "result"
+/// value = "pass"
+///
+/// [[expect.attributes]]
+/// span = "http.request"
+/// key = "http.status_code"
+/// value_regex = "^2\\d\\d$"
+/// ```
+#[derive(Debug, Clone)]
+pub struct AttributeExpectation {
+    /// Name of the span that must carry the attribute
+    pub span: String,
+    /// Attribute key
+    pub key: String,
+    /// Expected value
+    pub matcher: AttributeMatcher,
+}
+
+impl AttributeExpectation {
+    /// Expect `span` to have attribute `key` equal to `value`
+    pub fn equals(
+        span: impl Into<String>,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        Self {
+            span: span.into(),
+            key: key.into(),
+            matcher: AttributeMatcher::Equals(value.into()),
+        }
+    }
+
+    /// Expect `span` to have attribute `key` matching `pattern`
+    ///
+    /// # Errors
+    /// * `pattern` is not a valid regular expression
+    pub fn matches(span: impl Into<String>, key: impl Into<String>, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| {
+            CleanroomError::validation_error(format!(
+                "Invalid value_regex '{}' for attribute expectation: {}",
+                pattern, e
+            ))
+        })?;
+
+        Ok(Self {
+            span: span.into(),
+            key: key.into(),
+            matcher: AttributeMatcher::Regex(regex),
+        })
+    }
+
+    /// Validate the attribute expectation against all spans
+    ///
+    /// # Arguments
+    /// * `spans` - All spans to validate against
+    ///
+    /// # Returns
+    /// * `Ok(())` if a span with the expected name has a matching attribute
+    /// * `Err` naming the span and the actual vs expected value otherwise
+    ///
+    /// # Errors
+    /// * Span not found
+    /// * Attribute missing on every span with that name
+    /// * Attribute value does not match on any span with that name
+    pub fn validate(&self, spans: &[SpanData]) -> Result<()> {
+        let candidates: Vec<&SpanData> = spans.iter().filter(|s| s.name == self.span).collect();
+
+        if candidates.is_empty() {
+            return Err(CleanroomError::validation_error(format!(
+                "Attribute validation failed: span '{}' not found in trace",
+                self.span
+            ))
+            .with_code(ErrorCode::SpanNotFound));
+        }
+
+        let actual_values: Vec<String> = candidates
+            .iter()
+            .filter_map(|span| span.attributes.get(&self.key))
+            .map(extract_string_value)
+            .collect();
+
+        if actual_values
+            .iter()
+            .any(|actual| self.matcher.matches(actual))
+        {
+            return Ok(());
+        }
+
+        let actual = if actual_values.is_empty() {
+            "attribute not set".to_string()
+        } else {
+            actual_values
+                .iter()
+                .map(|v| format!("'{}'", v))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        Err(CleanroomError::validation_error(format!(
+            "Attribute validation failed: span '{}' attribute '{}' expected {}, found {}",
+            self.span,
+            self.key,
+            self.matcher.describe(),
+            actual
+        ))
+        .with_code(ErrorCode::SpanAttribute))
+    }
+}
@@ -75,3 +75,32 @@ pub fn count_spans_by_name(spans: &[SpanData], name: &str) -> usize {
 pub fn count_error_spans(spans: &[SpanData]) -> usize {
     spans.iter().filter(|s| is_error_span(s)).count()
 }
+
+/// Extract string value from JSON attribute value
+///
+/// Handles both plain JSON values and the OTLP attribute value format
+/// (`{"stringValue": "..."}`, `{"intValue": ...}`, `{"boolValue": ...}`).
+///
+/// # Arguments
+/// * `value` - The attribute value to convert
+///
+/// # Returns
+/// * `String` - String representation used for comparisons
+pub fn extract_string_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Object(obj) => {
+            // Handle OTEL attribute value format: {"stringValue": "..."}
+            if let Some(string_val) = obj.get("stringValue").and_then(|v| v.as_str()) {
+                string_val.to_string()
+            } else if let Some(int_val) = obj.get("intValue") {
+                int_val.to_string()
+            } else if let Some(bool_val) = obj.get("boolValue") {
+                bool_val.to_string()
+            } else {
+                format!("{}", serde_json::Value::Object(obj.clone()))
+            }
+        }
+        _ => format!("{}", value),
+    }
+}
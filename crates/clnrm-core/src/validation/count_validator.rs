@@ -3,7 +3,7 @@
 //! Validates that span counts match expected bounds (gte, lte, eq) for total counts,
 //! error counts, event counts, and per-name counts.
 
-use crate::error::{CleanroomError, Result};
+use crate::error::{CleanroomError, ErrorCode, Result};
 use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -73,7 +73,8 @@ impl CountBound {
                 return Err(CleanroomError::validation_error(format!(
                     "{}: expected exactly {} items, found {}",
                     context, expected, actual
-                )));
+                ))
+                .with_code(ErrorCode::SpanCountMismatch));
             }
             return Ok(());
         }
@@ -84,7 +85,8 @@ impl CountBound {
                 return Err(CleanroomError::validation_error(format!(
                     "{}: expected at least {} items, found {}",
                     context, min, actual
-                )));
+                ))
+                .with_code(ErrorCode::SpanCountMismatch));
             }
         }
 
@@ -94,7 +96,8 @@ impl CountBound {
                 return Err(CleanroomError::validation_error(format!(
                     "{}: expected at most {} items, found {}",
                     context, max, actual
-                )));
+                ))
+                .with_code(ErrorCode::SpanCountMismatch));
             }
         }
 
@@ -0,0 +1,116 @@
+//! Span duration validator for OTEL latency budgets
+//!
+//! Validates that every span with a given name completes within a minimum
+//! and/or maximum duration, computed from its start and end timestamps.
+
+use crate::error::{CleanroomError, ErrorCode, Result};
+use crate::validation::span_validator::SpanData;
+use std::collections::HashSet;
+
+/// Represents a span duration expectation
+///
+/// Every span with the given name must have real timing data and a duration
+/// within the configured bounds.
+///
+/// # Example
+///
+/// ```toml
+/// [[expect.duration]]
+/// span = "db.query"
+/// max_ms = 250
+///
+/// [[expect.duration]]
+/// span = "cache.warmup"
+/// min_ms = 10
+/// max_ms = 5000
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DurationExpectation {
+    /// Name of the spans to check
+    pub span: String,
+    /// Minimum duration in milliseconds (inclusive)
+    pub min_ms: Option<f64>,
+    /// Maximum duration in milliseconds (inclusive)
+    pub max_ms: Option<f64>,
+}
+
+impl DurationExpectation {
+    /// Create a new duration expectation with no bounds
+    pub fn new(span: impl Into<String>) -> Self {
+        Self {
+            span: span.into(),
+            min_ms: None,
+            max_ms: None,
+        }
+    }
+
+    /// Set minimum duration in milliseconds
+    pub fn with_min_ms(mut self, min_ms: f64) -> Self {
+        self.min_ms = Some(min_ms);
+        self
+    }
+
+    /// Set maximum duration in milliseconds
+    pub fn with_max_ms(mut self, max_ms: f64) -> Self {
+        self.max_ms = Some(max_ms);
+        self
+    }
+
+    /// Validate span durations
+    ///
+    /// # Arguments
+    /// * `spans` - All spans to validate against
+    /// * `synthetic_timing` - IDs of spans whose timestamps were filled in by
+    ///   a frozen clock; these are treated as having no timing data
+    ///
+    /// # Errors
+    /// * Span not found
+    /// * Span has no timing data (missing, inverted or synthesized timestamps)
+    /// * Span duration outside the configured bounds
+    pub fn validate(&self, spans: &[SpanData], synthetic_timing: &HashSet<String>) -> Result<()> {
+        let candidates: Vec<&SpanData> = spans.iter().filter(|s| s.name == self.span).collect();
+
+        if candidates.is_empty() {
+            return Err(CleanroomError::validation_error(format!(
+                "Duration validation failed: span '{}' not found in trace",
+                self.span
+            ))
+            .with_code(ErrorCode::SpanNotFound));
+        }
+
+        for span in candidates {
+            let duration_ms = span
+                .duration_ms()
+                .filter(|_| !synthetic_timing.contains(&span.span_id))
+                .ok_or_else(|| {
+                    CleanroomError::validation_error(format!(
+                        "Duration validation failed: span '{}' (span_id: {}) has no timing data",
+                        self.span, span.span_id
+                    ))
+                    .with_code(ErrorCode::SpanDuration)
+                })?;
+
+            if let Some(min) = self.min_ms {
+                if duration_ms < min {
+                    return Err(CleanroomError::validation_error(format!(
+                        "Duration validation failed: span '{}' took {}ms, expected at least {}ms",
+                        self.span, duration_ms, min
+                    ))
+                    .with_code(ErrorCode::SpanDuration));
+                }
+            }
+
+            if let Some(max) = self.max_ms {
+                if duration_ms > max {
+                    return Err(CleanroomError::validation_error(format!(
+                        "Duration validation failed: span '{}' took {}ms, expected at most {}ms",
+                        self.span, duration_ms, max
+                    ))
+                    .with_code(ErrorCode::SpanDuration));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -5,7 +5,7 @@
 //! - Forbidden edges (must_not_cross)
 //! - Acyclicity checks
 
-use crate::error::{CleanroomError, Result};
+use crate::error::{CleanroomError, ErrorCode, Result};
 use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -135,6 +135,7 @@ impl<'a> GraphValidator<'a> {
                 "Graph validation failed: parent span '{}' not found",
                 parent_name
             ))
+            .with_code(ErrorCode::SpanNotFound)
         })?;
 
         let child_spans = self.spans_by_name.get(child_name).ok_or_else(|| {
@@ -142,6 +143,7 @@ impl<'a> GraphValidator<'a> {
                 "Graph validation failed: child span '{}' not found",
                 child_name
             ))
+            .with_code(ErrorCode::SpanNotFound)
         })?;
 
         // Check if any child has any parent as its parent_span_id
@@ -159,7 +161,8 @@ impl<'a> GraphValidator<'a> {
             return Err(CleanroomError::validation_error(format!(
                 "Graph validation failed: required edge '{}' -> '{}' not found",
                 parent_name, child_name
-            )));
+            ))
+            .with_code(ErrorCode::SpanGraph));
         }
 
         Ok(())
@@ -201,7 +204,8 @@ impl<'a> GraphValidator<'a> {
             return Err(CleanroomError::validation_error(format!(
                 "Graph validation failed: forbidden edge '{}' -> '{}' found",
                 parent_name, child_name
-            )));
+            ))
+            .with_code(ErrorCode::SpanGraph));
         }
 
         Ok(())
@@ -230,7 +234,8 @@ impl<'a> GraphValidator<'a> {
                     return Err(CleanroomError::validation_error(format!(
                         "Graph validation failed: cycle detected in span graph: {}",
                         cycle_path.join(" -> ")
-                    )));
+                    ))
+                    .with_code(ErrorCode::SpanGraph));
                 }
             }
         }
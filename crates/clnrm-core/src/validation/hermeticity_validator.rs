@@ -7,7 +7,8 @@
 //!
 //! This implements the PRD section "Expectations: Hermeticity" (lines 123-131)
 
-use crate::error::{CleanroomError, Result};
+use crate::error::{CleanroomError, ErrorCode, Result};
+use crate::validation::common::extract_string_value;
 use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -250,7 +251,7 @@ impl HermeticityExpectation {
                     });
                 }
                 Some(actual_value) => {
-                    let actual_str = Self::extract_string_value(actual_value);
+                    let actual_str = extract_string_value(actual_value);
                     if actual_str != *expected_value {
                         violations.push(HermeticityViolation {
                             violation_type: ViolationType::ResourceAttributeMismatch,
@@ -317,7 +318,7 @@ impl HermeticityExpectation {
                     });
                 }
                 Some(actual_value) => {
-                    let actual_str = Self::extract_string_value(actual_value);
+                    let actual_str = extract_string_value(actual_value);
                     if actual_str != *expected_value {
                         violations.push(HermeticityViolation {
                             violation_type: ViolationType::SdkResourceAttributeMismatch,
@@ -369,26 +370,6 @@ impl HermeticityExpectation {
         violations
     }
 
-    /// Extract string value from JSON value
-    fn extract_string_value(value: &serde_json::Value) -> String {
-        match value {
-            serde_json::Value::String(s) => s.clone(),
-            serde_json::Value::Object(obj) => {
-                // Handle OTEL attribute value format: {"stringValue": "..."}
-                if let Some(string_val) = obj.get("stringValue").and_then(|v| v.as_str()) {
-                    string_val.to_string()
-                } else if let Some(int_val) = obj.get("intValue") {
-                    int_val.to_string()
-                } else if let Some(bool_val) = obj.get("boolValue") {
-                    bool_val.to_string()
-                } else {
-                    format!("{}", serde_json::Value::Object(obj.clone()))
-                }
-            }
-            _ => format!("{}", value),
-        }
-    }
-
     /// Create a detailed validation error from violations
     fn create_violation_error(&self, violations: Vec<HermeticityViolation>) -> CleanroomError {
         let violation_count = violations.len();
@@ -417,7 +398,7 @@ impl HermeticityExpectation {
             }
         }
 
-        CleanroomError::validation_error(message)
+        CleanroomError::validation_error(message).with_code(ErrorCode::Hermeticity)
     }
 }
 
@@ -0,0 +1,218 @@
+//! Metric validator for OTEL counters, gauges and histograms
+//!
+//! Validates the value of a metric, or the number of samples in a histogram,
+//! after a scenario has run. A metric that was never exported fails with
+//! [`ErrorCode::MetricNotFound`]; one with the wrong value fails with
+//! [`ErrorCode::MetricValue`].
+
+use crate::error::{CleanroomError, ErrorCode, Result};
+use crate::otel::metrics::{MetricData, MetricKind};
+use std::collections::HashMap;
+
+/// Represents a metric expectation
+///
+/// The value is the total of a counter or histogram across all matching
+/// series, or the latest reading of a gauge. Histogram sample counts are
+/// checked with `count` and `min_count`.
+///
+/// # Example
+///
+/// ```toml
+/// [[expect.metric]]
+/// name = "http.server.requests"
+/// attributes = { "http.route" = "/login" }
+/// value = 3
+///
+/// [[expect.metric]]
+/// name = "http.server.duration"
+/// min_count = 1
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MetricExpectation {
+    /// Metric name
+    pub name: String,
+    /// Attributes data points must have to be counted
+    pub attributes: HashMap<String, String>,
+    /// Exact expected value
+    pub value: Option<f64>,
+    /// Minimum value (inclusive)
+    pub min_value: Option<f64>,
+    /// Maximum value (inclusive)
+    pub max_value: Option<f64>,
+    /// Exact number of histogram samples
+    pub count: Option<u64>,
+    /// Minimum number of histogram samples (inclusive)
+    pub min_count: Option<u64>,
+}
+
+impl MetricExpectation {
+    /// Create a new metric expectation with no bounds
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Only count data points with this attribute value
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the exact expected value
+    pub fn with_value(mut self, value: f64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Set the minimum value
+    pub fn with_min_value(mut self, min: f64) -> Self {
+        self.min_value = Some(min);
+        self
+    }
+
+    /// Set the maximum value
+    pub fn with_max_value(mut self, max: f64) -> Self {
+        self.max_value = Some(max);
+        self
+    }
+
+    /// Set the exact number of histogram samples
+    pub fn with_count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    /// Set the minimum number of histogram samples
+    pub fn with_min_count(mut self, min: u64) -> Self {
+        self.min_count = Some(min);
+        self
+    }
+
+    /// Whether any bound is set
+    pub fn has_bounds(&self) -> bool {
+        self.value.is_some()
+            || self.min_value.is_some()
+            || self.max_value.is_some()
+            || self.count.is_some()
+            || self.min_count.is_some()
+    }
+
+    /// Validate the metric
+    ///
+    /// # Errors
+    /// * Metric not found, or no data points with the expected attributes
+    /// * Value or histogram sample count outside the configured bounds
+    /// * `count`/`min_count` set for a metric that isn't a histogram
+    pub fn validate(&self, metrics: &[MetricData]) -> Result<()> {
+        let candidates: Vec<&MetricData> = metrics.iter().filter(|m| m.name == self.name).collect();
+        if candidates.is_empty() {
+            return Err(CleanroomError::validation_error(format!(
+                "Metric validation failed: metric '{}' not found",
+                self.name
+            ))
+            .with_code(ErrorCode::MetricNotFound));
+        }
+
+        // Periodic exports repeat a metric; cumulative points from later
+        // exports supersede earlier ones
+        let mut metric = candidates[0].clone();
+        for other in &candidates[1..] {
+            metric.points.extend(other.points.iter().cloned());
+        }
+        if metric.points_matching(&self.attributes).next().is_none() {
+            return Err(CleanroomError::validation_error(format!(
+                "Metric validation failed: metric '{}' has no data points with attributes {}",
+                self.name,
+                self.describe_attributes()
+            ))
+            .with_code(ErrorCode::MetricNotFound));
+        }
+
+        if self.value.is_some() || self.min_value.is_some() || self.max_value.is_some() {
+            let value = metric.value(&self.attributes).ok_or_else(|| {
+                CleanroomError::validation_error(format!(
+                    "Metric validation failed: metric '{}' has no values",
+                    self.name
+                ))
+                .with_code(ErrorCode::MetricNotFound)
+            })?;
+            self.check_value(value)?;
+        }
+
+        if self.count.is_some() || self.min_count.is_some() {
+            if metric.kind != MetricKind::Histogram {
+                return Err(CleanroomError::validation_error(format!(
+                    "Metric validation failed: metric '{}' is a {}, only histograms have a \
+                     sample count",
+                    self.name,
+                    metric.kind.as_str()
+                ))
+                .with_code(ErrorCode::MetricValue));
+            }
+            self.check_count(metric.sample_count(&self.attributes).unwrap_or(0))?;
+        }
+
+        Ok(())
+    }
+
+    fn check_value(&self, value: f64) -> Result<()> {
+        let mismatch = |expected: String| {
+            Err(CleanroomError::validation_error(format!(
+                "Metric validation failed: metric '{}' is {}, expected {}",
+                self.name, value, expected
+            ))
+            .with_code(ErrorCode::MetricValue))
+        };
+
+        if let Some(expected) = self.value {
+            if value != expected {
+                return mismatch(expected.to_string());
+            }
+        }
+        if let Some(min) = self.min_value {
+            if value < min {
+                return mismatch(format!("at least {}", min));
+            }
+        }
+        if let Some(max) = self.max_value {
+            if value > max {
+                return mismatch(format!("at most {}", max));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_count(&self, count: u64) -> Result<()> {
+        let mismatch = |expected: String| {
+            Err(CleanroomError::validation_error(format!(
+                "Metric validation failed: histogram '{}' has {} sample(s), expected {}",
+                self.name, count, expected
+            ))
+            .with_code(ErrorCode::MetricValue))
+        };
+
+        if let Some(expected) = self.count {
+            if count != expected {
+                return mismatch(expected.to_string());
+            }
+        }
+        if let Some(min) = self.min_count {
+            if count < min {
+                return mismatch(format!("at least {}", min));
+            }
+        }
+        Ok(())
+    }
+
+    fn describe_attributes(&self) -> String {
+        let mut pairs: Vec<String> = self
+            .attributes
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect();
+        pairs.sort();
+        format!("{{{}}}", pairs.join(", "))
+    }
+}
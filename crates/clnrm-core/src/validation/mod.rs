@@ -3,10 +3,13 @@
 //! Provides validation capabilities for test assertions, including
 //! OpenTelemetry validation for observability testing.
 
+pub mod attribute_validator;
 pub mod common;
 pub mod count_validator;
+pub mod duration_validator;
 pub mod graph_validator;
 pub mod hermeticity_validator;
+pub mod metric_validator;
 pub mod orchestrator;
 pub mod order_validator;
 pub mod otel;
@@ -15,20 +18,25 @@ pub mod span_validator;
 pub mod status_validator;
 pub mod window_validator;
 
+pub use attribute_validator::{AttributeExpectation, AttributeMatcher};
 pub use count_validator::{CountBound, CountExpectation};
+pub use duration_validator::DurationExpectation;
 pub use graph_validator::{GraphExpectation, GraphValidator};
 pub use hermeticity_validator::{
     HermeticityExpectation, HermeticityValidator, HermeticityViolation, ViolationType,
 };
+pub use metric_validator::MetricExpectation;
 pub use orchestrator::{PrdExpectations, ValidationReport};
 pub use order_validator::OrderExpectation;
 pub use otel::{
-    OtelValidationConfig, OtelValidator, SpanAssertion as OtelSpanAssertion, TraceAssertion,
-    ValidationSpanProcessor, SpanValidationResult, TraceValidationResult,
+    EventAssertion, OtelValidationConfig, OtelValidator, SpanAssertion as OtelSpanAssertion,
+    SpanValidationResult, TraceAssertion, TraceValidationResult, ValidationSpanProcessor,
+};
+pub use shape::{
+    find_unknown_keys, ErrorCategory, ShapeValidationError, ShapeValidationResult, ShapeValidator,
 };
-pub use shape::{ErrorCategory, ShapeValidationError, ShapeValidationResult, ShapeValidator};
 pub use span_validator::{
-    FailureDetails, SpanAssertion, SpanData, SpanKind, SpanValidator, ValidationResult,
+    FailureDetails, SpanAssertion, SpanData, SpanEvent, SpanKind, SpanValidator, ValidationResult,
 };
 pub use status_validator::{StatusCode, StatusExpectation};
 pub use window_validator::{WindowExpectation, WindowValidator};
@@ -3,11 +3,17 @@
 //! Provides unified interface to run all validation checks and generate reports.
 
 use crate::error::{CleanroomError, Result};
+use crate::otel::metrics::MetricData;
+use crate::validation::attribute_validator::AttributeExpectation;
 use crate::validation::count_validator::CountExpectation;
+use crate::validation::duration_validator::DurationExpectation;
 use crate::validation::graph_validator::GraphExpectation;
 use crate::validation::hermeticity_validator::HermeticityExpectation;
+use crate::validation::metric_validator::MetricExpectation;
+use crate::validation::otel::EventAssertion;
 use crate::validation::span_validator::SpanData;
 use crate::validation::window_validator::WindowExpectation;
+use std::collections::HashSet;
 
 /// Complete PRD validation expectations
 #[derive(Debug, Clone, Default)]
@@ -20,6 +26,16 @@ pub struct PrdExpectations {
     pub windows: Vec<WindowExpectation>,
     /// Hermeticity expectations (isolation, no cross-contamination)
     pub hermeticity: Option<HermeticityExpectation>,
+    /// Span attribute key/value expectations
+    pub attributes: Vec<AttributeExpectation>,
+    /// Span duration (latency) expectations
+    pub durations: Vec<DurationExpectation>,
+    /// Span event expectations
+    pub events: Vec<EventAssertion>,
+    /// Metric expectations, checked separately with [`Self::validate_metrics`]
+    pub metrics: Vec<MetricExpectation>,
+    /// IDs of spans whose timestamps were synthesized by a frozen clock
+    pub synthetic_timing: HashSet<String>,
 }
 
 impl PrdExpectations {
@@ -52,6 +68,39 @@ impl PrdExpectations {
         self
     }
 
+    /// Add attribute expectation
+    pub fn add_attribute(mut self, attribute: AttributeExpectation) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
+    /// Add duration expectation
+    pub fn add_duration(mut self, duration: DurationExpectation) -> Self {
+        self.durations.push(duration);
+        self
+    }
+
+    /// Add span event expectation
+    pub fn add_event(mut self, event: EventAssertion) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Add metric expectation
+    pub fn add_metric(mut self, metric: MetricExpectation) -> Self {
+        self.metrics.push(metric);
+        self
+    }
+
+    /// Mark spans whose timestamps were filled in by a frozen clock
+    ///
+    /// Duration checks report these spans as having no timing data instead
+    /// of validating the synthetic values.
+    pub fn with_synthetic_timing(mut self, span_ids: HashSet<String>) -> Self {
+        self.synthetic_timing = span_ids;
+        self
+    }
+
     /// Run all validations in order
     ///
     /// Validation order:
@@ -59,6 +108,9 @@ impl PrdExpectations {
     /// 2. Span counts (expected spans exist)
     /// 3. Temporal windows (timing and ordering)
     /// 4. Hermeticity (isolation and no contamination)
+    /// 5. Span attributes (key/value assertions)
+    /// 6. Span durations (latency budgets)
+    /// 7. Span events (emitted events and their attributes)
     ///
     /// # Arguments
     /// * `spans` - Slice of span data to validate
@@ -101,9 +153,49 @@ impl PrdExpectations {
             }
         }
 
+        // 5. Validate span attributes
+        for (idx, attribute) in self.attributes.iter().enumerate() {
+            let name = format!("attribute_{}_{}_{}", idx, attribute.span, attribute.key);
+            match attribute.validate(spans) {
+                Ok(_) => report.add_pass(&name),
+                Err(e) => report.add_fail(&name, e.to_string()),
+            }
+        }
+
+        // 6. Validate span durations
+        for (idx, duration) in self.durations.iter().enumerate() {
+            let name = format!("duration_{}_{}", idx, duration.span);
+            match duration.validate(spans, &self.synthetic_timing) {
+                Ok(_) => report.add_pass(&name),
+                Err(e) => report.add_fail(&name, e.to_string()),
+            }
+        }
+
+        // 7. Validate span events
+        for (idx, event) in self.events.iter().enumerate() {
+            let name = format!("event_{}_{}_{}", idx, event.span_name, event.event_name);
+            match event.validate(spans) {
+                Ok(_) => report.add_pass(&name),
+                Err(e) => report.add_fail(&name, e.to_string()),
+            }
+        }
+
         Ok(report)
     }
 
+    /// Run metric expectations against the metrics a scenario exported
+    pub fn validate_metrics(&self, metrics: &[MetricData]) -> ValidationReport {
+        let mut report = ValidationReport::new();
+        for (idx, metric) in self.metrics.iter().enumerate() {
+            let name = format!("metric_{}_{}", idx, metric.name);
+            match metric.validate(metrics) {
+                Ok(_) => report.add_pass(&name),
+                Err(e) => report.add_fail(&name, e.to_string()),
+            }
+        }
+        report
+    }
+
     /// Validate and return Result (fail on first error)
     pub fn validate_strict(&self, spans: &[SpanData]) -> Result<()> {
         let report = self.validate_all(spans)?;
@@ -2,7 +2,7 @@
 //!
 //! Validates that spans occur in expected temporal order based on timestamps.
 
-use crate::error::{CleanroomError, Result};
+use crate::error::{CleanroomError, ErrorCode, Result};
 use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 
@@ -65,14 +65,16 @@ impl OrderExpectation {
             return Err(CleanroomError::validation_error(format!(
                 "Order validation failed: span '{}' not found for must_precede constraint",
                 first
-            )));
+            ))
+            .with_code(ErrorCode::SpanNotFound));
         }
 
         if second_spans.is_empty() {
             return Err(CleanroomError::validation_error(format!(
                 "Order validation failed: span '{}' not found for must_precede constraint",
                 second
-            )));
+            ))
+            .with_code(ErrorCode::SpanNotFound));
         }
 
         // Check if any first span precedes any second span
@@ -93,7 +95,8 @@ impl OrderExpectation {
             return Err(CleanroomError::validation_error(format!(
                 "Order validation failed: '{}' must precede '{}' but no valid ordering found",
                 first, second
-            )));
+            ))
+            .with_code(ErrorCode::SpanOrder));
         }
 
         Ok(())
@@ -2,6 +2,9 @@
 //!
 //! This module provides assertion structures for defining validation expectations.
 
+use crate::error::{CleanroomError, ErrorCode, Result};
+use crate::validation::common::extract_string_value;
+use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -33,6 +36,145 @@ pub struct TraceAssertion {
     pub parent_child_relationships: Vec<(String, String)>, // (parent_name, child_name)
 }
 
+/// Span event assertion configuration
+///
+/// Passes when at least one span named `span_name` recorded an event named
+/// `event_name` whose attributes equal every entry in `attributes`.
+///
+/// # Example
+///
+/// ```toml
+/// [[expect.event]]
+/// span = "http.request"
+/// name = "exception"
+/// attributes = { "exception.type" = "TimeoutError" }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventAssertion {
+    /// Name of the span that must emit the event
+    pub span_name: String,
+    /// Expected event name (e.g., "exception", "cache.miss")
+    pub event_name: String,
+    /// Expected event attributes (exact string match)
+    pub attributes: HashMap<String, String>,
+}
+
+impl EventAssertion {
+    /// Expect `span_name` to emit an event named `event_name`
+    pub fn new(span_name: impl Into<String>, event_name: impl Into<String>) -> Self {
+        Self {
+            span_name: span_name.into(),
+            event_name: event_name.into(),
+            attributes: HashMap::new(),
+        }
+    }
+
+    /// Require the event to carry attribute `key` equal to `value`
+    pub fn with_attribute(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.attributes.insert(key.into(), value.into());
+        self
+    }
+
+    /// Validate the event assertion against all spans
+    ///
+    /// # Errors
+    /// * Span not found
+    /// * No span with that name has an events array
+    /// * Event not emitted by any span with that name
+    /// * Event found but no occurrence has the expected attributes
+    pub fn validate(&self, spans: &[SpanData]) -> Result<()> {
+        let candidates: Vec<&SpanData> =
+            spans.iter().filter(|s| s.name == self.span_name).collect();
+
+        if candidates.is_empty() {
+            return Err(CleanroomError::validation_error(format!(
+                "Event validation failed: span '{}' not found in trace",
+                self.span_name
+            ))
+            .with_code(ErrorCode::SpanNotFound));
+        }
+
+        if candidates.iter().all(|span| span.events.is_none()) {
+            return Err(CleanroomError::validation_error(format!(
+                "Event validation failed: span '{}' has no events array, expected event '{}'",
+                self.span_name, self.event_name
+            ))
+            .with_code(ErrorCode::SpanEvent));
+        }
+
+        let emitted = candidates
+            .iter()
+            .any(|span| span.has_event(&self.event_name));
+        if !emitted {
+            let actual: Vec<&str> = candidates
+                .iter()
+                .flat_map(|span| span.event_names())
+                .collect();
+            return Err(CleanroomError::validation_error(format!(
+                "Event validation failed: span '{}' did not emit event '{}' (events: [{}])",
+                self.span_name,
+                self.event_name,
+                actual.join(", ")
+            ))
+            .with_code(ErrorCode::SpanEvent));
+        }
+
+        if self.attributes.is_empty() {
+            return Ok(());
+        }
+
+        let occurrences: Vec<&HashMap<String, serde_json::Value>> = candidates
+            .iter()
+            .filter_map(|span| span.events.as_ref())
+            .flatten()
+            .filter(|event| event.name == self.event_name)
+            .map(|event| &event.attributes)
+            .collect();
+
+        let matches = |attributes: &HashMap<String, serde_json::Value>| {
+            self.attributes.iter().all(|(key, expected)| {
+                attributes
+                    .get(key)
+                    .is_some_and(|actual| extract_string_value(actual) == *expected)
+            })
+        };
+        if occurrences.iter().any(|attributes| matches(attributes)) {
+            return Ok(());
+        }
+
+        let mut expected: Vec<String> = self
+            .attributes
+            .iter()
+            .map(|(k, v)| format!("{}='{}'", k, v))
+            .collect();
+        expected.sort();
+        let actual: Vec<String> = occurrences
+            .iter()
+            .map(|attributes| {
+                let mut pairs: Vec<String> = self
+                    .attributes
+                    .keys()
+                    .map(|key| match attributes.get(key) {
+                        Some(value) => format!("{}='{}'", key, extract_string_value(value)),
+                        None => format!("{} not set", key),
+                    })
+                    .collect();
+                pairs.sort();
+                format!("{{{}}}", pairs.join(", "))
+            })
+            .collect();
+
+        Err(CleanroomError::validation_error(format!(
+            "Event validation failed: span '{}' event '{}' expected {}, found {}",
+            self.span_name,
+            self.event_name,
+            expected.join(", "),
+            actual.join(" ")
+        ))
+        .with_code(ErrorCode::SpanEvent))
+    }
+}
+
 /// Helper function to create span assertion from TOML configuration
 pub fn span_assertion_from_toml(name: &str, attributes: HashMap<String, String>) -> SpanAssertion {
     SpanAssertion {
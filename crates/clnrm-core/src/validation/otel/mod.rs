@@ -14,7 +14,7 @@ pub mod validator;
 mod tests;
 
 // Re-export main types for convenience
-pub use assertions::{SpanAssertion, TraceAssertion, span_assertion_from_toml, trace_assertion_from_toml};
+pub use assertions::{EventAssertion, SpanAssertion, TraceAssertion, span_assertion_from_toml, trace_assertion_from_toml};
 pub use config::OtelValidationConfig;
 pub use results::{SpanValidationResult, TraceValidationResult};
 pub use span_processor::ValidationSpanProcessor;
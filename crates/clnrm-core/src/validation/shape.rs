@@ -2,16 +2,29 @@
 //!
 //! Validates TOML configuration structure without spinning up containers.
 //! Performs fast, static validation of configuration shape and relationships.
+//!
+//! Validation does not stop at the first problem: each scenario, step,
+//! service and top-level section is deserialized on its own, so every missing
+//! field, type mismatch and unknown key is reported in a single pass, with
+//! its line number, up to [`DEFAULT_MAX_ERRORS`].
 
 use crate::config::{
-    ExpectationsConfig, OrderExpectationConfig, OtelConfig, ScenarioConfig, ServiceConfig,
-    SpanExpectationConfig, TestConfig, VolumeConfig, WindowExpectationConfig,
+    ExpectationsConfig, HealthCheck, MetaConfig, OrderExpectationConfig, OtelConfig,
+    ScenarioConfig, ServiceConfig, SpanExpectationConfig, StepConfig, TestConfig, VolumeConfig,
+    WindowExpectationConfig,
 };
 use crate::error::{CleanroomError, Result};
 use glob::Pattern as GlobBuilder;
 use regex::Regex;
+use serde::de::{DeserializeOwned, Visitor};
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use toml::de::{DeArray, DeTable, DeValue};
+use toml::Spanned;
+
+/// Default cap on the number of errors collected for one file
+pub const DEFAULT_MAX_ERRORS: usize = 50;
 
 /// Result of shape validation
 #[derive(Debug, Clone)]
@@ -22,6 +35,8 @@ pub struct ShapeValidationResult {
     pub errors: Vec<ShapeValidationError>,
     /// File path that was validated
     pub file_path: String,
+    /// Errors dropped after reaching the validator's `max_errors` cap
+    pub suppressed: usize,
 }
 
 /// Shape validation error with file context
@@ -52,6 +67,8 @@ pub enum ErrorCategory {
     InvalidGlob,
     /// OTEL configuration error
     OtelError,
+    /// Key not recognized by the configuration schema
+    UnknownKey,
 }
 
 impl ShapeValidationError {
@@ -75,19 +92,58 @@ impl ShapeValidationError {
 pub struct ShapeValidator {
     /// Errors collected during validation
     errors: Vec<ShapeValidationError>,
+    /// Maximum number of errors kept
+    max_errors: usize,
+    /// Errors dropped after reaching `max_errors`
+    suppressed: usize,
+    /// Services whose definitions failed to deserialize; references to them
+    /// are not reported again as orphans
+    unparsed_services: HashSet<String>,
 }
 
 impl ShapeValidator {
     /// Create new shape validator
     pub fn new() -> Self {
-        Self { errors: Vec::new() }
+        Self {
+            errors: Vec::new(),
+            max_errors: DEFAULT_MAX_ERRORS,
+            suppressed: 0,
+            unparsed_services: HashSet::new(),
+        }
+    }
+
+    /// Keep at most `max_errors` errors; the rest are only counted
+    pub fn with_max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = max_errors;
+        self
+    }
+
+    /// Record an error unless the cap has been reached
+    fn push_error(&mut self, error: ShapeValidationError) {
+        if self.errors.len() < self.max_errors {
+            self.errors.push(error);
+        } else {
+            self.suppressed += 1;
+        }
+    }
+
+    /// Forget errors from a previous validation
+    fn reset(&mut self) {
+        self.errors.clear();
+        self.suppressed = 0;
+        self.unparsed_services.clear();
     }
 
     /// Validate a configuration file
     ///
+    /// Structural problems (TOML syntax, missing fields, type mismatches,
+    /// unknown keys) are reported in the result rather than as an `Err`.
+    /// `.yaml`/`.yml` files are parsed as YAML, with errors located by the
+    /// YAML line.
+    ///
     /// # Errors
     ///
-    /// Returns error if file cannot be read or parsed
+    /// Returns error if file cannot be read or its template cannot be rendered
     pub fn validate_file(&mut self, path: &Path) -> Result<ShapeValidationResult> {
         // Read and parse file
         let content = std::fs::read_to_string(path).map_err(|e| {
@@ -95,9 +151,10 @@ impl ShapeValidator {
         })?;
 
         // Check if template rendering is needed
-        let toml_content = if crate::is_template(&content) {
+        let rendered = if crate::is_template(&content) {
             // Render as Tera template
-            let mut renderer = crate::TemplateRenderer::new()?;
+            let mut renderer = crate::TemplateRenderer::new()?
+                .with_template_dir(path.parent().unwrap_or_else(|| Path::new("")));
             let path_str = path
                 .to_str()
                 .ok_or_else(|| CleanroomError::validation_error("Invalid file path encoding"))?;
@@ -106,21 +163,283 @@ impl ShapeValidator {
             content
         };
 
-        // Parse TOML
-        let config = toml::from_str::<TestConfig>(&toml_content)
-            .map_err(|e| CleanroomError::config_error(format!("TOML parse error: {}", e)))?;
+        if crate::config::is_yaml_config(path) {
+            return Ok(self.validate_yaml_content(&rendered, &path.to_string_lossy()));
+        }
+        Ok(self.validate_content(&rendered, &path.to_string_lossy()))
+    }
 
-        // Validate shape
-        self.validate_config(&config)?;
+    /// Validate configuration TOML that is already rendered
+    ///
+    /// Like [`Self::validate_file`] for content in memory, e.g. the output of
+    /// `clnrm render`. Error lines refer to `content`; `file_path` only labels
+    /// the result.
+    pub fn validate_content(&mut self, content: &str, file_path: &str) -> ShapeValidationResult {
+        // Parse TOML, collecting every structural error
+        self.reset();
+        if let Some(config) = self.parse_collecting_errors(content) {
+            // Validate shape
+            self.validate_parsed(&config);
+        }
+
+        self.result(file_path)
+    }
+
+    /// Validate configuration YAML that is already rendered
+    ///
+    /// serde_yaml stops at the first structural error, so at most one is
+    /// reported, at the line it occurred on.
+    pub fn validate_yaml_content(
+        &mut self,
+        content: &str,
+        file_path: &str,
+    ) -> ShapeValidationResult {
+        self.reset();
+        match serde_yaml::from_str::<TestConfig>(content) {
+            Ok(config) => self.validate_parsed(&config),
+            Err(e) => self.push_error(yaml_error(
+                ErrorCategory::InvalidStructure,
+                "Invalid configuration",
+                &e,
+            )),
+        }
+
+        self.result(file_path)
+    }
 
-        // Build result
-        let result = ShapeValidationResult {
-            passed: self.errors.is_empty(),
+    /// Result for the errors collected so far
+    fn result(&self, file_path: &str) -> ShapeValidationResult {
+        ShapeValidationResult {
+            passed: self.is_valid(),
             errors: self.errors.clone(),
-            file_path: path.to_string_lossy().into_owned(),
-        };
+            file_path: file_path.to_string(),
+            suppressed: self.suppressed,
+        }
+    }
+
+    /// Deserialize `content`, recording every structural error
+    ///
+    /// Each top-level section, `[[scenario]]`, `[[steps]]` entry and service
+    /// is deserialized separately so one bad entry doesn't hide the others.
+    /// Entries that fail are dropped and the rest is returned for semantic
+    /// validation; `None` means the document could not be parsed at all.
+    fn parse_collecting_errors(&mut self, content: &str) -> Option<TestConfig> {
+        // Collect uncapped so the cap keeps the errors that come first in the file
+        let max_errors = std::mem::replace(&mut self.max_errors, usize::MAX);
+        let config = self.parse_all_sections(content);
+        self.max_errors = max_errors;
+
+        self.errors.sort_by_key(|e| e.line.unwrap_or(usize::MAX));
+        if self.errors.len() > max_errors {
+            self.suppressed += self.errors.len() - max_errors;
+            self.errors.truncate(max_errors);
+        }
+        config
+    }
+
+    /// Body of [`Self::parse_collecting_errors`], before sorting and capping
+    fn parse_all_sections(&mut self, content: &str) -> Option<TestConfig> {
+        let (root, syntax_errors) = DeTable::parse_recoverable(content);
+        if !syntax_errors.is_empty() {
+            for e in &syntax_errors {
+                self.push_error(toml_error(
+                    ErrorCategory::InvalidStructure,
+                    "TOML syntax error",
+                    e,
+                    content,
+                ));
+            }
+            return None;
+        }
+
+        let root_span = root.span();
+        let root = root.into_inner();
+        self.collect_unknown_keys(&root, content);
+
+        let mut valid = DeTable::new();
+        for (key, value) in root.iter() {
+            let name = key.get_ref().as_ref();
+            let kept = match (name, value.get_ref()) {
+                ("scenario" | "steps", DeValue::Array(items)) => {
+                    self.check_array(name, value.span(), items, content)
+                }
+                ("service" | "services", DeValue::Table(services)) => {
+                    self.check_services(name, value.span(), services, content)
+                }
+                _ => self.check_section(name, value.clone(), &format!("[{}]", name), content),
+            };
+            if let Some(kept) = kept {
+                valid.insert(key.clone(), kept);
+            }
+        }
+
+        let deserializer =
+            toml::de::ValueDeserializer::from(Spanned::new(root_span, DeValue::Table(valid)));
+        match TestConfig::deserialize(deserializer) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                self.push_error(toml_error(
+                    ErrorCategory::InvalidStructure,
+                    "Invalid configuration",
+                    &e,
+                    content,
+                ));
+                None
+            }
+        }
+    }
+
+    /// Deserialize one top-level key on its own, returning it if it is valid
+    fn check_section<'i>(
+        &mut self,
+        key: &str,
+        value: Spanned<DeValue<'i>>,
+        label: &str,
+        content: &str,
+    ) -> Option<Spanned<DeValue<'i>>> {
+        let span = value.span();
+        let mut table = DeTable::new();
+        table.insert(
+            Spanned::new(span.clone(), key.to_string().into()),
+            value.clone(),
+        );
+
+        let deserializer =
+            toml::de::ValueDeserializer::from(Spanned::new(span, DeValue::Table(table)));
+        match TestConfig::deserialize(deserializer) {
+            Ok(_) => Some(value),
+            Err(e) => {
+                let category = if e.message().starts_with("missing field") {
+                    ErrorCategory::MissingRequired
+                } else {
+                    ErrorCategory::InvalidStructure
+                };
+                self.push_error(toml_error(category, label, &e, content));
+                None
+            }
+        }
+    }
+
+    /// Check each entry of an array of tables, keeping the valid ones
+    fn check_array<'i>(
+        &mut self,
+        key: &str,
+        span: std::ops::Range<usize>,
+        items: &DeArray<'i>,
+        content: &str,
+    ) -> Option<Spanned<DeValue<'i>>> {
+        let mut valid = DeArray::new();
+        for (idx, item) in items.iter().enumerate() {
+            let label = format!("[[{}]] #{}", key, idx + 1);
+            let single: DeArray<'i> = std::iter::once(item.clone()).collect();
+            let value = Spanned::new(item.span(), DeValue::Array(single));
+            if self.check_section(key, value, &label, content).is_some() {
+                valid.push(item.clone());
+            }
+        }
+        Some(Spanned::new(span, DeValue::Array(valid)))
+    }
+
+    /// Check each service definition, keeping the valid ones
+    fn check_services<'i>(
+        &mut self,
+        key: &str,
+        span: std::ops::Range<usize>,
+        services: &DeTable<'i>,
+        content: &str,
+    ) -> Option<Spanned<DeValue<'i>>> {
+        let mut valid = DeTable::new();
+        for (name, service) in services.iter() {
+            let label = format!("[{}.{}]", key, name.get_ref());
+            let mut single = DeTable::new();
+            single.insert(name.clone(), service.clone());
+            let value = Spanned::new(service.span(), DeValue::Table(single));
+            if self.check_section(key, value, &label, content).is_some() {
+                valid.insert(name.clone(), service.clone());
+            } else {
+                self.unparsed_services.insert(name.get_ref().to_string());
+            }
+        }
+        Some(Spanned::new(span, DeValue::Table(valid)))
+    }
 
-        Ok(result)
+    /// Report keys the schema doesn't declare at the top level, in `[meta]`,
+    /// services, scenarios and steps
+    fn collect_unknown_keys(&mut self, root: &DeTable<'_>, content: &str) {
+        self.check_unknown_keys::<TestConfig>(root, "at top level", content);
+
+        for (key, value) in root.iter() {
+            let name = key.get_ref().as_ref();
+            match (name, value.get_ref()) {
+                ("meta", DeValue::Table(meta)) => {
+                    self.check_unknown_keys::<MetaConfig>(meta, "in [meta]", content);
+                }
+                ("service" | "services", DeValue::Table(services)) => {
+                    for (service_name, service) in services.iter() {
+                        if let DeValue::Table(service) = service.get_ref() {
+                            let location = format!("in [{}.{}]", name, service_name.get_ref());
+                            self.check_unknown_keys::<ServiceConfig>(service, &location, content);
+                        }
+                    }
+                }
+                ("steps", DeValue::Array(steps)) => {
+                    for (idx, step) in tables(steps) {
+                        let location = format!("in [[steps]] #{}", idx + 1);
+                        self.check_unknown_keys::<StepConfig>(step, &location, content);
+                    }
+                }
+                ("scenario", DeValue::Array(scenarios)) => {
+                    for (idx, scenario) in tables(scenarios) {
+                        let label = format!("[[scenario]] #{}", idx + 1);
+                        let location = format!("in {}", label);
+                        self.check_unknown_keys::<ScenarioConfig>(scenario, &location, content);
+
+                        let steps = scenario
+                            .iter()
+                            .find(|(key, _)| key.get_ref() == "steps")
+                            .map(|(_, steps)| steps.get_ref());
+                        if let Some(DeValue::Array(steps)) = steps {
+                            for (step_idx, step) in tables(steps) {
+                                let location = format!("in step #{} of {}", step_idx + 1, label);
+                                self.check_unknown_keys::<StepConfig>(step, &location, content);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Report keys that `T` doesn't declare
+    fn check_unknown_keys<T: DeserializeOwned>(
+        &mut self,
+        table: &DeTable<'_>,
+        location: &str,
+        content: &str,
+    ) {
+        let known = struct_fields::<T>();
+        if known.is_empty() {
+            return;
+        }
+
+        for key in table.keys() {
+            let name = key.get_ref().as_ref();
+            if known.contains(&name) {
+                continue;
+            }
+
+            let suggestion = closest_match(name, known)
+                .map(|field| format!(", did you mean `{}`?", field))
+                .unwrap_or_default();
+            self.push_error(
+                ShapeValidationError::new(
+                    ErrorCategory::UnknownKey,
+                    format!("Unknown key `{}` {}{}", name, location, suggestion),
+                )
+                .with_line(line_of(content, key.span().start)),
+            );
+        }
     }
 
     /// Validate a parsed configuration
@@ -130,8 +449,13 @@ impl ShapeValidator {
     /// Returns error if validation logic fails unexpectedly
     pub fn validate_config(&mut self, config: &TestConfig) -> Result<()> {
         // Clear previous errors
-        self.errors.clear();
+        self.reset();
+        self.validate_parsed(config);
+        Ok(())
+    }
 
+    /// Run the semantic checks on a deserialized configuration
+    fn validate_parsed(&mut self, config: &TestConfig) {
         // 1. Validate required blocks
         self.validate_required_blocks(config);
 
@@ -167,15 +491,13 @@ impl ShapeValidator {
 
         // 12. Validate service dependencies (ENHANCED)
         self.validate_service_dependencies(config);
-
-        Ok(())
     }
 
     /// Validate required configuration blocks
     fn validate_required_blocks(&mut self, config: &TestConfig) {
         // Check [meta] or [test.metadata] exists
         if config.meta.is_none() && config.test.is_none() {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::MissingRequired,
                 "Configuration must have either [meta] or [test.metadata] section",
             ));
@@ -184,13 +506,13 @@ impl ShapeValidator {
         // Check meta has name and version (for v0.6.0 format)
         if let Some(ref meta) = config.meta {
             if meta.name.trim().is_empty() {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     "[meta] section missing required 'name' field",
                 ));
             }
             if meta.version.trim().is_empty() {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     "[meta] section missing required 'version' field",
                 ));
@@ -199,7 +521,7 @@ impl ShapeValidator {
 
         // Check at least one scenario exists
         if config.scenario.is_empty() && config.steps.is_empty() {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::MissingRequired,
                 "Configuration must have at least one [[scenario]] or [[steps]]",
             ));
@@ -225,7 +547,7 @@ impl ShapeValidator {
         ];
 
         if !valid_exporters.contains(&otel.exporter.as_str()) {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::OtelError,
                 format!(
                     "Invalid OTEL exporter '{}'. Valid options: {}",
@@ -238,7 +560,7 @@ impl ShapeValidator {
         // Validate sample ratio
         if let Some(ratio) = otel.sample_ratio {
             if !(0.0..=1.0).contains(&ratio) {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::OtelError,
                     format!(
                         "OTEL sample_ratio must be between 0.0 and 1.0, got {}",
@@ -253,16 +575,20 @@ impl ShapeValidator {
     fn validate_scenarios(&mut self, config: &TestConfig) {
         for (idx, scenario) in config.scenario.iter().enumerate() {
             if scenario.name.trim().is_empty() {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     format!("Scenario {} missing required 'name' field", idx),
                 ));
             }
 
-            if scenario.steps.is_empty() {
-                self.errors.push(ShapeValidationError::new(
+            // v1.0 scenarios run a single `run` command instead of steps
+            if scenario.steps.is_empty() && scenario.run.is_none() {
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
-                    format!("Scenario '{}' must have at least one step", scenario.name),
+                    format!(
+                        "Scenario '{}' must have at least one step or a 'run' command",
+                        scenario.name
+                    ),
                 ));
             }
         }
@@ -270,8 +596,9 @@ impl ShapeValidator {
 
     /// Validate service references
     fn validate_service_references(&mut self, config: &TestConfig) {
-        // Build set of defined services
-        let mut defined_services = HashSet::new();
+        // Build set of defined services, including ones already reported as
+        // malformed
+        let mut defined_services = self.unparsed_services.clone();
 
         if let Some(ref services) = config.services {
             for service_name in services.keys() {
@@ -294,7 +621,7 @@ impl ShapeValidator {
         for step in &config.steps {
             if let Some(ref service_name) = step.service {
                 if !defined_services.contains(service_name) {
-                    self.errors.push(ShapeValidationError::new(
+                    self.push_error(ShapeValidationError::new(
                         ErrorCategory::OrphanReference,
                         format!(
                             "Step '{}' references undefined service '{}'",
@@ -315,7 +642,7 @@ impl ShapeValidator {
         for step in &scenario.steps {
             if let Some(ref service_name) = step.service {
                 if !defined_services.contains(service_name) {
-                    self.errors.push(ShapeValidationError::new(
+                    self.push_error(ShapeValidationError::new(
                         ErrorCategory::OrphanReference,
                         format!(
                             "Scenario '{}' step '{}' references undefined service '{}'",
@@ -347,7 +674,7 @@ impl ShapeValidator {
                 for span in spans {
                     if let (Some(min), Some(max)) = (span.min_duration_ms, span.max_duration_ms) {
                         if min > max {
-                            self.errors.push(ShapeValidationError::new(
+                            self.push_error(ShapeValidationError::new(
                                 ErrorCategory::InvalidDuration,
                                 format!(
                                     "Span '{}' has invalid duration: min ({}) > max ({})",
@@ -424,7 +751,7 @@ impl ShapeValidator {
             if !visited.contains(node)
                 && Self::has_cycle_dfs(node, &graph, &mut visited, &mut rec_stack)
             {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::CircularOrdering,
                     format!(
                         "Circular temporal ordering detected involving span '{}'",
@@ -474,7 +801,7 @@ impl ShapeValidator {
             if let Some(ref spans) = otel_val.expected_spans {
                 for span in spans {
                     if let Err(e) = self.validate_glob_pattern(&span.name) {
-                        self.errors.push(ShapeValidationError::new(
+                        self.push_error(ShapeValidationError::new(
                             ErrorCategory::InvalidGlob,
                             format!("Invalid glob pattern in span '{}': {}", span.name, e),
                         ));
@@ -488,7 +815,7 @@ impl ShapeValidator {
     fn validate_expectation_globs(&mut self, expect: &ExpectationsConfig) {
         for span in &expect.span {
             if let Err(e) = self.validate_glob_pattern(&span.name) {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidGlob,
                     format!(
                         "Invalid glob pattern in span expectation '{}': {}",
@@ -514,7 +841,12 @@ impl ShapeValidator {
 
     /// Check if validation passed
     pub fn is_valid(&self) -> bool {
-        self.errors.is_empty()
+        self.errors.is_empty() && self.suppressed == 0
+    }
+
+    /// Number of errors dropped after reaching the `max_errors` cap
+    pub fn suppressed(&self) -> usize {
+        self.suppressed
     }
 
     // ========================================================================
@@ -534,7 +866,7 @@ impl ShapeValidator {
             // Check image exists and is not empty
             if let Some(ref image) = service.image {
                 if image.trim().is_empty() {
-                    self.errors.push(ShapeValidationError::new(
+                    self.push_error(ShapeValidationError::new(
                         ErrorCategory::InvalidStructure,
                         format!(
                             "Service '{}' has empty image. Suggestion: Use a valid image like 'alpine:latest' or 'ubuntu:20.04'",
@@ -562,7 +894,7 @@ impl ShapeValidator {
 
         // Check for invalid characters
         if image.contains(' ') {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::InvalidStructure,
                 format!(
                     "Service '{}' has invalid image format '{}'. Images cannot contain spaces. Example: 'alpine:latest'",
@@ -574,7 +906,7 @@ impl ShapeValidator {
 
         // Check for exclamation marks or other special characters
         if image.contains('!') || image.contains('?') || image.contains('*') {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::InvalidStructure,
                 format!(
                     "Service '{}' has invalid image format '{}'. Invalid characters detected. Example: 'alpine:latest'",
@@ -587,7 +919,7 @@ impl ShapeValidator {
         // Validate basic structure
         let parts: Vec<&str> = image.split('/').collect();
         if parts.len() > 3 {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::InvalidStructure,
                 format!(
                     "Service '{}' has invalid image format '{}'. Too many path segments. Example: 'registry/namespace/repo:tag'",
@@ -607,7 +939,7 @@ impl ShapeValidator {
                 for &port in ports {
                     // Check for reserved/system ports (1-1023)
                     if port < 1024 {
-                        self.errors.push(ShapeValidationError::new(
+                        self.push_error(ShapeValidationError::new(
                             ErrorCategory::InvalidStructure,
                             format!(
                                 "Service '{}' uses reserved port {}. Suggestion: Use ports >= 1024 (e.g., 8080, 9000, 3000)",
@@ -628,7 +960,7 @@ impl ShapeValidator {
         // Check for port conflicts
         for (port, services) in port_usage {
             if services.len() > 1 {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     format!(
                         "Port conflict detected: port {} is used by multiple services: {}. Each service must use unique ports.",
@@ -657,7 +989,7 @@ impl ShapeValidator {
     fn validate_single_volume(&mut self, service_name: &str, idx: usize, volume: &VolumeConfig) {
         // Check host path is absolute
         if !volume.host_path.starts_with('/') {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::InvalidStructure,
                 format!(
                     "Service '{}' volume {}: host path '{}' must be absolute. Suggestion: Use '/tmp/data' or '/home/user/project'",
@@ -668,7 +1000,7 @@ impl ShapeValidator {
 
         // Check container path is absolute
         if !volume.container_path.starts_with('/') {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::InvalidStructure,
                 format!(
                     "Service '{}' volume {}: container path '{}' must be absolute. Suggestion: Use '/app/data' or '/var/lib/app'",
@@ -701,7 +1033,7 @@ impl ShapeValidator {
                         .container_path
                         .starts_with(&format!("{}/", dangerous)))
             {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     format!(
                         "Service '{}' volume {}: mounting to system path '{}' is dangerous. Suggestion: Use application paths like '/app/data'",
@@ -752,7 +1084,7 @@ impl ShapeValidator {
     fn validate_env_var(&mut self, context: &str, key: &str, value: &str) {
         // Check key is not empty
         if key.is_empty() {
-            self.errors.push(ShapeValidationError::new(
+            self.push_error(ShapeValidationError::new(
                 ErrorCategory::InvalidStructure,
                 format!(
                     "{}: environment variable name cannot be empty. Use uppercase names like 'APP_ENV' or 'DATABASE_URL'",
@@ -767,7 +1099,7 @@ impl ShapeValidator {
         match Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$") {
             Ok(env_var_regex) => {
                 if !env_var_regex.is_match(key) {
-                    self.errors.push(ShapeValidationError::new(
+                    self.push_error(ShapeValidationError::new(
                         ErrorCategory::InvalidStructure,
                         format!(
                             "{}: invalid environment variable name '{}'. Names must start with a letter or underscore and contain only alphanumeric characters and underscores. Example: 'DATABASE_URL'",
@@ -778,7 +1110,7 @@ impl ShapeValidator {
             }
             Err(e) => {
                 // This should never happen with a static pattern, but handle gracefully
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     format!("{}: internal error compiling regex: {}", context, e),
                 ));
@@ -803,7 +1135,7 @@ impl ShapeValidator {
                 && !value.is_empty()
                 && !value.starts_with('$')
             {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::InvalidStructure,
                     format!(
                         "{}: potential hardcoded sensitive value in '{}'. Suggestion: Use environment variable references like '${{ENV_VAR}}' or template variables",
@@ -823,9 +1155,11 @@ impl ShapeValidator {
         let mut dep_graph: HashMap<String, Vec<String>> = HashMap::new();
 
         for (service_name, service) in &services {
-            if let Some(ref health_check) = service.health_check {
+            if let Some(HealthCheck::Command { command }) =
+                service.health_check.as_ref().map(|h| &h.check)
+            {
                 // Extract service dependencies from health check commands
-                let deps = self.extract_service_deps_from_command(&health_check.cmd, &services);
+                let deps = self.extract_service_deps_from_command(command, &services);
                 if !deps.is_empty() {
                     dep_graph.insert(service_name.clone(), deps);
                 }
@@ -840,7 +1174,7 @@ impl ShapeValidator {
             if !visited.contains(service_name)
                 && Self::has_circular_dep(service_name, &dep_graph, &mut visited, &mut rec_stack)
             {
-                self.errors.push(ShapeValidationError::new(
+                self.push_error(ShapeValidationError::new(
                     ErrorCategory::CircularOrdering,
                     format!(
                         "Circular service dependency detected involving '{}'. Services cannot depend on each other in a cycle.",
@@ -919,6 +1253,152 @@ impl ShapeValidator {
     }
 }
 
+/// Find keys no configuration struct declares, with "did you mean" hints
+///
+/// Only the unknown-key check is run; content that isn't valid TOML yields
+/// no findings.
+pub fn find_unknown_keys(content: &str) -> Vec<ShapeValidationError> {
+    let Ok(root) = DeTable::parse(content) else {
+        return Vec::new();
+    };
+
+    let mut validator = ShapeValidator::new().with_max_errors(usize::MAX);
+    validator.collect_unknown_keys(root.get_ref(), content);
+    validator
+        .errors
+        .sort_by_key(|e| e.line.unwrap_or(usize::MAX));
+    validator.errors
+}
+
+/// Tables in an array of tables, with their indices
+fn tables<'a, 'i>(array: &'a DeArray<'i>) -> impl Iterator<Item = (usize, &'a DeTable<'i>)> {
+    array
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, item)| match item.get_ref() {
+            DeValue::Table(table) => Some((idx, table)),
+            _ => None,
+        })
+}
+
+/// Known field closest to `key`, if it is plausibly a typo of it
+fn closest_match(key: &str, known: &[&'static str]) -> Option<&'static str> {
+    let max_distance = (key.chars().count() / 3).max(1);
+    known
+        .iter()
+        .map(|field| (edit_distance(key, field), *field))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, field)| field)
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Build an error for a TOML deserialization failure, locating it by line
+fn toml_error(
+    category: ErrorCategory,
+    label: &str,
+    error: &toml::de::Error,
+    content: &str,
+) -> ShapeValidationError {
+    let shape_error =
+        ShapeValidationError::new(category, format!("{}: {}", label, error.message().trim()));
+    match error.span() {
+        Some(span) => shape_error.with_line(line_of(content, span.start)),
+        None => shape_error,
+    }
+}
+
+/// Build an error for a YAML deserialization failure, locating it by line
+///
+/// serde_yaml's `at line L column C` is replaced by the error's line and a
+/// trailing `(column C)`.
+fn yaml_error(
+    category: ErrorCategory,
+    label: &str,
+    error: &serde_yaml::Error,
+) -> ShapeValidationError {
+    let message = error.to_string();
+    match error.location() {
+        Some(location) => {
+            let message = message.replacen(
+                &format!(" at line {} column {}", location.line(), location.column()),
+                "",
+                1,
+            );
+            ShapeValidationError::new(
+                category,
+                format!("{}: {} (column {})", label, message, location.column()),
+            )
+            .with_line(location.line())
+        }
+        None => ShapeValidationError::new(category, format!("{}: {}", label, message)),
+    }
+}
+
+/// 1-based line number of a byte offset
+pub(crate) fn line_of(content: &str, offset: usize) -> usize {
+    content
+        .get(..offset)
+        .map_or(0, |before| before.matches('\n').count())
+        + 1
+}
+
+/// Field names (aliases included) a derived `Deserialize` struct accepts
+///
+/// Asks `T` to deserialize from a probe that records the field list serde
+/// passes to `deserialize_struct`. Returns an empty list for types that don't
+/// deserialize as a plain struct (e.g. ones using `#[serde(flatten)]`).
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldsProbe<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for FieldsProbe<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(
+            self,
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> std::result::Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(serde::de::Error::custom("fields recorded"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldsProbe(&mut fields));
+    fields
+}
+
 impl Default for ShapeValidator {
     fn default() -> Self {
         Self::new()
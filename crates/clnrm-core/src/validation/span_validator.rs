@@ -87,14 +87,73 @@ pub struct SpanData {
     pub end_time_unix_nano: Option<u64>,
     /// Span kind (internal, server, client, producer, consumer)
     pub kind: Option<SpanKind>,
-    /// Span events (array of event names)
-    pub events: Option<Vec<String>>,
+    /// Span events with their attributes
+    pub events: Option<Vec<SpanEvent>>,
     /// Resource attributes (shared across all spans in a resource)
     #[serde(default)]
     pub resource_attributes: HashMap<String, serde_json::Value>,
 }
 
+/// A span event (log record) with its attributes
+///
+/// Deserializes from an event object or, for traces that only record
+/// event names, from a bare name.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "SpanEventRepr")]
+pub struct SpanEvent {
+    /// Event name (e.g., "exception", "cache.miss")
+    pub name: String,
+    /// Event attributes as key-value pairs
+    #[serde(default)]
+    pub attributes: HashMap<String, serde_json::Value>,
+}
+
+impl SpanEvent {
+    /// Create an event without attributes
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            attributes: HashMap::new(),
+        }
+    }
+}
+
+/// Serialized forms of a [`SpanEvent`]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SpanEventRepr {
+    Name(String),
+    Event {
+        name: String,
+        #[serde(default)]
+        attributes: HashMap<String, serde_json::Value>,
+    },
+}
+
+impl From<SpanEventRepr> for SpanEvent {
+    fn from(repr: SpanEventRepr) -> Self {
+        match repr {
+            SpanEventRepr::Name(name) => Self::new(name),
+            SpanEventRepr::Event { name, attributes } => Self { name, attributes },
+        }
+    }
+}
+
 impl SpanData {
+    /// Names of the span's events, in the order they were recorded
+    pub fn event_names(&self) -> Vec<&str> {
+        self.events
+            .iter()
+            .flatten()
+            .map(|event| event.name.as_str())
+            .collect()
+    }
+
+    /// Whether the span recorded an event named `name`
+    pub fn has_event(&self, name: &str) -> bool {
+        self.events.iter().flatten().any(|event| event.name == name)
+    }
+
     /// Calculate span duration in milliseconds
     pub fn duration_ms(&self) -> Option<f64> {
         match (self.start_time_unix_nano, self.end_time_unix_nano) {
@@ -362,17 +421,7 @@ impl SpanValidator {
         }
 
         // Parse events
-        let events = span_obj
-            .get("events")
-            .and_then(|v| v.as_array())
-            .map(|events_array| {
-                events_array
-                    .iter()
-                    .filter_map(|event| {
-                        event.get("name").and_then(|n| n.as_str()).map(String::from)
-                    })
-                    .collect()
-            });
+        let events = crate::otel::otlp_receiver::parse_span_events(span_obj);
 
         Some(SpanData {
             name,
@@ -722,19 +771,18 @@ impl SpanValidator {
         any_events: &[String],
         span_name: &str,
     ) -> Option<FailureDetails> {
-        if let Some(ref span_events) = span.events {
-            let has_any = any_events.iter().any(|event| span_events.contains(event));
-
-            if has_any {
-                return None;
-            }
+        if any_events.iter().any(|event| span.has_event(event)) {
+            return None;
         }
 
         Some(FailureDetails {
             rule: format!("expect.span[{}].events.any", span_name),
             span_name: span_name.to_string(),
             expected: format!("Any of: [{}]", any_events.join(", ")),
-            actual: span.events.as_ref().map(|events| format!("{:?}", events)),
+            actual: span
+                .events
+                .as_ref()
+                .map(|_| format!("{:?}", span.event_names())),
             message: format!(
                 "Span '{}' missing required events: [{}]",
                 span_name,
@@ -750,10 +798,10 @@ impl SpanValidator {
         all_events: &[String],
         span_name: &str,
     ) -> Option<FailureDetails> {
-        if let Some(ref span_events) = span.events {
+        if span.events.is_some() {
             let missing: Vec<&String> = all_events
                 .iter()
-                .filter(|event| !span_events.contains(event))
+                .filter(|event| !span.has_event(event))
                 .collect();
 
             if missing.is_empty() {
@@ -1038,13 +1086,9 @@ impl SpanValidator {
                 }
 
                 // Check if any span has at least one of the expected events
-                let has_any_event = spans.iter().any(|span| {
-                    if let Some(span_events) = &span.events {
-                        events.iter().any(|event| span_events.contains(event))
-                    } else {
-                        false
-                    }
-                });
+                let has_any_event = spans
+                    .iter()
+                    .any(|span| events.iter().any(|event| span.has_event(event)));
 
                 if !has_any_event {
                     return Err(CleanroomError::validation_error(format!(
@@ -3,7 +3,7 @@
 //! Validates OTEL span status codes (OK/ERROR/UNSET) with support for
 //! glob patterns to match span names flexibly.
 
-use crate::error::{CleanroomError, Result};
+use crate::error::{CleanroomError, ErrorCode, Result};
 use crate::validation::span_validator::SpanData;
 use glob::Pattern;
 use serde::{Deserialize, Serialize};
@@ -127,7 +127,8 @@ impl StatusExpectation {
                         span.name,
                         actual.as_str(),
                         expected_all.as_str()
-                    )));
+                    ))
+                    .with_code(ErrorCode::SpanStatus));
                 }
             }
         }
@@ -151,7 +152,8 @@ impl StatusExpectation {
                 return Err(CleanroomError::validation_error(format!(
                     "Status validation failed: no spans match pattern '{}'",
                     pattern
-                )));
+                ))
+                .with_code(ErrorCode::SpanNotFound));
             }
 
             // Validate each matching span
@@ -161,7 +163,7 @@ impl StatusExpectation {
                     return Err(CleanroomError::validation_error(format!(
                         "Status validation failed: span '{}' matching pattern '{}' has status {} but expected {}",
                         span.name, pattern, actual.as_str(), expected_status.as_str()
-                    )));
+                    )).with_code(ErrorCode::SpanStatus));
                 }
             }
         }
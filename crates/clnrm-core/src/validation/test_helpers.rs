@@ -4,7 +4,7 @@
 //! across validation test suites. All test helper functions follow AAA pattern
 //! and proper error handling.
 
-use crate::validation::span_validator::{SpanData, SpanKind};
+use crate::validation::span_validator::{SpanData, SpanEvent, SpanKind};
 use serde_json::json;
 use std::collections::HashMap;
 
@@ -32,7 +32,7 @@ pub struct SpanBuilder {
     start_time_unix_nano: Option<u64>,
     end_time_unix_nano: Option<u64>,
     kind: Option<SpanKind>,
-    events: Option<Vec<String>>,
+    events: Option<Vec<SpanEvent>>,
 }
 
 impl SpanBuilder {
@@ -141,14 +141,14 @@ impl SpanBuilder {
 
     /// Add span events
     pub fn with_events(mut self, events: Vec<String>) -> Self {
-        self.events = Some(events);
+        self.events = Some(events.into_iter().map(SpanEvent::new).collect());
         self
     }
 
     /// Add a single event
     pub fn with_event(mut self, event: impl Into<String>) -> Self {
         let mut events = self.events.unwrap_or_default();
-        events.push(event.into());
+        events.push(SpanEvent::new(event));
         self.events = Some(events);
         self
     }
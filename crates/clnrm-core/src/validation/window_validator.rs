@@ -3,7 +3,7 @@
 //! Validates that child spans are temporally contained within parent spans.
 //! This ensures proper span lifecycle management and helps detect timing issues.
 
-use crate::error::{CleanroomError, Result};
+use crate::error::{CleanroomError, ErrorCode, Result};
 use crate::validation::span_validator::SpanData;
 use serde::{Deserialize, Serialize};
 
@@ -89,6 +89,7 @@ impl WindowExpectation {
                 "Window validation failed: span '{}' not found in trace",
                 name
             ))
+            .with_code(ErrorCode::SpanNotFound)
         })
     }
 
@@ -127,7 +128,8 @@ impl WindowExpectation {
                 "Window validation failed: child span '{}' started before outer span '{}' \
                  (child_start: {}, outer_start: {})",
                 child_name, outer_name, child_start, outer_start
-            )));
+            ))
+            .with_code(ErrorCode::SpanWindow));
         }
 
         // Check: child.end <= outer.end
@@ -136,7 +138,8 @@ impl WindowExpectation {
                 "Window validation failed: child span '{}' ended after outer span '{}' \
                  (child_end: {}, outer_end: {})",
                 child_name, outer_name, child_end, outer_end
-            )));
+            ))
+            .with_code(ErrorCode::SpanWindow));
         }
 
         Ok(())
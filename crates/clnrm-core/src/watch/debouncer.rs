@@ -9,6 +9,8 @@
 //! - ✅ No unwrap() or expect() calls
 //! - ✅ Performance-optimized for <200ms debounce window
 
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use tracing::debug;
 
@@ -25,10 +27,13 @@ use tracing::debug;
 ///
 /// let mut debouncer = FileDebouncer::new(Duration::from_millis(200));
 ///
-/// // Record multiple events
-/// debouncer.record_event();
-/// debouncer.record_event();
-/// debouncer.record_event();
+/// // Record multiple events (e.g. an editor's atomic save)
+/// debouncer.record_change("tests/api.clnrm.toml.tera");
+/// debouncer.record_change("tests/api.clnrm.toml.tera");
+/// debouncer.record_change("tests/db.clnrm.toml.tera");
+///
+/// assert_eq!(debouncer.changed_paths().len(), 2);
+/// assert_eq!(debouncer.coalesced_count(), 2);
 ///
 /// // Check if we should trigger (after delay)
 /// if debouncer.should_trigger() {
@@ -44,6 +49,8 @@ pub struct FileDebouncer {
     last_event: Option<Instant>,
     /// Number of events in current window
     event_count: usize,
+    /// Distinct paths changed since the last reset
+    changed_paths: BTreeSet<PathBuf>,
 }
 
 impl FileDebouncer {
@@ -66,6 +73,7 @@ impl FileDebouncer {
             window,
             last_event: None,
             event_count: 0,
+            changed_paths: BTreeSet::new(),
         }
     }
 
@@ -103,6 +111,15 @@ impl FileDebouncer {
         self.last_event = Some(now);
     }
 
+    /// Record a change event for `path`
+    ///
+    /// Same as [`record_event`](Self::record_event), but also remembers the
+    /// path so the triggered run can report which files caused it.
+    pub fn record_change(&mut self, path: impl Into<PathBuf>) {
+        self.record_event();
+        self.changed_paths.insert(path.into());
+    }
+
     /// Check if debounced events should trigger action
     ///
     /// Returns true if:
@@ -134,12 +151,13 @@ impl FileDebouncer {
 
     /// Reset debouncer state after triggering action
     ///
-    /// Clears event count and last event timestamp.
+    /// Clears event count, changed paths and last event timestamp.
     /// Call this after processing debounced events.
     pub fn reset(&mut self) {
         debug!("Debouncer: Reset (processed {} event(s))", self.event_count);
         self.last_event = None;
         self.event_count = 0;
+        self.changed_paths.clear();
     }
 
     /// Get current event count in window
@@ -149,6 +167,20 @@ impl FileDebouncer {
         self.event_count
     }
 
+    /// Get the distinct paths changed since the last reset, in sorted order
+    pub fn changed_paths(&self) -> impl ExactSizeIterator<Item = &Path> {
+        self.changed_paths.iter().map(PathBuf::as_path)
+    }
+
+    /// Get the number of events merged into the pending run
+    ///
+    /// Every event after the first in a window is coalesced away; a high
+    /// count for a single file usually means the editor saves atomically
+    /// (write temp file, rename) and emits several events per save.
+    pub fn coalesced_count(&self) -> usize {
+        self.event_count.saturating_sub(1)
+    }
+
     /// Get time since last event
     ///
     /// Returns None if no events have been recorded.
@@ -0,0 +1,74 @@
+//! Include-dependency tracking for selective test runs
+//!
+//! Maps each watched test template to the partials it pulls in through
+//! `{% include %}`, `{% import %}` or `{% extends %}`, so a change to a
+//! partial re-runs only the tests that use it.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Test templates and the partial files each one depends on
+///
+/// Paths are canonicalized so watcher events (usually absolute) match
+/// discovered test paths (often relative).
+#[derive(Debug, Default)]
+pub struct DependencyGraph {
+    tests: BTreeMap<PathBuf, BTreeSet<PathBuf>>,
+}
+
+impl DependencyGraph {
+    /// Build the graph for a set of test templates
+    pub fn build(tests: &[PathBuf]) -> Self {
+        let mut graph = Self::default();
+        for test in tests {
+            graph.update(test);
+        }
+        graph
+    }
+
+    /// Re-read `test` and record its current dependencies
+    ///
+    /// A template that fails to parse or load is tracked without
+    /// dependencies; its own run reports the error.
+    pub fn update(&mut self, test: &Path) {
+        let dependencies = match crate::config::template_dependencies(test) {
+            Ok(paths) => paths.iter().map(|path| canonical(path)).collect(),
+            Err(e) => {
+                debug!("Could not resolve includes of {}: {}", test.display(), e);
+                BTreeSet::new()
+            }
+        };
+        self.tests.insert(canonical(test), dependencies);
+    }
+
+    /// Stop tracking a deleted test
+    pub fn remove(&mut self, test: &Path) {
+        self.tests.remove(&canonical(test));
+    }
+
+    /// Tests that depend on `path`, directly or through nested partials
+    pub fn dependents(&self, path: &Path) -> Vec<PathBuf> {
+        let path = canonical(path);
+        self.tests
+            .iter()
+            .filter(|(_, dependencies)| dependencies.contains(&path))
+            .map(|(test, _)| test.clone())
+            .collect()
+    }
+
+    /// Number of tracked tests
+    pub fn len(&self) -> usize {
+        self.tests.len()
+    }
+
+    /// Whether no tests are tracked
+    pub fn is_empty(&self) -> bool {
+        self.tests.is_empty()
+    }
+}
+
+/// Canonical form of `path`, or `path` itself once the file is gone
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -1,7 +1,9 @@
 //! File watching subsystem for auto-test execution
 //!
 //! Provides file system watching capabilities for development mode,
-//! automatically re-running tests when `.toml.tera` files change.
+//! automatically re-running tests when `.toml.tera` files change. Only the
+//! affected tests re-run: a changed test runs itself, and a changed partial
+//! runs every test that includes it.
 //!
 //! # Architecture
 //!
@@ -9,6 +11,7 @@
 //! - `NotifyWatcher`: Production implementation using `notify` crate
 //! - `WatchConfig`: Configuration for watch behavior
 //! - `debouncer`: Time-based event batching to prevent excessive runs
+//! - `dependencies`: Include map from tests to the partials they use
 //!
 //! # London TDD Approach
 //!
@@ -43,13 +46,16 @@
 //! ```
 
 pub mod debouncer;
+pub mod dependencies;
 pub mod watcher;
 
 pub use debouncer::FileDebouncer;
+pub use dependencies::DependencyGraph;
 pub use watcher::{FileWatcher, NotifyWatcher, WatchConfig, WatchEvent};
 
 use crate::error::Result;
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{debug, error, info, warn};
@@ -66,9 +72,10 @@ use tracing::{debug, error, info, warn};
 /// # Behavior
 ///
 /// 1. Creates file watcher for specified paths
-/// 2. Filters events for `.toml.tera` files only
+/// 2. Filters events for `.toml.tera` files only, ignoring editor temp files
 /// 3. Debounces rapid file saves (prevents excessive runs)
-/// 4. Executes tests when debounce window expires
+/// 4. Executes the affected tests when debounce window expires (every test
+///    with [`WatchConfig::all_on_change`])
 /// 5. Clears screen between runs if configured
 ///
 /// # Performance Target
@@ -108,7 +115,10 @@ pub async fn watch_and_run(config: WatchConfig) -> Result<()> {
 
     // Run initial tests
     info!("🧪 Running initial tests...");
-    run_tests(&config).await?;
+    let test_paths = determine_test_paths(&config.paths)?;
+    let mut dependencies = DependencyGraph::build(&test_paths);
+    debug!("Tracking includes of {} test file(s)", dependencies.len());
+    run_tests(&config, &test_paths).await?;
 
     info!("👀 Watching for changes (Press Ctrl+C to stop)...");
 
@@ -119,10 +129,10 @@ pub async fn watch_and_run(config: WatchConfig) -> Result<()> {
             Some(event) = rx.recv() => {
                 debug!("Received watch event: {:?}", event);
 
-                // Filter for .toml.tera files
+                // Filter for .toml.tera files, skipping editor temp files
                 if is_relevant_file(&event.path) {
                     info!("📝 Change detected: {}", event.path.display());
-                    debouncer.record_event();
+                    debouncer.record_change(event.path);
                 } else {
                     debug!("Ignoring non-template file: {}", event.path.display());
                 }
@@ -131,19 +141,36 @@ pub async fn watch_and_run(config: WatchConfig) -> Result<()> {
             // Check debouncer periodically
             _ = tokio::time::sleep(std::time::Duration::from_millis(50)) => {
                 if debouncer.should_trigger() {
+                    let file_count = debouncer.changed_paths().len();
                     let event_count = debouncer.event_count();
-                    info!("🔄 Running tests ({} change{})...",
+                    info!("🔄 Running tests ({} file{} changed, {} event{}, {} coalesced)...",
+                        file_count,
+                        if file_count == 1 { "" } else { "s" },
                         event_count,
-                        if event_count == 1 { "" } else { "s" }
+                        if event_count == 1 { "" } else { "s" },
+                        debouncer.coalesced_count()
                     );
+                    for path in debouncer.changed_paths() {
+                        info!("   • {}", path.display());
+                    }
 
                     // Clear screen if configured
                     if config.clear_screen {
                         clear_terminal();
                     }
 
+                    let test_paths = if config.all_on_change {
+                        determine_test_paths(&config.paths)
+                    } else {
+                        Ok(affected_test_paths(&mut dependencies, debouncer.changed_paths()))
+                    };
+
                     // Run tests and handle errors gracefully
-                    match run_tests(&config).await {
+                    let result = match test_paths {
+                        Ok(test_paths) => run_tests(&config, &test_paths).await,
+                        Err(e) => Err(e),
+                    };
+                    match result {
                         Ok(_) => {
                             info!("✅ Tests completed");
                         }
@@ -166,11 +193,12 @@ pub async fn watch_and_run(config: WatchConfig) -> Result<()> {
 /// # Arguments
 ///
 /// * `config` - Watch configuration containing CLI settings
+/// * `test_paths` - Test files to run
 ///
 /// # Returns
 ///
 /// Result indicating success or failure of test execution
-async fn run_tests(config: &WatchConfig) -> Result<()> {
+async fn run_tests(config: &WatchConfig, test_paths: &[PathBuf]) -> Result<()> {
     // Use the CLI configuration to run tests
     // This integrates with the existing test runner
 
@@ -179,9 +207,6 @@ async fn run_tests(config: &WatchConfig) -> Result<()> {
         config.cli_config.parallel, config.cli_config.jobs
     );
 
-    // Determine test paths to run
-    let test_paths = determine_test_paths(&config.paths)?;
-
     if test_paths.is_empty() {
         warn!("No test files found in watched paths");
         return Ok(());
@@ -190,7 +215,45 @@ async fn run_tests(config: &WatchConfig) -> Result<()> {
     info!("Running {} test file(s)", test_paths.len());
 
     // Execute tests using the run command logic
-    crate::cli::commands::run::run_tests(&test_paths, &config.cli_config).await
+    crate::cli::commands::run::run_tests(test_paths, &config.cli_config).await
+}
+
+/// Determine which tests a batch of changed files affects
+///
+/// A changed partial selects every test that includes it; any other
+/// changed file selects itself. Include maps of changed tests are refreshed
+/// first, since an edit may add or drop includes.
+///
+/// # Arguments
+///
+/// * `dependencies` - Include map of the watched tests
+/// * `changed` - Files changed since the last run
+///
+/// # Returns
+///
+/// Test file paths to execute
+fn affected_test_paths<'a>(
+    dependencies: &mut DependencyGraph,
+    changed: impl Iterator<Item = &'a Path>,
+) -> Vec<PathBuf> {
+    let mut test_paths = BTreeSet::new();
+
+    for path in changed {
+        if !path.is_file() {
+            dependencies.remove(path);
+        } else {
+            dependencies.update(path);
+        }
+
+        let dependents = dependencies.dependents(path);
+        if !dependents.is_empty() {
+            test_paths.extend(dependents);
+        } else if path.is_file() {
+            test_paths.insert(path.to_path_buf());
+        }
+    }
+
+    test_paths.into_iter().collect()
 }
 
 /// Determine which test files to run from watched paths
@@ -233,7 +296,9 @@ fn determine_test_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
 
 /// Check if a file is relevant for test watching
 ///
-/// Currently watches `.toml.tera` template files.
+/// Currently watches `.toml.tera` template files. Editor swap, backup and
+/// lock files (`.swp`, `~`, `.#name`, ...) are never relevant, so atomic
+/// saves don't trigger extra runs.
 ///
 /// # Arguments
 ///
@@ -243,6 +308,10 @@ fn determine_test_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
 ///
 /// `true` if file should trigger test runs, `false` otherwise
 fn is_relevant_file(path: &std::path::Path) -> bool {
+    if is_editor_temp_file(path) {
+        return false;
+    }
+
     path.extension()
         .and_then(|ext| ext.to_str())
         .map(|ext| ext == "tera")
@@ -254,6 +323,31 @@ fn is_relevant_file(path: &std::path::Path) -> bool {
             .unwrap_or(false)
 }
 
+/// Check if a file is an editor's temporary, swap, backup or lock file
+fn is_editor_temp_file(path: &std::path::Path) -> bool {
+    const TEMP_SUFFIXES: &[&str] = &[
+        "~",
+        ".swp",
+        ".swo",
+        ".swx",
+        ".tmp",
+        ".bak",
+        "___jb_tmp___",
+        "___jb_old___",
+    ];
+
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    TEMP_SUFFIXES.iter().any(|suffix| name.ends_with(suffix))
+        // Emacs lock files and auto-saves
+        || name.starts_with(".#")
+        || (name.len() > 1 && name.starts_with('#') && name.ends_with('#'))
+        // Vim's write-permission probe
+        || name == "4913"
+}
+
 /// Clear terminal screen
 ///
 /// Provides clean output between test runs.
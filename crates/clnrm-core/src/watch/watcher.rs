@@ -62,6 +62,8 @@ pub struct WatchConfig {
     pub filter_pattern: Option<String>,
     /// Optional timebox limit in milliseconds per scenario
     pub timebox_ms: Option<u64>,
+    /// Re-run every test on any change instead of only the affected ones
+    pub all_on_change: bool,
 }
 
 impl WatchConfig {
@@ -93,6 +95,7 @@ impl WatchConfig {
             cli_config: CliConfig::default(),
             filter_pattern: None,
             timebox_ms: None,
+            all_on_change: false,
         }
     }
 
@@ -170,6 +173,28 @@ impl WatchConfig {
         self
     }
 
+    /// Re-run every watched test on any change
+    ///
+    /// By default only the changed tests, and the tests that include a
+    /// changed partial, are re-run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use clnrm_core::watch::WatchConfig;
+    /// use std::path::PathBuf;
+    ///
+    /// let config = WatchConfig::new(
+    ///     vec![PathBuf::from("tests/")],
+    ///     300,
+    ///     false
+    /// ).with_all_on_change();
+    /// ```
+    pub fn with_all_on_change(mut self) -> Self {
+        self.all_on_change = true;
+        self
+    }
+
     /// Check if a filter pattern is set
     pub fn has_filter_pattern(&self) -> bool {
         self.filter_pattern.is_some()
@@ -0,0 +1,100 @@
+//! `clnrm analyze --baseline` comparing reports across runs
+
+use clnrm_core::cli::commands::v0_7_0::analyze::{AnalysisReport, ValidatorResult};
+
+fn report(validators: &[(&str, bool)]) -> AnalysisReport {
+    AnalysisReport {
+        test_name: "checkout".to_string(),
+        traces_file: "traces.json".to_string(),
+        span_count: 3,
+        event_count: 0,
+        digest: "sha256:abc".to_string(),
+        validators: validators
+            .iter()
+            .map(|(name, passed)| ValidatorResult {
+                name: name.to_string(),
+                passed: *passed,
+                details: format!("{} details", name),
+            })
+            .collect(),
+    }
+}
+
+fn names(results: &[ValidatorResult]) -> Vec<&str> {
+    results.iter().map(|v| v.name.as_str()).collect()
+}
+
+#[test]
+fn test_changed_validators_are_reported() {
+    let baseline = report(&[
+        ("Span Expectations", true),
+        ("Counts", true),
+        ("Ordering", false),
+        ("Status", false),
+    ]);
+    let current = report(&[
+        ("Span Expectations", true),
+        ("Counts", false),
+        ("Ordering", true),
+        ("Status", false),
+        ("Hermeticity", false),
+    ]);
+
+    let comparison = current.compare_to(&baseline);
+
+    assert!(comparison.has_regressions());
+    assert_eq!(names(&comparison.newly_failing), ["Counts", "Hermeticity"]);
+    assert_eq!(names(&comparison.newly_passing), ["Ordering"]);
+
+    let summary = comparison.format_summary();
+    assert!(
+        summary.contains("2 new validation failure(s), 1 newly passing"),
+        "{}",
+        summary
+    );
+    assert!(
+        summary.contains("❌ Counts now fails (Counts details)"),
+        "{}",
+        summary
+    );
+}
+
+#[test]
+fn test_failures_already_in_baseline_are_not_regressions() {
+    let baseline = report(&[("Counts", false), ("Status", true)]);
+    let current = report(&[("Counts", false), ("Status", true)]);
+
+    let comparison = current.compare_to(&baseline);
+
+    assert!(!comparison.has_regressions());
+    assert!(comparison.newly_passing.is_empty());
+    assert!(comparison
+        .format_summary()
+        .contains("No validator changed outcome"));
+}
+
+#[test]
+fn test_saved_report_loads_as_baseline() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("report.json");
+    let saved = report(&[("Counts", true), ("Status", false)]);
+
+    saved.save(&path).expect("report saves");
+    let loaded = AnalysisReport::load(&path).expect("report loads");
+
+    assert_eq!(loaded.test_name, "checkout");
+    assert_eq!(loaded.digest, saved.digest);
+    assert_eq!(names(&loaded.validators), ["Counts", "Status"]);
+    assert!(!loaded.compare_to(&saved).has_regressions());
+}
+
+#[test]
+fn test_invalid_baseline_names_the_file() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("report.json");
+    std::fs::write(&path, "{}").expect("write report");
+
+    let error = AnalysisReport::load(&path).expect_err("not a report");
+
+    assert!(error.to_string().contains("report.json"), "{}", error);
+}
@@ -5,14 +5,24 @@
 //! - Mock factories
 //! - Assertion helpers
 //! - Common test fixtures
+//! - Test file and trace writers
+//!
+//! Each test binary uses a different subset, hence `dead_code` is allowed.
+
+#![allow(dead_code)]
 
 use clnrm_core::backend::{Cmd, RunResult};
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::cli::types::{CliConfig, CliTestResult};
 use clnrm_core::config::*;
 use clnrm_core::policy::Policy;
 use clnrm_core::scenario::StepResult;
+use clnrm_core::testing::TestResult;
+use clnrm_core::validation::span_validator::SpanData;
 use clnrm_core::Result;
+use serde_json::{json, Value};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // ============================================================================
 // Test Data Builders
@@ -53,6 +63,12 @@ impl TestConfigBuilder {
             expected_exit_code: None,
             continue_on_failure: None,
             service: None,
+            skip: None,
+            skip_if: None,
+            retries: None,
+            retry_delay_ms: None,
+            timeout_ms: None,
+            redact: None,
         });
         self
     }
@@ -62,8 +78,7 @@ impl TestConfigBuilder {
         services.insert(
             name.to_string(),
             ServiceConfig {
-                r#type: "generic_container".to_string(),
-                plugin: "generic".to_string(),
+                plugin: "generic_container".to_string(),
                 image: Some(image.to_string()),
                 args: None,
                 env: None,
@@ -73,8 +88,14 @@ impl TestConfigBuilder {
                 username: None,
                 password: None,
                 strict: None,
+                database: None,
+                init_sql: None,
+                startup_order: None,
                 wait_for_span: None,
                 wait_for_span_timeout_secs: None,
+                memory: None,
+                cpus: None,
+                static_dir: None,
             },
         );
         self.services = Some(services);
@@ -104,6 +125,8 @@ impl TestConfigBuilder {
                     name: self.name,
                     version: "1.0.0".to_string(),
                     description: self.description,
+                    redact: None,
+                    inherit_env: None,
                 })
             } else {
                 None
@@ -123,6 +146,8 @@ impl TestConfigBuilder {
             limits: None,
             otel_headers: None,
             otel_propagators: None,
+            data: None,
+            policy: None,
         }
     }
 }
@@ -181,13 +206,18 @@ impl StepConfigBuilder {
             expected_exit_code: self.expected_exit_code,
             continue_on_failure: None,
             service: None,
+            skip: None,
+            skip_if: None,
+            retries: None,
+            retry_delay_ms: None,
+            timeout_ms: None,
+            redact: None,
         }
     }
 }
 
 /// Builder for creating ServiceConfig instances
 pub struct ServiceConfigBuilder {
-    service_type: String,
     plugin: String,
     image: Option<String>,
     env: Option<HashMap<String, String>>,
@@ -195,9 +225,8 @@ pub struct ServiceConfigBuilder {
 }
 
 impl ServiceConfigBuilder {
-    pub fn new(service_type: &str, plugin: &str) -> Self {
+    pub fn new(plugin: &str) -> Self {
         Self {
-            service_type: service_type.to_string(),
             plugin: plugin.to_string(),
             image: None,
             env: None,
@@ -226,7 +255,6 @@ impl ServiceConfigBuilder {
 
     pub fn build(self) -> ServiceConfig {
         ServiceConfig {
-            r#type: self.service_type,
             plugin: self.plugin,
             image: self.image,
             args: None,
@@ -237,8 +265,14 @@ impl ServiceConfigBuilder {
             username: None,
             password: None,
             strict: None,
+            database: None,
+            init_sql: None,
             wait_for_span: None,
             wait_for_span_timeout_secs: None,
+            startup_order: None,
+            memory: None,
+            cpus: None,
+            static_dir: None,
         }
     }
 }
@@ -277,6 +311,7 @@ pub fn mock_result_with_steps(steps: Vec<(&str, bool, u64)>) -> RunResult {
     for (i, (name, success, duration_ms)) in steps.iter().enumerate() {
         result.steps.push(StepResult {
             name: name.to_string(),
+            command: String::new(),
             exit_code: if *success { 0 } else { 1 },
             stdout: String::new(),
             stderr: String::new(),
@@ -284,6 +319,10 @@ pub fn mock_result_with_steps(steps: Vec<(&str, bool, u64)>) -> RunResult {
             start_ts: i as u64,
             success: *success,
             source: "test".to_string(),
+            retries: 0,
+            skipped: false,
+            failure_reason: None,
+            spans: Vec::new(),
         });
     }
     result
@@ -397,10 +436,14 @@ pub fn fixture_scenario_config() -> ScenarioConfig {
         steps: vec![StepConfigBuilder::new("step1", vec!["echo", "test"]).build()],
         service: None,
         run: None,
+        workdir: None,
         concurrent: Some(false),
         timeout_ms: Some(5000),
         policy: None,
         artifacts: None,
+        depends_on: Vec::new(),
+        continue_on_failure: None,
+        assertions: Vec::new(),
     }
 }
 
@@ -427,3 +470,98 @@ pub fn large_test_content(size_mb: usize) -> String {
 pub fn unicode_test_content() -> String {
     "Test: Hello 世界 🚀 Привет مرحبا".to_string()
 }
+
+// ============================================================================
+// Test Files and Traces
+// ============================================================================
+
+/// `[meta]` table naming a test file
+pub fn meta(name: &str) -> String {
+    format!("[meta]\nname = \"{}\"\nversion = \"1.0\"\n", name)
+}
+
+/// Writes `content` to `dir/file`, creating parent directories
+pub fn write_file(dir: &Path, file: &str, content: &str) -> PathBuf {
+    let path = dir.join(file);
+    std::fs::create_dir_all(path.parent().expect("parent dir")).expect("create dir");
+    std::fs::write(&path, content).expect("write file");
+    path
+}
+
+/// Writes `{name}.clnrm.toml` to `dir`
+pub fn write_test(dir: &Path, name: &str, content: &str) -> PathBuf {
+    write_file(dir, &format!("{}.clnrm.toml", name), content)
+}
+
+/// Writes a JSON trace to `dir/file`
+pub fn write_trace(dir: &Path, file: &str, trace: &Value) -> PathBuf {
+    write_file(dir, file, &trace.to_string())
+}
+
+/// Default CLI options with tests run on the container `backend`, e.g.
+/// `process` to run steps on the host
+pub fn on_backend(backend: &str) -> CliConfig {
+    let mut cleanroom = CleanroomConfig::default();
+    cleanroom.containers.backend = backend.to_string();
+    CliConfig {
+        cleanroom: Some(cleanroom),
+        ..CliConfig::default()
+    }
+}
+
+/// Runs `config` as a test file with default CLI options
+pub async fn run_config(config: &str) -> TestResult {
+    run_config_with(config, &CliConfig::default()).await
+}
+
+/// Runs `config` as a test file on the container `backend`
+pub async fn run_config_on(backend: &str, config: &str) -> TestResult {
+    run_config_with(config, &on_backend(backend)).await
+}
+
+async fn run_config_with(config: &str, cli_config: &CliConfig) -> TestResult {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = write_test(dir.path(), "test", config);
+
+    run_test_file(&path, cli_config).await.expect("test runs")
+}
+
+/// A failed run of the test file at `path` that took `duration_ms`
+pub fn failed_result(path: &Path, duration_ms: u64) -> CliTestResult {
+    CliTestResult {
+        name: path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string(),
+        path: path.to_path_buf(),
+        passed: false,
+        duration_ms,
+        error: Some("boom".to_string()),
+        steps: Vec::new(),
+    }
+}
+
+/// An exported span whose `span_id` is its name
+pub fn span(name: &str, kind: &str, attributes: Value) -> Value {
+    json!({
+        "name": name,
+        "trace_id": "trace-1",
+        "span_id": name,
+        "kind": kind,
+        "attributes": attributes,
+    })
+}
+
+/// [`span`] parsed into [`SpanData`]
+pub fn span_data(name: &str, kind: &str, attributes: Value) -> SpanData {
+    serde_json::from_value(span(name, kind, attributes)).expect("valid span")
+}
+
+/// An internal span with an explicit `span_id` and optional parent
+pub fn child_span(name: &str, id: &str, parent: Option<&str>) -> Value {
+    let mut span = span(name, "internal", json!({}));
+    span["span_id"] = json!(id);
+    span["parent_span_id"] = json!(parent);
+    span
+}
@@ -0,0 +1,113 @@
+//! `clnrm config merge` for layered test configurations
+
+use clnrm_core::cli::commands::config::{merge_config_files, merge_configs};
+use clnrm_core::config::parse_toml_config;
+use std::path::PathBuf;
+
+const BASE: &str = r#"
+[meta]
+name = "orders"
+version = "1.0"
+
+[services.db]
+type = "generic_container"
+plugin = "generic_container"
+image = "postgres:15"
+
+[services.cache]
+type = "generic_container"
+plugin = "generic_container"
+image = "redis:7"
+
+[[scenario]]
+name = "smoke"
+timeout_ms = 1000
+
+[[scenario.steps]]
+name = "ping"
+command = ["pg_isready"]
+
+[[scenario]]
+name = "load"
+
+[[scenario.steps]]
+name = "insert"
+command = ["psql", "-c", "INSERT"]
+"#;
+
+const STAGING: &str = r#"
+[services.db]
+image = "postgres:16"
+
+[[scenario]]
+name = "smoke"
+timeout_ms = 5000
+
+[[scenario]]
+name = "soak"
+
+[[scenario.steps]]
+name = "idle"
+command = ["sleep", "60"]
+"#;
+
+fn write_layers(dir: &std::path::Path) -> Vec<PathBuf> {
+    let base = dir.join("base.toml");
+    let staging = dir.join("staging.toml");
+    std::fs::write(&base, BASE).expect("write base");
+    std::fs::write(&staging, STAGING).expect("write override");
+    vec![base, staging]
+}
+
+#[test]
+fn test_override_wins_on_scalars_and_keeps_shared_tables() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let merged = merge_config_files(&write_layers(dir.path())).expect("configs merge");
+    let config = parse_toml_config(&merged).expect("merged config parses");
+
+    let services = config.services.expect("services");
+    assert_eq!(services["db"].image.as_deref(), Some("postgres:16"));
+    assert_eq!(services["db"].plugin, "generic_container");
+    assert_eq!(services["cache"].image.as_deref(), Some("redis:7"));
+    assert_eq!(config.meta.expect("meta").name, "orders");
+}
+
+#[test]
+fn test_scenarios_merge_by_name() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let merged = merge_config_files(&write_layers(dir.path())).expect("configs merge");
+    let config = parse_toml_config(&merged).expect("merged config parses");
+
+    let names: Vec<&str> = config.scenario.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["smoke", "load", "soak"]);
+
+    let smoke = &config.scenario[0];
+    assert_eq!(smoke.timeout_ms, Some(5000));
+    assert_eq!(smoke.steps.len(), 1, "steps not in the override are kept");
+}
+
+#[test]
+fn test_merge_writes_output_file() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let output = dir.path().join("merged.toml");
+    merge_configs(&write_layers(dir.path()), Some(&output)).expect("configs merge");
+
+    let merged = std::fs::read_to_string(&output).expect("merged output");
+    assert!(merged.contains("postgres:16"), "{}", merged);
+}
+
+#[test]
+fn test_merge_reports_unparseable_layer() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let mut files = write_layers(dir.path());
+    let broken = dir.path().join("broken.toml");
+    std::fs::write(&broken, "[services.db\nimage = 1").expect("write broken");
+    files.push(broken);
+
+    let error = merge_config_files(&files).expect_err("broken layer");
+    assert!(
+        error.to_string().contains("Failed to parse") && error.to_string().contains("broken.toml"),
+        "{}",
+        error
+    );
+}
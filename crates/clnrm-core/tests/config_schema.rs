@@ -0,0 +1,430 @@
+//! JSON Schema printed by `clnrm validate --schema`
+
+use clnrm_core::config::{parse_toml_config, test_config_schema};
+use clnrm_core::validation::find_unknown_keys;
+use serde_json::Value;
+use std::path::Path;
+
+/// Errors from checking `value` against the subset of JSON Schema the
+/// config schema uses
+fn check(value: &Value, schema: &Value, root: &Value, path: &str, errors: &mut Vec<String>) {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        match root["definitions"].get(name) {
+            Some(definition) => check(value, definition, root, path, errors),
+            None => errors.push(format!("{}: unknown $ref {}", path, reference)),
+        }
+        return;
+    }
+    for part in schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        check(value, part, root, path, errors);
+    }
+
+    let type_matches = match schema.get("type").and_then(Value::as_str) {
+        Some("object") => value.is_object(),
+        Some("array") => value.is_array(),
+        Some("string") => value.is_string(),
+        Some("integer") => value.is_i64() || value.is_u64(),
+        Some("number") => value.is_number(),
+        Some("boolean") => value.is_boolean(),
+        _ => true,
+    };
+    if !type_matches {
+        errors.push(format!(
+            "{}: expected {}, got {}",
+            path, schema["type"], value
+        ));
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            errors.push(format!("{}: {} not in {:?}", path, value, allowed));
+        }
+    }
+    if let (Some(minimum), Some(n)) = (
+        schema.get("minimum").and_then(Value::as_i64),
+        value.as_i64(),
+    ) {
+        if n < minimum {
+            errors.push(format!("{}: {} below minimum {}", path, n, minimum));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        for key in schema
+            .get("required")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            if !object.contains_key(key) {
+                errors.push(format!("{}: missing required '{}'", path, key));
+            }
+        }
+        if let Some(alternatives) = schema.get("anyOf").and_then(Value::as_array) {
+            let satisfied = alternatives.iter().any(|alternative| {
+                let mut alternative_errors = Vec::new();
+                check(value, alternative, root, path, &mut alternative_errors);
+                alternative_errors.is_empty()
+            });
+            if !satisfied {
+                errors.push(format!("{}: matches none of anyOf", path));
+            }
+        }
+        for (key, item) in object {
+            let item_path = format!("{}.{}", path, key);
+            if let Some(property) = schema.get("properties").and_then(|p| p.get(key)) {
+                check(item, property, root, &item_path, errors);
+            } else if let Some(values) = schema.get("additionalProperties") {
+                if values == false {
+                    errors.push(format!("{}: unknown key", item_path));
+                } else {
+                    check(item, values, root, &item_path, errors);
+                }
+            }
+        }
+    }
+
+    if let (Some(items), Some(array)) = (schema.get("items"), value.as_array()) {
+        for (i, item) in array.iter().enumerate() {
+            check(item, items, root, &format!("{}[{}]", path, i), errors);
+        }
+        let len = array.len() as u64;
+        if schema
+            .get("minItems")
+            .and_then(Value::as_u64)
+            .is_some_and(|min| len < min)
+            || schema
+                .get("maxItems")
+                .and_then(Value::as_u64)
+                .is_some_and(|max| len > max)
+        {
+            errors.push(format!("{}: unexpected length {}", path, len));
+        }
+    }
+}
+
+fn validate(value: &Value) -> Vec<String> {
+    let schema = test_config_schema();
+    let mut errors = Vec::new();
+    check(value, &schema, &schema, "$", &mut errors);
+    errors
+}
+
+/// Keys in `value` (a serialized config) missing from the schema
+fn undeclared_keys(
+    value: &Value,
+    schema: &Value,
+    root: &Value,
+    path: &str,
+    missing: &mut Vec<String>,
+) {
+    let schemas = alternatives(schema, root);
+    match value {
+        Value::Object(object) => {
+            for (key, item) in object {
+                let item_path = format!("{}.{}", path, key);
+                let declared = schemas.iter().find_map(|schema| {
+                    schema
+                        .get("properties")
+                        .and_then(|p| p.get(key))
+                        .or_else(|| {
+                            schema
+                                .get("additionalProperties")
+                                .filter(|values| *values != false)
+                        })
+                });
+                match declared {
+                    Some(property) => undeclared_keys(item, property, root, &item_path, missing),
+                    None => missing.push(item_path),
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schemas.iter().find_map(|schema| schema.get("items")) {
+                for item in items {
+                    undeclared_keys(item, item_schema, root, path, missing);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// `schema` with its `$ref`, `allOf` and `anyOf` parts resolved
+fn alternatives<'a>(schema: &'a Value, root: &'a Value) -> Vec<&'a Value> {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.trim_start_matches("#/definitions/");
+        return alternatives(&root["definitions"][name], root);
+    }
+    let mut schemas = vec![schema];
+    for part in ["allOf", "anyOf"]
+        .into_iter()
+        .filter_map(|key| schema.get(key).and_then(Value::as_array))
+        .flatten()
+    {
+        schemas.extend(alternatives(part, root));
+    }
+    schemas
+}
+
+#[test]
+fn test_schema_validates_example_configs() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let roots = [
+        manifest_dir.join("tests"),
+        manifest_dir.join("../../examples"),
+    ];
+
+    let mut validated = 0;
+    for root in &roots {
+        for entry in walkdir::WalkDir::new(root)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
+            let path = entry.path();
+            if !path.to_string_lossy().ends_with(".toml")
+                || path.components().any(|c| c.as_os_str() == "node_modules")
+            {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            // Only files that load as test configs; templates and project
+            // configs are skipped
+            if parse_toml_config(&content).is_err() {
+                continue;
+            }
+            let value: Value = toml::from_str(&content).expect("TOML that parsed as a config");
+
+            // Keys lint reports as unknown are the ones the schema rejects
+            let (unknown, errors): (Vec<_>, Vec<_>) = validate(&value)
+                .into_iter()
+                .partition(|error| error.ends_with(": unknown key"));
+            assert!(
+                errors.is_empty(),
+                "{}:\n{}",
+                path.display(),
+                errors.join("\n")
+            );
+            assert_eq!(
+                unknown.len(),
+                find_unknown_keys(&content).len(),
+                "{}: {:?}",
+                path.display(),
+                unknown
+            );
+            validated += 1;
+        }
+    }
+
+    assert!(validated >= 10, "only {} example configs found", validated);
+}
+
+#[test]
+fn test_schema_rejects_wrong_types_and_missing_keys() {
+    let value: Value = toml::from_str(
+        r#"
+[meta]
+name = "broken"
+
+[[scenario]]
+name = "s"
+concurrent = "yes"
+depends_on = "other"
+"#,
+    )
+    .expect("valid TOML");
+
+    let errors = validate(&value).join("\n");
+    assert!(
+        errors.contains("$.meta: missing required 'version'"),
+        "{}",
+        errors
+    );
+    assert!(
+        errors.contains("$.scenario[0].concurrent: expected \"boolean\""),
+        "{}",
+        errors
+    );
+    assert!(
+        errors.contains("$.scenario[0].depends_on: expected \"array\""),
+        "{}",
+        errors
+    );
+}
+
+#[test]
+fn test_schema_declares_every_serialized_config_key() {
+    let config = parse_toml_config(
+        r#"
+[meta]
+name = "everything"
+version = "1.0"
+redact = ["sk-[a-z]+"]
+
+[service.api]
+plugin = "generic_container"
+image = "alpine:3.19"
+volumes = [{ host_path = "/tmp", container_path = "/data" }]
+health_check = { command = ["true"] }
+
+[[steps]]
+name = "step"
+command = ["echo", "hi"]
+
+[[scenario]]
+name = "scenario"
+service = "api"
+run = "echo hi"
+policy = { security_level = "low" }
+artifacts = { collect = ["spans:default"] }
+assertions = [{ query = "SELECT 1", equals = "1" }]
+
+[otel]
+exporter = "stdout"
+propagators = { use = ["tracecontext"] }
+
+[otel_validation]
+enabled = true
+expected_spans = [{ name = "a" }]
+expected_traces = [{ span_names = ["a"] }]
+expect_graph = {}
+expect_counts = { spans_total = { gte = 1 } }
+expect_windows = [{ outer = "a", contains = ["b"] }]
+expect_hermeticity = { resource_attrs = {}, span_attrs = {} }
+expect_order = {}
+expect_status = {}
+
+[expect]
+span = [{ name = "a", attrs = {}, events = {}, duration_ms = {} }]
+order = {}
+status = {}
+counts = { by_name = { a = { eq = 1 } } }
+window = [{ outer = "a", contains = ["b"] }]
+graph = {}
+hermeticity = {}
+attributes = [{ span = "a", key = "k", value = "v" }]
+duration = [{ span = "a", max_ms = 10.0 }]
+event = [{ span = "a", name = "exception" }]
+
+[report]
+json = "report.json"
+
+[determinism]
+seed = 42
+
+[limits]
+max_steps = 10
+
+[otel_propagators]
+use = ["baggage"]
+
+[data]
+file = "rows.csv"
+
+[policy]
+denied_binaries = ["rm"]
+"#,
+    )
+    .expect("valid config");
+
+    let value = serde_json::to_value(&config).expect("serializable config");
+    let schema = test_config_schema();
+    let mut missing = Vec::new();
+    undeclared_keys(&value, &schema, &schema, "$", &mut missing);
+
+    assert!(
+        missing.is_empty(),
+        "keys missing from schema: {:?}",
+        missing
+    );
+}
+
+#[test]
+fn test_schema_lists_scenario_keys_for_completion() {
+    let schema = test_config_schema();
+    let scenario = schema["definitions"]["ScenarioConfig"]["properties"]
+        .as_object()
+        .expect("scenario properties");
+
+    for key in [
+        "name",
+        "service",
+        "run",
+        "concurrent",
+        "timeout_ms",
+        "artifacts",
+        "depends_on",
+    ] {
+        assert!(scenario.contains_key(key), "scenario.{} missing", key);
+    }
+    assert_eq!(
+        schema["properties"]["scenario"]["items"]["$ref"],
+        "#/definitions/ScenarioConfig"
+    );
+}
+
+#[test]
+fn test_schema_rejects_unknown_keys_where_lint_does() {
+    let value: Value = toml::from_str(
+        r#"
+verbose = true
+
+[meta]
+name = "typos"
+version = "1.0"
+autor = "me"
+
+[service.api]
+image = "alpine:3.19"
+imgae = "alpine:3.20"
+health_check = { command = ["true"], grace = 5 }
+
+[[steps]]
+name = "step"
+commnad = ["echo", "hi"]
+
+[[scenario]]
+name = "scenario"
+servce = "api"
+
+[[scenario.steps]]
+name = "nested"
+command = ["true"]
+retry = 2
+"#,
+    )
+    .expect("valid TOML");
+
+    let errors = validate(&value);
+    for key in [
+        "$.verbose",
+        "$.meta.autor",
+        "$.service.api.imgae",
+        "$.steps[0].commnad",
+        "$.scenario[0].servce",
+        "$.scenario[0].steps[0].retry",
+    ] {
+        assert!(
+            errors.contains(&format!("{}: unknown key", key)),
+            "{} not rejected: {:?}",
+            key,
+            errors
+        );
+    }
+    // Tables the unknown-key check doesn't cover stay open
+    assert!(
+        !errors.iter().any(|error| error.contains("grace")),
+        "{:?}",
+        errors
+    );
+}
@@ -0,0 +1,125 @@
+//! Consolidated JSON report with the test metadata, spans and validations
+
+use clnrm_core::reporting::{generate_reports, ConsolidatedReporter, ReportConfig, ReportMeta};
+use clnrm_core::ValidationReport;
+use serde_json::{json, Value};
+use std::path::Path;
+
+const SPANS: &str = r#"[{"name": "clnrm.run", "trace_id": "t1", "span_id": "s1"}]"#;
+
+fn validation_report() -> ValidationReport {
+    let mut report = ValidationReport::new();
+    report.add_pass("graph");
+    report.add_fail("counts", "expected 2 spans, found 1".to_string());
+    report
+}
+
+fn read_json(path: &Path) -> Value {
+    let content = std::fs::read_to_string(path).expect("report written");
+    serde_json::from_str(&content).expect("report is JSON")
+}
+
+#[test]
+fn test_report_holds_meta_spans_and_validations() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("report.json");
+    let meta = ReportMeta::new("login")
+        .with_version(Some("1.2".to_string()))
+        .with_description(Some("Signs in".to_string()))
+        .with_scenario("happy_path")
+        .with_seed(Some(42));
+
+    ConsolidatedReporter::write(&path, &meta, SPANS, &validation_report()).expect("report writes");
+
+    let report = read_json(&path);
+    assert_eq!(report["meta"]["name"], "login");
+    assert_eq!(report["meta"]["version"], "1.2");
+    assert_eq!(report["meta"]["description"], "Signs in");
+    assert_eq!(report["meta"]["scenario"], "happy_path");
+    assert_eq!(report["meta"]["seed"], 42);
+    assert_eq!(report["meta"]["clnrm_version"], env!("CARGO_PKG_VERSION"));
+    assert!(report["meta"]["generated_at"].is_string());
+    assert_eq!(
+        report["spans"],
+        json!([{"name": "clnrm.run", "trace_id": "t1", "span_id": "s1"}])
+    );
+    assert_eq!(
+        report["validations"],
+        json!({
+            "passed": false,
+            "total_passes": 1,
+            "total_failures": 1,
+            "passes": ["graph"],
+            "failures": [{"name": "counts", "error": "expected 2 spans, found 1"}]
+        })
+    );
+}
+
+#[test]
+fn test_unset_meta_fields_are_null() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("report.json");
+
+    ConsolidatedReporter::write(
+        &path,
+        &ReportMeta::new("bare"),
+        "[]",
+        &ValidationReport::new(),
+    )
+    .expect("report writes");
+
+    let report = read_json(&path);
+    for field in ["version", "description", "scenario", "seed"] {
+        assert!(report["meta"][field].is_null(), "{} is set", field);
+    }
+    assert_eq!(report["spans"], json!([]));
+    assert_eq!(report["validations"]["passed"], true);
+}
+
+#[test]
+fn test_invalid_spans_json_is_an_error_and_writes_nothing() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("report.json");
+
+    let error = ConsolidatedReporter::write(
+        &path,
+        &ReportMeta::new("broken"),
+        "[{not json",
+        &ValidationReport::new(),
+    )
+    .expect_err("spans aren't JSON");
+
+    assert!(
+        error.to_string().contains("Invalid spans JSON"),
+        "{}",
+        error
+    );
+    assert!(!path.exists());
+}
+
+#[test]
+fn test_generate_reports_writes_the_consolidated_report_when_configured() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let consolidated = dir.path().join("consolidated.json");
+    let json_report = dir.path().join("report.json");
+    let meta = ReportMeta::new("login");
+
+    generate_reports(
+        &ReportConfig::new().with_json(json_report.to_string_lossy()),
+        &validation_report(),
+        SPANS,
+        &meta,
+    )
+    .expect("reports write");
+    assert!(json_report.exists());
+    assert!(!consolidated.exists());
+
+    generate_reports(
+        &ReportConfig::new().with_consolidated(consolidated.to_string_lossy()),
+        &validation_report(),
+        SPANS,
+        &meta,
+    )
+    .expect("reports write");
+    assert_eq!(read_json(&consolidated)["meta"]["name"], "login");
+}
@@ -0,0 +1,280 @@
+//! Choosing the container runtime with `[containers] backend` or `CLNRM_BACKEND`
+//!
+//! A stub `podman` on `PATH` reports the API socket, so runtime selection is
+//! exercised without a real Podman install. Tests that change `PATH`,
+//! `DOCKER_HOST` or `CLNRM_BACKEND` are `#[serial]` and restore them.
+
+use clnrm_core::backend::runtime::{
+    active_backend, backend_from_name, export_api_host, select_backend, ContainerBackend,
+    DockerBackend, PodmanBackend, BACKEND_ENV_VAR,
+};
+use clnrm_core::cleanroom::ServicePlugin;
+use clnrm_core::config::CleanroomConfig;
+use clnrm_core::services::generic::GenericContainerPlugin;
+use clnrm_core::CleanroomEnvironment;
+use serial_test::serial;
+use std::ffi::{OsStr, OsString};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// An environment variable changed for the rest of a test, restored to its
+/// previous value on drop
+struct EnvVar {
+    name: &'static str,
+    previous: Option<OsString>,
+}
+
+impl EnvVar {
+    fn set(name: &'static str, value: impl AsRef<OsStr>) -> Self {
+        let previous = std::env::var_os(name);
+        std::env::set_var(name, value);
+        Self { name, previous }
+    }
+
+    fn remove(name: &'static str) -> Self {
+        let previous = std::env::var_os(name);
+        std::env::remove_var(name);
+        Self { name, previous }
+    }
+}
+
+impl Drop for EnvVar {
+    fn drop(&mut self) {
+        match &self.previous {
+            Some(value) => std::env::set_var(self.name, value),
+            None => std::env::remove_var(self.name),
+        }
+    }
+}
+
+/// A `PATH` holding only a fresh directory, so `podman` starts out missing
+struct StubPath {
+    bin: tempfile::TempDir,
+    _path: EnvVar,
+}
+
+impl StubPath {
+    fn new() -> Self {
+        let bin = tempfile::tempdir().expect("temp dir");
+        let path = EnvVar::set("PATH", bin.path());
+        Self { bin, _path: path }
+    }
+
+    /// Install a `podman` stub reporting `socket` as its API socket
+    fn podman(&self, socket: &Path) {
+        let podman = self.bin.path().join("podman");
+        std::fs::write(
+            &podman,
+            format!("#!/bin/sh\necho \"unix://{}\"\n", socket.display()),
+        )
+        .expect("write stub");
+        std::fs::set_permissions(&podman, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    }
+
+    /// Install a `podman` stub whose API socket exists
+    fn podman_with_socket(&self) -> PathBuf {
+        let socket = self.bin.path().join("podman.sock");
+        std::fs::write(&socket, "").expect("create socket file");
+        self.podman(&socket);
+        socket
+    }
+}
+
+fn podman_config() -> CleanroomConfig {
+    let mut config = CleanroomConfig::default();
+    config.containers.backend = "podman".to_string();
+    config
+}
+
+#[test]
+fn test_backends_are_chosen_by_name() {
+    // Act
+    let docker = backend_from_name("docker").expect("docker");
+    let podman = backend_from_name("Podman").expect("podman");
+
+    // Assert
+    assert_eq!(docker.name(), "docker");
+    assert_eq!(podman.binary(), "podman");
+}
+
+#[test]
+fn test_unknown_backend_name_is_rejected() {
+    // Act
+    let error = backend_from_name("lxc").expect_err("unknown backend");
+
+    // Assert
+    assert!(
+        error
+            .to_string()
+            .contains("Unknown container backend 'lxc': expected one of docker, podman"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_configured_backend_defaults_to_docker_and_is_validated() {
+    // Arrange
+    let mut config = CleanroomConfig::default();
+    assert_eq!(config.containers.backend, "docker");
+
+    // Act
+    config.containers.backend = "lxc".to_string();
+
+    // Assert
+    assert!(config.validate().is_err());
+}
+
+#[test]
+#[serial]
+fn test_backend_env_var_wins_over_configured_backend() {
+    // Arrange
+    let _backend = EnvVar::set(BACKEND_ENV_VAR, "podman");
+
+    // Act
+    let backend = select_backend(Some("docker")).expect("selects");
+
+    // Assert
+    assert_eq!(backend.name(), "podman");
+}
+
+#[test]
+#[serial]
+fn test_unknown_backend_env_var_is_named_in_the_error() {
+    // Arrange
+    let _backend = EnvVar::set(BACKEND_ENV_VAR, "lxc");
+
+    // Act
+    let error = select_backend(None).expect_err("unknown backend");
+
+    // Assert
+    assert!(error.to_string().contains(BACKEND_ENV_VAR), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_environment_on_missing_podman_is_not_installed() {
+    // Arrange
+    let _path = StubPath::new();
+
+    // Act
+    let error = CleanroomEnvironment::with_config(Some(podman_config()))
+        .await
+        .expect_err("podman is not installed");
+
+    // Assert
+    assert!(
+        error
+            .to_string()
+            .contains("Container backend 'podman' is not installed"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_service_on_missing_podman_is_not_installed() {
+    // Arrange
+    let _path = StubPath::new();
+    let plugin = GenericContainerPlugin::new("web", "nginx:alpine")
+        .with_backend(Arc::new(PodmanBackend::default()));
+
+    // Act
+    let error = plugin.start().expect_err("podman is not installed");
+
+    // Assert
+    assert!(error.to_string().contains("not installed"), "{}", error);
+}
+
+#[test]
+#[serial]
+fn test_missing_podman_socket_is_reported_with_how_to_start_it() {
+    // Arrange
+    let path = StubPath::new();
+    path.podman(&path.bin.path().join("missing.sock"));
+
+    // Act
+    let error = PodmanBackend::default().connect().expect_err("no socket");
+
+    // Assert
+    assert!(
+        error.to_string().contains("Podman API socket")
+            && error.to_string().contains("podman.socket"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+#[serial]
+fn test_podman_client_connects_to_its_socket() {
+    // Arrange
+    let path = StubPath::new();
+    path.podman_with_socket();
+
+    // Act
+    let client = PodmanBackend::default().client();
+
+    // Assert
+    assert!(client.is_ok(), "{:?}", client.err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_podman_environment_needs_docker_host_for_testcontainers() {
+    // Arrange
+    let path = StubPath::new();
+    path.podman_with_socket();
+    let _docker_host = EnvVar::remove("DOCKER_HOST");
+
+    // Act
+    let error = CleanroomEnvironment::with_config(Some(podman_config()))
+        .await
+        .expect_err("DOCKER_HOST not set");
+
+    // Assert
+    assert!(
+        error.to_string().contains("DOCKER_HOST is not set"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_exported_api_host_lets_the_environment_use_podman() {
+    // Arrange: export_api_host reads the backend from CLNRM_BACKEND or the
+    // working directory's cleanroom.toml
+    let path = StubPath::new();
+    let socket = path.podman_with_socket();
+    let _docker_host = EnvVar::remove("DOCKER_HOST");
+    let _backend = EnvVar::set(BACKEND_ENV_VAR, "podman");
+
+    // Act
+    export_api_host();
+    let environment = CleanroomEnvironment::with_config(Some(podman_config())).await;
+
+    // Assert
+    assert_eq!(
+        std::env::var("DOCKER_HOST").expect("DOCKER_HOST set"),
+        format!("unix://{}", socket.display())
+    );
+    assert!(environment.is_ok(), "{:?}", environment.err());
+    assert_eq!(active_backend().name(), "podman");
+}
+
+#[test]
+#[serial]
+fn test_docker_host_needs_no_local_docker_install() {
+    // Arrange
+    let _path = StubPath::new();
+    let _docker_host = EnvVar::set("DOCKER_HOST", "tcp://docker.example:2375");
+
+    // Act
+    let connected = DockerBackend.connect();
+
+    // Assert
+    assert!(connected.is_ok(), "{:?}", connected.err());
+}
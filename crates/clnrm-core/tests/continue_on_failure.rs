@@ -0,0 +1,129 @@
+//! `continue_on_failure` on steps and scenarios
+
+mod common;
+
+use clnrm_core::testing::TestResult;
+use common::run_config;
+
+fn outcome(result: &TestResult, source: &str, name: &str) -> (bool, bool) {
+    let step = result
+        .steps
+        .iter()
+        .find(|s| s.source == source && s.name == name)
+        .unwrap_or_else(|| panic!("no result for {}/{}: {:?}", source, name, result.steps));
+    (step.success, step.skipped)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failed_step_with_continue_on_failure_runs_later_steps() {
+    let result = run_config(
+        r#"
+[meta]
+name = "continue"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[[steps]]
+name = "cleanup"
+command = ["rm", "-rf", "/data"]
+continue_on_failure = true
+
+[[steps]]
+name = "purge"
+command = ["rm", "-rf", "/cache"]
+continue_on_failure = true
+
+[[steps]]
+name = "later"
+command = ["echo", "later"]
+skip = true
+"#,
+    )
+    .await;
+
+    assert!(!result.passed);
+    assert_eq!(result.steps.len(), 3, "{:?}", result.steps);
+    assert_eq!(outcome(&result, "continue", "cleanup"), (false, false));
+    assert_eq!(outcome(&result, "continue", "purge"), (false, false));
+    assert_eq!(outcome(&result, "continue", "later"), (true, true));
+
+    let error = result.error.expect("step error");
+    assert!(error.contains("Step 'cleanup' blocked"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failed_step_without_continue_on_failure_stops_the_test() {
+    let result = run_config(
+        r#"
+[meta]
+name = "stop"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[[steps]]
+name = "cleanup"
+command = ["rm", "-rf", "/data"]
+
+[[steps]]
+name = "later"
+command = ["echo", "later"]
+skip = true
+"#,
+    )
+    .await;
+
+    assert!(!result.passed);
+    assert_eq!(result.steps.len(), 1, "{:?}", result.steps);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_scenario_with_continue_on_failure_runs_the_rest_but_skips_dependents() {
+    let config = |continue_on_failure: bool| {
+        format!(
+            r#"
+[meta]
+name = "scenarios"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[[scenario]]
+name = "setup"
+service = "missing"
+run = "rm -rf /data"
+continue_on_failure = {}
+
+[[scenario]]
+name = "check"
+service = "missing"
+run = "true"
+depends_on = ["setup"]
+
+[[scenario]]
+name = "other"
+service = "missing"
+run = "rm -rf /cache"
+"#,
+            continue_on_failure
+        )
+    };
+
+    let stopped = run_config(&config(false)).await;
+    assert!(!stopped.passed);
+    assert_eq!(outcome(&stopped, "setup", "run"), (false, false));
+    assert_eq!(outcome(&stopped, "check", "run"), (true, true));
+    assert_eq!(outcome(&stopped, "other", "run"), (true, true));
+
+    let continued = run_config(&config(true)).await;
+    assert!(!continued.passed);
+    assert_eq!(outcome(&continued, "setup", "run"), (false, false));
+    assert_eq!(outcome(&continued, "check", "run"), (true, true));
+    assert_eq!(outcome(&continued, "other", "run"), (false, false));
+    let error = continued.error.expect("scenario error");
+    assert!(error.contains("Scenario 'setup'"), "{}", error);
+}
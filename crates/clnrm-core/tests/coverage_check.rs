@@ -0,0 +1,116 @@
+//! `clnrm coverage check` against a behavior manifest
+
+mod common;
+
+use clnrm_core::cli::commands::report::check_coverage;
+use clnrm_core::coverage::api_endpoint_from_span;
+use common::{span, span_data};
+use serde_json::{json, Value};
+use std::path::Path;
+
+const MANIFEST: &str = r#"
+[system]
+name = "shop"
+version = "1.0.0"
+
+[dimensions.api_surface]
+endpoints = ["GET /orders", "POST /orders"]
+
+[dimensions.integrations]
+services = [{ name = "postgresql", operations = ["INSERT"] }]
+
+[dimensions.span_coverage]
+expected_spans = ["orders.create"]
+"#;
+
+fn write_spans(path: &Path, spans: &[Value]) {
+    let lines: Vec<String> = spans.iter().map(Value::to_string).collect();
+    std::fs::write(path, lines.join("\n")).expect("write spans");
+}
+
+fn create_order_spans() -> Vec<Value> {
+    vec![
+        span(
+            "orders.create",
+            "server",
+            json!({ "http.request.method": "POST", "http.route": "/orders" }),
+        ),
+        span(
+            "INSERT orders",
+            "client",
+            json!({ "db.system": "postgresql", "db.operation": "INSERT" }),
+        ),
+    ]
+}
+
+#[test]
+fn test_server_span_is_an_api_endpoint() {
+    let served = span_data(
+        "GET /orders/{id}",
+        "server",
+        json!({ "http.method": "GET", "http.route": "/orders/{id}" }),
+    );
+    assert_eq!(
+        api_endpoint_from_span(&served).as_deref(),
+        Some("GET /orders/{id}")
+    );
+
+    let called = span_data(
+        "GET",
+        "client",
+        json!({ "http.method": "GET", "http.route": "/orders/{id}" }),
+    );
+    assert_eq!(api_endpoint_from_span(&called), None);
+}
+
+#[test]
+fn test_check_passes_when_every_behavior_is_covered() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let manifest = dir.path().join("behaviors.toml");
+    std::fs::write(&manifest, MANIFEST).expect("write manifest");
+    let traces = dir.path().join("traces");
+    std::fs::create_dir_all(traces.join("orders")).expect("traces dir");
+    write_spans(&traces.join("orders/create.json"), &create_order_spans());
+    write_spans(
+        &traces.join("list.json"),
+        &[span(
+            "orders.list",
+            "server",
+            json!({ "http.request.method": "GET", "url.path": "/orders" }),
+        )],
+    );
+
+    check_coverage(&manifest, &traces).expect("all behaviors covered");
+}
+
+#[test]
+fn test_check_fails_on_uncovered_endpoint() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let manifest = dir.path().join("behaviors.toml");
+    std::fs::write(&manifest, MANIFEST).expect("write manifest");
+    let traces = dir.path().join("spans.json");
+    write_spans(&traces, &create_order_spans());
+
+    let error = check_coverage(&manifest, &traces).expect_err("GET /orders is uncovered");
+    assert!(
+        error
+            .to_string()
+            .contains("1 declared behavior(s) not covered by traces in"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_check_fails_without_trace_files() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let manifest = dir.path().join("behaviors.toml");
+    std::fs::write(&manifest, MANIFEST).expect("write manifest");
+
+    let error = check_coverage(&manifest, dir.path()).expect_err("no traces");
+    assert!(
+        error.to_string().contains("No trace files found"),
+        "{}",
+        error
+    );
+}
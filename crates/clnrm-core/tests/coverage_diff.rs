@@ -0,0 +1,77 @@
+//! Coverage snapshots compared with `clnrm report --coverage-baseline`
+
+use clnrm_core::cli::commands::compare_coverage;
+use clnrm_core::coverage::tracker::CoverageTracker;
+use std::path::Path;
+
+const MANIFEST: &str = r#"
+[system]
+name = "shop"
+version = "1.0.0"
+
+[dimensions.api_surface]
+endpoints = ["GET /orders", "POST /orders"]
+
+[dimensions.span_coverage]
+expected_spans = ["orders.create"]
+"#;
+
+async fn save_snapshot(path: &Path, endpoints: &[&str]) {
+    let tracker = CoverageTracker::new();
+    for endpoint in endpoints {
+        tracker.record_api(endpoint.to_string()).await;
+    }
+    tracker.record_span("orders.create".to_string()).await;
+    tracker.save_snapshot(path).await.expect("snapshot saved");
+}
+
+#[tokio::test]
+async fn test_snapshot_round_trips() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("coverage.json");
+    save_snapshot(&path, &["GET /orders"]).await;
+
+    let loaded = CoverageTracker::load_snapshot(&path).expect("snapshot loads");
+    let coverage = loaded.snapshot().await;
+    assert!(coverage.api_endpoints_covered.contains("GET /orders"));
+    assert!(coverage.spans_observed.contains("orders.create"));
+}
+
+#[tokio::test]
+async fn test_diff_lists_newly_uncovered_behaviors_and_fails_on_drop() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let manifest = dir.path().join("behaviors.toml");
+    std::fs::write(&manifest, MANIFEST).expect("write manifest");
+    let baseline = dir.path().join("baseline.json");
+    let current = dir.path().join("current.json");
+    save_snapshot(&baseline, &["GET /orders", "POST /orders"]).await;
+    save_snapshot(&current, &["GET /orders"]).await;
+    let output = dir.path().join("diff.json");
+
+    compare_coverage(&baseline, &current, &manifest, 100.0, Some(&output), "json")
+        .await
+        .expect("drop within the allowed limit");
+    let diff: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&output).expect("diff written"))
+            .expect("diff is JSON");
+    let api = diff["dimensions"]
+        .as_array()
+        .expect("dimensions")
+        .iter()
+        .find(|d| d["name"] == "API Surface")
+        .expect("API surface dimension");
+    assert_eq!(api["newly_uncovered"], serde_json::json!(["POST /orders"]));
+    assert!(diff["current_coverage"].as_f64() < diff["previous_coverage"].as_f64());
+
+    let error = compare_coverage(&baseline, &current, &manifest, 0.0, Some(&output), "text")
+        .await
+        .expect_err("coverage dropped");
+    assert!(error.to_string().contains("Coverage dropped"), "{}", error);
+    let text = std::fs::read_to_string(&output).expect("diff written");
+    assert!(text.contains("  - POST /orders"), "{}", text);
+
+    // Identical coverage is never a drop
+    compare_coverage(&baseline, &baseline, &manifest, 0.0, Some(&output), "text")
+        .await
+        .expect("no change");
+}
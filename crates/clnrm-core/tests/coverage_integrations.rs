@@ -0,0 +1,81 @@
+//! Integration coverage derived from span attributes
+
+mod common;
+
+use clnrm_core::coverage::integration_from_span;
+use clnrm_core::coverage::tracker::CoverageTracker;
+use common::span_data;
+use serde_json::json;
+
+fn integration(service: &str, operation: &str) -> Option<(String, String)> {
+    Some((service.to_string(), operation.to_string()))
+}
+
+#[test]
+fn test_database_client_span_is_an_integration() {
+    let query = span_data(
+        "SELECT users",
+        "client",
+        json!({ "db.system": "postgresql", "db.operation": "SELECT" }),
+    );
+
+    assert_eq!(
+        integration_from_span(&query),
+        integration("postgresql", "SELECT")
+    );
+}
+
+#[test]
+fn test_http_client_span_uses_method_and_route() {
+    let call = span_data(
+        "GET",
+        "client",
+        json!({
+            "peer.service": "billing",
+            "server.address": "billing.internal",
+            "http.request.method": "GET",
+            "http.route": "/invoices/{id}"
+        }),
+    );
+    assert_eq!(
+        integration_from_span(&call),
+        integration("billing", "GET /invoices/{id}")
+    );
+
+    let bare = span_data("fetch", "client", json!({ "net.peer.name": "cache" }));
+    assert_eq!(integration_from_span(&bare), integration("cache", "fetch"));
+}
+
+#[test]
+fn test_spans_without_an_outgoing_call_are_not_integrations() {
+    let internal = span_data("clnrm.step", "internal", json!({ "step.name": "run" }));
+    assert_eq!(integration_from_span(&internal), None);
+
+    let server = span_data(
+        "GET /health",
+        "server",
+        json!({ "server.address": "app", "http.request.method": "GET" }),
+    );
+    assert_eq!(integration_from_span(&server), None);
+}
+
+#[tokio::test]
+async fn test_tracker_records_span_names_and_integrations() {
+    let tracker = CoverageTracker::new();
+    tracker
+        .record_spans(&[
+            span_data(
+                "publish orders",
+                "producer",
+                json!({ "messaging.system": "kafka", "messaging.operation": "publish" }),
+            ),
+            span_data("clnrm.run", "internal", json!({})),
+        ])
+        .await;
+
+    let coverage = tracker.snapshot().await;
+    assert!(coverage.spans_observed.contains("publish orders"));
+    assert!(coverage.spans_observed.contains("clnrm.run"));
+    assert_eq!(coverage.integrations_covered.len(), 1);
+    assert!(coverage.integrations_covered["kafka"].contains("publish"));
+}
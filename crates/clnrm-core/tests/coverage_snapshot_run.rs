@@ -0,0 +1,44 @@
+//! `clnrm run --coverage-snapshot` saves the run's behavior coverage
+
+mod common;
+
+use clnrm_core::cli::commands::run::run_tests_with_shard_and_report;
+use clnrm_core::cli::types::CliConfig;
+use clnrm_core::coverage::tracker::CoverageTracker;
+use common::{meta, on_backend, write_test};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_run_saves_coverage_snapshot() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let tests = dir.path().join("tests");
+    write_test(
+        &tests,
+        "ok",
+        &format!(
+            "{}\n[[steps]]\nname = \"ok\"\ncommand = [\"true\"]\n",
+            meta("ok")
+        ),
+    );
+    let snapshot = dir.path().join("coverage.json");
+    let config = CliConfig {
+        force: true,
+        coverage_snapshot: Some(snapshot.clone()),
+        ..on_backend("process")
+    };
+
+    // Act
+    run_tests_with_shard_and_report(&[tests], &config, None, None)
+        .await
+        .expect("run passes");
+
+    // Assert
+    let saved = CoverageTracker::load_snapshot(&snapshot)
+        .expect("snapshot saved")
+        .snapshot()
+        .await;
+    assert_eq!(
+        serde_json::to_value(&saved).expect("serializes"),
+        serde_json::to_value(config.coverage.snapshot().await).expect("serializes")
+    );
+}
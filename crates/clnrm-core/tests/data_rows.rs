@@ -0,0 +1,259 @@
+//! `[data]` rows read from CSV and JSON files and the scenarios they expand to
+
+use clnrm_core::cli::commands::run::data::{case_scenario, expand_scenarios, load_rows, DataRow};
+use clnrm_core::config::{DataConfig, DataFormat, ScenarioConfig};
+use clnrm_core::error::Result;
+use clnrm_core::TemplateRenderer;
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+use tempfile::TempDir;
+
+/// Write `content` as `file` next to a test file, returning the test file path
+fn data_file(dir: &TempDir, file: &str, content: &str) -> PathBuf {
+    std::fs::write(dir.path().join(file), content).expect("write data file");
+    dir.path().join("test.clnrm.toml")
+}
+
+fn csv(content: &str) -> Result<Vec<DataRow>> {
+    let dir = TempDir::new().expect("temp dir");
+    let test_path = data_file(&dir, "rows.csv", content);
+    load_rows(
+        &DataConfig {
+            file: "rows.csv".to_string(),
+            format: None,
+        },
+        &test_path,
+    )
+}
+
+fn fields(pairs: &[(&str, &str)]) -> Map<String, Value> {
+    pairs
+        .iter()
+        .map(|(key, value)| (key.to_string(), json!(value)))
+        .collect()
+}
+
+fn scenario(value: Value) -> ScenarioConfig {
+    serde_json::from_value(value).expect("valid scenario")
+}
+
+#[test]
+fn test_csv_rows_are_keyed_by_the_header() -> Result<()> {
+    let rows = csv("user,password\nalice,secret\nbob,hunter2\n")?;
+
+    assert_eq!(
+        rows,
+        vec![
+            Ok(fields(&[("user", "alice"), ("password", "secret")])),
+            Ok(fields(&[("user", "bob"), ("password", "hunter2")])),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_csv_quoted_fields_keep_commas_quotes_and_newlines() -> Result<()> {
+    let rows = csv("name,note\r\n\"Doe, Jane\",\"said \"\"hi\"\"\"\r\n\"multi\nline\",\"\"\r\n")?;
+
+    assert_eq!(
+        rows,
+        vec![
+            Ok(fields(&[("name", "Doe, Jane"), ("note", "said \"hi\"")])),
+            Ok(fields(&[("name", "multi\nline"), ("note", "")])),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_csv_blank_lines_and_missing_final_newline_are_accepted() -> Result<()> {
+    let rows = csv("id\n\n1\n\n2")?;
+
+    assert_eq!(
+        rows,
+        vec![Ok(fields(&[("id", "1")])), Ok(fields(&[("id", "2")]))]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_malformed_csv_rows_fail_on_their_own() -> Result<()> {
+    let rows = csv("a,b\n1,2\n1,2,3\nx\"y,z\n4,5\n\"open,6\n")?;
+
+    assert_eq!(rows.len(), 5);
+    assert_eq!(rows[0], Ok(fields(&[("a", "1"), ("b", "2")])));
+    assert_eq!(rows[1], Err("expected 2 field(s), found 3".to_string()));
+    assert_eq!(rows[2], Err("unexpected quote in field 'x'".to_string()));
+    assert_eq!(rows[3], Ok(fields(&[("a", "4"), ("b", "5")])));
+    assert_eq!(rows[4], Err("unterminated quoted field".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_csv_without_a_header_fails() {
+    let error = csv("").expect_err("empty file");
+    assert!(
+        error.to_string().contains("expected a header row"),
+        "{}",
+        error
+    );
+
+    let rows = csv("a,b\n").expect("header only");
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn test_json_rows_must_be_objects() -> Result<()> {
+    let dir = TempDir::new().expect("temp dir");
+    let test_path = data_file(&dir, "rows.data", r#"[{"id": 1}, "two", {"id": 3}]"#);
+    let data = DataConfig {
+        file: "rows.data".to_string(),
+        format: Some(DataFormat::Json),
+    };
+
+    let rows = load_rows(&data, &test_path)?;
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[0], Ok(Map::from_iter([("id".to_string(), json!(1))])));
+    assert!(rows[1].is_err());
+
+    std::fs::write(dir.path().join("rows.data"), r#"{"id": 1}"#).expect("write data file");
+    let error = load_rows(&data, &test_path).expect_err("not an array");
+    assert!(error.to_string().contains("array of objects"), "{}", error);
+    Ok(())
+}
+
+#[test]
+fn test_ten_rows_expand_to_ten_cases() -> Result<()> {
+    let content: String = std::iter::once("n".to_string())
+        .chain((0..10).map(|n| n.to_string()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let rows = csv(&content)?;
+    let scenarios = [scenario(json!({
+        "name": "count",
+        "service": "app",
+        "run": "echo {{ row.n }}"
+    }))];
+
+    let expansion = expand_scenarios(&scenarios, &rows, &TemplateRenderer::new()?);
+
+    assert!(expansion.failures.is_empty());
+    let cases: Vec<(&str, &str)> = expansion
+        .scenarios
+        .iter()
+        .map(|case| (case.name.as_str(), case.run.as_deref().unwrap_or_default()))
+        .collect();
+    let expected: Vec<(String, String)> = (0..10)
+        .map(|n| (format!("count[{}]", n), format!("echo {}", n)))
+        .collect();
+    assert_eq!(
+        cases,
+        expected
+            .iter()
+            .map(|(name, run)| (name.as_str(), run.as_str()))
+            .collect::<Vec<_>>()
+    );
+    assert!(expansion
+        .scenarios
+        .iter()
+        .all(|case| case_scenario(&case.name) == Some("count")));
+    Ok(())
+}
+
+#[test]
+fn test_steps_are_rendered_with_the_row() -> Result<()> {
+    let rows = vec![Ok(fields(&[("user", "alice"), ("dir", "/home/alice")]))];
+    let scenarios = [scenario(json!({
+        "name": "login",
+        "steps": [{
+            "name": "whoami",
+            "command": ["login", "{{ row.user }}"],
+            "workdir": "{{ row.dir }}",
+            "env": { "USER": "{{ row.user }}" }
+        }]
+    }))];
+
+    let expansion = expand_scenarios(&scenarios, &rows, &TemplateRenderer::new()?);
+
+    let step = &expansion.scenarios[0].steps[0];
+    assert_eq!(step.command, ["login", "alice"]);
+    assert_eq!(step.workdir.as_deref(), Some("/home/alice"));
+    assert_eq!(step.env.as_ref().expect("env")["USER"], "alice");
+    Ok(())
+}
+
+#[test]
+fn test_row_fields_do_not_leak_into_later_rows() -> Result<()> {
+    let rows = vec![
+        Ok(fields(&[("user", "alice"), ("role", "admin")])),
+        Ok(fields(&[("user", "bob")])),
+    ];
+    let scenarios = [scenario(json!({
+        "name": "grant",
+        "service": "app",
+        "run": "grant {{ row.user }} {{ row.role | default(value=\"none\") }}"
+    }))];
+    let renderer = TemplateRenderer::new()?;
+
+    let expansion = expand_scenarios(&scenarios, &rows, &renderer);
+
+    let runs: Vec<&str> = expansion
+        .scenarios
+        .iter()
+        .filter_map(|case| case.run.as_deref())
+        .collect();
+    assert_eq!(runs, ["grant alice admin", "grant bob none"]);
+
+    let after = renderer
+        .clone()
+        .render_str("{% if row is defined %}leaked{% endif %}", "after")?;
+    assert_eq!(after, "");
+    Ok(())
+}
+
+#[test]
+fn test_failed_rows_fail_every_case_and_keep_dependencies_per_row() -> Result<()> {
+    let rows = vec![
+        Ok(fields(&[("id", "1")])),
+        Err("expected 1 field(s), found 2".to_string()),
+        Ok(fields(&[("id", "3")])),
+    ];
+    let scenarios = [
+        scenario(json!({ "name": "create", "service": "app", "run": "create {{ row.id }}" })),
+        scenario(json!({
+            "name": "check",
+            "service": "app",
+            "run": "check {{ row.id }}",
+            "depends_on": ["create"]
+        })),
+    ];
+
+    let expansion = expand_scenarios(&scenarios, &rows, &TemplateRenderer::new()?);
+
+    let failed: Vec<&str> = expansion
+        .failures
+        .iter()
+        .map(|(case, _)| case.as_str())
+        .collect();
+    assert_eq!(failed, ["create[1]", "check[1]"]);
+    assert!(expansion.failures[0]
+        .1
+        .to_string()
+        .contains("Data row 1 is malformed"));
+
+    let cases: Vec<(&str, &[String])> = expansion
+        .scenarios
+        .iter()
+        .map(|case| (case.name.as_str(), case.depends_on.as_slice()))
+        .collect();
+    assert_eq!(
+        cases,
+        [
+            ("create[0]", &[][..]),
+            ("check[0]", &["create[0]".to_string()][..]),
+            ("create[2]", &[][..]),
+            ("check[2]", &["create[2]".to_string()][..]),
+        ]
+    );
+    Ok(())
+}
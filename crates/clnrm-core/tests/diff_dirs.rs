@@ -0,0 +1,131 @@
+//! `clnrm diff` over directories of recorded traces
+
+mod common;
+
+use clnrm_core::cli::commands::diff_trace_dirs;
+use clnrm_core::cli::commands::DurationThreshold;
+use clnrm_core::cli::types::MatchBy;
+use common::{span, write_trace};
+use serde_json::json;
+use std::path::Path;
+
+#[test]
+fn test_directories_are_compared_file_by_file() {
+    let baseline = tempfile::tempdir().expect("temp dir");
+    let current = tempfile::tempdir().expect("temp dir");
+
+    let login = json!([
+        span("login", "internal", json!({ "status": "ok" })),
+        span("db.query", "internal", json!({ "status": "ok" }))
+    ]);
+    write_trace(baseline.path(), "login.json", &login);
+    write_trace(current.path(), "login.json", &login);
+
+    write_trace(
+        baseline.path(),
+        "orders/checkout.json",
+        &json!([span("checkout", "internal", json!({ "status": "ok" }))]),
+    );
+    write_trace(
+        current.path(),
+        "orders/checkout.json",
+        &json!([
+            span("checkout", "internal", json!({ "status": "error" })),
+            span("retry", "internal", json!({ "status": "ok" }))
+        ]),
+    );
+
+    write_trace(
+        baseline.path(),
+        "legacy.json",
+        &json!([span("legacy", "internal", json!({ "status": "ok" }))]),
+    );
+    write_trace(
+        current.path(),
+        "search.json",
+        &json!([span("search", "internal", json!({ "status": "ok" }))]),
+    );
+    std::fs::write(current.path().join("notes.txt"), "not a trace").expect("write notes");
+
+    let result = diff_trace_dirs(
+        baseline.path(),
+        current.path(),
+        "tree",
+        false,
+        true,
+        &MatchBy::Name,
+        &DurationThreshold::default(),
+    )
+    .expect("directories diff");
+
+    assert!(result.has_changes());
+    assert_eq!(result.added, ["search.json"]);
+    assert_eq!(result.removed, ["legacy.json"]);
+    assert_eq!(result.unchanged_count, 1);
+
+    assert_eq!(result.changed.len(), 1);
+    let (name, diff) = &result.changed[0];
+    assert_eq!(
+        Path::new(name),
+        Path::new("orders").join("checkout.json").as_path()
+    );
+    assert_eq!(diff.added_count, 1);
+    assert_eq!(diff.modified_count, 1);
+}
+
+#[test]
+fn test_identical_directories_have_no_changes() {
+    let baseline = tempfile::tempdir().expect("temp dir");
+    let current = tempfile::tempdir().expect("temp dir");
+    for dir in [baseline.path(), current.path()] {
+        write_trace(
+            dir,
+            "login.json",
+            &json!([span("login", "internal", json!({ "status": "ok" }))]),
+        );
+    }
+
+    let result = diff_trace_dirs(
+        baseline.path(),
+        current.path(),
+        "json",
+        false,
+        false,
+        &MatchBy::Name,
+        &DurationThreshold::default(),
+    )
+    .expect("directories diff");
+
+    assert!(!result.has_changes());
+    assert_eq!(result.unchanged_count, 1);
+}
+
+#[test]
+fn test_unparseable_trace_names_the_file() {
+    let baseline = tempfile::tempdir().expect("temp dir");
+    let current = tempfile::tempdir().expect("temp dir");
+    write_trace(
+        baseline.path(),
+        "login.json",
+        &json!([span("login", "internal", json!({ "status": "ok" }))]),
+    );
+    std::fs::write(current.path().join("login.json"), "{").expect("write trace");
+
+    let error = diff_trace_dirs(
+        baseline.path(),
+        current.path(),
+        "tree",
+        false,
+        false,
+        &MatchBy::Name,
+        &DurationThreshold::default(),
+    )
+    .expect_err("broken trace");
+
+    assert!(
+        error.to_string().contains("Failed to parse current JSON")
+            && error.to_string().contains("login.json"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,72 @@
+//! `clnrm diff --match-by` pairs spans across runs
+
+mod common;
+
+use clnrm_core::cli::commands::v0_7_0::diff::compare_traces;
+use clnrm_core::cli::commands::DurationThreshold;
+use clnrm_core::cli::types::MatchBy;
+use common::{child_span, write_trace};
+use serde_json::json;
+use std::path::PathBuf;
+
+/// The same `checkout` -> `db.query` trace recorded twice, each run with
+/// fresh span IDs
+fn two_runs(dir: &tempfile::TempDir) -> (PathBuf, PathBuf) {
+    let baseline = write_trace(
+        dir.path(),
+        "baseline.json",
+        &json!([
+            child_span("checkout", "a1", None),
+            child_span("db.query", "a2", Some("a1"))
+        ]),
+    );
+    let current = write_trace(
+        dir.path(),
+        "current.json",
+        &json!([
+            child_span("checkout", "b1", None),
+            child_span("db.query", "b2", Some("b1"))
+        ]),
+    );
+    (baseline, current)
+}
+
+#[test]
+fn test_match_by_name_ignores_fresh_span_ids() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (baseline, current) = two_runs(&dir);
+
+    // Act
+    let result = compare_traces(
+        &baseline,
+        &current,
+        &MatchBy::Name,
+        &DurationThreshold::default(),
+    )
+    .expect("traces diff");
+
+    // Assert
+    assert!(!result.has_changes(), "{:?}", result);
+}
+
+#[test]
+fn test_match_by_id_reports_fresh_span_ids() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let (baseline, current) = two_runs(&dir);
+
+    // Act
+    let result = compare_traces(
+        &baseline,
+        &current,
+        &MatchBy::Id,
+        &DurationThreshold::default(),
+    )
+    .expect("traces diff");
+
+    // Assert
+    assert_eq!(result.added_count, 2, "{:?}", result);
+    assert_eq!(result.removed_count, 2, "{:?}", result);
+    assert_eq!(result.modified_count, 0, "{:?}", result);
+}
@@ -0,0 +1,119 @@
+//! `[[expect.duration]]` bounds on span durations
+
+mod common;
+
+use clnrm_core::error::ErrorCode;
+use clnrm_core::validation::duration_validator::DurationExpectation;
+use clnrm_core::validation::span_validator::SpanData;
+use common::span;
+use serde_json::json;
+use std::collections::HashSet;
+
+/// A `db.query` span lasting `duration_ms`
+fn query_span(duration_ms: u64) -> SpanData {
+    let mut span = span("db.query", "client", json!({}));
+    span["start_time_unix_nano"] = json!(1_000_000_000u64);
+    span["end_time_unix_nano"] = json!(1_000_000_000u64 + duration_ms * 1_000_000);
+    serde_json::from_value(span).expect("valid span")
+}
+
+fn no_synthetic_timing() -> HashSet<String> {
+    HashSet::new()
+}
+
+#[test]
+fn test_span_within_the_bounds_passes() {
+    // Arrange
+    let expectation = DurationExpectation::new("db.query")
+        .with_min_ms(10.0)
+        .with_max_ms(250.0);
+
+    // Act
+    let result = expectation.validate(&[query_span(100)], &no_synthetic_timing());
+
+    // Assert
+    assert!(result.is_ok(), "{:?}", result.err());
+}
+
+#[test]
+fn test_span_shorter_than_the_minimum_fails() {
+    // Arrange
+    let expectation = DurationExpectation::new("db.query").with_min_ms(10.0);
+
+    // Act
+    let error = expectation
+        .validate(&[query_span(5)], &no_synthetic_timing())
+        .expect_err("below min_ms");
+
+    // Assert
+    assert_eq!(error.code, Some(ErrorCode::SpanDuration));
+    assert!(
+        error
+            .to_string()
+            .contains("took 5ms, expected at least 10ms"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_span_longer_than_the_maximum_fails() {
+    // Arrange
+    let expectation = DurationExpectation::new("db.query").with_max_ms(250.0);
+
+    // Act
+    let error = expectation
+        .validate(&[query_span(300)], &no_synthetic_timing())
+        .expect_err("above max_ms");
+
+    // Assert
+    assert_eq!(error.code, Some(ErrorCode::SpanDuration));
+    assert!(
+        error
+            .to_string()
+            .contains("took 300ms, expected at most 250ms"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_span_without_timestamps_has_no_timing_data() {
+    // Arrange
+    let expectation = DurationExpectation::new("db.query").with_max_ms(250.0);
+    let untimed: SpanData =
+        serde_json::from_value(span("db.query", "client", json!({}))).expect("valid span");
+
+    // Act
+    let error = expectation
+        .validate(&[untimed], &no_synthetic_timing())
+        .expect_err("no timestamps");
+
+    // Assert
+    assert_eq!(error.code, Some(ErrorCode::SpanDuration));
+    assert!(
+        error.to_string().contains("has no timing data"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_span_timed_by_a_frozen_clock_has_no_timing_data() {
+    // Arrange: timestamps within the bounds, but synthesized
+    let expectation = DurationExpectation::new("db.query").with_max_ms(250.0);
+    let synthetic_timing = HashSet::from(["db.query".to_string()]);
+
+    // Act
+    let error = expectation
+        .validate(&[query_span(100)], &synthetic_timing)
+        .expect_err("synthetic timestamps");
+
+    // Assert
+    assert_eq!(error.code, Some(ErrorCode::SpanDuration));
+    assert!(
+        error.to_string().contains("has no timing data"),
+        "{}",
+        error
+    );
+}
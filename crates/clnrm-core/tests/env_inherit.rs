@@ -0,0 +1,75 @@
+//! Host environment allowlisting with `clnrm run --env-inherit` and
+//! `[meta] inherit_env`
+//!
+//! Steps run on the `process` backend, which clears the host environment
+//! the same way a container starts without it.
+
+mod common;
+
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::cli::types::CliConfig;
+use common::{meta, on_backend, write_test};
+
+/// A test whose only step fails unless `CLNRM_INHERIT_TOKEN` is set
+fn needs_token(name: &str, meta_extra: &str) -> String {
+    format!(
+        r#"{}{}
+
+[[steps]]
+name = "needs_token"
+command = ["test", "\"$CLNRM_INHERIT_TOKEN\"", "=", "secret"]
+"#,
+        meta(name),
+        meta_extra
+    )
+}
+
+// One test fn, since it sets process-wide environment variables
+#[tokio::test(flavor = "multi_thread")]
+async fn test_steps_only_see_allowlisted_host_variables() {
+    std::env::set_var("CLNRM_INHERIT_TOKEN", "secret");
+    let dir = tempfile::tempdir().expect("temp dir");
+
+    // Nothing is inherited by default
+    let plain = write_test(dir.path(), "plain", &needs_token("plain", ""));
+    let result = run_test_file(&plain, &on_backend("process"))
+        .await
+        .expect("test runs");
+    assert!(!result.passed, "host variable leaked into the step");
+
+    let config = CliConfig {
+        inherit_env: vec!["CLNRM_INHERIT_TOKEN".to_string()],
+        ..on_backend("process")
+    };
+    let result = run_test_file(&plain, &config).await.expect("test runs");
+    assert!(result.passed, "{:?}", result.error);
+
+    let allowlisted = write_test(
+        dir.path(),
+        "allowlisted",
+        &needs_token("allowlisted", r#"inherit_env = ["CLNRM_INHERIT_TOKEN"]"#),
+    );
+    let result = run_test_file(&allowlisted, &on_backend("process"))
+        .await
+        .expect("test runs");
+    assert!(result.passed, "{:?}", result.error);
+
+    // An allowlisted variable missing on the host fails the same way
+    std::env::remove_var("CLNRM_INHERIT_TOKEN");
+    let result = run_test_file(&allowlisted, &on_backend("process"))
+        .await
+        .expect("test runs");
+    assert!(!result.passed);
+
+    let config = CliConfig {
+        inherit_env: vec!["CLNRM_INHERIT_TOKEN=secret".to_string()],
+        ..on_backend("process")
+    };
+    let result = run_test_file(&plain, &config).await.expect("test runs");
+    let error = result.error.expect("invalid name is rejected");
+    assert!(
+        error.contains("Invalid environment variable name 'CLNRM_INHERIT_TOKEN=secret'"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,44 @@
+//! `clnrm explain` looks error codes up in the catalog
+
+use clnrm_core::cli::commands::explain::{explain_error_code, explanation};
+use clnrm_core::error::ErrorCode;
+
+#[test]
+fn test_error_code_resolves_to_its_explanation() {
+    // Act
+    let code = ErrorCode::parse("clnrm-v010").expect("known code");
+
+    // Assert
+    assert_eq!(code, ErrorCode::SpanCountMismatch);
+    assert_eq!(ErrorCode::parse("V010"), Some(code));
+    assert_eq!(explanation(code).title, "Span count mismatch");
+    assert!(explain_error_code(Some("CLNRM-V010")).is_ok());
+}
+
+#[test]
+fn test_every_error_code_has_an_explanation() {
+    for code in ErrorCode::ALL {
+        // Act
+        let explanation = explanation(*code);
+
+        // Assert
+        assert_eq!(ErrorCode::parse(code.as_str()), Some(*code));
+        assert!(!explanation.title.is_empty(), "{}", code);
+        assert!(!explanation.description.is_empty(), "{}", code);
+        assert!(!explanation.remediation.is_empty(), "{}", code);
+    }
+}
+
+#[test]
+fn test_unknown_error_code_is_an_error() {
+    // Act
+    let error = explain_error_code(Some("CLNRM-V999")).expect_err("unknown code");
+
+    // Assert
+    assert_eq!(ErrorCode::parse("CLNRM-V999"), None);
+    assert!(
+        error.to_string().contains("Unknown error code: CLNRM-V999"),
+        "{}",
+        error
+    );
+}
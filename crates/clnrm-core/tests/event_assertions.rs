@@ -0,0 +1,143 @@
+//! `[[expect.event]]` assertions on span events and their attributes
+
+mod common;
+
+use clnrm_core::error::Result;
+use clnrm_core::otel::otlp_receiver::parse_export_request;
+use clnrm_core::validation::{EventAssertion, SpanData, SpanEvent};
+use serde_json::{json, Value};
+
+fn http_span(events: Value) -> SpanData {
+    let mut span = common::span("http.request", "client", json!({}));
+    span["events"] = events;
+    serde_json::from_value(span).expect("valid span")
+}
+
+fn timeout_exception_span() -> SpanData {
+    http_span(json!([
+        { "name": "cache.miss" },
+        {
+            "name": "exception",
+            "attributes": {
+                "exception.type": "TimeoutError",
+                "exception.message": "deadline exceeded"
+            }
+        }
+    ]))
+}
+
+#[test]
+fn test_event_with_matching_attributes_passes() -> Result<()> {
+    EventAssertion::new("http.request", "exception")
+        .with_attribute("exception.type", "TimeoutError")
+        .validate(&[timeout_exception_span()])
+}
+
+#[test]
+fn test_event_with_other_attributes_reports_expected_and_actual() {
+    let error = EventAssertion::new("http.request", "exception")
+        .with_attribute("exception.type", "ConnectionError")
+        .validate(&[timeout_exception_span()])
+        .expect_err("attribute differs");
+
+    let message = error.to_string();
+    assert!(
+        message.contains("exception.type='ConnectionError'"),
+        "{}",
+        message
+    );
+    assert!(
+        message.contains("exception.type='TimeoutError'"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_event_recorded_by_name_only_fails_attribute_checks() {
+    let span = http_span(json!(["exception"]));
+    assert_eq!(span.events, Some(vec![SpanEvent::new("exception")]));
+
+    EventAssertion::new("http.request", "exception")
+        .validate(std::slice::from_ref(&span))
+        .expect("event was emitted");
+
+    let error = EventAssertion::new("http.request", "exception")
+        .with_attribute("exception.type", "TimeoutError")
+        .validate(&[span])
+        .expect_err("attributes were not recorded");
+    assert!(
+        error.to_string().contains("exception.type not set"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_span_without_events_array_fails() {
+    let span: SpanData = serde_json::from_value(common::span("http.request", "client", json!({})))
+        .expect("valid span");
+
+    let error = EventAssertion::new("http.request", "exception")
+        .validate(&[span])
+        .expect_err("no events");
+    assert!(
+        error.to_string().contains("has no events array"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_unemitted_event_lists_the_events_that_were() {
+    let error = EventAssertion::new("http.request", "retry")
+        .validate(&[timeout_exception_span()])
+        .expect_err("retry not emitted");
+
+    let message = error.to_string();
+    assert!(
+        message.contains("did not emit event 'retry'"),
+        "{}",
+        message
+    );
+    assert!(message.contains("cache.miss, exception"), "{}", message);
+}
+
+#[test]
+fn test_missing_span_fails() {
+    let error = EventAssertion::new("db.query", "exception")
+        .validate(&[timeout_exception_span()])
+        .expect_err("span missing");
+    assert!(
+        error.to_string().contains("span 'db.query' not found"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_otlp_events_keep_their_attributes() -> Result<()> {
+    let request = json!({
+        "resourceSpans": [{
+            "scopeSpans": [{
+                "spans": [{
+                    "name": "http.request",
+                    "traceId": "trace-1",
+                    "spanId": "span-1",
+                    "events": [{
+                        "name": "exception",
+                        "attributes": [{
+                            "key": "exception.type",
+                            "value": { "stringValue": "TimeoutError" }
+                        }]
+                    }]
+                }]
+            }]
+        }]
+    });
+
+    let spans = parse_export_request(&request);
+    EventAssertion::new("http.request", "exception")
+        .with_attribute("exception.type", "TimeoutError")
+        .validate(&spans)
+}
@@ -0,0 +1,74 @@
+//! `expected_exit_code` on steps
+
+use clnrm_core::config::{parse_toml_config, StepConfig};
+use clnrm_core::FailureReason;
+
+fn step(expectation: &str) -> StepConfig {
+    let config = parse_toml_config(&format!(
+        r#"
+[meta]
+name = "exit"
+version = "1.0"
+
+[[steps]]
+name = "missing_file"
+command = ["cat", "/missing"]
+{}
+"#,
+        expectation
+    ))
+    .expect("config parses");
+    config.steps[0].clone()
+}
+
+#[test]
+fn test_expected_code_passes_and_other_codes_fail() {
+    let step = step("expected_exit_code = 3");
+
+    assert_eq!(step.expected_exit_code(), 3);
+    assert!(step.check_exit_code(3).is_ok());
+    for other in [0, 1, 137] {
+        assert!(step.check_exit_code(other).is_err(), "exit {}", other);
+    }
+}
+
+#[test]
+fn test_mismatch_names_expected_and_actual_code() {
+    let error = step("expected_exit_code = 3")
+        .check_exit_code(0)
+        .expect_err("exit 0 is unexpected");
+
+    assert!(
+        error
+            .to_string()
+            .contains("Step 'missing_file' failed: expected exit 3, got 0"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_default_expectation_is_zero() {
+    let step = step("");
+
+    assert_eq!(step.expected_exit_code(), 0);
+    assert!(step.check_exit_code(0).is_ok());
+    let error = step.check_exit_code(1).expect_err("exit 1 fails");
+    assert!(
+        error.to_string().contains("expected exit 0, got 1"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_unexpected_success_has_its_own_failure_reason() {
+    assert_eq!(
+        FailureReason::UnexpectedExitCode.as_str(),
+        "unexpected_exit_code"
+    );
+    assert_eq!(
+        serde_json::to_value(FailureReason::UnexpectedExitCode).expect("serializes"),
+        serde_json::json!("unexpected_exit_code")
+    );
+}
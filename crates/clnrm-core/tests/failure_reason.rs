@@ -0,0 +1,123 @@
+//! Structured failure reasons on execution and step results
+
+use clnrm_core::cli::types::{CliTestResult, CliTestResults};
+use clnrm_core::cli::utils::generate_junit_xml;
+use clnrm_core::error::CleanroomError;
+use clnrm_core::scenario::StepResult;
+use clnrm_core::{ExecutionResult, FailureReason};
+use std::time::Duration;
+
+fn execution(exit_code: i32) -> ExecutionResult {
+    ExecutionResult {
+        exit_code,
+        oom_killed: false,
+        stdout: String::new(),
+        stderr: String::new(),
+        duration: Duration::from_millis(5),
+        command: vec!["true".to_string()],
+        container_name: "test".to_string(),
+    }
+}
+
+fn failed_step(name: &str, reason: Option<FailureReason>) -> StepResult {
+    StepResult {
+        exit_code: 1,
+        failure_reason: reason,
+        ..StepResult::failed(name, "suite.clnrm.toml", "boom")
+    }
+}
+
+fn failed_test(steps: Vec<StepResult>) -> CliTestResult {
+    CliTestResult {
+        name: "suite.clnrm.toml".to_string(),
+        path: "suite.clnrm.toml".into(),
+        passed: false,
+        duration_ms: 10,
+        error: Some("boom".to_string()),
+        steps,
+    }
+}
+
+#[test]
+fn test_failure_reason_from_exit_code() {
+    assert_eq!(execution(0).failure_reason(), None);
+    assert_eq!(
+        execution(1).failure_reason(),
+        Some(FailureReason::NonZeroExit)
+    );
+    assert_eq!(execution(143).failure_reason(), Some(FailureReason::Signal));
+    assert_eq!(execution(-1).failure_reason(), Some(FailureReason::Signal));
+}
+
+#[test]
+fn test_oom_kill_is_taken_from_the_runtime_not_the_exit_code() {
+    // A timeout or `docker kill` also ends a command with SIGKILL
+    assert_eq!(execution(137).failure_reason(), Some(FailureReason::Signal));
+
+    let oom = |exit_code| ExecutionResult {
+        oom_killed: true,
+        ..execution(exit_code)
+    };
+    assert_eq!(oom(137).failure_reason(), Some(FailureReason::OomKilled));
+    assert_eq!(oom(1).failure_reason(), Some(FailureReason::OomKilled));
+    assert_eq!(oom(0).failure_reason(), None);
+}
+
+#[test]
+fn test_failure_reason_from_timeout_error() {
+    assert_eq!(
+        FailureReason::from_error(&CleanroomError::timeout_error("too slow")),
+        Some(FailureReason::Timeout)
+    );
+    assert_eq!(
+        FailureReason::from_error(&CleanroomError::container_error("no such image")),
+        None
+    );
+}
+
+#[test]
+fn test_step_result_serializes_failure_reason() {
+    let json = serde_json::to_value(failed_step("run", Some(FailureReason::OomKilled)))
+        .expect("serializes");
+    assert_eq!(json["failure_reason"], "oom_killed");
+
+    let passed = serde_json::to_value(StepResult::skipped("run", "suite")).expect("serializes");
+    assert!(passed.get("failure_reason").is_none());
+
+    let parsed: StepResult = serde_json::from_value(json).expect("deserializes");
+    assert_eq!(parsed.failure_reason, Some(FailureReason::OomKilled));
+}
+
+#[test]
+fn test_junit_failure_type_is_the_failure_reason() {
+    let results = CliTestResults {
+        tests: vec![failed_test(vec![
+            failed_step("check", Some(FailureReason::RegexMismatch)),
+            failed_step("never_ran", None),
+        ])],
+        total_duration_ms: 10,
+    };
+
+    let xml = generate_junit_xml(&results, false).expect("junit");
+    assert!(xml.contains("type=\"regex_mismatch\""), "{}", xml);
+    assert!(xml.contains("type=\"step_failure\""), "{}", xml);
+
+    let flat = generate_junit_xml(&results, true).expect("junit");
+    assert!(flat.contains("type=\"regex_mismatch\""), "{}", flat);
+    assert!(!flat.contains("type=\"test_failure\""), "{}", flat);
+}
+
+#[test]
+fn test_test_failure_reason_is_first_failed_step() {
+    let mut passed = StepResult::skipped("setup", "suite");
+    passed.skipped = false;
+
+    let test = failed_test(vec![
+        passed,
+        failed_step("run", Some(FailureReason::Timeout)),
+        failed_step("cleanup", Some(FailureReason::NonZeroExit)),
+    ]);
+    assert_eq!(test.failure_reason(), Some(FailureReason::Timeout));
+
+    assert_eq!(failed_test(Vec::new()).failure_reason(), None);
+}
@@ -0,0 +1,133 @@
+//! `content_needs_formatting` fast path agrees with formatting and skips re-emitting
+
+use clnrm_core::cli::commands::format_files;
+use clnrm_core::error::Result;
+use clnrm_core::formatting::{content_needs_formatting, format_toml_content, is_canonical};
+
+const FORMATTED: &str = r#"# Login flow
+[meta]
+name = "login"
+version = "1.0"
+
+[services.api]
+image = "alpine:3.19" # pinned
+ports = [8080, 9090]
+
+[[steps]]
+name = "create"
+command = [
+    "sh", # shell
+    "-c",
+    "echo 'a = b' # not a comment",
+]
+expected_output_regex = """
+multi "line" with = and # inside
+"""
+env = { TOKEN = "x", LEVEL = 'debug' }
+
+[[steps]]
+name = "héllo ünïcode"
+command = ["echo", "\"quoted\" \\"]
+"#;
+
+fn full_path(content: &str) -> Result<bool> {
+    Ok(format_toml_content(content)? != content)
+}
+
+/// A large, already formatted file with `steps` `[[steps]]` entries
+fn large_config(steps: usize) -> String {
+    let mut content = String::from("[meta]\nname = \"large\"\nversion = \"1.0\"\n");
+    for i in 0..steps {
+        content.push_str(&format!(
+            "\n# Step {i}\n[[steps]]\nname = \"step_{i}\"\ncommand = [\"sh\", \"-c\", \"echo {i}\"]\nexpected_output_regex = \"^{i}$\" # exact\n"
+        ));
+    }
+    content
+}
+
+#[test]
+fn test_formatted_content_takes_fast_path() -> Result<()> {
+    assert!(!full_path(FORMATTED)?);
+    assert!(!content_needs_formatting(FORMATTED)?);
+    Ok(())
+}
+
+#[test]
+fn test_fast_path_agrees_with_formatter() -> Result<()> {
+    let variants = [
+        FORMATTED.replace("name = \"login\"", "name=\"login\""),
+        FORMATTED.replace("# pinned", "   # pinned"),
+        FORMATTED.replace("[services.api]", "[ services.api ]"),
+        FORMATTED.replace("version = \"1.0\"", "version = \"1.0\"  "),
+        FORMATTED.replace('\n', "\r\n"),
+        format!("{}\n\n", FORMATTED),
+        FORMATTED.trim_end().to_string(),
+        FORMATTED.replace("# Login flow", "# Login flow   "),
+    ];
+
+    for variant in &variants {
+        assert_eq!(
+            content_needs_formatting(variant)?,
+            full_path(variant)?,
+            "disagreement on:\n{}",
+            variant
+        );
+        assert!(
+            content_needs_formatting(variant)?,
+            "unformatted:\n{}",
+            variant
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_unrecognised_layout_falls_back_to_formatter() -> Result<()> {
+    // Quoted keys aren't recognised by the scan but are left as written
+    let quoted = FORMATTED.replace("name = \"create\"", "\"name\" = \"create\"");
+
+    assert!(!full_path(&quoted)?);
+    assert!(!content_needs_formatting(&quoted)?);
+    Ok(())
+}
+
+#[test]
+fn test_invalid_unformatted_toml_is_still_an_error() {
+    assert!(content_needs_formatting("[meta\nname=1\n").is_err());
+}
+
+#[test]
+fn test_invalid_toml_in_canonical_layout_is_an_error() {
+    assert!(content_needs_formatting("name = hello\n").is_err());
+    assert!(content_needs_formatting("[meta]\nname = \"a\"\nname = \"b\"\n").is_err());
+}
+
+#[test]
+fn test_fast_path_skips_reformatting_large_formatted_file() -> Result<()> {
+    let content = large_config(2_000);
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("large.clnrm.toml");
+    std::fs::write(&path, &content).expect("write config");
+    let modified = std::fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .expect("mtime");
+
+    // The scan accepts the file, so the check returns before formatting it,
+    // and the formatter agrees it is already formatted
+    assert!(is_canonical(&content));
+    assert!(!full_path(&content)?);
+
+    format_files(std::slice::from_ref(&path), true, false)?;
+
+    assert_eq!(
+        std::fs::read_to_string(&path).expect("read config"),
+        content
+    );
+    assert_eq!(
+        std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .expect("mtime"),
+        modified
+    );
+    Ok(())
+}
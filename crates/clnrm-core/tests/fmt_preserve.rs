@@ -0,0 +1,64 @@
+//! `clnrm fmt` keeps comments, grouping and order, changing only spacing
+
+use clnrm_core::error::Result;
+use clnrm_core::formatting::{format_toml_content, verify_idempotency};
+
+const COMMENTED: &str = r#"# Login flow against the API service
+[meta]
+name = "login"
+version = "1.0"
+
+# Services are started before any step
+[services.api]
+type = "generic_container"
+plugin = "generic_container"
+image = "alpine:3.19" # pinned for reproducibility
+
+[[steps]]
+# First: create the user
+name = "create"
+command = ["sh", "-c", "echo created"]
+
+[[steps]]
+name = "authenticate" # runs after create
+command = ["echo", "a=b"]
+expected_output_regex = "a=b"
+"#;
+
+#[test]
+fn test_formatted_config_round_trips_unchanged() -> Result<()> {
+    assert_eq!(format_toml_content(COMMENTED)?, COMMENTED);
+    Ok(())
+}
+
+#[test]
+fn test_only_spacing_changes() -> Result<()> {
+    let messy = COMMENTED
+        .replace("name = \"login\"", "name=\"login\"   ")
+        .replace("# pinned", "     # pinned")
+        .replace("[services.api]", "[ services.api ]  ")
+        .replace("version = \"1.0\"", "version   =    \"1.0\"")
+        .replace("# First: create the user", "# First: create the user   ");
+
+    assert_eq!(format_toml_content(&messy)?, COMMENTED);
+    Ok(())
+}
+
+#[test]
+fn test_keys_and_tables_keep_their_order() -> Result<()> {
+    let content = "[vars]\nzeta = \"1\"\nalpha = \"2\"\n\n[[steps]]\nname = \"b\"\n\n[[steps]]\nname = \"a\"\n";
+    let formatted = format_toml_content(content)?;
+
+    assert_eq!(formatted, content);
+    assert!(verify_idempotency(&formatted)?);
+    Ok(())
+}
+
+#[test]
+fn test_trailing_blank_lines_collapse_to_one_newline() -> Result<()> {
+    assert_eq!(
+        format_toml_content("[meta]\nname = \"x\"\n\n\n  \n")?,
+        "[meta]\nname = \"x\"\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! `clnrm fmt --stdin` formatting of piped TOML
+
+use clnrm_core::cli::commands::format_stream;
+use clnrm_core::error::Result;
+use clnrm_core::formatting::format_toml_content;
+
+const UNFORMATTED: &str = "[meta]\nname=\"fmt\"   \nversion  =  \"1.0\"\n";
+
+#[test]
+fn test_stream_writes_formatted_toml() -> Result<()> {
+    let mut output = Vec::new();
+    format_stream(UNFORMATTED.as_bytes(), &mut output, false, true)?;
+
+    let formatted = String::from_utf8(output).expect("utf-8 output");
+    assert_eq!(formatted, "[meta]\nname = \"fmt\"\nversion = \"1.0\"\n");
+    Ok(())
+}
+
+#[test]
+fn test_check_fails_on_unformatted_input_without_output() {
+    let mut output = Vec::new();
+    let error = format_stream(UNFORMATTED.as_bytes(), &mut output, true, false)
+        .expect_err("unformatted input rejected");
+
+    assert!(error.to_string().contains("stdin needs formatting"));
+    assert!(output.is_empty());
+}
+
+#[test]
+fn test_check_passes_on_formatted_input() -> Result<()> {
+    let formatted = format_toml_content(UNFORMATTED)?;
+    let mut output = Vec::new();
+
+    format_stream(formatted.as_bytes(), &mut output, true, false)?;
+    assert!(output.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_invalid_toml_is_an_error() {
+    let error = format_stream("[meta\n".as_bytes(), Vec::new(), false, false)
+        .expect_err("invalid TOML rejected");
+
+    assert!(error.to_string().contains("Failed to parse TOML"));
+}
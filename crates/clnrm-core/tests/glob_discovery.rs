@@ -0,0 +1,64 @@
+//! Test discovery expands glob patterns that don't name an existing path
+
+mod common;
+
+use clnrm_core::cli::utils::discover_test_files;
+use common::{write_file, write_test};
+use std::path::PathBuf;
+
+#[test]
+fn test_glob_pattern_matches_test_files() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let smoke_api = write_test(&dir.path().join("api"), "smoke_api", "");
+    let smoke_web = write_test(&dir.path().join("web"), "smoke_web", "");
+    write_test(&dir.path().join("api"), "load_api", "");
+    write_file(dir.path(), "web/smoke_notes.txt", "");
+    let pattern = dir.path().join("**/smoke_*");
+
+    // Act
+    let mut found = discover_test_files(&pattern).expect("pattern matches");
+
+    // Assert
+    found.sort();
+    assert_eq!(found, [smoke_api, smoke_web]);
+}
+
+#[test]
+fn test_glob_pattern_matching_nothing_is_an_error() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_test(dir.path(), "load_api", "");
+    let pattern = dir.path().join("smoke_*.clnrm.toml");
+
+    // Act
+    let error = discover_test_files(&pattern).expect_err("nothing matches");
+
+    // Assert
+    assert!(
+        error.to_string().contains(&format!(
+            "No test files (.toml, .clnrm.toml, .clnrm.yaml or .clnrm.yml) match pattern: {}",
+            pattern.display()
+        )),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_invalid_glob_pattern_is_an_error() {
+    // Arrange
+    let pattern = PathBuf::from("tests/[smoke.clnrm.toml");
+
+    // Act
+    let error = discover_test_files(&pattern).expect_err("unclosed bracket");
+
+    // Assert
+    assert!(
+        error
+            .to_string()
+            .contains("Invalid glob pattern 'tests/[smoke.clnrm.toml'"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,83 @@
+//! `clnrm graph --format dot` output
+
+use clnrm_core::cli::commands::visualize_graph;
+use clnrm_core::cli::types::GraphFormat;
+use serde_json::json;
+
+fn render_dot(trace: serde_json::Value) -> String {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let trace_path = dir.path().join("trace.json");
+    std::fs::write(&trace_path, trace.to_string()).expect("write trace");
+    let output = dir.path().join("graph.dot");
+
+    visualize_graph(&trace_path, &GraphFormat::Dot, false, None, Some(&output))
+        .expect("dot renders");
+    std::fs::read_to_string(&output).expect("graph written")
+}
+
+#[test]
+fn test_dot_labels_spans_with_duration_and_edges_with_kind() {
+    let dot = render_dot(json!({
+        "spans": [
+            {
+                "name": "GET /users",
+                "span_id": "a1",
+                "kind": "server",
+                "start_time_unix_nano": 1_000_000_000u64,
+                "end_time_unix_nano": 1_012_500_000u64
+            },
+            {
+                "name": "db \"users\"",
+                "span_id": "b2",
+                "parent_span_id": "a1",
+                "kind": "client",
+                "start_time_unix_nano": 1_001_000_000u64,
+                "end_time_unix_nano": 1_004_000_000u64
+            },
+            {
+                "name": "cache.get",
+                "span_id": "c3",
+                "parent_span_id": "gone",
+                "kind": "internal"
+            }
+        ]
+    }));
+
+    assert_eq!(
+        dot,
+        r#"digraph trace {
+  rankdir=TB;
+  node [shape=box, style=rounded];
+
+  "a1" [label="GET /users\n12.500ms"];
+  "b2" [label="db \"users\"\n3.000ms"];
+  "c3" [label="cache.get"];
+  "gone" [label="missing parent gone", color=red, style=dashed];
+
+  "a1" -> "b2" [label="client"];
+  "gone" -> "c3" [label="internal", color=red, style=dashed];
+}
+"#
+    );
+}
+
+#[test]
+fn test_dot_output_is_stable() {
+    let trace = json!({
+        "spans": [
+            { "name": "root", "span_id": "1" },
+            { "name": "x", "span_id": "2", "parent_span_id": "missing-b" },
+            { "name": "y", "span_id": "3", "parent_span_id": "missing-a" },
+            { "name": "z", "span_id": "4", "parent_span_id": "1" }
+        ]
+    });
+
+    let first = render_dot(trace.clone());
+    assert_eq!(first, render_dot(trace));
+    assert!(first.contains("  \"4\" [label=\"z\"];\n  \"missing-a\""));
+    assert!(
+        first.find("\"missing-a\" [").expect("placeholder a")
+            < first.find("\"missing-b\" [").expect("placeholder b")
+    );
+    assert!(first.contains("  \"1\" -> \"4\";\n"));
+}
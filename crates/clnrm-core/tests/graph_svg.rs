@@ -0,0 +1,96 @@
+//! `clnrm graph --format svg` rendering
+
+mod common;
+
+use clnrm_core::cli::commands::visualize_graph;
+use clnrm_core::cli::types::GraphFormat;
+use common::{child_span, write_trace};
+use serde_json::{json, Value};
+
+fn render_svg(spans: Value, highlight_missing: bool) -> clnrm_core::error::Result<String> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let trace = write_trace(dir.path(), "trace.json", &json!({ "spans": spans }));
+    let output = dir.path().join("graph.svg");
+    visualize_graph(
+        &trace,
+        &GraphFormat::Svg,
+        highlight_missing,
+        None,
+        Some(&output),
+    )?;
+    Ok(std::fs::read_to_string(&output).expect("graph written"))
+}
+
+#[test]
+fn test_svg_draws_spans_and_parent_edges() {
+    let svg = render_svg(
+        json!([
+            child_span("http <request>", "1", None),
+            child_span("db.query", "2", Some("1")),
+            child_span("cache.get", "3", Some("1")),
+        ]),
+        false,
+    )
+    .expect("svg renders");
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+    assert_eq!(svg.matches("<rect x=").count(), 3);
+    assert_eq!(svg.matches("<line ").count(), 2);
+    assert!(svg.contains(">http &lt;request&gt;</text>"));
+    assert!(svg.contains(">db.query</text>"));
+    assert!(!svg.contains("#d32f2f"));
+}
+
+#[test]
+fn test_svg_highlights_missing_parent_edges_in_red() {
+    let spans = json!([
+        child_span("root", "1", None),
+        child_span("orphan", "2", Some("gone"))
+    ]);
+
+    let highlighted = render_svg(spans.clone(), true).expect("svg renders");
+    assert!(highlighted.contains(">missing parent gone</text>"));
+    assert_eq!(highlighted.matches("stroke=\"#d32f2f\"").count(), 2);
+    assert!(highlighted.contains("stroke-dasharray"));
+
+    let plain = render_svg(spans, false).expect("svg renders");
+    assert!(!plain.contains("missing parent"));
+    assert!(plain.contains(">orphan</text>"));
+}
+
+#[test]
+fn test_cycles_are_reported_instead_of_looping() {
+    let error = render_svg(
+        json!([
+            child_span("root", "1", None),
+            child_span("a", "2", Some("3")),
+            child_span("b", "3", Some("2")),
+        ]),
+        false,
+    )
+    .expect_err("cycle detected");
+
+    assert!(
+        error
+            .to_string()
+            .contains("Span graph contains a cycle: a -> b -> a"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_repeated_span_ids_do_not_loop() {
+    let svg = render_svg(
+        json!([
+            child_span("first", "x", None),
+            child_span("child", "y", Some("x")),
+            child_span("second", "x", Some("y")),
+        ]),
+        false,
+    )
+    .expect("svg renders");
+
+    assert_eq!(svg.matches("<rect x=").count(), 3);
+}
@@ -0,0 +1,115 @@
+//! Validation of services on the hermetic network of `enforce_network`
+
+mod common;
+
+use clnrm_core::config::{parse_toml_config, TestConfig};
+use common::meta;
+
+fn hermetic_test(service: &str) -> TestConfig {
+    parse_toml_config(&format!(
+        r#"{}
+[services.api]
+plugin = "generic_container"
+image = "nginx:alpine"
+{}
+
+[[steps]]
+name = "call"
+command = ["wget", "-qO-", "http://api/"]
+
+[expect.hermeticity]
+enforce_network = true
+"#,
+        meta("hermetic"),
+        service
+    ))
+    .expect("config parses")
+}
+
+#[test]
+fn test_http_health_check_is_rejected_on_the_hermetic_network() {
+    let config = hermetic_test(
+        r#"
+[services.api.health_check]
+http = "http://localhost:80/"
+"#,
+    );
+
+    let error = config
+        .validate()
+        .expect_err("HTTP probe can't reach the service");
+    let message = error.to_string();
+    assert!(message.contains("Service api"), "{}", message);
+    assert!(
+        message.contains("HTTP health check 'http://localhost:80/' runs from the host"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_command_health_check_is_allowed_on_the_hermetic_network() {
+    let config = hermetic_test(
+        r#"
+[services.api.health_check]
+cmd = ["wget", "-qO-", "http://localhost/"]
+"#,
+    );
+
+    config
+        .validate()
+        .expect("command probe runs in the container");
+}
+
+#[test]
+fn test_http_health_check_is_allowed_without_enforce_network() {
+    let mut config = hermetic_test(
+        r#"
+[services.api.health_check]
+http = "http://localhost:80/"
+"#,
+    );
+    config.expect = None;
+
+    config.validate().expect("probe reaches the published port");
+}
+
+#[test]
+fn test_port_readiness_check_is_rejected_on_the_hermetic_network() {
+    let config = hermetic_test("ports = [80]");
+
+    let error = config
+        .validate()
+        .expect_err("port probe can't reach the service");
+    let message = error.to_string();
+    assert!(message.contains("Service api"), "{}", message);
+    assert!(
+        message.contains("ports are published to the host for readiness checks"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_other_plugins_keep_their_ports_on_the_hermetic_network() {
+    let config = parse_toml_config(&format!(
+        r#"{}
+[services.site]
+plugin = "web_server"
+ports = [8080]
+
+[[steps]]
+name = "call"
+command = ["true"]
+
+[expect.hermeticity]
+enforce_network = true
+"#,
+        meta("hermetic")
+    ))
+    .expect("config parses");
+
+    config
+        .validate()
+        .expect("web_server stays on the default bridge");
+}
@@ -0,0 +1,46 @@
+//! Egress detection for steps run on a hermetic network
+
+use clnrm_core::backend::network::egress_violation;
+
+#[test]
+fn test_curl_resolve_failure_names_host() {
+    let output = "curl: (6) Could not resolve host: example.com\n";
+
+    assert_eq!(
+        egress_violation(output, &[]).as_deref(),
+        Some("example.com")
+    );
+}
+
+#[test]
+fn test_busybox_wget_bad_address_names_host() {
+    let output = "wget: bad address 'example.com'\n";
+
+    assert_eq!(
+        egress_violation(output, &[]).as_deref(),
+        Some("example.com")
+    );
+}
+
+#[test]
+fn test_unreachable_network_without_host() {
+    let output = "wget: can't connect to remote host (93.184.215.14): Network unreachable\n";
+
+    assert_eq!(
+        egress_violation(output, &[]).as_deref(),
+        Some("an external address")
+    );
+}
+
+#[test]
+fn test_declared_service_failures_are_not_egress() {
+    let allowed = vec!["api".to_string()];
+    let output = "curl: (7) Failed to connect to api port 8080: Connection refused\n";
+
+    assert_eq!(egress_violation(output, &allowed), None);
+}
+
+#[test]
+fn test_clean_output_has_no_violation() {
+    assert_eq!(egress_violation("hello from api\n", &[]), None);
+}
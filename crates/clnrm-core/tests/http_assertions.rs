@@ -0,0 +1,174 @@
+//! HTTP response assertions: status, headers and JSONPath values
+
+use clnrm_core::assertions::http::{assert_header, assert_json_path, assert_status, HttpResponse};
+use clnrm_core::error::Result;
+use serde_json::json;
+
+fn user_response() -> HttpResponse {
+    HttpResponse::new(
+        200,
+        r#"{"user": {"id": 42, "name": "jane", "roles": ["admin", "dev"], "first name": "Jane"}}"#,
+    )
+    .with_header("Content-Type", "application/json; charset=utf-8")
+    .with_header("X-Request-Id", "abc")
+}
+
+fn error_message(result: Result<()>) -> String {
+    result.expect_err("assertion fails").to_string()
+}
+
+#[test]
+fn test_status_mismatch_shows_the_body() -> Result<()> {
+    let response = HttpResponse::new(503, "upstream down");
+
+    assert_status(&response, 503)?;
+    let message = error_message(assert_status(&response, 200));
+    assert!(
+        message.contains("Expected HTTP status 200, got 503 (body: upstream down)"),
+        "{}",
+        message
+    );
+    Ok(())
+}
+
+#[test]
+fn test_long_bodies_are_truncated_in_errors() {
+    let response = HttpResponse::new(500, "x".repeat(1_000));
+
+    let message = error_message(assert_status(&response, 200));
+    assert!(
+        message.contains(&format!("{}...", "x".repeat(200))),
+        "{}",
+        message
+    );
+    assert!(!message.contains(&"x".repeat(201)), "{}", message);
+}
+
+#[test]
+fn test_header_names_are_case_insensitive() -> Result<()> {
+    let response = user_response();
+
+    assert_header(&response, "x-request-id", "abc")?;
+    assert_header(&response, "X-REQUEST-ID", "abc")?;
+    Ok(())
+}
+
+#[test]
+fn test_media_type_matches_with_or_without_parameters() -> Result<()> {
+    let response = user_response();
+
+    assert_header(&response, "content-type", "application/json")?;
+    assert_header(&response, "content-type", "Application/JSON")?;
+    assert_header(&response, "content-type", "application/json; charset=utf-8")?;
+    assert_header(&response, "content-type", "application/json;charset=utf-8")?;
+    Ok(())
+}
+
+#[test]
+fn test_media_type_mismatches_fail() {
+    let response = user_response();
+
+    let message = error_message(assert_header(&response, "content-type", "text/html"));
+    assert!(
+        message.contains(
+            "Expected header 'content-type' to be 'text/html', got 'application/json; charset=utf-8'"
+        ),
+        "{}",
+        message
+    );
+    assert!(assert_header(
+        &response,
+        "content-type",
+        "application/json; charset=latin1"
+    )
+    .is_err());
+    assert!(assert_header(&response, "content-type", "application/js").is_err());
+}
+
+#[test]
+fn test_missing_header_fails() {
+    let message = error_message(assert_header(&user_response(), "etag", "\"v1\""));
+    assert!(
+        message.contains("the response has no such header"),
+        "{}",
+        message
+    );
+}
+
+#[test]
+fn test_json_path_reaches_fields_indexes_and_quoted_keys() -> Result<()> {
+    let response = user_response();
+
+    assert_json_path(&response, "$.user.id", 42)?;
+    assert_json_path(&response, "$.user.roles[1]", "dev")?;
+    assert_json_path(&response, "$['user']['first name']", "Jane")?;
+    assert_json_path(&response, "$.user[\"name\"]", "jane")?;
+    assert_json_path(&response, "$.user.roles", json!(["admin", "dev"]))?;
+    Ok(())
+}
+
+#[test]
+fn test_json_path_mismatch_lists_every_difference() {
+    let message = error_message(assert_json_path(
+        &user_response(),
+        "$.user",
+        json!({"id": 41, "name": "jane", "roles": ["admin"], "email": "j@x"}),
+    ));
+
+    for difference in [
+        "$.user.id: expected 41, found 42",
+        "$.user.roles[1]: unexpected \"dev\"",
+        "$.user.email: missing, expected \"j@x\"",
+        "$.user.first name: unexpected \"Jane\"",
+    ] {
+        assert!(message.contains(difference), "{}", message);
+    }
+    assert!(!message.contains("$.user.name"), "{}", message);
+}
+
+#[test]
+fn test_missing_json_path_names_the_step() {
+    let message = error_message(assert_json_path(&user_response(), "$.user.roles[5]", "ops"));
+    assert!(
+        message.contains("JSON path '$.user.roles[5]' not found: $.user.roles has no index 5"),
+        "{}",
+        message
+    );
+
+    let message = error_message(assert_json_path(&user_response(), "$.account.id", 1));
+    assert!(message.contains("$ has no field 'account'"), "{}", message);
+}
+
+#[test]
+fn test_invalid_json_paths_are_rejected() {
+    for (path, reason) in [
+        ("user.id", "must start with '$'"),
+        ("$..id", "empty field name after '.'"),
+        ("$.roles[0", "unclosed '['"),
+        (
+            "$.roles[first]",
+            "'[first]' is neither an index nor a quoted field",
+        ),
+        ("$user", "unexpected 'user'"),
+    ] {
+        let message = error_message(assert_json_path(&user_response(), path, 1));
+        assert!(
+            message.contains(&format!("Invalid JSON path '{}': {}", path, reason)),
+            "{}",
+            message
+        );
+    }
+}
+
+#[test]
+fn test_non_json_body_fails() {
+    let response = HttpResponse::new(200, "<html>ok</html>");
+
+    let message = error_message(assert_json_path(&response, "$.ok", true));
+    assert!(
+        message.contains("Response body is not valid JSON"),
+        "{}",
+        message
+    );
+    assert!(message.contains("<html>ok</html>"), "{}", message);
+}
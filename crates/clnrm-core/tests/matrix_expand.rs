@@ -0,0 +1,130 @@
+//! `[matrix]` expansion into one concrete config per combination
+
+mod common;
+
+use clnrm_core::config::{expand_matrix, matrix_combinations, parse_toml_config};
+use common::meta;
+use std::collections::HashMap;
+
+fn matrix_test(name: &str, matrix: &str, command: &str) -> String {
+    format!(
+        "{}\n[matrix]\n{}\n\n[[steps]]\nname = \"say\"\ncommand = [\"echo\", \"{}\"]\n",
+        meta(name),
+        matrix,
+        command
+    )
+}
+
+#[test]
+fn test_every_combination_becomes_a_named_case() {
+    let config = parse_toml_config(&matrix_test(
+        "smoke",
+        "version = [\"3.18\", \"3.19\"]\nos = [\"alpine\", \"debian\"]",
+        "{{ matrix.os }}:{{ matrix.version }}",
+    ))
+    .expect("config parses");
+
+    let cases = expand_matrix(&config).expect("matrix expands");
+    let summary: Vec<(String, String)> = cases
+        .iter()
+        .map(|case| {
+            (
+                case.get_name().expect("case name"),
+                case.steps[0].command[1].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (
+                "smoke [os=alpine, version=3.18]".to_string(),
+                "alpine:3.18".to_string()
+            ),
+            (
+                "smoke [os=alpine, version=3.19]".to_string(),
+                "alpine:3.19".to_string()
+            ),
+            (
+                "smoke [os=debian, version=3.18]".to_string(),
+                "debian:3.18".to_string()
+            ),
+            (
+                "smoke [os=debian, version=3.19]".to_string(),
+                "debian:3.19".to_string()
+            ),
+        ]
+    );
+    assert!(cases.iter().all(|case| case.matrix.is_none()));
+}
+
+#[test]
+fn test_names_that_reference_the_matrix_are_kept() {
+    let config = parse_toml_config(&matrix_test(
+        "greet_{{ matrix.word }}",
+        "word = [\"hi\", \"bye\"]",
+        "{{ matrix.word }}",
+    ))
+    .expect("config parses");
+
+    let names: Vec<String> = expand_matrix(&config)
+        .expect("matrix expands")
+        .iter()
+        .map(|case| case.get_name().expect("case name"))
+        .collect();
+    assert_eq!(names, ["greet_hi", "greet_bye"]);
+}
+
+#[test]
+fn test_config_without_matrix_expands_to_itself() {
+    let config = parse_toml_config(&format!(
+        "{}\n[[steps]]\nname = \"say\"\ncommand = [\"echo\", \"hi\"]\n",
+        meta("plain")
+    ))
+    .expect("config parses");
+
+    let cases = expand_matrix(&config).expect("expands");
+    assert_eq!(cases.len(), 1);
+    assert_eq!(cases[0].get_name().expect("name"), "plain");
+}
+
+#[test]
+fn test_invalid_dimensions_are_rejected() {
+    let cases = [
+        (HashMap::new(), "must declare at least one dimension"),
+        (
+            HashMap::from([("os".to_string(), vec![])]),
+            "Matrix dimension 'os' has no values",
+        ),
+        (
+            HashMap::from([("os".to_string(), vec!["a".to_string(), "a".to_string()])]),
+            "Matrix dimension 'os' lists 'a' more than once",
+        ),
+        (
+            HashMap::from([("os-name".to_string(), vec!["a".to_string()])]),
+            "Matrix dimension 'os-name' is not a valid name",
+        ),
+    ];
+
+    for (matrix, expected) in cases {
+        let error = matrix_combinations(&matrix).expect_err(expected);
+        assert!(error.to_string().contains(expected), "{}", error);
+    }
+}
+
+#[test]
+fn test_undeclared_dimension_fails_its_case() {
+    let config = parse_toml_config(&matrix_test(
+        "smoke",
+        "os = [\"alpine\"]",
+        "{{ matrix.version }}",
+    ))
+    .expect("config parses");
+
+    let error = expand_matrix(&config).expect_err("version is not declared");
+    assert!(
+        error.to_string().contains("Matrix case [os=alpine]"),
+        "{}",
+        error
+    );
+}
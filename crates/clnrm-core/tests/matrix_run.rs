@@ -0,0 +1,62 @@
+//! `clnrm run` runs a `[matrix]` test once per combination
+
+mod common;
+
+use clnrm_core::testing::TestResult;
+use common::{meta, run_config_on};
+
+/// Runs a matrix test whose `bye` case fails
+async fn run_greetings() -> TestResult {
+    run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[matrix]
+word = ["hi", "bye", "hello"]
+
+[[steps]]
+name = "say"
+command = ["echo", "{{{{ matrix.word }}}}"]
+expected_output_regex = "^h"
+"#,
+            meta("greet")
+        ),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_each_matrix_case_runs() {
+    // Act
+    let result = run_greetings().await;
+
+    // Assert
+    let steps: Vec<(&str, &str, bool)> = result
+        .steps
+        .iter()
+        .map(|s| (s.source.as_str(), s.stdout.trim(), s.success))
+        .collect();
+    assert_eq!(
+        steps,
+        [
+            ("greet [word=hi]", "hi", true),
+            ("greet [word=bye]", "bye", false),
+            ("greet [word=hello]", "hello", true),
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failed_matrix_cases_are_named() {
+    // Act
+    let result = run_greetings().await;
+
+    // Assert
+    assert!(!result.passed);
+    let error = result.error.expect("a case failed");
+    assert!(
+        error.contains("1 of 3 matrix case(s) failed: greet [word=bye]"),
+        "{}",
+        error
+    );
+}
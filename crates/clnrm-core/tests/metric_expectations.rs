@@ -0,0 +1,213 @@
+//! `[[expect.metric]]` assertions on OTLP counters and histograms
+
+use clnrm_core::config::parse_toml_config;
+use clnrm_core::otel::{parse_metrics_json, MetricData, MetricKind, OtlpReceiver};
+use clnrm_core::validation::{MetricExpectation, PrdExpectations};
+use serde_json::json;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// One export of a cumulative `http.server.requests` counter and an
+/// `http.server.duration` histogram
+fn export(login_requests: u64, health_requests: u64, time: u64) -> serde_json::Value {
+    let route = |route: &str| json!([{ "key": "http.route", "value": { "stringValue": route } }]);
+    json!({
+        "resourceMetrics": [{
+            "resource": { "attributes": [{ "key": "service.name", "value": { "stringValue": "api" } }] },
+            "scopeMetrics": [{
+                "metrics": [
+                    {
+                        "name": "http.server.requests",
+                        "unit": "{request}",
+                        "sum": {
+                            "aggregationTemporality": 2,
+                            "isMonotonic": true,
+                            "dataPoints": [
+                                { "attributes": route("/login"), "timeUnixNano": time.to_string(), "asInt": login_requests.to_string() },
+                                { "attributes": route("/health"), "timeUnixNano": time.to_string(), "asInt": health_requests.to_string() }
+                            ]
+                        }
+                    },
+                    {
+                        "name": "http.server.duration",
+                        "unit": "ms",
+                        "histogram": {
+                            "aggregationTemporality": 2,
+                            "dataPoints": [
+                                { "attributes": route("/login"), "timeUnixNano": time.to_string(), "count": login_requests.to_string(), "sum": 12.5 * login_requests as f64 }
+                            ]
+                        }
+                    }
+                ]
+            }]
+        }]
+    })
+}
+
+/// Two periodic exports, as a collector file exporter writes them
+fn metrics() -> Vec<MetricData> {
+    let lines = format!("{}\n{}\n", export(1, 4, 100), export(3, 9, 200));
+    parse_metrics_json(&lines).expect("metrics parse")
+}
+
+#[test]
+fn test_otlp_metrics_are_parsed() {
+    let metrics = metrics();
+
+    assert_eq!(metrics.len(), 4);
+    let requests = &metrics[2];
+    assert_eq!(requests.name, "http.server.requests");
+    assert_eq!(requests.kind, MetricKind::Sum);
+    assert_eq!(requests.unit.as_deref(), Some("{request}"));
+    assert!(!requests.delta);
+    assert_eq!(requests.points[0].value, Some(3.0));
+    assert_eq!(requests.resource_attributes["service.name"], json!("api"));
+    assert_eq!(metrics[3].kind, MetricKind::Histogram);
+    assert_eq!(metrics[3].points[0].count, Some(3));
+}
+
+#[test]
+fn test_counter_value_uses_the_latest_cumulative_export() {
+    let metrics = metrics();
+
+    let login = MetricExpectation::new("http.server.requests")
+        .with_attribute("http.route", "/login")
+        .with_value(3.0);
+    login.validate(&metrics).expect("3 login requests");
+
+    // Without attributes the series are totalled
+    MetricExpectation::new("http.server.requests")
+        .with_value(12.0)
+        .validate(&metrics)
+        .expect("12 requests in total");
+
+    MetricExpectation::new("http.server.duration")
+        .with_min_count(1)
+        .with_value(37.5)
+        .validate(&metrics)
+        .expect("histogram has samples");
+}
+
+#[test]
+fn test_missing_metric_is_reported_apart_from_a_wrong_value() {
+    let metrics = metrics();
+
+    let missing = MetricExpectation::new("db.client.connections")
+        .with_value(1.0)
+        .validate(&metrics)
+        .expect_err("metric was never exported");
+    assert!(
+        missing.to_string().starts_with("[CLNRM-V060]")
+            && missing
+                .to_string()
+                .contains("metric 'db.client.connections' not found"),
+        "{}",
+        missing
+    );
+
+    let no_series = MetricExpectation::new("http.server.requests")
+        .with_attribute("http.route", "/logout")
+        .with_value(1.0)
+        .validate(&metrics)
+        .expect_err("no /logout series");
+    assert!(
+        no_series.to_string().starts_with("[CLNRM-V060]"),
+        "{}",
+        no_series
+    );
+
+    let mismatch = MetricExpectation::new("http.server.requests")
+        .with_attribute("http.route", "/login")
+        .with_value(5.0)
+        .validate(&metrics)
+        .expect_err("3 requests, not 5");
+    assert!(
+        mismatch.to_string().starts_with("[CLNRM-V061]")
+            && mismatch
+                .to_string()
+                .contains("metric 'http.server.requests' is 3, expected 5"),
+        "{}",
+        mismatch
+    );
+
+    let not_histogram = MetricExpectation::new("http.server.requests")
+        .with_count(3)
+        .validate(&metrics)
+        .expect_err("counters have no sample count");
+    assert!(
+        not_histogram.to_string().contains("is a sum"),
+        "{}",
+        not_histogram
+    );
+
+    let report = PrdExpectations::new()
+        .add_metric(MetricExpectation::new("http.server.requests").with_min_value(1.0))
+        .add_metric(MetricExpectation::new("db.client.connections").with_value(1.0))
+        .validate_metrics(&metrics);
+    assert_eq!(report.pass_count(), 1);
+    assert_eq!(report.failure_count(), 1);
+}
+
+#[test]
+fn test_expect_metric_config_parses() {
+    let config = parse_toml_config(
+        r#"
+[meta]
+name = "metrics"
+version = "1.0"
+
+[[steps]]
+name = "noop"
+command = ["true"]
+
+[[expect.metric]]
+name = "http.server.requests"
+attributes = { "http.route" = "/login" }
+value = 3
+
+[[expect.metric]]
+name = "http.server.duration"
+min_count = 1
+"#,
+    )
+    .expect("config parses");
+
+    let metrics = &config.expect.expect("expectations").metric;
+    assert_eq!(metrics.len(), 2);
+    assert_eq!(metrics[0].value, Some(3.0));
+    assert_eq!(metrics[0].attributes["http.route"], "/login");
+    assert_eq!(metrics[1].min_count, Some(1));
+}
+
+#[tokio::test]
+async fn test_receiver_accepts_otlp_metrics() {
+    let receiver = OtlpReceiver::start("127.0.0.1:0")
+        .await
+        .expect("receiver starts");
+    let body = export(2, 0, 100).to_string();
+    let mut stream = tokio::net::TcpStream::connect(receiver.local_addr())
+        .await
+        .expect("connects");
+    let request = format!(
+        "POST /v1/metrics HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .expect("request sent");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("response read");
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+    assert!(receiver.spans().is_empty());
+    MetricExpectation::new("http.server.requests")
+        .with_attribute("http.route", "/login")
+        .with_value(2.0)
+        .validate(&receiver.metrics())
+        .expect("2 login requests");
+}
@@ -0,0 +1,179 @@
+//! OTLP headers and propagators from a test's `[otel_headers]` and
+//! `[otel_propagators]` tables
+//!
+//! Spans are exported to a mock OTLP/HTTP receiver on a local port that
+//! records the headers of every export request.
+
+use clnrm_core::config::load_config_from_file;
+use clnrm_core::error::Result;
+use clnrm_core::telemetry::test_run::init_test_telemetry;
+use opentelemetry::global;
+use opentelemetry::trace::{Span, Tracer, TracerProvider};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+
+/// Headers of each request received by the mock receiver, names lowercased
+type ReceivedHeaders = Arc<Mutex<Vec<HashMap<String, String>>>>;
+
+/// Accept OTLP/HTTP exports on a local port, answering each with `200 OK`
+async fn start_mock_receiver() -> (SocketAddr, ReceivedHeaders) {
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock receiver");
+    let addr = listener.local_addr().expect("mock receiver address");
+    let received = ReceivedHeaders::default();
+
+    let requests = received.clone();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let requests = requests.clone();
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                loop {
+                    let mut request_line = String::new();
+                    if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+
+                    let mut headers = HashMap::new();
+                    loop {
+                        let mut line = String::new();
+                        if stream.read_line(&mut line).await.unwrap_or(0) == 0 {
+                            return;
+                        }
+                        let Some((name, value)) = line.trim_end().split_once(':') else {
+                            break;
+                        };
+                        headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+                    }
+
+                    let length = headers
+                        .get("content-length")
+                        .and_then(|length| length.parse().ok())
+                        .unwrap_or(0);
+                    let mut body = vec![0; length];
+                    if stream.read_exact(&mut body).await.is_err() {
+                        return;
+                    }
+                    requests.lock().expect("received headers").push(headers);
+
+                    let response = "HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n";
+                    if stream
+                        .get_mut()
+                        .write_all(response.as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    (addr, received)
+}
+
+fn write_test_file(dir: &tempfile::TempDir, otel_tables: &str) -> std::path::PathBuf {
+    let path = dir.path().join("otel_headers.clnrm.toml");
+    let content = format!(
+        r#"
+[meta]
+name = "otel_headers"
+version = "1.0"
+
+[[steps]]
+name = "noop"
+command = ["true"]
+
+{}
+"#,
+        otel_tables
+    );
+    std::fs::write(&path, content).expect("write test file");
+    path
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_otel_headers_reach_collector() -> Result<()> {
+    let (addr, received) = start_mock_receiver().await;
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = write_test_file(
+        &dir,
+        &format!(
+            r#"
+[otel]
+exporter = "otlp"
+endpoint = "http://{}"
+
+[otel_headers]
+"x-api-key" = "secret-key"
+
+[otel_propagators]
+use = ["tracecontext", "b3"]
+"#,
+            addr
+        ),
+    );
+
+    let config = load_config_from_file(&path)?;
+    let guard = init_test_telemetry(&config)?.expect("otlp exporter installed");
+    guard
+        .tracer_provider()
+        .tracer("otel-headers-test")
+        .start("clnrm.container.exec")
+        .end();
+
+    // Dropping the guard blocks until the spans have been exported
+    tokio::task::spawn_blocking(move || drop(guard))
+        .await
+        .expect("flush telemetry");
+
+    let received = received.lock().expect("received headers").clone();
+    assert!(!received.is_empty(), "mock receiver got no export request");
+    assert!(received
+        .iter()
+        .all(|headers| headers.get("x-api-key").map(String::as_str) == Some("secret-key")));
+
+    let fields: Vec<String> = global::get_text_map_propagator(|propagator| {
+        propagator.fields().map(str::to_string).collect()
+    });
+    assert!(fields.iter().any(|field| field == "traceparent"));
+    assert!(fields.iter().any(|field| field == "b3"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_otel_header_name_fails_at_load() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = write_test_file(&dir, "[otel_headers]\n\"x api key\" = \"secret-key\"\n");
+
+    let error = load_config_from_file(&path).expect_err("invalid header name rejected");
+    assert!(error
+        .to_string()
+        .contains("Invalid OTLP header name 'x api key'"));
+}
+
+#[test]
+fn test_empty_otel_header_name_fails_at_load() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = write_test_file(&dir, "[otel_headers]\n\"\" = \"secret-key\"\n");
+
+    let error = load_config_from_file(&path).expect_err("empty header name rejected");
+    assert!(error
+        .to_string()
+        .contains("OTLP header name cannot be empty"));
+}
+
+#[test]
+fn test_unknown_otel_propagator_fails_at_load() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = write_test_file(&dir, "[otel_propagators]\nuse = [\"jaeger\"]\n");
+
+    let error = load_config_from_file(&path).expect_err("unknown propagator rejected");
+    assert!(error.to_string().contains("Invalid propagator 'jaeger'"));
+}
@@ -0,0 +1,117 @@
+//! Spans a scenario exports over OTLP reach validation through
+//! `artifacts.collect = ["spans:otlp"]`
+
+use clnrm_core::cleanroom::{CleanroomEnvironment, HealthStatus, ServiceHandle, ServicePlugin};
+use clnrm_core::cli::commands::run::scenario::execute_scenario;
+use clnrm_core::config::{parse_toml_config, CleanroomConfig, TestConfig};
+use clnrm_core::error::Result;
+use clnrm_core::scenario::StepResult;
+use std::collections::HashMap;
+
+/// Service stand-in; the scenario's command runs on the host
+#[derive(Debug)]
+struct AppPlugin;
+
+impl ServicePlugin for AppPlugin {
+    fn name(&self) -> &str {
+        "app"
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        Ok(ServiceHandle {
+            id: "app-1".to_string(),
+            service_name: "app".to_string(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        Ok(())
+    }
+
+    fn health_check(&self, _handle: &ServiceHandle) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+}
+
+/// A port nothing is listening on
+fn free_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .and_then(|listener| listener.local_addr())
+        .expect("free port")
+        .port()
+}
+
+/// A test whose scenario exports a `checkout` span with curl and expects
+/// one span named `expected`
+fn otlp_test(expected: &str) -> TestConfig {
+    let port = free_port();
+    parse_toml_config(&format!(
+        r#"
+[meta]
+name = "otlp"
+version = "1.0"
+
+[otel]
+exporter = "otlp"
+endpoint = "http://127.0.0.1:{port}"
+
+[[scenario]]
+name = "export"
+service = "app"
+run = '''curl -sf -X POST -H "Content-Type: application/json" --data '{{"resourceSpans":[{{"scopeSpans":[{{"spans":[{{"traceId":"t","spanId":"1","name":"checkout","kind":2}}]}}]}}]}}' http://127.0.0.1:{port}/v1/traces'''
+artifacts.collect = ["spans:otlp"]
+
+[expect.counts]
+by_name = {{ {expected} = {{ eq = 1 }} }}
+"#
+    ))
+    .expect("config parses")
+}
+
+/// Runs the test's scenario against the stand-in service, with commands on
+/// the host
+async fn run_scenario(test_config: &TestConfig) -> (Result<()>, Vec<StepResult>) {
+    let mut cleanroom = CleanroomConfig::default();
+    cleanroom.containers.backend = "process".to_string();
+    let env = CleanroomEnvironment::with_config(Some(cleanroom))
+        .await
+        .expect("environment starts without Docker");
+    env.register_service(Box::new(AppPlugin))
+        .await
+        .expect("register plugin");
+    let handle = env.start_service("app").await.expect("start service");
+    let handles = HashMap::from([("app".to_string(), handle)]);
+
+    let scenario = &test_config.scenario[0];
+    let mut results = Vec::new();
+    let outcome = execute_scenario(scenario, &env, &handles, test_config, &mut results).await;
+    (outcome, results)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spans_received_over_otlp_pass_validation() {
+    // Arrange
+    let test_config = otlp_test("checkout");
+
+    // Act
+    let (outcome, results) = run_scenario(&test_config).await;
+
+    // Assert
+    assert!(outcome.is_ok(), "{:?} {:?}", outcome.err(), results);
+    let names: Vec<&str> = results[0].spans.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, ["checkout"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spans_received_over_otlp_fail_unmet_expectations() {
+    // Arrange
+    let test_config = otlp_test("payment");
+
+    // Act
+    let (outcome, _) = run_scenario(&test_config).await;
+
+    // Assert
+    let error = outcome.expect_err("no payment span was exported");
+    assert!(error.to_string().contains("payment"), "{}", error);
+}
@@ -0,0 +1,88 @@
+//! Parallel runs report results in test file order, not completion order
+
+mod common;
+
+use clnrm_core::cli::commands::run::{run_tests_parallel_into, run_tests_parallel_with_results};
+use clnrm_core::cli::types::CliConfig;
+use common::{meta, on_backend, write_test};
+use std::path::{Path, PathBuf};
+
+/// A test whose only step sleeps for `seconds`
+fn sleeps(name: &str, seconds: &str) -> String {
+    format!(
+        "{}\n[[steps]]\nname = \"sleep\"\ncommand = [\"sleep\", \"{}\"]\n",
+        meta(name),
+        seconds
+    )
+}
+
+/// Tests in `dir` where earlier files finish last
+fn tests_finishing_in_reverse(dir: &Path) -> Vec<PathBuf> {
+    vec![
+        write_test(dir, "c_fast", &sleeps("c_fast", "0")),
+        write_test(dir, "a_slow", &sleeps("a_slow", "0.6")),
+        write_test(dir, "b_medium", &sleeps("b_medium", "0.3")),
+    ]
+}
+
+fn parallel() -> CliConfig {
+    CliConfig {
+        parallel: true,
+        jobs: 3,
+        ..on_backend("process")
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parallel_results_follow_file_order() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let paths = tests_finishing_in_reverse(dir.path());
+
+    // Act
+    let results = run_tests_parallel_with_results(&paths, &parallel())
+        .await
+        .expect("tests run");
+
+    // Assert
+    let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "a_slow.clnrm.toml",
+            "b_medium.clnrm.toml",
+            "c_fast.clnrm.toml"
+        ]
+    );
+    assert!(results.iter().all(|r| r.passed));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_results_collected_earlier_stay_first() {
+    // Arrange: results already collected, e.g. by an earlier `--repeat` run
+    let dir = tempfile::tempdir().expect("temp dir");
+    let paths = tests_finishing_in_reverse(dir.path());
+    let config = parallel();
+    let mut results = run_tests_parallel_with_results(&paths, &config)
+        .await
+        .expect("tests run");
+
+    // Act
+    run_tests_parallel_into(&paths, &config, &mut results)
+        .await
+        .expect("tests run");
+
+    // Assert
+    let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "a_slow.clnrm.toml",
+            "b_medium.clnrm.toml",
+            "c_fast.clnrm.toml",
+            "a_slow.clnrm.toml",
+            "b_medium.clnrm.toml",
+            "c_fast.clnrm.toml"
+        ]
+    );
+}
@@ -0,0 +1,163 @@
+//! Command allow/deny lists from `Policy` and a test's `[policy]` table
+
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::cli::commands::run::scenario::execute_scenarios;
+use clnrm_core::cli::types::CliConfig;
+use clnrm_core::config::parse_toml_config;
+use clnrm_core::{CleanroomEnvironment, Policy};
+use std::collections::HashMap;
+
+fn command(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn test_denied_binary_is_rejected() {
+    let policy = Policy::default().with_denied_binaries(["rm"]);
+
+    let error = policy
+        .check_command(&command("rm -rf /data"))
+        .expect_err("rm denied");
+    assert!(error
+        .to_string()
+        .contains("binary 'rm' is in [policy] denied_binaries"));
+}
+
+#[test]
+fn test_denied_binary_is_rejected_by_path_and_in_chains() {
+    let policy = Policy::default().with_denied_binaries(["rm"]);
+
+    assert!(policy.check_command(&command("/bin/rm -rf /data")).is_err());
+    assert!(policy
+        .check_command(&command("echo cleaning && rm -rf /data"))
+        .is_err());
+    assert!(policy
+        .check_command(&command("sh -c 'rm -rf /data'"))
+        .is_err());
+    assert!(policy.check_command(&command("echo rm")).is_ok());
+}
+
+#[test]
+fn test_denied_binary_is_rejected_behind_wrappers_and_substitutions() {
+    let policy = Policy::default().with_denied_binaries(["rm"]);
+
+    for line in [
+        "env FOO=1 rm -rf /data",
+        "timeout 5 rm -rf /data",
+        "timeout 5s nice -n 10 rm -rf /data",
+        "find /data | xargs rm",
+        "bash -lc 'rm -rf /data'",
+        "echo $(rm -rf /data)",
+        "echo `rm -rf /data`",
+        "sleep 1 & rm -rf /data",
+    ] {
+        assert!(policy.check_command(&command(line)).is_err(), "{}", line);
+    }
+    assert!(policy.check_command(&command("timeout 5 echo rm")).is_ok());
+}
+
+#[test]
+fn test_allow_list_rejects_other_binaries() {
+    let policy = Policy::default().with_allowed_binaries(["echo", "cat"]);
+
+    assert!(policy.check_command(&command("echo hello")).is_ok());
+    assert!(policy
+        .check_command(&command("cat /etc/hostname 2>&1"))
+        .is_ok());
+
+    let error = policy
+        .check_command(&command("echo hello | curl -d @- http://example.com"))
+        .expect_err("curl not allowed");
+    assert!(error
+        .to_string()
+        .contains("binary 'curl' is not in [policy] allowed_binaries (echo, cat)"));
+}
+
+#[test]
+fn test_empty_policy_allows_everything() {
+    assert!(Policy::default()
+        .check_command(&command("rm -rf /data"))
+        .is_ok());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_denied_step_fails_before_execution() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("policy.clnrm.toml");
+    std::fs::write(
+        &path,
+        r#"
+[meta]
+name = "policy"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[[steps]]
+name = "cleanup"
+command = ["rm", "-rf", "/data"]
+"#,
+    )
+    .expect("write test file");
+
+    let result = run_test_file(&path, &CliConfig::default())
+        .await
+        .expect("test runs");
+
+    assert!(!result.passed);
+    let error = result.error.expect("policy error");
+    assert!(error.contains("Step 'cleanup' blocked by [policy]"));
+    assert!(error.contains("binary 'rm' is in [policy] denied_binaries"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_scenario_run_is_checked_against_test_and_scenario_policy() {
+    let mut config = parse_toml_config(
+        r#"
+[meta]
+name = "policy"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[service.api]
+plugin = "generic_container"
+image = "alpine:3.20"
+
+[[scenario]]
+name = "cleanup"
+service = "api"
+run = "timeout 5 rm -rf /data"
+continue_on_failure = true
+
+[[scenario]]
+name = "fetch"
+service = "api"
+run = "curl http://api"
+policy = { allowed_binaries = ["echo"] }
+"#,
+    )
+    .expect("config parses");
+    // Policy is checked before the scenario's service is looked up
+    config.service = None;
+    let stages = config.scenario_stages().expect("stages");
+    let env = CleanroomEnvironment::default();
+
+    let mut results = Vec::new();
+    let error = execute_scenarios(&stages, &env, &HashMap::new(), &config, &mut results)
+        .await
+        .expect_err("policy error");
+
+    assert!(error
+        .to_string()
+        .contains("Scenario 'cleanup' blocked by [policy]: Command 'timeout 5 rm -rf /data'"));
+    let fetch = results
+        .iter()
+        .find(|result| result.source == "fetch")
+        .expect("fetch result");
+    assert!(fetch
+        .stderr
+        .contains("binary 'curl' is not in [policy] allowed_binaries (echo)"));
+}
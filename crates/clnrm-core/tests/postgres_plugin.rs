@@ -0,0 +1,123 @@
+//! postgres services and their init SQL
+//!
+//! Tests that start PostgreSQL need a reachable Docker API and return early
+//! without one.
+
+mod common;
+
+use clnrm_core::backend::runtime::{ContainerBackend, DockerBackend};
+use clnrm_core::cleanroom::ServicePlugin;
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::error::ErrorKind;
+use clnrm_core::services::postgres::PostgresPlugin;
+use common::{meta, on_backend, write_file, write_test};
+
+/// Whether containers can be started; prints why the calling test is skipped
+/// when they can't
+async fn docker_available() -> bool {
+    let reachable = match DockerBackend.client() {
+        Ok(client) => client.ping().await.is_ok(),
+        Err(_) => false,
+    };
+    if !reachable {
+        eprintln!("Docker API not reachable, skipping");
+    }
+    reachable
+}
+
+#[test]
+fn test_image_tag_is_split_after_the_registry_port() {
+    let cases = [
+        ("postgres:16-alpine", ("postgres", "16-alpine")),
+        ("postgres", ("postgres", "latest")),
+        (
+            "localhost:5000/postgres:16",
+            ("localhost:5000/postgres", "16"),
+        ),
+        (
+            "registry.local:5000/team/postgres",
+            ("registry.local:5000/team/postgres", "latest"),
+        ),
+    ];
+
+    for (image, expected) in cases {
+        let plugin = PostgresPlugin::new("db").with_image(image);
+        assert_eq!(plugin.image(), expected, "{}", image);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_init_sql_is_resolved_against_the_test_file() {
+    // Arrange: a missing init file fails before any container is started
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = write_test(
+        &dir.path().join("suite"),
+        "db",
+        &format!(
+            "{}\n[services.db]\nplugin = \"postgres\"\ninit_sql = \"schema.sql\"\n\n\
+             [[steps]]\nname = \"noop\"\ncommand = [\"true\"]\n",
+            meta("db")
+        ),
+    );
+
+    // Act
+    let result = run_test_file(&path, &on_backend("process")).await;
+
+    // Assert
+    let error = match result {
+        Ok(result) => result.error.expect("service fails to start"),
+        Err(e) => e.to_string(),
+    };
+    let expected = dir.path().join("suite").join("schema.sql");
+    assert!(
+        error.contains(&format!("init SQL file not found: {}", expected.display())),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failing_init_sql_is_a_service_error() {
+    if !docker_available().await {
+        return;
+    }
+
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let init_sql = write_file(dir.path(), "broken.sql", "SELECT * FROM missing_table;\n");
+    let plugin = PostgresPlugin::new("db").with_init_sql(&init_sql);
+
+    // Act
+    let error = plugin.start().expect_err("init SQL fails");
+
+    // Assert
+    assert!(matches!(error.kind, ErrorKind::ServiceError), "{:?}", error);
+    assert!(error.to_string().contains("init SQL failed"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_init_sql_is_applied_once_when_started_again() {
+    if !docker_available().await {
+        return;
+    }
+
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let init_sql = write_file(
+        dir.path(),
+        "schema.sql",
+        "CREATE TABLE applied (id SERIAL PRIMARY KEY);\nINSERT INTO applied DEFAULT VALUES;\n",
+    );
+    let plugin = PostgresPlugin::new("db").with_init_sql(&init_sql);
+    plugin.start().expect("postgres starts");
+
+    // Act
+    let handle = plugin.start().expect("running postgres is reused");
+
+    // Assert
+    let rows = plugin
+        .query(&handle, "SELECT count(*) FROM applied")
+        .expect("query runs");
+    assert_eq!(rows, [["1"]]);
+    plugin.stop(handle).expect("postgres stops");
+}
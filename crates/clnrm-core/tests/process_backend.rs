@@ -0,0 +1,145 @@
+//! The opt-in `process` backend, which runs steps on the host without Docker
+
+use clnrm_core::backend::runtime::{active_backend, backend_from_name, ContainerBackend};
+use clnrm_core::backend::{Backend, Cmd, HostBackend, ProcessBackend};
+use clnrm_core::config::CleanroomConfig;
+use clnrm_core::error::ErrorKind;
+use clnrm_core::CleanroomEnvironment;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[test]
+fn test_commands_run_in_a_scratch_directory_with_a_cleared_environment() {
+    let result = HostBackend
+        .run_cmd(
+            Cmd::new("sh")
+                .arg("-c")
+                .arg("pwd; echo \"home=$HOME\"; echo \"greeting=$GREETING\"; echo \"user=$USER\"")
+                .env("GREETING", "hello")
+                .workdir(PathBuf::from("/app")),
+        )
+        .expect("command runs");
+
+    assert!(result.success(), "{}", result.stderr);
+    assert_eq!(result.backend, "process");
+
+    let lines: Vec<&str> = result.stdout.lines().collect();
+    let sandbox = lines[1].strip_prefix("home=").expect("home line");
+    assert!(sandbox.contains("clnrm-process-"), "{}", sandbox);
+    assert!(lines[0].ends_with("/app"), "{}", lines[0]);
+    assert!(lines[0].starts_with(sandbox), "{}", lines[0]);
+    assert_eq!(lines[2], "greeting=hello");
+    assert_eq!(lines[3], "user=");
+
+    // The scratch directory is removed once the command is done
+    assert!(!std::path::Path::new(sandbox).exists());
+}
+
+#[test]
+fn test_workdir_cannot_leave_the_scratch_directory() {
+    let error = HostBackend
+        .run_cmd(Cmd::new("pwd").workdir(PathBuf::from("../../etc")))
+        .expect_err("workdir escapes the sandbox");
+
+    assert!(
+        error
+            .to_string()
+            .contains("leaves the process backend's scratch directory"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_failing_commands_report_their_exit_code() {
+    let result = HostBackend
+        .run_cmd(Cmd::new("sh").arg("-c").arg("echo broken >&2; exit 3"))
+        .expect("command runs");
+
+    assert_eq!(result.exit_code, 3);
+    assert_eq!(result.stderr.trim(), "broken");
+}
+
+#[test]
+fn test_commands_are_killed_at_their_timeout() {
+    let error = HostBackend
+        .run_cmd(
+            Cmd::new("sleep")
+                .arg("5")
+                .timeout(Duration::from_millis(100)),
+        )
+        .expect_err("sleep outlives its timeout");
+
+    assert!(
+        error.to_string().contains("timed out") && error.to_string().contains("limit: 100ms"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_process_backend_cannot_start_containers() {
+    let backend = backend_from_name("process").expect("process backend");
+    assert_eq!(backend.name(), "process");
+
+    let steps = backend.step_backend("alpine:latest").expect("step backend");
+    assert_eq!(steps.name(), "process");
+    assert!(!steps.supports_hermetic());
+
+    let error = ProcessBackend.connect().expect_err("no container runtime");
+    assert!(
+        error.to_string().contains("cannot start containers"),
+        "{}",
+        error
+    );
+
+    let error = HostBackend
+        .run_cmd(Cmd::new("true").network("clnrm-net"))
+        .expect_err("no isolated networks");
+    assert!(error.to_string().contains("isolated network"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_environment_runs_steps_without_docker() {
+    let mut config = CleanroomConfig::default();
+    config.containers.backend = "process".to_string();
+
+    let env = CleanroomEnvironment::with_config(Some(config))
+        .await
+        .expect("environment starts without Docker");
+    assert_eq!(env.backend().name(), "process");
+    assert_eq!(active_backend().name(), "process");
+
+    let result = env
+        .execute_in_container("step", &["echo".to_string(), "ok".to_string()])
+        .await
+        .expect("step runs on the host");
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(result.stdout.trim(), "ok");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_step_timeout_is_enforced_by_the_backend() {
+    let mut config = CleanroomConfig::default();
+    config.containers.backend = "process".to_string();
+    let env = CleanroomEnvironment::with_config(Some(config))
+        .await
+        .expect("environment starts without Docker");
+
+    let start = std::time::Instant::now();
+    let error = env
+        .execute_in_container_with_timeout(
+            "step",
+            &["sleep".to_string(), "5".to_string()],
+            Some(Duration::from_millis(200)),
+        )
+        .await
+        .expect_err("command times out");
+
+    assert_eq!(error.kind, ErrorKind::Timeout, "{}", error);
+    assert!(error.to_string().contains("timed out"), "{}", error);
+    assert!(
+        start.elapsed() < Duration::from_secs(4),
+        "command not killed"
+    );
+}
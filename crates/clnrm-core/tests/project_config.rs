@@ -0,0 +1,39 @@
+//! Finding the project's cleanroom.toml and reading its `[coverage]` section
+
+mod common;
+
+use clnrm_core::config::{find_project_config, load_coverage_config};
+use clnrm_core::error::Result;
+use common::write_file;
+
+#[test]
+fn test_project_config_is_found_from_a_subdirectory() -> Result<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let config = write_file(
+        dir.path(),
+        "cleanroom.toml",
+        "[coverage.weights]\napi_surface = 0.5\nstate_transitions = 0.1\nerror_scenarios = 0.1\n\
+         data_flows = 0.1\nintegrations = 0.1\nspan_coverage = 0.1\n",
+    );
+    let nested = dir.path().join("tests").join("api");
+    std::fs::create_dir_all(&nested).expect("create dir");
+
+    assert_eq!(find_project_config(&nested), Some(config.clone()));
+    assert_eq!(find_project_config(dir.path()), Some(config.clone()));
+
+    let weights = load_coverage_config(&config)?.weights;
+    assert_eq!(weights.api_surface, 0.5);
+    Ok(())
+}
+
+#[test]
+fn test_nearest_project_config_wins() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_file(dir.path(), "cleanroom.toml", "");
+    let inner = write_file(dir.path(), "service/cleanroom.toml", "");
+
+    assert_eq!(
+        find_project_config(&dir.path().join("service")),
+        Some(inner)
+    );
+}
@@ -0,0 +1,83 @@
+//! `clnrm pull --check` reporting local image presence
+//!
+//! A stub `docker` on `PATH` stands in for the daemon: it knows
+//! `alpine:3.19`, has never heard of anything else, and is unreachable
+//! when `STUB_DOCKER_DOWN` is set.
+
+mod common;
+
+use clnrm_core::cli::commands::check_images;
+use common::{meta, write_test};
+use std::os::unix::fs::PermissionsExt;
+
+const STUB_DOCKER: &str = r#"#!/bin/sh
+if [ -n "$STUB_DOCKER_DOWN" ]; then
+    echo "Cannot connect to the Docker daemon at unix:///var/run/docker.sock" >&2
+    exit 1
+fi
+for image; do :; done
+if [ "$image" = "alpine:3.19" ]; then
+    echo "sha256:0123"
+    exit 0
+fi
+echo "Error: No such image: $image" >&2
+exit 1
+"#;
+
+fn with_images(name: &str, images: &[(&str, &str)]) -> String {
+    let mut content = meta(name);
+    for (service, image) in images {
+        content.push_str(&format!(
+            "\n[services.{}]\nplugin = \"generic_container\"\nimage = \"{}\"\n",
+            service, image
+        ));
+    }
+    content
+}
+
+#[tokio::test]
+async fn test_check_reports_missing_images_without_pulling() {
+    let bin = tempfile::tempdir().expect("temp dir");
+    let docker = bin.path().join("docker");
+    std::fs::write(&docker, STUB_DOCKER).expect("write stub");
+    std::fs::set_permissions(&docker, std::fs::Permissions::from_mode(0o755)).expect("chmod");
+    std::env::set_var(
+        "PATH",
+        format!(
+            "{}:{}",
+            bin.path().display(),
+            std::env::var("PATH").unwrap_or_default()
+        ),
+    );
+
+    let tests = tempfile::tempdir().expect("temp dir");
+    write_test(
+        tests.path(),
+        "api",
+        &with_images("api", &[("db", "postgres:16"), ("cache", "alpine:3.19")]),
+    );
+    write_test(
+        tests.path(),
+        "worker",
+        &with_images("worker", &[("queue", "redis:7"), ("tools", "alpine:3.19")]),
+    );
+
+    let check = check_images(Some(vec![tests.path().to_path_buf()]))
+        .await
+        .expect("images checked");
+    assert_eq!(check.present, ["alpine:3.19"]);
+    assert_eq!(check.missing, ["postgres:16", "redis:7"]);
+
+    // An unreachable daemon is an error, not a missing image
+    std::env::set_var("STUB_DOCKER_DOWN", "1");
+    let error = check_images(Some(vec![tests.path().to_path_buf()]))
+        .await
+        .expect_err("daemon is down");
+    assert!(
+        error
+            .to_string()
+            .contains("Cannot connect to the Docker daemon"),
+        "{}",
+        error
+    );
+}
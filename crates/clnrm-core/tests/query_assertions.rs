@@ -0,0 +1,216 @@
+//! Database query assertions from `assertions::database` and
+//! `[[scenario.assertions]]`
+
+use clnrm_core::assertions::database::{assert_query_eq, assert_row_count, DatabaseService};
+use clnrm_core::cleanroom::{CleanroomEnvironment, HealthStatus, ServiceHandle, ServicePlugin};
+use clnrm_core::config::parse_toml_config;
+use clnrm_core::error::Result;
+use std::collections::HashMap;
+
+/// Database stand-in that answers every query with the same rows
+#[derive(Debug)]
+struct FixedRowsPlugin {
+    rows: Vec<Vec<String>>,
+}
+
+impl ServicePlugin for FixedRowsPlugin {
+    fn name(&self) -> &str {
+        "db"
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        Ok(ServiceHandle {
+            id: "db-1".to_string(),
+            service_name: "db".to_string(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        Ok(())
+    }
+
+    fn health_check(&self, _handle: &ServiceHandle) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+
+    fn query(&self, _handle: &ServiceHandle, _sql: &str) -> Result<Vec<Vec<String>>> {
+        Ok(self.rows.clone())
+    }
+}
+
+/// Service without query support
+#[derive(Debug)]
+struct CachePlugin;
+
+impl ServicePlugin for CachePlugin {
+    fn name(&self) -> &str {
+        "cache"
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        Ok(ServiceHandle {
+            id: "cache-1".to_string(),
+            service_name: "cache".to_string(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        Ok(())
+    }
+
+    fn health_check(&self, _handle: &ServiceHandle) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+}
+
+fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+    rows.iter()
+        .map(|row| row.iter().map(|value| value.to_string()).collect())
+        .collect()
+}
+
+async fn environment_with(plugin: Box<dyn ServicePlugin>) -> (CleanroomEnvironment, ServiceHandle) {
+    let env = CleanroomEnvironment::new().await.expect("environment");
+    let name = plugin.name().to_string();
+    env.register_service(plugin).await.expect("register plugin");
+    let handle = env.start_service(&name).await.expect("start service");
+    (env, handle)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_row_count_matches_selected_rows() {
+    let plugin = FixedRowsPlugin {
+        rows: rows(&[&["1"], &["2"], &["3"]]),
+    };
+    let (env, handle) = environment_with(Box::new(plugin)).await;
+    let service = DatabaseService::new(&env, &handle);
+
+    assert!(assert_row_count(&service, "SELECT id FROM users", 3)
+        .await
+        .is_ok());
+
+    let error = assert_row_count(&service, "SELECT id FROM users", 2)
+        .await
+        .expect_err("three rows, not two");
+    assert!(error
+        .to_string()
+        .contains("Expected 2 row(s) from 'SELECT id FROM users' on service 'db', got 3"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_eq_compares_single_value() {
+    let plugin = FixedRowsPlugin {
+        rows: rows(&[&["alice"]]),
+    };
+    let (env, handle) = environment_with(Box::new(plugin)).await;
+    let service = DatabaseService::new(&env, &handle);
+    let sql = "SELECT name FROM users WHERE id = 1";
+
+    assert!(assert_query_eq(&service, sql, "alice").await.is_ok());
+
+    let error = assert_query_eq(&service, sql, "bob")
+        .await
+        .expect_err("alice is not bob");
+    assert!(error.to_string().contains("Expected 'bob'"));
+    assert!(error.to_string().contains("got 'alice'"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_eq_rejects_multiple_values() {
+    let plugin = FixedRowsPlugin {
+        rows: rows(&[&["alice", "admin"], &["bob", "user"]]),
+    };
+    let (env, handle) = environment_with(Box::new(plugin)).await;
+    let service = DatabaseService::new(&env, &handle);
+
+    let error = assert_query_eq(&service, "SELECT name, role FROM users", "alice")
+        .await
+        .expect_err("not a single value");
+    assert!(error.to_string().contains("Expected a single value"));
+    assert!(error.to_string().contains("got 2 row(s)"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_on_service_without_sql_support_fails() {
+    let (env, handle) = environment_with(Box::new(CachePlugin)).await;
+    let service = DatabaseService::new(&env, &handle);
+
+    let error = assert_row_count(&service, "SELECT 1", 1)
+        .await
+        .expect_err("cache has no SQL");
+    assert!(error
+        .to_string()
+        .contains("Service 'cache' does not support SQL queries"));
+}
+
+#[test]
+fn test_scenario_assertions_are_parsed() {
+    let config = parse_toml_config(
+        r#"
+[meta]
+name = "migrations"
+version = "1.0"
+
+[service.db]
+plugin = "postgres"
+image = "postgres:15"
+
+[[scenario]]
+name = "migrate"
+service = "db"
+run = "psql -f /migrations/001.sql"
+
+[[scenario.assertions]]
+query = "SELECT id FROM users"
+row_count = 3
+
+[[scenario.assertions]]
+service = "db"
+query = "SELECT name FROM users WHERE id = 1"
+equals = "alice"
+"#,
+    )
+    .expect("valid config");
+
+    let assertions = &config.scenario[0].assertions;
+    assert_eq!(assertions.len(), 2);
+    assert_eq!(assertions[0].row_count, Some(3));
+    assert_eq!(assertions[0].service, None);
+    assert_eq!(assertions[1].equals.as_deref(), Some("alice"));
+    assert!(config.validate().is_ok());
+}
+
+#[test]
+fn test_scenario_assertion_needs_exactly_one_check() {
+    for checks in ["", "row_count = 1\nequals = \"1\""] {
+        let config = parse_toml_config(&format!(
+            r#"
+[meta]
+name = "migrations"
+version = "1.0"
+
+[[scenario]]
+name = "migrate"
+service = "db"
+run = "true"
+
+[[scenario.assertions]]
+query = "SELECT 1"
+{}
+"#,
+            checks
+        ))
+        .expect("parses");
+
+        let error = config.validate().expect_err("invalid assertion");
+        assert!(
+            error
+                .to_string()
+                .contains("Assertion must set exactly one of 'row_count' or 'equals'"),
+            "{}",
+            error
+        );
+    }
+}
@@ -0,0 +1,107 @@
+//! Redaction of span attributes recorded by `clnrm record`
+
+use clnrm_core::config::load_record_config;
+use clnrm_core::error::Result;
+use clnrm_core::otel::redact::{SpanRedactor, REDACTED};
+use clnrm_core::validation::span_validator::SpanData;
+use serde_json::json;
+
+fn http_span() -> SpanData {
+    serde_json::from_value(json!({
+        "name": "http.request",
+        "trace_id": "trace-1",
+        "span_id": "span-2",
+        "parent_span_id": "span-1",
+        "attributes": {
+            "http.request.header.authorization": "Bearer sk-abc123",
+            "http.url": "https://api.example.com/?key=sk-def456",
+            "http.method": "GET",
+            "http.status_code": 200
+        },
+        "events": [{
+            "name": "retry",
+            "attributes": { "Authorization": "Bearer sk-abc123" }
+        }]
+    }))
+    .expect("valid span")
+}
+
+#[test]
+fn test_redact_key_masks_namespaced_and_event_attributes() {
+    let mut span = http_span();
+    SpanRedactor::new()
+        .with_key("authorization")
+        .redact(&mut span);
+
+    assert_eq!(
+        span.attributes["http.request.header.authorization"],
+        json!(REDACTED)
+    );
+    assert_eq!(
+        span.events.as_ref().expect("events")[0].attributes["Authorization"],
+        json!(REDACTED)
+    );
+    assert_eq!(span.attributes["http.method"], json!("GET"));
+    assert_eq!(span.attributes["http.status_code"], json!(200));
+}
+
+#[test]
+fn test_redact_regex_masks_matches_and_keeps_structure() -> Result<()> {
+    let mut span = http_span();
+    SpanRedactor::new()
+        .with_pattern(r"sk-[a-z0-9]+")?
+        .redact(&mut span);
+
+    assert_eq!(
+        span.attributes["http.url"],
+        json!("https://api.example.com/?key=***")
+    );
+    assert_eq!(
+        span.attributes["http.request.header.authorization"],
+        json!("Bearer ***")
+    );
+    assert_eq!(span.name, "http.request");
+    assert_eq!(span.span_id, "span-2");
+    assert_eq!(span.parent_span_id.as_deref(), Some("span-1"));
+    Ok(())
+}
+
+#[test]
+fn test_record_redact_config_section() -> Result<()> {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("cleanroom.toml");
+    std::fs::write(
+        &path,
+        "[record.redact]\nkeys = [\"authorization\"]\npatterns = [\"sk-[a-z0-9]+\"]\n",
+    )
+    .expect("write cleanroom.toml");
+
+    let mut span = http_span();
+    load_record_config(&path)?
+        .redact
+        .redactor()?
+        .redact(&mut span);
+
+    assert_eq!(
+        span.attributes["http.request.header.authorization"],
+        json!(REDACTED)
+    );
+    assert_eq!(
+        span.attributes["http.url"],
+        json!("https://api.example.com/?key=***")
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_redact_pattern_fails_at_load() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("cleanroom.toml");
+    std::fs::write(&path, "[record.redact]\npatterns = [\"sk-(\"]\n")
+        .expect("write cleanroom.toml");
+
+    let error = load_record_config(&path).expect_err("invalid pattern rejected");
+    assert!(error
+        .to_string()
+        .contains("Invalid redaction pattern 'sk-('"));
+}
@@ -0,0 +1,90 @@
+//! `clnrm render --check` validating the rendered test config
+
+use clnrm_core::cli::commands::v0_7_0::prd_commands::render_template_with_vars;
+use clnrm_core::cli::types::RenderFormat;
+use std::path::{Path, PathBuf};
+
+fn render_checked(template: &Path, output: &PathBuf) -> clnrm_core::error::Result<()> {
+    render_template_with_vars(
+        template,
+        &["name=api".to_string()],
+        Some(output),
+        false,
+        &RenderFormat::Toml,
+        None,
+        None,
+        None,
+        true,
+    )
+}
+
+#[test]
+fn test_check_accepts_valid_rendered_config() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let template = dir.path().join("valid.toml.tera");
+    std::fs::write(
+        &template,
+        r#"[meta]
+name = "{{ name }}"
+version = "1.0"
+
+[[steps]]
+name = "hello"
+command = ["echo", "hello"]
+"#,
+    )
+    .expect("write template");
+    let output = dir.path().join("valid.toml");
+
+    render_checked(&template, &output).expect("valid config");
+    assert!(std::fs::read_to_string(&output)
+        .expect("rendered output")
+        .contains("name = \"api\""));
+}
+
+#[test]
+fn test_check_reports_broken_toml_with_rendered_line() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let template = dir.path().join("broken.toml.tera");
+    std::fs::write(
+        &template,
+        r#"[meta]
+name = "{{ name }}
+version = "1.0"
+"#,
+    )
+    .expect("write template");
+    let output = dir.path().join("broken.toml");
+
+    let error = render_checked(&template, &output).expect_err("broken TOML");
+    let message = error.to_string();
+    assert!(
+        message.contains("does not render to a valid test config"),
+        "{}",
+        message
+    );
+    assert!(message.contains("line 2: TOML syntax error"), "{}", message);
+    assert!(message.contains("2 | name = \"api"), "{}", message);
+    assert!(!output.exists(), "nothing is written when the check fails");
+}
+
+#[test]
+fn test_check_reports_invalid_config_shape() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let template = dir.path().join("shape.toml.tera");
+    std::fs::write(
+        &template,
+        r#"[meta]
+name = "{{ name }}"
+version = "1.0"
+
+[[steps]]
+name = "hello"
+"#,
+    )
+    .expect("write template");
+
+    let error =
+        render_checked(&template, &dir.path().join("shape.toml")).expect_err("step has no command");
+    assert!(error.to_string().contains("5 | [[steps]]"), "{}", error);
+}
@@ -0,0 +1,120 @@
+//! `clnrm render --dir` over a directory of templates
+
+use clnrm_core::cli::commands::render_template_dir;
+use std::path::Path;
+
+fn write_template(dir: &Path, name: &str, content: &str) {
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().expect("parent dir")).expect("create dir");
+    std::fs::write(path, content).expect("write template");
+}
+
+const TEMPLATE: &str = r#"[meta]
+name = "{{ name }}"
+version = "1.0"
+
+[[steps]]
+name = "hello"
+command = ["echo", "{{ greeting }}"]
+"#;
+
+#[test]
+fn test_every_template_is_rendered_with_shared_vars() {
+    let templates = tempfile::tempdir().expect("temp dir");
+    let generated = tempfile::tempdir().expect("temp dir");
+    write_template(
+        templates.path(),
+        "login.clnrm.toml.tera",
+        &TEMPLATE.replace("{{ name }}", "login"),
+    );
+    write_template(
+        templates.path(),
+        "orders/checkout.clnrm.toml.tera",
+        &TEMPLATE.replace("{{ name }}", "checkout"),
+    );
+    write_template(templates.path(), "README.md", "not a template");
+
+    let rendered = render_template_dir(
+        templates.path(),
+        Some(generated.path()),
+        &["greeting=hi".to_string()],
+        false,
+        None,
+        None,
+        None,
+        true,
+    )
+    .expect("templates render");
+
+    assert_eq!(
+        rendered,
+        [
+            generated.path().join("login.clnrm.toml"),
+            generated.path().join("orders").join("checkout.clnrm.toml"),
+        ]
+    );
+    let checkout = std::fs::read_to_string(&rendered[1]).expect("read output");
+    assert!(checkout.contains("name = \"checkout\""), "{}", checkout);
+    assert!(checkout.contains(r#"["echo", "hi"]"#), "{}", checkout);
+    assert!(!generated.path().join("README.md").exists());
+}
+
+#[test]
+fn test_output_defaults_to_template_directory() {
+    let templates = tempfile::tempdir().expect("temp dir");
+    write_template(templates.path(), "login.clnrm.toml.tera", TEMPLATE);
+
+    let rendered = render_template_dir(
+        templates.path(),
+        None,
+        &["name=login".to_string(), "greeting=hi".to_string()],
+        false,
+        None,
+        None,
+        None,
+        false,
+    )
+    .expect("templates render");
+
+    assert_eq!(rendered, [templates.path().join("login.clnrm.toml")]);
+}
+
+#[test]
+fn test_first_failing_template_is_named() {
+    let templates = tempfile::tempdir().expect("temp dir");
+    let generated = tempfile::tempdir().expect("temp dir");
+    write_template(templates.path(), "a.clnrm.toml.tera", TEMPLATE);
+    write_template(
+        templates.path(),
+        "b.clnrm.toml.tera",
+        "[meta]\nname = \"{{ missing }}\"\n",
+    );
+
+    let error = render_template_dir(
+        templates.path(),
+        Some(generated.path()),
+        &["name=a".to_string(), "greeting=hi".to_string()],
+        false,
+        None,
+        None,
+        None,
+        false,
+    )
+    .expect_err("b fails to render");
+
+    assert!(error.to_string().contains("b.clnrm.toml.tera"), "{}", error);
+}
+
+#[test]
+fn test_empty_directory_is_an_error() {
+    let templates = tempfile::tempdir().expect("temp dir");
+
+    let error = render_template_dir(templates.path(), None, &[], false, None, None, None, false)
+        .expect_err("nothing to render");
+
+    assert!(
+        error.to_string().contains("No *.clnrm.toml.tera"),
+        "{}",
+        error
+    );
+}
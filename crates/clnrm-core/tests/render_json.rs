@@ -0,0 +1,83 @@
+//! `clnrm render --as json` exports the resolved test config
+
+use clnrm_core::cli::commands::render_template_with_vars;
+use clnrm_core::cli::types::RenderFormat;
+use clnrm_core::error::ErrorCode;
+use std::path::Path;
+
+fn render_json(dir: &Path, template: &str) -> clnrm_core::error::Result<serde_json::Value> {
+    let path = dir.join("test.clnrm.toml.tera");
+    std::fs::write(&path, template).expect("write template");
+    let output = dir.join("test.json");
+    render_template_with_vars(
+        &path,
+        &["name=json_export".to_string()],
+        Some(&output),
+        false,
+        &RenderFormat::Json,
+        None,
+        None,
+        None,
+        false,
+    )?;
+    let json = std::fs::read_to_string(&output).expect("rendered output");
+    Ok(serde_json::from_str(&json).expect("output is JSON"))
+}
+
+#[test]
+fn test_json_export_round_trips_full_config() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let json = render_json(
+        dir.path(),
+        r#"
+[meta]
+name = "{{ name }}"
+version = "1.0"
+
+[services.app]
+plugin = "generic_container"
+image = "alpine:3.19"
+
+[[scenario]]
+name = "first"
+service = "app"
+run = "echo one"
+
+[[scenario]]
+name = "second"
+service = "app"
+run = "echo two"
+
+[[expect.span]]
+name = "clnrm.run"
+kind = "internal"
+"#,
+    )
+    .expect("template renders");
+
+    assert_eq!(json["meta"]["name"], "json_export");
+    assert_eq!(json["scenario"].as_array().map(|a| a.len()), Some(2));
+    assert_eq!(json["scenario"][1]["run"], "echo two");
+    assert_eq!(json["expect"]["span"][0]["name"], "clnrm.run");
+}
+
+#[test]
+fn test_json_export_rejects_invalid_configs() {
+    let dir = tempfile::tempdir().expect("temp dir");
+
+    let error = render_json(
+        dir.path(),
+        "[meta]\nname = \"{{ name }}\"\nversion = \"1.0\"\nbroken = \n",
+    )
+    .expect_err("invalid TOML");
+    assert_eq!(error.code, Some(ErrorCode::TomlParse));
+    assert!(error.to_string().contains("line 4"), "{}", error);
+
+    // Parses, but a scenario without a run command or steps can't run
+    let error = render_json(
+        dir.path(),
+        "[meta]\nname = \"{{ name }}\"\nversion = \"1.0\"\n\n[[scenario]]\nname = \"empty\"\n",
+    )
+    .expect_err("invalid config");
+    assert_eq!(error.code, Some(ErrorCode::InvalidConfig), "{}", error);
+}
@@ -0,0 +1,97 @@
+//! Typed `clnrm render --map` variables
+
+use clnrm_core::cli::commands::v0_7_0::prd_commands::{
+    parse_var_mapping, render_template_with_vars,
+};
+use clnrm_core::cli::types::RenderFormat;
+use serde_json::json;
+
+#[test]
+fn test_equals_maps_a_string() {
+    assert_eq!(
+        parse_var_mapping("name=db").expect("valid mapping"),
+        ("name".to_string(), json!("db"))
+    );
+    assert_eq!(
+        parse_var_mapping("port=8080").expect("valid mapping"),
+        ("port".to_string(), json!("8080"))
+    );
+    assert_eq!(
+        parse_var_mapping("url=a:=b").expect("valid mapping"),
+        ("url".to_string(), json!("a:=b"))
+    );
+}
+
+#[test]
+fn test_colon_equals_maps_a_json_value() {
+    assert_eq!(
+        parse_var_mapping("port:=8080").expect("valid mapping"),
+        ("port".to_string(), json!(8080))
+    );
+    assert_eq!(
+        parse_var_mapping("debug:=true").expect("valid mapping"),
+        ("debug".to_string(), json!(true))
+    );
+    assert_eq!(
+        parse_var_mapping("ports:=[8080,8081]").expect("valid mapping"),
+        ("ports".to_string(), json!([8080, 8081]))
+    );
+}
+
+#[test]
+fn test_invalid_mappings_are_rejected() {
+    let error = parse_var_mapping("name").expect_err("no value");
+    assert!(
+        error
+            .to_string()
+            .contains("expected key=value or key:=json"),
+        "{}",
+        error
+    );
+
+    let error = parse_var_mapping("ports:=[8080,").expect_err("bad JSON");
+    assert!(
+        error
+            .to_string()
+            .contains("Invalid JSON value for variable 'ports'"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_template_iterates_array_variable() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let template = dir.path().join("ports.toml.tera");
+    std::fs::write(
+        &template,
+        r#"[meta]
+name = "{{ name }}"
+version = "1.0"
+{% for port in ports %}
+[[steps]]
+name = "probe_{{ port + 1 }}"
+command = ["nc", "-z", "localhost", "{{ port }}"]
+{% endfor %}"#,
+    )
+    .expect("write template");
+    let output = dir.path().join("ports.toml");
+
+    render_template_with_vars(
+        &template,
+        &["name=db".to_string(), "ports:=[8080,8081]".to_string()],
+        Some(&output),
+        false,
+        &RenderFormat::Toml,
+        None,
+        None,
+        None,
+        true,
+    )
+    .expect("template renders");
+
+    let rendered = std::fs::read_to_string(&output).expect("rendered output");
+    assert!(rendered.contains("name = \"db\""), "{}", rendered);
+    assert!(rendered.contains("name = \"probe_8081\""), "{}", rendered);
+    assert!(rendered.contains("name = \"probe_8082\""), "{}", rendered);
+}
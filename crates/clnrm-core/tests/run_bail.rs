@@ -0,0 +1,154 @@
+//! `clnrm run --bail N` stops after N failed tests
+
+mod common;
+
+use clnrm_core::cli::commands::run::{
+    run_tests, run_tests_parallel_with_results, run_tests_sequential_with_results,
+};
+use clnrm_core::cli::types::CliConfig;
+use common::{meta, on_backend, write_test};
+use std::path::{Path, PathBuf};
+
+/// A test whose only step runs `command`, e.g. `true` or `false`
+fn runs(name: &str, command: &str) -> String {
+    format!(
+        "{}\n[[steps]]\nname = \"run\"\ncommand = [\"{}\"]\n",
+        meta(name),
+        command
+    )
+}
+
+/// Tests in `dir` where `a`, `c` and `d` fail
+fn write_tests(dir: &Path) -> Vec<PathBuf> {
+    vec![
+        write_test(dir, "a", &runs("a", "false")),
+        write_test(dir, "b", &runs("b", "true")),
+        write_test(dir, "c", &runs("c", "false")),
+        write_test(dir, "d", &runs("d", "false")),
+    ]
+}
+
+/// Failing tests in `dir` whose first step creates `<name>.ran` in `dir`,
+/// so a test that started leaves a marker
+fn write_marked_failing_tests(dir: &Path) -> Vec<PathBuf> {
+    ["a", "b", "c"]
+        .iter()
+        .map(|name| {
+            let marker = dir.join(format!("{}.ran", name));
+            let content = format!(
+                "{}\n[[steps]]\nname = \"mark\"\ncommand = [\"touch\", \"{}\"]\n\n[[steps]]\nname = \"fail\"\ncommand = [\"false\"]\n",
+                meta(name),
+                marker.display()
+            );
+            write_test(dir, name, &content)
+        })
+        .collect()
+}
+
+/// Parallel options running one test at a time and bailing after the first
+/// failure
+fn one_job_bail_after_one() -> CliConfig {
+    CliConfig {
+        parallel: true,
+        jobs: 1,
+        bail: Some(1),
+        ..on_backend("process")
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sequential_run_stops_after_bail_failures() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let paths = write_tests(dir.path());
+    let config = CliConfig {
+        bail: Some(2),
+        ..on_backend("process")
+    };
+
+    // Act
+    let results = run_tests_sequential_with_results(&paths, &config)
+        .await
+        .expect("tests run");
+
+    // Assert
+    let outcomes: Vec<(&str, bool)> = results
+        .iter()
+        .map(|r| (r.name.as_str(), r.passed))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            ("a.clnrm.toml", false),
+            ("b.clnrm.toml", true),
+            ("c.clnrm.toml", false)
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sequential_run_without_bail_runs_every_test() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let paths = write_tests(dir.path());
+
+    // Act
+    let results = run_tests_sequential_with_results(&paths, &on_backend("process"))
+        .await
+        .expect("tests run");
+
+    // Assert
+    assert_eq!(results.len(), 4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parallel_run_schedules_no_tests_after_bail_failures() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let paths = write_marked_failing_tests(dir.path());
+
+    // Act
+    let results = run_tests_parallel_with_results(&paths, &one_job_bail_after_one())
+        .await
+        .expect("tests run");
+
+    // Assert
+    let outcomes: Vec<(&str, bool)> = results
+        .iter()
+        .map(|r| (r.name.as_str(), r.passed))
+        .collect();
+    assert_eq!(outcomes, [("a.clnrm.toml", false)]);
+    assert!(dir.path().join("a.ran").exists());
+    assert!(!dir.path().join("b.ran").exists(), "b started after bail");
+    assert!(!dir.path().join("c.ran").exists(), "c started after bail");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_parallel_run_reports_stopping_at_bail() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_marked_failing_tests(dir.path());
+    let config = CliConfig {
+        force: true,
+        ..one_job_bail_after_one()
+    };
+
+    // Act
+    let error = run_tests(&[dir.path().to_path_buf()], &config)
+        .await
+        .expect_err("a failed");
+
+    // Assert
+    assert!(
+        error
+            .to_string()
+            .contains("1 test(s) failed, stopped after 1 failures (bail)"),
+        "{}",
+        error
+    );
+    let started = ["a", "b", "c"]
+        .iter()
+        .filter(|name| dir.path().join(format!("{}.ran", name)).exists())
+        .count();
+    assert_eq!(started, 1, "no test starts after the first failure");
+}
@@ -0,0 +1,134 @@
+//! `clnrm run` skips unchanged tests until an image they use changes
+
+mod common;
+
+use clnrm_core::cache::CacheManager;
+use clnrm_core::cli::commands::run::{filter_changed_tests, update_cache_for_results};
+use clnrm_core::cli::types::{CliConfig, CliTestResult};
+use clnrm_core::config::CleanroomConfig;
+use common::{meta, write_test};
+use std::path::{Path, PathBuf};
+
+/// Options running steps in `default_image`
+fn with_default_image(default_image: &str) -> CliConfig {
+    let mut cleanroom = CleanroomConfig::default();
+    cleanroom.containers.default_image = default_image.to_string();
+    CliConfig {
+        cleanroom: Some(cleanroom),
+        ..CliConfig::default()
+    }
+}
+
+/// A test whose only service runs `image`
+fn service_test(name: &str, image: &str) -> String {
+    format!(
+        r#"{}
+[services.db]
+plugin = "generic_container"
+image = "{}"
+
+[[scenario]]
+name = "ping"
+service = "db"
+run = "true"
+"#,
+        meta(name),
+        image
+    )
+}
+
+/// A test with steps, which run in the default image
+fn steps_test(name: &str) -> String {
+    format!(
+        "{}\n[[steps]]\nname = \"noop\"\ncommand = [\"true\"]\n",
+        meta(name)
+    )
+}
+
+/// A test whose only service runs the built-in postgres image and has no
+/// commands of its own
+fn idle_service_test(name: &str) -> String {
+    format!("{}\n[services.db]\nplugin = \"postgres\"\n", meta(name))
+}
+
+/// Tests in `dir`: one with steps and two with a service each
+fn write_tests(dir: &Path) -> Vec<PathBuf> {
+    vec![
+        write_test(dir, "steps", &steps_test("steps")),
+        write_test(dir, "db", &service_test("db", "postgres:15")),
+        write_test(dir, "cache", &service_test("cache", "redis:7")),
+    ]
+}
+
+/// Cache every test in `tests` as passed under `config`
+async fn cache_passed(tests: &[PathBuf], cache: &CacheManager, config: &CliConfig) {
+    let results: Vec<CliTestResult> = tests
+        .iter()
+        .map(|path| CliTestResult {
+            name: path.display().to_string(),
+            path: path.clone(),
+            passed: true,
+            duration_ms: 1,
+            error: None,
+            steps: Vec::new(),
+        })
+        .collect();
+    update_cache_for_results(&results, cache, config)
+        .await
+        .expect("cache updated");
+}
+
+#[tokio::test]
+async fn test_unchanged_tests_are_skipped() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache = CacheManager::with_path(dir.path().join("cache.json")).expect("cache");
+    let tests = write_tests(dir.path());
+    let config = with_default_image("alpine:3.19");
+    cache_passed(&tests, &cache, &config).await;
+
+    // Act
+    let changed = filter_changed_tests(&tests, &cache, &config)
+        .await
+        .expect("tests filtered");
+
+    // Assert
+    assert!(changed.is_empty(), "{:?}", changed);
+}
+
+#[tokio::test]
+async fn test_changed_default_image_reruns_tests_with_steps_or_scenarios() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache = CacheManager::with_path(dir.path().join("cache.json")).expect("cache");
+    let mut tests = write_tests(dir.path());
+    tests.push(write_test(dir.path(), "idle", &idle_service_test("idle")));
+    cache_passed(&tests, &cache, &with_default_image("alpine:3.19")).await;
+
+    // Act
+    let changed = filter_changed_tests(&tests, &cache, &with_default_image("alpine:3.20"))
+        .await
+        .expect("tests filtered");
+
+    // Assert: scenario commands run in the default image too
+    assert_eq!(changed, tests[..3]);
+}
+
+#[tokio::test]
+async fn test_changed_service_image_reruns_only_the_test_using_it() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache = CacheManager::with_path(dir.path().join("cache.json")).expect("cache");
+    let tests = write_tests(dir.path());
+    let config = with_default_image("alpine:3.19");
+    cache_passed(&tests, &cache, &config).await;
+
+    // Act
+    write_test(dir.path(), "db", &service_test("db", "postgres:16"));
+    let changed = filter_changed_tests(&tests, &cache, &config)
+        .await
+        .expect("tests filtered");
+
+    // Assert
+    assert_eq!(changed, [tests[1].clone()]);
+}
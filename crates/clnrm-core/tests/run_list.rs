@@ -0,0 +1,164 @@
+//! `clnrm run --list` execution plan
+
+mod common;
+
+use clnrm_core::cli::commands::run::{plan_run, run_tests, ScenarioFilter};
+use clnrm_core::cli::types::CliConfig;
+use clnrm_core::error::ErrorKind;
+use common::{meta, write_test};
+
+fn with_scenarios(name: &str, scenarios: &[&str]) -> String {
+    let mut content = meta(name);
+    for scenario in scenarios {
+        content.push_str(&format!(
+            "\n[[scenario]]\nname = \"{}\"\nservice = \"app\"\nrun = \"true\"\n",
+            scenario
+        ));
+    }
+    content
+}
+
+fn forced() -> CliConfig {
+    CliConfig {
+        force: true,
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn test_plan_lists_files_with_scenario_counts() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_test(dir.path(), "a", &with_scenarios("a", &["setup", "check"]));
+    write_test(dir.path(), "b", &with_scenarios("b", &["only"]));
+
+    let plan = plan_run(&[dir.path().to_path_buf()], &forced(), None)
+        .await
+        .expect("plan resolves");
+
+    let mut counts: Vec<(String, usize)> = plan
+        .tests
+        .iter()
+        .map(|t| {
+            let name = t.path.file_name().unwrap().to_string_lossy().to_string();
+            (name, t.scenarios)
+        })
+        .collect();
+    counts.sort();
+    assert_eq!(
+        counts,
+        vec![
+            ("a.clnrm.toml".to_string(), 2),
+            ("b.clnrm.toml".to_string(), 1)
+        ]
+    );
+    assert!(plan.cache_skipped.is_empty());
+
+    let rendered = plan.render();
+    assert!(
+        rendered.starts_with("Run plan: 2 file(s)\n"),
+        "{}",
+        rendered
+    );
+    assert!(
+        rendered.contains("a.clnrm.toml (2 scenario(s))"),
+        "{}",
+        rendered
+    );
+}
+
+#[tokio::test]
+async fn test_plan_applies_sharding() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    for file in ["a", "b", "c", "d", "e"] {
+        write_test(dir.path(), file, &with_scenarios(file, &["s"]));
+    }
+
+    let paths = [dir.path().to_path_buf()];
+    let mut sharded = Vec::new();
+    for index in 1..=4 {
+        let plan = plan_run(&paths, &forced(), Some((index, 4)))
+            .await
+            .expect("plan resolves");
+        assert_eq!(plan.tests.len() + plan.other_shards, 5);
+        assert!(plan.render().starts_with(&format!(
+            "Run plan for shard {}/4: {} file(s)",
+            index,
+            plan.tests.len()
+        )));
+        assert!(plan.render().contains(&format!(
+            "Assigned to the other 3 shard(s): {} file(s)",
+            plan.other_shards
+        )));
+        sharded.extend(plan.tests.into_iter().map(|t| t.path));
+    }
+
+    sharded.sort();
+    sharded.dedup();
+    assert_eq!(sharded.len(), 5, "every file lands in exactly one shard");
+}
+
+#[tokio::test]
+async fn test_plan_counts_only_filtered_scenarios() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_test(
+        dir.path(),
+        "a",
+        &with_scenarios("a", &["smoke_login", "smoke_logout", "load"]),
+    );
+
+    let config = CliConfig {
+        scenario_filter: Some("smoke_.*".to_string()),
+        ..forced()
+    };
+    let plan = plan_run(&[dir.path().to_path_buf()], &config, None)
+        .await
+        .expect("plan resolves");
+
+    assert_eq!(plan.tests[0].scenarios, 2);
+}
+
+#[tokio::test]
+async fn test_invalid_scenario_pattern_is_a_configuration_error() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_test(dir.path(), "a", &with_scenarios("a", &["smoke_login"]));
+
+    let config = CliConfig {
+        scenario_filter: Some("smoke_(login".to_string()),
+        ..forced()
+    };
+    let error = plan_run(&[dir.path().to_path_buf()], &config, None)
+        .await
+        .expect_err("invalid pattern");
+    assert_eq!(error.kind, ErrorKind::ConfigurationError, "{}", error);
+    assert!(error.message.contains("smoke_(login"), "{}", error);
+
+    let error = run_tests(&[dir.path().to_path_buf()], &config)
+        .await
+        .expect_err("invalid pattern");
+    assert_eq!(error.kind, ErrorKind::ConfigurationError, "{}", error);
+}
+
+#[test]
+fn test_scenario_filter_matches_whole_names_and_data_cases() {
+    let filter = ScenarioFilter::new("smoke_.*").expect("valid pattern");
+    assert!(filter.matches("smoke_login"));
+    assert!(filter.matches("smoke_login[2]"));
+    assert!(!filter.matches("load_smoke_login"));
+
+    let filter = ScenarioFilter::new("login").expect("valid pattern");
+    assert!(filter.matches("login"));
+    assert!(!filter.matches("login_admin"));
+}
+
+#[tokio::test]
+async fn test_plan_reports_invalid_files_without_failing() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    std::fs::write(dir.path().join("broken.clnrm.toml"), "[meta\nname =").expect("write");
+
+    let plan = plan_run(&[dir.path().to_path_buf()], &forced(), None)
+        .await
+        .expect("plan resolves");
+
+    assert!(plan.tests[0].error.is_some());
+    assert!(plan.render().contains("broken.clnrm.toml (invalid: "));
+}
@@ -0,0 +1,111 @@
+//! `clnrm run --repeat N` pass rates and flakiness detection
+
+use clnrm_core::cli::commands::run::{run_tests_repeated, FlakinessReport};
+use clnrm_core::cli::types::{CliConfig, CliTestResult};
+
+fn result(name: &str, passed: bool) -> CliTestResult {
+    CliTestResult {
+        name: name.to_string(),
+        path: name.into(),
+        passed,
+        duration_ms: 1,
+        error: (!passed).then(|| "boom".to_string()),
+        steps: Vec::new(),
+    }
+}
+
+fn results(name: &str, passed: usize, failed: usize) -> Vec<CliTestResult> {
+    std::iter::repeat_with(|| result(name, true))
+        .take(passed)
+        .chain(std::iter::repeat_with(|| result(name, false)).take(failed))
+        .collect()
+}
+
+#[test]
+fn test_report_flags_flaky_and_failing_tests() {
+    let names = vec![
+        "flaky.toml".to_string(),
+        "stable.toml".to_string(),
+        "broken.toml".to_string(),
+    ];
+    let mut all = results("flaky.toml", 17, 3);
+    all.extend(results("stable.toml", 20, 0));
+    all.extend(results("broken.toml", 0, 20));
+
+    let report = FlakinessReport::from_results(&names, &all, 20);
+
+    assert_eq!(
+        report.render(),
+        "flaky.toml: 17/20 passed (FLAKY)\n\
+         stable.toml: 20/20 passed\n\
+         broken.toml: 0/20 passed (FAILING)\n"
+    );
+    assert!(report.tests[0].is_flaky());
+    assert!(!report.tests[1].is_flaky());
+    assert!(report.tests[2].is_failing());
+}
+
+#[test]
+fn test_flaky_tests_fail_above_threshold() {
+    let names = vec!["flaky.toml".to_string()];
+    let report = FlakinessReport::from_results(&names, &results("flaky.toml", 17, 3), 20);
+
+    let error = report.check(0.0).expect_err("flaky by default");
+    assert!(
+        error
+            .to_string()
+            .contains("1 flaky test(s) failed more than 0% of 20 runs: flaky.toml"),
+        "{}",
+        error
+    );
+
+    // 3 of 20 runs is a 15% failure rate
+    assert!(report.check(10.0).is_err());
+    assert!(report.check(15.0).is_ok());
+}
+
+#[test]
+fn test_missing_runs_count_as_failures() {
+    let names = vec!["bailed.toml".to_string()];
+    let report = FlakinessReport::from_results(&names, &results("bailed.toml", 2, 0), 5);
+
+    assert_eq!(report.render(), "bailed.toml: 2/5 passed (FLAKY)\n");
+}
+
+#[tokio::test]
+async fn test_repeated_run_reports_test_that_always_fails() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    // The only step is blocked by policy, so every run fails without Docker
+    std::fs::write(
+        dir.path().join("blocked.clnrm.toml"),
+        r#"
+[meta]
+name = "blocked"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[[steps]]
+name = "blocked"
+command = ["rm", "-rf", "/data"]
+"#,
+    )
+    .expect("write test");
+
+    let error = run_tests_repeated(
+        &[dir.path().to_path_buf()],
+        &CliConfig::default(),
+        None,
+        3,
+        0.0,
+    )
+    .await
+    .expect_err("test fails every run");
+
+    assert!(
+        error.to_string().contains("1 test(s) failed all 3 runs"),
+        "{}",
+        error
+    );
+}
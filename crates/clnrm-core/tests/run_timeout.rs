@@ -0,0 +1,113 @@
+//! `clnrm run --timeout` budget for the whole run
+
+use clnrm_core::cli::commands::run::run_tests_with_shard_and_report;
+use clnrm_core::cli::types::CliConfig;
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+/// Point Docker at a listener that accepts connections and never answers,
+/// so any test that reaches Docker hangs
+fn stall_docker() {
+    static STALLED: OnceLock<String> = OnceLock::new();
+    let host = STALLED.get_or_init(|| {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        let address = listener.local_addr().expect("listener address");
+        std::thread::spawn(move || {
+            let mut connections = Vec::new();
+            for stream in listener.incoming().flatten() {
+                connections.push(stream);
+            }
+        });
+        format!("tcp://{}", address)
+    });
+    std::env::set_var("DOCKER_HOST", host);
+}
+
+/// A test that finishes without Docker: its only step is blocked by policy
+fn write_quick_test(dir: &Path) {
+    std::fs::write(
+        dir.join("a_quick.clnrm.toml"),
+        r#"
+[meta]
+name = "quick"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+
+[[steps]]
+name = "blocked"
+command = ["rm", "-rf", "/data"]
+"#,
+    )
+    .expect("write quick test");
+}
+
+/// A test whose step needs Docker, which never answers
+fn write_hanging_test(dir: &Path) {
+    std::fs::write(
+        dir.join("b_hanging.clnrm.toml"),
+        r#"
+[meta]
+name = "hanging"
+version = "1.0"
+
+[[steps]]
+name = "stuck"
+command = ["echo", "hello"]
+"#,
+    )
+    .expect("write hanging test");
+}
+
+async fn run_with_timeout(parallel: bool) {
+    stall_docker();
+    let dir = tempfile::tempdir().expect("temp dir");
+    write_quick_test(dir.path());
+    write_hanging_test(dir.path());
+
+    let config = CliConfig {
+        parallel,
+        force: true,
+        timeout: Some(Duration::from_secs(2)),
+        ..Default::default()
+    };
+
+    let started = Instant::now();
+    let error = run_tests_with_shard_and_report(&[dir.path().to_path_buf()], &config, None, None)
+        .await
+        .expect_err("run times out");
+
+    assert!(
+        started.elapsed() < Duration::from_secs(30),
+        "took {:?}",
+        started.elapsed()
+    );
+    assert!(
+        error
+            .to_string()
+            .contains("run timed out after 2s (1 of 2 test(s) completed)"),
+        "{}",
+        error
+    );
+}
+
+/// The stalled Docker call keeps a blocking thread busy after the run gives
+/// up, so shut the runtime down without waiting for it, as the CLI's exit does
+fn block_on_run(parallel: bool) {
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    runtime.block_on(run_with_timeout(parallel));
+    runtime.shutdown_background();
+}
+
+#[test]
+fn test_sequential_run_stops_at_timeout_with_partial_results() {
+    block_on_run(false);
+}
+
+#[test]
+fn test_parallel_run_stops_at_timeout_with_partial_results() {
+    block_on_run(true);
+}
@@ -0,0 +1,191 @@
+//! Scenarios run in `depends_on` order, in declaration order within a stage,
+//! and stop at the first failure
+
+mod common;
+
+use clnrm_core::config::{parse_toml_config, TestConfig};
+use common::run_config;
+
+/// A scenario that fails before reaching its service: `rm` is denied
+fn scenario(name: &str, extra: &str) -> String {
+    format!(
+        r#"
+[[scenario]]
+name = "{}"
+service = "api"
+run = "rm -rf /data"
+{}
+"#,
+        name, extra
+    )
+}
+
+const HEADER: &str = r#"
+[meta]
+name = "order"
+version = "1.0"
+
+[policy]
+denied_binaries = ["rm"]
+"#;
+
+fn parse(scenarios: &[String]) -> TestConfig {
+    parse_toml_config(&format!("{}{}", HEADER, scenarios.concat())).expect("config parses")
+}
+
+fn stage_names(config: &TestConfig) -> Vec<Vec<&str>> {
+    config
+        .scenario_stages()
+        .expect("stages resolve")
+        .iter()
+        .map(|stage| stage.iter().map(|s| s.name.as_str()).collect())
+        .collect()
+}
+
+#[test]
+fn test_scenarios_are_staged_after_their_dependencies() {
+    let config = parse(&[
+        scenario("check", "depends_on = [\"migrate\", \"seed\"]"),
+        scenario("seed", "depends_on = [\"migrate\"]"),
+        scenario("migrate", ""),
+        scenario("lint", ""),
+    ]);
+
+    assert_eq!(
+        stage_names(&config),
+        [vec!["migrate", "lint"], vec!["seed"], vec!["check"]]
+    );
+}
+
+#[test]
+fn test_dependency_cycle_is_reported_with_its_path() {
+    let config = parse(&[
+        scenario("first", "depends_on = [\"third\"]"),
+        scenario("second", "depends_on = [\"first\"]"),
+        scenario("third", "depends_on = [\"second\"]"),
+        scenario("outside", ""),
+    ]);
+
+    let error = config.scenario_stages().expect_err("cycle");
+    assert!(
+        error
+            .to_string()
+            .contains("Scenario dependency cycle detected: first -> third -> second -> first"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_self_dependency_is_a_cycle() {
+    let config = parse(&[scenario("loop", "depends_on = [\"loop\"]")]);
+
+    let error = config.scenario_stages().expect_err("cycle");
+    assert!(error.to_string().contains("loop -> loop"), "{}", error);
+}
+
+#[test]
+fn test_unknown_dependency_is_rejected() {
+    let config = parse(&[
+        scenario("first", ""),
+        scenario("second", "depends_on = [\"frist\"]"),
+    ]);
+
+    let error = config.scenario_stages().expect_err("unknown dependency");
+    assert!(
+        error
+            .to_string()
+            .contains("Scenario 'second' depends on unknown scenario 'frist'"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dependency_cycle_fails_the_test_without_running_scenarios() {
+    let config = [
+        HEADER.to_string(),
+        scenario("first", "depends_on = [\"second\"]"),
+        scenario("second", "depends_on = [\"first\"]"),
+    ]
+    .concat();
+
+    let result = run_config(&config).await;
+
+    assert!(!result.passed);
+    assert!(result.steps.is_empty());
+    let error = result.error.expect("cycle error");
+    assert!(error.contains("first -> second -> first"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dependents_of_a_failed_scenario_are_skipped() {
+    let config = [
+        HEADER.to_string(),
+        scenario("setup", "continue_on_failure = true"),
+        scenario("use", "depends_on = [\"setup\"]"),
+        scenario("use_again", "depends_on = [\"use\"]"),
+        scenario("unrelated", "continue_on_failure = true"),
+    ]
+    .concat();
+
+    let result = run_config(&config).await;
+
+    assert!(!result.passed);
+    let outcomes: Vec<(&str, bool)> = result
+        .steps
+        .iter()
+        .map(|s| (s.source.as_str(), s.skipped))
+        .collect();
+    assert_eq!(
+        outcomes,
+        [
+            ("setup", false),
+            ("unrelated", false),
+            ("use", true),
+            ("use_again", true)
+        ]
+    );
+    let error = result.error.expect("scenario error");
+    assert!(error.contains("Scenario 'setup'"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_concurrent_scenarios_keep_their_declaration_order() {
+    let config = [
+        HEADER.to_string(),
+        scenario("first", "concurrent = true\ncontinue_on_failure = true"),
+        scenario("second", "continue_on_failure = true"),
+        scenario("third", "concurrent = true\ncontinue_on_failure = true"),
+        scenario("fourth", "concurrent = true\ncontinue_on_failure = true"),
+    ]
+    .concat();
+
+    let result = run_config(&config).await;
+
+    assert!(!result.passed);
+    let sources: Vec<&str> = result.steps.iter().map(|s| s.source.as_str()).collect();
+    assert_eq!(sources, ["first", "second", "third", "fourth"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_independent_scenarios_stop_after_a_failure() {
+    let config = [
+        HEADER.to_string(),
+        scenario("first", ""),
+        scenario("second", ""),
+    ]
+    .concat();
+
+    let result = run_config(&config).await;
+
+    assert!(!result.passed);
+    let outcomes: Vec<(&str, bool)> = result
+        .steps
+        .iter()
+        .map(|s| (s.source.as_str(), s.skipped))
+        .collect();
+    assert_eq!(outcomes, [("first", false), ("second", true)]);
+    let error = result.error.expect("scenario error");
+    assert!(error.contains("Scenario 'first'"), "{}", error);
+}
@@ -0,0 +1,62 @@
+//! `clnrm self-test --filter` selecting tests within suites
+
+use clnrm_core::cli::commands::self_test::run_self_tests;
+use clnrm_core::testing::run_framework_tests_filtered;
+use regex::Regex;
+
+#[tokio::test]
+async fn test_filter_runs_only_matching_tests() {
+    let filter = Regex::new("TOML").expect("valid regex");
+    let results = run_framework_tests_filtered(Some("framework"), Some(&filter))
+        .await
+        .expect("self-tests run");
+
+    let names: Vec<&str> = results
+        .test_results
+        .iter()
+        .map(|t| t.name.as_str())
+        .collect();
+    assert_eq!(names, ["TOML Config Parsing"]);
+    assert_eq!(results.total_tests, 1);
+    assert_eq!(results.passed_tests, 1);
+}
+
+#[tokio::test]
+async fn test_filter_matching_nothing_runs_nothing() {
+    let filter = Regex::new("^No Such Test$").expect("valid regex");
+    let results = run_framework_tests_filtered(None, Some(&filter))
+        .await
+        .expect("self-tests run");
+
+    assert_eq!(results.total_tests, 0);
+    assert!(results.test_results.is_empty());
+}
+
+#[tokio::test]
+async fn test_self_test_rejects_filter_without_matches() {
+    let error = run_self_tests(
+        Some("framework".to_string()),
+        Some("^No Such Test$".to_string()),
+        false,
+        "none".to_string(),
+        None,
+    )
+    .await
+    .expect_err("nothing to run");
+    assert!(
+        error
+            .to_string()
+            .contains("No self-tests match filter '^No Such Test$'"),
+        "{}",
+        error
+    );
+
+    let error = run_self_tests(None, Some("(".to_string()), false, "none".to_string(), None)
+        .await
+        .expect_err("invalid regex");
+    assert!(
+        error.to_string().contains("Invalid test filter '('"),
+        "{}",
+        error
+    );
+}
@@ -0,0 +1,60 @@
+//! Spans emitted by the framework self-test harness
+
+use clnrm_core::testing::run_framework_tests_filtered;
+use opentelemetry::trace::TracerProvider;
+use opentelemetry::Value;
+use opentelemetry_sdk::trace::{InMemorySpanExporter, SdkTracerProvider, SpanData};
+use regex::Regex;
+use tracing::instrument::WithSubscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::Registry;
+
+fn attribute<'a>(span: &'a SpanData, key: &str) -> Option<&'a Value> {
+    span.attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| &kv.value)
+}
+
+#[tokio::test]
+async fn test_self_test_run_is_a_trace_tree() {
+    let exporter = InMemorySpanExporter::default();
+    let provider = SdkTracerProvider::builder()
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = Registry::default().with(OpenTelemetryLayer::new(provider.tracer("test")));
+
+    let filter = Regex::new("TOML|Config Validation").expect("valid regex");
+    let results = run_framework_tests_filtered(Some("framework"), Some(&filter))
+        .with_subscriber(subscriber)
+        .await
+        .expect("self-tests run");
+    assert_eq!(results.total_tests, 2);
+
+    let spans = exporter.get_finished_spans().expect("finished spans");
+    let suite = spans
+        .iter()
+        .find(|s| s.name == "clnrm.self_test.suite")
+        .expect("suite span");
+    assert_eq!(
+        attribute(suite, "suite.name"),
+        Some(&Value::from("framework"))
+    );
+
+    let tests: Vec<&SpanData> = spans
+        .iter()
+        .filter(|s| s.name == "clnrm.self_test.test")
+        .collect();
+    assert_eq!(tests.len(), 2);
+    for test in &tests {
+        assert_eq!(test.parent_span_id, suite.span_context.span_id());
+        assert_eq!(test.span_context.trace_id(), suite.span_context.trace_id());
+        assert_eq!(attribute(test, "test.result"), Some(&Value::from("pass")));
+        assert!(test.end_time >= test.start_time);
+    }
+    assert_eq!(
+        attribute(tests[0], "test.name"),
+        Some(&Value::from("TOML Config Parsing"))
+    );
+}
@@ -0,0 +1,146 @@
+//! `ServicePlugin::ready_check` during `start_service`, and port readiness
+
+use clnrm_core::cleanroom::{
+    CleanroomEnvironment, HealthStatus, ServiceHandle, ServicePlugin, DEFAULT_READY_TIMEOUT,
+};
+use clnrm_core::error::{CleanroomError, Result};
+use clnrm_core::services::readiness::wait_for_port;
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Service that becomes ready after a number of readiness probes
+#[derive(Debug)]
+struct SlowPlugin {
+    probes_until_ready: Option<usize>,
+    probes: Arc<AtomicUsize>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl SlowPlugin {
+    fn new(probes_until_ready: Option<usize>) -> Self {
+        Self {
+            probes_until_ready,
+            probes: Arc::new(AtomicUsize::new(0)),
+            stopped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl ServicePlugin for SlowPlugin {
+    fn name(&self) -> &str {
+        "db"
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        Ok(ServiceHandle {
+            id: "db-1".to_string(),
+            service_name: "db".to_string(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        self.stopped.store(true, Ordering::SeqCst);
+        Ok(())
+    }
+
+    fn health_check(&self, _handle: &ServiceHandle) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+
+    fn ready_check(&self, _handle: &ServiceHandle, timeout: Duration) -> Result<()> {
+        assert_eq!(timeout, DEFAULT_READY_TIMEOUT);
+        loop {
+            let probes = self.probes.fetch_add(1, Ordering::SeqCst) + 1;
+            match self.probes_until_ready {
+                Some(needed) if probes >= needed => return Ok(()),
+                Some(_) => std::thread::sleep(Duration::from_millis(10)),
+                None => {
+                    return Err(CleanroomError::timeout_error(
+                        "Service 'db' was not ready within 30000ms",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+/// Listener that accepts connections and closes them at once, like Docker's
+/// proxy for a published port nothing listens on yet
+fn closing_listener() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().expect("listener address").port();
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            drop(stream);
+        }
+    });
+    port
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_start_service_returns_once_ready() {
+    let plugin = SlowPlugin::new(Some(3));
+    let probes = plugin.probes.clone();
+    let env = CleanroomEnvironment::new().await.expect("environment");
+    env.register_service(Box::new(plugin))
+        .await
+        .expect("register plugin");
+
+    env.start_service("db").await.expect("service starts");
+    assert_eq!(probes.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_service_that_never_becomes_ready_is_stopped() {
+    let plugin = SlowPlugin::new(None);
+    let stopped = plugin.stopped.clone();
+    let env = CleanroomEnvironment::new().await.expect("environment");
+    env.register_service(Box::new(plugin))
+        .await
+        .expect("register plugin");
+
+    let error = env.start_service("db").await.expect_err("never ready");
+    assert!(error.to_string().contains("was not ready"), "{}", error);
+    assert!(stopped.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_wait_for_port_accepts_listening_port() {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+    let port = listener.local_addr().expect("listener address").port();
+
+    assert!(wait_for_port("web", "127.0.0.1", port, Duration::from_secs(5)).is_ok());
+}
+
+#[test]
+fn test_wait_for_port_times_out_when_connections_are_dropped() {
+    let port = closing_listener();
+
+    let started = Instant::now();
+    let error = wait_for_port("web", "127.0.0.1", port, Duration::from_millis(500))
+        .expect_err("connections are closed straight away");
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(
+        error.to_string().contains(&format!(
+            "Service 'web' did not accept connections on 127.0.0.1:{} within 500ms",
+            port
+        )),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_wait_for_port_times_out_on_closed_port() {
+    let port = {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind listener");
+        listener.local_addr().expect("listener address").port()
+    };
+
+    assert!(wait_for_port("web", "127.0.0.1", port, Duration::from_millis(300)).is_err());
+}
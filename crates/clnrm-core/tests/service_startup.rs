@@ -0,0 +1,138 @@
+//! Services start concurrently within a `startup_order` stage and stage by
+//! stage across them
+
+mod common;
+
+use clnrm_core::cleanroom::{CleanroomEnvironment, HealthStatus, ServiceHandle, ServicePlugin};
+use clnrm_core::cli::commands::run::services::start_services;
+use clnrm_core::config::ServiceConfig;
+use clnrm_core::error::Result;
+use common::ServiceConfigBuilder;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// When a service's `start` began and returned
+#[derive(Debug, Clone, Copy)]
+struct Started {
+    began: Instant,
+    returned: Instant,
+}
+
+/// Service stand-in whose `start` blocks for `delay`
+#[derive(Debug)]
+struct SlowPlugin {
+    name: String,
+    delay: Duration,
+    started: Arc<Mutex<HashMap<String, Started>>>,
+}
+
+impl ServicePlugin for SlowPlugin {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn start(&self) -> Result<ServiceHandle> {
+        let began = Instant::now();
+        std::thread::sleep(self.delay);
+        let started = Started {
+            began,
+            returned: Instant::now(),
+        };
+        self.started
+            .lock()
+            .expect("start log")
+            .insert(self.name.clone(), started);
+        Ok(ServiceHandle {
+            id: format!("{}-1", self.name),
+            service_name: self.name.clone(),
+            metadata: HashMap::new(),
+        })
+    }
+
+    fn stop(&self, _handle: ServiceHandle) -> Result<()> {
+        Ok(())
+    }
+
+    fn health_check(&self, _handle: &ServiceHandle) -> HealthStatus {
+        HealthStatus::Healthy
+    }
+}
+
+/// Registers a [`SlowPlugin`] for each `(name, delay_ms, startup_order)` and
+/// returns the services' configuration with the shared start log
+async fn register_slow_services(
+    env: &CleanroomEnvironment,
+    services: &[(&str, u64, u32)],
+) -> (
+    HashMap<String, ServiceConfig>,
+    Arc<Mutex<HashMap<String, Started>>>,
+) {
+    let started = Arc::new(Mutex::new(HashMap::new()));
+    let mut configs = HashMap::new();
+    for &(name, delay_ms, startup_order) in services {
+        let plugin = SlowPlugin {
+            name: name.to_string(),
+            delay: Duration::from_millis(delay_ms),
+            started: started.clone(),
+        };
+        env.register_service(Box::new(plugin))
+            .await
+            .expect("register plugin");
+
+        let mut config = ServiceConfigBuilder::new("slow").build();
+        config.startup_order = Some(startup_order);
+        configs.insert(name.to_string(), config);
+    }
+    (configs, started)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_independent_services_start_in_the_time_of_the_slowest() {
+    // Arrange
+    let env = CleanroomEnvironment::new().await.expect("environment");
+    let (services, _) =
+        register_slow_services(&env, &[("a", 300, 0), ("b", 400, 0), ("c", 500, 0)]).await;
+    let begin = Instant::now();
+
+    // Act
+    let handles = start_services(&env, &services)
+        .await
+        .expect("services start");
+
+    // Assert
+    assert_eq!(handles.len(), 3);
+    let elapsed = begin.elapsed();
+    assert!(
+        elapsed < Duration::from_millis(900),
+        "started one after another in {:?}",
+        elapsed
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_startup_order_stages_run_one_after_another() {
+    // Arrange: the slower first stage must finish before the second begins
+    let env = CleanroomEnvironment::new().await.expect("environment");
+    let (services, started) = register_slow_services(
+        &env,
+        &[
+            ("db", 300, 0),
+            ("cache", 200, 0),
+            ("api", 0, 1),
+            ("web", 0, 2),
+        ],
+    )
+    .await;
+
+    // Act
+    start_services(&env, &services)
+        .await
+        .expect("services start");
+
+    // Assert
+    let started = started.lock().expect("start log");
+    assert!(started["api"].began >= started["db"].returned);
+    assert!(started["api"].began >= started["cache"].returned);
+    assert!(started["web"].began >= started["api"].returned);
+}
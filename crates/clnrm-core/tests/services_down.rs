@@ -0,0 +1,14 @@
+//! `clnrm services down` with no container runtime to reach
+
+use clnrm_core::cli::commands::services::services_down;
+
+// Sets DOCKER_HOST, so this is the only test in this file
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unreachable_runtime_means_nothing_to_remove() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let socket = dir.path().join("docker.sock");
+    std::env::set_var("DOCKER_HOST", format!("unix://{}", socket.display()));
+
+    services_down(false).await.expect("nothing to remove");
+    services_down(true).await.expect("nothing to remove");
+}
@@ -0,0 +1,110 @@
+//! Finding the containers `clnrm services exec` runs in and `services down` removes
+
+use clnrm_core::error::CleanroomError;
+use clnrm_core::services::keep::{
+    find_running_service, remove_each, short_container_id, KeptService,
+};
+
+fn kept(service_name: &str, container_id: &str, project: &str, running: bool) -> KeptService {
+    KeptService {
+        service_name: service_name.to_string(),
+        container_id: container_id.to_string(),
+        project: project.to_string(),
+        running,
+    }
+}
+
+fn here() -> String {
+    std::env::current_dir()
+        .expect("current dir")
+        .display()
+        .to_string()
+}
+
+#[test]
+fn test_running_service_from_this_directory_is_preferred() {
+    let services = vec![
+        kept("db", "stopped", &here(), false),
+        kept("db", "elsewhere", "/other/project", true),
+        kept("db", "local", &here(), true),
+        kept("cache", "cache", &here(), true),
+    ];
+
+    let found = find_running_service(&services, "db").expect("db is running");
+    assert_eq!(found.container_id, "local");
+
+    let services = &services[..2];
+    let found = find_running_service(services, "db").expect("db runs elsewhere");
+    assert_eq!(found.container_id, "elsewhere");
+}
+
+#[test]
+fn test_missing_service_lists_what_is_running() {
+    let services = vec![
+        kept("db", "stopped", &here(), false),
+        kept("cache", "a", &here(), true),
+        kept("api", "b", &here(), true),
+        kept("cache", "c", "/other/project", true),
+    ];
+
+    let error = find_running_service(&services, "db").expect_err("db is stopped");
+    assert!(
+        error
+            .to_string()
+            .contains("Service 'db' is not running (running services: api, cache)"),
+        "{}",
+        error
+    );
+
+    let error = find_running_service(&[], "db").expect_err("nothing runs");
+    assert!(
+        error
+            .to_string()
+            .contains("(no services are currently running)"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_removal_carries_on_past_failures() {
+    let services = vec![
+        kept("db", "db-1", &here(), true),
+        kept("cache", "cache-1", &here(), false),
+        kept("queue", "queue-1", &here(), true),
+    ];
+
+    let outcome = remove_each(services, |service| {
+        let stuck = service.service_name == "cache";
+        async move {
+            if stuck {
+                Err(CleanroomError::container_error("container is stuck"))
+            } else {
+                Ok(())
+            }
+        }
+    })
+    .await;
+
+    let removed: Vec<&str> = outcome
+        .removed
+        .iter()
+        .map(|service| service.service_name.as_str())
+        .collect();
+    assert_eq!(removed, ["db", "queue"]);
+    assert_eq!(outcome.failed.len(), 1);
+    assert_eq!(outcome.failed[0].0.container_id, "cache-1");
+    assert!(outcome.failed[0]
+        .1
+        .to_string()
+        .contains("container is stuck"));
+}
+
+#[test]
+fn test_container_ids_are_shown_abbreviated() {
+    let id = "4f9a1c2b3d4e5f60718293a4b5c6d7e8f9011223344556677889900aabbccdd";
+
+    assert_eq!(short_container_id(id), "4f9a1c2b3d4e");
+    assert_eq!(kept("db", id, &here(), true).short_id(), "4f9a1c2b3d4e");
+    assert_eq!(short_container_id("abc"), "abc");
+}
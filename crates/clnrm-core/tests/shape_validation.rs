@@ -0,0 +1,76 @@
+//! Shape validation reports every structural problem in one pass
+
+use clnrm_core::validation::{ErrorCategory, ShapeValidator};
+
+/// An unknown service key, a missing step command and a mistyped step timeout
+const THREE_PROBLEMS: &str = r#"[meta]
+name = "broken"
+version = "1.0"
+
+[services.db]
+plugin = "generic_container"
+image = "postgres:15"
+imagee = "postgres:16"
+
+[[steps]]
+name = "no_command"
+
+[[steps]]
+name = "slow"
+command = ["true"]
+timeout_ms = "soon"
+
+[[steps]]
+name = "fine"
+command = ["true"]
+"#;
+
+#[test]
+fn test_three_problems_are_reported_in_one_pass() {
+    // Act
+    let result = ShapeValidator::new().validate_content(THREE_PROBLEMS, "broken.clnrm.toml");
+
+    // Assert
+    assert!(!result.passed);
+    let found: Vec<(Option<usize>, &ErrorCategory)> = result
+        .errors
+        .iter()
+        .map(|e| (e.line, &e.category))
+        .collect();
+    assert_eq!(found.len(), 3, "{:#?}", result.errors);
+    assert_eq!(found[0], (Some(8), &ErrorCategory::UnknownKey));
+    assert_eq!(found[1], (Some(10), &ErrorCategory::MissingRequired));
+    assert_eq!(found[2], (Some(16), &ErrorCategory::InvalidStructure));
+    assert!(
+        result.errors[1].message.contains("command"),
+        "{:#?}",
+        result.errors
+    );
+    assert!(
+        result.errors[2]
+            .message
+            .contains("invalid type: string \"soon\""),
+        "{:#?}",
+        result.errors
+    );
+    assert_eq!(result.suppressed, 0);
+}
+
+#[test]
+fn test_max_errors_caps_the_reported_errors() {
+    // Act
+    let result = ShapeValidator::new()
+        .with_max_errors(2)
+        .validate_content(THREE_PROBLEMS, "broken.clnrm.toml");
+
+    // Assert
+    assert!(!result.passed);
+    assert_eq!(result.errors.len(), 2, "{:#?}", result.errors);
+    assert_eq!(result.suppressed, 1);
+    let lines: Vec<Option<usize>> = result.errors.iter().map(|e| e.line).collect();
+    assert_eq!(
+        lines,
+        [Some(8), Some(10)],
+        "the first errors in the file are kept"
+    );
+}
@@ -0,0 +1,69 @@
+//! `--shard-strategy duration` balances shards by recorded test durations
+
+mod common;
+
+use clnrm_core::cache::CacheManager;
+use clnrm_core::cli::commands::run::{shard_tests, update_cache_for_results};
+use clnrm_core::cli::types::{CliConfig, ShardStrategy};
+use common::failed_result;
+use std::path::{Path, PathBuf};
+
+/// Four tests in `dir`, two sharing a file name in different directories
+fn test_paths(dir: &Path) -> Vec<PathBuf> {
+    [
+        "api/slow.clnrm.toml",
+        "web/slow.clnrm.toml",
+        "a.clnrm.toml",
+        "b.clnrm.toml",
+    ]
+    .iter()
+    .map(|name| dir.join(name))
+    .collect()
+}
+
+/// Record `durations_ms` for `tests`, in order, in `cache`
+async fn record_durations(tests: &[PathBuf], durations_ms: &[u64], cache: &CacheManager) {
+    let results: Vec<_> = tests
+        .iter()
+        .zip(durations_ms)
+        .map(|(path, duration_ms)| failed_result(path, *duration_ms))
+        .collect();
+    update_cache_for_results(&results, cache, &CliConfig::default())
+        .await
+        .expect("durations recorded");
+}
+
+#[tokio::test]
+async fn test_durations_are_recorded_by_test_path() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache = CacheManager::with_path(dir.path().join("cache.json")).expect("cache");
+    let tests = test_paths(dir.path());
+
+    // Act
+    record_durations(&tests, &[400, 300, 200, 100], &cache).await;
+
+    // Assert: same file name, each with its own timing
+    assert_eq!(cache.duration(&tests[0]).expect("lookup"), Some(400));
+    assert_eq!(cache.duration(&tests[1]).expect("lookup"), Some(300));
+}
+
+#[tokio::test]
+async fn test_longest_durations_first_balances_shards() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let cache = CacheManager::with_path(dir.path().join("cache.json")).expect("cache");
+    let tests = test_paths(dir.path());
+    record_durations(&tests, &[400, 300, 200, 100], &cache).await;
+
+    // Act
+    let first =
+        shard_tests(tests.clone(), (1, 2), &ShardStrategy::Duration, &cache).expect("shard 1");
+    let second =
+        shard_tests(tests.clone(), (2, 2), &ShardStrategy::Duration, &cache).expect("shard 2");
+
+    // Assert: 400 + 100 and 300 + 200, where modulo would give 400 + 200 and
+    // 300 + 100
+    assert_eq!(first, vec![tests[0].clone(), tests[3].clone()]);
+    assert_eq!(second, vec![tests[1].clone(), tests[2].clone()]);
+}
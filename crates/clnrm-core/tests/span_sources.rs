@@ -0,0 +1,122 @@
+//! Span sources selected by `artifacts.collect`
+
+use clnrm_core::otel::{
+    JsonFileSpanSource, OtlpReceiver, OtlpSpanSource, SpanSource, SpanSourceSpec, StdoutSpanSource,
+};
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+#[test]
+fn test_specs_pick_the_source() {
+    assert_eq!(
+        SpanSourceSpec::parse("spans:default"),
+        Some(SpanSourceSpec::Stdout)
+    );
+    assert_eq!(
+        SpanSourceSpec::parse("spans:otlp"),
+        Some(SpanSourceSpec::Otlp)
+    );
+    assert_eq!(
+        SpanSourceSpec::parse("spans:file:/tmp/trace.json"),
+        Some(SpanSourceSpec::File(PathBuf::from("/tmp/trace.json")))
+    );
+    assert_eq!(SpanSourceSpec::parse("logs:stderr"), None);
+}
+
+#[tokio::test]
+async fn test_stdout_source_skips_log_lines() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stdout = dir.path().join("stdout");
+    std::fs::write(
+        &stdout,
+        "starting\n{\"name\":\"a\",\"trace_id\":\"t\",\"span_id\":\"1\"}\ndone\n",
+    )
+    .expect("write stdout");
+
+    let spans = StdoutSpanSource::new(&stdout)
+        .collect()
+        .await
+        .expect("spans parse");
+
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].name, "a");
+}
+
+#[tokio::test]
+async fn test_file_source_reads_arrays_and_ndjson() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let array = dir.path().join("array.json");
+    std::fs::write(
+        &array,
+        r#"[{"name": "a", "trace_id": "t", "span_id": "1", "attributes": {}},
+            {"name": "b", "trace_id": "t", "span_id": "2", "attributes": {}}]"#,
+    )
+    .expect("write trace");
+    let ndjson = dir.path().join("spans.ndjson");
+    std::fs::write(
+        &ndjson,
+        "{\"name\":\"a\",\"trace_id\":\"t\",\"span_id\":\"1\"}\n\
+         {\"name\":\"b\",\"trace_id\":\"t\",\"span_id\":\"2\"}\n",
+    )
+    .expect("write trace");
+
+    for path in [array, ndjson] {
+        let spans = JsonFileSpanSource::new(&path)
+            .collect()
+            .await
+            .expect("spans load");
+        let names: Vec<_> = spans.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["a", "b"], "{}", path.display());
+    }
+}
+
+#[tokio::test]
+async fn test_missing_file_names_the_path() {
+    let error = JsonFileSpanSource::new("/nonexistent/trace.json")
+        .collect()
+        .await
+        .expect_err("file is missing");
+
+    assert!(
+        error.to_string().contains("/nonexistent/trace.json"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn test_otlp_source_returns_exported_spans() {
+    let receiver = OtlpReceiver::start("127.0.0.1:0")
+        .await
+        .expect("receiver starts");
+    let body = r#"{"resourceSpans":[{"scopeSpans":[{"spans":[
+        {"traceId":"t","spanId":"1","name":"checkout","kind":1}]}]}]}"#;
+    let mut stream = tokio::net::TcpStream::connect(receiver.local_addr())
+        .await
+        .expect("connects");
+    let request = format!(
+        "POST /v1/traces HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    stream
+        .write_all(request.as_bytes())
+        .await
+        .expect("request sent");
+    let mut response = String::new();
+    stream
+        .read_to_string(&mut response)
+        .await
+        .expect("response read");
+    assert!(response.starts_with("HTTP/1.1 200"), "{}", response);
+
+    let spans = OtlpSpanSource::new(&receiver, Duration::from_millis(10))
+        .collect()
+        .await
+        .expect("spans collected");
+
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].name, "checkout");
+}
@@ -0,0 +1,158 @@
+//! Streaming span parsing with `StdoutSpanParser::parse_reader`
+
+use clnrm_core::backend::{Backend, Cmd, HostBackend};
+use clnrm_core::otel::{SpanSource, StdoutSpanParser, StdoutSpanSource};
+use clnrm_core::validation::{CountBound, CountExpectation, PrdExpectations, SpanData};
+use std::cell::Cell;
+use std::io::{BufReader, Read};
+use std::rc::Rc;
+
+/// NDJSON trace produced on demand: `spans` span lines, each followed by a
+/// log line, without ever holding more than one line
+struct SyntheticTrace {
+    spans: usize,
+    next: usize,
+    pending: Vec<u8>,
+    produced: Rc<Cell<usize>>,
+}
+
+impl SyntheticTrace {
+    fn new(spans: usize) -> (Self, Rc<Cell<usize>>) {
+        let produced = Rc::new(Cell::new(0));
+        let trace = Self {
+            spans,
+            next: 0,
+            pending: Vec::new(),
+            produced: Rc::clone(&produced),
+        };
+        (trace, produced)
+    }
+}
+
+impl Read for SyntheticTrace {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.pending.is_empty() {
+            if self.next == self.spans {
+                return Ok(0);
+            }
+            let parent = match self.next {
+                0 => "null".to_string(),
+                _ => "\"s0\"".to_string(),
+            };
+            self.pending = format!(
+                "{{\"name\":\"step.{}\",\"trace_id\":\"t1\",\"span_id\":\"s{}\",\"parent_span_id\":{},\"attributes\":{{\"index\":{}}}}}\nprogress {}\n",
+                self.next, self.next, parent, self.next, self.next
+            )
+            .into_bytes();
+            self.next += 1;
+        }
+
+        let len = buf.len().min(self.pending.len());
+        buf[..len].copy_from_slice(&self.pending[..len]);
+        self.pending.drain(..len);
+        self.produced.set(self.produced.get() + len);
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_reader_yields_spans_before_consuming_the_input() {
+    let (trace, produced) = SyntheticTrace::new(1_000_000);
+    let mut spans = StdoutSpanParser::parse_reader(BufReader::new(trace));
+
+    let first = spans.next().expect("a span").expect("parses");
+    assert_eq!(first.name, "step.0");
+    assert!(
+        produced.get() <= 64 * 1024,
+        "read {} bytes for the first span",
+        produced.get()
+    );
+}
+
+#[test]
+fn test_large_synthetic_stream_validates() {
+    let total = 200_000;
+    let (trace, produced) = SyntheticTrace::new(total);
+
+    let spans = StdoutSpanParser::parse_reader(BufReader::new(trace))
+        .collect::<clnrm_core::error::Result<Vec<_>>>()
+        .expect("stream parses");
+
+    assert_eq!(spans.len(), total);
+    assert!(produced.get() > 20 * 1024 * 1024);
+    assert_eq!(spans[total - 1].parent_span_id.as_deref(), Some("s0"));
+
+    let report = PrdExpectations::new()
+        .with_counts(CountExpectation::new().with_spans_total(CountBound::eq(total)))
+        .validate_all(&spans)
+        .expect("validation runs");
+    assert!(report.is_success(), "{}", report.summary());
+}
+
+#[test]
+fn test_reader_matches_parse_on_mixed_output() {
+    let mut output = String::from(
+        "Starting test...\n\
+         {\"name\":\"a\",\"trace_id\":\"t\",\"span_id\":\"1\",\"attributes\":{}}\n\
+         {\"not\":\"a span\"}\n\
+         \n   \n\
+         {\"name\":\"b\",\"trace_id\":\"t\",\"span_id\":\"2\",\"parent_span_id\":\"1\"}",
+    )
+    .into_bytes();
+    output.extend_from_slice(b"\n\xff\xfe binary noise\n");
+
+    let streamed = StdoutSpanParser::parse_reader(output.as_slice())
+        .collect::<clnrm_core::error::Result<Vec<_>>>()
+        .expect("stream parses");
+    let parsed = StdoutSpanParser::parse(&String::from_utf8_lossy(&output)).expect("text parses");
+
+    let names = |spans: &[SpanData]| {
+        spans
+            .iter()
+            .map(|span| span.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&streamed), vec!["a", "b"]);
+    assert_eq!(names(&streamed), names(&parsed));
+}
+
+#[test]
+fn test_read_errors_are_yielded() {
+    struct Failing;
+
+    impl Read for Failing {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("disk gone"))
+        }
+    }
+
+    let error = StdoutSpanParser::parse_reader(BufReader::new(Failing))
+        .next()
+        .expect("an item")
+        .expect_err("read error");
+    assert!(error.to_string().contains("disk gone"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_command_stdout_is_written_to_the_file_and_parsed_from_it() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let stdout = dir.path().join("stdout");
+    let script = r#"i=0; while [ $i -lt 20000 ]; do
+        echo "{\"name\":\"step.$i\",\"trace_id\":\"t1\",\"span_id\":\"s$i\"}"
+        echo "progress $i"
+        i=$((i + 1))
+    done"#;
+
+    let result = HostBackend
+        .run_cmd(Cmd::new("sh").arg("-c").arg(script).stdout_file(&stdout))
+        .expect("command runs");
+
+    assert!(result.success());
+    assert!(result.stdout.is_empty(), "stdout was also captured");
+    let spans = StdoutSpanSource::new(&stdout)
+        .collect()
+        .await
+        .expect("spans parse");
+    assert_eq!(spans.len(), 20_000);
+    assert_eq!(spans[19_999].name, "step.19999");
+}
@@ -0,0 +1,65 @@
+//! Template rendering of `[[steps]] env` values
+
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::cli::types::CliConfig;
+
+async fn run_error(step: &str) -> String {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let path = dir.path().join("env.clnrm.toml");
+    std::fs::write(
+        &path,
+        format!(
+            r#"
+[meta]
+name = "env"
+version = "1.0"
+
+[vars]
+host = "db.internal"
+
+[policy]
+denied_binaries = ["psql"]
+
+[[steps]]
+name = "connect"
+{}
+"#,
+            step
+        ),
+    )
+    .expect("write test file");
+
+    let result = run_test_file(&path, &CliConfig::default())
+        .await
+        .expect("test runs");
+    assert!(!result.passed);
+    result.error.expect("test error")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_env_values_render_with_vars() {
+    // The denied command stops the step right after its env is rendered
+    let error = run_error(
+        r#"command = ["psql", "$DB_URL"]
+env = { DB_URL = "postgres://{{ vars.host }}:5432" }"#,
+    )
+    .await;
+
+    assert!(error.contains("blocked by [policy]"), "{}", error);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_undefined_var_in_env_fails_like_in_command() {
+    let in_command = run_error(r#"command = ["psql", "{{ vars.missing }}"]"#).await;
+    let in_env = run_error(
+        r#"command = ["psql", "$DB_URL"]
+env = { DB_URL = "{{ vars.missing }}" }"#,
+    )
+    .await;
+
+    assert!(!in_env.contains("blocked by [policy]"), "{}", in_env);
+    assert_eq!(
+        in_env.replace("step_connect_env_DB_URL", "TEMPLATE"),
+        in_command.replace("step_connect_arg", "TEMPLATE")
+    );
+}
@@ -0,0 +1,60 @@
+//! Masking of step output with `[meta] redact` and per-step `redact`
+
+use clnrm_core::config::{parse_toml_config, TestConfig};
+use clnrm_core::error::Result;
+
+const TEST_TOML: &str = r#"
+[meta]
+name = "redact"
+version = "1.0"
+redact = ["sk-[a-z0-9]+"]
+
+[[steps]]
+name = "login"
+command = ["sh", "-c", "echo token=sk-abc123 session=s3cr3t"]
+expected_output_regex = "token=sk-abc123"
+redact = ["session=\\w+"]
+
+[[steps]]
+name = "whoami"
+command = ["echo", "key sk-def456 session=visible"]
+"#;
+
+fn config() -> TestConfig {
+    parse_toml_config(TEST_TOML).expect("valid test config")
+}
+
+#[test]
+fn test_meta_and_step_patterns_mask_output() -> Result<()> {
+    let config = config();
+    let redactor = config.steps[0].output_redactor(config.meta.as_ref())?;
+
+    assert_eq!(
+        redactor.redact_text("token=sk-abc123 session=s3cr3t\n"),
+        "token=*** ***\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_step_patterns_do_not_leak_into_other_steps() -> Result<()> {
+    let config = config();
+    let redactor = config.steps[1].output_redactor(config.meta.as_ref())?;
+
+    assert_eq!(
+        redactor.redact_text("key sk-def456 session=visible"),
+        "key *** session=visible"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_invalid_redact_pattern_fails_validation() {
+    let config =
+        parse_toml_config(&TEST_TOML.replace("session=\\\\w+", "session=(")).expect("parses");
+
+    let error = config.validate().expect_err("invalid pattern rejected");
+    assert!(error
+        .to_string()
+        .contains("Invalid redaction pattern 'session=('"));
+}
@@ -0,0 +1,114 @@
+//! Step `retries` recorded on results and reported in JUnit output
+
+mod common;
+
+use clnrm_core::cli::types::{CliTestResult, CliTestResults};
+use clnrm_core::cli::utils::generate_junit_xml;
+use clnrm_core::testing::TestResult;
+use common::{meta, run_config_on};
+use std::path::Path;
+
+fn junit(result: CliTestResult, flat: bool) -> String {
+    let results = CliTestResults {
+        tests: vec![result],
+        total_duration_ms: 0,
+    };
+    generate_junit_xml(&results, flat).expect("junit")
+}
+
+/// Runs a step that fails on its first attempt only
+async fn run_flaky() -> TestResult {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let marker = dir.path().join("attempted");
+
+    run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[[steps]]
+name = "flaky"
+command = ["test -f {} || (touch {} && false)"]
+retries = 2
+retry_delay_ms = 0
+"#,
+            meta("flaky"),
+            marker.display(),
+            marker.display()
+        ),
+    )
+    .await
+}
+
+/// Runs a step that fails on every attempt
+async fn run_broken() -> TestResult {
+    run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[[steps]]
+name = "broken"
+command = ["false"]
+retries = 2
+retry_delay_ms = 0
+"#,
+            meta("broken")
+        ),
+    )
+    .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_step_passing_on_retry_records_its_retries() {
+    // Act
+    let result = run_flaky().await;
+
+    // Assert
+    assert!(result.passed, "{:?}", result.error);
+    assert_eq!(result.steps[0].retries, 1);
+    let json = serde_json::to_value(&result.steps[0]).expect("serializes");
+    assert_eq!(json["retries"], 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_junit_reports_retries_of_a_passing_step() {
+    // Arrange
+    let result = run_flaky().await;
+    let path = Path::new("flaky.clnrm.toml");
+
+    // Act
+    let per_step = junit(CliTestResult::new(path, result.clone()), false);
+    let flat = junit(CliTestResult::new(path, result), true);
+
+    // Assert
+    assert!(per_step.contains("Passed after 1 retries"), "{}", per_step);
+    assert!(flat.contains("Steps retried 1 times"), "{}", flat);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_step_failing_every_attempt_records_every_retry() {
+    // Act
+    let result = run_broken().await;
+
+    // Assert
+    assert!(!result.passed);
+    assert_eq!(result.steps[0].retries, 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_junit_reports_retries_of_a_failing_step() {
+    // Arrange
+    let result = run_broken().await;
+
+    // Act
+    let xml = junit(
+        CliTestResult::new(Path::new("broken.clnrm.toml"), result),
+        false,
+    );
+
+    // Assert
+    assert!(
+        xml.contains("failed (exit code 1) after 2 retries"),
+        "{}",
+        xml
+    );
+}
@@ -0,0 +1,103 @@
+//! Steps skipped with `skip` or `skip_if`
+
+mod common;
+
+use clnrm_core::cli::types::{CliTestResult, CliTestResults};
+use clnrm_core::cli::utils::generate_junit_xml;
+use clnrm_core::testing::TestResult;
+use common::{meta, run_config_on};
+use serial_test::serial;
+use std::path::Path;
+
+/// Runs a passing step followed by a step with `skip_setting`
+async fn run_with_skipped_step(skip_setting: &str) -> TestResult {
+    run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[[steps]]
+name = "build"
+command = ["true"]
+
+[[steps]]
+name = "deploy"
+command = ["false"]
+{}
+"#,
+            meta("skips"),
+            skip_setting
+        ),
+    )
+    .await
+}
+
+/// Runs [`run_with_skipped_step`] with `skip_if` on `CI`, which is set to
+/// `ci` for the run and restored afterwards
+async fn run_on_ci(ci: &str) -> TestResult {
+    let previous = std::env::var_os("CI");
+    std::env::set_var("CI", ci);
+    let result = run_with_skipped_step(r#"skip_if = "env(name='CI') == 'true'""#).await;
+    match previous {
+        Some(value) => std::env::set_var("CI", value),
+        None => std::env::remove_var("CI"),
+    }
+    result
+}
+
+fn assert_deploy_skipped(result: &TestResult) {
+    assert!(result.passed, "{:?}", result.error);
+    assert_eq!(result.steps.len(), 2, "{:?}", result.steps);
+    assert!(result.steps[0].success && !result.steps[0].skipped);
+    assert_eq!(result.steps[1].name, "deploy");
+    assert!(result.steps[1].skipped);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_skip_records_a_skipped_step_without_failing() {
+    // Act
+    let result = run_with_skipped_step("skip = true").await;
+
+    // Assert
+    assert_deploy_skipped(&result);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_skip_if_true_records_a_skipped_step_without_failing() {
+    // Act
+    let result = run_on_ci("true").await;
+
+    // Assert
+    assert_deploy_skipped(&result);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_skip_if_false_runs_the_step() {
+    // Act
+    let result = run_on_ci("false").await;
+
+    // Assert
+    assert!(!result.passed);
+    assert!(!result.steps[1].skipped);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_junit_marks_skipped_steps_skipped() {
+    // Arrange
+    let result = run_with_skipped_step("skip = true").await;
+    let results = CliTestResults {
+        tests: vec![CliTestResult::new(Path::new("skips.clnrm.toml"), result)],
+        total_duration_ms: 0,
+    };
+
+    // Act
+    let xml = generate_junit_xml(&results, false).expect("junit");
+
+    // Assert
+    let deploy = xml
+        .split("<testcase")
+        .find(|case| case.contains(r#"name="deploy""#))
+        .unwrap_or_else(|| panic!("no deploy testcase in {}", xml));
+    assert!(deploy.contains("<skipped/>"), "{}", xml);
+}
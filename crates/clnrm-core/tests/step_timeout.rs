@@ -0,0 +1,43 @@
+//! Step `timeout_ms` kills a hung command and reports how long it ran
+
+mod common;
+
+use clnrm_core::FailureReason;
+use common::{meta, run_config_on};
+use std::time::{Duration, Instant};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_step_stuck_in_sleep_is_killed_at_its_timeout() {
+    // Arrange
+    let start = Instant::now();
+
+    // Act
+    let result = run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[[steps]]
+name = "hung"
+command = ["sleep", "999"]
+timeout_ms = 1000
+"#,
+            meta("hung")
+        ),
+    )
+    .await;
+
+    // Assert
+    assert!(!result.passed);
+    assert!(start.elapsed() < Duration::from_secs(5), "step not killed");
+    let step = &result.steps[0];
+    assert_eq!(step.failure_reason, Some(FailureReason::Timeout));
+    let error = &step.stderr;
+    assert!(error.starts_with("ContainerError: "), "{}", error);
+    let elapsed_ms: u64 = error
+        .split("was killed after ")
+        .nth(1)
+        .and_then(|rest| rest.split("ms").next())
+        .and_then(|ms| ms.parse().ok())
+        .unwrap_or_else(|| panic!("elapsed time in '{}'", error));
+    assert!((1000..5000).contains(&elapsed_ms), "{}", error);
+}
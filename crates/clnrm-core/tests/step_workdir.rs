@@ -0,0 +1,112 @@
+//! Running step and scenario commands from `workdir`
+//!
+//! The container runtime execs [`Cmd::argv`] as is, so running it with the
+//! host's `sh` exercises the same working-directory handling.
+
+use clnrm_core::backend::{Cmd, WORKDIR_MISSING_EXIT_CODE};
+use clnrm_core::config::parse_toml_config;
+use std::path::Path;
+use std::process::Output;
+
+fn exec(cmd: &Cmd) -> Output {
+    let argv = cmd.argv();
+    std::process::Command::new(&argv[0])
+        .args(&argv[1..])
+        .output()
+        .expect("command runs")
+}
+
+#[test]
+fn test_command_runs_relative_to_workdir() {
+    let app = tempfile::tempdir().expect("temp dir");
+    std::fs::write(app.path().join("server.js"), "").expect("write file");
+
+    let cmd = Cmd::new("ls").workdir(app.path().to_path_buf());
+    let output = exec(&cmd);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "server.js\n");
+}
+
+#[test]
+fn test_arguments_are_passed_through_unchanged() {
+    let app = tempfile::tempdir().expect("temp dir");
+
+    let cmd = Cmd::new("sh")
+        .arg("-c")
+        .arg("pwd && echo \"$0 $1\"")
+        .arg("two words")
+        .arg("$HOME")
+        .workdir(app.path().to_path_buf());
+    let output = exec(&cmd);
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let cwd = std::fs::canonicalize(app.path()).expect("canonical path");
+    assert_eq!(
+        stdout,
+        format!("{}\ntwo words $HOME\n", cwd.display()),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_missing_workdir_is_reported() {
+    let cmd = Cmd::new("ls").workdir("/nonexistent/clnrm-app".into());
+    let output = exec(&cmd);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(WORKDIR_MISSING_EXIT_CODE));
+    assert_eq!(
+        cmd.missing_workdir(WORKDIR_MISSING_EXIT_CODE, &stderr),
+        Some(Path::new("/nonexistent/clnrm-app"))
+    );
+
+    // A command that exits 125 by itself is not mistaken for a missing directory
+    let app = tempfile::tempdir().expect("temp dir");
+    let cmd = Cmd::new("sh")
+        .arg("-c")
+        .arg("exit 125")
+        .workdir(app.path().to_path_buf());
+    let output = exec(&cmd);
+    assert_eq!(output.status.code(), Some(WORKDIR_MISSING_EXIT_CODE));
+    assert_eq!(
+        cmd.missing_workdir(
+            WORKDIR_MISSING_EXIT_CODE,
+            &String::from_utf8_lossy(&output.stderr)
+        ),
+        None
+    );
+}
+
+#[test]
+fn test_argv_without_workdir_is_the_command() {
+    let cmd = Cmd::new("ls").arg("-la");
+
+    assert_eq!(cmd.argv(), ["ls", "-la"]);
+    assert_eq!(cmd.missing_workdir(WORKDIR_MISSING_EXIT_CODE, ""), None);
+}
+
+#[test]
+fn test_scenario_workdir_is_parsed() {
+    let config = parse_toml_config(
+        r#"
+[meta]
+name = "workdir"
+version = "1.0"
+
+[services.app]
+plugin = "generic_container"
+image = "node:20-alpine"
+
+[[scenario]]
+name = "list"
+service = "app"
+run = "ls"
+workdir = "/app"
+"#,
+    )
+    .expect("config parses");
+
+    assert_eq!(config.scenario[0].workdir.as_deref(), Some("/app"));
+}
@@ -0,0 +1,69 @@
+//! TAP 14 output with steps nested as subtests
+
+use clnrm_core::formatting::TestSuite;
+use clnrm_core::{Formatter, TapFormatter, TestResult};
+
+fn tap14(results: Vec<TestResult>) -> String {
+    let suite = results
+        .into_iter()
+        .fold(TestSuite::new("suite"), |suite, result| {
+            suite.add_result(result)
+        });
+    TapFormatter::tap14().format(&suite).expect("formats")
+}
+
+#[test]
+fn test_results_without_steps_keep_their_own_status() {
+    let output = tap14(vec![
+        TestResult::failed("broken", "exit code 1"),
+        TestResult::passed("working"),
+        TestResult::skipped("ignored"),
+    ]);
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "TAP version 14",
+            "1..3",
+            "not ok 1 - broken",
+            "  ---",
+            "  message: exit code 1",
+            "  ...",
+            "ok 2 - working",
+            "ok 3 - ignored # SKIP",
+            "# tests 3, passed 1, failed 1, skipped 1",
+        ]
+    );
+}
+
+#[test]
+fn test_steps_are_nested_under_their_parent_test() {
+    let output = tap14(vec![TestResult::failed("scenario", "step check failed")
+        .with_step(TestResult::passed("setup"))
+        .with_step(TestResult::failed("check", "boom"))
+        .with_step(TestResult::skipped("cleanup"))]);
+
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "TAP version 14",
+            "1..1",
+            "# Subtest: scenario",
+            "    1..3",
+            "    ok 1 - setup",
+            "    not ok 2 - check",
+            "      ---",
+            "      message: boom",
+            "      ...",
+            "    ok 3 - cleanup # SKIP",
+            "    # steps 3, passed 1, failed 1, skipped 1",
+            "not ok 1 - scenario",
+            "  ---",
+            "  message: step check failed",
+            "  ...",
+            "# tests 1, passed 0, failed 1, skipped 0",
+        ]
+    );
+}
@@ -0,0 +1,108 @@
+//! `[limits]` enforced on duration, memory and step count
+
+mod common;
+
+use clnrm_core::config::parse_toml_config;
+use common::{meta, run_config_on};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_service_memory_above_the_limit_is_rejected() {
+    let config = parse_toml_config(&format!(
+        r#"{}
+[limits]
+max_memory_bytes = 1048576
+
+[services.db]
+plugin = "generic_container"
+image = "alpine:3.19"
+memory = "512m"
+
+[[steps]]
+name = "noop"
+command = ["true"]
+"#,
+        meta("memory")
+    ))
+    .expect("config parses");
+
+    let error = config.validate().expect_err("memory over the limit");
+    assert!(
+        error
+            .to_string()
+            .contains("exceeds limits.max_memory_bytes = 1048576"),
+        "{}",
+        error
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_run_stops_at_the_duration_limit() {
+    // Arrange
+    let start = Instant::now();
+
+    // Act
+    let result = run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[limits]
+max_duration_ms = 300
+
+[[steps]]
+name = "slow"
+command = ["sleep", "5"]
+
+[[steps]]
+name = "never"
+command = ["true"]
+"#,
+            meta("duration")
+        ),
+    )
+    .await;
+
+    // Assert
+    assert!(!result.passed);
+    assert!(
+        start.elapsed() < Duration::from_secs(4),
+        "step outlived the limit"
+    );
+    let error = result.error.expect("limit error");
+    assert!(error.contains("limits.max_duration_ms = 300"), "{}", error);
+    assert!(result.steps.iter().all(|s| s.name != "never"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_run_stops_at_the_step_limit() {
+    // Act
+    let result = run_config_on(
+        "process",
+        &format!(
+            r#"{}
+[limits]
+max_steps = 1
+
+[[steps]]
+name = "one"
+command = ["true"]
+
+[[steps]]
+name = "two"
+command = ["true"]
+"#,
+            meta("steps")
+        ),
+    )
+    .await;
+
+    // Assert
+    assert!(!result.passed);
+    assert!(result.steps.is_empty(), "nothing runs past the step limit");
+    let error = result.error.expect("limit error");
+    assert!(
+        error.contains("exceeding limits.max_steps = 1"),
+        "{}",
+        error
+    );
+}
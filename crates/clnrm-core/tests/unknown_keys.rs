@@ -0,0 +1,52 @@
+//! Unknown config keys reported with "did you mean" hints
+
+mod common;
+
+use clnrm_core::validation::{find_unknown_keys, ErrorCategory};
+use common::meta;
+
+#[test]
+fn test_unknown_keys_are_found_with_their_line_and_a_suggestion() {
+    let content = format!(
+        r#"{}
+[services.db]
+plugin = "generic_container"
+imge = "alpine:3.19"
+
+[[steps]]
+name = "check"
+command = ["true"]
+expected_exit = 0
+"#,
+        meta("typos")
+    );
+
+    let errors = find_unknown_keys(&content);
+    let messages: Vec<(&str, Option<usize>)> = errors
+        .iter()
+        .map(|e| (e.message.as_str(), e.line))
+        .collect();
+    assert_eq!(
+        messages,
+        [
+            (
+                "Unknown key `imge` in [services.db], did you mean `image`?",
+                Some(7)
+            ),
+            ("Unknown key `expected_exit` in [[steps]] #1", Some(12)),
+        ]
+    );
+    assert!(errors
+        .iter()
+        .all(|e| e.category == ErrorCategory::UnknownKey));
+}
+
+#[test]
+fn test_known_keys_and_invalid_toml_have_no_findings() {
+    let content = format!(
+        "{}\n[[steps]]\nname = \"check\"\ncommand = [\"true\"]\n",
+        meta("clean")
+    );
+    assert!(find_unknown_keys(&content).is_empty());
+    assert!(find_unknown_keys("[meta\nname =").is_empty());
+}
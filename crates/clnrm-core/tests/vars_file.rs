@@ -0,0 +1,131 @@
+//! Loading template variables from a JSON file with `--vars-file`
+
+mod common;
+
+use clnrm_core::cli::commands::render_template_with_vars;
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::cli::types::{CliConfig, RenderFormat};
+use clnrm_core::TemplateContext;
+use common::on_backend;
+use std::path::{Path, PathBuf};
+
+fn render(template: &Path, map: &[String], vars_file: &Path) -> clnrm_core::error::Result<String> {
+    let output = template.with_extension("out");
+    render_template_with_vars(
+        template,
+        map,
+        Some(&output),
+        false,
+        &RenderFormat::Toml,
+        Some(vars_file),
+        None,
+        None,
+        false,
+    )?;
+    Ok(std::fs::read_to_string(&output).expect("rendered output"))
+}
+
+#[test]
+fn test_vars_file_keys_are_injected_and_map_overrides_them() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let vars = dir.path().join("vars.json");
+    std::fs::write(
+        &vars,
+        r#"{"name": "api", "image": "alpine:3.20", "ports": [8080, 8081]}"#,
+    )
+    .expect("write vars");
+    let template = dir.path().join("api.toml.tera");
+    std::fs::write(
+        &template,
+        "name={{ name }} image={{ vars.image }} ports={{ ports | length }}",
+    )
+    .expect("write template");
+
+    let rendered = render(&template, &[], &vars).expect("template renders");
+    assert_eq!(rendered.trim(), "name=api image=alpine:3.20 ports=2");
+
+    let rendered = render(&template, &["name=web".to_string()], &vars).expect("template renders");
+    assert_eq!(rendered.trim(), "name=web image=alpine:3.20 ports=2");
+}
+
+#[test]
+fn test_vars_file_must_hold_a_json_object() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let vars = dir.path().join("vars.json");
+    std::fs::write(&vars, r#"["api", "web"]"#).expect("write vars");
+    let template = dir.path().join("api.toml.tera");
+    std::fs::write(&template, "{{ name }}").expect("write template");
+
+    let error = render(&template, &[], &vars).expect_err("array is rejected");
+    assert!(
+        error
+            .to_string()
+            .contains("must contain a JSON object, found an array"),
+        "{}",
+        error
+    );
+
+    let error =
+        TemplateContext::from_json_file(dir.path().join("missing.json")).expect_err("missing file");
+    assert!(
+        error.to_string().contains("Failed to read vars file"),
+        "{}",
+        error
+    );
+}
+
+/// A test that passes only if `expected` comes from `vars.json` in `dir`,
+/// overriding the test's `[vars]`
+fn write_vars_test(dir: &Path) -> PathBuf {
+    std::fs::write(dir.join("vars.json"), r#"{"expected": "from-file"}"#).expect("write vars");
+    let test = dir.join("vars.clnrm.toml");
+    std::fs::write(
+        &test,
+        r#"
+[meta]
+name = "vars"
+version = "1.0"
+
+[vars]
+expected = "from-test"
+
+[[steps]]
+name = "check"
+command = ["test", "{{ expected }}", "=", "from-file"]
+"#,
+    )
+    .expect("write test");
+    test
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_run_renders_steps_with_test_vars_without_vars_file() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let test = write_vars_test(dir.path());
+
+    // Act
+    let result = run_test_file(&test, &on_backend("process"))
+        .await
+        .expect("test runs");
+
+    // Assert
+    assert!(!result.passed, "[vars] applies without --vars-file");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_run_renders_steps_with_vars_file() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let test = write_vars_test(dir.path());
+    let config = CliConfig {
+        vars_file: Some(dir.path().join("vars.json")),
+        ..on_backend("process")
+    };
+
+    // Act
+    let result = run_test_file(&test, &config).await.expect("test runs");
+
+    // Assert
+    assert!(result.passed, "{:?}", result.error);
+}
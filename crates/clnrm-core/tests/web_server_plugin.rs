@@ -0,0 +1,39 @@
+//! web_server services created by `clnrm run`
+
+mod common;
+
+use clnrm_core::cli::commands::run::services::create_service_plugin;
+use clnrm_core::services::web_server::WebServerPlugin;
+use common::ServiceConfigBuilder;
+
+#[test]
+fn test_run_creates_web_server_services() {
+    let config = ServiceConfigBuilder::new("web_server")
+        .with_image("python:3.13-alpine")
+        .with_port(9000)
+        .build();
+
+    let plugin = create_service_plugin("web", &config, None).expect("web_server is supported");
+    assert_eq!(plugin.name(), "web");
+}
+
+#[test]
+fn test_image_tag_is_split_after_the_registry_port() {
+    let cases = [
+        ("python:3.13-alpine", ("python", "3.13-alpine")),
+        ("python", ("python", "latest")),
+        (
+            "registry.local:5000/team/python:3.12",
+            ("registry.local:5000/team/python", "3.12"),
+        ),
+        (
+            "registry.local:5000/python",
+            ("registry.local:5000/python", "latest"),
+        ),
+    ];
+
+    for (image, expected) in cases {
+        let plugin = WebServerPlugin::new("web").with_image(image);
+        assert_eq!(plugin.image(), expected, "{}", image);
+    }
+}
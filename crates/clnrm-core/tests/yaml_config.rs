@@ -0,0 +1,145 @@
+//! YAML test files parse into the same `TestConfig` as TOML and report
+//! errors at their YAML line
+
+mod common;
+
+use clnrm_core::cli::commands::run::run_test_file;
+use clnrm_core::cli::commands::validate_single_config;
+use clnrm_core::config::{load_config_from_file, parse_toml_config, parse_yaml_config};
+use clnrm_core::validation::{ErrorCategory, ShapeValidator};
+use common::{meta, on_backend, write_file};
+
+const YAML: &str = r#"meta:
+  name: greet
+  version: "1.0"
+steps:
+  - name: hello
+    command: ["echo", "hello"]
+    expected_output_regex: "hello"
+"#;
+
+fn toml() -> String {
+    format!(
+        "{}\n[[steps]]\nname = \"hello\"\ncommand = [\"echo\", \"hello\"]\nexpected_output_regex = \"hello\"\n",
+        meta("greet")
+    )
+}
+
+#[test]
+fn test_yaml_and_toml_parse_to_the_same_config() {
+    let yaml = parse_yaml_config(YAML).expect("yaml parses");
+    let toml = parse_toml_config(&toml()).expect("toml parses");
+
+    assert_eq!(
+        serde_json::to_value(&yaml).expect("serializes"),
+        serde_json::to_value(&toml).expect("serializes")
+    );
+}
+
+#[test]
+fn test_files_are_parsed_by_extension() {
+    let dir = tempfile::tempdir().expect("temp dir");
+
+    for file in ["greet.clnrm.yaml", "greet.clnrm.yml"] {
+        let config = load_config_from_file(&write_file(dir.path(), file, YAML)).expect(file);
+        assert_eq!(config.get_name().expect("name"), "greet");
+        assert_eq!(config.steps[0].command, ["echo", "hello"]);
+    }
+
+    let error = load_config_from_file(&write_file(dir.path(), "greet.clnrm.toml", YAML))
+        .expect_err("YAML in a .toml file");
+    assert!(error.to_string().contains("TOML parse error"), "{}", error);
+}
+
+#[test]
+fn test_parse_errors_name_the_yaml_line_and_column() {
+    let content = YAML.replace(r#"["echo", "hello"]"#, "5");
+
+    let error = parse_yaml_config(&content).expect_err("command is not a list");
+    assert!(
+        error.to_string().contains(
+            "steps[0].command: invalid type: integer `5`, expected a sequence at line 6 column 14"
+        ),
+        "{}",
+        error
+    );
+
+    let dir = tempfile::tempdir().expect("temp dir");
+    let error = validate_single_config(&write_file(dir.path(), "bad.clnrm.yaml", &content))
+        .expect_err("invalid file");
+    assert!(
+        error.to_string().contains("at line 6 column 14"),
+        "{}",
+        error
+    );
+}
+
+#[test]
+fn test_shape_validation_locates_yaml_errors_by_line() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let content = YAML.replace(r#"["echo", "hello"]"#, "5");
+    let path = write_file(dir.path(), "bad.clnrm.yaml", &content);
+
+    let result = ShapeValidator::new()
+        .validate_file(&path)
+        .expect("file is read");
+
+    assert!(!result.passed);
+    let error = &result.errors[0];
+    assert_eq!(error.category, ErrorCategory::InvalidStructure);
+    assert_eq!(error.line, Some(6), "{}", error.message);
+    assert!(error.message.ends_with("(column 14)"), "{}", error.message);
+    assert!(!error.message.contains("at line"), "{}", error.message);
+}
+
+#[test]
+fn test_shape_validation_checks_parsed_yaml() {
+    let dir = tempfile::tempdir().expect("temp dir");
+    let valid = write_file(dir.path(), "greet.clnrm.yaml", YAML);
+    let result = ShapeValidator::new()
+        .validate_file(&valid)
+        .expect("file is read");
+    assert!(result.passed, "{:?}", result.errors);
+
+    let orphan = YAML.replace("    command:", "    service: db\n    command:");
+    let result = ShapeValidator::new()
+        .validate_file(&write_file(dir.path(), "orphan.clnrm.yml", &orphan))
+        .expect("file is read");
+    assert!(
+        result
+            .errors
+            .iter()
+            .any(|e| e.category == ErrorCategory::OrphanReference),
+        "{:?}",
+        result.errors
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_yaml_file_runs_like_toml() {
+    // Arrange
+    let dir = tempfile::tempdir().expect("temp dir");
+    let config = on_backend("process");
+
+    // Act
+    let yaml = run_test_file(&write_file(dir.path(), "greet.clnrm.yaml", YAML), &config)
+        .await
+        .expect("yaml test runs");
+    let toml = run_test_file(
+        &write_file(dir.path(), "greet.clnrm.toml", &toml()),
+        &config,
+    )
+    .await
+    .expect("toml test runs");
+
+    // Assert
+    assert!(yaml.passed, "{:?}", yaml.error);
+    assert_eq!(yaml.passed, toml.passed);
+    let steps = |steps: &[clnrm_core::scenario::StepResult]| {
+        steps
+            .iter()
+            .map(|s| (s.name.clone(), s.success))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(steps(&yaml.steps), steps(&toml.steps));
+}
@@ -32,6 +32,9 @@ pub struct TemplateContext {
     pub matrix: HashMap<String, Value>,
     /// OpenTelemetry configuration
     pub otel: HashMap<String, Value>,
+    /// Variables loaded from a dotenv file, read by `env()` when the process
+    /// environment does not define them
+    pub env_file: HashMap<String, String>,
 }
 
 impl TemplateContext {
@@ -109,6 +112,73 @@ impl TemplateContext {
         self
     }
 
+    /// Load a dotenv-style file so `env(name=...)` can resolve its keys
+    ///
+    /// Supports `KEY=value` lines, `export KEY=value`, `#` comments, blank
+    /// lines, and single- or double-quoted values. Process environment
+    /// variables take precedence over values from the file.
+    ///
+    /// # Errors
+    /// Returns `TemplateError::ConfigError` if the file is missing or a line
+    /// cannot be parsed.
+    pub fn with_env_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            crate::error::TemplateError::ConfigError(format!(
+                "Failed to read env file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let vars = parse_env_file(&content).map_err(|e| {
+            crate::error::TemplateError::ConfigError(format!(
+                "Invalid env file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        self.env_file.extend(vars);
+        Ok(self)
+    }
+
+    /// Create a context whose `vars` are the keys of a JSON object file
+    ///
+    /// Values keep their JSON types, so `{"ports": [8080, 8081]}` renders
+    /// `{{ ports | length }}` as `2`.
+    ///
+    /// # Errors
+    /// Returns `TemplateError::ConfigError` if the file is missing, is not
+    /// valid JSON, or does not hold a JSON object.
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            crate::error::TemplateError::ConfigError(format!(
+                "Failed to read vars file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        let value: Value = serde_json::from_str(&content).map_err(|e| {
+            crate::error::TemplateError::ConfigError(format!(
+                "Invalid JSON in vars file '{}': {}",
+                path.display(),
+                e
+            ))
+        })?;
+
+        match value {
+            Value::Object(vars) => Ok(Self::new().with_vars(vars.into_iter().collect())),
+            other => Err(crate::error::TemplateError::ConfigError(format!(
+                "Vars file '{}' must contain a JSON object, found {}",
+                path.display(),
+                json_type_name(&other)
+            ))),
+        }
+    }
+
     /// Convert to Tera context for rendering
     ///
     /// Injects variables at both top-level (no prefix) and nested [vars] for authoring.
@@ -312,6 +382,98 @@ impl Default for TemplateContextBuilder {
     }
 }
 
+/// Parse dotenv-style content into key/value pairs
+///
+/// Double-quoted values support `\n`, `\t`, `\"` and `\\` escapes; single-quoted
+/// values are literal. Unquoted values end at an inline ` #` comment.
+fn parse_env_file(content: &str) -> std::result::Result<HashMap<String, String>, String> {
+    let mut vars = HashMap::new();
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let line = line
+            .strip_prefix("export")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .map(str::trim_start)
+            .unwrap_or(line);
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("line {}: expected KEY=value", line_no))?;
+
+        let key = key.trim();
+        let valid_key = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            return Err(format!("line {}: invalid variable name '{}'", line_no, key));
+        }
+
+        let value = value.trim();
+        let value = if let Some(quoted) = value.strip_prefix('"') {
+            let end = quoted
+                .rfind('"')
+                .ok_or_else(|| format!("line {}: unterminated double quote", line_no))?;
+            unescape_double_quoted(&quoted[..end])
+        } else if let Some(quoted) = value.strip_prefix('\'') {
+            let end = quoted
+                .rfind('\'')
+                .ok_or_else(|| format!("line {}: unterminated single quote", line_no))?;
+            quoted[..end].to_string()
+        } else {
+            match value.find(" #") {
+                Some(comment) => value[..comment].trim_end().to_string(),
+                None => value.to_string(),
+            }
+        };
+
+        vars.insert(key.to_string(), value);
+    }
+
+    Ok(vars)
+}
+
+/// Resolve escape sequences inside a double-quoted dotenv value
+fn unescape_double_quoted(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some('r') => result.push('\r'),
+            Some(other) => result.push(other),
+            None => result.push('\\'),
+        }
+    }
+
+    result
+}
+
+/// JSON type name of a value, for error messages
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
 /// Convenience functions for common context patterns
 pub mod patterns {
     use super::*;
@@ -401,4 +563,73 @@ mod tests {
         assert_eq!(context.vars["svc"], Value::String("clnrm".to_string()));
         assert_eq!(context.vars["env"], Value::String("ci".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_env_file_handles_quotes_exports_and_comments() {
+        let content = r#"
+# database settings
+export DB_USER=admin
+DB_PASS="s3cr#t \"quoted\""
+GREETING='hello $USER # not a comment'
+PORT=5432 # inline comment
+EMPTY=
+"#;
+
+        let vars = parse_env_file(content).unwrap();
+
+        assert_eq!(vars["DB_USER"], "admin");
+        assert_eq!(vars["DB_PASS"], "s3cr#t \"quoted\"");
+        assert_eq!(vars["GREETING"], "hello $USER # not a comment");
+        assert_eq!(vars["PORT"], "5432");
+        assert_eq!(vars["EMPTY"], "");
+        assert_eq!(vars.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_env_file_rejects_invalid_lines() {
+        let err = parse_env_file("VALID=1\nnot a pair\n").unwrap_err();
+        assert!(err.contains("line 2"));
+
+        let err = parse_env_file("1BAD=x").unwrap_err();
+        assert!(err.contains("invalid variable name"));
+    }
+
+    #[test]
+    fn test_from_json_file_loads_typed_vars() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vars.json");
+        std::fs::write(&path, r#"{"svc": "api", "replicas": 3, "ports": [8080, 8081]}"#).unwrap();
+
+        let context = TemplateContext::from_json_file(&path).unwrap();
+
+        assert_eq!(context.vars.len(), 3);
+        assert_eq!(context.vars["svc"], Value::String("api".to_string()));
+        assert_eq!(context.vars["replicas"], serde_json::json!(3));
+        assert_eq!(context.vars["ports"], serde_json::json!([8080, 8081]));
+    }
+
+    #[test]
+    fn test_from_json_file_rejects_non_objects() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("vars.json");
+        std::fs::write(&path, "[1, 2]").unwrap();
+
+        let err = TemplateContext::from_json_file(&path).unwrap_err();
+        assert!(matches!(&err, crate::error::TemplateError::ConfigError(msg)
+            if msg.contains("must contain a JSON object, found an array")));
+
+        std::fs::write(&path, "{not json").unwrap();
+        let err = TemplateContext::from_json_file(&path).unwrap_err();
+        assert!(matches!(&err, crate::error::TemplateError::ConfigError(msg)
+            if msg.contains("Invalid JSON in vars file")));
+    }
+
+    #[test]
+    fn test_with_env_file_missing_file_is_config_error() {
+        let result = TemplateContext::new().with_env_file("/nonexistent/clnrm/.env");
+        assert!(matches!(
+            result,
+            Err(crate::error::TemplateError::ConfigError(_))
+        ));
+    }
+}
@@ -233,7 +233,7 @@ impl TemplateDebugger {
                 let func = func_name.as_str();
                 // Check for unknown functions (basic check)
                 let known_functions = [
-                    "env", "now_rfc3339", "sha256", "toml_encode",
+                    "env", "now_rfc3339", "sha256", "toml_encode", "toml_decode",
                     "fake_name", "fake_email", "uuid_v4", "include", "extends"
                 ];
 
@@ -11,7 +11,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Enables reproducible tests by controlling randomness and time:
 /// - `seed` - Fixed random seed for matrix expansion
-/// - `freeze_clock` - Fixed timestamp for `now_rfc3339()` function
+/// - `freeze_clock` - Fixed timestamp for `now_rfc3339()` and the other time functions
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct DeterminismConfig {
     /// Random seed for deterministic matrix expansion
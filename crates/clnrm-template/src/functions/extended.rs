@@ -6,9 +6,11 @@
 //! - Collections (pick, weighted, shuffle, sample)
 //! - String transforms (slug, kebab, snake)
 //! - Time helpers (now_unix, now_ms, now_plus)
+//! - JSON bridge (json_encode, json_decode)
 //! - OTEL helpers (trace_id, span_id, traceparent, baggage)
 //! - Unified fake() interface
 
+use super::TimestampProvider;
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
 use std::collections::HashMap;
@@ -30,9 +32,7 @@ pub fn register_extended_functions(tera: &mut Tera) {
 
     // UUIDs
     tera.register_function("uuid_v4", UuidV4Function);
-    tera.register_function("uuid_v7", UuidV7Function);
     tera.register_function("uuid_v5", UuidV5Function);
-    tera.register_function("ulid", UlidFunction);
 
     // Collections
     tera.register_function("pick", PickFunction);
@@ -49,10 +49,11 @@ pub fn register_extended_functions(tera: &mut Tera) {
     register_string_filters(tera);
 
     // Time helpers
-    tera.register_function("now_unix", NowUnixFunction);
-    tera.register_function("now_ms", NowMsFunction);
-    tera.register_function("now_plus", NowPlusFunction);
-    tera.register_function("date_rfc3339", DateRfc3339Function);
+    register_clock_functions(tera, None);
+
+    // JSON bridge
+    tera.register_function("json_encode", JsonEncodeFunction);
+    tera.register_function("json_decode", JsonDecodeFunction);
 
     // OTEL helpers
     tera.register_function("trace_id", TraceIdFunction);
@@ -65,6 +66,40 @@ pub fn register_extended_functions(tera: &mut Tera) {
     tera.register_function("fake_kinds", FakeKindsFunction);
 }
 
+/// Register the time-based functions, reading "now" from `determinism` when set
+///
+/// With a frozen provider, `now_unix()`, `now_ms()`, `now_plus()`,
+/// `date_rfc3339()` and the timestamp part of `uuid_v7()` and `ulid()` all
+/// agree with `now_rfc3339()`.
+pub(crate) fn register_clock_functions(
+    tera: &mut Tera,
+    determinism: Option<Arc<dyn TimestampProvider + Send + Sync>>,
+) {
+    let clock = Clock(determinism);
+    tera.register_function("uuid_v7", UuidV7Function(clock.clone()));
+    tera.register_function("ulid", UlidFunction(clock.clone()));
+    tera.register_function("now_unix", NowUnixFunction(clock.clone()));
+    tera.register_function("now_ms", NowMsFunction(clock.clone()));
+    tera.register_function("now_plus", NowPlusFunction(clock.clone()));
+    tera.register_function("date_rfc3339", DateRfc3339Function(clock));
+}
+
+/// Source of the current time for the time-based functions
+///
+/// Uses the determinism provider's timestamp (honoring `freeze_clock`) when
+/// one is set and parses as RFC3339, the system clock otherwise.
+#[derive(Clone)]
+struct Clock(Option<Arc<dyn TimestampProvider + Send + Sync>>);
+
+impl Clock {
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        self.0
+            .as_ref()
+            .and_then(|p| chrono::DateTime::parse_from_rfc3339(&p.get_timestamp_rfc3339()).ok())
+            .map_or_else(chrono::Utc::now, |t| t.with_timezone(&chrono::Utc))
+    }
+}
+
 /// Register string transformation filters (ggen-style)
 /// Usage: {{ 'Hello World' | kebab }} instead of {{ kebab(s='Hello World') }}
 fn register_string_filters(tera: &mut Tera) {
@@ -195,17 +230,13 @@ impl Function for UuidV4Function {
 }
 
 /// uuid_v7(time=freeze_clock) - Generate UUID v7 (time-based)
-struct UuidV7Function;
+struct UuidV7Function(Clock);
 impl Function for UuidV7Function {
     fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
         // UUID v7 uses timestamp - if time is frozen, use that
-        let _time = args.get("time").and_then(|v| v.as_str());
-
-        // For now, generate a random UUID v7 format
-        // Real implementation would use timestamp from freeze_clock
         let seed = get_seed(args);
         let mut rng = StdRng::seed_from_u64(seed);
-        let timestamp_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let timestamp_ms = self.0.now().timestamp_millis() as u64;
 
         // UUID v7 format: timestamp_ms (48 bits) + version (4) + random (12) + variant (2) + random (62)
         let uuid_str = format!(
@@ -247,7 +278,7 @@ impl Function for UuidV5Function {
 }
 
 /// ulid(time=freeze_clock) - Generate ULID (Universally Unique Lexicographically Sortable Identifier)
-struct UlidFunction;
+struct UlidFunction(Clock);
 impl Function for UlidFunction {
     fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
         let seed = get_seed(args);
@@ -255,7 +286,7 @@ impl Function for UlidFunction {
 
         // ULID format: 10 chars timestamp (base32) + 16 chars random (base32)
         // For deterministic generation, use seed
-        let timestamp_ms = chrono::Utc::now().timestamp_millis() as u64;
+        let timestamp_ms = self.0.now().timestamp_millis() as u64;
 
         // Base32 encoding (Crockford's alphabet)
         let base32 = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
@@ -508,25 +539,25 @@ impl Function for SnakeFunction {
 // ========================================
 
 /// now_unix() - Current Unix timestamp (seconds)
-struct NowUnixFunction;
+struct NowUnixFunction(Clock);
 impl Function for NowUnixFunction {
     fn call(&self, _args: &HashMap<String, Value>) -> tera::Result<Value> {
-        let timestamp = chrono::Utc::now().timestamp();
+        let timestamp = self.0.now().timestamp();
         Ok(Value::Number(timestamp.into()))
     }
 }
 
 /// now_ms() - Current timestamp in milliseconds
-struct NowMsFunction;
+struct NowMsFunction(Clock);
 impl Function for NowMsFunction {
     fn call(&self, _args: &HashMap<String, Value>) -> tera::Result<Value> {
-        let timestamp_ms = chrono::Utc::now().timestamp_millis();
+        let timestamp_ms = self.0.now().timestamp_millis();
         Ok(Value::Number(timestamp_ms.into()))
     }
 }
 
 /// now_plus(seconds) - RFC3339 timestamp N seconds in future
-struct NowPlusFunction;
+struct NowPlusFunction(Clock);
 impl Function for NowPlusFunction {
     fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
         let seconds = args
@@ -534,13 +565,13 @@ impl Function for NowPlusFunction {
             .and_then(|v| v.as_i64())
             .ok_or_else(|| tera::Error::msg("now_plus() requires 'seconds' parameter"))?;
 
-        let future = chrono::Utc::now() + chrono::Duration::seconds(seconds);
+        let future = self.0.now() + chrono::Duration::seconds(seconds);
         Ok(Value::String(future.to_rfc3339()))
     }
 }
 
 /// date_rfc3339(offset_seconds) - RFC3339 timestamp with offset
-struct DateRfc3339Function;
+struct DateRfc3339Function(Clock);
 impl Function for DateRfc3339Function {
     fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
         let offset = args
@@ -548,11 +579,47 @@ impl Function for DateRfc3339Function {
             .and_then(|v| v.as_i64())
             .unwrap_or(0);
 
-        let dt = chrono::Utc::now() + chrono::Duration::seconds(offset);
+        let dt = self.0.now() + chrono::Duration::seconds(offset);
         Ok(Value::String(dt.to_rfc3339()))
     }
 }
 
+// ========================================
+// JSON Functions
+// ========================================
+
+/// json_encode(value) - Serialize a value as compact JSON
+struct JsonEncodeFunction;
+impl Function for JsonEncodeFunction {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        let value = args
+            .get("value")
+            .ok_or_else(|| tera::Error::msg("json_encode() requires 'value' parameter"))?;
+
+        let json = serde_json::to_string(value)
+            .map_err(|e| tera::Error::msg(format!("json_encode() failed: {}", e)))?;
+        Ok(Value::String(json))
+    }
+}
+
+/// json_decode(s) - Parse a JSON string into a value
+///
+/// Objects and arrays stay navigable, e.g.
+/// `{% for item in json_decode(s=vars.items) %}{{ item.name }}{% endfor %}`
+struct JsonDecodeFunction;
+impl Function for JsonDecodeFunction {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        let s = args
+            .get("s")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("json_decode() requires 's' string parameter"))?;
+
+        // serde_json errors already end with "at line N column M"
+        serde_json::from_str(s)
+            .map_err(|e| tera::Error::msg(format!("json_decode() failed to parse JSON: {}", e)))
+    }
+}
+
 // ========================================
 // OTEL Helper Functions
 // ========================================
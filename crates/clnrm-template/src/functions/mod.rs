@@ -4,9 +4,12 @@
 //! - `env(name)` - Get environment variable
 //! - `now_rfc3339()` - Current timestamp (respects freeze_clock)
 //! - `sha256(s)` - SHA-256 hex digest
+//! - `file_sha256(path)` - SHA-256 hex digest of a file under the template directory
 //! - `toml_encode(value)` - Encode as TOML literal
+//! - `toml_decode(s)` - Parse a TOML document into a value
 //! - `fake_name()` - Generate fake names for testing (test-only)
 //! - `fake_email()` - Generate fake emails for testing (test-only)
+//! - `fake_iban(country)` / `fake_bic()` - Generate fake bank identifiers
 //! - 50+ fake data generators for testing
 //! - Extended functions: UUIDs, collections, OTEL helpers, etc.
 
@@ -18,6 +21,7 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
 use std::sync::Arc;
 use tera::{Function, Tera, Value};
 
@@ -31,116 +35,257 @@ pub fn register_functions(
     determinism: Option<Arc<dyn TimestampProvider + Send + Sync>>,
 ) -> Result<()> {
     // Original functions
-    tera.register_function("env", EnvFunction);
+    tera.register_function("env", EnvFunction::new(HashMap::new(), false));
     tera.register_function("now_rfc3339", NowRfc3339Function::new(determinism.clone()));
     tera.register_function("sha256", Sha256Function);
+    tera.register_function("file_sha256", FileSha256Function::new(PathBuf::from(".")));
     tera.register_function("toml_encode", TomlEncodeFunction);
+    tera.register_function("toml_decode", TomlDecodeFunction);
 
     // Fake data generators with determinism support
     register_fake_data_functions(tera, determinism.clone());
 
     // Extended functions (UUIDs, collections, OTEL, etc.)
     extended::register_extended_functions(tera);
+    extended::register_clock_functions(tera, determinism);
 
     Ok(())
 }
 
+/// Re-register `env()` so it falls back to values loaded from a dotenv file
+///
+/// Process environment variables still take precedence over `env_file`.
+pub(crate) fn register_env_function(tera: &mut Tera, env_file: &HashMap<String, String>) {
+    tera.register_function("env", EnvFunction::new(env_file.clone(), false));
+}
+
+/// Re-register `file_sha256()` so paths resolve against `template_dir`
+pub(crate) fn register_file_functions(tera: &mut Tera, template_dir: &Path) {
+    tera.register_function(
+        "file_sha256",
+        FileSha256Function::new(template_dir.to_path_buf()),
+    );
+}
+
+/// Override functions whose strict behavior is wrong for boolean conditions
+///
+/// `env()` yields an empty string for unset variables here, so a condition
+/// like `env(name='CI') == 'true'` is simply false outside CI instead of
+/// failing to render.
+pub(crate) fn register_condition_functions(tera: &mut Tera, env_file: &HashMap<String, String>) {
+    tera.register_function("env", EnvFunction::new(env_file.clone(), true));
+}
+
+/// Re-register clock and fake data functions to use `determinism`
+///
+/// `now_rfc3339()` and the other time helpers (`now_unix()`, `now_ms()`,
+/// `now_plus()`, ...) return the provider's timestamp, and when the provider
+/// is seeded, `fake_*` functions called without `seed=` draw from its
+/// random stream.
+pub(crate) fn register_determinism_functions(
+    tera: &mut Tera,
+    determinism: Arc<dyn TimestampProvider + Send + Sync>,
+) {
+    tera.register_function(
+        "now_rfc3339",
+        NowRfc3339Function::new(Some(determinism.clone())),
+    );
+    extended::register_clock_functions(tera, Some(determinism.clone()));
+    register_fake_data_functions(tera, Some(determinism));
+}
+
 /// Trait for timestamp providers (for determinism support)
 pub trait TimestampProvider {
     fn get_timestamp_rfc3339(&self) -> String;
+
+    /// Master seed, if seeded random generation is configured
+    fn seed(&self) -> Option<u64> {
+        None
+    }
+
+    /// Next value from the seeded random stream shared by all fake data
+    /// functions, or `None` if not seeded
+    fn next_seed(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Wraps a fake data function so calls without `seed=` draw their seed from
+/// the shared determinism stream
+///
+/// Every call advances the one stream, so sequential calls (including calls
+/// to different functions) produce distinct values that are reproducible
+/// for a given master seed and render order.
+struct SeededFunction<F> {
+    inner: F,
+    determinism: Arc<dyn TimestampProvider + Send + Sync>,
+}
+
+impl<F: Function> Function for SeededFunction<F> {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        if args.contains_key("seed") {
+            return self.inner.call(args);
+        }
+
+        match self.determinism.next_seed() {
+            Some(seed) => {
+                let mut seeded_args = args.clone();
+                seeded_args.insert("seed".to_string(), Value::from(seed));
+                self.inner.call(&seeded_args)
+            }
+            None => self.inner.call(args),
+        }
+    }
+}
+
+/// Register a fake data function, seeding it from `determinism` when set
+fn register_fake<F: Function + 'static>(
+    tera: &mut Tera,
+    name: &str,
+    function: F,
+    determinism: &Option<Arc<dyn TimestampProvider + Send + Sync>>,
+) {
+    match determinism {
+        Some(determinism) => tera.register_function(
+            name,
+            SeededFunction {
+                inner: function,
+                determinism: determinism.clone(),
+            },
+        ),
+        None => tera.register_function(name, function),
+    }
 }
 
 /// Register all fake data generator functions
 fn register_fake_data_functions(
     tera: &mut Tera,
-    _determinism: Option<Arc<dyn TimestampProvider + Send + Sync>>,
+    determinism: Option<Arc<dyn TimestampProvider + Send + Sync>>,
 ) {
+    let seeded = determinism.filter(|d| d.seed().is_some());
+
     // UUIDs
-    tera.register_function("fake_uuid", FakeUuidFunction);
-    tera.register_function("fake_uuid_seeded", FakeUuidSeededFunction);
+    register_fake(tera, "fake_uuid", FakeUuidFunction, &seeded);
+    register_fake(tera, "fake_uuid_seeded", FakeUuidSeededFunction, &seeded);
 
     // Names
-    tera.register_function("fake_name", FakeNameFunction);
-    tera.register_function("fake_first_name", FakeFirstNameFunction);
-    tera.register_function("fake_last_name", FakeLastNameFunction);
-    tera.register_function("fake_title", FakeTitleFunction);
-    tera.register_function("fake_suffix", FakeSuffixFunction);
+    register_fake(tera, "fake_name", FakeNameFunction, &seeded);
+    register_fake(tera, "fake_first_name", FakeFirstNameFunction, &seeded);
+    register_fake(tera, "fake_last_name", FakeLastNameFunction, &seeded);
+    register_fake(tera, "fake_title", FakeTitleFunction, &seeded);
+    register_fake(tera, "fake_suffix", FakeSuffixFunction, &seeded);
 
     // Internet
-    tera.register_function("fake_email", FakeEmailFunction);
-    tera.register_function("fake_username", FakeUsernameFunction);
-    tera.register_function("fake_password", FakePasswordFunction);
-    tera.register_function("fake_domain", FakeDomainFunction);
-    tera.register_function("fake_url", FakeUrlFunction);
-    tera.register_function("fake_ipv4", FakeIpv4Function);
-    tera.register_function("fake_ipv6", FakeIpv6Function);
-    tera.register_function("fake_user_agent", FakeUserAgentFunction);
-    tera.register_function("fake_mac_address", FakeMacAddressFunction);
+    register_fake(tera, "fake_email", FakeEmailFunction, &seeded);
+    register_fake(tera, "fake_username", FakeUsernameFunction, &seeded);
+    register_fake(tera, "fake_password", FakePasswordFunction, &seeded);
+    register_fake(tera, "fake_domain", FakeDomainFunction, &seeded);
+    register_fake(tera, "fake_url", FakeUrlFunction, &seeded);
+    register_fake(tera, "fake_ipv4", FakeIpv4Function, &seeded);
+    register_fake(tera, "fake_ipv6", FakeIpv6Function, &seeded);
+    register_fake(tera, "fake_user_agent", FakeUserAgentFunction, &seeded);
+    register_fake(tera, "fake_mac_address", FakeMacAddressFunction, &seeded);
 
     // Address
-    tera.register_function("fake_street", FakeStreetFunction);
-    tera.register_function("fake_city", FakeCityFunction);
-    tera.register_function("fake_state", FakeStateFunction);
-    tera.register_function("fake_zip", FakeZipFunction);
-    tera.register_function("fake_country", FakeCountryFunction);
-    tera.register_function("fake_latitude", FakeLatitudeFunction);
-    tera.register_function("fake_longitude", FakeLongitudeFunction);
+    register_fake(tera, "fake_street", FakeStreetFunction, &seeded);
+    register_fake(tera, "fake_city", FakeCityFunction, &seeded);
+    register_fake(tera, "fake_state", FakeStateFunction, &seeded);
+    register_fake(tera, "fake_zip", FakeZipFunction, &seeded);
+    register_fake(tera, "fake_country", FakeCountryFunction, &seeded);
+    register_fake(tera, "fake_latitude", FakeLatitudeFunction, &seeded);
+    register_fake(tera, "fake_longitude", FakeLongitudeFunction, &seeded);
 
     // Phone
-    tera.register_function("fake_phone", FakePhoneFunction);
-    tera.register_function("fake_cell_phone", FakeCellPhoneFunction);
+    register_fake(tera, "fake_phone", FakePhoneFunction, &seeded);
+    register_fake(tera, "fake_cell_phone", FakeCellPhoneFunction, &seeded);
 
     // Company
-    tera.register_function("fake_company", FakeCompanyFunction);
-    tera.register_function("fake_company_suffix", FakeCompanySuffixFunction);
-    tera.register_function("fake_industry", FakeIndustryFunction);
-    tera.register_function("fake_profession", FakeProfessionFunction);
+    register_fake(tera, "fake_company", FakeCompanyFunction, &seeded);
+    register_fake(
+        tera,
+        "fake_company_suffix",
+        FakeCompanySuffixFunction,
+        &seeded,
+    );
+    register_fake(tera, "fake_industry", FakeIndustryFunction, &seeded);
+    register_fake(tera, "fake_profession", FakeProfessionFunction, &seeded);
 
     // Lorem
-    tera.register_function("fake_word", FakeWordFunction);
-    tera.register_function("fake_words", FakeWordsFunction);
-    tera.register_function("fake_sentence", FakeSentenceFunction);
-    tera.register_function("fake_paragraph", FakeParagraphFunction);
+    register_fake(tera, "fake_word", FakeWordFunction, &seeded);
+    register_fake(tera, "fake_words", FakeWordsFunction, &seeded);
+    register_fake(tera, "fake_sentence", FakeSentenceFunction, &seeded);
+    register_fake(tera, "fake_paragraph", FakeParagraphFunction, &seeded);
 
     // Numbers
-    tera.register_function("fake_int", FakeIntFunction);
-    tera.register_function("fake_int_range", FakeIntRangeFunction);
-    tera.register_function("fake_float", FakeFloatFunction);
-    tera.register_function("fake_bool", FakeBoolFunction);
+    register_fake(tera, "fake_int", FakeIntFunction, &seeded);
+    register_fake(tera, "fake_int_range", FakeIntRangeFunction, &seeded);
+    register_fake(tera, "fake_float", FakeFloatFunction, &seeded);
+    register_fake(tera, "fake_bool", FakeBoolFunction, &seeded);
 
     // Dates & Times
-    tera.register_function("fake_date", FakeDateFunction);
-    tera.register_function("fake_time", FakeTimeFunction);
-    tera.register_function("fake_datetime", FakeDateTimeFunction);
-    tera.register_function("fake_timestamp", FakeTimestampFunction);
+    register_fake(tera, "fake_date", FakeDateFunction, &seeded);
+    register_fake(tera, "fake_time", FakeTimeFunction, &seeded);
+    register_fake(tera, "fake_datetime", FakeDateTimeFunction, &seeded);
+    register_fake(tera, "fake_timestamp", FakeTimestampFunction, &seeded);
 
     // Finance
-    tera.register_function("fake_credit_card", FakeCreditCardFunction);
-    tera.register_function("fake_currency_code", FakeCurrencyCodeFunction);
-    tera.register_function("fake_currency_name", FakeCurrencyNameFunction);
-    tera.register_function("fake_currency_symbol", FakeCurrencySymbolFunction);
+    register_fake(tera, "fake_credit_card", FakeCreditCardFunction, &seeded);
+    register_fake(
+        tera,
+        "fake_currency_code",
+        FakeCurrencyCodeFunction,
+        &seeded,
+    );
+    register_fake(
+        tera,
+        "fake_currency_name",
+        FakeCurrencyNameFunction,
+        &seeded,
+    );
+    register_fake(
+        tera,
+        "fake_currency_symbol",
+        FakeCurrencySymbolFunction,
+        &seeded,
+    );
+    register_fake(tera, "fake_iban", FakeIbanFunction, &seeded);
+    register_fake(tera, "fake_bic", FakeBicFunction, &seeded);
 
     // File & Path
-    tera.register_function("fake_filename", FakeFilenameFunction);
-    tera.register_function("fake_extension", FakeExtensionFunction);
-    tera.register_function("fake_mime_type", FakeMimeTypeFunction);
-    tera.register_function("fake_file_path", FakeFilePathFunction);
+    register_fake(tera, "fake_filename", FakeFilenameFunction, &seeded);
+    register_fake(tera, "fake_extension", FakeExtensionFunction, &seeded);
+    register_fake(tera, "fake_mime_type", FakeMimeTypeFunction, &seeded);
+    register_fake(tera, "fake_file_path", FakeFilePathFunction, &seeded);
 
     // Color
-    tera.register_function("fake_color", FakeColorFunction);
-    tera.register_function("fake_hex_color", FakeHexColorFunction);
-    tera.register_function("fake_rgb_color", FakeRgbColorFunction);
+    register_fake(tera, "fake_color", FakeColorFunction, &seeded);
+    register_fake(tera, "fake_hex_color", FakeHexColorFunction, &seeded);
+    register_fake(tera, "fake_rgb_color", FakeRgbColorFunction, &seeded);
 
     // Misc
-    tera.register_function("fake_string", FakeStringFunction);
-    tera.register_function("fake_port", FakePortFunction);
-    tera.register_function("fake_semver", FakeSemverFunction);
+    register_fake(tera, "fake_string", FakeStringFunction, &seeded);
+    register_fake(tera, "fake_port", FakePortFunction, &seeded);
+    register_fake(tera, "fake_semver", FakeSemverFunction, &seeded);
 }
 
 /// env(name) - Get environment variable
 ///
 /// Usage: `{{ env(name="HOME") }}`
-struct EnvFunction;
+struct EnvFunction {
+    /// Fallback values loaded from a dotenv file
+    env_file: HashMap<String, String>,
+    /// Return an empty string instead of an error for unset variables
+    missing_as_empty: bool,
+}
+
+impl EnvFunction {
+    fn new(env_file: HashMap<String, String>, missing_as_empty: bool) -> Self {
+        Self {
+            env_file,
+            missing_as_empty,
+        }
+    }
+}
 
 impl Function for EnvFunction {
     fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
@@ -149,9 +294,22 @@ impl Function for EnvFunction {
             .and_then(|v| v.as_str())
             .ok_or_else(|| tera::Error::msg("env() requires 'name' parameter"))?;
 
-        std::env::var(name)
-            .map(Value::String)
-            .map_err(|_| tera::Error::msg(format!("Environment variable '{}' not found", name)))
+        // Process environment takes precedence over the env file
+        if let Ok(value) = std::env::var(name) {
+            return Ok(Value::String(value));
+        }
+        if let Some(value) = self.env_file.get(name) {
+            return Ok(Value::String(value.clone()));
+        }
+
+        if self.missing_as_empty {
+            Ok(Value::String(String::new()))
+        } else {
+            Err(tera::Error::msg(format!(
+                "Environment variable '{}' not found",
+                name
+            )))
+        }
     }
 }
 
@@ -192,11 +350,93 @@ impl Function for Sha256Function {
             .and_then(|v| v.as_str())
             .ok_or_else(|| tera::Error::msg("sha256() requires 's' parameter"))?;
 
-        let mut hasher = Sha256::new();
-        hasher.update(input.as_bytes());
-        let result = hasher.finalize();
+        Ok(Value::String(sha256_hex(input.as_bytes())))
+    }
+}
+
+/// Hex-encoded SHA-256 digest of `bytes`
+fn sha256_hex(bytes: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+}
+
+/// file_sha256(path) - SHA-256 hex digest of a file
+///
+/// Usage: `{{ file_sha256(path="fixtures/seed.json") }}`
+///
+/// `path` is resolved relative to the directory of the template being
+/// rendered (the current directory for string templates) and may not
+/// escape it.
+struct FileSha256Function {
+    root: PathBuf,
+}
+
+impl FileSha256Function {
+    fn new(root: PathBuf) -> Self {
+        // `Path::parent` of a bare file name is empty; treat it as the cwd
+        if root.as_os_str().is_empty() {
+            return Self {
+                root: PathBuf::from("."),
+            };
+        }
+        Self { root }
+    }
+
+    /// Resolve `path` under the template root, rejecting traversal outside it
+    fn resolve(&self, path: &str) -> tera::Result<PathBuf> {
+        let relative = Path::new(path);
+        let mut depth = 0usize;
+        for component in relative.components() {
+            match component {
+                Component::Normal(_) => depth += 1,
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => depth -= 1,
+                _ => {
+                    return Err(tera::Error::msg(format!(
+                        "file_sha256() path '{}' must stay within the template directory",
+                        path
+                    )))
+                }
+            }
+        }
 
-        Ok(Value::String(format!("{:x}", result)))
+        let root = self.root.canonicalize().map_err(|e| {
+            tera::Error::msg(format!(
+                "file_sha256() cannot resolve template directory '{}': {}",
+                self.root.display(),
+                e
+            ))
+        })?;
+        let resolved = root.join(relative).canonicalize().map_err(|e| {
+            tera::Error::msg(format!("file_sha256() cannot read '{}': {}", path, e))
+        })?;
+
+        // Catches symlinks that point outside the template directory
+        if !resolved.starts_with(&root) {
+            return Err(tera::Error::msg(format!(
+                "file_sha256() path '{}' must stay within the template directory",
+                path
+            )));
+        }
+
+        Ok(resolved)
+    }
+}
+
+impl Function for FileSha256Function {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("file_sha256() requires 'path' parameter"))?;
+
+        let resolved = self.resolve(path)?;
+        let bytes = std::fs::read(&resolved).map_err(|e| {
+            tera::Error::msg(format!("file_sha256() cannot read '{}': {}", path, e))
+        })?;
+
+        Ok(Value::String(sha256_hex(&bytes)))
     }
 }
 
@@ -245,6 +485,56 @@ impl Function for TomlEncodeFunction {
     }
 }
 
+/// toml_decode(s) - Parse a TOML document into a value
+///
+/// Usage: `{% set cfg = toml_decode(s=vars.raw) %}{{ cfg.name }}`
+///
+/// Datetimes decode to their RFC3339 string form.
+struct TomlDecodeFunction;
+
+impl Function for TomlDecodeFunction {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        let s = args
+            .get("s")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| tera::Error::msg("toml_decode() requires 's' string parameter"))?;
+
+        let table = toml::from_str::<toml::Table>(s).map_err(|e| {
+            let position = e.span().map_or_else(String::new, |span| {
+                let before = &s[..span.start];
+                let line = before.matches('\n').count() + 1;
+                let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+                format!(" at line {}, column {}", line, column)
+            });
+            tera::Error::msg(format!(
+                "toml_decode() failed to parse TOML{}: {}",
+                position,
+                e.message().trim()
+            ))
+        })?;
+
+        Ok(toml_to_value(toml::Value::Table(table)))
+    }
+}
+
+/// Convert a TOML value into the JSON value Tera works with
+fn toml_to_value(value: toml::Value) -> Value {
+    match value {
+        toml::Value::String(s) => Value::String(s),
+        toml::Value::Integer(i) => Value::from(i),
+        toml::Value::Float(f) => Value::from(f),
+        toml::Value::Boolean(b) => Value::Bool(b),
+        toml::Value::Datetime(dt) => Value::String(dt.to_string()),
+        toml::Value::Array(items) => Value::Array(items.into_iter().map(toml_to_value).collect()),
+        toml::Value::Table(table) => Value::Object(
+            table
+                .into_iter()
+                .map(|(k, v)| (k, toml_to_value(v)))
+                .collect(),
+        ),
+    }
+}
+
 // ========================================
 // Fake Data Generator Functions (50+)
 // ========================================
@@ -258,11 +548,22 @@ fn get_seed(args: &HashMap<String, Value>) -> u64 {
 
 // === UUIDs ===
 
-/// fake_uuid() - Generate random UUID v4
+/// fake_uuid(seed=42) - Generate UUID v4, random unless seeded
 struct FakeUuidFunction;
 impl Function for FakeUuidFunction {
-    fn call(&self, _args: &HashMap<String, Value>) -> tera::Result<Value> {
-        Ok(Value::String(uuid::Uuid::new_v4().to_string()))
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        use rand::Rng;
+        if !args.contains_key("seed") {
+            return Ok(Value::String(uuid::Uuid::new_v4().to_string()));
+        }
+
+        let mut rng = StdRng::seed_from_u64(get_seed(args));
+        let bytes: [u8; 16] = rng.gen();
+        Ok(Value::String(
+            uuid::Builder::from_random_bytes(bytes)
+                .into_uuid()
+                .to_string(),
+        ))
     }
 }
 
@@ -802,6 +1103,123 @@ impl Function for FakeCurrencySymbolFunction {
     }
 }
 
+/// BBAN layouts for `fake_iban`: country code and its BBAN pattern
+///
+/// Pattern characters: `n` = digit, `a` = uppercase letter. France is
+/// handled separately because its last two digits are a RIB check key.
+const IBAN_BBAN_FORMATS: &[(&str, &str)] = &[
+    ("DE", "nnnnnnnnnnnnnnnnnn"),
+    ("GB", "aaaannnnnnnnnnnnnn"),
+    ("FR", "nnnnnnnnnnnnnnnnnnnnnnn"),
+    ("NL", "aaaannnnnnnnnn"),
+    ("ES", "nnnnnnnnnnnnnnnnnnnn"),
+    ("IT", "annnnnnnnnnnnnnnnnnnnnn"),
+];
+
+/// Fill a BBAN pattern with random digits and letters
+fn random_bban(rng: &mut StdRng, pattern: &str) -> String {
+    use rand::Rng;
+    pattern
+        .chars()
+        .map(|c| match c {
+            'a' => rng.gen_range(b'A'..=b'Z') as char,
+            _ => rng.gen_range(b'0'..=b'9') as char,
+        })
+        .collect()
+}
+
+/// Compute the French RIB key for a 21-digit bank/branch/account prefix
+fn french_rib_key(prefix: &str) -> u64 {
+    let bank: u64 = prefix[0..5].parse().unwrap_or(0);
+    let branch: u64 = prefix[5..10].parse().unwrap_or(0);
+    let account: u64 = prefix[10..21].parse().unwrap_or(0);
+    97 - ((89 * bank + 15 * branch + 3 * account) % 97)
+}
+
+/// Compute the two ISO 13616 mod-97 check digits for a country and BBAN
+fn iban_check_digits(country: &str, bban: &str) -> u32 {
+    let rearranged = format!("{}{}00", bban, country);
+    let remainder = rearranged.chars().fold(0u32, |acc, c| {
+        // Letters map to 10..=35, so they contribute two decimal digits
+        match c.to_digit(36) {
+            Some(value) if value >= 10 => (acc * 100 + value) % 97,
+            Some(value) => (acc * 10 + value) % 97,
+            None => acc,
+        }
+    });
+    98 - remainder
+}
+
+/// fake_iban(country="DE") - Generate IBAN with a valid mod-97 checksum
+///
+/// Supported countries: DE, GB, FR, NL, ES, IT. The US does not use IBANs,
+/// so it is rejected like any other unsupported country.
+struct FakeIbanFunction;
+impl Function for FakeIbanFunction {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        let country = args
+            .get("country")
+            .and_then(|v| v.as_str())
+            .unwrap_or("DE")
+            .to_uppercase();
+
+        let (_, pattern) = IBAN_BBAN_FORMATS
+            .iter()
+            .find(|(code, _)| *code == country)
+            .ok_or_else(|| {
+                let supported: Vec<&str> = IBAN_BBAN_FORMATS.iter().map(|(c, _)| *c).collect();
+                tera::Error::msg(format!(
+                    "fake_iban() does not support country '{}' (supported: {})",
+                    country,
+                    supported.join(", ")
+                ))
+            })?;
+
+        let seed = get_seed(args);
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut bban = random_bban(&mut rng, pattern);
+        if country == "FR" {
+            bban.truncate(21);
+            bban.push_str(&format!("{:02}", french_rib_key(&bban)));
+        }
+
+        let check = iban_check_digits(&country, &bban);
+        Ok(Value::String(format!("{}{:02}{}", country, check, bban)))
+    }
+}
+
+/// fake_bic(country="DE") - Generate 8-character BIC/SWIFT code
+struct FakeBicFunction;
+impl Function for FakeBicFunction {
+    fn call(&self, args: &HashMap<String, Value>) -> tera::Result<Value> {
+        use rand::Rng;
+        let seed = get_seed(args);
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let country = match args.get("country").and_then(|v| v.as_str()) {
+            Some(country)
+                if country.len() == 2 && country.chars().all(|c| c.is_ascii_alphabetic()) =>
+            {
+                country.to_uppercase()
+            }
+            Some(country) => {
+                return Err(tera::Error::msg(format!(
+                    "fake_bic() requires a two-letter country code, got '{}'",
+                    country
+                )))
+            }
+            None => {
+                let index = rng.gen_range(0..IBAN_BBAN_FORMATS.len());
+                IBAN_BBAN_FORMATS[index].0.to_string()
+            }
+        };
+
+        let bank = random_bban(&mut rng, "aaaa");
+        let location = random_bban(&mut rng, "an");
+        Ok(Value::String(format!("{}{}{}", bank, country, location)))
+    }
+}
+
 // === File & Path ===
 
 /// fake_filename() - Generate filename
@@ -923,3 +1341,291 @@ impl Function for FakeSemverFunction {
         Ok(Value::String(format!("{}.{}.{}", major, minor, patch)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Validate an IBAN with the ISO 13616 mod-97 check
+    fn is_valid_iban(iban: &str) -> bool {
+        let (head, bban) = iban.split_at(4);
+        let rearranged = format!("{}{}", bban, head);
+        rearranged
+            .chars()
+            .fold(0u32, |acc, c| match c.to_digit(36) {
+                Some(v) if v >= 10 => (acc * 100 + v) % 97,
+                Some(v) => (acc * 10 + v) % 97,
+                None => acc,
+            })
+            == 1
+    }
+
+    fn call(function: &dyn Function, args: &[(&str, Value)]) -> tera::Result<Value> {
+        let args: HashMap<String, Value> = args
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        function.call(&args)
+    }
+
+    #[test]
+    fn test_fake_iban_has_valid_checksum_for_each_country() {
+        for (country, pattern) in IBAN_BBAN_FORMATS {
+            for seed in 0..20u64 {
+                let iban = call(
+                    &FakeIbanFunction,
+                    &[
+                        ("country", Value::from(*country)),
+                        ("seed", Value::from(seed)),
+                    ],
+                )
+                .unwrap();
+                let iban = iban.as_str().unwrap();
+
+                assert!(iban.starts_with(country));
+                assert_eq!(iban.len(), 4 + pattern.len());
+                assert!(is_valid_iban(iban), "invalid checksum: {}", iban);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fake_iban_known_value() {
+        // Reference IBAN from the ISO 13616 examples
+        assert_eq!(iban_check_digits("GB", "WEST12345698765432"), 82);
+        assert_eq!(iban_check_digits("DE", "370400440532013000"), 89);
+    }
+
+    #[test]
+    fn test_fake_iban_french_rib_key() {
+        let iban = call(
+            &FakeIbanFunction,
+            &[("country", Value::from("fr")), ("seed", Value::from(7))],
+        )
+        .unwrap();
+        let bban = &iban.as_str().unwrap()[4..];
+        let key: u64 = bban[21..].parse().unwrap();
+        assert_eq!(key, french_rib_key(&bban[..21]));
+    }
+
+    #[test]
+    fn test_fake_iban_and_bic_are_deterministic_with_seed() {
+        let args = [("seed", Value::from(42))];
+        assert_eq!(
+            call(&FakeIbanFunction, &args).unwrap(),
+            call(&FakeIbanFunction, &args).unwrap()
+        );
+        assert_eq!(
+            call(&FakeBicFunction, &args).unwrap(),
+            call(&FakeBicFunction, &args).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_fake_iban_rejects_unsupported_country() {
+        let err = call(&FakeIbanFunction, &[("country", Value::from("US"))]).unwrap_err();
+        assert!(err.to_string().contains("US"));
+    }
+
+    /// Create an empty scratch directory unique to `name`
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("clnrm-template-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_file_sha256_hashes_file_relative_to_template_dir() {
+        let dir = scratch_dir("file-sha256");
+        std::fs::create_dir_all(dir.join("fixtures")).unwrap();
+        std::fs::write(dir.join("fixtures/seed.json"), "hello").unwrap();
+
+        let function = FileSha256Function::new(dir.clone());
+        let digest = call(&function, &[("path", Value::from("fixtures/seed.json"))]).unwrap();
+        assert_eq!(
+            digest,
+            Value::from("2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824")
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_file_sha256_rejects_traversal_and_missing_files() {
+        let dir = scratch_dir("file-sha256-errors");
+        let function = FileSha256Function::new(dir.join("templates"));
+        std::fs::create_dir_all(dir.join("templates")).unwrap();
+        std::fs::write(dir.join("secret.txt"), "secret").unwrap();
+
+        for path in ["../secret.txt", "fixtures/../../secret.txt", "/etc/passwd"] {
+            let err = call(&function, &[("path", Value::from(path))]).unwrap_err();
+            assert!(err.to_string().contains("template directory"), "{}", path);
+        }
+
+        let err = call(&function, &[("path", Value::from("missing.json"))]).unwrap_err();
+        assert!(err.to_string().contains("cannot read 'missing.json'"));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_toml_decode_iterates_in_templates() {
+        let mut tera = Tera::default();
+        register_functions(&mut tera, None).unwrap();
+        let mut context = tera::Context::new();
+        context.insert(
+            "vars",
+            &serde_json::json!({ "raw": "name = \"api\"\nports = [80, 443]" }),
+        );
+
+        let rendered = tera
+            .render_str(
+                "{% set cfg = toml_decode(s=vars.raw) %}{{ cfg.name }}:{% for p in cfg.ports %}{{ p }};{% endfor %}",
+                &context,
+            )
+            .unwrap();
+
+        assert_eq!(rendered, "api:80;443;");
+    }
+
+    #[test]
+    fn test_toml_decode_reports_parse_position() {
+        let error = call(
+            &TomlDecodeFunction,
+            &[("s", Value::from("name = \"api\"\nports = [80,"))],
+        )
+        .unwrap_err();
+
+        assert!(
+            error.to_string().contains("at line 2, column"),
+            "unexpected error: {}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_json_decode_round_trips_json_encode() {
+        let mut tera = Tera::default();
+        register_functions(&mut tera, None).unwrap();
+        let mut context = tera::Context::new();
+        context.insert(
+            "vars",
+            &serde_json::json!({ "items": r#"[{"name": "a", "tags": ["x"]}, {"name": "b", "tags": []}]"# }),
+        );
+
+        let rendered = tera
+            .render_str(
+                "{% for item in json_decode(s=vars.items) %}{{ item.name }}={{ json_encode(value=item.tags) }};{% endfor %}",
+                &context,
+            )
+            .unwrap();
+
+        assert_eq!(rendered, r#"a=["x"];b=[];"#);
+
+        context.insert("broken", "[1,\n2,");
+        let error = tera
+            .render_str("{{ json_decode(s=broken) }}", &context)
+            .unwrap_err();
+        assert!(
+            format!("{:?}", error).contains("line 2 column"),
+            "unexpected error: {:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_fake_bic_format() {
+        let bic = call(
+            &FakeBicFunction,
+            &[("country", Value::from("gb")), ("seed", Value::from(1))],
+        )
+        .unwrap();
+        let bic = bic.as_str().unwrap();
+
+        assert_eq!(bic.len(), 8);
+        assert_eq!(&bic[4..6], "GB");
+        assert!(bic[..4].chars().all(|c| c.is_ascii_uppercase()));
+    }
+
+    /// Provider with a frozen clock and a seeded stream, like `DeterminismEngine`
+    struct SeededStream {
+        seed: u64,
+        rng: std::sync::Mutex<StdRng>,
+    }
+
+    impl SeededStream {
+        fn new(seed: u64) -> Self {
+            Self {
+                seed,
+                rng: std::sync::Mutex::new(StdRng::seed_from_u64(seed)),
+            }
+        }
+    }
+
+    impl TimestampProvider for SeededStream {
+        fn get_timestamp_rfc3339(&self) -> String {
+            "2025-01-01T00:00:00Z".to_string()
+        }
+
+        fn seed(&self) -> Option<u64> {
+            Some(self.seed)
+        }
+
+        fn next_seed(&self) -> Option<u64> {
+            use rand::RngCore;
+            Some(self.rng.lock().unwrap().next_u64())
+        }
+    }
+
+    fn render_seeded(seed: u64, template: &str) -> String {
+        let mut tera = Tera::default();
+        register_functions(&mut tera, None).unwrap();
+        register_determinism_functions(&mut tera, Arc::new(SeededStream::new(seed)));
+        tera.render_str(template, &tera::Context::new()).unwrap()
+    }
+
+    #[test]
+    fn test_determinism_seed_makes_fake_functions_reproducible() {
+        let template = "{{ fake_name() }}|{{ fake_uuid() }}|{{ now_rfc3339() }}";
+
+        let first = render_seeded(7, template);
+        assert_eq!(first, render_seeded(7, template));
+        assert_ne!(first, render_seeded(8, template));
+        assert!(first.ends_with("|2025-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_determinism_stream_advances_across_loop_iterations() {
+        let template = "{% for i in range(end=5) %}{{ fake_name() }};{% endfor %}";
+
+        let names = render_seeded(7, template);
+        assert_eq!(names, render_seeded(7, template));
+
+        let distinct: std::collections::HashSet<&str> =
+            names.split(';').filter(|n| !n.is_empty()).collect();
+        assert_eq!(distinct.len(), 5, "expected distinct names, got {}", names);
+    }
+
+    #[test]
+    fn test_determinism_freezes_time_helpers() {
+        let rendered = render_seeded(
+            7,
+            "{{ now_unix() }}|{{ now_ms() }}|{{ now_plus(seconds=60) }}|{{ date_rfc3339(offset_seconds=-60) }}",
+        );
+
+        assert_eq!(
+            rendered,
+            "1735689600|1735689600000|2025-01-01T00:01:00+00:00|2024-12-31T23:59:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_explicit_seed_argument_bypasses_determinism_stream() {
+        assert_eq!(
+            render_seeded(1, "{{ fake_email(seed=7) }}"),
+            render_seeded(2, "{{ fake_email(seed=7) }}")
+        );
+    }
+}
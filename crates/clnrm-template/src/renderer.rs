@@ -6,9 +6,10 @@
 use crate::error::{TemplateError, Result};
 use crate::context::TemplateContext;
 use crate::functions::{register_functions, TimestampProvider};
-use std::collections::HashMap;
-use std::path::Path;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use tera::ast::Node;
 use tera::{Tera, Function, Value};
 
 /// Template renderer with Tera engine
@@ -79,13 +80,140 @@ impl TemplateRenderer {
     /// Set template context variables
     pub fn with_context(mut self, context: TemplateContext) -> Self {
         self.context = context;
+        if !self.context.env_file.is_empty() {
+            crate::functions::register_env_function(&mut self.tera, &self.context.env_file);
+        }
+        self
+    }
+
+    /// Load a dotenv-style file so `env(name=...)` can resolve its keys
+    ///
+    /// Process environment variables take precedence over the file.
+    /// See [`TemplateContext::with_env_file`] for the supported syntax.
+    pub fn with_env_file<P: AsRef<Path>>(mut self, path: P) -> Result<Self> {
+        self.context = self.context.with_env_file(path)?;
+        crate::functions::register_env_function(&mut self.tera, &self.context.env_file);
+        Ok(self)
+    }
+
+    /// Load every `*.tera` file under `dir` for `{% include %}` / `{% extends %}`
+    ///
+    /// Templates are keyed by their path relative to `dir` with `/`
+    /// separators, so `dir/partials/db.toml.tera` is included as
+    /// `{% include "partials/db.toml.tera" %}`. Partials may include each other.
+    pub fn with_include_dir<P: AsRef<Path>>(mut self, dir: P) -> Result<Self> {
+        use walkdir::WalkDir;
+
+        let dir = dir.as_ref();
+        if !dir.is_dir() {
+            return Err(TemplateError::IoError(format!(
+                "Include directory not found: {}",
+                dir.display()
+            )));
+        }
+
+        let mut files = Vec::new();
+        for entry in WalkDir::new(dir).follow_links(true) {
+            let entry = entry.map_err(|e| {
+                TemplateError::IoError(format!("Failed to read directory entry: {}", e))
+            })?;
+
+            let path = entry.path();
+            let is_tera = path.extension().is_some_and(|ext| ext == "tera");
+            if !entry.file_type().is_file() || !is_tera {
+                continue;
+            }
+
+            let relative = path.strip_prefix(dir).map_err(|e| {
+                TemplateError::IoError(format!("Failed to resolve {:?}: {}", path, e))
+            })?;
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((path.to_path_buf(), Some(name)));
+        }
+
+        self.tera.add_template_files(files).map_err(|e| {
+            TemplateError::RenderError(format!(
+                "Failed to load templates from include directory '{}': {}",
+                dir.display(),
+                describe_tera_error(&e)
+            ))
+        })?;
+
+        Ok(self)
+    }
+
+    /// Load the partials `template` pulls in through `{% include %}`,
+    /// `{% import %}` and `{% extends %}`, resolving names relative to `dir`
+    ///
+    /// Partials are followed transitively. Names that are already loaded
+    /// (the macro library, or anything from [`with_include_dir`](Self::with_include_dir))
+    /// are reused rather than read again, and names with no file under `dir`
+    /// are left for Tera to report when rendering.
+    ///
+    /// Returns the file of every partial the template depends on.
+    pub fn load_template_dependencies(&mut self, template: &str, dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut pending = parse_template_references(template, "template");
+        let mut seen = HashSet::new();
+        let mut dependencies = Vec::new();
+        let mut new_files = Vec::new();
+
+        while let Some(name) = pending.pop() {
+            if !seen.insert(name.clone()) {
+                continue;
+            }
+
+            if let Ok(loaded) = self.tera.get_template(&name) {
+                if let Some(path) = &loaded.path {
+                    dependencies.push(PathBuf::from(path));
+                }
+                collect_template_references(&loaded.ast, &mut pending);
+                continue;
+            }
+
+            let path = dir.join(&name);
+            if !path.is_file() {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                TemplateError::IoError(format!("Failed to read partial {}: {}", path.display(), e))
+            })?;
+            pending.extend(parse_template_references(&content, &name));
+            dependencies.push(path.clone());
+            new_files.push((path, Some(name)));
+        }
+
+        if !new_files.is_empty() {
+            self.tera.add_template_files(new_files).map_err(|e| {
+                TemplateError::RenderError(format!(
+                    "Failed to load partials from '{}': {}",
+                    dir.display(),
+                    describe_tera_error(&e)
+                ))
+            })?;
+        }
+
+        Ok(dependencies)
+    }
+
+    /// Resolve `file_sha256(path=...)` relative to `dir`
+    ///
+    /// [`render_file`](Self::render_file) does this automatically; use it
+    /// when rendering file contents through [`render_str`](Self::render_str).
+    pub fn with_template_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+        crate::functions::register_file_functions(&mut self.tera, dir.as_ref());
         self
     }
 
     /// Set determinism engine for reproducible template rendering
     ///
-    /// When configured, this freezes `now_rfc3339()` function and provides
-    /// seeded random generation for fake data functions.
+    /// When configured, this freezes `now_rfc3339()` and the other time
+    /// functions (`now_unix()`, `now_ms()`, `now_plus()`, ...) and provides
+    /// seeded random generation for fake data functions: `fake_*` calls
+    /// without an explicit `seed=` draw from the engine's seed.
     ///
     /// # Arguments
     /// * `engine` - DeterminismEngine with optional seed and freeze_clock
@@ -108,6 +236,7 @@ impl TemplateRenderer {
     ///     .with_determinism(engine);
     /// ```
     pub fn with_determinism(mut self, determinism: std::sync::Arc<dyn TimestampProvider + Send + Sync>) -> Self {
+        crate::functions::register_determinism_functions(&mut self.tera, determinism.clone());
         self.determinism = Some(determinism);
         self
     }
@@ -135,9 +264,75 @@ impl TemplateRenderer {
             ))
         })?;
 
+        // Resolve file_sha256() paths and partials against the template's own directory
+        if let Some(dir) = path.parent() {
+            crate::functions::register_file_functions(&mut self.tera, dir);
+            self.load_template_dependencies(&template_str, dir)?;
+        }
+
         self.render_str(&template_str, path_str)
     }
 
+    /// Render every `*.clnrm.toml.tera` template under `dir` into `out_dir`
+    ///
+    /// Each template is written to the same relative path under `out_dir`
+    /// without its `.tera` extension, e.g. `api/login.clnrm.toml.tera` to
+    /// `api/login.clnrm.toml`. All templates share this renderer's context.
+    /// Templates render in path order, stopping at the first that fails.
+    ///
+    /// Returns the paths of the rendered files.
+    pub fn render_all(&mut self, dir: &Path, out_dir: &Path) -> Result<Vec<PathBuf>> {
+        let mut templates: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+            .follow_links(true)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.ends_with(".clnrm.toml.tera"))
+            })
+            .collect();
+        templates.sort();
+
+        if templates.is_empty() {
+            return Err(TemplateError::ValidationError(format!(
+                "No *.clnrm.toml.tera templates found in {}",
+                dir.display()
+            )));
+        }
+
+        let mut rendered_files = Vec::with_capacity(templates.len());
+        for template in templates {
+            let rendered = self.render_file(&template).map_err(|e| {
+                TemplateError::RenderError(format!(
+                    "Failed to render {}: {}",
+                    template.display(),
+                    e
+                ))
+            })?;
+
+            let relative = template.strip_prefix(dir).unwrap_or(&template);
+            let output = out_dir.join(relative).with_extension("");
+            if let Some(parent) = output.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    TemplateError::IoError(format!(
+                        "Failed to create {}: {}",
+                        parent.display(),
+                        e
+                    ))
+                })?;
+            }
+            std::fs::write(&output, rendered).map_err(|e| {
+                TemplateError::IoError(format!("Failed to write {}: {}", output.display(), e))
+            })?;
+            rendered_files.push(output);
+        }
+
+        Ok(rendered_files)
+    }
+
     /// Render template string to TOML
     pub fn render_str(&mut self, template: &str, name: &str) -> Result<String> {
         // Build Tera context
@@ -147,11 +342,35 @@ impl TemplateRenderer {
         self.tera.render_str(template, &tera_ctx).map_err(|e| {
             TemplateError::RenderError(format!(
                 "Template rendering failed in '{}': {}",
-                name, e
+                name,
+                describe_tera_error(&e)
             ))
         })
     }
 
+    /// Evaluate a Tera expression as a boolean condition
+    ///
+    /// The expression is wrapped in `{% if ... %}` and rendered against the
+    /// current context, so anything valid in an `if` tag works here, e.g.
+    /// `env(name='CI') == 'true'` or `vars.platform != 'linux'`. Unset
+    /// environment variables read as empty strings rather than erroring.
+    pub fn eval_condition(&mut self, expr: &str, name: &str) -> Result<bool> {
+        let tera_ctx = self.context.to_tera_context()?;
+
+        let mut tera = self.tera.clone();
+        crate::functions::register_condition_functions(&mut tera, &self.context.env_file);
+
+        let template = format!("{{% if {} %}}true{{% else %}}false{{% endif %}}", expr);
+        let rendered = tera.render_str(&template, &tera_ctx).map_err(|e| {
+            TemplateError::RenderError(format!(
+                "Condition evaluation failed in '{}': {}",
+                name, e
+            ))
+        })?;
+
+        Ok(rendered == "true")
+    }
+
     /// Render template to specific output format
     ///
     /// # Arguments
@@ -229,6 +448,64 @@ impl TemplateRenderer {
     }
 }
 
+/// Format a Tera error with its full cause chain
+///
+/// Tera reports most failures (a missing `{% include %}` target, an unknown
+/// variable) as the source of a generic "Failed to render" error, so only
+/// printing the top-level message hides the actual problem.
+pub(crate) fn describe_tera_error(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+/// Names of the templates `content` includes, imports or extends
+///
+/// Syntax errors yield no references; rendering reports them with context.
+fn parse_template_references(content: &str, name: &str) -> Vec<String> {
+    let mut references = Vec::new();
+    if let Ok(template) = tera::Template::new(name, None, content) {
+        collect_template_references(&template.ast, &mut references);
+    }
+    references
+}
+
+fn collect_template_references(nodes: &[Node], references: &mut Vec<String>) {
+    for node in nodes {
+        match node {
+            Node::Include(_, names, _) => references.extend(names.iter().cloned()),
+            Node::ImportMacro(_, file, _) | Node::Extends(_, file) => references.push(file.clone()),
+            Node::Block(_, block, _) => collect_template_references(&block.body, references),
+            Node::MacroDefinition(_, definition, _) => {
+                collect_template_references(&definition.body, references)
+            }
+            Node::FilterSection(_, section, _) => {
+                collect_template_references(&section.body, references)
+            }
+            Node::Forloop(_, forloop, _) => {
+                collect_template_references(&forloop.body, references);
+                if let Some(body) = &forloop.empty_body {
+                    collect_template_references(body, references);
+                }
+            }
+            Node::If(condition, _) => {
+                for (_, _, body) in &condition.conditions {
+                    collect_template_references(body, references);
+                }
+                if let Some((_, body)) = &condition.otherwise {
+                    collect_template_references(body, references);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Output format for template rendering
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OutputFormat {
@@ -303,4 +580,149 @@ pub fn is_template(content: &str) -> bool {
 pub fn get_cached_template_renderer() -> Result<TemplateRenderer> {
     static INSTANCE: OnceLock<Result<TemplateRenderer>> = OnceLock::new();
     INSTANCE.get_or_init(TemplateRenderer::new).clone()
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_include_dir_resolves_nested_includes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("partials/db")).unwrap();
+        std::fs::write(
+            dir.join("partials/service.toml.tera"),
+            "[services.db]\n{% include \"partials/db/image.toml.tera\" %}",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("partials/db/image.toml.tera"),
+            "image = \"{{ vars.image }}\"",
+        )
+        .unwrap();
+
+        let mut renderer = TemplateRenderer::new()
+            .unwrap()
+            .with_include_dir(dir)
+            .unwrap();
+        renderer.merge_user_vars(HashMap::from([(
+            "image".to_string(),
+            serde_json::json!("postgres:16"),
+        )]));
+
+        let rendered = renderer
+            .render_str("{% include \"partials/service.toml.tera\" %}", "test")
+            .unwrap();
+        assert_eq!(rendered, "[services.db]\nimage = \"postgres:16\"");
+
+    }
+
+    #[test]
+    fn test_with_include_dir_missing_include_names_template() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("partials/db")).unwrap();
+        std::fs::write(
+            dir.join("partials/service.toml.tera"),
+            "{% include \"partials/missing.toml.tera\" %}",
+        )
+        .unwrap();
+
+        let mut renderer = TemplateRenderer::new()
+            .unwrap()
+            .with_include_dir(dir)
+            .unwrap();
+        let err = renderer
+            .render_str("{% include \"partials/service.toml.tera\" %}", "test")
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("partials/missing.toml.tera"),
+            "{}",
+            err
+        );
+
+    }
+
+    #[test]
+    fn test_load_template_dependencies_follows_nested_partials() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("partials/db")).unwrap();
+        std::fs::write(
+            dir.join("partials/service.toml.tera"),
+            "{% if true %}{% include \"partials/db/image.toml.tera\" %}{% endif %}",
+        )
+        .unwrap();
+        std::fs::write(dir.join("partials/db/image.toml.tera"), "image = \"alpine\"").unwrap();
+
+        let mut renderer = TemplateRenderer::new().unwrap();
+        let template = "{% include \"partials/service.toml.tera\" %}";
+        let mut dependencies = renderer.load_template_dependencies(template, dir).unwrap();
+        dependencies.sort();
+        assert_eq!(
+            dependencies,
+            vec![
+                dir.join("partials/db/image.toml.tera"),
+                dir.join("partials/service.toml.tera"),
+            ]
+        );
+        assert_eq!(renderer.render_str(template, "test").unwrap(), "image = \"alpine\"");
+
+    }
+
+    #[test]
+    fn test_render_all_writes_sibling_configs_with_shared_context() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::create_dir_all(dir.join("templates/api")).unwrap();
+        std::fs::write(dir.join("templates/db.clnrm.toml.tera"), "name = \"{{ vars.env }}-db\"").unwrap();
+        std::fs::write(dir.join("templates/api/login.clnrm.toml.tera"), "name = \"{{ vars.env }}-login\"").unwrap();
+        std::fs::write(dir.join("templates/partial.toml.tera"), "{{ not_rendered }}").unwrap();
+
+        let mut renderer = TemplateRenderer::new().unwrap();
+        renderer.merge_user_vars(HashMap::from([("env".to_string(), serde_json::json!("ci"))]));
+        let out = dir.join("generated");
+        let rendered = renderer.render_all(&dir.join("templates"), &out).unwrap();
+
+        assert_eq!(rendered, vec![out.join("api/login.clnrm.toml"), out.join("db.clnrm.toml")]);
+        assert_eq!(std::fs::read_to_string(&rendered[0]).unwrap(), "name = \"ci-login\"");
+        assert_eq!(std::fs::read_to_string(&rendered[1]).unwrap(), "name = \"ci-db\"");
+        assert!(!out.join("partial.toml").exists());
+
+    }
+
+    #[test]
+    fn test_render_all_stops_at_first_failing_template() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        std::fs::write(dir.join("a.clnrm.toml.tera"), "name = \"a\"").unwrap();
+        std::fs::write(dir.join("b.clnrm.toml.tera"), "name = \"{{ vars.missing }}\"").unwrap();
+        std::fs::write(dir.join("c.clnrm.toml.tera"), "name = \"c\"").unwrap();
+
+        let out = dir.join("generated");
+        let err = TemplateRenderer::new().unwrap().render_all(dir, &out).unwrap_err();
+
+        assert!(err.to_string().contains("b.clnrm.toml.tera"), "{}", err);
+        assert!(out.join("a.clnrm.toml").exists());
+        assert!(!out.join("c.clnrm.toml").exists());
+
+    }
+
+    #[test]
+    fn test_render_all_rejects_directory_without_templates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let dir = tmp.path();
+        let err = TemplateRenderer::new().unwrap().render_all(dir, dir).unwrap_err();
+        assert!(matches!(err, TemplateError::ValidationError(_)), "{}", err);
+
+    }
+
+    #[test]
+    fn test_with_include_dir_rejects_missing_directory() {
+        let result = TemplateRenderer::new()
+            .unwrap()
+            .with_include_dir("/nonexistent/clnrm/partials");
+        assert!(matches!(result, Err(TemplateError::IoError(_))));
+    }
+}
@@ -162,16 +162,20 @@ pub enum OutputFormat {
 }
 
 /// Convert TOML to JSON format
-fn convert_to_json(toml_content: &str) -> Result<String> {
+///
+/// Works on full test configs, including nested `[[scenario]]` arrays and
+/// `[expect]` tables. Parse errors carry the line and column of the
+/// offending rendered TOML.
+pub(crate) fn convert_to_json(toml_content: &str) -> Result<String> {
     let parsed: Value = toml::from_str(toml_content)
-        .map_err(|e| TemplateError::ValidationError(format!("Failed to parse TOML for JSON conversion: {}", e)))?;
+        .map_err(|e| TemplateError::ValidationError(format!("Rendered output is not valid TOML: {}", e)))?;
 
     serde_json::to_string_pretty(&parsed)
         .map_err(|e| TemplateError::ValidationError(format!("Failed to serialize to JSON: {}", e)))
 }
 
 /// Convert TOML to YAML format
-fn convert_to_yaml(toml_content: &str) -> Result<String> {
+pub(crate) fn convert_to_yaml(toml_content: &str) -> Result<String> {
     let parsed: Value = toml::from_str(toml_content)
         .map_err(|e| TemplateError::ValidationError(format!("Failed to parse TOML for YAML conversion: {}", e)))?;
 
@@ -180,7 +184,7 @@ fn convert_to_yaml(toml_content: &str) -> Result<String> {
 }
 
 /// Strip template syntax to get plain text
-fn strip_template_syntax(content: &str) -> Result<String> {
+pub(crate) fn strip_template_syntax(content: &str) -> Result<String> {
     // Simple implementation - remove {{ }} and {% %} blocks
     let mut result = String::new();
     let mut in_braces = false;
@@ -404,6 +404,9 @@ pub enum MergeStrategy {
     MergeArrays,
     /// Preserve existing values
     Preserve,
+    /// Merge tables key by key and arrays of named tables by `name`,
+    /// overwriting anything else
+    DeepMerge,
     /// Custom merge function
     Custom,
 }
@@ -414,6 +417,7 @@ impl std::fmt::Debug for MergeStrategy {
             MergeStrategy::Overwrite => write!(f, "Overwrite"),
             MergeStrategy::MergeArrays => write!(f, "MergeArrays"),
             MergeStrategy::Preserve => write!(f, "Preserve"),
+            MergeStrategy::DeepMerge => write!(f, "DeepMerge"),
             MergeStrategy::Custom => write!(f, "Custom"),
         }
     }
@@ -425,6 +429,7 @@ impl Clone for MergeStrategy {
             MergeStrategy::Overwrite => MergeStrategy::Overwrite,
             MergeStrategy::MergeArrays => MergeStrategy::MergeArrays,
             MergeStrategy::Preserve => MergeStrategy::Preserve,
+            MergeStrategy::DeepMerge => MergeStrategy::DeepMerge,
             MergeStrategy::Custom => MergeStrategy::Custom,
         }
     }
@@ -466,6 +471,12 @@ impl TomlMerger {
 
     /// Merge two TOML values
     ///
+    /// With [`MergeStrategy::DeepMerge`], tables present in both values are
+    /// merged key by key, and arrays of tables whose entries all have a string
+    /// `name` (e.g. `[[scenario]]`) are merged entry by entry: entries with
+    /// the same name are merged, new names are appended. Other conflicts are
+    /// won by `overlay`.
+    ///
     /// # Arguments
     /// * `base` - Base TOML value
     /// * `overlay` - TOML value to merge on top
@@ -506,10 +517,41 @@ impl TomlMerger {
                     Ok(overlay.clone())
                 }
             }
+            MergeStrategy::DeepMerge => match (base, overlay) {
+                (Value::Object(_), Value::Object(_)) => self.merge(base, overlay),
+                (Value::Array(base_arr), Value::Array(overlay_arr)) => Ok(self
+                    .merge_named_tables(base_arr, overlay_arr)?
+                    .unwrap_or_else(|| overlay.clone())),
+                _ => Ok(overlay.clone()),
+            },
             MergeStrategy::Custom => Ok(overlay.clone()), // Simplified for now
         }
     }
 
+    /// Merge arrays of tables by their `name` key
+    ///
+    /// Returns `None` unless every entry of both arrays is a table with a
+    /// string `name`.
+    fn merge_named_tables(&self, base: &[Value], overlay: &[Value]) -> Result<Option<Value>> {
+        fn name(entry: &Value) -> Option<&str> {
+            entry.get("name")?.as_str()
+        }
+
+        if !base.iter().chain(overlay).all(|entry| name(entry).is_some()) {
+            return Ok(None);
+        }
+
+        let mut merged = base.to_vec();
+        for entry in overlay {
+            match merged.iter().position(|existing| name(existing) == name(entry)) {
+                Some(index) => merged[index] = self.merge(&merged[index], entry)?,
+                None => merged.push(entry.clone()),
+            }
+        }
+
+        Ok(Some(Value::Array(merged)))
+    }
+
     /// Merge multiple TOML files
     ///
     /// # Arguments
@@ -914,6 +956,71 @@ debug = true
         assert!(merged.get("config").is_some());
     }
 
+    #[test]
+    fn test_toml_merging_arrays_of_tables_by_name() {
+        let base_content = r#"
+tags = ["a", "b"]
+
+[[scenario]]
+name = "smoke"
+timeout_ms = 1000
+
+[[scenario]]
+name = "load"
+        "#;
+
+        let overlay_content = r#"
+tags = ["c"]
+
+[[scenario]]
+name = "smoke"
+timeout_ms = 5000
+
+[[scenario]]
+name = "soak"
+        "#;
+
+        let base_parsed = toml::from_str::<Value>(base_content).unwrap();
+        let overlay_parsed = toml::from_str::<Value>(overlay_content).unwrap();
+
+        let merged = TomlMerger::new()
+            .with_strategy(MergeStrategy::DeepMerge)
+            .merge(&base_parsed, &overlay_parsed)
+            .unwrap();
+
+        let scenarios = merged["scenario"].as_array().unwrap();
+        let names: Vec<&str> = scenarios.iter().map(|s| s["name"].as_str().unwrap()).collect();
+        assert_eq!(names, ["smoke", "load", "soak"]);
+        assert_eq!(scenarios[0]["timeout_ms"], 5000);
+        // Arrays of plain values are replaced
+        assert_eq!(merged["tags"], serde_json::json!(["c"]));
+    }
+
+    #[test]
+    fn test_toml_merging_other_strategies_do_not_deep_merge() {
+        let base = serde_json::json!({
+            "service": { "name": "base", "image": "alpine" },
+            "scenario": [{ "name": "smoke", "timeout_ms": 1000 }],
+        });
+        let overlay = serde_json::json!({
+            "service": { "image": "debian" },
+            "scenario": [{ "name": "smoke", "timeout_ms": 5000 }],
+        });
+
+        let preserved = TomlMerger::new()
+            .with_strategy(MergeStrategy::Preserve)
+            .merge(&base, &overlay)
+            .unwrap();
+        assert_eq!(preserved, base);
+
+        let appended = TomlMerger::new()
+            .with_strategy(MergeStrategy::MergeArrays)
+            .merge(&base, &overlay)
+            .unwrap();
+        assert_eq!(appended["service"], overlay["service"]);
+        assert_eq!(appended["scenario"].as_array().unwrap().len(), 2);
+    }
+
     #[test]
     fn test_variable_extraction() {
         let content = r#"
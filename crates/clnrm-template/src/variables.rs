@@ -0,0 +1,279 @@
+//! Static analysis of variable references in Tera templates
+//!
+//! Walks the parsed Tera AST to find which context variables a template
+//! reads, without rendering it. Used by `clnrm lint` to report `[vars]`
+//! entries that are never used and references to variables that are never
+//! defined.
+
+use crate::error::{Result, TemplateError};
+use std::collections::{BTreeSet, HashSet};
+use tera::ast::{Expr, ExprVal, LogicOperator, Node};
+
+/// Context roots that are always present and are not user variables
+const BUILTIN_ROOTS: &[&str] = &["vars", "matrix", "otel", "loop", "__tera_context"];
+
+/// Variables referenced by a template
+///
+/// Both `{{ svc }}` and `{{ vars.svc }}` count as a reference to `svc`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VariableReferences {
+    /// Every variable the template reads
+    pub used: BTreeSet<String>,
+    /// Variables read without a fallback; rendering fails if these are unset
+    ///
+    /// References guarded by `| default(...)`, `is defined` tests or a bare
+    /// `{% if name %}` are excluded.
+    pub required: BTreeSet<String>,
+    /// The template reads `vars` as a whole (e.g. `{% for k, v in vars %}`
+    /// or `vars[key]`), so any variable may be used
+    pub uses_all_vars: bool,
+    /// The template includes, extends or imports other templates whose
+    /// references are not visible here
+    pub has_external_templates: bool,
+}
+
+impl VariableReferences {
+    /// Whether `name` may be read by the template
+    pub fn may_use(&self, name: &str) -> bool {
+        self.uses_all_vars || self.has_external_templates || self.used.contains(name)
+    }
+}
+
+/// Collect the variables referenced by `template`
+///
+/// Loop variables, `{% set %}` targets and macro bodies (which cannot see
+/// the render context) are not reported.
+pub fn scan_variables(template: &str) -> Result<VariableReferences> {
+    let parsed = tera::Template::new("__clnrm_scan", None, template).map_err(|e| {
+        TemplateError::ValidationError(format!(
+            "Template syntax error: {}",
+            crate::renderer::describe_tera_error(&e)
+        ))
+    })?;
+
+    let mut refs = VariableReferences::default();
+    let mut locals = HashSet::new();
+    scan_nodes(&parsed.ast, &mut locals, &mut refs);
+    Ok(refs)
+}
+
+fn scan_nodes(nodes: &[Node], locals: &mut HashSet<String>, refs: &mut VariableReferences) {
+    for node in nodes {
+        match node {
+            Node::VariableBlock(_, expr) => scan_expr(expr, locals, refs, false),
+            Node::Set(_, set) => {
+                scan_expr(&set.value, locals, refs, false);
+                locals.insert(set.key.clone());
+            }
+            Node::FilterSection(_, section, _) => {
+                for arg in section.filter.args.values() {
+                    scan_expr(arg, locals, refs, false);
+                }
+                scan_nodes(&section.body, locals, refs);
+            }
+            Node::Block(_, block, _) => scan_nodes(&block.body, locals, refs),
+            Node::Forloop(_, forloop, _) => {
+                scan_expr(&forloop.container, locals, refs, false);
+
+                let mut loop_locals = locals.clone();
+                loop_locals.insert(forloop.value.clone());
+                if let Some(key) = &forloop.key {
+                    loop_locals.insert(key.clone());
+                }
+                scan_nodes(&forloop.body, &mut loop_locals, refs);
+                if let Some(empty_body) = &forloop.empty_body {
+                    scan_nodes(empty_body, locals, refs);
+                }
+            }
+            Node::If(if_node, _) => {
+                for (_, condition, body) in &if_node.conditions {
+                    scan_expr(condition, locals, refs, true);
+                    scan_nodes(body, locals, refs);
+                }
+                if let Some((_, body)) = &if_node.otherwise {
+                    scan_nodes(body, locals, refs);
+                }
+            }
+            Node::Extends(..) | Node::Include(..) | Node::ImportMacro(..) => {
+                refs.has_external_templates = true;
+            }
+            // Macro bodies only see their own arguments
+            Node::MacroDefinition(..)
+            | Node::Super
+            | Node::Text(_)
+            | Node::Raw(..)
+            | Node::Break(_)
+            | Node::Continue(_)
+            | Node::Comment(..) => {}
+        }
+    }
+}
+
+/// Record references in `expr`
+///
+/// `optional` is set for bare identifiers in `if` conditions, which Tera
+/// treats as falsy rather than an error when undefined.
+fn scan_expr(expr: &Expr, locals: &HashSet<String>, refs: &mut VariableReferences, optional: bool) {
+    for filter in &expr.filters {
+        for arg in filter.args.values() {
+            scan_expr(arg, locals, refs, false);
+        }
+    }
+
+    let optional = optional || expr.has_default_filter();
+    scan_expr_val(&expr.val, locals, refs, optional);
+}
+
+fn scan_expr_val(
+    val: &ExprVal,
+    locals: &HashSet<String>,
+    refs: &mut VariableReferences,
+    optional: bool,
+) {
+    match val {
+        ExprVal::Ident(ident) => record_ident(ident, locals, refs, optional),
+        ExprVal::Math(math) => {
+            scan_expr(&math.lhs, locals, refs, false);
+            scan_expr(&math.rhs, locals, refs, false);
+        }
+        ExprVal::Logic(logic) => {
+            // `a and b` / `a or b` evaluate each side as a condition
+            let short_circuit =
+                optional && matches!(logic.operator, LogicOperator::And | LogicOperator::Or);
+            scan_expr(&logic.lhs, locals, refs, short_circuit);
+            scan_expr(&logic.rhs, locals, refs, short_circuit);
+        }
+        ExprVal::Test(test) => {
+            // `x is defined` must not require `x`
+            record_ident(&test.ident, locals, refs, true);
+            for arg in &test.args {
+                scan_expr(arg, locals, refs, false);
+            }
+        }
+        ExprVal::MacroCall(call) => {
+            for arg in call.args.values() {
+                scan_expr(arg, locals, refs, false);
+            }
+        }
+        ExprVal::FunctionCall(call) => {
+            for arg in call.args.values() {
+                scan_expr(arg, locals, refs, false);
+            }
+        }
+        ExprVal::Array(items) => {
+            for item in items {
+                scan_expr(item, locals, refs, false);
+            }
+        }
+        ExprVal::StringConcat(concat) => {
+            for value in &concat.values {
+                scan_expr_val(value, locals, refs, false);
+            }
+        }
+        ExprVal::In(in_expr) => {
+            scan_expr(&in_expr.lhs, locals, refs, false);
+            scan_expr(&in_expr.rhs, locals, refs, false);
+        }
+        ExprVal::String(_) | ExprVal::Int(_) | ExprVal::Float(_) | ExprVal::Bool(_) => {}
+    }
+}
+
+/// Record a dotted identifier such as `svc`, `vars.svc` or `vars[key].x`
+fn record_ident(
+    ident: &str,
+    locals: &HashSet<String>,
+    refs: &mut VariableReferences,
+    optional: bool,
+) {
+    let (root, rest) = split_ident(ident);
+    if locals.contains(root) {
+        return;
+    }
+
+    let name = if root == "vars" {
+        match rest {
+            Some(rest) if !rest.starts_with('[') => split_ident(rest.trim_start_matches('.')).0,
+            // `vars` itself or `vars[expr]`
+            _ => {
+                refs.uses_all_vars = true;
+                return;
+            }
+        }
+    } else if BUILTIN_ROOTS.contains(&root) {
+        return;
+    } else {
+        root
+    };
+
+    refs.used.insert(name.to_string());
+    if !optional {
+        refs.required.insert(name.to_string());
+    }
+}
+
+/// Split `a.b[c]` into `("a", Some(".b[c]"))`
+fn split_ident(ident: &str) -> (&str, Option<&str>) {
+    match ident.find(['.', '[']) {
+        Some(idx) => (&ident[..idx], Some(&ident[idx..])),
+        None => (ident, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_scan_variables_collects_top_level_and_vars_namespace() {
+        let refs = scan_variables(
+            r#"name = "{{ svc }}"
+image = "{{ vars.image | upper }}"
+port = {{ matrix.port }}
+endpoint = "{{ otel.endpoint }}""#,
+        )
+        .unwrap();
+
+        assert_eq!(refs.used, set(&["image", "svc"]));
+        assert_eq!(refs.required, set(&["image", "svc"]));
+        assert!(!refs.uses_all_vars);
+    }
+
+    #[test]
+    fn test_scan_variables_ignores_locals_and_guarded_references() {
+        let refs = scan_variables(
+            r#"{% set region = "eu" %}{{ region }}
+{% for svc in services %}{{ svc.name }}{{ loop.index }}{% endfor %}
+{{ token | default(value="") }}
+{% if debug %}{{ level }}{% endif %}
+{% if tag is defined %}{{ tag }}{% endif %}
+{{ env(name="HOME") }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            refs.used,
+            set(&["debug", "level", "services", "tag", "token"])
+        );
+        assert_eq!(refs.required, set(&["level", "services", "tag"]));
+    }
+
+    #[test]
+    fn test_scan_variables_flags_whole_vars_and_includes() {
+        let refs = scan_variables("{% for k, v in vars %}{{ k }}{% endfor %}").unwrap();
+        assert!(refs.uses_all_vars);
+        assert!(refs.may_use("anything"));
+
+        let refs = scan_variables(r#"{% include "partials/db.toml.tera" %}"#).unwrap();
+        assert!(refs.has_external_templates);
+    }
+
+    #[test]
+    fn test_scan_variables_reports_syntax_errors() {
+        let err = scan_variables("{% if %}").unwrap_err();
+        assert!(matches!(err, TemplateError::ValidationError(_)));
+    }
+}
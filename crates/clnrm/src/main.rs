@@ -4,13 +4,23 @@
 //! It uses clap for professional command-line argument parsing and provides
 //! comprehensive functionality for running tests, managing services, and generating reports.
 
-use clnrm_core::{cli::run_cli, error::Result};
+use clnrm_core::backend::runtime::export_api_host;
+use clnrm_core::cli::run_cli;
+use clnrm_core::error::{CleanroomError, Result};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
     // Initialize logging
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
+    // testcontainers reads the container API host from DOCKER_HOST, which is
+    // only safe to set before the runtime starts its threads
+    export_api_host();
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| {
+        CleanroomError::internal_error("Failed to start the async runtime")
+            .with_source(e.to_string())
+    })?;
+
     // Run CLI
-    run_cli().await
+    runtime.block_on(run_cli())
 }
@@ -0,0 +1,61 @@
+//! CLI Integration Tests - `clnrm diff` duration thresholds
+//!
+//! Verifies that duration jitter below `--duration-threshold-ms` is not a
+//! change, while a larger shift fails the command.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Test helper to get the clnrm binary command
+fn clnrm_cmd() -> Command {
+    Command::cargo_bin("clnrm").expect("Failed to find clnrm binary")
+}
+
+/// Write a one-span trace whose `db.query` span lasts `duration_ms`
+fn write_trace(dir: &Path, file: &str, duration_ms: u64) -> PathBuf {
+    let path = dir.join(file);
+    let start_ns = 1_000_000_000u64;
+    let trace = format!(
+        r#"[{{"name": "db.query", "trace_id": "trace-1", "span_id": "span-1", "kind": "client", "start_time_unix_nano": {}, "end_time_unix_nano": {}}}]"#,
+        start_ns,
+        start_ns + duration_ms * 1_000_000
+    );
+    fs::write(&path, trace).expect("Failed to write trace");
+    path
+}
+
+/// `clnrm diff` of a 100ms baseline against `current_ms`, ignoring changes
+/// under 50ms
+fn diff_with_threshold(current_ms: u64) -> assert_cmd::assert::Assert {
+    let dir = TempDir::new().expect("Failed to create temp directory");
+    let baseline = write_trace(dir.path(), "baseline.json", 100);
+    let current = write_trace(dir.path(), "current.json", current_ms);
+
+    clnrm_cmd()
+        .arg("diff")
+        .arg(&baseline)
+        .arg(&current)
+        .arg("--duration-threshold-ms")
+        .arg("50")
+        .assert()
+}
+
+#[test]
+fn test_diff_under_duration_threshold_succeeds() {
+    // Act & Assert
+    diff_with_threshold(120)
+        .success()
+        .stdout(predicate::str::contains("0 added, 0 removed, 0 modified"));
+}
+
+#[test]
+fn test_diff_over_duration_threshold_fails() {
+    // Act & Assert
+    diff_with_threshold(200).failure().stdout(
+        predicate::str::contains("0 added, 0 removed, 1 modified")
+            .and(predicate::str::contains("duration 100.000ms -> 200.000ms")),
+    );
+}
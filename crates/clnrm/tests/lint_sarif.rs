@@ -0,0 +1,71 @@
+//! CLI Integration Tests - `clnrm lint --format sarif`
+//!
+//! Verifies that SARIF output lists the lint rules and ties every result to
+//! one of them by `ruleId`.
+
+use assert_cmd::Command;
+use serde_json::Value;
+use std::fs;
+use tempfile::TempDir;
+
+/// Test helper to get the clnrm binary command
+fn clnrm_cmd() -> Command {
+    Command::cargo_bin("clnrm").expect("Failed to find clnrm binary")
+}
+
+/// Lint `content` as a test file and parse the SARIF log printed
+fn lint_sarif(content: &str) -> Value {
+    let dir = TempDir::new().expect("Failed to create temp directory");
+    let file = dir.path().join("lint.clnrm.toml");
+    fs::write(&file, content).expect("Failed to write test file");
+
+    let output = clnrm_cmd()
+        .arg("lint")
+        .arg("--format")
+        .arg("sarif")
+        .arg(&file)
+        .output()
+        .expect("Failed to run clnrm lint");
+    serde_json::from_slice(&output.stdout).expect("SARIF output is JSON")
+}
+
+#[test]
+fn test_sarif_output_lists_rules_and_result_rule_ids() {
+    // Arrange: no description, and a misspelt step key
+    let content = r#"
+[meta]
+name = "lint"
+version = "1.0"
+
+[[steps]]
+name = "noop"
+command = ["true"]
+comand = ["true"]
+"#;
+
+    // Act
+    let sarif = lint_sarif(content);
+
+    // Assert
+    let run = &sarif["runs"][0];
+    let rules: Vec<&str> = run["tool"]["driver"]["rules"]
+        .as_array()
+        .expect("rules")
+        .iter()
+        .filter_map(|rule| rule["id"].as_str())
+        .collect();
+    assert!(rules.contains(&"missing-description"), "{:?}", rules);
+    assert!(rules.contains(&"unknown-key"), "{:?}", rules);
+
+    let results = run["results"].as_array().expect("results");
+    let mut rule_ids: Vec<&str> = results
+        .iter()
+        .map(|result| result["ruleId"].as_str().expect("ruleId"))
+        .collect();
+    rule_ids.sort_unstable();
+    assert_eq!(rule_ids, ["missing-description", "unknown-key"]);
+    for result in results {
+        let index = result["ruleIndex"].as_u64().expect("ruleIndex") as usize;
+        assert_eq!(rules[index], result["ruleId"], "{}", result);
+    }
+}
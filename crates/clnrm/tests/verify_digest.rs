@@ -0,0 +1,86 @@
+//! CLI Integration Tests - `clnrm verify-digest`
+//!
+//! Verifies that a trace differing from its recorded baseline fails the
+//! command and reports the normalized fields that differ.
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Test helper to get the clnrm binary command
+fn clnrm_cmd() -> Command {
+    Command::cargo_bin("clnrm").expect("Failed to find clnrm binary")
+}
+
+/// Write a one-span trace whose `http.method` attribute is `method`
+fn write_trace(dir: &Path, file: &str, method: &str) -> PathBuf {
+    let path = dir.join(file);
+    let trace = format!(
+        r#"[{{"name": "http.request", "trace_id": "trace-1", "span_id": "span-1", "kind": "server", "attributes": {{"http.method": "{}"}}}}]"#,
+        method
+    );
+    fs::write(&path, trace).expect("Failed to write trace");
+    path
+}
+
+/// Record a digest baseline from a `GET` trace
+fn record_baseline(dir: &Path) -> PathBuf {
+    let baseline = dir.join("baseline.digest");
+    let trace = write_trace(dir, "baseline.json", "GET");
+    clnrm_cmd()
+        .arg("verify-digest")
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg("--trace")
+        .arg(&trace)
+        .arg("--update")
+        .assert()
+        .success();
+    baseline
+}
+
+#[test]
+fn test_verify_digest_matching_trace_succeeds() {
+    // Arrange
+    let dir = TempDir::new().expect("Failed to create temp directory");
+    let baseline = record_baseline(dir.path());
+    let trace = write_trace(dir.path(), "same.json", "GET");
+
+    // Act & Assert
+    clnrm_cmd()
+        .arg("verify-digest")
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg("--trace")
+        .arg(&trace)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Digest matches"));
+}
+
+#[test]
+fn test_verify_digest_mismatch_fails_with_field_diff() {
+    // Arrange
+    let dir = TempDir::new().expect("Failed to create temp directory");
+    let baseline = record_baseline(dir.path());
+    let trace = write_trace(dir.path(), "changed.json", "POST");
+
+    // Act & Assert
+    clnrm_cmd()
+        .arg("verify-digest")
+        .arg("--baseline")
+        .arg(&baseline)
+        .arg("--trace")
+        .arg(&trace)
+        .assert()
+        .failure()
+        .stdout(
+            predicate::str::contains("Digest mismatch")
+                .and(predicate::str::contains("Normalized fields differing (1)"))
+                .and(predicate::str::contains(
+                    r#"~ http.request[0].attr.http.method: "GET" -> "POST""#,
+                )),
+        );
+}